@@ -31,10 +31,8 @@ async fn create_new_association_pair(
     // Setup client
     tokio::spawn(async move {
         let client = Association::client(sctp::association::Config {
-            net_conn: ca,
-            max_receive_buffer_size: 0,
-            max_message_size: 0,
             name: "client".to_owned(),
+            ..sctp::association::Config::new(ca)
         })
         .await;
 
@@ -47,10 +45,8 @@ async fn create_new_association_pair(
     // Setup server
     tokio::spawn(async move {
         let server = Association::server(sctp::association::Config {
-            net_conn: cb,
-            max_receive_buffer_size: 0,
-            max_message_size: 0,
             name: "server".to_owned(),
+            ..sctp::association::Config::new(cb)
         })
         .await;
 
@@ -412,8 +408,141 @@ async fn test_data_channel_channel_type_partial_reliable_timed_unordered() -> Re
     pr_ordered_unordered_test(ChannelType::PartialReliableTimedUnordered, false).await
 }
 
+#[tokio::test]
+async fn test_data_channel_empty_messages() -> Result<()> {
+    let mut rbuf = vec![0u8; 1500];
+
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, a1) = create_new_association_pair(&br, Arc::new(ca), Arc::new(cb)).await?;
+
+    let cfg = Config {
+        channel_type: ChannelType::Reliable,
+        label: "data".to_string(),
+        ..Default::default()
+    };
+
+    let dc0 = DataChannel::dial(&a0, 100, cfg.clone()).await?;
+    bridge_process_at_least_one(&br).await;
+
+    let existing_data_channels: Vec<DataChannel> = Vec::new();
+    let dc1 = DataChannel::accept(&a1, Config::default(), &existing_data_channels).await?;
+    bridge_process_at_least_one(&br).await;
+
+    let n = dc0.write_data_channel(&Bytes::new(), true).await?;
+    assert_eq!(n, 0, "data length should match");
+    let n = dc0.write_data_channel(&Bytes::new(), false).await?;
+    assert_eq!(n, 0, "data length should match");
+    bridge_process_at_least_one(&br).await;
+
+    // Empty messages are delivered, and not mistaken for the end of the stream
+    let msg = dc1.read_message(&mut rbuf[..]).await?;
+    assert_eq!(msg, Some((0, true)), "should read an empty string");
+    let msg = dc1.read_message(&mut rbuf[..]).await?;
+    assert_eq!(msg, Some((0, false)), "should read an empty binary message");
+
+    dc0.close().await?;
+    bridge_process_at_least_one(&br).await;
+
+    let msg = dc1.read_message(&mut rbuf[..]).await?;
+    assert_eq!(msg, None, "stream should be reset by the remote");
+
+    dc1.close().await?;
+    bridge_process_at_least_one(&br).await;
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_data_channel_negotiated_reliability_params() -> Result<()> {
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, a1) = create_new_association_pair(&br, Arc::new(ca), Arc::new(cb)).await?;
+
+    let cfg = Config {
+        channel_type: ChannelType::PartialReliableRexmitUnordered,
+        negotiated: true,
+        reliability_parameter: 3,
+        label: "data".to_string(),
+        ..Default::default()
+    };
+
+    let dc0 = DataChannel::dial(&a0, 100, cfg.clone()).await?;
+
+    // Without a DATA_CHANNEL_ACK to wait for, the channel is configured at once
+    assert!(dc0.stream.is_unordered(), "should be unordered");
+    assert_eq!(dc0.stream.reliability_type(), ReliabilityType::Rexmit);
+    assert_eq!(dc0.stream.reliability_value(), 3);
+
+    dc0.close().await?;
+    bridge_process_at_least_one(&br).await;
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
+
 //TODO: remove this conditional test
 #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+#[tokio::test]
+async fn test_data_channel_negotiated_unread_message() -> Result<()> {
+    let mut rbuf = vec![0u8; 1500];
+
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, a1) = create_new_association_pair(&br, Arc::new(ca), Arc::new(cb)).await?;
+
+    let cfg = Config {
+        negotiated: true,
+        label: "data".to_string(),
+        ..Default::default()
+    };
+
+    let dc0 = DataChannel::dial(&a0, 100, cfg.clone()).await?;
+    dc0.write_data_channel(&Bytes::from_static(b"first"), true)
+        .await?;
+    dc0.write_data_channel(&Bytes::from_static(b"second"), true)
+        .await?;
+    bridge_process_at_least_one(&br).await;
+
+    // Without a DATA_CHANNEL_OPEN, accepting hands the first message back
+    let existing_data_channels: Vec<DataChannel> = Vec::new();
+    let stream = a1.accept_stream().await.unwrap();
+    let (ppi, data) = match DataChannel::accept_stream(
+        Arc::clone(&stream),
+        Config::default(),
+        &existing_data_channels,
+    )
+    .await
+    {
+        Err(Error::UnexpectedUserMessage { ppi, data }) => (ppi, data),
+        result => panic!("expected UnexpectedUserMessage, got {result:?}"),
+    };
+    assert_eq!(ppi, PayloadProtocolIdentifier::String);
+    assert_eq!(&data[..], b"first");
+
+    let dc1 = DataChannel::client(stream, cfg).await?;
+    dc1.unread(ppi, data);
+
+    let (n, is_string) = dc1.read_data_channel(&mut rbuf[..]).await?;
+    assert!(is_string, "should be a string");
+    assert_eq!(&rbuf[..n], b"first", "unread message should be read first");
+
+    let (n, is_string) = dc1.read_data_channel(&mut rbuf[..]).await?;
+    assert!(is_string, "should be a string");
+    assert_eq!(&rbuf[..n], b"second", "data should match");
+
+    dc0.close().await?;
+    dc1.close().await?;
+    bridge_process_at_least_one(&br).await;
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_data_channel_buffered_amount() -> Result<()> {
     let sbuf = vec![0u8; 1000];
@@ -668,3 +797,45 @@ async fn test_poll_data_channel() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_poll_data_channel_skips_empty_messages() -> Result<()> {
+    let mut rbuf = vec![0u8; 1500];
+
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, a1) = create_new_association_pair(&br, Arc::new(ca), Arc::new(cb)).await?;
+
+    let cfg = Config {
+        channel_type: ChannelType::Reliable,
+        reliability_parameter: 123,
+        label: "data".to_string(),
+        ..Default::default()
+    };
+
+    let dc0 = Arc::new(DataChannel::dial(&a0, 100, cfg.clone()).await?);
+    bridge_process_at_least_one(&br).await;
+
+    let existing_data_channels: Vec<DataChannel> = Vec::new();
+    let dc1 = Arc::new(DataChannel::accept(&a1, Config::default(), &existing_data_channels).await?);
+    bridge_process_at_least_one(&br).await;
+
+    dc0.write(&Bytes::new()).await?;
+    dc0.write(&Bytes::from_static(b"data")).await?;
+    bridge_process_at_least_one(&br).await;
+
+    let mut poll_dc1 = PollDataChannel::new(dc1);
+    let n = poll_dc1
+        .read(&mut rbuf[..])
+        .await
+        .map_err(|e| Error::new(e.to_string()))?;
+    assert_eq!(&rbuf[..n], b"data", "empty message should not read as EOF");
+
+    dc0.close().await?;
+    poll_dc1.into_inner().close().await?;
+    bridge_process_at_least_one(&br).await;
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ use std::future::Future;
 use std::net::Shutdown;
 use std::pin::Pin;
 use std::sync::atomic::Ordering;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 use std::{fmt, io};
 
@@ -41,6 +41,8 @@ pub struct Config {
 pub struct DataChannel {
     pub config: Config,
     stream: Arc<Stream>,
+    // A message read off the stream before the DataChannel was set up
+    unread: Arc<Mutex<Option<(PayloadProtocolIdentifier, Bytes)>>>,
 
     // stats
     messages_sent: Arc<AtomicUsize>,
@@ -85,6 +87,20 @@ impl DataChannel {
             .await
             .ok_or(Error::ErrStreamClosed)?;
 
+        Self::accept_stream(stream, config, existing_channels).await
+    }
+
+    /// AcceptStream sets up a data channel over a stream accepted from the remote. The stream
+    /// either belongs to one of `existing_channels`, or must start with a DATA_CHANNEL_OPEN
+    /// message.
+    pub async fn accept_stream<T>(
+        stream: Arc<Stream>,
+        config: Config,
+        existing_channels: &[T],
+    ) -> Result<Self>
+    where
+        T: Borrow<Self>,
+    {
         for channel in existing_channels.iter().map(|ch| ch.borrow()) {
             if channel.stream_identifier() == stream.stream_identifier() {
                 let ch = channel.to_owned();
@@ -115,7 +131,15 @@ impl DataChannel {
                 .write_sctp(&msg, PayloadProtocolIdentifier::Dcep)
                 .await?;
         }
-        Ok(DataChannel::new(stream, config))
+
+        let data_channel = DataChannel::new(stream, config);
+        if data_channel.config.negotiated {
+            // No DATA_CHANNEL_ACK arrives for a channel negotiated out of band,
+            // so the reliability parameters apply right away.
+            data_channel.commit_reliability_params();
+        }
+
+        Ok(data_channel)
     }
 
     /// Server accepts a data channel over an SCTP stream
@@ -124,8 +148,18 @@ impl DataChannel {
 
         let (n, ppi) = stream.read_sctp(&mut buf).await?;
 
-        if ppi != PayloadProtocolIdentifier::Dcep {
-            return Err(Error::InvalidPayloadProtocolIdentifier(ppi as u8));
+        match ppi {
+            PayloadProtocolIdentifier::Dcep => {}
+            PayloadProtocolIdentifier::Unknown => {
+                return Err(Error::InvalidPayloadProtocolIdentifier(ppi as u8));
+            }
+            _ => {
+                // Hand the message back, the stream may belong to a pre-negotiated channel
+                return Err(Error::UnexpectedUserMessage {
+                    ppi,
+                    data: Bytes::copy_from_slice(&buf[..n]),
+                });
+            }
         }
 
         let mut read_buf = &buf[..n];
@@ -159,14 +193,40 @@ impl DataChannel {
     /// ReadDataChannel reads a packet of len(p) bytes. It returns the number of bytes read and
     /// `true` if the data read is a string.
     ///
+    /// An empty message reads as 0 bytes, the same as the end of the stream. Use
+    /// [`DataChannel::read_message`] to tell the two apart.
+    ///
     /// See [`sctp::stream::Stream::read_sctp`].
     pub async fn read_data_channel(&self, buf: &mut [u8]) -> Result<(usize, bool)> {
+        Ok(self.read_message(buf).await?.unwrap_or((0, false)))
+    }
+
+    /// ReadMessage reads a packet of len(p) bytes. It returns the number of bytes read and
+    /// `true` if the data read is a string, or `None` once the incoming stream was reset or
+    /// the reading half was shutdown.
+    ///
+    /// See [`sctp::stream::Stream::read_sctp`].
+    pub async fn read_message(&self, buf: &mut [u8]) -> Result<Option<(usize, bool)>> {
         loop {
-            //TODO: add handling of cancel read_data_channel
-            let (mut n, ppi) = match self.stream.read_sctp(buf).await {
+            let unread = self.unread.lock().unwrap().take();
+            let result = match unread {
+                Some((ppi, data)) => {
+                    let n = data.len().min(buf.len());
+                    buf[..n].copy_from_slice(&data[..n]);
+                    if n < data.len() {
+                        Err(sctp::Error::ErrShortBuffer { size: buf.len() })
+                    } else {
+                        Ok((n, ppi))
+                    }
+                }
+                //TODO: add handling of cancel read_data_channel
+                None => self.stream.read_sctp(buf).await,
+            };
+
+            let (mut n, ppi) = match result {
                 Ok((0, PayloadProtocolIdentifier::Unknown)) => {
                     // The incoming stream was reset or the reading half was shutdown
-                    return Ok((0, false));
+                    return Ok(None);
                 }
                 Ok((n, ppi)) => (n, ppi),
                 Err(err) => {
@@ -204,7 +264,7 @@ impl DataChannel {
             self.messages_received.fetch_add(1, Ordering::SeqCst);
             self.bytes_received.fetch_add(n, Ordering::SeqCst);
 
-            return Ok((n, is_string));
+            return Ok(Some((n, is_string)));
         }
     }
 
@@ -228,6 +288,12 @@ impl DataChannel {
         self.bytes_received.load(Ordering::SeqCst)
     }
 
+    /// Unread hands back a message that was read off the stream before this DataChannel was
+    /// set up, see [`Error::UnexpectedUserMessage`]. The next read returns it.
+    pub fn unread(&self, ppi: PayloadProtocolIdentifier, data: Bytes) {
+        let _ = self.unread.lock().unwrap().replace((ppi, data));
+    }
+
     /// StreamIdentifier returns the Stream identifier associated to the stream.
     pub fn stream_identifier(&self) -> u16 {
         self.stream.stream_identifier()
@@ -318,6 +384,12 @@ impl DataChannel {
         Ok(self.stream.shutdown(Shutdown::Both).await?)
     }
 
+    /// IsAssociationClosed returns true once the SCTP association carrying this
+    /// DataChannel has been closed.
+    pub fn is_association_closed(&self) -> bool {
+        self.stream.is_association_closed()
+    }
+
     /// BufferedAmount returns the number of bytes of data currently queued to be
     /// sent over this stream.
     pub fn buffered_amount(&self) -> usize {
@@ -495,10 +567,19 @@ impl AsyncRead for PollDataChannel {
                 let data_channel = self.data_channel.clone();
                 let mut temp_buf = vec![0; self.read_buf_cap];
                 self.read_fut = ReadFut::Reading(Box::pin(async move {
-                    data_channel.read(temp_buf.as_mut_slice()).await.map(|n| {
-                        temp_buf.truncate(n);
-                        temp_buf
-                    })
+                    loop {
+                        match data_channel.read_message(temp_buf.as_mut_slice()).await {
+                            // An empty message carries no bytes for the stream, so it must
+                            // not be mistaken for EOF.
+                            Ok(Some((0, _))) => continue,
+                            Ok(Some((n, _))) => {
+                                temp_buf.truncate(n);
+                                return Ok(temp_buf);
+                            }
+                            Ok(None) => return Ok(Vec::new()),
+                            Err(err) => return Err(err),
+                        }
+                    }
                 }));
                 self.read_fut.get_reading_mut()
             }
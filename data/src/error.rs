@@ -1,6 +1,8 @@
 use std::io;
 use std::string::FromUtf8Error;
 
+use bytes::Bytes;
+use sctp::chunk::chunk_payload_data::PayloadProtocolIdentifier;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -18,6 +20,11 @@ pub enum Error {
     InvalidChannelType(u8),
     #[error("Unknown PayloadProtocolIdentifier {0}")]
     InvalidPayloadProtocolIdentifier(u8),
+    #[error("User message with PayloadProtocolIdentifier {ppi} before DATA_CHANNEL_OPEN")]
+    UnexpectedUserMessage {
+        ppi: PayloadProtocolIdentifier,
+        data: Bytes,
+    },
     #[error("Stream closed")]
     ErrStreamClosed,
 
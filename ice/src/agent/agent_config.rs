@@ -3,10 +3,12 @@ use std::time::Duration;
 
 use util::vnet::net::*;
 
+use super::agent_selector::CandidatePairPreference;
 use super::*;
 use crate::error::*;
 use crate::mdns::*;
 use crate::network_type::*;
+use crate::tcp_mux::TCPMux;
 use crate::udp_network::UDPNetwork;
 use crate::url::*;
 
@@ -34,6 +36,9 @@ pub(crate) const DEFAULT_PRFLX_ACCEPTANCE_MIN_WAIT: Duration = Duration::from_mi
 /// Wait time before nominating a relay candidate.
 pub(crate) const DEFAULT_RELAY_ACCEPTANCE_MIN_WAIT: Duration = Duration::from_millis(2000);
 
+/// The number of consecutive unanswered consent checks before consent expires.
+pub(crate) const DEFAULT_CONSENT_MAX_FAILURES: u16 = 6;
+
 /// Max binding request before considering a pair failed.
 pub(crate) const DEFAULT_MAX_BINDING_REQUESTS: u16 = 7;
 
@@ -64,6 +69,10 @@ pub struct AgentConfig {
     /// See [`UDPNetwork`]
     pub udp_network: UDPNetwork,
 
+    /// Passive ICE TCP candidates are gathered on this mux when set and a TCP network type is
+    /// enabled in `network_types`.
+    pub tcp_mux: Option<Arc<dyn TCPMux + Send + Sync>>,
+
     /// It is used to perform connectivity checks. The values MUST be unguessable, with at least
     /// 128 bits of random number generator output used to generate the password, and at least 24
     /// bits of output to generate the username fragment.
@@ -91,11 +100,21 @@ pub struct AgentConfig {
     pub failed_timeout: Option<Duration>,
 
     /// Determines how often should we send ICE keepalives (should be less then connectiontimeout
-    /// above) when this is nil, it defaults to 10 seconds.
+    /// above) when this is nil, it defaults to 2 seconds.
     /// A keepalive interval of 0 means we never send keepalive packets
     pub keepalive_interval: Option<Duration>,
 
+    /// Determines how often consent checks (RFC 7675) are sent on the selected pair, even while
+    /// media is flowing. When this is nil or 0, consent freshness is not enforced and the
+    /// connection state only follows the disconnected and failed timeouts above.
+    pub consent_check_interval: Option<Duration>,
+
+    /// The number of consent checks in a row that can go unanswered before consent expires and
+    /// the agent transitions to failed. Defaults to 6.
+    pub consent_max_failures: Option<u16>,
+
     /// An optional configuration for disabling or enabling support for specific network types.
+    /// Defaults to [`supported_network_types`] when empty.
     pub network_types: Vec<NetworkType>,
 
     /// An optional configuration for disabling or enabling support for specific candidate types.
@@ -113,6 +132,10 @@ pub struct AgentConfig {
 
     pub is_controlling: bool,
 
+    /// Overrides which candidate pair is preferred when nominating. Defaults to the pair with
+    /// the highest priority, see [`PriorityPairPreference`](super::agent_selector::PriorityPairPreference).
+    pub pair_preference: Option<Arc<dyn CandidatePairPreference + Send + Sync>>,
+
     /// lite agents do not perform connectivity check and only provide host candidates.
     pub lite: bool,
 
@@ -205,6 +228,16 @@ impl AgentConfig {
             a.keepalive_interval = DEFAULT_KEEPALIVE_INTERVAL;
         }
 
+        if let Some(consent_check_interval) = self.consent_check_interval {
+            a.consent_check_interval = consent_check_interval;
+        }
+
+        if let Some(consent_max_failures) = self.consent_max_failures {
+            a.consent_max_failures = consent_max_failures;
+        } else {
+            a.consent_max_failures = DEFAULT_CONSENT_MAX_FAILURES;
+        }
+
         if self.check_interval == Duration::from_secs(0) {
             a.check_interval = DEFAULT_CHECK_INTERVAL;
         } else {
@@ -22,6 +22,7 @@ const STUN_GATHER_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub(crate) struct GatherCandidatesInternalParams {
     pub(crate) udp_network: UDPNetwork,
+    pub(crate) tcp_mux: Option<Arc<dyn TCPMux + Send + Sync>>,
     pub(crate) candidate_types: Vec<CandidateType>,
     pub(crate) urls: Vec<Url>,
     pub(crate) network_types: Vec<NetworkType>,
@@ -38,6 +39,7 @@ pub(crate) struct GatherCandidatesInternalParams {
 
 struct GatherCandidatesLocalParams {
     udp_network: UDPNetwork,
+    tcp_mux: Option<Arc<dyn TCPMux + Send + Sync>>,
     network_types: Vec<NetworkType>,
     mdns_mode: MulticastDnsMode,
     mdns_name: String,
@@ -58,6 +60,16 @@ struct GatherCandidatesLocalUDPMuxParams {
     udp_mux: Arc<dyn UDPMux + Send + Sync>,
 }
 
+struct GatherCandidatesLocalTCPMuxParams {
+    network_types: Vec<NetworkType>,
+    interface_filter: Arc<Option<InterfaceFilterFn>>,
+    ip_filter: Arc<Option<IpFilterFn>>,
+    ext_ip_mapper: Arc<Option<ExternalIpMapper>>,
+    net: Arc<Net>,
+    agent_internal: Arc<AgentInternal>,
+    tcp_mux: Arc<dyn TCPMux + Send + Sync>,
+}
+
 struct GatherCandidatesSrflxMappedParasm {
     network_types: Vec<NetworkType>,
     port_max: u16,
@@ -92,6 +104,7 @@ impl Agent {
                 CandidateType::Host => {
                     let local_params = GatherCandidatesLocalParams {
                         udp_network: params.udp_network.clone(),
+                        tcp_mux: params.tcp_mux.clone(),
                         network_types: params.network_types.clone(),
                         mdns_mode: params.mdns_mode,
                         mdns_name: params.mdns_name.clone(),
@@ -195,6 +208,7 @@ impl Agent {
     async fn gather_candidates_local(params: GatherCandidatesLocalParams) {
         let GatherCandidatesLocalParams {
             udp_network,
+            tcp_mux,
             network_types,
             mdns_mode,
             mdns_name,
@@ -205,8 +219,34 @@ impl Agent {
             agent_internal,
         } = params;
 
+        // ICE TCP is only supported in passive mode, through a TCP mux
+        if let Some(tcp_mux) = tcp_mux {
+            if network_types.iter().any(|n| n.is_tcp()) {
+                let result =
+                    Self::gather_candidates_local_tcp_mux(GatherCandidatesLocalTCPMuxParams {
+                        network_types: network_types.clone(),
+                        interface_filter: Arc::clone(&interface_filter),
+                        ip_filter: Arc::clone(&ip_filter),
+                        ext_ip_mapper: Arc::clone(&ext_ip_mapper),
+                        net: Arc::clone(&net),
+                        agent_internal: Arc::clone(&agent_internal),
+                        tcp_mux,
+                    })
+                    .await;
+
+                if let Err(err) = result {
+                    log::error!("Failed to gather local candidates using TCP mux: {}", err);
+                }
+            }
+        }
+
+        // Filter out non UDP network types
+        let network_types: Vec<_> = network_types.into_iter().filter(|n| n.is_udp()).collect();
+        if network_types.is_empty() {
+            return;
+        }
+
         // If we wanna use UDP mux, do so
-        // FIXME: We still need to support TCP in combination with this option
         if let UDPNetwork::Muxed(udp_mux) = udp_network {
             let result = Self::gather_candidates_local_udp_mux(GatherCandidatesLocalUDPMuxParams {
                 network_types,
@@ -252,27 +292,8 @@ impl Agent {
                 mapped_ip.to_string()
             };
 
-            //TODO: for network in networks
             let network = UDP.to_owned();
             if let UDPNetwork::Ephemeral(ephemeral_config) = &udp_network {
-                /*TODO:switch network {
-                case tcp:
-                    // Handle ICE TCP passive mode
-
-                    a.log.Debugf("GetConn by ufrag: %s\n", a.localUfrag)
-                    conn, err = a.tcpMux.GetConnByUfrag(a.localUfrag)
-                    if err != nil {
-                        if !errors.Is(err, ErrTCPMuxNotInitialized) {
-                            a.log.Warnf("error getting tcp conn by ufrag: %s %s %s\n", network, ip, a.localUfrag)
-                        }
-                        continue
-                    }
-                    port = conn.LocalAddr().(*net.TCPAddr).Port
-                    tcpType = TCPTypePassive
-                    // is there a way to verify that the listen address is even
-                    // accessible from the current interface.
-                case udp:*/
-
                 let conn: Arc<dyn Conn + Send + Sync> = match listen_udp_in_port_range(
                     &net,
                     ephemeral_config.port_max(),
@@ -453,6 +474,79 @@ impl Agent {
         Ok(())
     }
 
+    async fn gather_candidates_local_tcp_mux(
+        params: GatherCandidatesLocalTCPMuxParams,
+    ) -> Result<()> {
+        let GatherCandidatesLocalTCPMuxParams {
+            network_types,
+            interface_filter,
+            ip_filter,
+            ext_ip_mapper,
+            net,
+            agent_internal,
+            tcp_mux,
+        } = params;
+
+        // Filter out non TCP network types
+        let relevant_network_types: Vec<_> =
+            network_types.into_iter().filter(|n| n.is_tcp()).collect();
+
+        let local_ips =
+            local_interfaces(&net, &interface_filter, &ip_filter, &relevant_network_types).await;
+
+        let candidate_ips: Vec<std::net::IpAddr> = match ext_ip_mapper.as_ref() {
+            Some(mapper) if mapper.candidate_type == CandidateType::Host => local_ips
+                .iter()
+                .filter_map(|ip| match mapper.find_external_ip(&ip.to_string()) {
+                    Ok(ip) => Some(ip),
+                    Err(err) => {
+                        log::warn!(
+                            "1:1 NAT mapping is enabled but not external IP is found for {}: {}",
+                            ip,
+                            err
+                        );
+                        None
+                    }
+                })
+                .collect(),
+            _ => local_ips.iter().copied().collect(),
+        };
+
+        if candidate_ips.is_empty() {
+            return Err(Error::ErrCandidateIpNotFound);
+        }
+
+        let ufrag = {
+            let ufrag_pwd = agent_internal.ufrag_pwd.lock().await;
+
+            ufrag_pwd.local_ufrag.clone()
+        };
+
+        let conn = tcp_mux.get_conn_by_ufrag(&ufrag).await?;
+        let port = conn.local_addr()?.port();
+
+        for candidate_ip in candidate_ips {
+            let host_config = CandidateHostConfig {
+                base_config: CandidateBaseConfig {
+                    network: TCP.to_owned(),
+                    address: candidate_ip.to_string(),
+                    port,
+                    conn: Some(conn.clone()),
+                    component: COMPONENT_RTP,
+                    ..Default::default()
+                },
+                tcp_type: TcpType::Passive,
+            };
+
+            let candidate: Arc<dyn Candidate + Send + Sync> =
+                Arc::new(host_config.new_candidate_host()?);
+
+            agent_internal.add_candidate(&candidate).await?;
+        }
+
+        Ok(())
+    }
+
     async fn gather_candidates_srflx_mapped(params: GatherCandidatesSrflxMappedParasm) {
         let GatherCandidatesSrflxMappedParasm {
             network_types,
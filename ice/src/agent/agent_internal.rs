@@ -1,12 +1,14 @@
-use portable_atomic::{AtomicBool, AtomicU64};
+use portable_atomic::{AtomicBool, AtomicU16, AtomicU64};
 
 use arc_swap::ArcSwapOption;
+use stun::error_code::*;
 use util::sync::Mutex as SyncMutex;
 
 use super::agent_transport::*;
 use super::*;
 use crate::candidate::candidate_base::CandidateBaseConfig;
 use crate::candidate::candidate_peer_reflexive::CandidatePeerReflexiveConfig;
+use crate::control::{AttrControlled, AttrControlling};
 use crate::util::*;
 
 pub type ChanCandidateTx =
@@ -33,7 +35,7 @@ pub struct AgentInternal {
         Mutex<Option<(mpsc::Receiver<()>, mpsc::Receiver<bool>)>>,
 
     pub(crate) chan_candidate_tx: ChanCandidateTx,
-    pub(crate) chan_candidate_pair_tx: Mutex<Option<mpsc::Sender<()>>>,
+    pub(crate) chan_candidate_pair_tx: Mutex<Option<mpsc::Sender<Arc<CandidatePair>>>>,
     pub(crate) chan_state_tx: Mutex<Option<mpsc::Sender<ConnectionState>>>,
 
     pub(crate) on_connection_state_change_hdlr: ArcSwapOption<Mutex<OnConnectionStateChangeHdlrFn>>,
@@ -50,6 +52,11 @@ pub struct AgentInternal {
 
     pub(crate) connection_state: AtomicU8, //ConnectionState,
 
+    // Consecutive consent checks sent on the selected pair without a response
+    pub(crate) consent_unanswered_checks: AtomicU16,
+    // When the last consent check was sent on the selected pair
+    pub(crate) last_consent_check: SyncMutex<Option<Instant>>,
+
     pub(crate) started_ch_tx: Mutex<Option<broadcast::Sender<()>>>,
 
     pub(crate) ufrag_pwd: Mutex<UfragPwd>,
@@ -81,6 +88,11 @@ pub struct AgentInternal {
     pub(crate) keepalive_interval: Duration,
     // How often should we run our internal taskLoop to check for state changes when connecting
     pub(crate) check_interval: Duration,
+    // How often should we send consent checks on the selected pair?
+    // 0 means never
+    pub(crate) consent_check_interval: Duration,
+    // How many consent checks in a row can go unanswered before consent expires
+    pub(crate) consent_max_failures: u16,
 }
 
 impl AgentInternal {
@@ -93,6 +105,11 @@ impl AgentInternal {
         let (force_candidate_contact_tx, force_candidate_contact_rx) = mpsc::channel(1);
         let (started_ch_tx, _) = broadcast::channel(1);
 
+        let mut agent_conn = AgentConn::new();
+        if let Some(pair_preference) = &config.pair_preference {
+            agent_conn.pair_preference = Arc::clone(pair_preference);
+        }
+
         let ai = AgentInternal {
             on_connected_tx: Mutex::new(Some(on_connected_tx)),
             on_connected_rx: Mutex::new(Some(on_connected_rx)),
@@ -121,6 +138,9 @@ impl AgentInternal {
 
             connection_state: AtomicU8::new(ConnectionState::New as u8),
 
+            consent_unanswered_checks: AtomicU16::new(0),
+            last_consent_check: SyncMutex::new(None),
+
             insecure_skip_verify: config.insecure_skip_verify,
 
             started_ch_tx: Mutex::new(Some(started_ch_tx)),
@@ -147,6 +167,13 @@ impl AgentInternal {
             // How often should we run our internal taskLoop to check for state changes when connecting
             check_interval: Duration::from_secs(0),
 
+            // How often should we send consent checks on the selected pair?
+            // 0 means never
+            consent_check_interval: Duration::from_secs(0),
+
+            // How many consent checks in a row can go unanswered before consent expires
+            consent_max_failures: 0,
+
             ufrag_pwd: Mutex::new(UfragPwd::default()),
 
             local_candidates: Mutex::new(HashMap::new()),
@@ -156,7 +183,7 @@ impl AgentInternal {
             pending_binding_requests: Mutex::new(vec![]),
 
             // AgentConn
-            agent_conn: Arc::new(AgentConn::new()),
+            agent_conn: Arc::new(agent_conn),
         };
 
         let chan_receivers = ChanReceivers {
@@ -221,11 +248,13 @@ impl AgentInternal {
                 *checking_duration = Instant::now();
             }
 
-            // We have been in checking longer then Disconnect+Failed timeout, set the connection to Failed
-            if Instant::now()
-                .checked_duration_since(*checking_duration)
-                .unwrap_or_else(|| Duration::from_secs(0))
-                > self.disconnected_timeout + self.failed_timeout
+            // We have been in checking longer then Disconnect+Failed timeout, set the connection to Failed.
+            // Only allow transitions to failed if a.failedTimeout is non-zero
+            if self.failed_timeout != Duration::from_secs(0)
+                && Instant::now()
+                    .checked_duration_since(*checking_duration)
+                    .unwrap_or_else(|| Duration::from_secs(0))
+                    > self.disconnected_timeout + self.failed_timeout
             {
                 self.update_connection_state(ConnectionState::Failed).await;
                 *last_connection_state = self.connection_state.load(Ordering::SeqCst).into();
@@ -242,9 +271,16 @@ impl AgentInternal {
         const ZERO_DURATION: Duration = Duration::from_secs(0);
         let mut last_connection_state = ConnectionState::Unspecified;
         let mut checking_duration = Instant::now();
-        let (check_interval, keepalive_interval, disconnected_timeout, failed_timeout) = (
+        let (
+            check_interval,
+            keepalive_interval,
+            consent_check_interval,
+            disconnected_timeout,
+            failed_timeout,
+        ) = (
             self.check_interval,
             self.keepalive_interval,
+            self.consent_check_interval,
             self.disconnected_timeout,
             self.failed_timeout,
         );
@@ -276,6 +312,7 @@ impl AgentInternal {
                         }
                         ConnectionState::Connected | ConnectionState::Disconnected => {
                             update_interval(keepalive_interval);
+                            update_interval(consent_check_interval);
                         }
                         _ => {}
                     };
@@ -337,16 +374,21 @@ impl AgentInternal {
 
         if let Some(p) = p {
             p.nominated.store(true, Ordering::SeqCst);
-            self.agent_conn.selected_pair.store(Some(p));
+            self.agent_conn.selected_pair.store(Some(Arc::clone(&p)));
+
+            // Consent is granted by the checks that selected the pair
+            self.consent_unanswered_checks.store(0, Ordering::SeqCst);
+            *self.last_consent_check.lock() = None;
 
             self.update_connection_state(ConnectionState::Connected)
                 .await;
 
-            // Notify when the selected pair changes
+            // Notify when the selected pair changes. The pair travels with the event so the
+            // handler sees it even if the selection is cleared before the handler runs.
             {
                 let chan_candidate_pair_tx = self.chan_candidate_pair_tx.lock().await;
                 if let Some(tx) = &*chan_candidate_pair_tx {
-                    let _ = tx.send(()).await;
+                    let _ = tx.send(p).await;
                 }
             }
 
@@ -438,6 +480,11 @@ impl AgentInternal {
     /// Checks if the selected pair is (still) valid.
     /// Note: the caller should hold the agent lock.
     pub(crate) async fn validate_selected_pair(&self) -> bool {
+        // Failed is left only through a restart, e.g. once consent expired
+        if self.connection_state.load(Ordering::SeqCst) == ConnectionState::Failed as u8 {
+            return false;
+        }
+
         let (valid, disconnected_time) = {
             let selected_pair = self.agent_conn.selected_pair.load();
             (*selected_pair).as_ref().map_or_else(
@@ -512,6 +559,69 @@ impl AgentInternal {
         }
     }
 
+    /// Sends a consent check (RFC 7675) on the selected pair every `consent_check_interval`,
+    /// regardless of the traffic flowing over it, and transitions to failed once
+    /// `consent_max_failures` checks in a row went unanswered. Without consent the pair is
+    /// dropped, so nothing is sent on it anymore.
+    /// Note: the caller should hold the agent lock.
+    pub(crate) async fn check_consent(&self) {
+        if self.consent_check_interval == Duration::from_secs(0) {
+            return;
+        }
+
+        let selected_pair = match self.agent_conn.get_selected_pair() {
+            Some(selected_pair) => selected_pair,
+            None => return,
+        };
+
+        {
+            let mut last_consent_check = self.last_consent_check.lock();
+            if let Some(last) = *last_consent_check {
+                if Instant::now()
+                    .checked_duration_since(last)
+                    .unwrap_or_else(|| Duration::from_secs(0))
+                    < self.consent_check_interval
+                {
+                    return;
+                }
+            }
+            *last_consent_check = Some(Instant::now());
+        }
+
+        let unanswered = self
+            .consent_unanswered_checks
+            .fetch_add(1, Ordering::SeqCst);
+        if unanswered >= self.consent_max_failures {
+            log::warn!(
+                "[{}]: consent expired after {} unanswered checks",
+                self.get_name(),
+                unanswered
+            );
+            selected_pair
+                .state
+                .store(CandidatePairState::Failed as u8, Ordering::SeqCst);
+            self.set_selected_pair(None).await;
+            self.update_connection_state(ConnectionState::Failed).await;
+            return;
+        }
+
+        self.ping_candidate(&selected_pair.local, &selected_pair.remote)
+            .await;
+    }
+
+    /// Refreshes consent if the authenticated success response was received on the selected pair.
+    pub(crate) fn refresh_consent(
+        &self,
+        local: &Arc<dyn Candidate + Send + Sync>,
+        remote: &Arc<dyn Candidate + Send + Sync>,
+    ) {
+        if let Some(selected_pair) = self.agent_conn.get_selected_pair() {
+            if selected_pair.local.equal(&**local) && selected_pair.remote.equal(&**remote) {
+                self.consent_unanswered_checks.store(0, Ordering::SeqCst);
+            }
+        }
+    }
+
     fn request_connectivity_check(&self) {
         let _ = self.force_candidate_contact_tx.try_send(true);
     }
@@ -828,6 +938,11 @@ impl AgentInternal {
         local: &Arc<dyn Candidate + Send + Sync>,
         remote: SocketAddr,
     ) {
+        if m.typ.method == METHOD_BINDING && m.typ.class == CLASS_ERROR_RESPONSE {
+            self.handle_error_response(m, local, remote).await;
+            return;
+        }
+
         if m.typ.method != METHOD_BINDING
             || !(m.typ.class == CLASS_SUCCESS_RESPONSE
                 || m.typ.class == CLASS_REQUEST
@@ -844,7 +959,21 @@ impl AgentInternal {
             return;
         }
 
-        if self.is_controlling.load(Ordering::SeqCst) {
+        // Both agents claim the same role, this is resolved with the tie-breaker once the
+        // request has been authenticated. See RFC 8445 Section 7.3.1.1.
+        let role_conflict = m.typ.class == CLASS_REQUEST
+            && if self.is_controlling.load(Ordering::SeqCst) {
+                m.contains(ATTR_ICE_CONTROLLING)
+            } else {
+                m.contains(ATTR_ICE_CONTROLLED)
+            };
+
+        if role_conflict {
+            log::debug!(
+                "[{}]: inbound request with a role conflict",
+                self.get_name()
+            );
+        } else if self.is_controlling.load(Ordering::SeqCst) {
             if m.contains(ATTR_ICE_CONTROLLING) {
                 log::debug!(
                     "[{}]: inbound isControlling && a.isControlling == true",
@@ -887,6 +1016,7 @@ impl AgentInternal {
 
             if let Some(rc) = &remote_candidate {
                 self.handle_success_response(m, local, rc, remote).await;
+                self.refresh_consent(local, rc);
             } else {
                 log::warn!(
                     "[{}]: discard success message from ({}), no such remote",
@@ -922,7 +1052,7 @@ impl AgentInternal {
             }
 
             if remote_candidate.is_none() {
-                let (ip, port, network_type) = (remote.ip(), remote.port(), NetworkType::Udp4);
+                let (ip, port, network_type) = (remote.ip(), remote.port(), local.network_type());
 
                 let prflx_candidate_config = CandidatePeerReflexiveConfig {
                     base_config: CandidateBaseConfig {
@@ -966,6 +1096,10 @@ impl AgentInternal {
             );
 
             if let Some(rc) = &remote_candidate {
+                if role_conflict && !self.resolve_role_conflict(m, local, rc).await {
+                    return;
+                }
+
                 self.handle_binding_request(m, local, rc).await;
             }
         }
@@ -975,6 +1109,159 @@ impl AgentInternal {
         }
     }
 
+    /// Resolves a role conflict for an authenticated binding request carrying the same role as
+    /// ours. The agent with the larger tie-breaker keeps or takes the controlling role, see
+    /// RFC 8445 Section 7.3.1.1. Returns false if the request was rejected with a 487
+    /// (Role Conflict) error, true if we switched role and the request should be processed.
+    pub(crate) async fn resolve_role_conflict(
+        &self,
+        m: &Message,
+        local: &Arc<dyn Candidate + Send + Sync>,
+        remote: &Arc<dyn Candidate + Send + Sync>,
+    ) -> bool {
+        let controlling = self.is_controlling.load(Ordering::SeqCst);
+        let remote_tie_breaker = if controlling {
+            let mut attr = AttrControlling::default();
+            attr.get_from(m).map(|_| attr.0)
+        } else {
+            let mut attr = AttrControlled::default();
+            attr.get_from(m).map(|_| attr.0)
+        };
+        let remote_tie_breaker = match remote_tie_breaker {
+            Ok(remote_tie_breaker) => remote_tie_breaker,
+            Err(err) => {
+                log::warn!(
+                    "[{}]: discard message from ({}), {}",
+                    self.get_name(),
+                    remote,
+                    err
+                );
+                return false;
+            }
+        };
+
+        let local_tie_breaker = self.tie_breaker.load(Ordering::SeqCst);
+        // The controlling agent keeps its role if its tie-breaker is larger or equal, the
+        // controlled agent takes the controlling role under the same condition.
+        if (local_tie_breaker >= remote_tie_breaker) == controlling {
+            self.send_role_conflict(m, local, remote).await;
+            false
+        } else {
+            self.switch_role(!controlling).await;
+            true
+        }
+    }
+
+    /// Switches to the controlling or controlled role, updating the priorities of the checklist.
+    pub(crate) async fn switch_role(&self, controlling: bool) {
+        if self.is_controlling.swap(controlling, Ordering::SeqCst) == controlling {
+            return;
+        }
+
+        log::info!(
+            "[{}]: switching role to resolve a role conflict",
+            self.get_name()
+        );
+
+        {
+            let checklist = self.agent_conn.checklist.lock().await;
+            for p in &*checklist {
+                p.ice_role_controlling.store(controlling, Ordering::SeqCst);
+            }
+        }
+
+        self.start().await;
+    }
+
+    async fn send_role_conflict(
+        &self,
+        m: &Message,
+        local: &Arc<dyn Candidate + Send + Sync>,
+        remote: &Arc<dyn Candidate + Send + Sync>,
+    ) {
+        let local_pwd = {
+            let ufrag_pwd = self.ufrag_pwd.lock().await;
+            ufrag_pwd.local_pwd.clone()
+        };
+
+        let (out, result) = {
+            let mut out = Message::new();
+            let result = out.build(&[
+                Box::new(m.clone()),
+                Box::new(BINDING_ERROR),
+                Box::new(CODE_ROLE_CONFLICT),
+                Box::new(MessageIntegrity::new_short_term_integrity(local_pwd)),
+                Box::new(FINGERPRINT),
+            ]);
+            (out, result)
+        };
+
+        if let Err(err) = result {
+            log::warn!(
+                "[{}]: Failed to send role conflict from: {} to: {} error: {}",
+                self.get_name(),
+                local,
+                remote,
+                err
+            );
+        } else {
+            self.send_stun(&out, local, remote).await;
+        }
+    }
+
+    /// Handles binding error responses. A 487 (Role Conflict) response to one of our requests
+    /// means the remote agent won the tie-breaker, so we switch role, see RFC 8445
+    /// Section 7.2.5.1.
+    async fn handle_error_response(
+        &self,
+        m: &mut Message,
+        local: &Arc<dyn Candidate + Send + Sync>,
+        remote: SocketAddr,
+    ) {
+        let mut error_code = ErrorCodeAttribute::default();
+        if error_code.get_from(m).is_err() || error_code.code != CODE_ROLE_CONFLICT {
+            log::trace!(
+                "[{}]: unhandled STUN error response from {} to {}",
+                self.get_name(),
+                remote,
+                local
+            );
+            return;
+        }
+
+        {
+            let ufrag_pwd = self.ufrag_pwd.lock().await;
+            if let Err(err) = assert_inbound_message_integrity(m, ufrag_pwd.remote_pwd.as_bytes()) {
+                log::warn!(
+                    "[{}]: discard message from ({}), {}",
+                    self.get_name(),
+                    remote,
+                    err
+                );
+                return;
+            }
+        }
+
+        let pending = {
+            let pending_binding_requests = self.pending_binding_requests.lock().await;
+            pending_binding_requests
+                .iter()
+                .any(|r| r.transaction_id == m.transaction_id)
+        };
+        if !pending {
+            log::warn!(
+                "[{}]: discard role conflict from ({}), unknown TransactionID 0x{:?}",
+                self.get_name(),
+                remote,
+                m.transaction_id
+            );
+            return;
+        }
+
+        let controlling = self.is_controlling.load(Ordering::SeqCst);
+        self.switch_role(!controlling).await;
+    }
+
     /// Processes non STUN traffic from a remote candidate, and returns true if it is an actual
     /// remote candidate.
     pub(crate) async fn validate_non_stun_traffic(
@@ -1055,17 +1342,14 @@ impl AgentInternal {
         self: &Arc<Self>,
         mut chan_state_rx: mpsc::Receiver<ConnectionState>,
         mut chan_candidate_rx: mpsc::Receiver<Option<Arc<dyn Candidate + Send + Sync>>>,
-        mut chan_candidate_pair_rx: mpsc::Receiver<()>,
+        mut chan_candidate_pair_rx: mpsc::Receiver<Arc<CandidatePair>>,
     ) {
         let ai = Arc::clone(self);
         tokio::spawn(async move {
             // CandidatePair and ConnectionState are usually changed at once.
             // Blocking one by the other one causes deadlock.
-            while chan_candidate_pair_rx.recv().await.is_some() {
-                if let (Some(cb), Some(p)) = (
-                    &*ai.on_selected_candidate_pair_change_hdlr.load(),
-                    &*ai.agent_conn.selected_pair.load(),
-                ) {
+            while let Some(p) = chan_candidate_pair_rx.recv().await {
+                if let Some(cb) = &*ai.on_selected_candidate_pair_change_hdlr.load() {
                     let mut f = cb.lock().await;
                     f(&p.local, &p.remote).await;
                 }
@@ -1,3 +1,4 @@
+use std::cmp;
 use std::net::SocketAddr;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -17,6 +18,38 @@ use crate::control::*;
 use crate::priority::*;
 use crate::use_candidate::*;
 
+/// Decides which of two candidate pairs the agent prefers. It is consulted when picking the pair
+/// to nominate, so applications can override the RFC 8445 pair priority, e.g. to always prefer
+/// relayed pairs for privacy or pairs on a specific interface.
+pub trait CandidatePairPreference {
+    /// Compares two candidate pairs, the pair ordered `Greater` is preferred.
+    fn compare(&self, a: &CandidatePair, b: &CandidatePair) -> cmp::Ordering;
+}
+
+/// Prefers the pair with the highest priority. This is the default preference.
+#[derive(Default, Debug, Copy, Clone)]
+pub struct PriorityPairPreference;
+
+impl CandidatePairPreference for PriorityPairPreference {
+    fn compare(&self, a: &CandidatePair, b: &CandidatePair) -> cmp::Ordering {
+        a.priority().cmp(&b.priority())
+    }
+}
+
+/// Prefers pairs with a relayed local candidate, then falls back to the pair priority.
+#[derive(Default, Debug, Copy, Clone)]
+pub struct RelayPairPreference;
+
+impl CandidatePairPreference for RelayPairPreference {
+    fn compare(&self, a: &CandidatePair, b: &CandidatePair) -> cmp::Ordering {
+        let is_relay = |p: &CandidatePair| p.local.candidate_type() == CandidateType::Relay;
+
+        is_relay(a)
+            .cmp(&is_relay(b))
+            .then_with(|| a.priority().cmp(&b.priority()))
+    }
+}
+
 #[async_trait]
 trait ControllingSelector {
     async fn start(&self);
@@ -241,6 +274,7 @@ impl ControllingSelector for AgentInternal {
             if self.validate_selected_pair().await {
                 log::trace!("[{}]: checking keepalive", self.get_name());
                 self.check_keepalive().await;
+                self.check_consent().await;
             }
         } else if nominated_pair_is_some {
             self.nominate_pair().await;
@@ -419,6 +453,7 @@ impl ControlledSelector for AgentInternal {
             if self.validate_selected_pair().await {
                 log::trace!("[{}]: checking keepalive", self.get_name());
                 self.check_keepalive().await;
+                self.check_consent().await;
             }
         } else {
             self.ping_all_candidates().await;
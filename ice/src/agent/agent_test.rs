@@ -9,6 +9,7 @@ use util::vnet::*;
 use util::Conn;
 use waitgroup::{WaitGroup, Worker};
 
+use super::agent_selector::*;
 use super::agent_vnet_test::*;
 use super::*;
 use crate::agent::agent_transport_test::pipe;
@@ -17,7 +18,7 @@ use crate::candidate::candidate_host::*;
 use crate::candidate::candidate_peer_reflexive::*;
 use crate::candidate::candidate_relay::*;
 use crate::candidate::candidate_server_reflexive::*;
-use crate::control::AttrControlling;
+use crate::control::{AttrControlled, AttrControlling};
 use crate::priority::PriorityAttr;
 use crate::use_candidate::UseCandidateAttr;
 
@@ -48,6 +49,87 @@ async fn test_pair_search() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_pair_preference() -> Result<()> {
+    let host_config = CandidateHostConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "192.168.1.1".to_owned(),
+            port: 19216,
+            component: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let host_local: Arc<dyn Candidate + Send + Sync> = Arc::new(host_config.new_candidate_host()?);
+
+    let relay_config = CandidateRelayConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "1.2.3.4".to_owned(),
+            port: 12340,
+            component: 1,
+            ..Default::default()
+        },
+        rel_addr: "4.3.2.1".to_owned(),
+        rel_port: 43210,
+        ..Default::default()
+    };
+    let relay_local: Arc<dyn Candidate + Send + Sync> =
+        Arc::new(relay_config.new_candidate_relay()?);
+
+    let remote_config = CandidateHostConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "192.168.1.2".to_owned(),
+            port: 19217,
+            component: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let remote: Arc<dyn Candidate + Send + Sync> = Arc::new(remote_config.new_candidate_host()?);
+
+    let host_pair = Arc::new(CandidatePair::new(host_local, Arc::clone(&remote), true));
+    let relay_pair = Arc::new(CandidatePair::new(relay_local, remote, true));
+    assert!(host_pair.priority() > relay_pair.priority());
+
+    for (pair_preference, expected) in [
+        (
+            Arc::new(PriorityPairPreference) as Arc<dyn CandidatePairPreference + Send + Sync>,
+            &host_pair,
+        ),
+        (
+            Arc::new(RelayPairPreference) as Arc<dyn CandidatePairPreference + Send + Sync>,
+            &relay_pair,
+        ),
+    ] {
+        let a = Agent::new(AgentConfig {
+            pair_preference: Some(pair_preference),
+            ..Default::default()
+        })
+        .await?;
+
+        {
+            let mut checklist = a.internal.agent_conn.checklist.lock().await;
+            checklist.push(Arc::clone(&host_pair));
+            checklist.push(Arc::clone(&relay_pair));
+        }
+
+        let best = a
+            .internal
+            .agent_conn
+            .get_best_available_candidate_pair()
+            .await
+            .expect("a candidate pair should be available");
+        assert_eq!(&*best, &**expected);
+
+        a.close().await?;
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_pair_priority() -> Result<()> {
     let a = Agent::new(AgentConfig::default()).await?;
@@ -241,6 +323,123 @@ async fn test_on_selected_candidate_pair_change() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_on_selected_candidate_pair_change_after_reset() -> Result<()> {
+    let a = Agent::new(AgentConfig::default()).await?;
+    let (pair_tx, mut pair_rx) = mpsc::channel::<(String, String)>(1);
+    a.on_selected_candidate_pair_change(Box::new(move |local, remote| {
+        let pair_tx = pair_tx.clone();
+        let addrs = (local.address(), remote.address());
+        Box::pin(async move {
+            let _ = pair_tx.send(addrs).await;
+        })
+    }));
+
+    let host_local = CandidateHostConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "192.168.1.1".to_owned(),
+            port: 19216,
+            component: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .new_candidate_host()?;
+    let host_remote = CandidateHostConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "192.168.1.2".to_owned(),
+            port: 19217,
+            component: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .new_candidate_host()?;
+
+    let p = Arc::new(CandidatePair::new(
+        Arc::new(host_local),
+        Arc::new(host_remote),
+        false,
+    ));
+    a.internal.set_selected_pair(Some(p)).await;
+    // Clearing the selection must not swallow the pending event.
+    a.internal.set_selected_pair(None).await;
+
+    let (local, remote) = tokio::time::timeout(Duration::from_secs(5), pair_rx.recv())
+        .await
+        .expect("selected candidate pair change handler was not called")
+        .unwrap();
+    assert_eq!(local, "192.168.1.1");
+    assert_eq!(remote, "192.168.1.2");
+
+    a.close().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resolve_role_conflict() -> Result<()> {
+    let a = Agent::new(AgentConfig {
+        is_controlling: true,
+        ..Default::default()
+    })
+    .await?;
+
+    let host_config = CandidateHostConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "192.168.1.1".to_owned(),
+            port: 19216,
+            component: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let local: Arc<dyn Candidate + Send + Sync> = Arc::new(host_config.new_candidate_host()?);
+
+    let remote_config = CandidateHostConfig {
+        base_config: CandidateBaseConfig {
+            network: "udp".to_owned(),
+            address: "192.168.1.2".to_owned(),
+            port: 19217,
+            component: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let remote: Arc<dyn Candidate + Send + Sync> = Arc::new(remote_config.new_candidate_host()?);
+
+    a.internal.tie_breaker.store(100, Ordering::SeqCst);
+
+    // Controlling with the larger tie-breaker, we keep our role and reject the request
+    let mut m = Message::new();
+    m.build(&[Box::new(BINDING_REQUEST), Box::new(AttrControlling(50))])?;
+    assert!(!a.internal.resolve_role_conflict(&m, &local, &remote).await);
+    assert!(a.internal.is_controlling.load(Ordering::SeqCst));
+
+    // Controlling with the smaller tie-breaker, we become controlled
+    let mut m = Message::new();
+    m.build(&[Box::new(BINDING_REQUEST), Box::new(AttrControlling(150))])?;
+    assert!(a.internal.resolve_role_conflict(&m, &local, &remote).await);
+    assert!(!a.internal.is_controlling.load(Ordering::SeqCst));
+
+    // Controlled with the smaller tie-breaker, we stay controlled and reject the request
+    let mut m = Message::new();
+    m.build(&[Box::new(BINDING_REQUEST), Box::new(AttrControlled(150))])?;
+    assert!(!a.internal.resolve_role_conflict(&m, &local, &remote).await);
+    assert!(!a.internal.is_controlling.load(Ordering::SeqCst));
+
+    // Controlled with the larger tie-breaker, we become controlling
+    let mut m = Message::new();
+    m.build(&[Box::new(BINDING_REQUEST), Box::new(AttrControlled(50))])?;
+    assert!(a.internal.resolve_role_conflict(&m, &local, &remote).await);
+    assert!(a.internal.is_controlling.load(Ordering::SeqCst));
+
+    a.close().await?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_handle_peer_reflexive_udp_pflx_candidate() -> Result<()> {
     let a = Agent::new(AgentConfig::default()).await?;
@@ -1770,6 +1969,57 @@ async fn test_connection_state_connecting_to_failed() -> Result<()> {
     Ok(())
 }
 
+// Assert that a zero failed timeout keeps the Agent checking instead of failing
+#[tokio::test]
+async fn test_connection_state_zero_failed_timeout() -> Result<()> {
+    let disconnected_duration = Duration::from_millis(100);
+
+    let a_agent = Arc::new(
+        Agent::new(AgentConfig {
+            disconnected_timeout: Some(disconnected_duration),
+            failed_timeout: Some(Duration::from_secs(0)),
+            keepalive_interval: Some(Duration::from_secs(0)),
+            check_interval: Duration::from_millis(50),
+            ..Default::default()
+        })
+        .await?,
+    );
+
+    let (is_checking_tx, mut is_checking_rx) = mpsc::channel::<()>(1);
+    let is_checking_tx = Arc::new(Mutex::new(Some(is_checking_tx)));
+    a_agent.on_connection_state_change(Box::new(move |c: ConnectionState| {
+        let is_checking_tx_clone = Arc::clone(&is_checking_tx);
+        Box::pin(async move {
+            if c == ConnectionState::Checking {
+                let mut tx = is_checking_tx_clone.lock().await;
+                tx.take();
+            }
+        })
+    }));
+
+    let agent_a = Arc::clone(&a_agent);
+    tokio::spawn(async move {
+        let (_cancel_tx, cancel_rx) = mpsc::channel(1);
+        let _ = agent_a
+            .accept(cancel_rx, "InvalidFrag".to_owned(), "InvalidPwd".to_owned())
+            .await;
+    });
+
+    let _ = is_checking_rx.recv().await;
+
+    // Well past the disconnected timeout, which alone used to fail the Agent
+    tokio::time::sleep(disconnected_duration * 5).await;
+    assert_eq!(
+        a_agent.internal.connection_state.load(Ordering::SeqCst),
+        ConnectionState::Checking as u8,
+        "should still be checking"
+    );
+
+    a_agent.close().await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_agent_restart_during_gather() -> Result<()> {
     //"Restart During Gather"
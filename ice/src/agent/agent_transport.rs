@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use portable_atomic::AtomicBool;
 use util::Conn;
 
+use super::agent_selector::*;
 use super::*;
 use crate::error::*;
 
@@ -90,6 +91,7 @@ pub(crate) struct AgentConn {
     pub(crate) bytes_received: AtomicUsize,
     pub(crate) bytes_sent: AtomicUsize,
     pub(crate) done: AtomicBool,
+    pub(crate) pair_preference: Arc<dyn CandidatePairPreference + Send + Sync>,
 }
 
 impl AgentConn {
@@ -104,6 +106,7 @@ impl AgentConn {
             bytes_received: AtomicUsize::new(0),
             bytes_sent: AtomicUsize::new(0),
             done: AtomicBool::new(false),
+            pair_preference: Arc::new(PriorityPairPreference),
         }
     }
     pub(crate) fn get_selected_pair(&self) -> Option<Arc<CandidatePair>> {
@@ -120,7 +123,7 @@ impl AgentConn {
             }
 
             if let Some(b) = &mut best {
-                if b.priority() < p.priority() {
+                if self.pair_preference.compare(p, b) == std::cmp::Ordering::Greater {
                     *b = p;
                 }
             } else {
@@ -141,7 +144,7 @@ impl AgentConn {
             }
 
             if let Some(b) = &mut best {
-                if b.priority() < p.priority() {
+                if self.pair_preference.compare(p, b) == std::cmp::Ordering::Greater {
                     *b = p;
                 }
             } else {
@@ -884,6 +884,121 @@ async fn test_disconnected_to_connected() -> Result<(), Error> {
     Ok(())
 }
 
+// test_consent_expiry_is_final asserts that an agent whose consent expired stays failed and
+// stops sending, even once the remote answers again
+#[tokio::test]
+async fn test_consent_expiry_is_final() -> Result<(), Error> {
+    let wan = router::Router::new(router::RouterConfig {
+        cidr: "0.0.0.0/0".to_owned(),
+        ..Default::default()
+    })?;
+
+    let drop_all_data = Arc::new(AtomicU64::new(0));
+    let drop_all_data2 = Arc::clone(&drop_all_data);
+    wan.add_chunk_filter(Box::new(move |_c: &(dyn Chunk + Send + Sync)| -> bool {
+        drop_all_data2.load(Ordering::SeqCst) != 1
+    }))
+    .await;
+    let wan = Arc::new(Mutex::new(wan));
+
+    let net0 = Arc::new(net::Net::new(Some(net::NetConfig {
+        static_ips: vec!["192.168.0.1".to_owned()],
+        ..Default::default()
+    })));
+    let net1 = Arc::new(net::Net::new(Some(net::NetConfig {
+        static_ips: vec!["192.168.0.2".to_owned()],
+        ..Default::default()
+    })));
+
+    connect_net2router(&net0, &wan).await?;
+    connect_net2router(&net1, &wan).await?;
+    start_router(&wan).await?;
+
+    // Consent expires well before the agent would go disconnected on its own
+    let consent_check_interval = Duration::from_millis(20);
+    let agent_config = |net: &Arc<net::Net>| AgentConfig {
+        network_types: supported_network_types(),
+        multicast_dns_mode: MulticastDnsMode::Disabled,
+        net: Some(Arc::clone(net)),
+        disconnected_timeout: Some(Duration::from_secs(10)),
+        keepalive_interval: Some(consent_check_interval),
+        check_interval: consent_check_interval,
+        ..Default::default()
+    };
+
+    let consent_agent = Arc::new(
+        Agent::new(AgentConfig {
+            consent_check_interval: Some(consent_check_interval),
+            consent_max_failures: Some(3),
+            ..agent_config(&net0)
+        })
+        .await?,
+    );
+    let remote_agent = Arc::new(Agent::new(agent_config(&net1)).await?);
+
+    let (state_changes_tx, mut state_changes_rx) = mpsc::channel::<ConnectionState>(100);
+    let state_changes_tx = Arc::new(state_changes_tx);
+    consent_agent.on_connection_state_change(Box::new(move |c: ConnectionState| {
+        let state_changes_tx_clone = Arc::clone(&state_changes_tx);
+        Box::pin(async move {
+            let _ = state_changes_tx_clone.try_send(c);
+        })
+    }));
+
+    let (consent_conn, _) = connect_with_vnet(&consent_agent, &remote_agent).await?;
+    block_until_state_seen(ConnectionState::Connected, &mut state_changes_rx).await;
+
+    // Answered checks keep refreshing consent
+    tokio::time::sleep(consent_check_interval * 10).await;
+    assert_eq!(
+        consent_agent
+            .internal
+            .connection_state
+            .load(Ordering::SeqCst),
+        ConnectionState::Connected as u8,
+        "answered consent checks should keep the agent connected"
+    );
+
+    // Drop all packets, and block until consent expired
+    drop_all_data.store(1, Ordering::SeqCst);
+    block_until_state_seen(ConnectionState::Failed, &mut state_changes_rx).await;
+    assert!(
+        consent_agent
+            .internal
+            .agent_conn
+            .get_selected_pair()
+            .is_none(),
+        "the selected pair should be dropped"
+    );
+
+    // Nothing is sent anymore, and the agent stays failed once the remote is reachable again
+    drop_all_data.store(0, Ordering::SeqCst);
+    assert_eq!(consent_conn.send(b"data").await?, 0);
+    tokio::time::sleep(consent_check_interval * 10).await;
+    assert_eq!(
+        consent_agent
+            .internal
+            .connection_state
+            .load(Ordering::SeqCst),
+        ConnectionState::Failed as u8,
+        "the agent should stay failed"
+    );
+    assert!(
+        state_changes_rx.try_recv().is_err(),
+        "no state change expected"
+    );
+
+    {
+        let mut w = wan.lock().await;
+        w.stop().await?;
+    }
+
+    consent_agent.close().await?;
+    remote_agent.close().await?;
+
+    Ok(())
+}
+
 //use std::io::Write;
 
 // Agent.Write should use the best valid pair if a selected pair is not yet available
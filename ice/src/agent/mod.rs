@@ -46,6 +46,7 @@ use crate::mdns::*;
 use crate::network_type::*;
 use crate::rand::*;
 use crate::state::*;
+use crate::tcp_mux::TCPMux;
 use crate::tcp_type::TcpType;
 use crate::udp_mux::UDPMux;
 use crate::udp_network::UDPNetwork;
@@ -95,7 +96,7 @@ pub type GatherCandidateCancelFn = Box<dyn Fn() + Send + Sync>;
 struct ChanReceivers {
     chan_state_rx: mpsc::Receiver<ConnectionState>,
     chan_candidate_rx: mpsc::Receiver<Option<Arc<dyn Candidate + Send + Sync>>>,
-    chan_candidate_pair_rx: mpsc::Receiver<()>,
+    chan_candidate_pair_rx: mpsc::Receiver<Arc<CandidatePair>>,
 }
 
 /// Represents the ICE agent.
@@ -103,6 +104,7 @@ pub struct Agent {
     pub(crate) internal: Arc<AgentInternal>,
 
     pub(crate) udp_network: UDPNetwork,
+    pub(crate) tcp_mux: Option<Arc<dyn TCPMux + Send + Sync>>,
     pub(crate) interface_filter: Arc<Option<InterfaceFilterFn>>,
     pub(crate) ip_filter: Arc<Option<IpFilterFn>>,
     pub(crate) mdns_mode: MulticastDnsMode,
@@ -167,6 +169,12 @@ impl Agent {
             return Err(Error::ErrLiteUsingNonHostCandidates);
         }
 
+        let network_types = if config.network_types.is_empty() {
+            supported_network_types()
+        } else {
+            config.network_types.clone()
+        };
+
         if !config.urls.is_empty()
             && !contains_candidate_type(CandidateType::ServerReflexive, &candidate_types)
             && !contains_candidate_type(CandidateType::Relay, &candidate_types)
@@ -198,6 +206,7 @@ impl Agent {
 
         let agent = Self {
             udp_network: config.udp_network,
+            tcp_mux: config.tcp_mux,
             internal: Arc::new(ai),
             interface_filter: Arc::clone(&config.interface_filter),
             ip_filter: Arc::clone(&config.ip_filter),
@@ -209,7 +218,7 @@ impl Agent {
             gathering_state: Arc::new(AtomicU8::new(0)), //GatheringState::New,
             candidate_types,
             urls: config.urls.clone(),
-            network_types: config.network_types.clone(),
+            network_types,
 
             gather_candidate_cancel: None, //TODO: add cancel
         };
@@ -347,6 +356,11 @@ impl Agent {
             udp_mux.remove_conn_by_ufrag(&ufrag).await;
         }
 
+        if let Some(tcp_mux) = &self.tcp_mux {
+            let (ufrag, _) = self.get_local_user_credentials().await;
+            tcp_mux.remove_conn_by_ufrag(&ufrag).await;
+        }
+
         //FIXME: deadlock here
         self.internal.close().await
     }
@@ -453,6 +467,7 @@ impl Agent {
 
         let params = GatherCandidatesInternalParams {
             udp_network: self.udp_network.clone(),
+            tcp_mux: self.tcp_mux.clone(),
             candidate_types: self.candidate_types.clone(),
             urls: self.urls.clone(),
             network_types: self.network_types.clone(),
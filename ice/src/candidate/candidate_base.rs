@@ -389,6 +389,8 @@ impl CandidateBase {
 
 /// Creates a Candidate from its string representation.
 pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
+    // Browsers hand out candidates with the attribute name still attached
+    let raw = raw.strip_prefix("candidate:").unwrap_or(raw);
     let split: Vec<&str> = raw.split_whitespace().collect();
     if split.len() < 8 {
         return Err(Error::Other(format!(
@@ -438,19 +440,23 @@ pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
 
             // RelatedPort
             rel_port = split2[3].parse()?;
-        } else if split2[0] == "tcptype" {
-            if split2.len() < 2 {
+        }
+
+        // tcptype may follow the related address, e.g. `raddr 0.0.0.0 rport 0 tcptype passive`
+        if let Some(i) = split2.iter().step_by(2).position(|s| *s == "tcptype") {
+            let i = i * 2;
+            if split2.len() < i + 2 {
                 return Err(Error::Other(format!(
                     "{:?}: incorrect length",
                     Error::ErrParseType
                 )));
             }
 
-            tcp_type = TcpType::from(split2[1]);
+            tcp_type = TcpType::from(split2[i + 1]);
         }
     }
 
-    match typ {
+    let mut candidate = match typ {
         "host" => {
             let config = CandidateHostConfig {
                 base_config: CandidateBaseConfig {
@@ -521,5 +527,9 @@ pub fn unmarshal_candidate(raw: &str) -> Result<impl Candidate> {
             Error::ErrUnknownCandidateType,
             typ
         ))),
-    }
+    }?;
+
+    // Only the host config takes a tcptype, but any TCP candidate may carry one
+    candidate.tcp_type = tcp_type;
+    Ok(candidate)
 }
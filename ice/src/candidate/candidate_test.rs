@@ -409,3 +409,32 @@ fn test_candidate_marshal() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_candidate_unmarshal_browser_format() -> Result<()> {
+    let c = unmarshal_candidate(
+        "candidate:1052353102 1 tcp 1518214911 191.228.238.68 9 typ srflx raddr 192.168.0.196 rport 0 tcptype passive generation 0 network-id 1",
+    )?;
+    assert_eq!(c.network_type(), NetworkType::Tcp4);
+    assert_eq!(c.candidate_type(), CandidateType::ServerReflexive);
+    assert_eq!(c.address(), "191.228.238.68");
+    assert_eq!(c.port(), 9);
+    assert_eq!(c.tcp_type(), TcpType::Passive);
+    assert_eq!(
+        c.related_address(),
+        Some(CandidateRelatedAddress {
+            address: "192.168.0.196".to_owned(),
+            port: 0,
+        })
+    );
+
+    let c = unmarshal_candidate(
+        "candidate:4273957277 1 udp 2130706431 10.0.75.1 53634 typ host generation 0 ufrag abcd",
+    )?;
+    assert_eq!(
+        c.marshal(),
+        "4273957277 1 udp 2130706431 10.0.75.1 53634 typ host"
+    );
+
+    Ok(())
+}
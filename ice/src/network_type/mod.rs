@@ -3,6 +3,7 @@ mod network_type_test;
 
 use std::fmt;
 use std::net::IpAddr;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
@@ -69,6 +70,21 @@ impl fmt::Display for NetworkType {
     }
 }
 
+impl FromStr for NetworkType {
+    type Err = Error;
+
+    /// Parses the short network description (e.g. `udp4`), case-insensitively.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "udp4" => Ok(Self::Udp4),
+            "udp6" => Ok(Self::Udp6),
+            "tcp4" => Ok(Self::Tcp4),
+            "tcp6" => Ok(Self::Tcp6),
+            _ => Err(Error::ErrDetermineNetworkType),
+        }
+    }
+}
+
 impl Default for NetworkType {
     fn default() -> Self {
         Self::Unspecified
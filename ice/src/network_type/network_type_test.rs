@@ -93,3 +93,24 @@ fn test_network_type_to_string() {
         assert_eq!(network_type.to_string(), expected_string);
     }
 }
+
+#[test]
+fn test_network_type_from_str() -> Result<()> {
+    let tests = vec![
+        ("udp4", NetworkType::Udp4),
+        ("UDP6", NetworkType::Udp6),
+        ("tcp4", NetworkType::Tcp4),
+        ("Tcp6", NetworkType::Tcp6),
+    ];
+
+    for (input, expected) in tests {
+        let actual: NetworkType = input.parse()?;
+        assert_eq!(actual, expected, "input:{input}");
+        assert_eq!(actual.to_string().parse::<NetworkType>()?, actual);
+    }
+
+    assert!("udp".parse::<NetworkType>().is_err());
+    assert!("unspecified".parse::<NetworkType>().is_err());
+
+    Ok(())
+}
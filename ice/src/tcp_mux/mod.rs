@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{watch, Mutex};
+use util::{Conn, Error};
+
+mod tcp_mux_conn;
+pub use tcp_mux_conn::TCPMuxConn;
+
+#[cfg(test)]
+mod tcp_mux_test;
+
+use stun::attributes::ATTR_USERNAME;
+use stun::message::{is_message as is_stun_message, Message as STUNMessage, BINDING_REQUEST};
+
+/// How long an accepted connection may take to send its first STUN binding request.
+const DEFAULT_FIRST_PACKET_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait before accepting again after the listener failed to accept a connection.
+const ACCEPT_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Reads a single packet framed as described in
+/// [RFC 4571](https://datatracker.ietf.org/doc/html/rfc4571#section-2):
+/// a 16 bit big-endian length followed by the packet itself.
+pub(crate) async fn read_streaming_packet<R>(reader: &mut R) -> io::Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut header = [0u8; 2];
+    reader.read_exact(&mut header).await?;
+
+    let mut packet = vec![0u8; u16::from_be_bytes(header) as usize];
+    reader.read_exact(&mut packet).await?;
+
+    Ok(packet)
+}
+
+/// Writes a single packet framed as described in
+/// [RFC 4571](https://datatracker.ietf.org/doc/html/rfc4571#section-2).
+pub(crate) async fn write_streaming_packet<W>(writer: &mut W, buf: &[u8]) -> io::Result<usize>
+where
+    W: AsyncWrite + Unpin,
+{
+    if buf.len() > u16::MAX as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("packet of {} bytes is too large to frame", buf.len()),
+        ));
+    }
+
+    let mut framed = Vec::with_capacity(2 + buf.len());
+    framed.extend_from_slice(&(buf.len() as u16).to_be_bytes());
+    framed.extend_from_slice(buf);
+    writer.write_all(&framed).await?;
+
+    Ok(buf.len())
+}
+
+#[async_trait]
+pub trait TCPMux {
+    /// Close the muxing.
+    async fn close(&self) -> Result<(), Error>;
+
+    /// Get the underlying connection for a given ufrag.
+    async fn get_conn_by_ufrag(
+        self: Arc<Self>,
+        ufrag: &str,
+    ) -> Result<Arc<dyn Conn + Send + Sync>, Error>;
+
+    /// Remove the underlying connection for a given ufrag.
+    async fn remove_conn_by_ufrag(&self, ufrag: &str);
+}
+
+pub struct TCPMuxParams {
+    listener: TcpListener,
+    first_packet_timeout: Duration,
+}
+
+impl TCPMuxParams {
+    pub fn new(listener: TcpListener) -> Self {
+        Self {
+            listener,
+            first_packet_timeout: DEFAULT_FIRST_PACKET_TIMEOUT,
+        }
+    }
+
+    /// Sets how long an accepted connection may take to send its first packet before it is
+    /// dropped. Defaults to 30 seconds.
+    pub fn with_first_packet_timeout(mut self, first_packet_timeout: Duration) -> Self {
+        self.first_packet_timeout = first_packet_timeout;
+        self
+    }
+}
+
+/// Accepts ICE TCP connections on a single listening port and routes them to the
+/// agent owning the ufrag found in the first STUN binding request of each connection.
+pub struct TCPMuxDefault {
+    /// The params this instance is configured with.
+    /// Contains the underlying TCP listener in use
+    params: TCPMuxParams,
+
+    /// Maps from ufrag to the underlying connection.
+    conns: Mutex<HashMap<String, TCPMuxConn>>,
+
+    // Close sender
+    closed_watch_tx: Mutex<Option<watch::Sender<()>>>,
+
+    /// Close receiver
+    closed_watch_rx: watch::Receiver<()>,
+}
+
+impl TCPMuxDefault {
+    pub fn new(params: TCPMuxParams) -> Arc<Self> {
+        let (closed_watch_tx, closed_watch_rx) = watch::channel(());
+
+        let mux = Arc::new(Self {
+            params,
+            conns: Mutex::default(),
+            closed_watch_tx: Mutex::new(Some(closed_watch_tx)),
+            closed_watch_rx: closed_watch_rx.clone(),
+        });
+
+        let cloned_mux = Arc::clone(&mux);
+        cloned_mux.start_accept_worker(closed_watch_rx);
+
+        mux
+    }
+
+    pub async fn is_closed(&self) -> bool {
+        self.closed_watch_tx.lock().await.is_none()
+    }
+
+    /// Returns the address the underlying listener is bound to.
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Ok(self.params.listener.local_addr()?)
+    }
+
+    fn start_accept_worker(self: Arc<Self>, mut closed_watch_rx: watch::Receiver<()>) {
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    res = self.params.listener.accept() => {
+                        match res {
+                            Ok((stream, addr)) => {
+                                let mux = Arc::clone(&self);
+                                tokio::spawn(async move {
+                                    mux.handle_stream(stream, addr).await;
+                                });
+                            }
+                            Err(err) => {
+                                // Errors such as a connection reset before it was accepted or
+                                // running out of file descriptors only affect this attempt.
+                                log::warn!("Could not accept tcp connection: {}", err);
+                                tokio::select! {
+                                    _ = tokio::time::sleep(ACCEPT_RETRY_INTERVAL) => {}
+                                    _ = closed_watch_rx.changed() => return,
+                                }
+                            }
+                        }
+                    }
+                    _ = closed_watch_rx.changed() => {
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Reads the first packet of an accepted stream, which must be a STUN binding request, and
+    /// hands the stream over to the connection registered for its ufrag.
+    async fn handle_stream(&self, stream: TcpStream, addr: SocketAddr) {
+        let (mut reader, writer) = stream.into_split();
+
+        let first_packet = tokio::time::timeout(
+            self.params.first_packet_timeout,
+            read_streaming_packet(&mut reader),
+        );
+        let packet = match first_packet.await {
+            Ok(Ok(packet)) => packet,
+            Ok(Err(err)) => {
+                log::warn!("Failed to read first packet from {}: {}", addr, err);
+                return;
+            }
+            Err(_) => {
+                log::warn!("Timed out waiting for the first packet from {}", addr);
+                return;
+            }
+        };
+
+        let ufrag = match ufrag_from_stun_message(&packet, &addr) {
+            Some(ufrag) => ufrag,
+            None => return,
+        };
+
+        let conn = {
+            let conns = self.conns.lock().await;
+            conns.get(&ufrag).cloned()
+        };
+
+        let conn = match conn {
+            Some(conn) => conn,
+            None => {
+                log::warn!("No connection for ufrag {} from {}", ufrag, addr);
+                return;
+            }
+        };
+
+        conn.add_stream(addr, writer);
+        if let Err(err) = conn.write_packet(packet, addr).await {
+            log::warn!("Failed to write packet: {}", err);
+            conn.remove_stream(&addr);
+            return;
+        }
+
+        Self::read_loop(&conn, &mut reader, addr).await;
+        conn.remove_stream(&addr);
+    }
+
+    async fn read_loop(conn: &TCPMuxConn, reader: &mut OwnedReadHalf, addr: SocketAddr) {
+        let mut close_rx = conn.close_rx();
+
+        loop {
+            tokio::select! {
+                res = read_streaming_packet(reader) => {
+                    let packet = match res {
+                        Ok(packet) => packet,
+                        Err(err) => {
+                            log::debug!("Closing tcp connection from {}: {}", addr, err);
+                            return;
+                        }
+                    };
+
+                    if let Err(err) = conn.write_packet(packet, addr).await {
+                        log::warn!("Failed to write packet: {}", err);
+                        return;
+                    }
+                }
+                _ = close_rx.changed() => {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Extracts the local ufrag from the USERNAME attribute of a STUN binding request.
+fn ufrag_from_stun_message(buffer: &[u8], addr: &SocketAddr) -> Option<String> {
+    if !is_stun_message(buffer) {
+        log::warn!("Not a STUN message from {}", addr);
+        return None;
+    }
+
+    let mut message = STUNMessage::new();
+    if let Err(err) = message.unmarshal_binary(buffer) {
+        log::warn!("Failed to handle decode ICE from {}: {}", addr, err);
+        return None;
+    }
+
+    if message.typ != BINDING_REQUEST {
+        log::warn!("Not a binding request from {}, got {}", addr, message.typ);
+        return None;
+    }
+
+    let (attr, found) = message.attributes.get(ATTR_USERNAME);
+    if !found {
+        log::warn!("No username attribute in STUN message from {}", addr);
+        return None;
+    }
+
+    match String::from_utf8(attr.value) {
+        Ok(s) => s.split(':').next().map(ToOwned::to_owned),
+        Err(err) => {
+            log::warn!(
+                "Failed to decode USERNAME from STUN message as UTF-8: {}",
+                err
+            );
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl TCPMux for TCPMuxDefault {
+    async fn close(&self) -> Result<(), Error> {
+        if self.is_closed().await {
+            return Err(Error::ErrAlreadyClosed);
+        }
+
+        let mut closed_tx = self.closed_watch_tx.lock().await;
+
+        if let Some(tx) = closed_tx.take() {
+            let _ = tx.send(());
+            drop(closed_tx);
+
+            let old_conns = {
+                let mut conns = self.conns.lock().await;
+
+                std::mem::take(&mut (*conns))
+            };
+
+            // NOTE: We don't wait for these closure to complete
+            for (_, conn) in old_conns {
+                conn.close();
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_conn_by_ufrag(
+        self: Arc<Self>,
+        ufrag: &str,
+    ) -> Result<Arc<dyn Conn + Send + Sync>, Error> {
+        if self.is_closed().await {
+            return Err(Error::ErrUseClosedNetworkConn);
+        }
+
+        let mut conns = self.conns.lock().await;
+        if let Some(conn) = conns.get(ufrag) {
+            return Ok(Arc::new(conn.clone()) as Arc<dyn Conn + Send + Sync>);
+        }
+
+        let muxed_conn = TCPMuxConn::new(self.local_addr()?, ufrag.into());
+        let mut close_rx = muxed_conn.close_rx();
+        let cloned_self = Arc::clone(&self);
+        let cloned_ufrag = ufrag.to_string();
+        tokio::spawn(async move {
+            let _ = close_rx.changed().await;
+
+            cloned_self.remove_conn_by_ufrag(&cloned_ufrag).await;
+        });
+
+        conns.insert(ufrag.into(), muxed_conn.clone());
+
+        Ok(Arc::new(muxed_conn) as Arc<dyn Conn + Send + Sync>)
+    }
+
+    async fn remove_conn_by_ufrag(&self, ufrag: &str) {
+        let removed_conn = {
+            let mut conns = self.conns.lock().await;
+            conns.remove(ufrag)
+        };
+
+        if let Some(conn) = removed_conn {
+            conn.close();
+        }
+    }
+}
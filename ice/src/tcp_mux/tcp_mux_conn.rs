@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::sync::{mpsc, watch};
+use util::sync::Mutex;
+use util::{Conn, Error};
+
+use super::write_streaming_packet;
+
+/// The number of packets buffered per connection before reads from the TCP streams stall.
+const PACKET_CHANNEL_CAPACITY: usize = 128;
+
+type ConnResult<T> = Result<T, util::Error>;
+
+/// A TCP mux connection. It exposes all the TCP streams accepted for a single ufrag as one
+/// packet oriented [`Conn`], keyed by the remote address of each stream.
+#[derive(Clone)]
+pub struct TCPMuxConn {
+    /// Close Receiver. A copy of this can be obtained via [`close_rx`].
+    closed_watch_rx: watch::Receiver<bool>,
+
+    inner: Arc<TCPMuxConnInner>,
+}
+
+impl TCPMuxConn {
+    /// Creates a new [`TCPMuxConn`].
+    pub fn new(local_addr: SocketAddr, key: String) -> Self {
+        let (closed_watch_tx, closed_watch_rx) = watch::channel(false);
+        let (packets_tx, packets_rx) = mpsc::channel(PACKET_CHANNEL_CAPACITY);
+
+        Self {
+            closed_watch_rx,
+            inner: Arc::new(TCPMuxConnInner {
+                local_addr,
+                key,
+                closed_watch_tx: Mutex::new(Some(closed_watch_tx)),
+                streams: Default::default(),
+                packets_tx,
+                packets_rx: tokio::sync::Mutex::new(packets_rx),
+            }),
+        }
+    }
+
+    /// Returns a key identifying this connection.
+    pub fn key(&self) -> &str {
+        &self.inner.key
+    }
+
+    /// Queues a packet received from the given address, to be returned by `recv_from`.
+    pub async fn write_packet(&self, packet: Vec<u8>, addr: SocketAddr) -> ConnResult<()> {
+        if self.is_closed() {
+            return Err(Error::ErrUseClosedNetworkConn);
+        }
+
+        self.inner
+            .packets_tx
+            .send((packet, addr))
+            .await
+            .map_err(|_| Error::ErrUseClosedNetworkConn)
+    }
+
+    /// Returns true if this connection is closed.
+    pub fn is_closed(&self) -> bool {
+        self.inner.closed_watch_tx.lock().is_none()
+    }
+
+    /// Gets a copy of the close [`tokio::sync::watch::Receiver`] that fires when this
+    /// connection is closed.
+    pub fn close_rx(&self) -> watch::Receiver<bool> {
+        self.closed_watch_rx.clone()
+    }
+
+    /// Closes this connection and all the TCP streams associated with it.
+    pub fn close(&self) {
+        let mut closed_tx = self.inner.closed_watch_tx.lock();
+
+        if let Some(tx) = closed_tx.take() {
+            let _ = tx.send(true);
+            drop(closed_tx);
+
+            // Dropping the write halves shuts the streams down.
+            let mut streams = self.inner.streams.lock();
+            streams.clear();
+        }
+    }
+
+    /// Gets the list of the remote addresses with an open TCP stream.
+    pub fn get_addresses(&self) -> Vec<SocketAddr> {
+        let streams = self.inner.streams.lock();
+
+        streams.keys().copied().collect()
+    }
+
+    /// Registers the write half of a TCP stream accepted from `addr`.
+    pub(super) fn add_stream(&self, addr: SocketAddr, writer: OwnedWriteHalf) {
+        if self.is_closed() {
+            return;
+        }
+
+        let mut streams = self.inner.streams.lock();
+        streams.insert(addr, Arc::new(tokio::sync::Mutex::new(writer)));
+    }
+
+    /// Deregisters the TCP stream accepted from `addr`.
+    pub(super) fn remove_stream(&self, addr: &SocketAddr) {
+        let mut streams = self.inner.streams.lock();
+        streams.remove(addr);
+    }
+}
+
+struct TCPMuxConnInner {
+    local_addr: SocketAddr,
+
+    /// Static key identifying the connection.
+    key: String,
+
+    /// Close Sender. We'll send a value on this channel when we close
+    closed_watch_tx: Mutex<Option<watch::Sender<bool>>>,
+
+    /// Write halves of the accepted TCP streams, keyed by remote address.
+    streams: Mutex<HashMap<SocketAddr, Arc<tokio::sync::Mutex<OwnedWriteHalf>>>>,
+
+    packets_tx: mpsc::Sender<(Vec<u8>, SocketAddr)>,
+    packets_rx: tokio::sync::Mutex<mpsc::Receiver<(Vec<u8>, SocketAddr)>>,
+}
+
+#[async_trait]
+impl Conn for TCPMuxConn {
+    async fn connect(&self, _addr: SocketAddr) -> ConnResult<()> {
+        Err(io::Error::other("Not applicable").into())
+    }
+
+    async fn recv(&self, _buf: &mut [u8]) -> ConnResult<usize> {
+        Err(io::Error::other("Not applicable").into())
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> ConnResult<(usize, SocketAddr)> {
+        let mut closed_watch_rx = self.closed_watch_rx.clone();
+        if *closed_watch_rx.borrow() {
+            return Err(Error::ErrUseClosedNetworkConn);
+        }
+
+        let mut packets_rx = self.inner.packets_rx.lock().await;
+        tokio::select! {
+            packet = packets_rx.recv() => {
+                match packet {
+                    Some((packet, addr)) => {
+                        if packet.len() > buf.len() {
+                            return Err(Error::ErrBufferShort);
+                        }
+
+                        buf[..packet.len()].copy_from_slice(&packet);
+                        Ok((packet.len(), addr))
+                    }
+                    None => Err(Error::ErrUseClosedNetworkConn),
+                }
+            }
+            _ = closed_watch_rx.changed() => Err(Error::ErrUseClosedNetworkConn),
+        }
+    }
+
+    async fn send(&self, _buf: &[u8]) -> ConnResult<usize> {
+        Err(io::Error::other("Not applicable").into())
+    }
+
+    async fn send_to(&self, buf: &[u8], target: SocketAddr) -> ConnResult<usize> {
+        let stream = {
+            let streams = self.inner.streams.lock();
+            streams.get(&target).cloned()
+        };
+
+        match stream {
+            Some(stream) => {
+                let mut writer = stream.lock().await;
+                Ok(write_streaming_packet(&mut *writer, buf).await?)
+            }
+            None => Err(Error::Other(format!(
+                "wanted to send {} bytes to {}, but there is no tcp connection to it",
+                buf.len(),
+                target
+            ))),
+        }
+    }
+
+    fn local_addr(&self) -> ConnResult<SocketAddr> {
+        Ok(self.inner.local_addr)
+    }
+
+    fn remote_addr(&self) -> Option<SocketAddr> {
+        None
+    }
+
+    async fn close(&self) -> ConnResult<()> {
+        TCPMuxConn::close(self);
+
+        Ok(())
+    }
+
+    fn as_any(&self) -> &(dyn std::any::Any + Send + Sync) {
+        self
+    }
+}
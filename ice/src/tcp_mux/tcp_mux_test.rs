@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use stun::message::{Message, BINDING_REQUEST};
+use tokio::time::timeout;
+
+use super::*;
+use crate::error::Result;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn binding_request(ufrag: &str) -> Vec<u8> {
+    let mut m = Message {
+        typ: BINDING_REQUEST,
+        ..Message::default()
+    };
+
+    m.add(ATTR_USERNAME, format!("{ufrag}:otherufrag").as_bytes());
+    m.write_header();
+
+    m.marshal_binary().unwrap()
+}
+
+#[tokio::test]
+async fn test_streaming_packet_framing() -> Result<()> {
+    let (mut client, mut server) = tokio::io::duplex(1024);
+
+    let n = write_streaming_packet(&mut client, b"hello").await?;
+    assert_eq!(n, 5);
+    write_streaming_packet(&mut client, b"").await?;
+
+    assert_eq!(read_streaming_packet(&mut server).await?, b"hello");
+    assert!(read_streaming_packet(&mut server).await?.is_empty());
+
+    let too_large = vec![0u8; u16::MAX as usize + 1];
+    assert!(write_streaming_packet(&mut client, &too_large)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tcp_mux() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let listener_addr = listener.local_addr()?;
+
+    let tcp_mux = TCPMuxDefault::new(TCPMuxParams::new(listener));
+    let tcp_mux_dyn = Arc::clone(&tcp_mux) as Arc<dyn TCPMux + Send + Sync>;
+
+    let conn = Arc::clone(&tcp_mux_dyn).get_conn_by_ufrag("ufrag1").await?;
+    assert_eq!(conn.local_addr()?, listener_addr);
+
+    let mut remote = TcpStream::connect(listener_addr).await?;
+    let remote_addr = remote.local_addr()?;
+
+    let stun_msg = binding_request("ufrag1");
+    write_streaming_packet(&mut remote, &stun_msg).await?;
+
+    let mut buffer = vec![0u8; 1500];
+    let (n, addr) = timeout(TIMEOUT, conn.recv_from(&mut buffer))
+        .await
+        .expect("recv_from timed out")?;
+    assert_eq!(&buffer[..n], &stun_msg[..]);
+    assert_eq!(addr, remote_addr);
+
+    // Subsequent packets on the same stream are not required to be STUN.
+    write_streaming_packet(&mut remote, b"media").await?;
+    let (n, addr) = timeout(TIMEOUT, conn.recv_from(&mut buffer))
+        .await
+        .expect("recv_from timed out")?;
+    assert_eq!(&buffer[..n], b"media");
+    assert_eq!(addr, remote_addr);
+
+    conn.send_to(b"response", remote_addr).await?;
+    let response = timeout(TIMEOUT, read_streaming_packet(&mut remote))
+        .await
+        .expect("read timed out")?;
+    assert_eq!(response, b"response");
+
+    tcp_mux.close().await?;
+    assert!(
+        Arc::clone(&tcp_mux_dyn)
+            .get_conn_by_ufrag("failurefrag")
+            .await
+            .is_err(),
+        "Getting connections after TCPMuxDefault is closed should fail"
+    );
+    assert!(conn.recv_from(&mut buffer).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tcp_mux_unknown_ufrag() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let listener_addr = listener.local_addr()?;
+
+    let tcp_mux = TCPMuxDefault::new(TCPMuxParams::new(listener));
+
+    let mut remote = TcpStream::connect(listener_addr).await?;
+    write_streaming_packet(&mut remote, &binding_request("unknown")).await?;
+
+    // The mux drops streams it can't route, so the remote observes EOF.
+    let res = timeout(TIMEOUT, read_streaming_packet(&mut remote))
+        .await
+        .expect("read timed out");
+    assert!(res.is_err());
+
+    tcp_mux.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tcp_mux_first_packet_timeout() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let listener_addr = listener.local_addr()?;
+
+    let tcp_mux = TCPMuxDefault::new(
+        TCPMuxParams::new(listener).with_first_packet_timeout(Duration::from_millis(100)),
+    );
+
+    // A stream that never sends its binding request is dropped after the timeout.
+    let mut remote = TcpStream::connect(listener_addr).await?;
+    let res = timeout(TIMEOUT, read_streaming_packet(&mut remote))
+        .await
+        .expect("read timed out");
+    assert!(res.is_err());
+
+    tcp_mux.close().await?;
+
+    Ok(())
+}
@@ -181,11 +181,14 @@ impl UDPMuxDefault {
                                     address_map.get(&addr).cloned()
                                 };
 
+                                // Only look at the bytes of this datagram, the tail of `buffer` may
+                                // still hold data from a previous, longer packet.
+                                let packet = &buffer[..len];
                                 let conn = match conn {
                                     // If we couldn't find the connection based on source address, see if
                                     // this is a STUN message and if so if we can find the connection based on ufrag.
-                                    None if is_stun_message(&buffer) => {
-                                        loop_self.conn_from_stun_message(&buffer, &addr).await
+                                    None if is_stun_message(packet) => {
+                                        loop_self.conn_from_stun_message(packet, &addr).await
                                     }
                                     s @ Some(_) => s,
                                     _ => None,
@@ -196,7 +199,7 @@ impl UDPMuxDefault {
                                         log::trace!("Dropping packet from {}", &addr);
                                     }
                                     Some(conn) => {
-                                        if let Err(err) = conn.write_packet(&buffer[..len], addr).await {
+                                        if let Err(err) = conn.write_packet(packet, addr).await {
                                             log::error!("Failed to write packet: {}", err);
                                         }
                                     }
@@ -149,6 +149,48 @@ async fn test_udp_mux() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_udp_mux_ignores_stale_bytes() -> Result<()> {
+    let udp_socket = UdpSocket::bind("127.0.0.1:0").await?;
+    let addr = udp_socket.local_addr()?;
+    let udp_mux = UDPMuxDefault::new(UDPMuxParams::new(udp_socket));
+    let conn = udp_mux.get_conn("ufrag1").await?;
+
+    let stun_msg = {
+        let mut m = Message {
+            typ: BINDING_REQUEST,
+            ..Message::default()
+        };
+
+        m.add(ATTR_USERNAME, "ufrag1:otherufrag".as_bytes());
+        m.write_header();
+
+        m.marshal_binary().unwrap()
+    };
+
+    let remote_connection_1 = UdpSocket::bind("127.0.0.1:0").await?;
+    remote_connection_1.send_to(&stun_msg, addr).await?;
+
+    let mut buffer = vec![0u8; RECEIVE_MTU];
+    let (n, _) = timeout(TIMEOUT, conn.recv_from(&mut buffer))
+        .await
+        .expect("STUN message should be delivered")
+        .expect("recv_from should not error");
+    assert_eq!(buffer[..n], stun_msg);
+
+    // The start of the same STUN message, which is not a STUN message on its own. The mux
+    // must not complete it with the rest of the previous datagram left in its buffer.
+    let remote_connection_2 = UdpSocket::bind("127.0.0.1:0").await?;
+    remote_connection_2.send_to(&stun_msg[..4], addr).await?;
+
+    let result = timeout(Duration::from_millis(200), conn.recv_from(&mut buffer)).await;
+    assert!(result.is_err(), "truncated packet should be dropped");
+
+    conn.close().await?;
+
+    Ok(())
+}
+
 async fn test_mux_connection(
     mux: Arc<dyn UDPMux + Send + Sync>,
     ufrag: &str,
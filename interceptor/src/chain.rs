@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod chain_test;
+
 use std::sync::Arc;
 
 use crate::error::*;
@@ -5,6 +8,9 @@ use crate::stream_info::StreamInfo;
 use crate::*;
 
 /// Chain is an interceptor that runs all child interceptors in order.
+/// Each child wraps the readers and writers returned by the previous ones, so incoming packets
+/// pass through the children in order, while outgoing packets pass through them in reverse
+/// order.
 #[derive(Default)]
 pub struct Chain {
     interceptors: Vec<Arc<dyn Interceptor + Send + Sync>>,
@@ -0,0 +1,147 @@
+use bytes::{BufMut, Bytes, BytesMut};
+
+use super::*;
+use crate::mock::mock_builder::MockBuilder;
+use crate::mock::mock_interceptor::MockInterceptor;
+use crate::mock::mock_stream::MockStream;
+use crate::registry::Registry;
+
+/// AppendRTPWriter appends its id to the payload of every written packet
+struct AppendRTPWriter {
+    id: u8,
+    next_rtp_writer: Arc<dyn RTPWriter + Send + Sync>,
+}
+
+#[async_trait]
+impl RTPWriter for AppendRTPWriter {
+    async fn write(&self, pkt: &rtp::packet::Packet, a: &Attributes) -> Result<usize> {
+        let mut pkt = pkt.clone();
+        let mut payload = BytesMut::from(&pkt.payload[..]);
+        payload.put_u8(self.id);
+        pkt.payload = payload.freeze();
+
+        self.next_rtp_writer.write(&pkt, a).await
+    }
+}
+
+/// AppendRTPReader appends its id to the payload of every read packet
+struct AppendRTPReader {
+    id: u8,
+    parent_rtp_reader: Arc<dyn RTPReader + Send + Sync>,
+}
+
+#[async_trait]
+impl RTPReader for AppendRTPReader {
+    async fn read(
+        &self,
+        buf: &mut [u8],
+        a: &Attributes,
+    ) -> Result<(rtp::packet::Packet, Attributes)> {
+        let (mut pkt, attr) = self.parent_rtp_reader.read(buf, a).await?;
+        let mut payload = BytesMut::from(&pkt.payload[..]);
+        payload.put_u8(self.id);
+        pkt.payload = payload.freeze();
+
+        Ok((pkt, attr))
+    }
+}
+
+fn append_interceptor(id: u8) -> Arc<dyn Interceptor + Send + Sync> {
+    Arc::new(MockInterceptor {
+        bind_local_stream_fn: Some(Box::new(
+            move |_: &StreamInfo,
+                  writer: Arc<dyn RTPWriter + Send + Sync>|
+                  -> Pin<
+                Box<dyn Future<Output = Arc<dyn RTPWriter + Send + Sync>> + Send + Sync>,
+            > {
+                Box::pin(async move {
+                    Arc::new(AppendRTPWriter {
+                        id,
+                        next_rtp_writer: writer,
+                    }) as Arc<dyn RTPWriter + Send + Sync>
+                })
+            },
+        )),
+        bind_remote_stream_fn: Some(Box::new(
+            move |_: &StreamInfo,
+                  reader: Arc<dyn RTPReader + Send + Sync>|
+                  -> Pin<
+                Box<dyn Future<Output = Arc<dyn RTPReader + Send + Sync>> + Send + Sync>,
+            > {
+                Box::pin(async move {
+                    Arc::new(AppendRTPReader {
+                        id,
+                        parent_rtp_reader: reader,
+                    }) as Arc<dyn RTPReader + Send + Sync>
+                })
+            },
+        )),
+        close_fn: Some(Box::new(
+            move || -> Pin<Box<dyn Future<Output = Result<()>> + Send + Sync>> {
+                Box::pin(async move { Err(Error::Other(format!("close {id}"))) })
+            },
+        )),
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn test_chain_order() -> Result<()> {
+    let mut registry = Registry::new();
+    for id in [1, 2] {
+        registry.add(Box::new(MockBuilder::new(move |_: &str| {
+            Ok(append_interceptor(id))
+        })));
+    }
+    let chain = registry.build("")?;
+
+    let stream = MockStream::new(&StreamInfo::default(), Arc::clone(&chain)).await;
+
+    // The writer of the last added interceptor is called first
+    stream
+        .write_rtp(&rtp::packet::Packet {
+            payload: Bytes::from_static(&[0]),
+            ..Default::default()
+        })
+        .await?;
+    let pkt = stream.written_rtp().await.unwrap();
+    assert_eq!(&pkt.payload[..], &[0, 2, 1]);
+
+    // The reader of the first added interceptor is called first
+    stream
+        .receive_rtp(rtp::packet::Packet {
+            payload: Bytes::from_static(&[0]),
+            ..Default::default()
+        })
+        .await;
+    let pkt = stream.read_rtp().await.unwrap()?;
+    assert_eq!(&pkt.payload[..], &[0, 1, 2]);
+
+    // Every interceptor is closed, even if closing the previous one failed
+    assert_eq!(
+        chain.close().await,
+        Err(Error::Other("close 1\nclose 2".to_owned()))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_registry_without_builders() -> Result<()> {
+    let registry = Registry::new();
+    let chain = registry.build("")?;
+
+    let stream = MockStream::new(&StreamInfo::default(), chain).await;
+    stream
+        .write_rtp(&rtp::packet::Packet {
+            payload: Bytes::from_static(&[0]),
+            ..Default::default()
+        })
+        .await?;
+    let pkt = stream.written_rtp().await.unwrap();
+    assert_eq!(&pkt.payload[..], &[0]);
+
+    stream.close().await?;
+
+    Ok(())
+}
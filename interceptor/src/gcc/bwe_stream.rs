@@ -0,0 +1,43 @@
+use super::*;
+
+pub(super) struct BweStream {
+    next_rtp_writer: Arc<dyn RTPWriter + Send + Sync>,
+    internal: Arc<SendSideBweInternal>,
+    hdr_ext_id: u8,
+}
+
+impl BweStream {
+    pub(super) fn new(
+        next_rtp_writer: Arc<dyn RTPWriter + Send + Sync>,
+        internal: Arc<SendSideBweInternal>,
+        hdr_ext_id: u8,
+    ) -> Self {
+        BweStream {
+            next_rtp_writer,
+            internal,
+            hdr_ext_id,
+        }
+    }
+}
+
+/// RTPWriter is used by Interceptor.bind_local_stream.
+#[async_trait]
+impl RTPWriter for BweStream {
+    /// write a rtp packet
+    async fn write(&self, pkt: &rtp::packet::Packet, a: &Attributes) -> Result<usize> {
+        if let Some(mut ext) = pkt.header.get_extension(self.hdr_ext_id) {
+            let tcc_ext = TransportCcExtension::unmarshal(&mut ext)?;
+
+            let mut estimator = self.internal.estimator.lock().await;
+            estimator.on_sent(SentPacket {
+                sequence_number: tcc_ext.transport_sequence,
+                send_time: (tokio::time::Instant::now() - self.internal.start_time).as_micros()
+                    as i64,
+                size: pkt.marshal_size(),
+                probe_cluster_id: a.get(&PROBE_CLUSTER_ID_ATTRIBUTE).copied(),
+            });
+        }
+
+        self.next_rtp_writer.write(pkt, a).await
+    }
+}
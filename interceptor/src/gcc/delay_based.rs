@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+
+/// Packets sent within 5ms of the first packet of a group belong to the same group, as the
+/// pacer sends them in a burst.
+const SEND_TIME_GROUP_LENGTH: i64 = 5_000;
+
+const TRENDLINE_WINDOW_SIZE: usize = 20;
+const TRENDLINE_SMOOTHING: f64 = 0.9;
+const TRENDLINE_THRESHOLD_GAIN: f64 = 4.0;
+const MAX_NUM_DELTAS: usize = 60;
+const MAX_DELTAS_COUNTED: usize = 1000;
+
+/// Overuse is signaled once the delay grew for 10ms
+const OVERUSE_TIME_THRESHOLD: f64 = 10.0;
+const INITIAL_THRESHOLD: f64 = 12.5;
+const MIN_THRESHOLD: f64 = 6.0;
+const MAX_THRESHOLD: f64 = 600.0;
+const THRESHOLD_GAIN_UP: f64 = 0.0087;
+const THRESHOLD_GAIN_DOWN: f64 = 0.039;
+const MAX_ADAPT_OFFSET: f64 = 15.0;
+const MAX_THRESHOLD_TIME_DELTA: f64 = 100.0;
+
+/// BandwidthUsage is the state of the network path as detected from the delay variation
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BandwidthUsage {
+    #[default]
+    Normal,
+    Underusing,
+    Overusing,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct PacketGroup {
+    first_send_time: i64,
+    last_send_time: i64,
+    last_arrival_time: i64,
+}
+
+impl PacketGroup {
+    fn new(send_time: i64, arrival_time: i64) -> Self {
+        PacketGroup {
+            first_send_time: send_time,
+            last_send_time: send_time,
+            last_arrival_time: arrival_time,
+        }
+    }
+}
+
+/// GroupDelta is the difference between two consecutive packet groups, in us
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct GroupDelta {
+    pub(crate) send_delta: i64,
+    pub(crate) arrival_delta: i64,
+    /// Arrival time of the last packet of the newer group
+    pub(crate) arrival_time: i64,
+}
+
+/// InterArrival groups received packets by send time and computes the deltas between
+/// consecutive groups.
+#[derive(Debug, Default)]
+pub(crate) struct InterArrival {
+    current: Option<PacketGroup>,
+    previous: Option<PacketGroup>,
+}
+
+impl InterArrival {
+    /// compute_deltas adds a received packet and returns the deltas between the two previous
+    /// groups once it starts a new group.
+    pub(crate) fn compute_deltas(
+        &mut self,
+        send_time: i64,
+        arrival_time: i64,
+    ) -> Option<GroupDelta> {
+        if self.current.is_none() {
+            self.current = Some(PacketGroup::new(send_time, arrival_time));
+            return None;
+        }
+        let current = self.current.as_mut()?;
+
+        if send_time < current.first_send_time {
+            // Reordered packet of an older group
+            return None;
+        }
+
+        if send_time - current.first_send_time <= SEND_TIME_GROUP_LENGTH {
+            current.last_send_time = current.last_send_time.max(send_time);
+            current.last_arrival_time = current.last_arrival_time.max(arrival_time);
+            return None;
+        }
+
+        let deltas = self.previous.map(|previous| GroupDelta {
+            send_delta: current.last_send_time - previous.last_send_time,
+            arrival_delta: current.last_arrival_time - previous.last_arrival_time,
+            arrival_time: current.last_arrival_time,
+        });
+        self.previous = Some(*current);
+        *current = PacketGroup::new(send_time, arrival_time);
+
+        deltas
+    }
+}
+
+/// TrendlineEstimator estimates the trend of the one way delay variation with a linear
+/// regression over the last group deltas, and detects overuse by comparing it with an
+/// adaptive threshold.
+/// Reference: <https://datatracker.ietf.org/doc/html/draft-ietf-rmcat-gcc-02#section-5.4>
+#[derive(Debug)]
+pub(crate) struct TrendlineEstimator {
+    num_of_deltas: usize,
+    first_arrival_time: Option<i64>,
+    accumulated_delay: f64,
+    smoothed_delay: f64,
+    /// (arrival time, smoothed accumulated delay) in ms
+    delay_history: VecDeque<(f64, f64)>,
+
+    threshold: f64,
+    last_threshold_update: Option<i64>,
+    prev_trend: f64,
+    time_over_using: Option<f64>,
+    overuse_counter: usize,
+    state: BandwidthUsage,
+}
+
+impl Default for TrendlineEstimator {
+    fn default() -> Self {
+        TrendlineEstimator {
+            num_of_deltas: 0,
+            first_arrival_time: None,
+            accumulated_delay: 0.0,
+            smoothed_delay: 0.0,
+            delay_history: VecDeque::with_capacity(TRENDLINE_WINDOW_SIZE + 1),
+
+            threshold: INITIAL_THRESHOLD,
+            last_threshold_update: None,
+            prev_trend: 0.0,
+            time_over_using: None,
+            overuse_counter: 0,
+            state: BandwidthUsage::Normal,
+        }
+    }
+}
+
+impl TrendlineEstimator {
+    pub(crate) fn state(&self) -> BandwidthUsage {
+        self.state
+    }
+
+    pub(crate) fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// update adds the deltas of a packet group and returns the detected bandwidth usage.
+    pub(crate) fn update(&mut self, delta: &GroupDelta) -> BandwidthUsage {
+        let send_delta_ms = delta.send_delta as f64 / 1000.0;
+        let delay_ms = (delta.arrival_delta - delta.send_delta) as f64 / 1000.0;
+
+        self.num_of_deltas = (self.num_of_deltas + 1).min(MAX_DELTAS_COUNTED);
+        let first_arrival_time = *self.first_arrival_time.get_or_insert(delta.arrival_time);
+
+        self.accumulated_delay += delay_ms;
+        self.smoothed_delay = TRENDLINE_SMOOTHING * self.smoothed_delay
+            + (1.0 - TRENDLINE_SMOOTHING) * self.accumulated_delay;
+
+        self.delay_history.push_back((
+            (delta.arrival_time - first_arrival_time) as f64 / 1000.0,
+            self.smoothed_delay,
+        ));
+        if self.delay_history.len() > TRENDLINE_WINDOW_SIZE {
+            self.delay_history.pop_front();
+        }
+
+        if self.delay_history.len() == TRENDLINE_WINDOW_SIZE {
+            if let Some(trend) = linear_fit_slope(&self.delay_history) {
+                self.detect(trend, send_delta_ms, delta.arrival_time);
+            }
+        }
+
+        self.state
+    }
+
+    fn detect(&mut self, trend: f64, send_delta_ms: f64, now: i64) {
+        if self.num_of_deltas < 2 {
+            self.state = BandwidthUsage::Normal;
+            return;
+        }
+
+        let modified_trend =
+            self.num_of_deltas.min(MAX_NUM_DELTAS) as f64 * trend * TRENDLINE_THRESHOLD_GAIN;
+        if modified_trend > self.threshold {
+            let time_over_using = match self.time_over_using {
+                // Assume the overuse started in the middle of the interval
+                None => send_delta_ms / 2.0,
+                Some(time_over_using) => time_over_using + send_delta_ms,
+            };
+            self.time_over_using = Some(time_over_using);
+            self.overuse_counter += 1;
+
+            if time_over_using > OVERUSE_TIME_THRESHOLD
+                && self.overuse_counter > 1
+                && trend >= self.prev_trend
+            {
+                self.time_over_using = Some(0.0);
+                self.overuse_counter = 0;
+                self.state = BandwidthUsage::Overusing;
+            }
+        } else if modified_trend < -self.threshold {
+            self.time_over_using = None;
+            self.overuse_counter = 0;
+            self.state = BandwidthUsage::Underusing;
+        } else {
+            self.time_over_using = None;
+            self.overuse_counter = 0;
+            self.state = BandwidthUsage::Normal;
+        }
+        self.prev_trend = trend;
+
+        self.update_threshold(modified_trend, now);
+    }
+
+    fn update_threshold(&mut self, modified_trend: f64, now: i64) {
+        let last_update = *self.last_threshold_update.get_or_insert(now);
+        self.last_threshold_update = Some(now);
+
+        // Don't adapt to spikes, e.g. caused by a route change
+        if modified_trend.abs() > self.threshold + MAX_ADAPT_OFFSET {
+            return;
+        }
+
+        let gain = if modified_trend.abs() < self.threshold {
+            THRESHOLD_GAIN_DOWN
+        } else {
+            THRESHOLD_GAIN_UP
+        };
+        let time_delta_ms = ((now - last_update) as f64 / 1000.0).min(MAX_THRESHOLD_TIME_DELTA);
+        self.threshold += gain * (modified_trend.abs() - self.threshold) * time_delta_ms;
+        self.threshold = self.threshold.clamp(MIN_THRESHOLD, MAX_THRESHOLD);
+    }
+}
+
+/// linear_fit_slope returns the slope of the least squares line through points
+fn linear_fit_slope(points: &VecDeque<(f64, f64)>) -> Option<f64> {
+    let n = points.len() as f64;
+    let (sum_x, sum_y) = points
+        .iter()
+        .fold((0.0, 0.0), |(sum_x, sum_y), (x, y)| (sum_x + x, sum_y + y));
+    let (x_avg, y_avg) = (sum_x / n, sum_y / n);
+
+    let (mut numerator, mut denominator) = (0.0, 0.0);
+    for (x, y) in points {
+        numerator += (x - x_avg) * (y - y_avg);
+        denominator += (x - x_avg) * (x - x_avg);
+    }
+
+    if denominator == 0.0 {
+        None
+    } else {
+        Some(numerator / denominator)
+    }
+}
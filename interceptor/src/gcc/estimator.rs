@@ -0,0 +1,103 @@
+use rtcp::transport_feedbacks::transport_layer_cc::TransportLayerCc;
+
+use super::delay_based::{BandwidthUsage, InterArrival, TrendlineEstimator};
+use super::feedback_adapter::{FeedbackAdapter, SentPacket};
+use super::loss_based::LossBasedBwe;
+use super::probe::ProbeBitrateEstimator;
+use super::rate_controller::{AckedBitrateEstimator, AimdRateController};
+
+/// SendSideEstimator combines the delay based and loss based controllers into the target
+/// bitrate of the sender. Times are in us on the local clock, except for arrival times, which
+/// are on the clock of the remote peer.
+#[derive(Debug)]
+pub(crate) struct SendSideEstimator {
+    feedback_adapter: FeedbackAdapter,
+    inter_arrival: InterArrival,
+    trendline: TrendlineEstimator,
+    acked_bitrate: AckedBitrateEstimator,
+    rate_controller: AimdRateController,
+    loss_based: LossBasedBwe,
+    probe: ProbeBitrateEstimator,
+
+    target_bitrate: u64,
+}
+
+impl SendSideEstimator {
+    pub(crate) fn new(initial_bitrate: u64, min_bitrate: u64, max_bitrate: u64) -> Self {
+        SendSideEstimator {
+            feedback_adapter: FeedbackAdapter::default(),
+            inter_arrival: InterArrival::default(),
+            trendline: TrendlineEstimator::default(),
+            acked_bitrate: AckedBitrateEstimator::default(),
+            rate_controller: AimdRateController::new(initial_bitrate, min_bitrate, max_bitrate),
+            loss_based: LossBasedBwe::new(initial_bitrate, min_bitrate, max_bitrate),
+            probe: ProbeBitrateEstimator::default(),
+
+            target_bitrate: initial_bitrate.clamp(min_bitrate, max_bitrate),
+        }
+    }
+
+    pub(crate) fn target_bitrate(&self) -> u64 {
+        self.target_bitrate
+    }
+
+    pub(crate) fn on_sent(&mut self, packet: SentPacket) {
+        self.feedback_adapter.on_sent(packet);
+    }
+
+    /// on_feedback updates the estimate with feedback received at now and returns the new
+    /// target bitrate in bps.
+    pub(crate) fn on_feedback(&mut self, feedback: &TransportLayerCc, now: i64) -> u64 {
+        let results = self.feedback_adapter.on_feedback(feedback);
+        if results.is_empty() {
+            return self.target_bitrate;
+        }
+
+        let mut usage = self.trendline.state();
+        let mut probe_estimate = None;
+        let (mut received, mut lost) = (0, 0);
+        for result in &results {
+            let arrival_time = match result.arrival_time {
+                Some(arrival_time) => arrival_time,
+                None => {
+                    lost += 1;
+                    continue;
+                }
+            };
+            received += 1;
+
+            self.acked_bitrate.on_packet(arrival_time, result.sent.size);
+            if let Some(estimate) = self.probe.on_result(result) {
+                probe_estimate = Some(estimate);
+            }
+            if let Some(delta) = self
+                .inter_arrival
+                .compute_deltas(result.sent.send_time, arrival_time)
+            {
+                usage = self.trendline.update(&delta);
+            }
+        }
+
+        if let Some(probe_estimate) = probe_estimate {
+            // A successful probe skips the slow increase to the probed bitrate
+            if usage != BandwidthUsage::Overusing
+                && probe_estimate > self.rate_controller.estimate()
+            {
+                self.rate_controller.set_estimate(probe_estimate);
+                self.loss_based.set_estimate(probe_estimate);
+            }
+        }
+
+        let delay_based = self
+            .rate_controller
+            .update(usage, self.acked_bitrate.bitrate(), now);
+        let loss_based = self.loss_based.update(received, lost, now);
+
+        // The loss based estimate can't run away from the delay based one while the loss is
+        // low, or it wouldn't have any effect once the loss grows.
+        self.target_bitrate = delay_based.min(loss_based);
+        self.loss_based.set_estimate(self.target_bitrate);
+
+        self.target_bitrate
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::{HashMap, VecDeque};
+
+use rtcp::transport_feedbacks::transport_layer_cc::TransportLayerCc;
+
+/// Sent packets are forgotten after 60s without feedback
+const MAX_HISTORY_AGE: i64 = 60_000_000;
+
+/// SentPacket describes a packet carrying a transport wide sequence number when it was sent
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SentPacket {
+    pub(crate) sequence_number: u16,
+    /// Local send time in us
+    pub(crate) send_time: i64,
+    /// Size of the RTP packet in bytes
+    pub(crate) size: usize,
+    /// Probe cluster the packet was sent for, if it was sent as bandwidth probe
+    pub(crate) probe_cluster_id: Option<usize>,
+}
+
+/// PacketResult is the fate of a sent packet as reported by the remote peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PacketResult {
+    pub(crate) sent: SentPacket,
+    /// Arrival time in us on the clock of the remote peer, None if the packet was lost
+    pub(crate) arrival_time: Option<i64>,
+}
+
+/// FeedbackAdapter remembers sent packets and matches them with the arrivals reported by
+/// transport wide congestion control feedback.
+#[derive(Debug, Default)]
+pub(crate) struct FeedbackAdapter {
+    history: HashMap<u16, SentPacket>,
+    sequence_numbers: VecDeque<u16>,
+}
+
+impl FeedbackAdapter {
+    /// on_sent adds packet to the history, dropping the packets that are too old to still get
+    /// feedback.
+    pub(crate) fn on_sent(&mut self, packet: SentPacket) {
+        while let Some(&sequence_number) = self.sequence_numbers.front() {
+            let expired = match self.history.get(&sequence_number) {
+                Some(p) => packet.send_time - p.send_time >= MAX_HISTORY_AGE,
+                // Already acknowledged
+                None => true,
+            };
+            if !expired {
+                break;
+            }
+
+            self.history.remove(&sequence_number);
+            self.sequence_numbers.pop_front();
+        }
+
+        self.history.insert(packet.sequence_number, packet);
+        self.sequence_numbers.push_back(packet.sequence_number);
+    }
+
+    /// on_feedback returns the results of the sent packets reported by feedback, in sequence
+    /// number order. Received packets are removed from the history, so feedback that is
+    /// received twice doesn't report them again.
+    pub(crate) fn on_feedback(&mut self, feedback: &TransportLayerCc) -> Vec<PacketResult> {
+        let mut results = vec![];
+        for arrival in feedback.packet_arrivals() {
+            let sent = if arrival.arrival_time.is_some() {
+                self.history.remove(&arrival.sequence_number)
+            } else {
+                self.history.get(&arrival.sequence_number).copied()
+            };
+
+            if let Some(sent) = sent {
+                results.push(PacketResult {
+                    sent,
+                    arrival_time: arrival.arrival_time,
+                });
+            }
+        }
+
+        results
+    }
+}
@@ -0,0 +1,338 @@
+use bytes::Bytes;
+use tokio::time::Duration;
+use util::Marshal;
+
+use super::delay_based::*;
+use super::feedback_adapter::*;
+use super::loss_based::*;
+use super::probe::*;
+use super::rate_controller::*;
+use super::*;
+use crate::mock::mock_stream::MockStream;
+use crate::stream_info::RTPHeaderExtension;
+use crate::twcc::Recorder;
+
+fn feedback(recorder: &mut Recorder) -> TransportLayerCc {
+    let pkts = recorder.build_feedback_packet();
+    assert_eq!(pkts.len(), 1);
+    pkts[0]
+        .as_any()
+        .downcast_ref::<TransportLayerCc>()
+        .cloned()
+        .unwrap()
+}
+
+fn probe_result(
+    probe_cluster_id: Option<usize>,
+    send_time: i64,
+    arrival_time: i64,
+) -> PacketResult {
+    PacketResult {
+        sent: SentPacket {
+            send_time,
+            size: 1000,
+            probe_cluster_id,
+            ..Default::default()
+        },
+        arrival_time: Some(arrival_time),
+    }
+}
+
+#[test]
+fn test_feedback_adapter() {
+    let mut adapter = FeedbackAdapter::default();
+    for sequence_number in 0..3 {
+        adapter.on_sent(SentPacket {
+            sequence_number,
+            send_time: sequence_number as i64 * 1000,
+            size: 100,
+            probe_cluster_id: None,
+        });
+    }
+
+    let mut recorder = Recorder::new(1);
+    recorder.record(2, 0, 21_000);
+    recorder.record(2, 2, 23_000);
+    let feedback = feedback(&mut recorder);
+
+    let results = adapter.on_feedback(&feedback);
+    assert_eq!(
+        results
+            .iter()
+            .map(|r| (r.sent.sequence_number, r.sent.send_time, r.arrival_time))
+            .collect::<Vec<_>>(),
+        vec![
+            (0, 0, Some(21_000)),
+            (1, 1000, None),
+            (2, 2000, Some(23_000))
+        ]
+    );
+
+    // Received packets are only reported once
+    let results = adapter.on_feedback(&feedback);
+    assert_eq!(
+        results
+            .iter()
+            .map(|r| (r.sent.sequence_number, r.arrival_time))
+            .collect::<Vec<_>>(),
+        vec![(1, None)]
+    );
+}
+
+#[test]
+fn test_inter_arrival() {
+    let mut inter_arrival = InterArrival::default();
+
+    assert_eq!(inter_arrival.compute_deltas(0, 100_000), None);
+    // Same burst
+    assert_eq!(inter_arrival.compute_deltas(2_000, 102_000), None);
+    // Completes the first group, there's no previous one to compare it with
+    assert_eq!(inter_arrival.compute_deltas(10_000, 110_000), None);
+    assert_eq!(
+        inter_arrival.compute_deltas(20_000, 125_000),
+        Some(GroupDelta {
+            send_delta: 8_000,
+            arrival_delta: 8_000,
+            arrival_time: 110_000,
+        })
+    );
+    // Reordered packet of a completed group
+    assert_eq!(inter_arrival.compute_deltas(15_000, 126_000), None);
+}
+
+#[test]
+fn test_trendline_estimator() {
+    let mut trendline = TrendlineEstimator::default();
+    let mut arrival_time = 0;
+    let mut update = |trendline: &mut TrendlineEstimator, arrival_delta: i64| {
+        arrival_time += arrival_delta;
+        trendline.update(&GroupDelta {
+            send_delta: 10_000,
+            arrival_delta,
+            arrival_time,
+        })
+    };
+
+    // Stable delay
+    for _ in 0..40 {
+        assert_eq!(update(&mut trendline, 10_000), BandwidthUsage::Normal);
+    }
+
+    // Queues building up
+    let mut overusing = false;
+    for _ in 0..40 {
+        overusing |= update(&mut trendline, 12_000) == BandwidthUsage::Overusing;
+    }
+    assert!(overusing, "growing delay should be detected as overuse");
+
+    // Queues draining
+    let mut underusing = false;
+    for _ in 0..40 {
+        underusing |= update(&mut trendline, 8_000) == BandwidthUsage::Underusing;
+    }
+    assert!(underusing, "shrinking delay should be detected as underuse");
+}
+
+#[test]
+fn test_aimd_rate_controller() {
+    let mut controller = AimdRateController::new(300_000, 30_000, 10_000_000);
+
+    assert_eq!(
+        controller.update(BandwidthUsage::Normal, None, 0),
+        300_000,
+        "nothing to increase before any time passed"
+    );
+
+    // Multiplicative increase far from the link capacity
+    let estimate = controller.update(BandwidthUsage::Normal, None, 1_000_000);
+    assert!(estimate > 320_000 && estimate < 330_000, "{estimate}");
+
+    // The estimate doesn't run away from the acknowledged bitrate
+    assert_eq!(
+        controller.update(BandwidthUsage::Normal, Some(100_000), 2_000_000),
+        estimate
+    );
+
+    assert_eq!(
+        controller.update(BandwidthUsage::Overusing, Some(200_000), 2_100_000),
+        170_000
+    );
+    assert_eq!(
+        controller.update(BandwidthUsage::Overusing, Some(150_000), 2_200_000),
+        170_000,
+        "decrease should wait for the previous one to take effect"
+    );
+    assert_eq!(
+        controller.update(BandwidthUsage::Overusing, Some(150_000), 2_500_000),
+        127_500
+    );
+
+    assert_eq!(
+        controller.update(BandwidthUsage::Underusing, Some(150_000), 3_000_000),
+        127_500,
+        "estimate should hold while queues drain"
+    );
+
+    // Additive increase of about one packet per response time close to the link capacity
+    let before = controller.update(BandwidthUsage::Normal, None, 3_500_000);
+    let increase = controller.update(BandwidthUsage::Normal, None, 4_500_000) - before;
+    assert!(increase > 31_000 && increase < 33_000, "{increase}");
+}
+
+#[test]
+fn test_acked_bitrate_estimator() {
+    let mut acked_bitrate = AckedBitrateEstimator::default();
+
+    acked_bitrate.on_packet(0, 1000);
+    assert_eq!(acked_bitrate.bitrate(), None);
+
+    for i in 1..=100 {
+        acked_bitrate.on_packet(i * 10_000, 1000);
+    }
+    // 51 packets of 1000 bytes in the last 500ms
+    assert_eq!(acked_bitrate.bitrate(), Some(816_000));
+}
+
+#[test]
+fn test_loss_based_bwe() {
+    let mut loss_based = LossBasedBwe::new(1_000_000, 30_000, 10_000_000);
+
+    assert_eq!(loss_based.update(100, 0, 0), 1_050_000);
+    assert_eq!(
+        loss_based.update(100, 0, 100_000),
+        1_050_000,
+        "increase should be rate limited"
+    );
+    assert_eq!(loss_based.update(95, 5, 400_000), 1_050_000);
+    assert_eq!(loss_based.update(80, 20, 500_000), 945_000);
+    assert_eq!(loss_based.update(0, 0, 600_000), 945_000);
+
+    for i in 0..20 {
+        loss_based.update(0, 100, 700_000 + i * 100_000);
+    }
+    assert_eq!(loss_based.estimate(), 30_000);
+}
+
+#[test]
+fn test_probe_bitrate_estimator() {
+    let mut probe = ProbeBitrateEstimator::default();
+
+    // Sent and received at 8Mbps
+    for i in 0..4 {
+        assert_eq!(
+            probe.on_result(&probe_result(Some(1), i * 1000, 10_000 + i * 1000)),
+            None
+        );
+    }
+    assert_eq!(
+        probe.on_result(&probe_result(Some(1), 4000, 14_000)),
+        Some(8_000_000)
+    );
+
+    // Sent at 8Mbps, but only received at 4Mbps
+    for i in 0..4 {
+        assert_eq!(
+            probe.on_result(&probe_result(Some(2), i * 1000, 20_000 + i * 2000)),
+            None
+        );
+    }
+    assert_eq!(
+        probe.on_result(&probe_result(Some(2), 4000, 28_000)),
+        Some(3_800_000)
+    );
+
+    assert_eq!(probe.on_result(&probe_result(None, 5000, 29_000)), None);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_send_side_bwe_interceptor() -> Result<()> {
+    let estimators = Arc::new(std::sync::Mutex::new(vec![]));
+    let estimators2 = Arc::clone(&estimators);
+    let icpr = SendSideBwe::builder()
+        .with_initial_bitrate(100_000)
+        .with_on_new_estimator(Box::new(move |_id: &str, bwe: Arc<SendSideBwe>| {
+            estimators2.lock().unwrap().push(bwe);
+        }))
+        .build("")?;
+    let bwe = estimators.lock().unwrap().pop().unwrap();
+    assert_eq!(bwe.target_bitrate(), 100_000);
+    let mut target_bitrate_rx = bwe.subscribe();
+
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            rtp_header_extensions: vec![RTPHeaderExtension {
+                uri: TRANSPORT_CC_URI.to_owned(),
+                id: 1,
+            }],
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    let mut recorder = Recorder::new(2);
+    let send_round = |round: u16, lost: fn(u16) -> bool| {
+        let stream = Arc::clone(&stream);
+        let mut received = vec![];
+        async move {
+            for i in 0..50 {
+                let sequence_number = round * 50 + i;
+                let mut pkt = rtp::packet::Packet {
+                    header: rtp::header::Header {
+                        ssrc: 1,
+                        sequence_number,
+                        ..Default::default()
+                    },
+                    payload: Bytes::from(vec![0u8; 1000]),
+                };
+                let ext = TransportCcExtension {
+                    transport_sequence: sequence_number,
+                }
+                .marshal()?;
+                pkt.header.set_extension(1, ext)?;
+                stream.write_rtp(&pkt).await?;
+
+                if !lost(sequence_number) {
+                    // Constant one way delay of 20ms
+                    let now = (round as i64 * 50 + i as i64) * 10_000;
+                    received.push((sequence_number, now + 20_000));
+                }
+                tokio::time::advance(Duration::from_millis(10)).await;
+            }
+            Result::<Vec<(u16, i64)>>::Ok(received)
+        }
+    };
+
+    for round in 0..2 {
+        for (sequence_number, arrival_time) in send_round(round, |_| false).await? {
+            recorder.record(2, sequence_number, arrival_time);
+        }
+        stream
+            .receive_rtcp(vec![Box::new(feedback(&mut recorder))])
+            .await;
+        stream.read_rtcp().await;
+    }
+
+    let increased = bwe.target_bitrate();
+    assert!(increased > 100_000, "{increased}");
+    assert!(target_bitrate_rx.has_changed().unwrap_or(false));
+    assert_eq!(*target_bitrate_rx.borrow_and_update(), increased);
+
+    // A third of the packets are lost
+    for (sequence_number, arrival_time) in send_round(2, |s| s % 3 == 0).await? {
+        recorder.record(2, sequence_number, arrival_time);
+    }
+    stream
+        .receive_rtcp(vec![Box::new(feedback(&mut recorder))])
+        .await;
+    stream.read_rtcp().await;
+
+    let decreased = bwe.target_bitrate();
+    assert!(decreased < increased, "{decreased} >= {increased}");
+    assert!(target_bitrate_rx.has_changed().unwrap_or(false));
+
+    stream.close().await?;
+
+    Ok(())
+}
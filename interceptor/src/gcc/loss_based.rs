@@ -0,0 +1,64 @@
+/// Below 2% loss, the estimate is increased
+const LOW_LOSS_THRESHOLD: f64 = 0.02;
+/// Above 10% loss, the estimate is decreased
+const HIGH_LOSS_THRESHOLD: f64 = 0.1;
+const INCREASE_FACTOR: f64 = 1.05;
+/// The estimate is increased at most once per 200ms
+const MIN_INCREASE_INTERVAL: i64 = 200_000;
+
+/// LossBasedBwe adjusts the estimate to the fraction of packets lost on the network path.
+/// Reference: <https://datatracker.ietf.org/doc/html/draft-ietf-rmcat-gcc-02#section-6>
+#[derive(Debug)]
+pub(crate) struct LossBasedBwe {
+    estimate: u64,
+    min_bitrate: u64,
+    max_bitrate: u64,
+
+    last_increase: Option<i64>,
+}
+
+impl LossBasedBwe {
+    pub(crate) fn new(initial_bitrate: u64, min_bitrate: u64, max_bitrate: u64) -> Self {
+        LossBasedBwe {
+            estimate: initial_bitrate,
+            min_bitrate,
+            max_bitrate,
+
+            last_increase: None,
+        }
+    }
+
+    pub(crate) fn estimate(&self) -> u64 {
+        self.estimate
+    }
+
+    /// set_estimate overrides the estimate, so it follows the delay based estimate.
+    pub(crate) fn set_estimate(&mut self, estimate: u64) {
+        self.estimate = estimate.clamp(self.min_bitrate, self.max_bitrate);
+    }
+
+    /// update applies the number of received and lost packets reported at now, in us, and
+    /// returns the new estimate.
+    pub(crate) fn update(&mut self, received: usize, lost: usize, now: i64) -> u64 {
+        let total = received + lost;
+        if total == 0 {
+            return self.estimate;
+        }
+
+        let loss = lost as f64 / total as f64;
+        let estimate = self.estimate as f64;
+        if loss > HIGH_LOSS_THRESHOLD {
+            self.set_estimate((estimate * (1.0 - 0.5 * loss)) as u64);
+        } else if loss < LOW_LOSS_THRESHOLD {
+            match self.last_increase {
+                Some(last_increase) if now - last_increase < MIN_INCREASE_INTERVAL => {}
+                _ => {
+                    self.last_increase = Some(now);
+                    self.set_estimate((estimate * INCREASE_FACTOR) as u64);
+                }
+            }
+        }
+
+        self.estimate
+    }
+}
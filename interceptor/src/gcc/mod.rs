@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod gcc_test;
+
+mod bwe_stream;
+mod delay_based;
+mod estimator;
+mod feedback_adapter;
+mod loss_based;
+mod probe;
+mod rate_controller;
+
+use std::sync::Arc;
+
+use bwe_stream::BweStream;
+use estimator::SendSideEstimator;
+use feedback_adapter::SentPacket;
+use rtcp::transport_feedbacks::transport_layer_cc::TransportLayerCc;
+use rtp::extension::transport_cc_extension::TransportCcExtension;
+use tokio::sync::{watch, Mutex};
+use util::{MarshalSize, Unmarshal};
+
+use crate::twcc::sender::TRANSPORT_CC_URI;
+use crate::*;
+
+/// Packets written with this attribute are bandwidth probes of the probe cluster it is set to,
+/// e.g. padding sent by a pacer to find out whether a higher bitrate is available.
+pub const PROBE_CLUSTER_ID_ATTRIBUTE: usize = 0x6763_6370;
+
+const DEFAULT_INITIAL_BITRATE: u64 = 300_000;
+const DEFAULT_MIN_BITRATE: u64 = 30_000;
+const DEFAULT_MAX_BITRATE: u64 = 20_000_000;
+
+/// NewEstimatorFn is called with the id of the PeerConnection and its SendSideBwe each time
+/// the interceptor is built.
+pub type NewEstimatorFn = Box<dyn Fn(&str, Arc<SendSideBwe>) + Send + Sync>;
+
+/// SendSideBweBuilder is a InterceptorBuilder for a SendSideBwe Interceptor
+#[derive(Default)]
+pub struct SendSideBweBuilder {
+    initial_bitrate: Option<u64>,
+    min_bitrate: Option<u64>,
+    max_bitrate: Option<u64>,
+    on_new_estimator: Option<NewEstimatorFn>,
+}
+
+impl SendSideBweBuilder {
+    /// with_initial_bitrate sets the bitrate in bps the estimate starts at.
+    pub fn with_initial_bitrate(mut self, initial_bitrate: u64) -> SendSideBweBuilder {
+        self.initial_bitrate = Some(initial_bitrate);
+        self
+    }
+
+    /// with_min_bitrate sets the bitrate in bps the estimate never goes below.
+    pub fn with_min_bitrate(mut self, min_bitrate: u64) -> SendSideBweBuilder {
+        self.min_bitrate = Some(min_bitrate);
+        self
+    }
+
+    /// with_max_bitrate sets the bitrate in bps the estimate never goes above.
+    pub fn with_max_bitrate(mut self, max_bitrate: u64) -> SendSideBweBuilder {
+        self.max_bitrate = Some(max_bitrate);
+        self
+    }
+
+    /// with_on_new_estimator sets the function handing the estimator of each new PeerConnection
+    /// to the application.
+    pub fn with_on_new_estimator(mut self, f: NewEstimatorFn) -> SendSideBweBuilder {
+        self.on_new_estimator = Some(f);
+        self
+    }
+}
+
+impl InterceptorBuilder for SendSideBweBuilder {
+    /// build constructs a new SendSideBwe
+    fn build(&self, id: &str) -> Result<Arc<dyn Interceptor + Send + Sync>> {
+        let min_bitrate = self.min_bitrate.unwrap_or(DEFAULT_MIN_BITRATE);
+        let max_bitrate = self
+            .max_bitrate
+            .unwrap_or(DEFAULT_MAX_BITRATE)
+            .max(min_bitrate);
+        let initial_bitrate = self
+            .initial_bitrate
+            .unwrap_or(DEFAULT_INITIAL_BITRATE)
+            .clamp(min_bitrate, max_bitrate);
+
+        let (target_bitrate_tx, _) = watch::channel(initial_bitrate);
+        let bwe = Arc::new(SendSideBwe {
+            internal: Arc::new(SendSideBweInternal {
+                estimator: Mutex::new(SendSideEstimator::new(
+                    initial_bitrate,
+                    min_bitrate,
+                    max_bitrate,
+                )),
+                target_bitrate_tx,
+                start_time: tokio::time::Instant::now(),
+            }),
+        });
+
+        if let Some(f) = &self.on_new_estimator {
+            f(id, Arc::clone(&bwe));
+        }
+
+        Ok(bwe)
+    }
+}
+
+struct SendSideBweInternal {
+    estimator: Mutex<SendSideEstimator>,
+    target_bitrate_tx: watch::Sender<u64>,
+    // we use tokio's Instant because it makes testing easier via `tokio::time::advance`.
+    start_time: tokio::time::Instant,
+}
+
+impl SendSideBweInternal {
+    async fn on_feedback(&self, feedback: &TransportLayerCc) {
+        let now = (tokio::time::Instant::now() - self.start_time).as_micros() as i64;
+        let target_bitrate = {
+            let mut estimator = self.estimator.lock().await;
+            estimator.on_feedback(feedback, now)
+        };
+
+        self.target_bitrate_tx.send_if_modified(|current| {
+            if *current == target_bitrate {
+                false
+            } else {
+                *current = target_bitrate;
+                true
+            }
+        });
+    }
+}
+
+struct SendSideBweRtcpReader {
+    parent_rtcp_reader: Arc<dyn RTCPReader + Send + Sync>,
+    internal: Arc<SendSideBweInternal>,
+}
+
+#[async_trait]
+impl RTCPReader for SendSideBweRtcpReader {
+    async fn read(
+        &self,
+        buf: &mut [u8],
+        a: &Attributes,
+    ) -> Result<(Vec<Box<dyn rtcp::packet::Packet + Send + Sync>>, Attributes)> {
+        let (pkts, attr) = self.parent_rtcp_reader.read(buf, a).await?;
+        for p in &pkts {
+            if let Some(feedback) = p.as_any().downcast_ref::<TransportLayerCc>() {
+                self.internal.on_feedback(feedback).await;
+            }
+        }
+
+        Ok((pkts, attr))
+    }
+}
+
+/// SendSideBwe estimates the bandwidth available to the sender with Google Congestion Control,
+/// from the transport wide congestion control feedback sent by the remote peer.
+/// It reads the transport wide sequence numbers of the outgoing packets, so it has to be
+/// registered before the TWCC sender interceptor adding them.
+/// Reference: <https://datatracker.ietf.org/doc/html/draft-ietf-rmcat-gcc-02>
+pub struct SendSideBwe {
+    internal: Arc<SendSideBweInternal>,
+}
+
+impl SendSideBwe {
+    /// builder returns a new SendSideBweBuilder.
+    pub fn builder() -> SendSideBweBuilder {
+        SendSideBweBuilder::default()
+    }
+
+    /// target_bitrate returns the bitrate in bps the sender should currently not exceed.
+    pub fn target_bitrate(&self) -> u64 {
+        *self.internal.target_bitrate_tx.borrow()
+    }
+
+    /// subscribe returns a receiver notified each time the target bitrate changes, e.g. to
+    /// reconfigure an encoder.
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.internal.target_bitrate_tx.subscribe()
+    }
+}
+
+#[async_trait]
+impl Interceptor for SendSideBwe {
+    /// bind_rtcp_reader returns a reader updating the estimate with each TWCC feedback.
+    async fn bind_rtcp_reader(
+        &self,
+        reader: Arc<dyn RTCPReader + Send + Sync>,
+    ) -> Arc<dyn RTCPReader + Send + Sync> {
+        Arc::new(SendSideBweRtcpReader {
+            parent_rtcp_reader: reader,
+            internal: Arc::clone(&self.internal),
+        })
+    }
+
+    /// bind_rtcp_writer lets you modify any outgoing RTCP packets. It is called once per PeerConnection. The returned method
+    /// will be called once per packet batch.
+    async fn bind_rtcp_writer(
+        &self,
+        writer: Arc<dyn RTCPWriter + Send + Sync>,
+    ) -> Arc<dyn RTCPWriter + Send + Sync> {
+        writer
+    }
+
+    /// bind_local_stream returns a writer recording the send time of each outgoing packet
+    /// carrying a transport wide sequence number.
+    async fn bind_local_stream(
+        &self,
+        info: &StreamInfo,
+        writer: Arc<dyn RTPWriter + Send + Sync>,
+    ) -> Arc<dyn RTPWriter + Send + Sync> {
+        let mut hdr_ext_id = 0u8;
+        for e in &info.rtp_header_extensions {
+            if e.uri == TRANSPORT_CC_URI {
+                hdr_ext_id = e.id as u8;
+                break;
+            }
+        }
+        if hdr_ext_id == 0 {
+            // Packets without transport wide sequence numbers can't be matched with feedback
+            return writer;
+        }
+
+        Arc::new(BweStream::new(
+            writer,
+            Arc::clone(&self.internal),
+            hdr_ext_id,
+        ))
+    }
+
+    /// unbind_local_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_local_stream(&self, _info: &StreamInfo) {}
+
+    /// bind_remote_stream lets you modify any incoming RTP packets. It is called once for per RemoteStream. The returned method
+    /// will be called once per rtp packet.
+    async fn bind_remote_stream(
+        &self,
+        _info: &StreamInfo,
+        reader: Arc<dyn RTPReader + Send + Sync>,
+    ) -> Arc<dyn RTPReader + Send + Sync> {
+        reader
+    }
+
+    /// unbind_remote_stream is called when the Stream is removed. It can be used to clean up any data related to that track.
+    async fn unbind_remote_stream(&self, _info: &StreamInfo) {}
+
+    /// close closes the Interceptor, cleaning up any data if necessary.
+    async fn close(&self) -> Result<()> {
+        Ok(())
+    }
+}
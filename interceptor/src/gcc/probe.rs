@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use super::feedback_adapter::PacketResult;
+
+/// A probe cluster is evaluated once 5 of its packets were received
+const MIN_RECEIVED_PROBES: usize = 5;
+/// Clusters without new results for 1s are forgotten
+const MAX_CLUSTER_AGE: i64 = 1_000_000;
+/// A receive rate well above the send rate means the arrivals were bunched up on the way
+const MAX_VALID_RATIO: f64 = 2.0;
+/// A receive rate below 90% of the send rate means the probe saturated the link
+const MIN_RATIO_FOR_UNSATURATED_LINK: f64 = 0.9;
+const TARGET_UTILIZATION_FRACTION: f64 = 0.95;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ProbeCluster {
+    first_send_time: i64,
+    last_send_time: i64,
+    first_arrival_time: i64,
+    last_arrival_time: i64,
+    /// Size of the last sent packet, which isn't part of the send interval
+    size_last_send: usize,
+    /// Size of the first received packet, which isn't part of the receive interval
+    size_first_arrival: usize,
+    size_total: usize,
+    num_probes: usize,
+}
+
+/// ProbeBitrateEstimator estimates the available bandwidth from the rates at which the
+/// packets of a probe cluster were sent and received.
+#[derive(Debug, Default)]
+pub(crate) struct ProbeBitrateEstimator {
+    clusters: HashMap<usize, ProbeCluster>,
+}
+
+impl ProbeBitrateEstimator {
+    /// on_result adds a received probe packet and returns the estimate of its cluster in bps,
+    /// once the cluster has enough packets for a valid one.
+    pub(crate) fn on_result(&mut self, result: &PacketResult) -> Option<u64> {
+        let (cluster_id, arrival_time) = match (result.sent.probe_cluster_id, result.arrival_time) {
+            (Some(cluster_id), Some(arrival_time)) => (cluster_id, arrival_time),
+            _ => return None,
+        };
+        let send_time = result.sent.send_time;
+        let size = result.sent.size;
+
+        self.clusters
+            .retain(|_, cluster| send_time - cluster.last_send_time < MAX_CLUSTER_AGE);
+
+        let cluster = self.clusters.entry(cluster_id).or_insert(ProbeCluster {
+            first_send_time: send_time,
+            last_send_time: send_time,
+            first_arrival_time: arrival_time,
+            last_arrival_time: arrival_time,
+            size_last_send: size,
+            size_first_arrival: size,
+            ..Default::default()
+        });
+
+        if send_time < cluster.first_send_time {
+            cluster.first_send_time = send_time;
+        }
+        if send_time >= cluster.last_send_time {
+            cluster.last_send_time = send_time;
+            cluster.size_last_send = size;
+        }
+        if arrival_time < cluster.first_arrival_time {
+            cluster.first_arrival_time = arrival_time;
+            cluster.size_first_arrival = size;
+        }
+        if arrival_time > cluster.last_arrival_time {
+            cluster.last_arrival_time = arrival_time;
+        }
+        cluster.size_total += size;
+        cluster.num_probes += 1;
+
+        if cluster.num_probes < MIN_RECEIVED_PROBES {
+            return None;
+        }
+
+        let send_interval = cluster.last_send_time - cluster.first_send_time;
+        let receive_interval = cluster.last_arrival_time - cluster.first_arrival_time;
+        if send_interval <= 0 || receive_interval <= 0 {
+            return None;
+        }
+
+        let send_rate =
+            (cluster.size_total - cluster.size_last_send) as f64 * 8.0 * 1e6 / send_interval as f64;
+        let receive_rate = (cluster.size_total - cluster.size_first_arrival) as f64 * 8.0 * 1e6
+            / receive_interval as f64;
+        if receive_rate > MAX_VALID_RATIO * send_rate {
+            return None;
+        }
+
+        let estimate = if receive_rate < MIN_RATIO_FOR_UNSATURATED_LINK * send_rate {
+            TARGET_UTILIZATION_FRACTION * receive_rate
+        } else {
+            send_rate.min(receive_rate)
+        };
+
+        Some(estimate as u64)
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::VecDeque;
+
+use super::delay_based::BandwidthUsage;
+
+/// The acknowledged bitrate is measured over the last 500ms of arrivals
+const ACKED_BITRATE_WINDOW: i64 = 500_000;
+/// The acknowledged bitrate isn't reported before arrivals span 150ms
+const MIN_ACKED_BITRATE_SPAN: i64 = 150_000;
+
+/// On overuse, the estimate is decreased to 85% of the acknowledged bitrate
+const BETA: f64 = 0.85;
+/// Far from the link capacity, the estimate grows by 8% per second
+const MULTIPLICATIVE_INCREASE_PER_SECOND: f64 = 1.08;
+const MIN_INCREASE: f64 = 1000.0;
+const EXPECTED_PACKET_SIZE_BITS: f64 = 1200.0 * 8.0;
+const DEFAULT_RESPONSE_TIME: f64 = 0.3;
+/// The estimate is decreased at most once per response time, until the decrease took effect
+const MIN_DECREASE_INTERVAL: i64 = 300_000;
+/// The estimate isn't increased beyond 1.5 times the acknowledged bitrate plus 10kbps
+const MAX_ACKED_BITRATE_RATIO: f64 = 1.5;
+const MAX_ACKED_BITRATE_OFFSET: f64 = 10_000.0;
+
+/// AckedBitrateEstimator measures the bitrate received by the remote peer
+#[derive(Debug, Default)]
+pub(crate) struct AckedBitrateEstimator {
+    /// (arrival time, size in bytes) of the packets received in the window
+    arrivals: VecDeque<(i64, usize)>,
+    bytes: usize,
+}
+
+impl AckedBitrateEstimator {
+    pub(crate) fn on_packet(&mut self, arrival_time: i64, size: usize) {
+        self.arrivals.push_back((arrival_time, size));
+        self.bytes += size;
+
+        while let Some(&(first_arrival_time, first_size)) = self.arrivals.front() {
+            if arrival_time - first_arrival_time <= ACKED_BITRATE_WINDOW {
+                break;
+            }
+            self.bytes -= first_size;
+            self.arrivals.pop_front();
+        }
+    }
+
+    /// bitrate returns the received bitrate in bps, if enough packets were received.
+    pub(crate) fn bitrate(&self) -> Option<u64> {
+        let (first, last) = match (self.arrivals.front(), self.arrivals.back()) {
+            (Some(first), Some(last)) => (first.0, last.0),
+            _ => return None,
+        };
+        if last - first < MIN_ACKED_BITRATE_SPAN {
+            return None;
+        }
+
+        Some((self.bytes as f64 * 8.0 * 1_000_000.0 / (last - first) as f64) as u64)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum RateControlState {
+    #[default]
+    Hold,
+    Increase,
+    Decrease,
+}
+
+/// AimdRateController adjusts the delay based estimate to the detected bandwidth usage, with
+/// an additive increase close to the link capacity and a multiplicative decrease on overuse.
+/// Reference: <https://datatracker.ietf.org/doc/html/draft-ietf-rmcat-gcc-02#section-5.5>
+#[derive(Debug)]
+pub(crate) struct AimdRateController {
+    estimate: u64,
+    min_bitrate: u64,
+    max_bitrate: u64,
+
+    state: RateControlState,
+    /// Average acknowledged bitrate at the last overuses, in bps
+    link_capacity: Option<f64>,
+    last_update: Option<i64>,
+    last_decrease: Option<i64>,
+}
+
+impl AimdRateController {
+    pub(crate) fn new(initial_bitrate: u64, min_bitrate: u64, max_bitrate: u64) -> Self {
+        AimdRateController {
+            estimate: initial_bitrate,
+            min_bitrate,
+            max_bitrate,
+
+            state: RateControlState::Hold,
+            link_capacity: None,
+            last_update: None,
+            last_decrease: None,
+        }
+    }
+
+    pub(crate) fn estimate(&self) -> u64 {
+        self.estimate
+    }
+
+    /// set_estimate overrides the estimate, e.g. with the result of a bandwidth probe.
+    pub(crate) fn set_estimate(&mut self, estimate: u64) {
+        self.estimate = estimate.clamp(self.min_bitrate, self.max_bitrate);
+    }
+
+    /// update applies the bandwidth usage detected at now, in us, and returns the new estimate.
+    pub(crate) fn update(
+        &mut self,
+        usage: BandwidthUsage,
+        acked_bitrate: Option<u64>,
+        now: i64,
+    ) -> u64 {
+        self.state = match (usage, self.state) {
+            (BandwidthUsage::Overusing, _) => RateControlState::Decrease,
+            (BandwidthUsage::Underusing, _) => RateControlState::Hold,
+            (BandwidthUsage::Normal, RateControlState::Hold) => RateControlState::Increase,
+            (BandwidthUsage::Normal, state) => state,
+        };
+
+        let elapsed = (now - self.last_update.unwrap_or(now)).clamp(0, 1_000_000) as f64 / 1e6;
+        self.last_update = Some(now);
+
+        let estimate = self.estimate as f64;
+        let acked_bitrate = acked_bitrate.map(|bitrate| bitrate as f64);
+        let new_estimate = match self.state {
+            RateControlState::Hold => estimate,
+            RateControlState::Increase => {
+                if let (Some(link_capacity), Some(acked_bitrate)) =
+                    (self.link_capacity, acked_bitrate)
+                {
+                    // The capacity of the link changed, look for the new one
+                    if acked_bitrate > link_capacity * MAX_ACKED_BITRATE_RATIO {
+                        self.link_capacity = None;
+                    }
+                }
+
+                let increase = if self.link_capacity.is_some() {
+                    // About one packet per response time
+                    (EXPECTED_PACKET_SIZE_BITS / DEFAULT_RESPONSE_TIME).max(MIN_INCREASE) * elapsed
+                } else {
+                    let factor = MULTIPLICATIVE_INCREASE_PER_SECOND.powf(elapsed);
+                    (estimate * (factor - 1.0)).max(MIN_INCREASE * elapsed)
+                };
+
+                let mut new_estimate = estimate + increase;
+                if let Some(acked_bitrate) = acked_bitrate {
+                    // Don't run away from what the link actually carries, but never decrease
+                    // while increasing
+                    let limit = MAX_ACKED_BITRATE_RATIO * acked_bitrate + MAX_ACKED_BITRATE_OFFSET;
+                    new_estimate = new_estimate.min(limit.max(estimate));
+                }
+                new_estimate
+            }
+            RateControlState::Decrease => {
+                self.state = RateControlState::Hold;
+                match (self.last_decrease, acked_bitrate) {
+                    (Some(last_decrease), _) if now - last_decrease < MIN_DECREASE_INTERVAL => {
+                        estimate
+                    }
+                    (_, Some(acked_bitrate)) => {
+                        self.last_decrease = Some(now);
+                        self.link_capacity = Some(match self.link_capacity {
+                            Some(link_capacity) => 0.95 * link_capacity + 0.05 * acked_bitrate,
+                            None => acked_bitrate,
+                        });
+                        (BETA * acked_bitrate).min(estimate)
+                    }
+                    (_, None) => {
+                        self.last_decrease = Some(now);
+                        BETA * estimate
+                    }
+                }
+            }
+        };
+
+        self.estimate = (new_estimate as u64).clamp(self.min_bitrate, self.max_bitrate);
+        self.estimate
+    }
+}
@@ -1,14 +1,23 @@
+use tokio::time::Instant;
 use util::sync::Mutex;
 
 use super::*;
 use crate::nack::UINT16SIZE_HALF;
 
+/// NackLog keeps track of how often a missing packet was requested
+#[derive(Debug, Clone, Copy)]
+struct NackLog {
+    first_nacked: Instant,
+    count: u16,
+}
+
 struct GeneratorStreamInternal {
     packets: Vec<u64>,
     size: u16,
     end: u16,
     started: bool,
     last_consecutive: u16,
+    nack_logs: HashMap<u16, NackLog>,
 }
 
 impl GeneratorStreamInternal {
@@ -19,6 +28,7 @@ impl GeneratorStreamInternal {
             end: 0,
             started: false,
             last_consecutive: 0,
+            nack_logs: HashMap::new(),
         }
     }
 
@@ -96,6 +106,53 @@ impl GeneratorStreamInternal {
         missing_packet_seq_nums
     }
 
+    /// nack_seq_numbers returns the missing sequence numbers that should be requested at now,
+    /// leaving out the ones already requested max_nacks_per_packet times or first requested
+    /// longer than max_age ago.
+    fn nack_seq_numbers(
+        &mut self,
+        skip_last_n: u16,
+        max_nacks_per_packet: Option<u16>,
+        max_age: Option<Duration>,
+        now: Instant,
+    ) -> Vec<u16> {
+        let missing = self.missing_seq_numbers(skip_last_n);
+        if max_nacks_per_packet.is_none() && max_age.is_none() {
+            return missing;
+        }
+
+        // Forget the packets which were received in the meantime or fell out of the buffer
+        let mut nack_logs = HashMap::with_capacity(missing.len());
+        for seq in &missing {
+            if let Some(log) = self.nack_logs.get(seq) {
+                nack_logs.insert(*seq, *log);
+            }
+        }
+        self.nack_logs = nack_logs;
+
+        let mut nacks = vec![];
+        for seq in missing {
+            let log = self.nack_logs.entry(seq).or_insert(NackLog {
+                first_nacked: now,
+                count: 0,
+            });
+            if let Some(max_nacks_per_packet) = max_nacks_per_packet {
+                if log.count >= max_nacks_per_packet {
+                    continue;
+                }
+            }
+            if let Some(max_age) = max_age {
+                if now.duration_since(log.first_nacked) > max_age {
+                    continue;
+                }
+            }
+            log.count += 1;
+            nacks.push(seq);
+        }
+
+        nacks
+    }
+
     fn set_received(&mut self, seq: u16) {
         let pos = (seq % self.size) as usize;
         self.packets[pos / 64] |= 1u64 << (pos % 64);
@@ -135,9 +192,15 @@ impl GeneratorStream {
         }
     }
 
-    pub(super) fn missing_seq_numbers(&self, skip_last_n: u16) -> Vec<u16> {
-        let internal = self.internal.lock();
-        internal.missing_seq_numbers(skip_last_n)
+    pub(super) fn nack_seq_numbers(
+        &self,
+        skip_last_n: u16,
+        max_nacks_per_packet: Option<u16>,
+        max_age: Option<Duration>,
+        now: Instant,
+    ) -> Vec<u16> {
+        let mut internal = self.internal.lock();
+        internal.nack_seq_numbers(skip_last_n, max_nacks_per_packet, max_age, now)
     }
 
     pub(super) fn add(&self, seq: u16) {
@@ -297,6 +360,51 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_generator_stream_nack_limits() {
+        let now = Instant::now();
+
+        let mut rl = GeneratorStreamInternal::new(1);
+        for seq in [0, 1, 2, 4, 6] {
+            rl.add(seq);
+        }
+        for _ in 0..3 {
+            assert_eq!(rl.nack_seq_numbers(0, None, None, now), vec![3, 5]);
+        }
+
+        let mut rl = GeneratorStreamInternal::new(1);
+        for seq in [0, 1, 2, 4, 6] {
+            rl.add(seq);
+        }
+        assert_eq!(rl.nack_seq_numbers(0, Some(2), None, now), vec![3, 5]);
+        assert_eq!(rl.nack_seq_numbers(0, Some(2), None, now), vec![3, 5]);
+        assert!(rl.nack_seq_numbers(0, Some(2), None, now).is_empty());
+        rl.add(7);
+        rl.add(9);
+        assert_eq!(rl.nack_seq_numbers(0, Some(2), None, now), vec![8]);
+        rl.add(3);
+        assert_eq!(rl.nack_seq_numbers(0, Some(2), None, now), vec![8]);
+        assert!(rl.nack_seq_numbers(0, Some(2), None, now).is_empty());
+
+        let max_age = Some(Duration::from_millis(100));
+        let mut rl = GeneratorStreamInternal::new(1);
+        rl.add(0);
+        rl.add(2);
+        assert_eq!(rl.nack_seq_numbers(0, None, max_age, now), vec![1]);
+        assert_eq!(
+            rl.nack_seq_numbers(0, None, max_age, now + Duration::from_millis(100)),
+            vec![1]
+        );
+        assert!(rl
+            .nack_seq_numbers(0, None, max_age, now + Duration::from_millis(101))
+            .is_empty());
+        rl.add(4);
+        assert_eq!(
+            rl.nack_seq_numbers(0, None, max_age, now + Duration::from_millis(150)),
+            vec![3]
+        );
+    }
+
     #[test]
     fn test_generator_stream_rollover() {
         let mut rl = GeneratorStreamInternal::new(1);
@@ -27,6 +27,8 @@ pub struct GeneratorBuilder {
     log2_size_minus_6: Option<u8>,
     skip_last_n: Option<u16>,
     interval: Option<Duration>,
+    max_nacks_per_packet: Option<u16>,
+    max_age: Option<Duration>,
 }
 
 impl GeneratorBuilder {
@@ -49,6 +51,20 @@ impl GeneratorBuilder {
         self.interval = Some(interval);
         self
     }
+
+    /// with_max_nacks_per_packet sets how many times a missing packet is requested at most,
+    /// by default it is requested until it is received or falls out of the buffer.
+    pub fn with_max_nacks_per_packet(mut self, max_nacks_per_packet: u16) -> GeneratorBuilder {
+        self.max_nacks_per_packet = Some(max_nacks_per_packet);
+        self
+    }
+
+    /// with_max_age sets for how long after its first request a missing packet is requested
+    /// again, e.g. the time after which a retransmission would arrive too late to be played.
+    pub fn with_max_age(mut self, max_age: Duration) -> GeneratorBuilder {
+        self.max_age = Some(max_age);
+        self
+    }
 }
 
 impl InterceptorBuilder for GeneratorBuilder {
@@ -71,6 +87,8 @@ impl InterceptorBuilder for GeneratorBuilder {
                 } else {
                     Duration::from_millis(100)
                 },
+                max_nacks_per_packet: self.max_nacks_per_packet,
+                max_age: self.max_age,
 
                 streams: Mutex::new(HashMap::new()),
                 close_rx: Mutex::new(Some(close_rx)),
@@ -86,6 +104,8 @@ struct GeneratorInternal {
     log2_size_minus_6: u8,
     skip_last_n: u16,
     interval: Duration,
+    max_nacks_per_packet: Option<u16>,
+    max_age: Option<Duration>,
 
     streams: Mutex<HashMap<u32, Arc<GeneratorStream>>>,
     close_rx: Mutex<Option<mpsc::Receiver<()>>>,
@@ -130,9 +150,15 @@ impl Generator {
                 _ = ticker.tick() =>{
                     let nacks = {
                         let mut nacks = vec![];
+                        let now = tokio::time::Instant::now();
                         let streams = internal.streams.lock().await;
                         for (ssrc, stream) in streams.iter() {
-                            let missing = stream.missing_seq_numbers(internal.skip_last_n);
+                            let missing = stream.nack_seq_numbers(
+                                internal.skip_last_n,
+                                internal.max_nacks_per_packet,
+                                internal.max_age,
+                                now,
+                            );
                             if missing.is_empty(){
                                 continue;
                             }
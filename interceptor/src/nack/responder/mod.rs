@@ -19,7 +19,7 @@ use crate::{
     Attributes, Interceptor, InterceptorBuilder, RTCPReader, RTCPWriter, RTPReader, RTPWriter,
 };
 
-/// GeneratorBuilder can be used to configure Responder Interceptor
+/// ResponderBuilder can be used to configure Responder Interceptor
 #[derive(Default)]
 pub struct ResponderBuilder {
     log2_size: Option<u8>,
@@ -76,11 +76,8 @@ impl ResponderInternal {
                 move |seq: u16| -> Pin<Box<dyn Future<Output = bool> + Send + 'static>> {
                     let stream3 = Arc::clone(&stream2);
                     Box::pin(async move {
-                        if let Some(p) = stream3.get(seq).await {
-                            let a = Attributes::new();
-                            if let Err(err) = stream3.next_rtp_writer.write(&p, &a).await {
-                                log::warn!("failed resending nacked packet: {}", err);
-                            }
+                        if let Err(err) = stream3.resend(seq).await {
+                            log::warn!("failed resending nacked packet: {}", err);
                         }
                         true
                     })
@@ -122,7 +119,9 @@ impl RTCPReader for ResponderRtcpReader {
     }
 }
 
-/// Responder responds to nack feedback messages
+/// Responder responds to nack feedback messages by resending the requested packets from a
+/// buffer of the recently sent ones. Streams with an RTX SSRC and payload type in their
+/// StreamInfo get the packets resent on the RTX stream, as described in RFC 4588.
 pub struct Responder {
     internal: Arc<ResponderInternal>,
 }
@@ -168,7 +167,7 @@ impl Interceptor for Responder {
             return writer;
         }
 
-        let stream = Arc::new(ResponderStream::new(self.internal.log2_size, writer));
+        let stream = Arc::new(ResponderStream::new(self.internal.log2_size, info, writer));
         {
             let mut streams = self.internal.streams.lock().await;
             streams.insert(info.ssrc, Arc::clone(&stream));
@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use rtp::rtx::RtxEncoder;
 use tokio::sync::Mutex;
 
 use crate::error::Result;
 use crate::nack::UINT16SIZE_HALF;
+use crate::stream_info::StreamInfo;
 use crate::{Attributes, RTPWriter};
 
 struct ResponderStreamInternal {
@@ -64,13 +66,29 @@ impl ResponderStreamInternal {
 
 pub(super) struct ResponderStream {
     internal: Mutex<ResponderStreamInternal>,
-    pub(super) next_rtp_writer: Arc<dyn RTPWriter + Send + Sync>,
+    /// rtx wraps the resent packets when the stream has a negotiated RTX stream
+    rtx: Option<Mutex<RtxEncoder>>,
+    next_rtp_writer: Arc<dyn RTPWriter + Send + Sync>,
 }
 
 impl ResponderStream {
-    pub(super) fn new(log2_size: u8, writer: Arc<dyn RTPWriter + Send + Sync>) -> Self {
+    pub(super) fn new(
+        log2_size: u8,
+        info: &StreamInfo,
+        writer: Arc<dyn RTPWriter + Send + Sync>,
+    ) -> Self {
+        let rtx = if info.ssrc_rtx != 0 && info.payload_type_rtx != 0 {
+            let mut encoder = RtxEncoder::default();
+            encoder.add_payload_type(info.payload_type, info.payload_type_rtx);
+            encoder.add_stream(info.ssrc, info.ssrc_rtx, rand::random::<u16>());
+            Some(Mutex::new(encoder))
+        } else {
+            None
+        };
+
         ResponderStream {
             internal: Mutex::new(ResponderStreamInternal::new(log2_size)),
+            rtx,
             next_rtp_writer: writer,
         }
     }
@@ -80,10 +98,28 @@ impl ResponderStream {
         internal.add(pkt);
     }
 
-    pub(super) async fn get(&self, seq: u16) -> Option<rtp::packet::Packet> {
+    async fn get(&self, seq: u16) -> Option<rtp::packet::Packet> {
         let internal = self.internal.lock().await;
         internal.get(seq).cloned()
     }
+
+    /// resend writes the buffered packet with sequence number seq again, on the RTX stream
+    /// if there is one. Packets no longer buffered are skipped.
+    pub(super) async fn resend(&self, seq: u16) -> Result<()> {
+        let packet = match self.get(seq).await {
+            Some(packet) => packet,
+            None => return Ok(()),
+        };
+        let packet = match &self.rtx {
+            Some(rtx) => rtx.lock().await.encapsulate(&packet)?,
+            None => packet,
+        };
+
+        self.next_rtp_writer
+            .write(&packet, &Attributes::new())
+            .await?;
+        Ok(())
+    }
 }
 
 /// RTPWriter is used by Interceptor.bind_local_stream.
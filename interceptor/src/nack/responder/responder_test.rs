@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use rtcp::transport_feedbacks::transport_layer_nack::{NackPair, TransportLayerNack};
 use tokio::time::Duration;
 
@@ -74,3 +75,85 @@ async fn test_responder_interceptor() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_responder_interceptor_rtx() -> Result<()> {
+    let icpr: Arc<dyn Interceptor + Send + Sync> =
+        Responder::builder().with_log2_size(3).build("")?;
+
+    let stream = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            payload_type: 96,
+            ssrc_rtx: 2,
+            payload_type_rtx: 97,
+            rtcp_feedback: vec![RTCPFeedback {
+                typ: "nack".to_owned(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    for seq_num in [10, 11, 12] {
+        stream
+            .write_rtp(&rtp::packet::Packet {
+                header: rtp::header::Header {
+                    ssrc: 1,
+                    payload_type: 96,
+                    sequence_number: seq_num,
+                    ..Default::default()
+                },
+                payload: Bytes::from_static(&[0xaa, 0xbb]),
+            })
+            .await?;
+
+        let p = timeout_or_fail(Duration::from_millis(10), stream.written_rtp())
+            .await
+            .expect("A packet");
+        assert_eq!(p.header.ssrc, 1);
+        assert_eq!(p.header.sequence_number, seq_num);
+    }
+
+    stream
+        .receive_rtcp(vec![Box::new(TransportLayerNack {
+            media_ssrc: 1,
+            sender_ssrc: 3,
+            nacks: vec![NackPair {
+                packet_id: 10,
+                lost_packets: 0b10,
+            }], // sequence numbers: 10, 12
+        })])
+        .await;
+
+    let mut rtx_seq_num: Option<u16> = None;
+    for seq_num in [10u16, 12] {
+        let p = timeout_or_fail(Duration::from_millis(50), stream.written_rtp())
+            .await
+            .expect("A retransmission");
+        assert_eq!(p.header.ssrc, 2);
+        assert_eq!(p.header.payload_type, 97);
+        let mut payload = seq_num.to_be_bytes().to_vec();
+        payload.extend_from_slice(&[0xaa, 0xbb]);
+        assert_eq!(
+            p.payload,
+            Bytes::from(payload),
+            "OSN should prepend the payload"
+        );
+
+        // RTX packets are numbered in their own sequence space
+        if let Some(prev) = rtx_seq_num {
+            assert_eq!(p.header.sequence_number, prev.wrapping_add(1));
+        }
+        rtx_seq_num = Some(p.header.sequence_number);
+    }
+
+    let result = tokio::time::timeout(Duration::from_millis(10), stream.written_rtp()).await;
+    assert!(result.is_err(), "no more rtp packets expected");
+
+    stream.close().await?;
+
+    Ok(())
+}
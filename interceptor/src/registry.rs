@@ -16,7 +16,8 @@ impl Registry {
         Registry { builders: vec![] }
     }
 
-    /// add adds a new InterceptorBuilder to the registry.
+    /// add adds a new InterceptorBuilder to the registry. The interceptors are chained in the
+    /// order their builders were added, see Chain.
     pub fn add(&mut self, builder: Box<dyn InterceptorBuilder + Send + Sync>) {
         self.builders.push(builder);
     }
@@ -0,0 +1,77 @@
+use super::*;
+
+#[test]
+fn test_rtcp_interval_minimum() {
+    // 64 kbit/s leaves 400 octets/s for RTCP.
+    let mut interval = RtcpInterval::new(64_000);
+    interval.set_members(2, 1);
+
+    // Before the first report the minimum is halved.
+    assert_eq!(
+        interval.deterministic_interval(),
+        Duration::from_millis(2500)
+    );
+
+    interval.on_rtcp_sent(128);
+    assert_eq!(interval.deterministic_interval(), RTCP_MIN_INTERVAL);
+
+    let interval = interval.with_min_interval(Duration::from_secs(1));
+    assert_eq!(interval.deterministic_interval(), Duration::from_secs(1));
+}
+
+#[test]
+fn test_rtcp_interval_scales_with_members() {
+    let mut interval = RtcpInterval::new(64_000);
+    interval.on_rtcp_sent(128);
+
+    // Receivers share 75% of 400 octets/s: 100 * 128 / 300.
+    interval.set_members(100, 0);
+    assert_eq!(
+        interval.deterministic_interval(),
+        Duration::from_secs_f64(12800.0 / 300.0)
+    );
+
+    // A sender among few senders shares 25% of 400 octets/s with the other sender.
+    interval.set_members(100, 2);
+    interval.set_we_sent(true);
+    assert_eq!(interval.deterministic_interval(), RTCP_MIN_INTERVAL);
+    let interval = interval.with_min_interval(Duration::ZERO);
+    assert_eq!(
+        interval.deterministic_interval(),
+        Duration::from_secs_f64(256.0 / 100.0)
+    );
+
+    // Once senders exceed 25% of the members all share the whole RTCP bandwidth.
+    let mut interval = interval;
+    interval.set_members(100, 50);
+    assert_eq!(
+        interval.deterministic_interval(),
+        Duration::from_secs_f64(12800.0 / 400.0)
+    );
+}
+
+#[test]
+fn test_rtcp_interval_randomization() {
+    let mut interval = RtcpInterval::new(64_000);
+    interval.set_members(100, 0);
+    let td = interval.deterministic_interval();
+
+    for _ in 0..100 {
+        let t = interval.next_interval();
+        assert!(t >= td.mul_f64(0.5 / COMPENSATION), "{t:?} too short");
+        assert!(t <= td.mul_f64(1.5 / COMPENSATION), "{t:?} too long");
+    }
+
+    assert_eq!(interval.reconsider(td.mul_f64(1.5 / COMPENSATION)), None);
+    let remaining = interval.reconsider(Duration::ZERO).unwrap();
+    assert!(remaining >= td.mul_f64(0.5 / COMPENSATION));
+}
+
+#[test]
+fn test_rtcp_interval_avg_size() {
+    let mut interval = RtcpInterval::new(64_000);
+    assert_eq!(interval.avg_rtcp_size(), RTCP_INITIAL_AVG_SIZE);
+
+    interval.on_rtcp_received(256);
+    assert_eq!(interval.avg_rtcp_size(), 136.0);
+}
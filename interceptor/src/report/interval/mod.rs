@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod interval_test;
+
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Fraction of the session bandwidth allocated to RTCP, see RFC 3550 section 6.2.
+pub const RTCP_BANDWIDTH_FRACTION: f64 = 0.05;
+/// Fraction of the RTCP bandwidth shared by senders when they are at most this
+/// fraction of the members, see RFC 3550 section 6.2.
+pub const RTCP_SENDER_BANDWIDTH_FRACTION: f64 = 0.25;
+/// Recommended minimum interval between RTCP reports, see RFC 3550 section 6.2.
+pub const RTCP_MIN_INTERVAL: Duration = Duration::from_secs(5);
+/// Average RTCP compound packet size assumed before any report has been sent,
+/// including UDP and IPv4 headers.
+pub const RTCP_INITIAL_AVG_SIZE: f64 = 128.0;
+
+/// Size of the IPv4 and UDP headers accounted to every RTCP packet sent.
+pub const IP_UDP_HEADER_SIZE: usize = 28;
+
+/// Compensation for the "timer reconsideration" converging to a value below
+/// the intended average, e - 3/2 (RFC 3550 appendix A.7).
+const COMPENSATION: f64 = std::f64::consts::E - 1.5;
+
+/// RtcpInterval computes the RTCP transmission interval as specified in
+/// RFC 3550 section 6.3, so that reports scale with the session bandwidth and
+/// the number of participants instead of being sent at a fixed period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtcpInterval {
+    /// rtcp_bandwidth is the RTCP bandwidth in octets per second.
+    rtcp_bandwidth: f64,
+    min_interval: Duration,
+    members: usize,
+    senders: usize,
+    we_sent: bool,
+    avg_rtcp_size: f64,
+    initial: bool,
+}
+
+impl RtcpInterval {
+    /// new creates an RtcpInterval for a session bandwidth given in bits per
+    /// second, 5% of which is allocated to RTCP.
+    pub fn new(session_bandwidth: u64) -> Self {
+        RtcpInterval {
+            rtcp_bandwidth: session_bandwidth as f64 * RTCP_BANDWIDTH_FRACTION / 8.0,
+            min_interval: RTCP_MIN_INTERVAL,
+            members: 1,
+            senders: 0,
+            we_sent: false,
+            avg_rtcp_size: RTCP_INITIAL_AVG_SIZE,
+            initial: true,
+        }
+    }
+
+    /// with_min_interval overrides the minimum interval of 5 seconds, e.g. with
+    /// the reduced minimum of 360 / session bandwidth in kbit/s.
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// set_members updates the number of members and senders of the session.
+    /// The local participant is counted as a member, and as a sender if it has
+    /// sent RTP data.
+    pub fn set_members(&mut self, members: usize, senders: usize) {
+        self.members = members.max(1);
+        self.senders = senders.min(self.members);
+    }
+
+    /// set_we_sent records whether the local participant has sent RTP data since
+    /// the second previous report was transmitted.
+    pub fn set_we_sent(&mut self, we_sent: bool) {
+        self.we_sent = we_sent;
+    }
+
+    /// avg_rtcp_size returns the running average of the RTCP packet size in octets.
+    pub fn avg_rtcp_size(&self) -> f64 {
+        self.avg_rtcp_size
+    }
+
+    /// on_rtcp_sent updates the average RTCP packet size with a compound packet
+    /// sent by the local participant. The size should include UDP and IP headers.
+    pub fn on_rtcp_sent(&mut self, size: usize) {
+        self.update_avg_rtcp_size(size);
+        self.initial = false;
+    }
+
+    /// on_rtcp_received updates the average RTCP packet size with a compound
+    /// packet received from another participant. The size should include UDP and
+    /// IP headers.
+    pub fn on_rtcp_received(&mut self, size: usize) {
+        self.update_avg_rtcp_size(size);
+    }
+
+    fn update_avg_rtcp_size(&mut self, size: usize) {
+        self.avg_rtcp_size = size as f64 / 16.0 + self.avg_rtcp_size * 15.0 / 16.0;
+    }
+
+    /// deterministic_interval returns the calculated interval Td before
+    /// randomization. Before the first report is sent the minimum is halved.
+    pub fn deterministic_interval(&self) -> Duration {
+        let min_interval = if self.initial {
+            self.min_interval.as_secs_f64() / 2.0
+        } else {
+            self.min_interval.as_secs_f64()
+        };
+
+        let mut rtcp_bandwidth = self.rtcp_bandwidth;
+        let mut n = self.members;
+        if self.senders as f64 <= self.members as f64 * RTCP_SENDER_BANDWIDTH_FRACTION {
+            if self.we_sent {
+                rtcp_bandwidth *= RTCP_SENDER_BANDWIDTH_FRACTION;
+                n = self.senders;
+            } else {
+                rtcp_bandwidth *= 1.0 - RTCP_SENDER_BANDWIDTH_FRACTION;
+                n -= self.senders;
+            }
+        }
+
+        let t = if rtcp_bandwidth > 0.0 {
+            self.avg_rtcp_size * n as f64 / rtcp_bandwidth
+        } else {
+            f64::INFINITY
+        };
+
+        Duration::from_secs_f64(t.max(min_interval).min(u32::MAX as f64))
+    }
+
+    /// next_interval returns the randomized interval until the next report,
+    /// uniformly distributed between 0.5 and 1.5 times the deterministic interval
+    /// and divided by e - 3/2 to compensate for timer reconsideration.
+    pub fn next_interval(&self) -> Duration {
+        self.interval_with_factor(rand::thread_rng().gen_range(0.5..1.5))
+    }
+
+    /// reconsider implements timer reconsideration: when the transmission timer
+    /// expires `elapsed` after the previous report, a new interval is computed
+    /// with the current state. It returns None if the report should be sent now,
+    /// or the remaining time to wait otherwise.
+    pub fn reconsider(&self, elapsed: Duration) -> Option<Duration> {
+        let interval = self.next_interval();
+        if elapsed >= interval {
+            None
+        } else {
+            Some(interval - elapsed)
+        }
+    }
+
+    fn interval_with_factor(&self, factor: f64) -> Duration {
+        self.deterministic_interval().mul_f64(factor / COMPENSATION)
+    }
+}
@@ -5,9 +5,11 @@ use std::time::{Duration, SystemTime};
 use tokio::sync::{mpsc, Mutex};
 use waitgroup::WaitGroup;
 
+pub mod interval;
 pub mod receiver;
 pub mod sender;
 
+use interval::{RtcpInterval, IP_UDP_HEADER_SIZE};
 use receiver::{ReceiverReport, ReceiverReportInternal};
 use sender::{SenderReport, SenderReportInternal};
 
@@ -21,6 +23,7 @@ type FnTimeGen = Arc<dyn Fn() -> SystemTime + Sync + 'static + Send>;
 pub struct ReportBuilder {
     is_rr: bool,
     interval: Option<Duration>,
+    session_bandwidth: Option<u64>,
     now: Option<FnTimeGen>,
 }
 
@@ -31,6 +34,14 @@ impl ReportBuilder {
         self
     }
 
+    /// with_session_bandwidth sets the session bandwidth in bits per second. When set,
+    /// reports are sent at the interval computed per RFC 3550 section 6.3 instead of
+    /// the fixed send interval.
+    pub fn with_session_bandwidth(mut self, session_bandwidth: u64) -> ReportBuilder {
+        self.session_bandwidth = Some(session_bandwidth);
+        self
+    }
+
     /// with_now_fn sets an alternative for the time.Now function.
     pub fn with_now_fn(mut self, now: FnTimeGen) -> ReportBuilder {
         self.now = Some(now);
@@ -41,11 +52,13 @@ impl ReportBuilder {
         let (close_tx, close_rx) = mpsc::channel(1);
         ReceiverReport {
             internal: Arc::new(ReceiverReportInternal {
+                receiver_ssrc: rand::random::<u32>(),
                 interval: if let Some(interval) = &self.interval {
                     *interval
                 } else {
                     Duration::from_secs(1)
                 },
+                rtcp_interval: self.session_bandwidth.map(RtcpInterval::new),
                 now: self.now.clone(),
                 streams: Mutex::new(HashMap::new()),
                 close_rx: Mutex::new(Some(close_rx)),
@@ -65,6 +78,7 @@ impl ReportBuilder {
                 } else {
                     Duration::from_secs(1)
                 },
+                rtcp_interval: self.session_bandwidth.map(RtcpInterval::new),
                 now: self.now.clone(),
                 streams: Mutex::new(HashMap::new()),
                 close_rx: Mutex::new(Some(close_rx)),
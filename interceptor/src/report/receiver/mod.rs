@@ -6,7 +6,9 @@ use std::collections::HashMap;
 use std::time::{Duration, SystemTime};
 
 use receiver_stream::ReceiverStream;
+use rtcp::header::COUNT_MAX;
 use tokio::sync::{mpsc, Mutex};
+use util::MarshalSize;
 use waitgroup::WaitGroup;
 
 use super::*;
@@ -14,7 +16,10 @@ use crate::error::Error;
 use crate::*;
 
 pub(crate) struct ReceiverReportInternal {
+    /// receiver_ssrc is the SSRC all receiver reports of the interceptor are sent with
+    pub(crate) receiver_ssrc: u32,
     pub(crate) interval: Duration,
+    pub(crate) rtcp_interval: Option<RtcpInterval>,
     pub(crate) now: Option<FnTimeGen>,
     pub(crate) streams: Mutex<HashMap<u32, Arc<ReceiverStream>>>,
     pub(crate) close_rx: Mutex<Option<mpsc::Receiver<()>>>,
@@ -59,7 +64,8 @@ impl RTCPReader for ReceiverReportRtcpReader {
     }
 }
 
-/// ReceiverReport interceptor generates receiver reports.
+/// ReceiverReport interceptor generates receiver reports. The reception reports of all remote
+/// streams are sent together, with the same SSRC.
 pub struct ReceiverReport {
     pub(crate) internal: Arc<ReceiverReportInternal>,
 
@@ -86,6 +92,11 @@ impl ReceiverReport {
         internal: Arc<ReceiverReportInternal>,
     ) -> Result<()> {
         let mut ticker = tokio::time::interval(internal.interval);
+        let mut rtcp_interval = internal.rtcp_interval.clone();
+        if let Some(rtcp_interval) = &rtcp_interval {
+            ticker.reset_after(rtcp_interval.next_interval());
+        }
+        let mut last_report = tokio::time::Instant::now();
         let mut close_rx = {
             let mut close_rx = internal.close_rx.lock().await;
             if let Some(close) = close_rx.take() {
@@ -109,14 +120,38 @@ impl ReceiverReport {
                         let m = internal.streams.lock().await;
                         m.values().cloned().collect()
                     };
-                    for stream in streams {
-                        let pkt = stream.generate_report(now);
+                    if let Some(rtcp_interval) = &mut rtcp_interval {
+                        rtcp_interval.set_members(streams.len() + 1, streams.len());
+                        rtcp_interval.set_we_sent(false);
+                        if let Some(remaining) = rtcp_interval.reconsider(last_report.elapsed()) {
+                            ticker.reset_after(remaining);
+                            continue;
+                        }
+                    }
+                    let reports: Vec<rtcp::reception_report::ReceptionReport> = streams
+                        .iter()
+                        .map(|stream| stream.generate_report(now))
+                        .collect();
+                    // A receiver report holds at most 31 reception reports
+                    for reports in reports.chunks(COUNT_MAX) {
+                        let pkt = rtcp::receiver_report::ReceiverReport {
+                            ssrc: internal.receiver_ssrc,
+                            reports: reports.to_vec(),
+                            ..Default::default()
+                        };
+                        let size = pkt.marshal_size();
 
                         let a = Attributes::new();
                         if let Err(err) = rtcp_writer.write(&[Box::new(pkt)], &a).await{
                             log::warn!("failed sending: {}", err);
+                        } else if let Some(rtcp_interval) = &mut rtcp_interval {
+                            rtcp_interval.on_rtcp_sent(size + IP_UDP_HEADER_SIZE);
                         }
                     }
+                    if let Some(rtcp_interval) = &rtcp_interval {
+                        ticker.reset_after(rtcp_interval.next_interval());
+                    }
+                    last_report = tokio::time::Instant::now();
                 }
                 _ = close_rx.recv() =>{
                     return Ok(());
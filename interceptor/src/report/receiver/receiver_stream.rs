@@ -8,7 +8,6 @@ use crate::{Attributes, RTPReader};
 
 struct ReceiverStreamInternal {
     ssrc: u32,
-    receiver_ssrc: u32,
     clock_rate: f64,
 
     packets: Vec<u64>,
@@ -86,7 +85,7 @@ impl ReceiverStreamInternal {
         self.last_sender_report_time = now;
     }
 
-    fn generate_report(&mut self, now: SystemTime) -> rtcp::receiver_report::ReceiverReport {
+    fn generate_report(&mut self, now: SystemTime) -> rtcp::reception_report::ReceptionReport {
         let total_since_report = (self.last_seq_num - self.last_report_seq_num) as u16;
         let mut total_lost_since_report = {
             if self.last_seq_num == self.last_report_seq_num {
@@ -114,29 +113,24 @@ impl ReceiverStreamInternal {
             self.total_lost = 0xFFFFFF
         }
 
-        let r = rtcp::receiver_report::ReceiverReport {
-            ssrc: self.receiver_ssrc,
-            reports: vec![rtcp::reception_report::ReceptionReport {
-                ssrc: self.ssrc,
-                last_sequence_number: (self.seq_num_cycles as u32) << 16
-                    | (self.last_seq_num as u32),
-                last_sender_report: self.last_sender_report,
-                fraction_lost: ((total_lost_since_report * 256) as f64 / total_since_report as f64)
-                    as u8,
-                total_lost: self.total_lost,
-                delay: {
-                    if self.last_sender_report_time == SystemTime::UNIX_EPOCH {
-                        0
-                    } else {
-                        match now.duration_since(self.last_sender_report_time) {
-                            Ok(d) => (d.as_secs_f64() * 65536.0) as u32,
-                            Err(_) => 0,
-                        }
+        let r = rtcp::reception_report::ReceptionReport {
+            ssrc: self.ssrc,
+            last_sequence_number: (self.seq_num_cycles as u32) << 16 | (self.last_seq_num as u32),
+            last_sender_report: self.last_sender_report,
+            fraction_lost: ((total_lost_since_report * 256) as f64 / total_since_report as f64)
+                as u8,
+            total_lost: self.total_lost,
+            delay: {
+                if self.last_sender_report_time == SystemTime::UNIX_EPOCH {
+                    0
+                } else {
+                    match now.duration_since(self.last_sender_report_time) {
+                        Ok(d) => (d.as_secs_f64() * 65536.0) as u32,
+                        Err(_) => 0,
                     }
-                },
-                jitter: self.jitter as u32,
-            }],
-            ..Default::default()
+                }
+            },
+            jitter: self.jitter as u32,
         };
 
         self.last_report_seq_num = self.last_seq_num;
@@ -159,14 +153,12 @@ impl ReceiverStream {
         reader: Arc<dyn RTPReader + Send + Sync>,
         now: Option<FnTimeGen>,
     ) -> Self {
-        let receiver_ssrc = rand::random::<u32>();
         ReceiverStream {
             parent_rtp_reader: reader,
             now,
 
             internal: Mutex::new(ReceiverStreamInternal {
                 ssrc,
-                receiver_ssrc,
                 clock_rate: clock_rate as f64,
 
                 packets: vec![0u64; 128],
@@ -198,7 +190,10 @@ impl ReceiverStream {
         internal.process_sender_report(now, sr);
     }
 
-    pub(crate) fn generate_report(&self, now: SystemTime) -> rtcp::receiver_report::ReceiverReport {
+    pub(crate) fn generate_report(
+        &self,
+        now: SystemTime,
+    ) -> rtcp::reception_report::ReceptionReport {
         let mut internal = self.internal.lock();
         internal.generate_report(now)
     }
@@ -770,3 +770,62 @@ async fn test_receiver_interceptor_delay() -> Result<()> {
     stream.close().await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_receiver_interceptor_multiple_streams() -> Result<()> {
+    let icpr: Arc<dyn Interceptor + Send + Sync> = ReceiverReport::builder()
+        .with_interval(Duration::from_millis(50))
+        .build("")?;
+
+    let stream1 = MockStream::new(
+        &StreamInfo {
+            ssrc: 1,
+            clock_rate: 90000,
+            ..Default::default()
+        },
+        Arc::clone(&icpr),
+    )
+    .await;
+    let stream2 = MockStream::new(
+        &StreamInfo {
+            ssrc: 2,
+            clock_rate: 90000,
+            ..Default::default()
+        },
+        icpr,
+    )
+    .await;
+
+    // The first report might have been generated before the second stream was bound
+    let mut rr = None;
+    for _ in 0..3 {
+        let pkts = stream1.written_rtcp().await.unwrap();
+        assert_eq!(pkts.len(), 1);
+        let report = pkts[0]
+            .as_any()
+            .downcast_ref::<rtcp::receiver_report::ReceiverReport>()
+            .cloned()
+            .unwrap();
+        if report.reports.len() == 2 {
+            rr = Some(report);
+            break;
+        }
+    }
+
+    let rr = rr.expect("reception reports of both streams in one receiver report");
+    let mut ssrcs: Vec<u32> = rr.reports.iter().map(|r| r.ssrc).collect();
+    ssrcs.sort_unstable();
+    assert_eq!(ssrcs, vec![1, 2]);
+
+    let pkts = stream1.written_rtcp().await.unwrap();
+    let next = pkts[0]
+        .as_any()
+        .downcast_ref::<rtcp::receiver_report::ReceiverReport>()
+        .unwrap();
+    assert_eq!(next.ssrc, rr.ssrc, "reports should keep the receiver SSRC");
+
+    stream1.close().await?;
+    stream2.close().await?;
+
+    Ok(())
+}
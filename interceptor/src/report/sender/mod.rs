@@ -7,6 +7,7 @@ use std::time::{Duration, SystemTime};
 
 use sender_stream::SenderStream;
 use tokio::sync::{mpsc, Mutex};
+use util::MarshalSize;
 use waitgroup::WaitGroup;
 
 use super::*;
@@ -15,6 +16,7 @@ use crate::*;
 
 pub(crate) struct SenderReportInternal {
     pub(crate) interval: Duration,
+    pub(crate) rtcp_interval: Option<RtcpInterval>,
     pub(crate) now: Option<FnTimeGen>,
     pub(crate) streams: Mutex<HashMap<u32, Arc<SenderStream>>>,
     pub(crate) close_rx: Mutex<Option<mpsc::Receiver<()>>>,
@@ -47,6 +49,11 @@ impl SenderReport {
         internal: Arc<SenderReportInternal>,
     ) -> Result<()> {
         let mut ticker = tokio::time::interval(internal.interval);
+        let mut rtcp_interval = internal.rtcp_interval.clone();
+        if let Some(rtcp_interval) = &rtcp_interval {
+            ticker.reset_after(rtcp_interval.next_interval());
+        }
+        let mut last_report = tokio::time::Instant::now();
         let mut close_rx = {
             let mut close_rx = internal.close_rx.lock().await;
             if let Some(close) = close_rx.take() {
@@ -69,14 +76,29 @@ impl SenderReport {
                         let m = internal.streams.lock().await;
                         m.values().cloned().collect()
                     };
+                    if let Some(rtcp_interval) = &mut rtcp_interval {
+                        rtcp_interval.set_members(streams.len() + 1, streams.len());
+                        rtcp_interval.set_we_sent(!streams.is_empty());
+                        if let Some(remaining) = rtcp_interval.reconsider(last_report.elapsed()) {
+                            ticker.reset_after(remaining);
+                            continue;
+                        }
+                    }
                     for stream in streams {
                         let pkt = stream.generate_report(now).await;
+                        let size = pkt.marshal_size();
 
                         let a = Attributes::new();
                         if let Err(err) = rtcp_writer.write(&[Box::new(pkt)], &a).await{
                             log::warn!("failed sending: {}", err);
+                        } else if let Some(rtcp_interval) = &mut rtcp_interval {
+                            rtcp_interval.on_rtcp_sent(size + IP_UDP_HEADER_SIZE);
                         }
                     }
+                    if let Some(rtcp_interval) = &rtcp_interval {
+                        ticker.reset_after(rtcp_interval.next_interval());
+                    }
+                    last_report = tokio::time::Instant::now();
                 }
                 _ = close_rx.recv() =>{
                     return Ok(());
@@ -4,7 +4,7 @@ use std::sync::Arc;
 use std::time::SystemTime;
 
 use async_trait::async_trait;
-use rtcp::extended_report::{DLRRReportBlock, ExtendedReport};
+use rtcp::extended_report::{DLRRReportBlock, ExtendedReport, StatisticsSummaryReportBlock};
 use rtcp::payload_feedbacks::full_intra_request::FullIntraRequest;
 use rtcp::payload_feedbacks::picture_loss_indication::PictureLossIndication;
 use rtcp::receiver_report::ReceiverReport;
@@ -45,6 +45,10 @@ enum StatsUpdate {
         header_bytes: u64,
         payload_bytes: u64,
         last_packet_timestamp: SystemTime,
+        /// The highest extended sequence number received so far.
+        ext_seq_num: u32,
+        /// The interarrival jitter in seconds.
+        jitter: f64,
     },
     /// Stats collected on the sending end(outbound) of an RTP stream.
     OutboundRTP {
@@ -52,6 +56,8 @@ enum StatsUpdate {
         header_bytes: u64,
         payload_bytes: u64,
         last_packet_timestamp: SystemTime,
+        /// The sequence number of the sent packet.
+        seq_num: u16,
     },
     /// Stats collected from received RTCP packets.
     InboundRTCP {
@@ -75,6 +81,12 @@ enum StatsUpdate {
         rtt_ms: Option<f64>,
         fraction_lost: u8,
     },
+    /// Stats collected from received Statistics Summary extended report blocks i.e. where we have
+    /// an outbound RTP stream.
+    InboundStatisticsSummary {
+        fraction_lost: Option<u8>,
+        jitter: Option<u32>,
+    },
     /// Stats collected from received Sender Reports i.e. where we have an inbound RTP stream.
     InboundSenderRerport {
         packets_and_bytes_sent: Option<(u32, u32)>,
@@ -219,12 +231,15 @@ fn handle_stats_update(ssrc_stats: &mut StatsContainer, ssrc: u32, update: Stats
             header_bytes,
             payload_bytes,
             last_packet_timestamp,
+            ext_seq_num,
+            jitter,
         } => {
             let stats = ssrc_stats.get_or_create_inbound_stream_stats(ssrc);
 
             stats
                 .rtp_stats
                 .update(header_bytes, payload_bytes, packets, last_packet_timestamp);
+            stats.record_reception(ext_seq_num, jitter);
             stats.mark_updated();
         }
         StatsUpdate::OutboundRTP {
@@ -232,11 +247,15 @@ fn handle_stats_update(ssrc_stats: &mut StatsContainer, ssrc: u32, update: Stats
             header_bytes,
             payload_bytes,
             last_packet_timestamp,
+            seq_num,
         } => {
             let stats = ssrc_stats.get_or_create_outbound_stream_stats(ssrc);
             stats
                 .rtp_stats
                 .update(header_bytes, payload_bytes, packets, last_packet_timestamp);
+            // The remote extends the sequence numbers starting from the first packet it
+            // receives, so the first packet sent has no rollovers.
+            stats.record_initial_ext_seq_num(seq_num as u32);
             stats.mark_updated();
         }
         StatsUpdate::InboundRTCP {
@@ -259,7 +278,7 @@ fn handle_stats_update(ssrc_stats: &mut StatsContainer, ssrc: u32, update: Stats
         }
         StatsUpdate::OutboundSRExtSeqNum { seq_num } => {
             let stats = ssrc_stats.get_or_create_outbound_stream_stats(ssrc);
-            stats.record_sr_ext_seq_num(seq_num);
+            stats.record_initial_ext_seq_num(seq_num);
             stats.mark_updated();
         }
         StatsUpdate::InboundReceiverReport {
@@ -278,6 +297,20 @@ fn handle_stats_update(ssrc_stats: &mut StatsContainer, ssrc: u32, update: Stats
 
             stats.mark_updated();
         }
+        StatsUpdate::InboundStatisticsSummary {
+            fraction_lost,
+            jitter,
+        } => {
+            let stats = ssrc_stats.get_or_create_outbound_stream_stats(ssrc);
+            if let Some(fraction_lost) = fraction_lost {
+                stats.update_remote_fraction_lost(fraction_lost);
+            }
+            if let Some(jitter) = jitter {
+                stats.update_remote_jitter(jitter);
+            }
+
+            stats.mark_updated();
+        }
         StatsUpdate::InboundSenderRerport {
             rtt_ms,
             packets_and_bytes_sent,
@@ -307,9 +340,13 @@ impl Interceptor for StatsInterceptor {
     ) -> Arc<dyn RTPReader + Send + Sync> {
         let mut lock = self.recv_streams.lock();
 
-        let e = lock
-            .entry(info.ssrc)
-            .or_insert_with(|| Arc::new(RTPReadRecorder::new(reader, self.tx.clone())));
+        let e = lock.entry(info.ssrc).or_insert_with(|| {
+            Arc::new(RTPReadRecorder::new(
+                reader,
+                info.clock_rate,
+                self.tx.clone(),
+            ))
+        });
 
         e.clone()
     }
@@ -424,6 +461,14 @@ where
             fraction_lost: u8,
         }
 
+        #[derive(Default, Debug)]
+        struct StatisticsSummaryEntry {
+            /// Fraction of packets lost in the summarized sequence number interval.
+            fraction_lost: Option<u8>,
+            /// Mean jitter in the summarized sequence number interval.
+            jitter: Option<u32>,
+        }
+
         #[derive(Default, Debug)]
         struct SenderReportEntry {
             /// NTP timestamp(from Sender Report).
@@ -442,6 +487,7 @@ where
         struct Entry {
             generic_rtcp: GenericRTCP,
             receiver_reports: Vec<ReceiverReportEntry>,
+            statistics_summaries: Vec<StatisticsSummaryEntry>,
             sender_reports: Vec<SenderReportEntry>,
         }
         let updates = pkts
@@ -530,6 +576,29 @@ where
                         sr_e.dlrr_last_rr = Some(dlrr.last_rr);
                         sr_e.dlrr_delay_rr = Some(dlrr.dlrr);
                     }
+
+                    // Statistics Summary reports of the remote about our outbound streams
+                    let summaries = xr.reports.iter().filter_map(|report| {
+                        report
+                            .as_any()
+                            .downcast_ref::<StatisticsSummaryReportBlock>()
+                    });
+
+                    for summary in summaries {
+                        // end_seq is the last sequence number in the interval plus one
+                        let expected = summary.end_seq.wrapping_sub(summary.begin_seq) as u32;
+                        let fraction_lost = (summary.loss_reports && expected != 0).then(|| {
+                            // Same fixed point representation as the fraction lost of an RR
+                            ((summary.lost_packets.min(expected) as u64 * 256 / expected as u64)
+                                .min(255)) as u8
+                        });
+
+                        let e = acc.entry(summary.ssrc).or_default();
+                        e.statistics_summaries.push(StatisticsSummaryEntry {
+                            fraction_lost,
+                            jitter: summary.jitter_reports.then_some(summary.mean_jitter),
+                        });
+                    }
                 }
 
                 acc
@@ -540,6 +609,7 @@ where
             Entry {
                 generic_rtcp,
                 mut receiver_reports,
+                statistics_summaries,
                 mut sender_reports,
             },
         ) in updates.into_iter()
@@ -579,6 +649,19 @@ where
                 let _ = fut.await;
             }
 
+            for summary in statistics_summaries {
+                let _ = self
+                    .tx
+                    .send(Message::StatUpdate {
+                        ssrc,
+                        update: StatsUpdate::InboundStatisticsSummary {
+                            fraction_lost: summary.fraction_lost,
+                            jitter: summary.jitter,
+                        },
+                    })
+                    .await;
+            }
+
             let futures = sender_reports.into_iter().map(|sr| {
                 let rtt_ms = match (sr.dlrr_last_rr, sr.dlrr_delay_rr, sr.sr_packets_sent) {
                     (Some(last_rr), Some(delay_rr), Some(_)) if last_rr != 0 && delay_rr != 0 => {
@@ -703,14 +786,78 @@ where
     }
 }
 
+/// Tracks the sequence numbers and the interarrival jitter of a received RTP stream.
+#[derive(Debug, Default)]
+struct ReceptionState {
+    started: bool,
+    cycles: u32,
+    last_seq_num: u16,
+    last_timestamp: u32,
+    last_arrival: Option<SystemTime>,
+    /// The interarrival jitter in timestamp units.
+    jitter: f64,
+}
+
+impl ReceptionState {
+    /// Records a packet received at `arrival` and returns the highest extended sequence number
+    /// received so far.
+    fn update(
+        &mut self,
+        header: &rtp::header::Header,
+        arrival: SystemTime,
+        clock_rate: u32,
+    ) -> u32 {
+        if !self.started {
+            self.started = true;
+            self.last_seq_num = header.sequence_number;
+        } else {
+            let diff = header.sequence_number.wrapping_sub(self.last_seq_num);
+            if diff != 0 && diff < 1 << 15 {
+                if header.sequence_number < self.last_seq_num {
+                    self.cycles += 1 << 16;
+                }
+                self.last_seq_num = header.sequence_number;
+            }
+        }
+
+        // https://datatracker.ietf.org/doc/html/rfc3550#appendix-A.8
+        if let Some(last_arrival) = self.last_arrival {
+            if clock_rate != 0 {
+                let arrival_delta = match arrival.duration_since(last_arrival) {
+                    Ok(d) => d.as_secs_f64(),
+                    Err(e) => -e.duration().as_secs_f64(),
+                };
+                let timestamp_delta = header.timestamp.wrapping_sub(self.last_timestamp) as i32;
+                let d = arrival_delta * clock_rate as f64 - timestamp_delta as f64;
+                self.jitter += (d.abs() - self.jitter) / 16.0;
+            }
+        }
+        self.last_timestamp = header.timestamp;
+        self.last_arrival = Some(arrival);
+
+        self.cycles | self.last_seq_num as u32
+    }
+}
+
 pub struct RTPReadRecorder {
     rtp_reader: Arc<dyn RTPReader + Send + Sync>,
+    clock_rate: u32,
+    reception: Mutex<ReceptionState>,
     tx: mpsc::Sender<Message>,
 }
 
 impl RTPReadRecorder {
-    fn new(rtp_reader: Arc<dyn RTPReader + Send + Sync>, tx: mpsc::Sender<Message>) -> Self {
-        Self { rtp_reader, tx }
+    fn new(
+        rtp_reader: Arc<dyn RTPReader + Send + Sync>,
+        clock_rate: u32,
+        tx: mpsc::Sender<Message>,
+    ) -> Self {
+        Self {
+            rtp_reader,
+            clock_rate,
+            reception: Mutex::new(ReceptionState::default()),
+            tx,
+        }
     }
 }
 
@@ -729,6 +876,18 @@ impl RTPReader for RTPReadRecorder {
     ) -> Result<(rtp::packet::Packet, Attributes)> {
         let (pkt, attributes) = self.rtp_reader.read(buf, attributes).await?;
 
+        let now = SystemTime::now();
+        let (ext_seq_num, jitter) = {
+            let mut reception = self.reception.lock();
+            let ext_seq_num = reception.update(&pkt.header, now, self.clock_rate);
+            let jitter = if self.clock_rate != 0 {
+                reception.jitter / self.clock_rate as f64
+            } else {
+                0.0
+            };
+            (ext_seq_num, jitter)
+        };
+
         let _ = self
             .tx
             .send(Message::StatUpdate {
@@ -737,7 +896,9 @@ impl RTPReader for RTPReadRecorder {
                     packets: 1,
                     header_bytes: pkt.header.marshal_size() as u64,
                     payload_bytes: pkt.payload.len() as u64,
-                    last_packet_timestamp: SystemTime::now(),
+                    last_packet_timestamp: now,
+                    ext_seq_num,
+                    jitter,
                 },
             })
             .await;
@@ -778,6 +939,7 @@ impl RTPWriter for RTPWriteRecorder {
                     header_bytes: pkt.header.marshal_size() as u64,
                     payload_bytes: pkt.payload.len() as u64,
                     last_packet_timestamp: SystemTime::now(),
+                    seq_num: pkt.header.sequence_number,
                 },
             })
             .await;
@@ -840,7 +1002,9 @@ mod test {
     use std::time::{Duration, SystemTime};
 
     use bytes::Bytes;
-    use rtcp::extended_report::{DLRRReport, DLRRReportBlock, ExtendedReport};
+    use rtcp::extended_report::{
+        DLRRReport, DLRRReportBlock, ExtendedReport, StatisticsSummaryReportBlock,
+    };
     use rtcp::payload_feedbacks::full_intra_request::{FirEntry, FullIntraRequest};
     use rtcp::payload_feedbacks::picture_loss_indication::PictureLossIndication;
     use rtcp::receiver_report::ReceiverReport;
@@ -848,7 +1012,7 @@ mod test {
     use rtcp::sender_report::SenderReport;
     use rtcp::transport_feedbacks::transport_layer_nack::{NackPair, TransportLayerNack};
 
-    use super::StatsInterceptor;
+    use super::{ReceptionState, StatsInterceptor};
     use crate::error::Result;
     use crate::mock::mock_stream::MockStream;
     use crate::stream_info::StreamInfo;
@@ -929,6 +1093,178 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_stats_interceptor_remote_packets_received() -> Result<()> {
+        let icpr: Arc<_> = Arc::new(StatsInterceptor::new("Hello".to_owned()));
+
+        let send_stream = MockStream::new(
+            &StreamInfo {
+                ssrc: 234567,
+                ..Default::default()
+            },
+            icpr.clone(),
+        )
+        .await;
+
+        for sequence_number in [65534, 65535, 0, 1] {
+            let _ = send_stream
+                .write_rtp(&rtp::packet::Packet {
+                    header: rtp::header::Header {
+                        ssrc: 234567,
+                        sequence_number,
+                        ..Default::default()
+                    },
+                    payload: Bytes::from_static(&[0x13, 0x37]),
+                    ..Default::default()
+                })
+                .await;
+        }
+
+        send_stream
+            .receive_rtcp(vec![Box::new(ReceiverReport {
+                reports: vec![ReceptionReport {
+                    ssrc: 234567,
+                    // One rollover since the first packet
+                    last_sequence_number: (1 << 16) | 1,
+                    total_lost: 1,
+                    jitter: 450,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })])
+            .await;
+        let _ = send_stream.read_rtcp().await.expect("read_rtcp failed");
+
+        let snapshots = icpr.fetch_outbound_stats(vec![234567]).await;
+        let send_snapshot = snapshots[0]
+            .as_ref()
+            .expect("Outbound Stats should exist for ssrc: 234567");
+        assert_eq!(send_snapshot.remote_packets_received(), 3);
+        assert_eq!(send_snapshot.remote_total_lost(), 1);
+        assert_eq!(send_snapshot.remote_jitter(), 450);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stats_interceptor_remote_statistics_summary() -> Result<()> {
+        let icpr: Arc<_> = Arc::new(StatsInterceptor::new("Hello".to_owned()));
+
+        let send_stream = MockStream::new(
+            &StreamInfo {
+                ssrc: 234567,
+                ..Default::default()
+            },
+            icpr.clone(),
+        )
+        .await;
+
+        send_stream
+            .receive_rtcp(vec![Box::new(ExtendedReport {
+                sender_ssrc: 1,
+                reports: vec![Box::new(StatisticsSummaryReportBlock {
+                    loss_reports: true,
+                    jitter_reports: true,
+                    ssrc: 234567,
+                    // 64 packets, wrapping around
+                    begin_seq: 65504,
+                    end_seq: 32,
+                    lost_packets: 16,
+                    mean_jitter: 900,
+                    ..Default::default()
+                })],
+            })])
+            .await;
+        let _ = send_stream.read_rtcp().await.expect("read_rtcp failed");
+
+        let snapshots = icpr.fetch_outbound_stats(vec![234567]).await;
+        let send_snapshot = snapshots[0]
+            .as_ref()
+            .expect("Outbound Stats should exist for ssrc: 234567");
+        assert_eq!(send_snapshot.remote_fraction_lost(), Some(64.0 / 255.0));
+        assert_eq!(send_snapshot.remote_jitter(), 900);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reception_state() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let header = |sequence_number: u16, timestamp: u32| rtp::header::Header {
+            sequence_number,
+            timestamp,
+            ..Default::default()
+        };
+
+        let mut reception = ReceptionState::default();
+        assert_eq!(reception.update(&header(65534, 0), t0, 90000), 65534);
+        assert_eq!(
+            reception.update(&header(65535, 900), t0 + Duration::from_millis(10), 90000),
+            65535
+        );
+        // Sequence number rollover
+        assert_eq!(
+            reception.update(&header(1, 2700), t0 + Duration::from_millis(30), 90000),
+            65537
+        );
+        assert_feq!(reception.jitter, 0.0);
+        // Reordered packet from before the rollover
+        assert_eq!(
+            reception.update(&header(0, 1800), t0 + Duration::from_millis(30), 90000),
+            65537
+        );
+        // Arrived 900 timestamp units earlier than expected from the previous packet
+        assert_feq!(reception.jitter, 900.0 / 16.0);
+
+        let mut reception = ReceptionState::default();
+        reception.update(&header(1, 0), t0, 90000);
+        reception.update(&header(2, 900), t0 + Duration::from_millis(20), 90000);
+        // Arrived 10ms, i.e. 900 timestamp units, later than expected
+        assert_feq!(reception.jitter, 900.0 / 16.0);
+    }
+
+    #[tokio::test]
+    async fn test_stats_interceptor_packets_lost() -> Result<()> {
+        let icpr: Arc<_> = Arc::new(StatsInterceptor::new("Hello".to_owned()));
+
+        let recv_stream = MockStream::new(
+            &StreamInfo {
+                ssrc: 123456,
+                clock_rate: 90000,
+                ..Default::default()
+            },
+            icpr.clone(),
+        )
+        .await;
+
+        for sequence_number in [1, 2, 5, 4] {
+            recv_stream
+                .receive_rtp(rtp::packet::Packet {
+                    header: rtp::header::Header {
+                        ssrc: 123456,
+                        sequence_number,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .await;
+
+            let _ = recv_stream
+                .read_rtp()
+                .await
+                .expect("After calling receive_rtp read_rtp should return Some")?;
+        }
+
+        let snapshots = icpr.fetch_inbound_stats(vec![123456]).await;
+        let recv_snapshot = snapshots[0]
+            .as_ref()
+            .expect("Stats should exist for ssrc: 123456");
+        assert_eq!(recv_snapshot.packets_received(), 4);
+        assert_eq!(recv_snapshot.packets_lost(), 1);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_stats_interceptor_rtcp() -> Result<()> {
         let icpr: Arc<_> = Arc::new(StatsInterceptor::with_time_gen("Hello".to_owned(), || {
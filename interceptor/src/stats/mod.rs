@@ -51,6 +51,16 @@ mod inbound {
 
         /// The total number of measurements of the remote round trip time.
         remote_round_trip_time_measurements: u64,
+
+        /// The extended sequence number of the first received packet. [`None`] before any packet
+        /// was received.
+        base_ext_seq_num: Option<u32>,
+
+        /// The highest extended sequence number received.
+        highest_ext_seq_num: u32,
+
+        /// The interarrival jitter in seconds.
+        jitter: f64,
     }
 
     impl Default for StreamStats {
@@ -65,6 +75,9 @@ mod inbound {
                 remote_round_trip_time: None,
                 remote_total_round_trip_time: 0.0,
                 remote_round_trip_time_measurements: 0,
+                base_ext_seq_num: None,
+                highest_ext_seq_num: 0,
+                jitter: 0.0,
             }
         }
     }
@@ -98,6 +111,25 @@ mod inbound {
                 self.remote_round_trip_time_measurements += 1;
             }
         }
+
+        pub(super) fn record_reception(&mut self, ext_seq_num: u32, jitter: f64) {
+            let base_ext_seq_num = *self.base_ext_seq_num.get_or_insert(ext_seq_num);
+            if ext_seq_num >= base_ext_seq_num {
+                self.highest_ext_seq_num = self.highest_ext_seq_num.max(ext_seq_num);
+            }
+            self.jitter = jitter;
+        }
+
+        fn packets_lost(&self) -> i64 {
+            match self.base_ext_seq_num {
+                Some(base_ext_seq_num) => {
+                    let expected = (self.highest_ext_seq_num - base_ext_seq_num) as i64 + 1;
+                    // Duplicated packets can make this negative
+                    expected - self.rtp_stats.packets as i64
+                }
+                None => 0,
+            }
+        }
     }
 
     /// A point in time snapshot of the stream stats for an inbound RTP stream.
@@ -128,6 +160,12 @@ mod inbound {
 
         /// The total number of measurements of the remote round trip time.
         remote_round_trip_time_measurements: u64,
+
+        /// The number of packets lost, derived from the received sequence numbers.
+        packets_lost: i64,
+
+        /// The interarrival jitter in seconds.
+        jitter: f64,
     }
 
     impl StatsSnapshot {
@@ -135,6 +173,17 @@ mod inbound {
             self.rtp_stats.packets
         }
 
+        /// The number of packets expected from the received sequence numbers, less the
+        /// number of packets received. Duplicated packets can make this negative.
+        pub fn packets_lost(&self) -> i64 {
+            self.packets_lost
+        }
+
+        /// The interarrival jitter in seconds, as defined in RFC 3550 section 6.4.1.
+        pub fn jitter(&self) -> f64 {
+            self.jitter
+        }
+
         pub fn payload_bytes_received(&self) -> u64 {
             self.rtp_stats.payload_bytes
         }
@@ -195,6 +244,8 @@ mod inbound {
                 remote_total_round_trip_time: stream_stats.remote_total_round_trip_time,
                 remote_round_trip_time_measurements: stream_stats
                     .remote_round_trip_time_measurements,
+                packets_lost: stream_stats.packets_lost(),
+                jitter: stream_stats.jitter,
             }
         }
     }
@@ -221,8 +272,8 @@ mod outbound {
         /// The last time any stats where update, used for garbage collection to remove obsolete stats.
         last_update: Instant,
 
-        /// The first value of extended seq num that was sent in an SR for this SSRC. [`None`] before
-        /// the first SR is sent.
+        /// The first value of extended seq num that was sent for this SSRC, either in an RTP packet
+        /// or in an SR. [`None`] before either is sent.
         ///
         /// Used to calculate packet statistic for remote stats.
         initial_outbound_ext_seq_num: Option<u32>,
@@ -302,7 +353,7 @@ mod outbound {
         }
 
         #[inline(always)]
-        pub(super) fn record_sr_ext_seq_num(&mut self, seq_num: u32) {
+        pub(super) fn record_initial_ext_seq_num(&mut self, seq_num: u32) {
             // Only record the initial value
             if self.initial_outbound_ext_seq_num.is_none() {
                 self.initial_outbound_ext_seq_num = Some(seq_num);
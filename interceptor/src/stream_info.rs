@@ -14,6 +14,10 @@ pub struct StreamInfo {
     pub attributes: Attributes,
     pub ssrc: u32,
     pub payload_type: u8,
+    /// ssrc_rtx is the SSRC RFC 4588 retransmissions of this stream are sent on, 0 if none.
+    pub ssrc_rtx: u32,
+    /// payload_type_rtx is the payload type of the RFC 4588 retransmissions, 0 if none.
+    pub payload_type_rtx: u8,
     pub rtp_header_extensions: Vec<RTPHeaderExtension>,
     pub mime_type: String,
     pub clock_rate: u32,
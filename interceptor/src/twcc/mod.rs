@@ -67,6 +67,9 @@ impl Recorder {
             .sort_by(|a: &PktInfo, b: &PktInfo| -> Ordering {
                 a.sequence_number.cmp(&b.sequence_number)
             });
+        // A duplicated packet is reported with its first arrival
+        self.received_packets
+            .dedup_by_key(|pkt| pkt.sequence_number);
         feedback.set_base(
             (self.received_packets[0].sequence_number & 0xffff) as u16,
             self.received_packets[0].arrival_time,
@@ -81,6 +84,7 @@ impl Recorder {
                 pkts.push(p);
                 feedback = Feedback::new(self.sender_ssrc, self.media_ssrc, self.fb_pkt_cnt);
                 self.fb_pkt_cnt = self.fb_pkt_cnt.wrapping_add(1);
+                feedback.set_base((pkt.sequence_number & 0xffff) as u16, pkt.arrival_time);
                 feedback.add_received((pkt.sequence_number & 0xffff) as u16, pkt.arrival_time);
             }
         }
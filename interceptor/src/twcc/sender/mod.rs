@@ -2,14 +2,11 @@ mod sender_stream;
 #[cfg(test)]
 mod sender_test;
 
-use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
-use portable_atomic::AtomicU32;
-use rtp::extension::transport_cc_extension::TransportCcExtension;
+use rtp::extension::transport_cc_extension::TransportSequencer;
 use sender_stream::SenderStream;
 use tokio::sync::Mutex;
-use util::Marshal;
 
 use crate::{Attributes, RTPWriter, *};
 
@@ -34,7 +31,7 @@ impl InterceptorBuilder for SenderBuilder {
     /// build constructs a new SenderInterceptor
     fn build(&self, _id: &str) -> Result<Arc<dyn Interceptor + Send + Sync>> {
         Ok(Arc::new(Sender {
-            next_sequence_nr: Arc::new(AtomicU32::new(self.init_sequence_nr)),
+            sequencer: Arc::new(TransportSequencer::new(self.init_sequence_nr as u16)),
             streams: Mutex::new(HashMap::new()),
         }))
     }
@@ -42,7 +39,7 @@ impl InterceptorBuilder for SenderBuilder {
 
 /// Sender adds transport wide sequence numbers as header extension to each RTP packet
 pub struct Sender {
-    next_sequence_nr: Arc<AtomicU32>,
+    sequencer: Arc<TransportSequencer>,
     streams: Mutex<HashMap<u32, Arc<SenderStream>>>,
 }
 
@@ -94,7 +91,7 @@ impl Interceptor for Sender {
 
         let stream = Arc::new(SenderStream::new(
             writer,
-            Arc::clone(&self.next_sequence_nr),
+            Arc::clone(&self.sequencer),
             hdr_ext_id,
         ));
 
@@ -1,3 +1,4 @@
+use rtp::extension::transport_cc_extension::TransportCcExtension;
 use rtp::packet::Packet;
 use tokio::sync::mpsc;
 use tokio::time::Duration;
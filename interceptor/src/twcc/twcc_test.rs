@@ -1,5 +1,5 @@
 use rtcp::packet::Packet;
-use util::Marshal;
+use util::{Marshal, Unmarshal};
 
 use super::*;
 use crate::error::Result;
@@ -563,3 +563,95 @@ fn test_build_feedback_packet_rolling() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_build_feedback_packet_arrivals() -> Result<()> {
+    let mut r = Recorder::new(5000);
+
+    add_run(
+        &mut r,
+        &[10, 11, 13, 14],
+        &[130_000, 131_000, 140_250, 200_000],
+    );
+
+    let rtcp_packets = r.build_feedback_packet();
+    assert_eq!(1, rtcp_packets.len());
+
+    let mut raw = rtcp_packets[0].marshal()?;
+    let tcc = TransportLayerCc::unmarshal(&mut raw)?;
+
+    let arrivals: Vec<(u16, Option<i64>)> = tcc
+        .packet_arrivals()
+        .iter()
+        .map(|a| (a.sequence_number, a.arrival_time))
+        .collect();
+    assert_eq!(
+        arrivals,
+        vec![
+            (10, Some(130_000)),
+            (11, Some(131_000)),
+            (12, None),
+            (13, Some(140_250)),
+            (14, Some(200_000)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_build_feedback_packet_large_gap() -> Result<()> {
+    let mut r = Recorder::new(5000);
+
+    // The arrival time delta to the last packet doesn't fit into a feedback, so the last
+    // packet starts a new one
+    add_run(&mut r, &[1, 2, 3], &[64_000, 65_000, 10_064_000]);
+
+    let rtcp_packets = r.build_feedback_packet();
+    assert_eq!(2, rtcp_packets.len());
+
+    let mut arrivals = vec![];
+    for p in &rtcp_packets {
+        let mut raw = p.marshal()?;
+        let tcc = TransportLayerCc::unmarshal(&mut raw)?;
+        arrivals.push(
+            tcc.packet_arrivals()
+                .iter()
+                .map(|a| (a.sequence_number, a.arrival_time))
+                .collect::<Vec<_>>(),
+        );
+    }
+    assert_eq!(
+        arrivals,
+        vec![
+            vec![(1, Some(64_000)), (2, Some(65_000))],
+            vec![(3, Some(10_064_000))],
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_build_feedback_packet_duplicates() -> Result<()> {
+    let mut r = Recorder::new(5000);
+
+    add_run(&mut r, &[1, 2, 2, 3], &[64_000, 65_000, 66_000, 67_000]);
+
+    let rtcp_packets = r.build_feedback_packet();
+    assert_eq!(1, rtcp_packets.len());
+
+    let mut raw = rtcp_packets[0].marshal()?;
+    let tcc = TransportLayerCc::unmarshal(&mut raw)?;
+    let arrivals: Vec<(u16, Option<i64>)> = tcc
+        .packet_arrivals()
+        .iter()
+        .map(|a| (a.sequence_number, a.arrival_time))
+        .collect();
+    assert_eq!(
+        arrivals,
+        vec![(1, Some(64_000)), (2, Some(65_000)), (3, Some(67_000))]
+    );
+
+    Ok(())
+}
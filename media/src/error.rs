@@ -43,6 +43,19 @@ pub enum Error {
     #[error("Io EOF")]
     ErrIoEOF,
 
+    #[error("SFrame header is too short")]
+    ErrSFrameShortHeader,
+    #[error("SFrame key id is unknown")]
+    ErrSFrameUnknownKeyId,
+    #[error("SFrame ratcheting is disabled")]
+    ErrSFrameRatchetDisabled,
+    #[error("SFrame ratchet bits must leave room for the key id")]
+    ErrSFrameInvalidRatchetBits,
+    #[error("failed to encrypt SFrame frame")]
+    ErrSFrameEncryptFailed,
+    #[error("failed to decrypt SFrame frame")]
+    ErrSFrameDecryptFailed,
+
     #[allow(non_camel_case_types)]
     #[error("{0}")]
     Io(#[source] IoError),
@@ -0,0 +1,111 @@
+use rtp::header::Header;
+
+use super::*;
+
+fn packet(sequence_number: u16, timestamp: u32) -> Packet {
+    Packet {
+        header: Header {
+            sequence_number,
+            timestamp,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn sequence_numbers(packets: &[Packet]) -> Vec<u16> {
+    packets.iter().map(|p| p.header.sequence_number).collect()
+}
+
+#[test]
+fn test_jitter_buffer_reorder() {
+    let mut jb = JitterBuffer::new(10);
+    assert!(jb.pop().is_none(), "empty buffer");
+
+    for sn in [5000u16, 5002, 5001, 5004, 5003] {
+        jb.push(packet(sn, 0));
+    }
+
+    let mut out = vec![];
+    while let Some(p) = jb.pop() {
+        out.push(p);
+    }
+    assert_eq!(sequence_numbers(&out), vec![5000, 5001, 5002, 5003, 5004]);
+    assert!(jb.is_empty());
+    assert_eq!(jb.lost_packets(), 0);
+}
+
+#[test]
+fn test_jitter_buffer_reorder_before_start() {
+    // A packet older than the first one can still be emitted first
+    let mut jb = JitterBuffer::new(10);
+    jb.push(packet(1, 0));
+    jb.push(packet(0, 0));
+    assert_eq!(jb.pop().map(|p| p.header.sequence_number), Some(0));
+    assert_eq!(jb.pop().map(|p| p.header.sequence_number), Some(1));
+}
+
+#[test]
+fn test_jitter_buffer_wraparound() {
+    let mut jb = JitterBuffer::new(10);
+    for sn in [65534u16, 0, 65535, 1] {
+        jb.push(packet(sn, 0));
+    }
+
+    let mut out = vec![];
+    while let Some(p) = jb.pop() {
+        out.push(p);
+    }
+    assert_eq!(sequence_numbers(&out), vec![65534, 65535, 0, 1]);
+}
+
+#[test]
+fn test_jitter_buffer_loss() {
+    let mut jb = JitterBuffer::new(2);
+    jb.push(packet(10, 0));
+    assert_eq!(jb.pop().map(|p| p.header.sequence_number), Some(10));
+
+    // 11 is missing, wait until more than 2 packets are held back
+    jb.push(packet(12, 0));
+    jb.push(packet(13, 0));
+    assert!(jb.pop().is_none());
+    jb.push(packet(14, 0));
+    assert_eq!(jb.pop().map(|p| p.header.sequence_number), Some(12));
+    assert_eq!(jb.lost_packets(), 1);
+
+    // 11 arriving now is too late
+    jb.push(packet(11, 0));
+    assert_eq!(jb.late_packets(), 1);
+
+    jb.push(packet(13, 0));
+    assert_eq!(jb.duplicate_packets(), 1);
+
+    assert_eq!(sequence_numbers(&jb.flush()), vec![13, 14]);
+    assert!(jb.is_empty());
+}
+
+#[test]
+fn test_jitter_buffer_max_delay() {
+    let mut jb = JitterBuffer::new(100).with_max_delay(Duration::from_millis(100), 90000);
+    jb.push(packet(1, 0));
+    assert!(jb.pop().is_some());
+
+    jb.push(packet(3, 3000));
+    jb.push(packet(4, 6000));
+    assert!(jb.pop().is_none(), "within 100ms");
+
+    jb.push(packet(5, 3000 + 9001));
+    assert_eq!(jb.pop().map(|p| p.header.sequence_number), Some(3));
+    assert_eq!(jb.lost_packets(), 1);
+}
+
+#[test]
+fn test_jitter_buffer_flush_counts_gaps() {
+    let mut jb = JitterBuffer::new(10);
+    for sn in [1u16, 3, 6] {
+        jb.push(packet(sn, 0));
+    }
+    assert_eq!(sequence_numbers(&jb.flush()), vec![1, 3, 6]);
+    assert_eq!(jb.lost_packets(), 3);
+    assert!(jb.pop().is_none());
+}
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod jitter_buffer_test;
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use rtp::packet::Packet;
+use rtp::sequence::{timestamp_diff, SequenceNumberUnwrapper};
+
+/// JitterBuffer reorders the RTP packets of a single stream and hands them out
+/// in sequence number order.
+///
+/// A missing packet holds back the ones following it until either more than
+/// `depth` packets are buffered, or the buffered packets span more than the
+/// configured maximum delay. The missing packets are then considered lost.
+/// Packets arriving after their turn are dropped.
+///
+/// Packets can be fed to a [`SampleBuilder`](crate::io::sample_builder::SampleBuilder)
+/// to assemble them into frames.
+pub struct JitterBuffer {
+    depth: usize,
+    max_delay_timestamp: u32,
+    packets: BTreeMap<i64, Packet>,
+    unwrapper: SequenceNumberUnwrapper,
+    /// next is the unwrapped sequence number of the next packet to emit.
+    next: Option<i64>,
+    started: bool,
+
+    lost_packets: u64,
+    late_packets: u64,
+    duplicate_packets: u64,
+}
+
+impl JitterBuffer {
+    /// Constructs a new JitterBuffer holding up to `depth` packets while
+    /// waiting for a missing one.
+    pub fn new(depth: usize) -> Self {
+        JitterBuffer {
+            depth,
+            max_delay_timestamp: 0,
+            packets: BTreeMap::new(),
+            unwrapper: SequenceNumberUnwrapper::default(),
+            next: None,
+            started: false,
+            lost_packets: 0,
+            late_packets: 0,
+            duplicate_packets: 0,
+        }
+    }
+
+    /// with_max_delay also gives up on missing packets once the buffered
+    /// packets span more than `max_delay`, given the RTP `clock_rate` of the stream.
+    pub fn with_max_delay(mut self, max_delay: Duration, clock_rate: u32) -> Self {
+        self.max_delay_timestamp = (clock_rate as u128 * max_delay.as_millis() / 1000) as u32;
+        self
+    }
+
+    /// push adds a packet to the buffer.
+    pub fn push(&mut self, packet: Packet) {
+        let sequence_number = self
+            .unwrapper
+            .unwrap_sequence_number(packet.header.sequence_number);
+
+        match self.next {
+            None => self.next = Some(sequence_number),
+            Some(next) if sequence_number < next => {
+                if self.started {
+                    self.late_packets += 1;
+                    return;
+                }
+                // Nothing was emitted yet, a reordered packet can still go first
+                self.next = Some(sequence_number);
+            }
+            _ => {}
+        }
+
+        if self.packets.contains_key(&sequence_number) {
+            self.duplicate_packets += 1;
+            return;
+        }
+        self.packets.insert(sequence_number, packet);
+    }
+
+    /// pop returns the next packet in order, or None if it hasn't arrived yet
+    /// and it is still worth waiting for it.
+    pub fn pop(&mut self) -> Option<Packet> {
+        let next = self.next?;
+
+        if let Some(packet) = self.packets.remove(&next) {
+            self.next = Some(next + 1);
+            self.started = true;
+            return Some(packet);
+        }
+
+        if !self.should_skip() {
+            return None;
+        }
+
+        let (&first, _) = self.packets.iter().next()?;
+        self.lost_packets += (first - next) as u64;
+        self.next = Some(first);
+        self.pop()
+    }
+
+    /// flush returns all the buffered packets in order, skipping over missing
+    /// ones, e.g. when the stream ends.
+    pub fn flush(&mut self) -> Vec<Packet> {
+        let buffered = std::mem::take(&mut self.packets);
+        let mut packets = Vec::with_capacity(buffered.len());
+        for (sequence_number, packet) in buffered {
+            if let Some(next) = self.next {
+                self.lost_packets += (sequence_number - next) as u64;
+            }
+            self.next = Some(sequence_number + 1);
+            self.started = true;
+            packets.push(packet);
+        }
+        packets
+    }
+
+    fn should_skip(&self) -> bool {
+        if self.packets.len() > self.depth {
+            return true;
+        }
+
+        if self.max_delay_timestamp == 0 {
+            return false;
+        }
+        match (
+            self.packets.values().next(),
+            self.packets.values().next_back(),
+        ) {
+            (Some(oldest), Some(newest)) => {
+                timestamp_diff(newest.header.timestamp, oldest.header.timestamp)
+                    > self.max_delay_timestamp as i32
+            }
+            _ => false,
+        }
+    }
+
+    /// len returns the number of buffered packets.
+    pub fn len(&self) -> usize {
+        self.packets.len()
+    }
+
+    /// is_empty reports whether no packet is buffered.
+    pub fn is_empty(&self) -> bool {
+        self.packets.is_empty()
+    }
+
+    /// lost_packets returns the number of packets that were given up on.
+    pub fn lost_packets(&self) -> u64 {
+        self.lost_packets
+    }
+
+    /// late_packets returns the number of packets dropped for arriving after their turn.
+    pub fn late_packets(&self) -> u64 {
+        self.late_packets
+    }
+
+    /// duplicate_packets returns the number of packets dropped for being already buffered.
+    pub fn duplicate_packets(&self) -> u64 {
+        self.duplicate_packets
+    }
+}
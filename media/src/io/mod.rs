@@ -4,6 +4,7 @@ use crate::error::Result;
 
 pub mod ivf_reader;
 pub mod ivf_writer;
+pub mod jitter_buffer;
 pub mod ogg_reader;
 pub mod ogg_writer;
 pub mod sample_builder;
@@ -5,6 +5,7 @@ mod sample_sequence_location_test;
 
 pub mod sample_sequence_location;
 
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use bytes::Bytes;
@@ -12,7 +13,7 @@ use rtp::packet::Packet;
 use rtp::packetizer::Depacketizer;
 
 use self::sample_sequence_location::{Comparison, SampleSequenceLocation};
-use crate::Sample;
+use crate::{FrameTransform, Sample};
 
 /// SampleBuilder buffers packets until media frames are complete.
 pub struct SampleBuilder<T: Depacketizer> {
@@ -45,6 +46,9 @@ pub struct SampleBuilder<T: Depacketizer> {
     /// number of padding packets detected and dropped. This number will be a subset of
     /// `dropped_packets`
     padding_packets: u16,
+
+    /// transforms the samples before they are returned
+    frame_transform: Option<Arc<dyn FrameTransform>>,
 }
 
 impl<T: Depacketizer> SampleBuilder<T> {
@@ -68,6 +72,7 @@ impl<T: Depacketizer> SampleBuilder<T> {
             prepared: SampleSequenceLocation::new(),
             dropped_packets: 0,
             padding_packets: 0,
+            frame_transform: None,
         }
     }
 
@@ -77,6 +82,16 @@ impl<T: Depacketizer> SampleBuilder<T> {
         self
     }
 
+    /// with_frame_transform transforms each sample before it is returned, e.g. to decrypt
+    /// end to end encrypted frames. Samples the transform drops are skipped.
+    ///
+    /// The depacketizer sees the transformed frames, so only codecs whose depacketizer doesn't
+    /// look into them can be used, see `TrackLocalStaticSample::with_frame_transform`.
+    pub fn with_frame_transform(mut self, frame_transform: Arc<dyn FrameTransform>) -> Self {
+        self.frame_transform = Some(frame_transform);
+        self
+    }
+
     fn too_old(&self, location: &SampleSequenceLocation) -> bool {
         if self.max_late_timestamp == 0 {
             return false;
@@ -368,14 +383,25 @@ impl<T: Depacketizer> SampleBuilder<T> {
     /// Compiles pushed RTP packets into media samples and then
     /// returns the next valid sample (or None if no sample is compiled).
     pub fn pop(&mut self) -> Option<Sample> {
-        let _ = self.build_sample(false);
+        loop {
+            let _ = self.build_sample(false);
 
-        if self.prepared.empty() {
-            return None;
+            if self.prepared.empty() {
+                return None;
+            }
+            let result = self.prepared_samples[self.prepared.head as usize].take();
+            self.prepared.head = self.prepared.head.wrapping_add(1);
+
+            match (&self.frame_transform, result) {
+                (Some(frame_transform), Some(mut sample)) => {
+                    if let Some(data) = frame_transform.transform(&sample) {
+                        sample.data = data;
+                        return Some(sample);
+                    }
+                }
+                (_, result) => return result,
+            }
         }
-        let result = self.prepared_samples[self.prepared.head as usize].take();
-        self.prepared.head = self.prepared.head.wrapping_add(1);
-        result
     }
 
     /// Compiles pushed RTP packets into media samples and then
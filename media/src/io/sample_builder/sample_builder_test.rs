@@ -1497,3 +1497,39 @@ fn test_sample_builder_data() {
     // only the last packet should be dropped
     assert_eq!(j, 0x1FFFF);
 }
+
+#[derive(Debug)]
+struct XorFrameTransform;
+
+impl FrameTransform for XorFrameTransform {
+    fn transform(&self, frame: &Sample) -> Option<Bytes> {
+        if frame.data[0] == 0x02 {
+            return None;
+        }
+        Some(frame.data.iter().map(|b| b ^ 0xff).collect())
+    }
+}
+
+#[test]
+fn test_sample_builder_frame_transform() {
+    let mut s = SampleBuilder::new(10, FakeDepacketizer::new(), 1)
+        .with_frame_transform(Arc::new(XorFrameTransform));
+    for i in 0..4u8 {
+        s.push(Packet {
+            header: Header {
+                sequence_number: i as u16,
+                timestamp: i as u32,
+                ..Default::default()
+            },
+            payload: Bytes::copy_from_slice(&[i + 1]),
+            ..Default::default()
+        });
+    }
+
+    let mut data = vec![];
+    while let Some(sample) = s.pop() {
+        data.push(sample.data);
+    }
+    // the second sample is dropped by the transform, the last one isn't complete yet
+    assert_eq!(data, vec![bytes!(0xfe), bytes!(0xfc)]);
+}
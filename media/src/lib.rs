@@ -4,8 +4,10 @@
 pub mod audio;
 mod error;
 pub mod io;
+pub mod sframe;
 pub mod video;
 
+use std::fmt;
 use std::time::{Duration, SystemTime};
 
 use bytes::Bytes;
@@ -108,3 +110,13 @@ impl PartialEq for Sample {
         equal
     }
 }
+
+/// FrameTransform transforms encoded frames where they are complete, similar to the insertable
+/// streams of WebRTC Encoded Transform: before a local track packetizes them and after a
+/// [`io::sample_builder::SampleBuilder`] assembled them. It can be used to encrypt frames end
+/// to end, so that only the remote peers and not the servers forwarding them can read them.
+pub trait FrameTransform: fmt::Debug + Send + Sync {
+    /// transform returns the new data of `frame`, or None to drop the frame, e.g. because it
+    /// couldn't be decrypted.
+    fn transform(&self, frame: &Sample) -> Option<Bytes>;
+}
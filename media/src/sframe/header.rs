@@ -0,0 +1,94 @@
+use crate::error::{Error, Result};
+
+const EXTENDED_KID_FLAG: u8 = 0x80;
+const EXTENDED_CTR_FLAG: u8 = 0x08;
+const MAX_INLINE_VALUE: u64 = 0x07;
+
+/// Header is the SFrame header preceding each encrypted frame, see RFC 9605 section 4.3.
+///
+/// The first byte holds the key id (KID) and counter (CTR) inline if they are below 8,
+/// otherwise the length of their big endian encoding that follows.
+///
+/// ```text
+///  0 1 2 3 4 5 6 7
+/// +-+-+-+-+-+-+-+-+---------------------------------+-------------------+
+/// |X|  K  |Y|  C  |   Key ID (if X=1, K+1 bytes)    | CTR (if Y=1, C+1) |
+/// +-+-+-+-+-+-+-+-+---------------------------------+-------------------+
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Header {
+    pub(crate) kid: u64,
+    pub(crate) ctr: u64,
+}
+
+impl Header {
+    /// marshal returns the encoded header.
+    pub(crate) fn marshal(&self) -> Vec<u8> {
+        let mut raw = vec![0];
+
+        if self.kid > MAX_INLINE_VALUE {
+            let kid = encode_value(self.kid);
+            raw[0] |= EXTENDED_KID_FLAG | ((kid.len() as u8 - 1) << 4);
+            raw.extend_from_slice(&kid);
+        } else {
+            raw[0] |= (self.kid as u8) << 4;
+        }
+
+        if self.ctr > MAX_INLINE_VALUE {
+            let ctr = encode_value(self.ctr);
+            raw[0] |= EXTENDED_CTR_FLAG | (ctr.len() as u8 - 1);
+            raw.extend_from_slice(&ctr);
+        } else {
+            raw[0] |= self.ctr as u8;
+        }
+
+        raw
+    }
+
+    /// unmarshal parses the header at the start of `raw` and returns it together with its length.
+    pub(crate) fn unmarshal(raw: &[u8]) -> Result<(Self, usize)> {
+        if raw.is_empty() {
+            return Err(Error::ErrSFrameShortHeader);
+        }
+
+        let config = raw[0];
+        let mut offset = 1;
+
+        let kid = if config & EXTENDED_KID_FLAG != 0 {
+            let len = ((config >> 4) & 0x07) as usize + 1;
+            let kid = decode_value(raw, offset, len)?;
+            offset += len;
+            kid
+        } else {
+            ((config >> 4) & 0x07) as u64
+        };
+
+        let ctr = if config & EXTENDED_CTR_FLAG != 0 {
+            let len = (config & 0x07) as usize + 1;
+            let ctr = decode_value(raw, offset, len)?;
+            offset += len;
+            ctr
+        } else {
+            (config & 0x07) as u64
+        };
+
+        Ok((Header { kid, ctr }, offset))
+    }
+}
+
+/// encode_value returns the shortest big endian encoding of `value`.
+fn encode_value(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = (value.leading_zeros() / 8) as usize;
+    bytes[skip..].to_vec()
+}
+
+fn decode_value(raw: &[u8], offset: usize, len: usize) -> Result<u64> {
+    if raw.len() < offset + len {
+        return Err(Error::ErrSFrameShortHeader);
+    }
+
+    Ok(raw[offset..offset + len]
+        .iter()
+        .fold(0u64, |value, b| (value << 8) | *b as u64))
+}
@@ -0,0 +1,377 @@
+#[cfg(test)]
+mod sframe_test;
+
+mod header;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{Aes128Gcm, Aes256Gcm, KeyInit, Nonce};
+use bytes::Bytes;
+use hkdf::Hkdf;
+use sha2::{Sha256, Sha512};
+
+use self::header::Header;
+use crate::error::{Error, Result};
+use crate::{FrameTransform, Sample};
+
+const SECRET_KEY_LABEL: &[u8] = b"SFrame 1.0 Secret key ";
+const SECRET_SALT_LABEL: &[u8] = b"SFrame 1.0 Secret salt ";
+const RATCHET_LABEL: &[u8] = b"SFrame 1.0 Ratchet";
+
+/// MAX_RATCHET_STEPS limits how far a receiver ratchets a key forward to decrypt a frame.
+const MAX_RATCHET_STEPS: u64 = 16;
+
+const NONCE_LEN: usize = 12;
+
+/// CipherSuite is the SFrame cipher suite, see RFC 9605 section 4.5.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CipherSuite {
+    /// AES_128_GCM_SHA256_128
+    Aes128GcmSha256,
+    /// AES_256_GCM_SHA512_128
+    Aes256GcmSha512,
+}
+
+impl CipherSuite {
+    /// id returns the IANA value of the cipher suite.
+    pub fn id(&self) -> u16 {
+        match self {
+            CipherSuite::Aes128GcmSha256 => 0x0004,
+            CipherSuite::Aes256GcmSha512 => 0x0005,
+        }
+    }
+
+    fn key_len(&self) -> usize {
+        match self {
+            CipherSuite::Aes128GcmSha256 => 16,
+            CipherSuite::Aes256GcmSha512 => 32,
+        }
+    }
+
+    fn hash_len(&self) -> usize {
+        match self {
+            CipherSuite::Aes128GcmSha256 => 32,
+            CipherSuite::Aes256GcmSha512 => 64,
+        }
+    }
+
+    /// expand runs HKDF-Extract with an empty salt on `secret`, followed by HKDF-Expand.
+    fn expand(&self, secret: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+        let mut out = vec![0u8; len];
+        // The lengths never exceed 255 times the hash length, so expanding can't fail.
+        match self {
+            CipherSuite::Aes128GcmSha256 => Hkdf::<Sha256>::new(None, secret)
+                .expand(info, &mut out)
+                .expect("valid HKDF output length"),
+            CipherSuite::Aes256GcmSha512 => Hkdf::<Sha512>::new(None, secret)
+                .expand(info, &mut out)
+                .expect("valid HKDF output length"),
+        }
+        out
+    }
+
+    /// ratchet derives the next base key from `base_key`, see RFC 9605 section 5.1.
+    fn ratchet(&self, base_key: &[u8]) -> Vec<u8> {
+        self.expand(base_key, RATCHET_LABEL, self.hash_len())
+    }
+}
+
+enum AeadCipher {
+    Aes128(Box<Aes128Gcm>),
+    Aes256(Box<Aes256Gcm>),
+}
+
+/// KeyContext holds the AEAD key and salt derived from the base key of one KID.
+struct KeyContext {
+    cipher: AeadCipher,
+    salt: [u8; NONCE_LEN],
+}
+
+impl KeyContext {
+    fn new(suite: CipherSuite, kid: u64, base_key: &[u8]) -> Self {
+        let mut info = SECRET_KEY_LABEL.to_vec();
+        info.extend_from_slice(&kid.to_be_bytes());
+        info.extend_from_slice(&suite.id().to_be_bytes());
+        let key = suite.expand(base_key, &info, suite.key_len());
+
+        let mut info = SECRET_SALT_LABEL.to_vec();
+        info.extend_from_slice(&kid.to_be_bytes());
+        info.extend_from_slice(&suite.id().to_be_bytes());
+        let mut salt = [0u8; NONCE_LEN];
+        salt.copy_from_slice(&suite.expand(base_key, &info, NONCE_LEN));
+
+        let cipher = match suite {
+            CipherSuite::Aes128GcmSha256 => AeadCipher::Aes128(Box::new(
+                Aes128Gcm::new_from_slice(&key).expect("valid AES-128 key length"),
+            )),
+            CipherSuite::Aes256GcmSha512 => AeadCipher::Aes256(Box::new(
+                Aes256Gcm::new_from_slice(&key).expect("valid AES-256 key length"),
+            )),
+        };
+
+        KeyContext { cipher, salt }
+    }
+
+    fn nonce(&self, ctr: u64) -> [u8; NONCE_LEN] {
+        let mut nonce = self.salt;
+        for (n, c) in nonce[NONCE_LEN - 8..].iter_mut().zip(ctr.to_be_bytes()) {
+            *n ^= c;
+        }
+        nonce
+    }
+
+    fn seal(&self, ctr: u64, aad: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+        let nonce = self.nonce(ctr);
+        let payload = Payload { msg, aad };
+        match &self.cipher {
+            AeadCipher::Aes128(c) => c.encrypt(Nonce::from_slice(&nonce), payload),
+            AeadCipher::Aes256(c) => c.encrypt(Nonce::from_slice(&nonce), payload),
+        }
+        .map_err(|_| Error::ErrSFrameEncryptFailed)
+    }
+
+    fn open(&self, ctr: u64, aad: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+        let nonce = self.nonce(ctr);
+        let payload = Payload { msg, aad };
+        match &self.cipher {
+            AeadCipher::Aes128(c) => c.decrypt(Nonce::from_slice(&nonce), payload),
+            AeadCipher::Aes256(c) => c.decrypt(Nonce::from_slice(&nonce), payload),
+        }
+        .map_err(|_| Error::ErrSFrameDecryptFailed)
+    }
+}
+
+struct SenderState {
+    key_id: u64,
+    generation: u64,
+    base_key: Vec<u8>,
+    context: KeyContext,
+    counter: u64,
+}
+
+/// SFrameSender encrypts frames end to end with SFrame (RFC 9605).
+///
+/// The frames stay encrypted while forwarded by a server that only terminates DTLS and SRTP.
+/// Use it as the [`FrameTransform`] of a local sample track.
+///
+/// With `ratchet_bits` set, the low bits of the KID carry the generation of the key, which
+/// [`SFrameSender::ratchet`] advances to derive a new key without distributing it again.
+pub struct SFrameSender {
+    suite: CipherSuite,
+    ratchet_bits: u8,
+    state: Mutex<SenderState>,
+}
+
+impl SFrameSender {
+    /// new returns a SFrameSender encrypting with `base_key` under `key_id`.
+    pub fn new(suite: CipherSuite, key_id: u64, base_key: &[u8]) -> Self {
+        SFrameSender {
+            suite,
+            ratchet_bits: 0,
+            state: Mutex::new(SenderState {
+                key_id,
+                generation: 0,
+                base_key: base_key.to_vec(),
+                context: KeyContext::new(suite, key_id, base_key),
+                counter: 0,
+            }),
+        }
+    }
+
+    /// with_ratchet_bits reserves the low `ratchet_bits` bits of the KID for the key generation.
+    /// The receivers must use the same value, which must be below 64.
+    pub fn with_ratchet_bits(mut self, ratchet_bits: u8) -> Result<Self> {
+        check_ratchet_bits(ratchet_bits)?;
+        self.ratchet_bits = ratchet_bits;
+        let state = self.state.get_mut().unwrap();
+        let kid = kid(ratchet_bits, state.key_id, state.generation);
+        state.context = KeyContext::new(self.suite, kid, &state.base_key);
+        Ok(self)
+    }
+
+    /// set_key switches to a new base key, e.g. after a participant left the call.
+    pub fn set_key(&self, key_id: u64, base_key: &[u8]) {
+        let mut state = self.state.lock().unwrap();
+        state.key_id = key_id;
+        state.generation = 0;
+        state.base_key = base_key.to_vec();
+        state.context = KeyContext::new(self.suite, kid(self.ratchet_bits, key_id, 0), base_key);
+    }
+
+    /// ratchet derives the next generation of the current key, e.g. after a participant joined
+    /// the call so that they can't decrypt earlier frames.
+    pub fn ratchet(&self) -> Result<()> {
+        if self.ratchet_bits == 0 {
+            return Err(Error::ErrSFrameRatchetDisabled);
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.generation = (state.generation + 1) & generation_mask(self.ratchet_bits);
+        state.base_key = self.suite.ratchet(&state.base_key);
+        let kid = kid(self.ratchet_bits, state.key_id, state.generation);
+        state.context = KeyContext::new(self.suite, kid, &state.base_key);
+        Ok(())
+    }
+
+    /// encrypt returns the SFrame header followed by the encrypted `frame`.
+    pub fn encrypt(&self, frame: &[u8]) -> Result<Bytes> {
+        let mut state = self.state.lock().unwrap();
+        let header = Header {
+            kid: kid(self.ratchet_bits, state.key_id, state.generation),
+            ctr: state.counter,
+        };
+        state.counter += 1;
+
+        let mut raw = header.marshal();
+        let encrypted = state.context.seal(header.ctr, &raw, frame)?;
+        raw.extend(encrypted);
+        Ok(Bytes::from(raw))
+    }
+}
+
+impl fmt::Debug for SFrameSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SFrameSender")
+            .field("suite", &self.suite)
+            .field("ratchet_bits", &self.ratchet_bits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FrameTransform for SFrameSender {
+    fn transform(&self, frame: &Sample) -> Option<Bytes> {
+        self.encrypt(&frame.data).ok()
+    }
+}
+
+struct ReceiverKey {
+    generation: u64,
+    base_key: Vec<u8>,
+    context: KeyContext,
+}
+
+/// SFrameReceiver decrypts the frames of [`SFrameSender`]s.
+///
+/// Use it as the [`FrameTransform`] of the
+/// [`SampleBuilder`](crate::io::sample_builder::SampleBuilder) of a remote track. Frames
+/// that can't be decrypted are dropped.
+pub struct SFrameReceiver {
+    suite: CipherSuite,
+    ratchet_bits: u8,
+    keys: Mutex<HashMap<u64, ReceiverKey>>,
+}
+
+impl SFrameReceiver {
+    /// new returns a SFrameReceiver without keys.
+    pub fn new(suite: CipherSuite) -> Self {
+        SFrameReceiver {
+            suite,
+            ratchet_bits: 0,
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// with_ratchet_bits reserves the low `ratchet_bits` bits of the KID for the key generation,
+    /// as the senders do.
+    pub fn with_ratchet_bits(mut self, ratchet_bits: u8) -> Result<Self> {
+        check_ratchet_bits(ratchet_bits)?;
+        self.ratchet_bits = ratchet_bits;
+        Ok(self)
+    }
+
+    /// add_key adds or replaces the base key of `key_id`.
+    pub fn add_key(&self, key_id: u64, base_key: &[u8]) {
+        let mut keys = self.keys.lock().unwrap();
+        keys.insert(
+            key_id,
+            ReceiverKey {
+                generation: 0,
+                base_key: base_key.to_vec(),
+                context: KeyContext::new(self.suite, kid(self.ratchet_bits, key_id, 0), base_key),
+            },
+        );
+    }
+
+    /// remove_key removes the base key of `key_id`.
+    pub fn remove_key(&self, key_id: u64) {
+        let mut keys = self.keys.lock().unwrap();
+        keys.remove(&key_id);
+    }
+
+    /// decrypt returns the frame encrypted in `data`.
+    ///
+    /// A frame of a later key generation ratchets the key forward, once it has been
+    /// authenticated with the ratcheted key.
+    pub fn decrypt(&self, data: &[u8]) -> Result<Bytes> {
+        let (header, header_len) = Header::unmarshal(data)?;
+        let (aad, encrypted) = data.split_at(header_len);
+
+        let key_id = header.kid >> self.ratchet_bits;
+        let generation = header.kid & generation_mask(self.ratchet_bits);
+
+        let mut keys = self.keys.lock().unwrap();
+        let key = keys.get_mut(&key_id).ok_or(Error::ErrSFrameUnknownKeyId)?;
+
+        if generation == key.generation {
+            return key
+                .context
+                .open(header.ctr, aad, encrypted)
+                .map(Bytes::from);
+        }
+
+        let steps = generation.wrapping_sub(key.generation) & generation_mask(self.ratchet_bits);
+        if steps > MAX_RATCHET_STEPS {
+            return Err(Error::ErrSFrameUnknownKeyId);
+        }
+
+        let mut base_key = key.base_key.clone();
+        for _ in 0..steps {
+            base_key = self.suite.ratchet(&base_key);
+        }
+        let context = KeyContext::new(self.suite, header.kid, &base_key);
+        let frame = context.open(header.ctr, aad, encrypted)?;
+
+        *key = ReceiverKey {
+            generation,
+            base_key,
+            context,
+        };
+
+        Ok(Bytes::from(frame))
+    }
+}
+
+impl fmt::Debug for SFrameReceiver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SFrameReceiver")
+            .field("suite", &self.suite)
+            .field("ratchet_bits", &self.ratchet_bits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FrameTransform for SFrameReceiver {
+    fn transform(&self, frame: &Sample) -> Option<Bytes> {
+        self.decrypt(&frame.data).ok()
+    }
+}
+
+/// check_ratchet_bits ensures the KID can be shifted by `ratchet_bits`.
+fn check_ratchet_bits(ratchet_bits: u8) -> Result<()> {
+    if u32::from(ratchet_bits) >= u64::BITS {
+        return Err(Error::ErrSFrameInvalidRatchetBits);
+    }
+    Ok(())
+}
+
+fn kid(ratchet_bits: u8, key_id: u64, generation: u64) -> u64 {
+    (key_id << ratchet_bits) | generation
+}
+
+fn generation_mask(ratchet_bits: u8) -> u64 {
+    (1u64 << ratchet_bits) - 1
+}
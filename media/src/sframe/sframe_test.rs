@@ -0,0 +1,161 @@
+use super::*;
+
+const BASE_KEY: &[u8] = b"0123456789abcdef";
+
+#[test]
+fn test_sframe_header() {
+    let tests = vec![
+        (Header { kid: 0, ctr: 0 }, vec![0x00]),
+        (Header { kid: 7, ctr: 7 }, vec![0x77]),
+        (Header { kid: 8, ctr: 1 }, vec![0x81, 0x08]),
+        // RFC 9605 Appendix C
+        (
+            Header {
+                kid: 0x0123,
+                ctr: 0x4567,
+            },
+            vec![0x99, 0x01, 0x23, 0x45, 0x67],
+        ),
+        (
+            Header {
+                kid: 1,
+                ctr: 0x0100,
+            },
+            vec![0x19, 0x01, 0x00],
+        ),
+        (
+            Header {
+                kid: 0x0102_0304,
+                ctr: u64::MAX,
+            },
+            vec![
+                0xbf, 0x01, 0x02, 0x03, 0x04, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            ],
+        ),
+    ];
+
+    for (header, raw) in tests {
+        assert_eq!(header.marshal(), raw, "marshal {header:?}");
+        assert_eq!(
+            Header::unmarshal(&raw),
+            Ok((header, raw.len())),
+            "unmarshal {raw:?}"
+        );
+    }
+
+    assert_eq!(Header::unmarshal(&[]), Err(Error::ErrSFrameShortHeader));
+    assert_eq!(Header::unmarshal(&[0x81]), Err(Error::ErrSFrameShortHeader));
+}
+
+#[test]
+fn test_sframe_round_trip() -> Result<()> {
+    for suite in [CipherSuite::Aes128GcmSha256, CipherSuite::Aes256GcmSha512] {
+        let sender = SFrameSender::new(suite, 3, BASE_KEY);
+        let receiver = SFrameReceiver::new(suite);
+        receiver.add_key(3, BASE_KEY);
+
+        for i in 0..10u8 {
+            let frame = vec![i; 100];
+            let encrypted = sender.encrypt(&frame)?;
+            assert_ne!(&encrypted[1..101], &frame[..], "frame {i} isn't encrypted");
+            assert_eq!(receiver.decrypt(&encrypted)?, frame, "frame {i}");
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_sframe_rejects_unknown_or_tampered_frames() -> Result<()> {
+    let sender = SFrameSender::new(CipherSuite::Aes128GcmSha256, 1, BASE_KEY);
+    let receiver = SFrameReceiver::new(CipherSuite::Aes128GcmSha256);
+
+    let encrypted = sender.encrypt(b"frame")?;
+    assert_eq!(
+        receiver.decrypt(&encrypted),
+        Err(Error::ErrSFrameUnknownKeyId)
+    );
+
+    receiver.add_key(1, b"fedcba9876543210");
+    assert_eq!(
+        receiver.decrypt(&encrypted),
+        Err(Error::ErrSFrameDecryptFailed)
+    );
+
+    receiver.add_key(1, BASE_KEY);
+    let mut tampered = encrypted.to_vec();
+    // The header is authenticated as well
+    tampered[0] ^= 0x01;
+    assert_eq!(
+        receiver.decrypt(&tampered),
+        Err(Error::ErrSFrameDecryptFailed)
+    );
+    assert_eq!(receiver.decrypt(&encrypted)?, &b"frame"[..]);
+
+    Ok(())
+}
+
+#[test]
+fn test_sframe_ratchet() -> Result<()> {
+    let sender =
+        SFrameSender::new(CipherSuite::Aes128GcmSha256, 2, BASE_KEY).with_ratchet_bits(4)?;
+    let receiver = SFrameReceiver::new(CipherSuite::Aes128GcmSha256).with_ratchet_bits(4)?;
+    receiver.add_key(2, BASE_KEY);
+
+    let first = sender.encrypt(b"first")?;
+    sender.ratchet()?;
+    sender.ratchet()?;
+    let third = sender.encrypt(b"third")?;
+
+    let (header, _) = Header::unmarshal(&third)?;
+    assert_eq!(header.kid, 2 << 4 | 2);
+
+    // The receiver ratchets forward to the generation of the frame
+    assert_eq!(receiver.decrypt(&third)?, &b"third"[..]);
+    // and can't go back to the earlier keys.
+    assert_eq!(receiver.decrypt(&first), Err(Error::ErrSFrameDecryptFailed));
+    assert_eq!(receiver.decrypt(&third)?, &b"third"[..]);
+
+    assert_eq!(
+        SFrameSender::new(CipherSuite::Aes128GcmSha256, 2, BASE_KEY).ratchet(),
+        Err(Error::ErrSFrameRatchetDisabled)
+    );
+
+    // The generation can't take up the whole KID
+    let sender =
+        SFrameSender::new(CipherSuite::Aes128GcmSha256, 0, BASE_KEY).with_ratchet_bits(63)?;
+    let receiver = SFrameReceiver::new(CipherSuite::Aes128GcmSha256).with_ratchet_bits(63)?;
+    receiver.add_key(0, BASE_KEY);
+    sender.ratchet()?;
+    assert_eq!(receiver.decrypt(&sender.encrypt(b"frame")?)?, &b"frame"[..]);
+    assert!(matches!(
+        SFrameSender::new(CipherSuite::Aes128GcmSha256, 0, BASE_KEY).with_ratchet_bits(64),
+        Err(Error::ErrSFrameInvalidRatchetBits)
+    ));
+    assert!(matches!(
+        SFrameReceiver::new(CipherSuite::Aes128GcmSha256).with_ratchet_bits(64),
+        Err(Error::ErrSFrameInvalidRatchetBits)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_sframe_frame_transform() {
+    let sender = SFrameSender::new(CipherSuite::Aes128GcmSha256, 0, BASE_KEY);
+    let receiver = SFrameReceiver::new(CipherSuite::Aes128GcmSha256);
+    receiver.add_key(0, BASE_KEY);
+
+    let mut sample = Sample {
+        data: Bytes::from_static(b"frame"),
+        ..Default::default()
+    };
+    sample.data = sender.transform(&sample).unwrap();
+    assert_eq!(
+        receiver.transform(&sample),
+        Some(Bytes::from_static(b"frame"))
+    );
+
+    sample.data = Bytes::from_static(b"\x00garbage");
+    assert_eq!(receiver.transform(&sample), None);
+}
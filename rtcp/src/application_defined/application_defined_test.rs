@@ -0,0 +1,180 @@
+use super::*;
+
+#[test]
+fn test_application_defined_unmarshal() {
+    let tests = vec![
+        (
+            "valid",
+            Bytes::from_static(&[
+                0x85, 0xcc, 0x00, 0x03, // v=2, p=0, subtype=5, APP, len=3
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                0x4e, 0x41, 0x4d, 0x45, // name=NAME
+                0x01, 0x02, 0x03, 0x04, // data
+            ]),
+            ApplicationDefined {
+                sub_type: 5,
+                ssrc: 0x902f9e2e,
+                name: "NAME".to_owned(),
+                data: Bytes::from_static(&[0x01, 0x02, 0x03, 0x04]),
+            },
+            None,
+        ),
+        (
+            "padded",
+            Bytes::from_static(&[
+                0xa0, 0xcc, 0x00, 0x03, // v=2, p=1, subtype=0, APP, len=3
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                0x4e, 0x41, 0x4d, 0x45, // name=NAME
+                0x01, 0x02, 0x00, 0x02, // data, 2 bytes of padding
+            ]),
+            ApplicationDefined {
+                sub_type: 0,
+                ssrc: 0x902f9e2e,
+                name: "NAME".to_owned(),
+                data: Bytes::from_static(&[0x01, 0x02]),
+            },
+            None,
+        ),
+        (
+            "no data",
+            Bytes::from_static(&[
+                0x80, 0xcc, 0x00, 0x02, // v=2, p=0, subtype=0, APP, len=2
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                0x4e, 0x41, 0x4d, 0x45, // name=NAME
+            ]),
+            ApplicationDefined {
+                sub_type: 0,
+                ssrc: 0x902f9e2e,
+                name: "NAME".to_owned(),
+                data: Bytes::new(),
+            },
+            None,
+        ),
+        (
+            "short packet",
+            Bytes::from_static(&[
+                0x80, 0xcc, 0x00, 0x01, // v=2, p=0, subtype=0, APP, len=1
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+            ]),
+            ApplicationDefined::default(),
+            Some(Error::PacketTooShort),
+        ),
+        (
+            "wrong type",
+            Bytes::from_static(&[
+                0x80, 0xcb, 0x00, 0x02, // v=2, p=0, count=0, BYE, len=2
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                0x4e, 0x41, 0x4d, 0x45, // name=NAME
+            ]),
+            ApplicationDefined::default(),
+            Some(Error::WrongType),
+        ),
+        (
+            "non ascii name",
+            Bytes::from_static(&[
+                0x80, 0xcc, 0x00, 0x02, // v=2, p=0, subtype=0, APP, len=2
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                0x4e, 0x41, 0x4d, 0xc5, // name=NAM\xc5
+            ]),
+            ApplicationDefined::default(),
+            Some(Error::InvalidApplicationName),
+        ),
+    ];
+
+    for (name, mut data, want, want_error) in tests {
+        let got = ApplicationDefined::unmarshal(&mut data);
+
+        assert_eq!(
+            got.is_err(),
+            want_error.is_some(),
+            "Unmarshal {name}: err = {got:?}, want {want_error:?}"
+        );
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(
+                err, got_err,
+                "Unmarshal {name}: err = {got_err:?}, want {err:?}",
+            );
+        } else {
+            let actual = got.unwrap();
+            assert_eq!(
+                actual, want,
+                "Unmarshal {name}: got {actual:?}, want {want:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_application_defined_round_trip() {
+    let tests = vec![
+        (
+            "valid",
+            ApplicationDefined {
+                sub_type: 31,
+                ssrc: 1,
+                name: "TEST".to_owned(),
+                data: Bytes::from_static(&[0x01, 0x02, 0x03, 0x04]),
+            },
+            None,
+        ),
+        (
+            "unaligned data",
+            ApplicationDefined {
+                sub_type: 1,
+                ssrc: 2,
+                name: "TEST".to_owned(),
+                data: Bytes::from_static(&[0x01, 0x02, 0x03, 0x04, 0x05]),
+            },
+            None,
+        ),
+        (
+            "subtype overflow",
+            ApplicationDefined {
+                sub_type: 32,
+                ssrc: 1,
+                name: "TEST".to_owned(),
+                ..Default::default()
+            },
+            Some(Error::InvalidSubType),
+        ),
+        (
+            "name too long",
+            ApplicationDefined {
+                ssrc: 1,
+                name: "TESTS".to_owned(),
+                ..Default::default()
+            },
+            Some(Error::InvalidApplicationName),
+        ),
+    ];
+
+    for (name, want, want_error) in tests {
+        let got = want.marshal();
+
+        assert_eq!(
+            got.is_ok(),
+            want_error.is_none(),
+            "Marshal {name}: err = {got:?}, want {want_error:?}"
+        );
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(
+                err, got_err,
+                "Marshal {name}: err = {got_err:?}, want {err:?}",
+            );
+        } else {
+            let mut data = got.ok().unwrap();
+            assert_eq!(data.len() % 4, 0, "{name}: not 32-bit aligned");
+            let actual = ApplicationDefined::unmarshal(&mut data)
+                .unwrap_or_else(|_| panic!("Unmarshal {name}"));
+
+            assert_eq!(
+                actual, want,
+                "{name} round trip: got {actual:?}, want {want:?}"
+            )
+        }
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod application_defined_test;
+
+use std::any::Any;
+use std::fmt;
+
+use bytes::{Buf, BufMut, Bytes};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+use crate::header::*;
+use crate::packet::*;
+use crate::util::*;
+
+type Result<T> = std::result::Result<T, util::Error>;
+
+pub(crate) const APP_NAME_LENGTH: usize = 4;
+
+/// The ApplicationDefined (APP) packet is intended for experimental use as new
+/// applications and new features are developed, without requiring packet type
+/// value registration.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct ApplicationDefined {
+    /// May be used as a subtype to allow a set of APP packets to be defined
+    /// under one unique name, or for any application-dependent data.
+    pub sub_type: u8,
+    /// The synchronization source identifier for the originator of this APP packet.
+    pub ssrc: u32,
+    /// A name chosen by the person defining the set of APP packets to be unique
+    /// with respect to other APP packets this application might receive,
+    /// made of four ASCII characters.
+    pub name: String,
+    /// Application-dependent data, padded to a 32-bit boundary when marshaled.
+    pub data: Bytes,
+}
+
+impl fmt::Display for ApplicationDefined {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = format!("ApplicationDefined from {}\n", self.ssrc);
+        out += format!("\tSubType: {}\n", self.sub_type).as_str();
+        out += format!("\tName: {}\n", self.name).as_str();
+        out += format!("\tData: {:?}\n", self.data).as_str();
+
+        write!(f, "{out}")
+    }
+}
+
+impl Packet for ApplicationDefined {
+    /// Header returns the Header associated with this packet.
+    fn header(&self) -> Header {
+        Header {
+            padding: get_padding_size(self.raw_size()) != 0,
+            count: self.sub_type,
+            packet_type: PacketType::ApplicationDefined,
+            length: ((self.marshal_size() / 4) - 1) as u16,
+        }
+    }
+
+    /// destination_ssrc returns an array of SSRC values that this packet refers to.
+    fn destination_ssrc(&self) -> Vec<u32> {
+        vec![self.ssrc]
+    }
+
+    fn raw_size(&self) -> usize {
+        HEADER_LENGTH + SSRC_LENGTH + APP_NAME_LENGTH + self.data.len()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn equal(&self, other: &(dyn Packet + Send + Sync)) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<ApplicationDefined>()
+            .is_some_and(|a| self == a)
+    }
+
+    fn cloned(&self) -> Box<dyn Packet + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+impl MarshalSize for ApplicationDefined {
+    fn marshal_size(&self) -> usize {
+        let l = self.raw_size();
+        // align to 32-bit boundary
+        l + get_padding_size(l)
+    }
+}
+
+impl Marshal for ApplicationDefined {
+    /// marshal_to encodes the packet in binary.
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
+        if self.sub_type as usize > COUNT_MAX {
+            return Err(Error::InvalidSubType.into());
+        }
+
+        if self.name.len() != APP_NAME_LENGTH || !self.name.is_ascii() {
+            return Err(Error::InvalidApplicationName.into());
+        }
+
+        if buf.remaining_mut() < self.marshal_size() {
+            return Err(Error::BufferTooShort.into());
+        }
+
+        /*
+         *  0                   1                   2                   3
+         *  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |V=2|P| subtype |   PT=APP=204  |             length            |
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |                           SSRC/CSRC                           |
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |                          name (ASCII)                         |
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |                   application-dependent data                ...
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         */
+        let h = self.header();
+        let n = h.marshal_to(buf)?;
+        buf = &mut buf[n..];
+
+        buf.put_u32(self.ssrc);
+        buf.put(self.name.as_bytes());
+        buf.put(self.data.clone());
+
+        if h.padding {
+            put_padding(buf, self.raw_size());
+        }
+
+        Ok(self.marshal_size())
+    }
+}
+
+impl Unmarshal for ApplicationDefined {
+    /// Unmarshal decodes the ApplicationDefined from binary
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        /*
+         *  0                   1                   2                   3
+         *  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |V=2|P| subtype |   PT=APP=204  |             length            |
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |                           SSRC/CSRC                           |
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |                          name (ASCII)                         |
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |                   application-dependent data                ...
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         */
+        let raw_packet_len = raw_packet.remaining();
+        if raw_packet_len < HEADER_LENGTH + SSRC_LENGTH + APP_NAME_LENGTH {
+            return Err(Error::PacketTooShort.into());
+        }
+
+        let header = Header::unmarshal(raw_packet)?;
+        if header.packet_type != PacketType::ApplicationDefined {
+            return Err(Error::WrongType.into());
+        }
+
+        let ssrc = raw_packet.get_u32();
+        let name = raw_packet.copy_to_bytes(APP_NAME_LENGTH);
+        if !name.is_ascii() {
+            return Err(Error::InvalidApplicationName.into());
+        }
+        let name = String::from_utf8_lossy(&name).into_owned();
+
+        let mut data = raw_packet.copy_to_bytes(raw_packet.remaining());
+        if header.padding {
+            strip_padding(&mut data)?;
+        }
+
+        Ok(ApplicationDefined {
+            sub_type: header.count,
+            ssrc,
+            name,
+            data,
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::application_defined::ApplicationDefined;
 use crate::goodbye::Goodbye;
 use crate::payload_feedbacks::picture_loss_indication::PictureLossIndication;
 
@@ -41,6 +42,45 @@ fn test_read_eof() {
     assert!(result.is_err(), "missing type & len");
 }
 
+#[test]
+fn test_compound_packet_unmarshal() {
+    let mut data = Bytes::copy_from_slice(&REAL_PACKET);
+    let compound = CompoundPacket::unmarshal(&mut data).expect("Unmarshal(REAL_PACKET)");
+    assert_eq!(compound.0.len(), 5);
+
+    assert!(compound.0[0]
+        .as_any()
+        .downcast_ref::<ReceiverReport>()
+        .is_some());
+    assert!(compound.0[1]
+        .as_any()
+        .downcast_ref::<SourceDescription>()
+        .is_some());
+    assert!(compound.0[2].as_any().downcast_ref::<Goodbye>().is_some());
+    assert!(compound.0[3]
+        .as_any()
+        .downcast_ref::<PictureLossIndication>()
+        .is_some());
+    assert_eq!(
+        compound.0[4].header().packet_type,
+        PacketType::TransportSpecificFeedback
+    );
+
+    assert_eq!(
+        compound.cname().expect("cname"),
+        Bytes::from_static(b"{9c00eb92-1afb-9d49-a47d-91f64eee69f5}")
+    );
+    assert_eq!(compound.destination_ssrc(), vec![0xbc5e9a40]);
+
+    // A reduced-size datagram is accepted as a list of packets, not as a compound
+    let mut reduced = Bytes::copy_from_slice(&REAL_PACKET[92..104]);
+    let packets = unmarshal(&mut reduced).expect("Unmarshal(reduced-size)");
+    assert_eq!(packets.len(), 1);
+    let mut reduced = Bytes::copy_from_slice(&REAL_PACKET[92..104]);
+    let err = CompoundPacket::unmarshal(&mut reduced).expect_err("reduced-size compound");
+    assert_eq!(Error::BadFirstPacket, err);
+}
+
 #[test]
 fn test_bad_compound() {
     let mut bad_compound = Bytes::copy_from_slice(&REAL_PACKET[..34]);
@@ -285,7 +325,25 @@ fn test_compound_packet_roundtrip() {
             "goodbye",
             CompoundPacket(vec![
                 Box::<ReceiverReport>::default(),
+                Box::new(cname.clone()),
+                Box::new(Goodbye {
+                    sources: vec![1234],
+                    ..Default::default()
+                }),
+            ]),
+            None,
+        ),
+        (
+            "application defined",
+            CompoundPacket(vec![
+                Box::<SenderReport>::default(),
                 Box::new(cname),
+                Box::new(ApplicationDefined {
+                    sub_type: 1,
+                    ssrc: 1234,
+                    name: "TEST".to_owned(),
+                    data: Bytes::from_static(&[0x01, 0x02, 0x03]),
+                }),
                 Box::new(Goodbye {
                     sources: vec![1234],
                     ..Default::default()
@@ -96,6 +96,12 @@ pub enum Error {
     BadStructMemberType,
     #[error("Cannot read into non-pointer")]
     BadReadParameter,
+    /// APP subtype doesn't fit in 5 bits.
+    #[error("Invalid APP subtype")]
+    InvalidSubType,
+    /// APP name is not made of 4 ASCII characters.
+    #[error("APP name must be 4 ASCII characters")]
+    InvalidApplicationName,
 
     #[error("{0}")]
     Util(#[from] util::Error),
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use super::*;
+use crate::util::round_trip_time;
 
 const DLRR_REPORT_LENGTH: u16 = 12;
 
@@ -10,6 +13,16 @@ pub struct DLRRReport {
     pub dlrr: u32,
 }
 
+impl DLRRReport {
+    /// round_trip_time computes the round trip time to the receiver `ssrc` from
+    /// the LRR and DLRR fields, given `now`, the middle 32 bits of the NTP time
+    /// at which this report was received. It returns None when no receiver
+    /// reference time was echoed yet or when the fields are inconsistent with `now`.
+    pub fn round_trip_time(&self, now: u32) -> Option<Duration> {
+        round_trip_time(now, self.last_rr, self.dlrr)
+    }
+}
+
 impl fmt::Display for DLRRReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{self:?}")
@@ -182,3 +182,29 @@ fn test_decode() -> Result<()> {
     assert_eq!(actual.to_string(), expected.to_string());
     Ok(())
 }
+
+#[test]
+fn test_receive_only_round_trip_time() {
+    // The receiver sends its reference time
+    let rrt = ReceiverReferenceTimeReportBlock {
+        ntp_timestamp: 0x0123_0001_0000_4567,
+    };
+    assert_eq!(rrt.last_rr(), 0x0001_0000);
+
+    // and the sender echoes it in a DLRR block, 0.5s later
+    let dlrr = DLRRReport {
+        ssrc: 0x12345678,
+        last_rr: rrt.last_rr(),
+        dlrr: 0x0000_8000,
+    };
+
+    // received back 1.75s after the reference time
+    assert_eq!(
+        dlrr.round_trip_time(0x0002_c000),
+        Some(std::time::Duration::from_millis(1250))
+    );
+    // arrival before LRR + DLRR
+    assert_eq!(dlrr.round_trip_time(0x0001_4000), None);
+    // no reference time received yet
+    assert_eq!(DLRRReport::default().round_trip_time(0x0002_c000), None);
+}
@@ -26,6 +26,12 @@ impl fmt::Display for ReceiverReferenceTimeReportBlock {
 }
 
 impl ReceiverReferenceTimeReportBlock {
+    /// last_rr returns the middle 32 bits of the NTP timestamp, as echoed back
+    /// by senders in the LRR field of their DLRR reports.
+    pub fn last_rr(&self) -> u32 {
+        (self.ntp_timestamp >> 16) as u32
+    }
+
     pub fn xr_header(&self) -> XRHeader {
         XRHeader {
             block_type: BlockType::ReceiverReferenceTime,
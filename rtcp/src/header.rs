@@ -14,7 +14,7 @@ pub enum PacketType {
     ReceiverReport = 201,            // RFC 3550, 6.4.2
     SourceDescription = 202,         // RFC 3550, 6.5
     Goodbye = 203,                   // RFC 3550, 6.6
-    ApplicationDefined = 204,        // RFC 3550, 6.7 (unimplemented)
+    ApplicationDefined = 204,        // RFC 3550, 6.7
     TransportSpecificFeedback = 205, // RFC 4585, 6051
     PayloadSpecificFeedback = 206,   // RFC 4585, 6.3
     ExtendedReport = 207,            // RFC 3611
@@ -60,7 +60,7 @@ impl From<u8> for PacketType {
             201 => PacketType::ReceiverReport,            // RFC 3550, 6.4.2
             202 => PacketType::SourceDescription,         // RFC 3550, 6.5
             203 => PacketType::Goodbye,                   // RFC 3550, 6.6
-            204 => PacketType::ApplicationDefined,        // RFC 3550, 6.7 (unimplemented)
+            204 => PacketType::ApplicationDefined,        // RFC 3550, 6.7
             205 => PacketType::TransportSpecificFeedback, // RFC 4585, 6051
             206 => PacketType::PayloadSpecificFeedback,   // RFC 4585, 6.3
             207 => PacketType::ExtendedReport,            // RFC 3611
@@ -4,6 +4,7 @@ use std::fmt;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use util::marshal::{Marshal, Unmarshal};
 
+use crate::application_defined::*;
 use crate::error::{Error, Result};
 use crate::extended_report::ExtendedReport;
 use crate::goodbye::*;
@@ -56,9 +57,10 @@ pub fn marshal(packets: &[Box<dyn Packet + Send + Sync>]) -> Result<Bytes> {
 /// Unmarshal takes an entire udp datagram (which may consist of multiple RTCP packets) and
 /// returns the unmarshaled packets it contains.
 ///
-/// If this is a reduced-size RTCP packet a feedback packet (Goodbye, SliceLossIndication, etc)
-/// will be returned. Otherwise, the underlying type of the returned packet will be
-/// CompoundPacket.
+/// The packets are returned in order, each with its concrete type (SenderReport,
+/// SourceDescription, PictureLossIndication, etc). No compound validation is applied,
+/// so reduced-size RTCP (RFC 5506) datagrams made of a single feedback packet are
+/// accepted; use CompoundPacket::unmarshal to enforce the RFC 3550 rules.
 pub fn unmarshal<B>(raw_data: &mut B) -> Result<Vec<Box<dyn Packet + Send + Sync>>>
 where
     B: Buf,
@@ -99,6 +101,7 @@ where
         PacketType::ReceiverReport => Box::new(ReceiverReport::unmarshal(&mut in_packet)?),
         PacketType::SourceDescription => Box::new(SourceDescription::unmarshal(&mut in_packet)?),
         PacketType::Goodbye => Box::new(Goodbye::unmarshal(&mut in_packet)?),
+        PacketType::ApplicationDefined => Box::new(ApplicationDefined::unmarshal(&mut in_packet)?),
 
         PacketType::TransportSpecificFeedback => match h.count {
             FORMAT_TLN => Box::new(TransportLayerNack::unmarshal(&mut in_packet)?),
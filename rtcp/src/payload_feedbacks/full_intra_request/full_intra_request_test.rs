@@ -213,3 +213,22 @@ fn test_full_intra_request_unmarshal_header() {
         }
     }
 }
+
+#[test]
+fn test_full_intra_request_new() {
+    let fir = FullIntraRequest::new(0x902f9e2e, 0x4bc4fcb4, 7);
+    assert_eq!(fir.media_ssrc, 0);
+    assert_eq!(fir.destination_ssrc(), vec![0x4bc4fcb4]);
+
+    let data = fir.marshal().expect("Marshal fir");
+    assert_eq!(
+        data,
+        Bytes::from_static(&[
+            0x84, 0xce, 0x00, 0x04, // v=2, p=0, FMT=4, PSFB, len=4
+            0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+            0x00, 0x00, 0x00, 0x00, // media=0x0
+            0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
+            0x07, 0x00, 0x00, 0x00, // seqno=7
+        ])
+    );
+}
@@ -33,6 +33,23 @@ pub struct FullIntraRequest {
 
 const FIR_OFFSET: usize = 8;
 
+impl FullIntraRequest {
+    /// new builds a FullIntraRequest from `sender_ssrc` requesting a keyframe
+    /// for `media_ssrc`. `sequence_number` must be incremented for every new
+    /// request sent to the same media source, and kept when a request is
+    /// repeated. The media SSRC of the common header is unused and set to 0.
+    pub fn new(sender_ssrc: u32, media_ssrc: u32, sequence_number: u8) -> Self {
+        FullIntraRequest {
+            sender_ssrc,
+            media_ssrc: 0,
+            fir: vec![FirEntry {
+                ssrc: media_ssrc,
+                sequence_number,
+            }],
+        }
+    }
+}
+
 impl fmt::Display for FullIntraRequest {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = format!("FullIntraRequest {} {}", self.sender_ssrc, self.media_ssrc);
@@ -25,6 +25,17 @@ pub struct PictureLossIndication {
     pub media_ssrc: u32,
 }
 
+impl PictureLossIndication {
+    /// new builds a PictureLossIndication from `sender_ssrc` requesting a
+    /// keyframe for `media_ssrc`.
+    pub fn new(sender_ssrc: u32, media_ssrc: u32) -> Self {
+        PictureLossIndication {
+            sender_ssrc,
+            media_ssrc,
+        }
+    }
+}
+
 impl fmt::Display for PictureLossIndication {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -160,3 +160,20 @@ fn test_picture_loss_indication_unmarshal_header() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_picture_loss_indication_new() {
+    let pli = PictureLossIndication::new(0x902f9e2e, 0x4bc4fcb4);
+    assert_eq!(pli.sender_ssrc, 0x902f9e2e);
+    assert_eq!(pli.destination_ssrc(), vec![0x4bc4fcb4]);
+
+    let data = pli.marshal().expect("Marshal pli");
+    assert_eq!(
+        data,
+        Bytes::from_static(&[
+            0x81, 0xce, 0x00, 0x02, // v=2, p=0, FMT=1, PSFB, len=2
+            0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+            0x4b, 0xc4, 0xfc, 0xb4, // media=0x4bc4fcb4
+        ])
+    );
+}
@@ -29,6 +29,20 @@ pub struct ReceiverEstimatedMaximumBitrate {
 }
 
 const REMB_OFFSET: usize = 16;
+/// The number of SSRC entries is carried by a single octet.
+const SSRCS_MAX: usize = (1 << 8) - 1;
+
+impl ReceiverEstimatedMaximumBitrate {
+    /// new builds a REMB from `sender_ssrc` estimating the maximum bitrate, in
+    /// bits per second, available to the streams of `ssrcs`.
+    pub fn new(sender_ssrc: u32, bitrate: f32, ssrcs: Vec<u32>) -> Self {
+        ReceiverEstimatedMaximumBitrate {
+            sender_ssrc,
+            bitrate,
+            ssrcs,
+        }
+    }
+}
 
 /// Keep a table of powers to units for fast conversion.
 const BIT_UNITS: [&str; 7] = ["b", "Kb", "Mb", "Gb", "Tb", "Pb", "Eb"];
@@ -125,6 +139,10 @@ impl Marshal for ReceiverEstimatedMaximumBitrate {
            |  ...                                                          |
         */
 
+        if self.ssrcs.len() > SSRCS_MAX {
+            return Err(Error::TooManySources.into());
+        }
+
         if buf.remaining_mut() < self.marshal_size() {
             return Err(Error::BufferTooShort.into());
         }
@@ -264,7 +282,15 @@ impl Unmarshal for ReceiverEstimatedMaximumBitrate {
         }
 
         // bitrate = mantissa * 2^exp
-        let bitrate = f32::from_bits(((exp as u32) << 23) | (mantissa & MANTISSA_MAX));
+        let bitrate = if mantissa != 0 {
+            f32::from_bits(((exp as u32) << 23) | (mantissa & MANTISSA_MAX))
+        } else {
+            0.0
+        };
+
+        if raw_packet.remaining() < ssrcs_len * SSRC_LENGTH {
+            return Err(Error::PacketTooShort.into());
+        }
 
         let mut ssrcs = vec![];
         for _i in 0..ssrcs_len {
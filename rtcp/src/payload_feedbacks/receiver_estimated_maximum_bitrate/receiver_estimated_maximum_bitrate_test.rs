@@ -112,10 +112,47 @@ fn test_receiver_estimated_maximum_bitrate_overflow() {
     let output = packet.marshal().unwrap();
     assert_eq!(output, expected);
 
-    // Finally, try unmarshalling one number higher than we used to be able to handle.
+    // Finally, try unmarshalling the largest exponent with a zero mantissa. The bitrate is
+    // mantissa * 2^exp, so it's zero; the zero mantissa used to be decoded as if it carried
+    // the implicit leading bit of an IEEE754 float, which gave 2^70 instead.
     let mut input = Bytes::from_static(&[
         143, 206, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 82, 69, 77, 66, 0, 188, 0, 0,
     ]);
     let packet = ReceiverEstimatedMaximumBitrate::unmarshal(&mut input).unwrap();
-    assert_eq!(packet.bitrate, f32::from_bits(0x62800000));
+    assert_eq!(packet.bitrate, 0.0);
+}
+
+#[test]
+fn test_receiver_estimated_maximum_bitrate_zero() {
+    let packet = ReceiverEstimatedMaximumBitrate::new(1, 0.0, vec![1215622422]);
+
+    let mut output = packet.marshal().unwrap();
+    assert_eq!(
+        output,
+        Bytes::from_static(&[
+            143, 206, 0, 5, 0, 0, 0, 1, 0, 0, 0, 0, 82, 69, 77, 66, 1, 0, 0, 0, 72, 116, 237, 22,
+        ])
+    );
+
+    let packet = ReceiverEstimatedMaximumBitrate::unmarshal(&mut output).unwrap();
+    assert_eq!(packet.bitrate, 0.0);
+}
+
+#[test]
+fn test_receiver_estimated_maximum_bitrate_truncated_ssrcs() {
+    // Num SSRC = 2, but a single SSRC follows
+    let mut input = Bytes::from_static(&[
+        143, 206, 0, 5, 0, 0, 0, 1, 0, 0, 0, 0, 82, 69, 77, 66, 2, 26, 32, 223, 72, 116, 237, 22,
+    ]);
+
+    let result = ReceiverEstimatedMaximumBitrate::unmarshal(&mut input);
+    assert_eq!(Error::PacketTooShort, result.unwrap_err());
+}
+
+#[test]
+fn test_receiver_estimated_maximum_bitrate_too_many_ssrcs() {
+    let packet = ReceiverEstimatedMaximumBitrate::new(1, 8927168.0, vec![0; 256]);
+
+    let result = packet.marshal();
+    assert_eq!(Error::TooManySources, result.unwrap_err());
 }
@@ -214,12 +214,10 @@ impl Unmarshal for ReceiverReport {
             reports.push(reception_report);
             offset += RECEPTION_REPORT_LENGTH;
         }
-        let profile_extensions = raw_packet.copy_to_bytes(raw_packet.remaining());
-        /*
-        if header.padding && raw_packet.has_remaining() {
-            raw_packet.advance(raw_packet.remaining());
+        let mut profile_extensions = raw_packet.copy_to_bytes(raw_packet.remaining());
+        if header.padding {
+            strip_padding(&mut profile_extensions)?;
         }
-         */
 
         Ok(ReceiverReport {
             ssrc,
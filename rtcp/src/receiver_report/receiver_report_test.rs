@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use super::*;
 
 #[test]
@@ -202,6 +204,30 @@ fn test_receiver_report_roundtrip() {
             },
             Some(Error::InvalidTotalLost),
         ),
+        (
+            "totallost 24-bit overflow",
+            ReceiverReport {
+                ssrc: 1,
+                reports: vec![ReceptionReport {
+                    total_lost: 1 << 24,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            Some(Error::InvalidTotalLost),
+        ),
+        (
+            "padded profile extensions",
+            ReceiverReport {
+                ssrc: 1,
+                reports: vec![ReceptionReport {
+                    total_lost: (1 << 24) - 1,
+                    ..Default::default()
+                }],
+                profile_extensions: Bytes::from_static(&[0x01, 0x02, 0x03, 0x04, 0x05]),
+            },
+            None,
+        ),
         (
             "count overflow",
             ReceiverReport {
@@ -240,3 +266,44 @@ fn test_receiver_report_roundtrip() {
         }
     }
 }
+
+#[test]
+fn test_receiver_report_unmarshal_padding() {
+    let mut data = Bytes::from_static(&[
+        0xa0, 0xc9, 0x0, 0x2, // v=2, p=1, count=0, RR, len=2
+        0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+        0xab, 0x0, 0x0, 0x3, // profile extension 0xab, 3 bytes of padding
+    ]);
+    let rr = ReceiverReport::unmarshal(&mut data).expect("Unmarshal padded rr");
+    assert_eq!(rr.ssrc, 0x902f9e2e);
+    assert_eq!(rr.profile_extensions, Bytes::from_static(&[0xab]));
+
+    let mut data = Bytes::from_static(&[
+        0xa0, 0xc9, 0x0, 0x2, // v=2, p=1, count=0, RR, len=2
+        0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+        0x0, 0x0, 0x0, 0x5, // padding longer than the packet body
+    ]);
+    let got_err = ReceiverReport::unmarshal(&mut data).expect_err("Unmarshal bad padding");
+    assert_eq!(Error::WrongPadding, got_err);
+}
+
+#[test]
+fn test_reception_report_round_trip_time() {
+    let report = ReceptionReport {
+        last_sender_report: 0x0001_0000,
+        delay: 0x0000_8000,
+        ..Default::default()
+    };
+    // received 1.75s after the sender report, held 0.5s by the receiver
+    assert_eq!(
+        report.round_trip_time(0x0002_c000),
+        Some(Duration::from_millis(1250))
+    );
+    // arrival before LSR + DLSR
+    assert_eq!(report.round_trip_time(0x0001_4000), None);
+    // no sender report received yet
+    assert_eq!(
+        ReceptionReport::default().round_trip_time(0x0002_c000),
+        None
+    );
+}
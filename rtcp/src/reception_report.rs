@@ -1,5 +1,6 @@
 use std::any::Any;
 use std::fmt;
+use std::time::Duration;
 
 use bytes::{Buf, BufMut};
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
@@ -49,6 +50,16 @@ pub struct ReceptionReport {
     pub delay: u32,
 }
 
+impl ReceptionReport {
+    /// round_trip_time computes the round trip time from the LSR and DLSR fields,
+    /// given `now`, the middle 32 bits of the NTP time at which this report was
+    /// received. It returns None when no sender report was acknowledged yet or
+    /// when the fields are inconsistent with `now`.
+    pub fn round_trip_time(&self, now: u32) -> Option<Duration> {
+        round_trip_time(now, self.last_sender_report, self.delay)
+    }
+}
+
 impl fmt::Display for ReceptionReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{self:?}")
@@ -121,7 +132,7 @@ impl Marshal for ReceptionReport {
         buf.put_u8(self.fraction_lost);
 
         // pack TotalLost into 24 bits
-        if self.total_lost >= (1 << 25) {
+        if self.total_lost >= (1 << 24) {
             return Err(Error::InvalidTotalLost.into());
         }
 
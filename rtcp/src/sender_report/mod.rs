@@ -64,6 +64,14 @@ pub struct SenderReport {
     pub profile_extensions: Bytes,
 }
 
+impl SenderReport {
+    /// last_sender_report returns the middle 32 bits of the NTP timestamp, as
+    /// echoed back by receivers in the LSR field of their reception reports.
+    pub fn last_sender_report(&self) -> u32 {
+        (self.ntp_time >> 16) as u32
+    }
+}
+
 impl fmt::Display for SenderReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = format!("SenderReport from {}\n", self.ssrc);
@@ -279,12 +287,10 @@ impl Unmarshal for SenderReport {
             reports.push(reception_report);
             offset += RECEPTION_REPORT_LENGTH;
         }
-        let profile_extensions = raw_packet.copy_to_bytes(raw_packet.remaining());
-        /*
-        if header.padding && raw_packet.has_remaining() {
-            raw_packet.advance(raw_packet.remaining());
+        let mut profile_extensions = raw_packet.copy_to_bytes(raw_packet.remaining());
+        if header.padding {
+            strip_padding(&mut profile_extensions)?;
         }
-         */
 
         Ok(SenderReport {
             ssrc,
@@ -250,3 +250,31 @@ fn test_sender_report_roundtrip() {
         }
     }
 }
+
+#[test]
+fn test_sender_report_last_sender_report() {
+    let sr = SenderReport {
+        ntp_time: 0xda8bd1fcdddda05a,
+        ..Default::default()
+    };
+    assert_eq!(sr.last_sender_report(), 0xd1fcdddd);
+}
+
+#[test]
+fn test_sender_report_padded_profile_extensions() {
+    let want = SenderReport {
+        ssrc: 1,
+        ntp_time: 2,
+        rtp_time: 3,
+        packet_count: 4,
+        octet_count: 5,
+        reports: vec![],
+        profile_extensions: Bytes::from_static(&[0x01, 0x02, 0x03]),
+    };
+    let mut data = want.marshal().expect("Marshal padded sr");
+    assert_eq!(data.len(), 32);
+    assert_eq!(data[0] & 0x20, 0x20, "padding bit must be set");
+
+    let got = SenderReport::unmarshal(&mut data).expect("Unmarshal padded sr");
+    assert_eq!(got, want);
+}
@@ -450,6 +450,69 @@ pub struct TransportLayerCc {
     pub recv_deltas: Vec<RecvDelta>,
 }
 
+/// PacketArrival is the reception status of a single packet reported by a
+/// TransportLayerCc.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct PacketArrival {
+    /// Transport wide sequence number of the packet
+    pub sequence_number: u16,
+    /// Arrival time of the packet in us, on the clock of the reference time.
+    /// None if the packet was not received, or reported without a recv delta.
+    pub arrival_time: Option<i64>,
+}
+
+/// The reference time is expressed in multiples of 64ms
+const REFERENCE_TIME_SCALE_FACTOR: i64 = 64000;
+
+impl TransportLayerCc {
+    /// packet_arrivals walks the packet status chunks and recv deltas to
+    /// reconstruct the arrival time of every packet reported by this feedback.
+    pub fn packet_arrivals(&self) -> Vec<PacketArrival> {
+        let mut arrivals = Vec::with_capacity(self.packet_status_count as usize);
+        let mut recv_deltas = self.recv_deltas.iter();
+        let mut arrival_time = self.reference_time as i64 * REFERENCE_TIME_SCALE_FACTOR;
+
+        let mut push = |symbol: SymbolTypeTcc| {
+            if arrivals.len() >= self.packet_status_count as usize {
+                return;
+            }
+
+            let received = match symbol {
+                SymbolTypeTcc::PacketReceivedSmallDelta
+                | SymbolTypeTcc::PacketReceivedLargeDelta => recv_deltas.next().map(|d| {
+                    arrival_time += d.delta;
+                    arrival_time
+                }),
+                _ => None,
+            };
+
+            arrivals.push(PacketArrival {
+                sequence_number: self
+                    .base_sequence_number
+                    .wrapping_add(arrivals.len() as u16),
+                arrival_time: received,
+            });
+        };
+
+        for chunk in &self.packet_chunks {
+            match chunk {
+                PacketStatusChunk::RunLengthChunk(c) => {
+                    for _ in 0..c.run_length {
+                        push(c.packet_status_symbol);
+                    }
+                }
+                PacketStatusChunk::StatusVectorChunk(c) => {
+                    for symbol in &c.symbol_list {
+                        push(*symbol);
+                    }
+                }
+            }
+        }
+
+        arrivals
+    }
+}
+
 impl fmt::Display for TransportLayerCc {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = String::new();
@@ -925,3 +925,122 @@ fn test_transport_layer_cc_marshal() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_transport_layer_cc_packet_arrivals() -> Result<()> {
+    let reference: i64 = 4567386 * 64000;
+    let tests = vec![
+        (
+            "status vector chunks",
+            TransportLayerCc {
+                base_sequence_number: 372,
+                packet_status_count: 14,
+                reference_time: 4567386,
+                packet_chunks: vec![
+                    PacketStatusChunk::StatusVectorChunk(StatusVectorChunk {
+                        type_tcc: StatusChunkTypeTcc::StatusVectorChunk,
+                        symbol_size: SymbolSizeTypeTcc::TwoBit,
+                        symbol_list: vec![
+                            SymbolTypeTcc::PacketReceivedSmallDelta,
+                            SymbolTypeTcc::PacketReceivedLargeDelta,
+                            SymbolTypeTcc::PacketNotReceived,
+                            SymbolTypeTcc::PacketNotReceived,
+                            SymbolTypeTcc::PacketNotReceived,
+                            SymbolTypeTcc::PacketNotReceived,
+                            SymbolTypeTcc::PacketNotReceived,
+                        ],
+                    }),
+                    PacketStatusChunk::StatusVectorChunk(StatusVectorChunk {
+                        type_tcc: StatusChunkTypeTcc::StatusVectorChunk,
+                        symbol_size: SymbolSizeTypeTcc::TwoBit,
+                        symbol_list: vec![
+                            SymbolTypeTcc::PacketReceivedWithoutDelta,
+                            SymbolTypeTcc::PacketNotReceived,
+                            SymbolTypeTcc::PacketNotReceived,
+                            SymbolTypeTcc::PacketReceivedSmallDelta,
+                            SymbolTypeTcc::PacketNotReceived,
+                            SymbolTypeTcc::PacketNotReceived,
+                            SymbolTypeTcc::PacketNotReceived,
+                        ],
+                    }),
+                ],
+                recv_deltas: vec![
+                    RecvDelta {
+                        type_tcc_packet: SymbolTypeTcc::PacketReceivedSmallDelta,
+                        delta: 52000,
+                    },
+                    RecvDelta {
+                        type_tcc_packet: SymbolTypeTcc::PacketReceivedLargeDelta,
+                        delta: -1000,
+                    },
+                    RecvDelta {
+                        type_tcc_packet: SymbolTypeTcc::PacketReceivedSmallDelta,
+                        delta: 250,
+                    },
+                ],
+                ..Default::default()
+            },
+            vec![
+                (372, Some(reference + 52000)),
+                (373, Some(reference + 51000)),
+                (374, None),
+                (375, None),
+                (376, None),
+                (377, None),
+                (378, None),
+                (379, None),
+                (380, None),
+                (381, None),
+                (382, Some(reference + 51250)),
+                (383, None),
+                (384, None),
+                (385, None),
+            ],
+        ),
+        (
+            "run length chunk across rollover",
+            TransportLayerCc {
+                base_sequence_number: 65534,
+                packet_status_count: 3,
+                reference_time: 4567386,
+                packet_chunks: vec![PacketStatusChunk::RunLengthChunk(RunLengthChunk {
+                    type_tcc: StatusChunkTypeTcc::RunLengthChunk,
+                    packet_status_symbol: SymbolTypeTcc::PacketReceivedSmallDelta,
+                    // more than packet_status_count, extra symbols are ignored
+                    run_length: 4,
+                })],
+                recv_deltas: vec![
+                    RecvDelta {
+                        type_tcc_packet: SymbolTypeTcc::PacketReceivedSmallDelta,
+                        delta: 1000,
+                    },
+                    RecvDelta {
+                        type_tcc_packet: SymbolTypeTcc::PacketReceivedSmallDelta,
+                        delta: 2000,
+                    },
+                    RecvDelta {
+                        type_tcc_packet: SymbolTypeTcc::PacketReceivedSmallDelta,
+                        delta: 3000,
+                    },
+                ],
+                ..Default::default()
+            },
+            vec![
+                (65534, Some(reference + 1000)),
+                (65535, Some(reference + 3000)),
+                (0, Some(reference + 6000)),
+            ],
+        ),
+    ];
+
+    for (name, packet, want) in tests {
+        let got: Vec<(u16, Option<i64>)> = packet
+            .packet_arrivals()
+            .iter()
+            .map(|a| (a.sequence_number, a.arrival_time))
+            .collect();
+        assert_eq!(got, want, "Packet arrivals {name}");
+    }
+
+    Ok(())
+}
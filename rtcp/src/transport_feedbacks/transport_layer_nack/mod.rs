@@ -59,6 +59,7 @@ impl Iterator for NackIterator {
 }
 
 impl NackPair {
+    /// new creates a NackPair reporting `seq` as lost, and no following packets.
     pub fn new(seq: u16) -> Self {
         Self {
             packet_id: seq,
@@ -71,6 +72,7 @@ impl NackPair {
         self.into_iter().collect()
     }
 
+    /// range calls `f` with each Nack'd packet until it returns false.
     pub fn range<F>(&self, f: F)
     where
         F: Fn(u16) -> bool,
@@ -114,6 +116,23 @@ pub struct TransportLayerNack {
     pub nacks: Vec<NackPair>,
 }
 
+impl TransportLayerNack {
+    /// new builds a TransportLayerNack from `sender_ssrc` reporting the loss of
+    /// `seq_nos` in the stream of `media_ssrc`.
+    pub fn new(sender_ssrc: u32, media_ssrc: u32, seq_nos: &[u16]) -> Self {
+        TransportLayerNack {
+            sender_ssrc,
+            media_ssrc,
+            nacks: nack_pairs_from_sequence_numbers(seq_nos),
+        }
+    }
+
+    /// packet_list returns the sequence numbers of all the packets Nack'd by this packet.
+    pub fn packet_list(&self) -> Vec<u16> {
+        sequence_numbers_from_nack_pairs(&self.nacks)
+    }
+}
+
 impl fmt::Display for TransportLayerNack {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = format!("TransportLayerNack from {:x}\n", self.sender_ssrc);
@@ -247,6 +266,8 @@ impl Unmarshal for TransportLayerNack {
     }
 }
 
+/// nack_pairs_from_sequence_numbers encodes the lost sequence numbers `seq_nos`,
+/// sorted in ascending order, as NackPairs.
 pub fn nack_pairs_from_sequence_numbers(seq_nos: &[u16]) -> Vec<NackPair> {
     if seq_nos.is_empty() {
         return vec![];
@@ -273,3 +294,9 @@ pub fn nack_pairs_from_sequence_numbers(seq_nos: &[u16]) -> Vec<NackPair> {
 
     pairs
 }
+
+/// sequence_numbers_from_nack_pairs decodes the lost sequence numbers carried
+/// by `pairs`, the reverse of nack_pairs_from_sequence_numbers.
+pub fn sequence_numbers_from_nack_pairs(pairs: &[NackPair]) -> Vec<u16> {
+    pairs.iter().flat_map(|pair| pair.into_iter()).collect()
+}
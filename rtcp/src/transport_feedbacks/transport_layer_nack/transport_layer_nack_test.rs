@@ -359,3 +359,27 @@ fn test_lost_packets_is_reset_when_crossing_16_bit_boundary() {
         ],
     )
 }
+
+#[test]
+fn test_transport_layer_nack_sequence_numbers_round_trip() {
+    let tests = vec![
+        vec![],
+        vec![100u16],
+        vec![100, 101, 105, 115],
+        vec![100, 117, 500, 501, 502],
+        vec![100, 117, 65534, 65535, 0, 1, 99],
+        (0u16..=40u16).collect(),
+    ];
+
+    for seq_nos in tests {
+        let pairs = nack_pairs_from_sequence_numbers(&seq_nos);
+        let actual = sequence_numbers_from_nack_pairs(&pairs);
+        assert_eq!(actual, seq_nos, "round trip of {pairs:?}");
+
+        let nack = TransportLayerNack::new(0x902f9e2e, 0x4bc4fcb4, &seq_nos);
+        assert_eq!(nack.sender_ssrc, 0x902f9e2e);
+        assert_eq!(nack.media_ssrc, 0x4bc4fcb4);
+        assert_eq!(nack.nacks, pairs);
+        assert_eq!(nack.packet_list(), seq_nos);
+    }
+}
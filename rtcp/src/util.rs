@@ -1,4 +1,6 @@
-use bytes::BufMut;
+use std::time::Duration;
+
+use bytes::{BufMut, Bytes};
 
 use crate::error::{Error, Result};
 
@@ -22,6 +24,37 @@ pub(crate) fn put_padding(mut buf: &mut [u8], len: usize) {
     }
 }
 
+// strip_padding removes the padding at the end of `data`, the last octet of which holds
+// the padding length, itself included
+pub(crate) fn strip_padding(data: &mut Bytes) -> Result<()> {
+    let padding_len = match data.last() {
+        Some(&n) => n as usize,
+        None => return Err(Error::WrongPadding),
+    };
+    if padding_len == 0 || padding_len > data.len() {
+        return Err(Error::WrongPadding);
+    }
+    data.truncate(data.len() - padding_len);
+
+    Ok(())
+}
+
+// round_trip_time computes A - LSR - DLSR, all of them in units of 1/65536 seconds
+// (the middle 32 bits of NTP times). It returns None when no report was echoed
+// (`last` is 0) or when the result would be negative.
+pub(crate) fn round_trip_time(now: u32, last: u32, delay: u32) -> Option<Duration> {
+    if last == 0 {
+        return None;
+    }
+
+    let rtt = now.wrapping_sub(last).wrapping_sub(delay);
+    if rtt >= 1 << 31 {
+        return None;
+    }
+
+    Some(Duration::from_nanos((rtt as u64 * 1_000_000_000) >> 16))
+}
+
 // set_nbits_of_uint16 will truncate the value to size, left-shift to start_index position and set
 pub(crate) fn set_nbits_of_uint16(
     src: u16,
@@ -75,6 +108,20 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_strip_padding() -> Result<()> {
+        let mut data = Bytes::from_static(&[0x01, 0x02, 0x00, 0x02]);
+        strip_padding(&mut data)?;
+        assert_eq!(data, Bytes::from_static(&[0x01, 0x02]));
+
+        for wrong in [&[][..], &[0x01, 0x00], &[0x03, 0x03]] {
+            let mut data = Bytes::copy_from_slice(wrong);
+            assert_eq!(strip_padding(&mut data), Err(Error::WrongPadding));
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_set_nbits_of_uint16() -> Result<()> {
         let tests = vec![
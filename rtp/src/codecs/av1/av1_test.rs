@@ -1,3 +1,4 @@
+use crate::codecs::av1::leb128::leb128_size;
 use crate::codecs::av1::obu::{
     OBU_HAS_EXTENSION_BIT, OBU_TYPE_FRAME, OBU_TYPE_FRAME_HEADER, OBU_TYPE_METADATA,
     OBU_TYPE_SEQUENCE_HEADER, OBU_TYPE_TEMPORAL_DELIMITER, OBU_TYPE_TILE_GROUP, OBU_TYPE_TILE_LIST,
@@ -452,3 +453,183 @@ fn test_split_two_obus_into_two_packets() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_depacketize_restores_obus_with_size() -> Result<()> {
+    let frame = build_av1_frame(&vec![
+        Av1Obu::new(OBU_TYPE_SEQUENCE_HEADER).with_payload(vec![11, 12]),
+        Av1Obu::new(OBU_TYPE_FRAME)
+            .with_extension(OBU_EXTENSION_S1T1)
+            .with_payload(vec![27; 1200]),
+        Av1Obu::new(OBU_TYPE_METADATA).with_payload(vec![1, 2, 3]),
+    ]);
+    let mut payloader = Av1Payloader {};
+    let packets = payloader.payload(100, &frame)?;
+    assert!(packets.len() > 1);
+
+    let mut depacketizer = Av1Packet::default();
+    let mut depacketized = BytesMut::new();
+    assert!(depacketizer.is_partition_head(&packets[0]));
+    assert!(!depacketizer.is_partition_head(&packets[1]));
+    for packet in &packets {
+        depacketized.put(depacketizer.depacketize(packet)?);
+    }
+    assert_eq!(depacketized.freeze(), frame);
+
+    Ok(())
+}
+
+#[test]
+fn test_depacketize_obu_without_size() -> Result<()> {
+    let mut depacketizer = Av1Packet::default();
+
+    // W=2: the first element has a size field, the last one does not
+    let packet = Bytes::from_static(&[
+        0b0010_1000,                   // aggregation header
+        3,                             // size of the first OBU
+        OBU_TYPE_SEQUENCE_HEADER << 3, // header
+        11,
+        12,
+        OBU_TYPE_FRAME << 3, // header of the second OBU
+        1,
+        2,
+    ]);
+    let payload = depacketizer.depacketize(&packet)?;
+    assert!(depacketizer.n);
+    assert_eq!(depacketizer.w, 2);
+    assert_eq!(
+        payload,
+        Bytes::from_static(&[
+            OBU_TYPE_SEQUENCE_HEADER << 3 | OBU_HAS_SIZE_BIT,
+            2,
+            11,
+            12,
+            OBU_TYPE_FRAME << 3 | OBU_HAS_SIZE_BIT,
+            2,
+            1,
+            2,
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_depacketize_drops_fragments_after_loss() -> Result<()> {
+    let frame = build_av1_frame(&vec![
+        Av1Obu::new(OBU_TYPE_FRAME).with_payload(vec![27; 1200])
+    ]);
+    let mut payloader = Av1Payloader {};
+    let packets = payloader.payload(100, &frame)?;
+
+    // The first packet is lost, none of the remaining fragments can be used
+    let mut depacketizer = Av1Packet::default();
+    for packet in packets.iter().skip(1) {
+        assert!(depacketizer.depacketize(packet)?.is_empty());
+    }
+
+    // The last packet is lost, the next OBU is returned on its own
+    let mut depacketizer = Av1Packet::default();
+    for packet in packets.iter().take(packets.len() - 1) {
+        assert!(depacketizer.depacketize(packet)?.is_empty());
+    }
+    let next = Bytes::from_static(&[0b0001_0000, OBU_TYPE_FRAME << 3, 1]);
+    assert_eq!(
+        depacketizer.depacketize(&next)?,
+        Bytes::from_static(&[OBU_TYPE_FRAME << 3 | OBU_HAS_SIZE_BIT, 1, 1])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_depacketize_short_packet() {
+    let mut depacketizer = Av1Packet::default();
+
+    assert!(depacketizer.depacketize(&Bytes::new()).is_err());
+    assert!(depacketizer
+        .depacketize(&Bytes::from_static(&[0b0001_0000]))
+        .is_err());
+
+    // The size of the element is larger than the packet
+    assert!(depacketizer
+        .depacketize(&Bytes::from_static(&[
+            0b0000_0000,
+            5,
+            OBU_TYPE_FRAME << 3,
+            1
+        ]))
+        .is_err());
+
+    // Unterminated leb128 size
+    assert!(depacketizer
+        .depacketize(&Bytes::from_static(&[0b0000_0000, 0x80]))
+        .is_err());
+}
+
+#[test]
+fn test_av1_layer_filter() -> Result<()> {
+    let packet = |payload: Vec<u8>| Packet {
+        payload: Bytes::from(payload),
+        ..Default::default()
+    };
+    // A single OBU element with an extension header carrying the layer ids
+    let layer_packet = |sid: u8, tid: u8| {
+        packet(vec![
+            0b0001_0000,
+            OBU_TYPE_FRAME << 3 | OBU_HAS_EXTENSION_BIT,
+            (tid << 5) | (sid << 3),
+            0xAA,
+        ])
+    };
+
+    let mut filter = Av1LayerFilter::new(0, 1);
+
+    assert!(filter.filter(&layer_packet(0, 0))?);
+    assert!(filter.filter(&layer_packet(0, 1))?);
+    assert!(!filter.filter(&layer_packet(0, 2))?);
+    assert!(!filter.filter(&layer_packet(1, 0))?);
+
+    // A fragment continuing the dropped OBU is dropped as well
+    let continuation = packet(vec![0b1001_0000, 0xAA]);
+    assert!(!filter.filter(&continuation)?);
+
+    filter.spatial_layer = 1;
+    assert!(filter.filter(&continuation)?);
+    assert!(filter.filter(&layer_packet(1, 0))?);
+
+    // The layer is read from the first OBU with an extension header
+    assert!(!filter.filter(&packet(vec![
+        0b0010_0000,
+        2,
+        OBU_TYPE_SEQUENCE_HEADER << 3,
+        0xAA,
+        OBU_TYPE_FRAME << 3 | OBU_HAS_EXTENSION_BIT,
+        OBU_EXTENSION_S1T1 | (1 << 6),
+        0xAA,
+    ]))?);
+
+    // Packets without layer ids can't be filtered
+    assert!(filter.filter(&packet(vec![0b0001_0000, OBU_TYPE_FRAME << 3, 0xAA]))?);
+
+    let mut padding = Packet::default();
+    padding.header.padding = true;
+    assert!(filter.filter(&padding)?);
+
+    assert!(filter.filter(&packet(vec![0b0001_0000])).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_leb128_round_trip() {
+    for value in [0, 1, 0x7F, 0x80, 0x3FFF, 0x4000, 0x0FFF_FFFF, u32::MAX] {
+        let mut buf = BytesMut::new();
+        buf.put_leb128(value);
+        assert_eq!(buf.len(), leb128_size(value), "size of {value}");
+
+        let (decoded, n) = read_leb128(&buf.freeze());
+        assert_eq!(decoded, value, "value of {value}");
+        assert_eq!(n, leb128_size(value), "read size of {value}");
+    }
+}
@@ -26,14 +26,16 @@ pub fn decode_leb128(mut val: u32) -> u32 {
     }
 }
 
+/// read_leb128 reads a LEB128 value of at most 5 bytes, as values larger than u32 are
+/// not used by AV1. Returns the value and the number of bytes read, or (0, 0) if the
+/// value isn't terminated.
 pub fn read_leb128(bytes: &Bytes) -> (u32, usize) {
-    let mut encoded = 0;
-    for i in 0..bytes.len() {
-        encoded |= bytes[i] as u32;
-        if bytes[i] & 0b_1000_0000 == 0 {
-            return (decode_leb128(encoded), i + 1);
+    let mut value = 0;
+    for (i, b) in bytes.iter().take(5).enumerate() {
+        value |= ((b & 0b_0111_1111) as u32) << (7 * i);
+        if b & 0b_1000_0000 == 0 {
+            return (value, i + 1);
         }
-        encoded <<= 8;
     }
     (0, 0)
 }
@@ -53,12 +55,11 @@ pub trait BytesMutExt {
 }
 
 impl BytesMutExt for BytesMut {
-    fn put_leb128(&mut self, n: u32) {
-        let mut encoded = encode_leb128(n);
-        while encoded >= 0b_1000_0000 {
-            self.put_u8(0b_1000_0000 | (encoded & 0b_0111_1111) as u8);
-            encoded >>= 7;
+    fn put_leb128(&mut self, mut n: u32) {
+        while n >= 0b_1000_0000 {
+            self.put_u8(0b_1000_0000 | (n & 0b_0111_1111) as u8);
+            n >>= 7;
         }
-        self.put_u8(encoded as u8);
+        self.put_u8(n as u8);
     }
 }
@@ -1,11 +1,13 @@
 use bytes::{BufMut, Bytes, BytesMut};
 
-use crate::codecs::av1::leb128::BytesMutExt;
-use crate::codecs::av1::obu::{obu_has_extension, parse_obus, OBU_HAS_SIZE_BIT};
+use crate::codecs::av1::leb128::{read_leb128, BytesMutExt};
+use crate::codecs::av1::obu::{obu_has_extension, obu_has_size, parse_obus, OBU_HAS_SIZE_BIT};
 use crate::codecs::av1::packetizer::{
     get_aggregation_header, packetize, AGGREGATION_HEADER_SIZE, MAX_NUM_OBUS_TO_OMIT_SIZE,
 };
-use crate::packetizer::Payloader;
+use crate::error::{Error, Result};
+use crate::packet::Packet;
+use crate::packetizer::{Depacketizer, Payloader};
 
 #[cfg(test)]
 mod av1_test;
@@ -19,7 +21,7 @@ pub struct Av1Payloader {}
 impl Payloader for Av1Payloader {
     /// Based on <https://chromium.googlesource.com/external/webrtc/+/4e513346ec56c829b3a6010664998469fc237b35/modules/rtp_rtcp/source/rtp_packetizer_av1.cc>
     /// Reference: <https://aomediacodec.github.io/av1-rtp-spec/#45-payload-structure>
-    fn payload(&mut self, mtu: usize, payload: &Bytes) -> crate::error::Result<Vec<Bytes>> {
+    fn payload(&mut self, mtu: usize, payload: &Bytes) -> Result<Vec<Bytes>> {
         // 0                   1                   2                   3
         // 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
         // +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
@@ -120,3 +122,212 @@ impl Payloader for Av1Payloader {
         Box::new(self.clone())
     }
 }
+
+/// Av1Packet depacketizes AV1 RTP payloads into a low overhead bitstream, where every OBU
+/// carries its size field, as expected by AV1 decoders.
+/// Reference: <https://aomediacodec.github.io/av1-rtp-spec/#45-payload-structure>
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct Av1Packet {
+    /// The first OBU element continues an OBU fragment of the previous packet
+    pub z: bool,
+    /// The last OBU element continues in the next packet
+    pub y: bool,
+    /// The number of OBU elements in the packet, 0 if every element has a size field
+    pub w: u8,
+    /// The packet is the first packet of a coded video sequence
+    pub n: bool,
+
+    /// Fragment of an OBU that continues in the next packet
+    fragment: Option<BytesMut>,
+}
+
+impl Depacketizer for Av1Packet {
+    /// depacketize parses the passed byte slice and returns the OBUs it completes. OBUs
+    /// fragmented across packets are returned once their last fragment is received.
+    fn depacketize(&mut self, packet: &Bytes) -> Result<Bytes> {
+        if packet.len() <= AGGREGATION_HEADER_SIZE {
+            return Err(Error::ErrShortPacket);
+        }
+
+        // 0 1 2 3 4 5 6 7
+        // +-+-+-+-+-+-+-+-+
+        // |Z|Y| W |N|-|-|-|
+        // +-+-+-+-+-+-+-+-+
+        let b = packet[0];
+        self.z = b & 0x80 != 0;
+        self.y = b & 0x40 != 0;
+        self.w = (b >> 4) & 0x03;
+        self.n = b & 0x08 != 0;
+
+        if !self.z {
+            // A fragment left over lost its end, it can't be completed anymore
+            self.fragment = None;
+        }
+
+        let mut payload = BytesMut::new();
+        let mut offset = AGGREGATION_HEADER_SIZE;
+        let mut index = 0;
+        while offset < packet.len() {
+            index += 1;
+
+            // The last of W elements has no size field
+            let element_size = if self.w as usize == index {
+                packet.len() - offset
+            } else {
+                let (size, leb128_size) = read_leb128(&packet.slice(offset..));
+                if leb128_size == 0 {
+                    return Err(Error::ErrShortPacket);
+                }
+                offset += leb128_size;
+                size as usize
+            };
+            if packet.len() < offset + element_size {
+                return Err(Error::ErrShortPacket);
+            }
+            let element = packet.slice(offset..offset + element_size);
+            offset += element_size;
+
+            let obu = if index == 1 && self.z {
+                match self.fragment.take() {
+                    Some(mut fragment) => {
+                        fragment.put(element);
+                        fragment
+                    }
+                    // The start of the OBU was lost
+                    None => continue,
+                }
+            } else {
+                BytesMut::from(&element[..])
+            };
+
+            if offset >= packet.len() && self.y {
+                self.fragment = Some(obu);
+            } else {
+                put_obu(&mut payload, obu.freeze())?;
+            }
+        }
+
+        Ok(payload.freeze())
+    }
+
+    /// is_partition_head checks whether the packet starts with a new OBU
+    fn is_partition_head(&self, payload: &Bytes) -> bool {
+        if payload.is_empty() {
+            false
+        } else {
+            (payload[0] & 0x80) == 0
+        }
+    }
+
+    fn is_partition_tail(&self, marker: bool, _payload: &Bytes) -> bool {
+        marker
+    }
+}
+
+/// put_obu writes obu to payload with its size field.
+fn put_obu(payload: &mut BytesMut, obu: Bytes) -> Result<()> {
+    if obu.is_empty() {
+        return Ok(());
+    }
+
+    let header = obu[0];
+    if obu_has_size(header) {
+        payload.put(obu);
+        return Ok(());
+    }
+
+    let header_size = if obu_has_extension(header) { 2 } else { 1 };
+    if obu.len() < header_size {
+        return Err(Error::ErrPayloadTooSmallForObuExtensionHeader);
+    }
+    payload.put_u8(header | OBU_HAS_SIZE_BIT);
+    payload.put(&obu[1..header_size]);
+    payload.put_leb128((obu.len() - header_size) as u32);
+    payload.put(&obu[header_size..]);
+
+    Ok(())
+}
+
+/// Av1LayerFilter drops the packets of an AV1 SVC stream that belong to layers above a target
+/// spatial and temporal layer, so a forwarder can send a lower layer without re-encoding.
+/// The layer of a packet is read from the first OBU extension header it carries, packets that
+/// only continue an OBU fragment keep the layer of the previous packet. Sequence numbers of the
+/// forwarded packets have to be rewritten by the caller to close the gaps left by the dropped
+/// packets.
+#[derive(Debug, Default, Clone)]
+pub struct Av1LayerFilter {
+    /// Highest spatial layer forwarded
+    pub spatial_layer: u8,
+    /// Highest temporal layer forwarded
+    pub temporal_layer: u8,
+
+    /// (spatial_id, temporal_id) of the last packet with an OBU extension header
+    layer: Option<(u8, u8)>,
+}
+
+impl Av1LayerFilter {
+    pub fn new(spatial_layer: u8, temporal_layer: u8) -> Self {
+        Av1LayerFilter {
+            spatial_layer,
+            temporal_layer,
+            ..Default::default()
+        }
+    }
+
+    /// filter returns whether packet belongs to a layer that should be forwarded. Packets
+    /// without OBU extension headers and padding only packets are always forwarded.
+    pub fn filter(&mut self, packet: &Packet) -> Result<bool> {
+        if packet.is_padding_only() {
+            return Ok(true);
+        }
+
+        let payload = &packet.payload;
+        if payload.len() <= AGGREGATION_HEADER_SIZE {
+            return Err(Error::ErrShortPacket);
+        }
+
+        let z = payload[0] & 0x80 != 0;
+        let w = ((payload[0] >> 4) & 0x03) as usize;
+        if !z {
+            self.layer = None;
+        }
+
+        let mut offset = AGGREGATION_HEADER_SIZE;
+        let mut index = 0;
+        while offset < payload.len() {
+            index += 1;
+
+            let element_size = if w == index {
+                payload.len() - offset
+            } else {
+                let (size, leb128_size) = read_leb128(&payload.slice(offset..));
+                if leb128_size == 0 {
+                    return Err(Error::ErrShortPacket);
+                }
+                offset += leb128_size;
+                size as usize
+            };
+            if payload.len() < offset + element_size {
+                return Err(Error::ErrShortPacket);
+            }
+
+            // A continued fragment has no OBU header of its own
+            let starts_obu = index > 1 || !z;
+            if starts_obu && element_size >= 2 && obu_has_extension(payload[offset]) {
+                // 0 1 2 3 4 5 6 7
+                // +-+-+-+-+-+-+-+-+
+                // |T_ID |S_ID |   |
+                // +-+-+-+-+-+-+-+-+
+                let extension = payload[offset + 1];
+                self.layer = Some(((extension >> 3) & 0x03, extension >> 5));
+                break;
+            }
+            offset += element_size;
+        }
+
+        match self.layer {
+            Some((sid, tid)) => Ok(sid <= self.spatial_layer && tid <= self.temporal_layer),
+            None => Ok(true),
+        }
+    }
+}
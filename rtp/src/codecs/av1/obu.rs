@@ -73,6 +73,11 @@ pub fn parse_obus(payload: &Bytes) -> Result<Vec<Obu>> {
                 return Err(ErrPayloadTooSmallForObuPayloadSize);
             }
             let (obu_payload_size, leb128_size) = read_leb128(&payload_without_header);
+            if leb128_size == 0
+                || payload_without_header.len() < leb128_size + obu_payload_size as usize
+            {
+                return Err(ErrPayloadTooSmallForObuPayloadSize);
+            }
             payload_data_remaining -= leb128_size as isize;
             payload_data_index += leb128_size;
             payload_without_header.slice(leb128_size..leb128_size + obu_payload_size as usize)
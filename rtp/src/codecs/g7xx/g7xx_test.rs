@@ -48,3 +48,29 @@ fn test_g7xx_payload() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_g7xx_depacketize() -> Result<()> {
+    let mut pck = G711Packet::default();
+
+    let result = pck.depacketize(&Bytes::from_static(&[]));
+    assert!(result.is_err(), "Result should be err in case of error");
+
+    let raw_bytes = Bytes::from_static(&[0xff, 0x7f, 0x00, 0x80]);
+    let payload = pck.depacketize(&raw_bytes)?;
+    assert_eq!(&raw_bytes, &payload, "Payload must be same");
+    assert!(pck.is_partition_head(&payload));
+    assert!(pck.is_partition_tail(false, &payload));
+
+    Ok(())
+}
+
+#[test]
+fn test_g7xx_timestamp_increment() {
+    // 20ms of audio at 8kHz
+    let samples = vec![0u8; 160];
+    assert_eq!(G711Payloader::timestamp_increment(&samples), 160);
+    // G722 encodes 16kHz audio in 8 bits per 2 samples, on an 8kHz clock
+    assert_eq!(G722Payloader::timestamp_increment(&samples), 160);
+    assert_eq!(G7xxPayloader::timestamp_increment(&[]), 0);
+}
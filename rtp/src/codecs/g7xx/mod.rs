@@ -3,8 +3,12 @@ mod g7xx_test;
 
 use bytes::Bytes;
 
-use crate::error::Result;
-use crate::packetizer::Payloader;
+use crate::error::{Error, Result};
+use crate::packetizer::{Depacketizer, Payloader};
+
+/// G7XX_CLOCK_RATE is the RTP clock rate of PCMU, PCMA and G722. G722 samples at
+/// 16kHz but keeps an 8kHz clock for historical reasons, see RFC 3551 section 4.5.2.
+pub const G7XX_CLOCK_RATE: u32 = 8000;
 
 /// G711Payloader payloads G711 packets
 pub type G711Payloader = G7xxPayloader;
@@ -14,6 +18,14 @@ pub type G722Payloader = G7xxPayloader;
 #[derive(Default, Debug, Copy, Clone)]
 pub struct G7xxPayloader;
 
+impl G7xxPayloader {
+    /// timestamp_increment returns the number of RTP timestamp units covered by
+    /// a G711 or G722 payload. Both codecs produce one byte per clock tick.
+    pub fn timestamp_increment(payload: &[u8]) -> u32 {
+        payload.len() as u32
+    }
+}
+
 impl Payloader for G7xxPayloader {
     /// Payload fragments an G7xx packet across one or more byte arrays
     fn payload(&mut self, mtu: usize, payload: &Bytes) -> Result<Vec<Bytes>> {
@@ -41,3 +53,29 @@ impl Payloader for G7xxPayloader {
         Box::new(*self)
     }
 }
+
+/// G711Packet represents a PCMU or PCMA payload stored in an RTP Packet
+pub type G711Packet = G7xxPacket;
+/// G722Packet represents a G722 payload stored in an RTP Packet
+pub type G722Packet = G7xxPacket;
+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct G7xxPacket;
+
+impl Depacketizer for G7xxPacket {
+    fn depacketize(&mut self, packet: &Bytes) -> Result<Bytes> {
+        if packet.is_empty() {
+            Err(Error::ErrShortPacket)
+        } else {
+            Ok(packet.clone())
+        }
+    }
+
+    fn is_partition_head(&self, _payload: &Bytes) -> bool {
+        true
+    }
+
+    fn is_partition_tail(&self, _marker: bool, _payload: &Bytes) -> bool {
+        true
+    }
+}
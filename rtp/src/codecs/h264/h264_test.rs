@@ -191,6 +191,63 @@ fn test_h264_packet_unmarshal() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_h264_packet_depacketize_idr() -> Result<()> {
+    let mut pkt = H264Packet::default();
+
+    // Single NAL unit packets
+    pkt.depacketize(&Bytes::from_static(&[0x65, 0x88, 0x84]))?;
+    assert!(pkt.is_idr(), "IDR slice should be flagged");
+    pkt.depacketize(&Bytes::from_static(&[0x41, 0x9a, 0x02]))?;
+    assert!(!pkt.is_idr(), "non-IDR slice should not be flagged");
+
+    // STAP-A with SPS, PPS and IDR slice
+    pkt.depacketize(&Bytes::from_static(&[
+        0x78, 0x00, 0x02, 0x67, 0x42, 0x00, 0x02, 0x68, 0xce, 0x00, 0x02, 0x65, 0x88,
+    ]))?;
+    assert!(
+        pkt.is_idr(),
+        "STAP-A containing an IDR slice should be flagged"
+    );
+
+    // FU-A of an IDR slice is flagged once reassembled
+    let payload = pkt.depacketize(&Bytes::from_static(&[0x7c, 0x85, 0x01, 0x02]))?;
+    assert!(payload.is_empty());
+    assert!(!pkt.is_idr());
+    let payload = pkt.depacketize(&Bytes::from_static(&[0x7c, 0x45, 0x03, 0x04]))?;
+    assert_eq!(
+        payload,
+        Bytes::from_static(&[0x00, 0x00, 0x00, 0x01, 0x65, 0x01, 0x02, 0x03, 0x04])
+    );
+    assert!(pkt.is_idr());
+
+    Ok(())
+}
+
+#[test]
+fn test_h264_packet_depacketize_fua_loss() -> Result<()> {
+    let mut pkt = H264Packet::default();
+
+    // A fragment whose start was lost can't be reassembled
+    let payload = pkt.depacketize(&Bytes::from_static(&[0x7c, 0x45, 0x03, 0x04]))?;
+    assert!(payload.is_empty());
+
+    // A new start drops the fragments of a NAL unit whose end was lost
+    pkt.depacketize(&Bytes::from_static(&[0x7c, 0x85, 0x01, 0x02]))?;
+    pkt.depacketize(&Bytes::from_static(&[0x7c, 0x81, 0x05, 0x06]))?;
+    let payload = pkt.depacketize(&Bytes::from_static(&[0x7c, 0x41, 0x07, 0x08]))?;
+    assert_eq!(
+        payload,
+        Bytes::from_static(&[0x00, 0x00, 0x00, 0x01, 0x61, 0x05, 0x06, 0x07, 0x08])
+    );
+
+    // STAP-A cut in the middle of a NAL unit size
+    let result = pkt.depacketize(&Bytes::from_static(&[0x78, 0x00, 0x01, 0x65, 0x00]));
+    assert!(result.is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_h264_partition_head_checker_is_partition_head() -> Result<()> {
     let h264 = H264Packet::default();
@@ -13,6 +13,7 @@ pub struct H264Payloader {
     pps_nalu: Option<Bytes>,
 }
 
+pub const IDR_NALU_TYPE: u8 = 5;
 pub const STAPA_NALU_TYPE: u8 = 24;
 pub const FUA_NALU_TYPE: u8 = 28;
 pub const FUB_NALU_TYPE: u8 = 29;
@@ -201,6 +202,15 @@ impl Payloader for H264Payloader {
 pub struct H264Packet {
     pub is_avc: bool,
     fua_buffer: Option<BytesMut>,
+    idr: bool,
+}
+
+impl H264Packet {
+    /// is_idr checks whether the NAL units returned by the last call to depacketize
+    /// include an IDR slice, i.e. whether decoding can start from them.
+    pub fn is_idr(&self) -> bool {
+        self.idr
+    }
 }
 
 impl Depacketizer for H264Packet {
@@ -211,6 +221,7 @@ impl Depacketizer for H264Packet {
         }
 
         let mut payload = BytesMut::new();
+        self.idr = false;
 
         // NALU Types
         // https://tools.ietf.org/html/rfc6184#section-5.4
@@ -219,6 +230,7 @@ impl Depacketizer for H264Packet {
 
         match nalu_type {
             1..=23 => {
+                self.idr = nalu_type == IDR_NALU_TYPE;
                 if self.is_avc {
                     payload.put_u32(packet.len() as u32);
                 } else {
@@ -230,6 +242,9 @@ impl Depacketizer for H264Packet {
             STAPA_NALU_TYPE => {
                 let mut curr_offset = STAPA_HEADER_SIZE;
                 while curr_offset < packet.len() {
+                    if packet.len() < curr_offset + STAPA_NALU_LENGTH_SIZE {
+                        return Err(Error::ErrShortPacket);
+                    }
                     let nalu_size =
                         ((packet[curr_offset] as usize) << 8) | packet[curr_offset + 1] as usize;
                     curr_offset += STAPA_NALU_LENGTH_SIZE;
@@ -247,6 +262,9 @@ impl Depacketizer for H264Packet {
                         payload.put(&*ANNEXB_NALUSTART_CODE);
                     }
                     payload.put(&*packet.slice(curr_offset..curr_offset + nalu_size));
+                    if nalu_size > 0 && packet[curr_offset] & NALU_TYPE_BITMASK == IDR_NALU_TYPE {
+                        self.idr = true;
+                    }
                     curr_offset += nalu_size;
                 }
 
@@ -257,15 +275,19 @@ impl Depacketizer for H264Packet {
                     return Err(Error::ErrShortPacket);
                 }
 
-                if self.fua_buffer.is_none() {
+                let b1 = packet[1];
+                if b1 & FU_START_BITMASK != 0 {
+                    // Drop what is left of a NAL unit whose end was lost
                     self.fua_buffer = Some(BytesMut::new());
                 }
 
                 if let Some(fua_buffer) = &mut self.fua_buffer {
                     fua_buffer.put(&*packet.slice(FUA_HEADER_SIZE..));
+                } else {
+                    // The start of this NAL unit was lost, it can't be reassembled
+                    return Ok(Bytes::new());
                 }
 
-                let b1 = packet[1];
                 if b1 & FU_END_BITMASK != 0 {
                     let nalu_ref_idc = b0 & NALU_REF_IDC_BITMASK;
                     let fragmented_nalu_type = b1 & NALU_TYPE_BITMASK;
@@ -278,6 +300,7 @@ impl Depacketizer for H264Packet {
                         }
                         payload.put_u8(nalu_ref_idc | fragmented_nalu_type);
                         payload.put(fua_buffer);
+                        self.idr = fragmented_nalu_type == IDR_NALU_TYPE;
                     }
 
                     Ok(payload.freeze())
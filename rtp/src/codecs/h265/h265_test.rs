@@ -887,3 +887,119 @@ fn test_h265_packet_real() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_h265_payloader_aggregation() -> Result<()> {
+    let vps = Bytes::from_static(&[0x40, 0x01, 0x0c, 0x01]);
+    let sps = Bytes::from_static(&[0x42, 0x01, 0x01, 0x01, 0x60]);
+    let pps = Bytes::from_static(&[0x44, 0x01, 0xc0, 0xf2]);
+
+    let mut annexb = BytesMut::new();
+    for nalu in [&vps, &sps, &pps] {
+        annexb.put(&*ANNEXB_NALUSTART_CODE);
+        annexb.put(&**nalu);
+    }
+    let annexb = annexb.freeze();
+
+    let mut pck = H265Payloader;
+    let payloads = pck.payload(1200, &annexb)?;
+    assert_eq!(payloads.len(), 1, "small NAL units must share a single AP");
+    assert_eq!(
+        &payloads[0][..],
+        &[
+            0x60, 0x01, // AP payload header, type 48, tid 1
+            0x00, 0x04, 0x40, 0x01, 0x0c, 0x01, // VPS
+            0x00, 0x05, 0x42, 0x01, 0x01, 0x01, 0x60, // SPS
+            0x00, 0x04, 0x44, 0x01, 0xc0, 0xf2, // PPS
+        ]
+    );
+
+    let mut depacketizer = H265Packet::default();
+    let out = depacketizer.depacketize(&payloads[0])?;
+    assert_eq!(out, annexb, "AP must depacketize back to the Annex B input");
+
+    // An MTU that can only fit one unit per AP sends each unit on its own.
+    let payloads = pck.payload(10, &annexb)?;
+    assert_eq!(payloads, vec![vps, sps, pps]);
+
+    // No start code means the whole payload is a single NAL unit.
+    let nalu = Bytes::from_static(&[0x02, 0x01, 0xaa, 0xbb]);
+    let payloads = pck.payload(1200, &nalu)?;
+    assert_eq!(payloads, vec![nalu]);
+
+    let payloads = pck.payload(1200, &Bytes::new())?;
+    assert!(payloads.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_h265_payloader_fragmentation() -> Result<()> {
+    // IDR_W_RADL NAL unit with 20 bytes of data.
+    let mut nalu = vec![0x26, 0x01];
+    nalu.extend(0..20u8);
+    let nalu = Bytes::from(nalu);
+
+    let mut pck = H265Payloader;
+    let payloads = pck.payload(10, &nalu)?;
+    assert_eq!(
+        payloads.len(),
+        3,
+        "20 bytes must be split in fragments of 7"
+    );
+    assert_eq!(
+        &payloads[0][..],
+        &[0x62, 0x01, 0x93, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]
+    );
+    assert_eq!(
+        &payloads[1][..],
+        &[0x62, 0x01, 0x13, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d]
+    );
+    assert_eq!(
+        &payloads[2][..],
+        &[0x62, 0x01, 0x53, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13]
+    );
+
+    let mut depacketizer = H265Packet::default();
+    assert!(depacketizer.is_partition_head(&payloads[0]));
+    assert!(!depacketizer.is_partition_head(&payloads[1]));
+    assert!(!depacketizer.is_partition_head(&payloads[2]));
+
+    assert!(depacketizer.depacketize(&payloads[0])?.is_empty());
+    assert!(depacketizer.depacketize(&payloads[1])?.is_empty());
+    let out = depacketizer.depacketize(&payloads[2])?;
+
+    let mut expected = BytesMut::new();
+    expected.put(&*ANNEXB_NALUSTART_CODE);
+    expected.put(&*nalu);
+    assert_eq!(out, expected.freeze(), "FUs must reassemble the NAL unit");
+
+    // A NAL unit that doesn't fit in an AP but fits in a packet is sent as is.
+    let payloads = pck.payload(nalu.len(), &nalu)?;
+    assert_eq!(payloads, vec![nalu]);
+
+    Ok(())
+}
+
+#[test]
+fn test_h265_packet_fu_loss() -> Result<()> {
+    let start = Bytes::from_static(&[0x62, 0x01, 0x93, 0x01, 0x02]);
+    let middle = Bytes::from_static(&[0x62, 0x01, 0x13, 0x03, 0x04]);
+    let end = Bytes::from_static(&[0x62, 0x01, 0x53, 0x05, 0x06]);
+
+    // Without its start the NAL unit is dropped.
+    let mut pck = H265Packet::default();
+    assert!(pck.depacketize(&middle)?.is_empty());
+    assert!(pck.depacketize(&end)?.is_empty());
+
+    // A new start discards the incomplete unit that preceded it.
+    assert!(pck.depacketize(&start)?.is_empty());
+    assert!(pck.depacketize(&start)?.is_empty());
+    let out = pck.depacketize(&end)?;
+    assert_eq!(
+        out,
+        Bytes::from_static(&[0x00, 0x00, 0x00, 0x01, 0x26, 0x01, 0x01, 0x02, 0x05, 0x06])
+    );
+
+    Ok(())
+}
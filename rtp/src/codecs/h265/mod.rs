@@ -1,7 +1,7 @@
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
 
 use crate::error::{Error, Result};
-use crate::packetizer::Depacketizer;
+use crate::packetizer::{Depacketizer, Payloader};
 
 #[cfg(test)]
 mod h265_test;
@@ -18,6 +18,11 @@ const H265NALU_FRAGMENTATION_UNIT_TYPE: u8 = 49;
 /// <https://datatracker.ietf.org/doc/html/rfc7798#section-4.4.4>
 const H265NALU_PACI_PACKET_TYPE: u8 = 50;
 
+/// Length of the NAL unit size field preceding each unit of an AP.
+const H265AGGREGATION_UNIT_SIZE_LENGTH: usize = 2;
+
+static ANNEXB_NALUSTART_CODE: Bytes = Bytes::from_static(&[0x00, 0x00, 0x00, 0x01]);
+
 /// H265NALUHeader is a H265 NAL Unit Header
 /// <https://datatracker.ietf.org/doc/html/rfc7798#section-1.1.4>
 /// +---------------+---------------+
@@ -722,6 +727,7 @@ impl Default for H265Payload {
 pub struct H265Packet {
     payload: H265Payload,
     might_need_donl: bool,
+    fu_buffer: Option<BytesMut>,
 }
 
 impl H265Packet {
@@ -740,10 +746,79 @@ impl H265Packet {
     pub fn payload(&self) -> &H265Payload {
         &self.payload
     }
+
+    /// emit_annexb converts the parsed payload into Annex B NAL units. Fragmentation
+    /// units are buffered until the fragment carrying the end bit arrives.
+    fn emit_annexb(&mut self) -> Bytes {
+        match &self.payload {
+            H265Payload::H265SingleNALUnitPacket(p) => {
+                let mut out = BytesMut::new();
+                out.put(&*ANNEXB_NALUSTART_CODE);
+                out.put_u16(p.payload_header().0);
+                out.put(&*p.payload());
+                out.freeze()
+            }
+            H265Payload::H265AggregationPacket(p) => {
+                let mut out = BytesMut::new();
+                if let Some(first_unit) = p.first_unit() {
+                    out.put(&*ANNEXB_NALUSTART_CODE);
+                    out.put(&*first_unit.nal_unit());
+                }
+                for unit in p.other_units() {
+                    out.put(&*ANNEXB_NALUSTART_CODE);
+                    out.put(&*unit.nal_unit());
+                }
+                out.freeze()
+            }
+            H265Payload::H265FragmentationUnitPacket(p) => {
+                let fu_header = p.fu_header();
+                if fu_header.s() {
+                    // Rebuild the NAL unit header from the payload header and the
+                    // FU type, dropping whatever was left of a previous unit.
+                    let nalu_header =
+                        (p.payload_header().0 & 0x81FF) | ((fu_header.fu_type() as u16) << 9);
+                    let mut buffer = BytesMut::new();
+                    buffer.put_u16(nalu_header);
+                    self.fu_buffer = Some(buffer);
+                }
+
+                // A fragment without a start means the first one was lost,
+                // the NAL unit can't be recovered so discard it.
+                let buffer = match self.fu_buffer.as_mut() {
+                    Some(buffer) => buffer,
+                    None => return Bytes::new(),
+                };
+                buffer.put(&*p.payload());
+
+                if !fu_header.e() {
+                    return Bytes::new();
+                }
+
+                let mut out = BytesMut::with_capacity(ANNEXB_NALUSTART_CODE.len() + buffer.len());
+                out.put(&*ANNEXB_NALUSTART_CODE);
+                out.put(&**buffer);
+                self.fu_buffer = None;
+                out.freeze()
+            }
+            H265Payload::H265PACIPacket(p) => {
+                // The PACI payload is a regular packet whose type was moved to cType.
+                let inner_header = (p.payload_header().0 & 0x81FF) | ((p.ctype() as u16) << 9);
+                let mut inner = BytesMut::new();
+                inner.put_u16(inner_header);
+                inner.put(&*p.payload());
+
+                let paci = self.payload.clone();
+                let out = self.depacketize(&inner.freeze()).unwrap_or_default();
+                self.payload = paci;
+                out
+            }
+        }
+    }
 }
 
 impl Depacketizer for H265Packet {
-    /// depacketize parses the passed byte slice and stores the result in the H265Packet this method is called upon
+    /// depacketize parses the passed byte slice and stores the result in the H265Packet
+    /// this method is called upon. The returned bytes hold Annex B NAL units.
     fn depacketize(&mut self, payload: &Bytes) -> Result<Bytes> {
         if payload.len() <= H265NALU_HEADER_SIZE {
             return Err(Error::ErrShortPacket);
@@ -782,12 +857,20 @@ impl Depacketizer for H265Packet {
             self.payload = H265Payload::H265SingleNALUnitPacket(decoded);
         }
 
-        Ok(payload.clone())
+        Ok(self.emit_annexb())
     }
 
     /// is_partition_head checks if this is the head of a packetized nalu stream.
-    fn is_partition_head(&self, _payload: &Bytes) -> bool {
-        //TODO:
+    fn is_partition_head(&self, payload: &Bytes) -> bool {
+        if payload.len() < H265NALU_HEADER_SIZE + H265FRAGMENTATION_UNIT_HEADER_SIZE {
+            return false;
+        }
+
+        let payload_header = H265NALUHeader::new(payload[0], payload[1]);
+        if payload_header.is_fragmentation_unit() {
+            return H265FragmentationUnitHeader(payload[2]).s();
+        }
+
         true
     }
 
@@ -795,3 +878,160 @@ impl Depacketizer for H265Packet {
         marker
     }
 }
+
+///
+/// Payloader implementation
+///
+/// H265Payloader payloads H265 packets, aggregating small NAL units into
+/// Aggregation Packets and splitting large ones into Fragmentation Units.
+/// DONL fields are never emitted, i.e. `sprop-max-don-diff` is assumed to be 0.
+#[derive(Default, Debug, Clone)]
+pub struct H265Payloader;
+
+impl H265Payloader {
+    /// split_nalus splits an Annex B byte stream on its start codes. Input
+    /// without any start code is handled as a single NAL unit.
+    fn split_nalus(payload: &Bytes) -> Vec<Bytes> {
+        let mut nalus = vec![];
+        let mut nalu_start: Option<usize> = None;
+
+        let mut i = 0;
+        while i + 2 < payload.len() {
+            if payload[i] == 0 && payload[i + 1] == 0 && payload[i + 2] == 1 {
+                if let Some(start) = nalu_start {
+                    // Trailing zeros belong to the next (4 byte) start code.
+                    let mut end = i;
+                    while end > start && payload[end - 1] == 0 {
+                        end -= 1;
+                    }
+                    nalus.push(payload.slice(start..end));
+                }
+                i += 3;
+                nalu_start = Some(i);
+            } else {
+                i += 1;
+            }
+        }
+
+        match nalu_start {
+            Some(start) => nalus.push(payload.slice(start..)),
+            None => nalus.push(payload.clone()),
+        }
+
+        nalus
+            .into_iter()
+            .filter(|nalu| nalu.len() >= H265NALU_HEADER_SIZE)
+            .collect()
+    }
+
+    /// flush_aggregated emits the pending NAL units, as a single NAL unit packet
+    /// if there is only one, or as an Aggregation Packet otherwise.
+    fn flush_aggregated(aggregated: &mut Vec<Bytes>, payloads: &mut Vec<Bytes>) {
+        match aggregated.len() {
+            0 => {}
+            1 => payloads.push(aggregated[0].clone()),
+            _ => {
+                // F is set if any aggregated unit has it set, LayerId and TID are
+                // the lowest of the aggregated units.
+                let mut f = false;
+                let mut layer_id = u8::MAX;
+                let mut tid = u8::MAX;
+                for nalu in aggregated.iter() {
+                    let header = H265NALUHeader::new(nalu[0], nalu[1]);
+                    f |= header.f();
+                    layer_id = layer_id.min(header.layer_id());
+                    tid = tid.min(header.tid());
+                }
+
+                let payload_header = ((f as u16) << 15)
+                    | ((H265NALU_AGGREGATION_PACKET_TYPE as u16) << 9)
+                    | ((layer_id as u16) << 3)
+                    | tid as u16;
+
+                let mut out = BytesMut::new();
+                out.put_u16(payload_header);
+                for nalu in aggregated.iter() {
+                    out.put_u16(nalu.len() as u16);
+                    out.put(&**nalu);
+                }
+                payloads.push(out.freeze());
+            }
+        }
+        aggregated.clear();
+    }
+
+    /// fragment splits a NAL unit that doesn't fit in a single packet into
+    /// Fragmentation Units.
+    fn fragment(nalu: &Bytes, mtu: usize, payloads: &mut Vec<Bytes>) {
+        const FU_OVERHEAD: usize = H265NALU_HEADER_SIZE + H265FRAGMENTATION_UNIT_HEADER_SIZE;
+        if mtu <= FU_OVERHEAD {
+            return;
+        }
+
+        let nalu_header = H265NALUHeader::new(nalu[0], nalu[1]);
+        let payload_header =
+            (nalu_header.0 & 0x81FF) | ((H265NALU_FRAGMENTATION_UNIT_TYPE as u16) << 9);
+
+        let data = &nalu[H265NALU_HEADER_SIZE..];
+        let max_fragment_size = mtu - FU_OVERHEAD;
+        let fragment_count = data.chunks(max_fragment_size).len();
+
+        for (i, fragment) in data.chunks(max_fragment_size).enumerate() {
+            let mut fu_header = nalu_header.nalu_type();
+            if i == 0 {
+                fu_header |= 0x80;
+            }
+            if i + 1 == fragment_count {
+                fu_header |= 0x40;
+            }
+
+            let mut out = BytesMut::with_capacity(FU_OVERHEAD + fragment.len());
+            out.put_u16(payload_header);
+            out.put_u8(fu_header);
+            out.put(fragment);
+            payloads.push(out.freeze());
+        }
+    }
+}
+
+impl Payloader for H265Payloader {
+    /// Payload fragments a H265 packet across one or more byte arrays
+    fn payload(&mut self, mtu: usize, payload: &Bytes) -> Result<Vec<Bytes>> {
+        if payload.is_empty() || mtu == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut payloads = vec![];
+        let mut aggregated = vec![];
+        let mut aggregated_size = H265NALU_HEADER_SIZE;
+
+        for nalu in H265Payloader::split_nalus(payload) {
+            let unit_size = H265AGGREGATION_UNIT_SIZE_LENGTH + nalu.len();
+            if H265NALU_HEADER_SIZE + unit_size <= mtu {
+                if aggregated_size + unit_size > mtu {
+                    H265Payloader::flush_aggregated(&mut aggregated, &mut payloads);
+                    aggregated_size = H265NALU_HEADER_SIZE;
+                }
+                aggregated_size += unit_size;
+                aggregated.push(nalu);
+                continue;
+            }
+
+            H265Payloader::flush_aggregated(&mut aggregated, &mut payloads);
+            aggregated_size = H265NALU_HEADER_SIZE;
+
+            if nalu.len() <= mtu {
+                payloads.push(nalu);
+            } else {
+                H265Payloader::fragment(&nalu, mtu, &mut payloads);
+            }
+        }
+        H265Payloader::flush_aggregated(&mut aggregated, &mut payloads);
+
+        Ok(payloads)
+    }
+
+    fn clone_to(&self) -> Box<dyn Payloader + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
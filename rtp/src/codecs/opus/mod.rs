@@ -6,9 +6,47 @@ use bytes::Bytes;
 use crate::error::{Error, Result};
 use crate::packetizer::{Depacketizer, Payloader};
 
+/// OPUS_CLOCK_RATE is the RTP clock rate of Opus, whatever the actual sampling rate.
+/// See <https://datatracker.ietf.org/doc/html/rfc7587#section-4.1>
+pub const OPUS_CLOCK_RATE: u32 = 48000;
+
 #[derive(Default, Debug, Copy, Clone)]
 pub struct OpusPayloader;
 
+impl OpusPayloader {
+    /// timestamp_increment returns the number of RTP timestamp units covered by
+    /// an Opus packet, read from its TOC byte (RFC 6716 section 3.1).
+    pub fn timestamp_increment(payload: &[u8]) -> Result<u32> {
+        if payload.is_empty() {
+            return Err(Error::ErrShortPacket);
+        }
+
+        let config = payload[0] >> 3;
+        // Frame durations in 48kHz samples
+        let frame_samples = match config {
+            // SILK-only: 10, 20, 40 or 60 ms
+            0..=11 => [480, 960, 1920, 2880][(config & 0x03) as usize],
+            // Hybrid: 10 or 20 ms
+            12..=15 => [480, 960][(config & 0x01) as usize],
+            // CELT-only: 2.5, 5, 10 or 20 ms
+            _ => [120, 240, 480, 960][(config & 0x03) as usize],
+        };
+
+        let frame_count = match payload[0] & 0x03 {
+            0 => 1,
+            1 | 2 => 2,
+            _ => {
+                if payload.len() < 2 {
+                    return Err(Error::ErrShortPacket);
+                }
+                (payload[1] & 0x3F) as u32
+            }
+        };
+
+        Ok(frame_samples * frame_count)
+    }
+}
+
 impl Payloader for OpusPayloader {
     fn payload(&mut self, mtu: usize, payload: &Bytes) -> Result<Vec<Bytes>> {
         if payload.is_empty() || mtu == 0 {
@@ -49,3 +49,41 @@ fn test_opus_is_partition_head() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_opus_timestamp_increment() -> Result<()> {
+    let tests = vec![
+        // SILK-only NB 10ms, one frame
+        (vec![0x00, 0xaa], 480),
+        // SILK-only WB 60ms, one frame
+        (vec![0x58, 0xaa], 2880),
+        // Hybrid FB 20ms, two frames of equal size
+        (vec![0x7d, 0xaa], 1920),
+        // CELT-only FB 20ms, one frame
+        (vec![0xf8, 0xaa], 960),
+        // CELT-only NB 2.5ms, two frames of different size
+        (vec![0x82, 0x01, 0xaa], 240),
+        // CELT-only FB 10ms, code 3 with 6 frames
+        (vec![0xf3, 0x06, 0xaa], 2880),
+    ];
+
+    for (payload, expected) in tests {
+        assert_eq!(
+            OpusPayloader::timestamp_increment(&payload)?,
+            expected,
+            "TOC {:#04x}",
+            payload[0]
+        );
+    }
+
+    assert_eq!(
+        OpusPayloader::timestamp_increment(&[]),
+        Err(Error::ErrShortPacket)
+    );
+    assert_eq!(
+        OpusPayloader::timestamp_increment(&[0xf3]),
+        Err(Error::ErrShortPacket)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod red_test;
+
+use std::collections::VecDeque;
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::error::{Error, Result};
+use crate::packetizer::Depacketizer;
+
+/// RED_REDUNDANT_HEADER_SIZE is the size of the header of a redundant block.
+pub const RED_REDUNDANT_HEADER_SIZE: usize = 4;
+/// RED_PRIMARY_HEADER_SIZE is the size of the header of the primary block.
+pub const RED_PRIMARY_HEADER_SIZE: usize = 1;
+/// RED_MAX_TIMESTAMP_OFFSET is the largest timestamp offset a redundant block can carry (14 bits).
+pub const RED_MAX_TIMESTAMP_OFFSET: u32 = 0x3FFF;
+/// RED_MAX_BLOCK_LENGTH is the largest redundant block that can be described (10 bits).
+pub const RED_MAX_BLOCK_LENGTH: usize = 0x03FF;
+
+/// fmtp_line returns the fmtp line advertising RED over `payload_type`
+/// with `distance` redundant blocks, e.g. `111/111` for a distance of 1.
+/// See <https://datatracker.ietf.org/doc/html/rfc2198#section-5>
+pub fn fmtp_line(payload_type: u8, distance: usize) -> String {
+    vec![payload_type.to_string(); distance + 1].join("/")
+}
+
+/// parse_fmtp parses a RED fmtp line, returning the payload type of the
+/// primary encoding and the number of redundant blocks.
+pub fn parse_fmtp(line: &str) -> Option<(u8, usize)> {
+    let payload_types = line
+        .trim()
+        .split('/')
+        .map(|pt| pt.trim().parse::<u8>())
+        .collect::<std::result::Result<Vec<u8>, _>>()
+        .ok()?;
+
+    let primary = *payload_types.first()?;
+    Some((primary, payload_types.len() - 1))
+}
+
+/// RedEncoder builds RED (RFC 2198) payloads, repeating the last `distance`
+/// payloads of the primary encoding in front of the current one.
+#[derive(Debug, Clone)]
+pub struct RedEncoder {
+    payload_type: u8,
+    distance: usize,
+    history: VecDeque<(u32, Bytes)>,
+}
+
+impl RedEncoder {
+    /// new creates a RedEncoder for the primary encoding `payload_type`.
+    pub fn new(payload_type: u8, distance: usize) -> Self {
+        RedEncoder {
+            payload_type: payload_type & 0x7F,
+            distance,
+            history: VecDeque::with_capacity(distance + 1),
+        }
+    }
+
+    /// encode wraps `payload`, sent with RTP `timestamp`, in a RED payload.
+    /// Previous payloads too old or too large to be described by a RED
+    /// header are left out.
+    pub fn encode(&mut self, timestamp: u32, payload: &Bytes) -> Bytes {
+        let redundant: Vec<(u32, &Bytes)> = self
+            .history
+            .iter()
+            .map(|(ts, data)| (timestamp.wrapping_sub(*ts), data))
+            .filter(|(offset, data)| {
+                *offset > 0
+                    && *offset <= RED_MAX_TIMESTAMP_OFFSET
+                    && data.len() <= RED_MAX_BLOCK_LENGTH
+            })
+            .collect();
+
+        let size = redundant
+            .iter()
+            .map(|(_, data)| RED_REDUNDANT_HEADER_SIZE + data.len())
+            .sum::<usize>()
+            + RED_PRIMARY_HEADER_SIZE
+            + payload.len();
+        let mut out = BytesMut::with_capacity(size);
+
+        for (offset, data) in &redundant {
+            out.put_u32(
+                0x8000_0000
+                    | ((self.payload_type as u32) << 24)
+                    | (offset << 10)
+                    | data.len() as u32,
+            );
+        }
+        out.put_u8(self.payload_type);
+        for (_, data) in &redundant {
+            out.put(&***data);
+        }
+        out.put(&**payload);
+
+        if self.distance > 0 {
+            if self.history.len() == self.distance {
+                self.history.pop_front();
+            }
+            self.history.push_back((timestamp, payload.clone()));
+        }
+
+        out.freeze()
+    }
+}
+
+/// RedBlock is one encoding carried by a RED payload.
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct RedBlock {
+    pub payload_type: u8,
+    /// timestamp_offset is subtracted from the RTP timestamp of the packet to
+    /// get the timestamp of the block, it is 0 for the primary block.
+    pub timestamp_offset: u16,
+    pub payload: Bytes,
+}
+
+/// RedPacket represents a RED payload stored in an RTP Packet
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct RedPacket {
+    blocks: Vec<RedBlock>,
+}
+
+impl RedPacket {
+    /// blocks returns the blocks of the last depacketized payload, oldest
+    /// first. The last one is the primary block.
+    pub fn blocks(&self) -> &[RedBlock] {
+        &self.blocks
+    }
+
+    /// redundant_blocks returns the blocks carrying previous payloads.
+    pub fn redundant_blocks(&self) -> &[RedBlock] {
+        match self.blocks.split_last() {
+            Some((_, redundant)) => redundant,
+            None => &[],
+        }
+    }
+}
+
+impl Depacketizer for RedPacket {
+    /// depacketize parses a RED payload and returns the primary block.
+    fn depacketize(&mut self, packet: &Bytes) -> Result<Bytes> {
+        let mut blocks = vec![];
+        let mut lengths = vec![];
+        let mut offset = 0;
+        loop {
+            if offset >= packet.len() {
+                return Err(Error::ErrShortPacket);
+            }
+
+            let b = packet[offset];
+            if b & 0x80 == 0 {
+                blocks.push(RedBlock {
+                    payload_type: b & 0x7F,
+                    ..Default::default()
+                });
+                offset += RED_PRIMARY_HEADER_SIZE;
+                break;
+            }
+
+            if offset + RED_REDUNDANT_HEADER_SIZE > packet.len() {
+                return Err(Error::ErrShortPacket);
+            }
+            blocks.push(RedBlock {
+                payload_type: b & 0x7F,
+                timestamp_offset: ((packet[offset + 1] as u16) << 6)
+                    | ((packet[offset + 2] as u16) >> 2),
+                ..Default::default()
+            });
+            lengths.push(((packet[offset + 2] as usize & 0x03) << 8) | packet[offset + 3] as usize);
+            offset += RED_REDUNDANT_HEADER_SIZE;
+        }
+
+        for (block, length) in blocks.iter_mut().zip(lengths) {
+            if offset + length > packet.len() {
+                return Err(Error::ErrShortPacket);
+            }
+            block.payload = packet.slice(offset..offset + length);
+            offset += length;
+        }
+
+        let primary = packet.slice(offset..);
+        if let Some(block) = blocks.last_mut() {
+            block.payload = primary.clone();
+        }
+        self.blocks = blocks;
+
+        Ok(primary)
+    }
+
+    fn is_partition_head(&self, _payload: &Bytes) -> bool {
+        true
+    }
+
+    fn is_partition_tail(&self, _marker: bool, _payload: &Bytes) -> bool {
+        true
+    }
+}
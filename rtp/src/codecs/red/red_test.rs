@@ -0,0 +1,93 @@
+use super::*;
+
+#[test]
+fn test_red_encode() {
+    let mut encoder = RedEncoder::new(111, 1);
+
+    let out = encoder.encode(960, &Bytes::from_static(&[0x01, 0x02, 0x03]));
+    assert_eq!(&out[..], &[0x6f, 0x01, 0x02, 0x03], "no redundancy yet");
+
+    let out = encoder.encode(1920, &Bytes::from_static(&[0x04, 0x05]));
+    assert_eq!(
+        &out[..],
+        &[0xef, 0x0f, 0x00, 0x03, 0x6f, 0x01, 0x02, 0x03, 0x04, 0x05]
+    );
+
+    // Only the last payload is repeated with a distance of 1
+    let out = encoder.encode(2880, &Bytes::from_static(&[0x06]));
+    assert_eq!(&out[..], &[0xef, 0x0f, 0x00, 0x02, 0x6f, 0x04, 0x05, 0x06]);
+
+    // Payloads older than the 14 bit timestamp offset are dropped
+    let out = encoder.encode(2880 + 0x4000, &Bytes::from_static(&[0x07]));
+    assert_eq!(&out[..], &[0x6f, 0x07]);
+
+    let mut encoder = RedEncoder::new(111, 0);
+    encoder.encode(960, &Bytes::from_static(&[0x01]));
+    let out = encoder.encode(1920, &Bytes::from_static(&[0x02]));
+    assert_eq!(&out[..], &[0x6f, 0x02], "distance 0 never adds redundancy");
+}
+
+#[test]
+fn test_red_depacketize() -> Result<()> {
+    let mut encoder = RedEncoder::new(111, 2);
+    encoder.encode(960, &Bytes::from_static(&[0x01, 0x02, 0x03]));
+    encoder.encode(1920, &Bytes::from_static(&[0x04, 0x05]));
+    let payload = encoder.encode(2880, &Bytes::from_static(&[0x06]));
+
+    let mut pck = RedPacket::default();
+    let primary = pck.depacketize(&payload)?;
+    assert_eq!(&primary[..], &[0x06]);
+    assert_eq!(
+        pck.blocks(),
+        &[
+            RedBlock {
+                payload_type: 111,
+                timestamp_offset: 1920,
+                payload: Bytes::from_static(&[0x01, 0x02, 0x03]),
+            },
+            RedBlock {
+                payload_type: 111,
+                timestamp_offset: 960,
+                payload: Bytes::from_static(&[0x04, 0x05]),
+            },
+            RedBlock {
+                payload_type: 111,
+                timestamp_offset: 0,
+                payload: Bytes::from_static(&[0x06]),
+            },
+        ]
+    );
+    assert_eq!(pck.redundant_blocks().len(), 2);
+
+    let tests = vec![
+        Bytes::from_static(&[]),
+        // Redundant header without a primary header
+        Bytes::from_static(&[0xef, 0x0f, 0x00]),
+        Bytes::from_static(&[0xef, 0x0f, 0x00, 0x03]),
+        // Redundant block longer than the packet
+        Bytes::from_static(&[0xef, 0x0f, 0x00, 0x05, 0x6f, 0x01]),
+    ];
+    for payload in tests {
+        let result = pck.depacketize(&payload);
+        assert_eq!(result, Err(Error::ErrShortPacket), "{:?}", payload);
+    }
+    assert_eq!(
+        pck.blocks().len(),
+        3,
+        "failed parsing keeps previous blocks"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_red_fmtp() {
+    assert_eq!(fmtp_line(111, 1), "111/111");
+    assert_eq!(fmtp_line(111, 0), "111");
+
+    assert_eq!(parse_fmtp("111/111"), Some((111, 1)));
+    assert_eq!(parse_fmtp(" 109/109/109 "), Some((109, 2)));
+    assert_eq!(parse_fmtp("111"), Some((111, 0)));
+    assert_eq!(parse_fmtp("minptime=10"), None);
+    assert_eq!(parse_fmtp(""), None);
+}
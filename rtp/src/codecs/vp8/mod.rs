@@ -144,6 +144,17 @@ pub struct Vp8Packet {
     pub key_idx: u8,
 }
 
+impl Vp8Packet {
+    /// is_keyframe checks whether payload, as returned by depacketize, starts a key frame.
+    /// This can only be told from the first packet of the first partition of a frame, as
+    /// the key frame flag is carried by the VP8 payload header.
+    /// See <https://tools.ietf.org/html/rfc7741#section-4.3>.
+    pub fn is_keyframe(&self, payload: &[u8]) -> bool {
+        // The P bit is the inverse key frame flag
+        self.s == 1 && self.pid == 0 && !payload.is_empty() && payload[0] & 0x01 == 0
+    }
+}
+
 impl Depacketizer for Vp8Packet {
     /// depacketize parses the passed byte slice and stores the result in the VP8Packet this method is called upon
     fn depacketize(&mut self, packet: &Bytes) -> Result<Bytes> {
@@ -166,6 +177,10 @@ impl Depacketizer for Vp8Packet {
         //T/K:|tid|Y| KEYIDX  | (OPTIONAL)
         //    +-+-+-+-+-+-+-+-+
 
+        // Every field describes the current packet only, so none may be kept from the
+        // previous one when the extended control bits are absent.
+        *self = Vp8Packet::default();
+
         let reader = &mut packet.clone();
         let mut payload_index = 0;
 
@@ -101,6 +101,77 @@ fn test_vp8_unmarshal() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_vp8_unmarshal_resets_extended_fields() -> Result<()> {
+    let mut pck = Vp8Packet::default();
+
+    let raw_bytes = Bytes::from_static(&[0xff, 0xff, 0x81, 0x02, 0x03, 0xe4, 0x00, 0x00]);
+    pck.depacketize(&raw_bytes)?;
+    assert_eq!(pck.picture_id, 0x102);
+    assert_eq!(pck.tl0_pic_idx, 0x03);
+    assert_eq!(pck.tid, 3);
+    assert_eq!(pck.y, 1);
+    assert_eq!(pck.key_idx, 4);
+
+    // Without X, none of the extended fields may be kept from the previous packet
+    let raw_bytes = Bytes::from_static(&[0x10, 0x11, 0x22, 0x33]);
+    let payload = pck.depacketize(&raw_bytes)?;
+    assert_eq!(payload, raw_bytes.slice(1..));
+    assert_eq!(pck.x, 0);
+    assert_eq!(pck.i, 0);
+    assert_eq!(pck.l, 0);
+    assert_eq!(pck.picture_id, 0);
+    assert_eq!(pck.tl0_pic_idx, 0);
+    assert_eq!(pck.tid, 0);
+    assert_eq!(pck.key_idx, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_vp8_is_keyframe() -> Result<()> {
+    let tests = vec![
+        // S=1, PID=0, P=0
+        (
+            "KeyFrame",
+            Bytes::from_static(&[0x10, 0x00, 0x9d, 0x01]),
+            true,
+        ),
+        // S=1, PID=0, P=1
+        (
+            "InterFrame",
+            Bytes::from_static(&[0x10, 0x01, 0x9d, 0x01]),
+            false,
+        ),
+        // Not the start of a partition
+        (
+            "Continuation",
+            Bytes::from_static(&[0x00, 0x00, 0x9d, 0x01]),
+            false,
+        ),
+        // Start of the second partition
+        (
+            "SecondPartition",
+            Bytes::from_static(&[0x11, 0x00, 0x9d, 0x01]),
+            false,
+        ),
+        // X, I and L, then a key frame
+        (
+            "ExtendedKeyFrame",
+            Bytes::from_static(&[0x90, 0xc0, 0x12, 0x05, 0x00, 0x9d]),
+            true,
+        ),
+    ];
+
+    for (name, raw_bytes, expected) in tests {
+        let mut pck = Vp8Packet::default();
+        let payload = pck.depacketize(&raw_bytes)?;
+        assert_eq!(pck.is_keyframe(&payload), expected, "{name}");
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_vp8_payload() -> Result<()> {
     let tests = vec![
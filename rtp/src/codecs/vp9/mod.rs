@@ -7,6 +7,7 @@ use std::sync::Arc;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 
 use crate::error::{Error, Result};
+use crate::packet::Packet;
 use crate::packetizer::{Depacketizer, Payloader};
 
 /// Flexible mode 15 bit picture ID
@@ -206,6 +207,10 @@ impl Depacketizer for Vp9Packet {
             return Err(Error::ErrShortPacket);
         }
 
+        // Every field describes the current packet only, nothing may be kept or appended to
+        // from the previous one.
+        *self = Vp9Packet::default();
+
         let reader = &mut packet.clone();
         let b = reader.get_u8();
 
@@ -254,6 +259,32 @@ impl Depacketizer for Vp9Packet {
 }
 
 impl Vp9Packet {
+    /// is_keyframe checks whether the packet starts a key frame: the first packet of a
+    /// picture of the lowest spatial layer that is not inter-picture predicted.
+    pub fn is_keyframe(&self) -> bool {
+        self.b && !self.p && self.sid == 0
+    }
+
+    /// spatial_layers returns the number of spatial layers announced by the scalability
+    /// structure, if the packet carries one.
+    pub fn spatial_layers(&self) -> Option<usize> {
+        if self.v {
+            Some(self.ns as usize + 1)
+        } else {
+            None
+        }
+    }
+
+    /// resolution returns the frame resolution of spatial layer sid as (width, height), if
+    /// the packet carries a scalability structure with resolutions.
+    pub fn resolution(&self, sid: u8) -> Option<(u16, u16)> {
+        let sid = sid as usize;
+        match (self.width.get(sid), self.height.get(sid)) {
+            (Some(width), Some(height)) if self.y => Some((*width, *height)),
+            _ => None,
+        }
+    }
+
     // Picture ID:
     //
     //      +-+-+-+-+-+-+-+-+
@@ -363,15 +394,17 @@ impl Vp9Packet {
         reader: &mut dyn Buf,
         mut payload_index: usize,
     ) -> Result<usize> {
-        let mut b = 1u8;
-        while (b & 0x1) != 0 {
+        loop {
             if reader.remaining() == 0 {
                 return Err(Error::ErrShortPacket);
             }
-            b = reader.get_u8();
+            let b = reader.get_u8();
             payload_index += 1;
 
             self.pdiff.push(b >> 1);
+            if (b & 0x1) == 0 {
+                break;
+            }
             if self.pdiff.len() >= MAX_VP9REF_PICS {
                 return Err(Error::ErrTooManyPDiff);
             }
@@ -410,7 +443,7 @@ impl Vp9Packet {
 
         self.ns = b >> 5;
         self.y = b & 0x10 != 0;
-        self.g = (b >> 1) & 0x7 != 0;
+        self.g = b & 0x08 != 0;
 
         let ns = (self.ns + 1) as usize;
         self.ng = 0;
@@ -465,3 +498,51 @@ impl Vp9Packet {
         Ok(payload_index)
     }
 }
+
+/// Vp9LayerFilter drops the packets of a VP9 SVC stream that belong to layers above a target
+/// spatial and temporal layer, so a forwarder can send a lower layer without re-encoding.
+/// Sequence numbers of the forwarded packets have to be rewritten by the caller to close the
+/// gaps left by the dropped packets.
+#[derive(Debug, Default, Clone)]
+pub struct Vp9LayerFilter {
+    /// Highest spatial layer forwarded
+    pub spatial_layer: u8,
+    /// Highest temporal layer forwarded
+    pub temporal_layer: u8,
+
+    vp9: Vp9Packet,
+}
+
+impl Vp9LayerFilter {
+    pub fn new(spatial_layer: u8, temporal_layer: u8) -> Self {
+        Vp9LayerFilter {
+            spatial_layer,
+            temporal_layer,
+            ..Default::default()
+        }
+    }
+
+    /// filter returns whether packet belongs to a layer that should be forwarded. Packets
+    /// without layer indices and padding only packets are always forwarded. As the marker bit
+    /// ends a picture, it is set on the last packet of the highest forwarded spatial layer.
+    pub fn filter(&mut self, packet: &mut Packet) -> Result<bool> {
+        if packet.is_padding_only() {
+            return Ok(true);
+        }
+
+        self.vp9.depacketize(&packet.payload)?;
+        if !self.vp9.l {
+            return Ok(true);
+        }
+
+        if self.vp9.sid > self.spatial_layer || self.vp9.tid > self.temporal_layer {
+            return Ok(false);
+        }
+
+        if self.vp9.e && self.vp9.sid == self.spatial_layer {
+            packet.header.marker = true;
+        }
+
+        Ok(true)
+    }
+}
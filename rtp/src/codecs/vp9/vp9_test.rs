@@ -113,6 +113,20 @@ fn test_vp9_packet_unmarshal() -> Result<()> {
             Bytes::from_static(&[0xAA]),
             None,
         ),
+        (
+            "FlexiblePictureIDRefIndex_ThreePDiff",
+            Bytes::from_static(&[0xD0, 0x02, 0x03, 0x05, 0x06, 0xAA]),
+            Vp9Packet {
+                i: true,
+                p: true,
+                f: true,
+                picture_id: 0x02,
+                pdiff: vec![0x01, 0x02, 0x03],
+                ..Default::default()
+            },
+            Bytes::from_static(&[0xAA]),
+            None,
+        ),
         (
             "FlexiblePictureIDRefIndex_TooManyPDiff",
             Bytes::from_static(&[0xD0, 0x02, 0x03, 0x05, 0x07, 0x09, 0x10, 0xAA]),
@@ -208,6 +222,22 @@ fn test_vp9_packet_unmarshal() -> Result<()> {
             Bytes::new(),
             None,
         ),
+        (
+            "ScalabilityStructureReservedBits",
+            Bytes::from_static(&[
+                0x0A,
+                (1 << 5) | 0x07, // NS:1 Y:0 G:0, reserved bits set
+                0xAA,
+            ]),
+            Vp9Packet {
+                b: true,
+                v: true,
+                ns: 1,
+                ..Default::default()
+            },
+            Bytes::from_static(&[0xAA]),
+            None,
+        ),
     ];
 
     for (name, b, pkt, expected, err) in tests {
@@ -232,6 +262,53 @@ fn test_vp9_packet_unmarshal() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_vp9_packet_layers() -> Result<()> {
+    let mut p = Vp9Packet::default();
+
+    // Key frame of the lowest spatial layer carrying the scalability structure
+    let raw = Bytes::from_static(&[
+        0x2A, // L:1 B:1 V:1
+        (1 << 5),
+        0x00,                // tl0picidx
+        (1 << 5) | (1 << 4), // NS:1 Y:1 G:0
+        (640 >> 8) as u8,
+        (640 & 0xff) as u8,
+        (360 >> 8) as u8,
+        (360 & 0xff) as u8,
+        (1280 >> 8) as u8,
+        (1280 & 0xff) as u8,
+        (720 >> 8) as u8,
+        (720 & 0xff) as u8,
+        0xAA,
+    ]);
+    p.depacketize(&raw)?;
+    assert!(p.is_keyframe());
+    assert_eq!(p.tid, 1);
+    assert_eq!(p.sid, 0);
+    assert_eq!(p.spatial_layers(), Some(2));
+    assert_eq!(p.resolution(0), Some((640, 360)));
+    assert_eq!(p.resolution(1), Some((1280, 720)));
+    assert_eq!(p.resolution(2), None);
+
+    // Inter-picture predicted frame of the second spatial layer, reusing the packet
+    let raw = Bytes::from_static(&[
+        0x78, // P:1 L:1 F:1 B:1
+        (2 << 5) | (1 << 1),
+        0x02,
+        0xAA,
+    ]);
+    p.depacketize(&raw)?;
+    assert!(!p.is_keyframe());
+    assert_eq!(p.tid, 2);
+    assert_eq!(p.sid, 1);
+    assert_eq!(p.pdiff, vec![0x01]);
+    assert_eq!(p.spatial_layers(), None);
+    assert_eq!(p.resolution(0), None);
+
+    Ok(())
+}
+
 #[test]
 fn test_vp9_payloader_payload() -> Result<()> {
     let mut r0 = 8692;
@@ -362,3 +439,54 @@ fn test_vp9_partition_head_checker_is_partition_head() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_vp9_layer_filter() -> Result<()> {
+    // Non-flexible mode packet with layer indices, starting and ending a frame
+    let packet = |sid: u8, tid: u8| Packet {
+        payload: Bytes::from(vec![0x2C, (tid << 5) | (sid << 1), 0x00, 0xAA]),
+        ..Default::default()
+    };
+
+    let mut filter = Vp9LayerFilter::new(1, 0);
+
+    let mut p = packet(0, 0);
+    assert!(filter.filter(&mut p)?);
+    assert!(
+        !p.header.marker,
+        "lower spatial layer should not end the picture"
+    );
+
+    let mut p = packet(1, 0);
+    assert!(filter.filter(&mut p)?);
+    assert!(
+        p.header.marker,
+        "highest forwarded spatial layer should end the picture"
+    );
+
+    assert!(!filter.filter(&mut packet(2, 0))?);
+    assert!(!filter.filter(&mut packet(0, 1))?);
+
+    filter.temporal_layer = 1;
+    assert!(filter.filter(&mut packet(0, 1))?);
+
+    // Packets without layer indices can't be filtered
+    let mut p = Packet {
+        payload: Bytes::from_static(&[0x0C, 0xAA]),
+        ..Default::default()
+    };
+    assert!(filter.filter(&mut p)?);
+
+    let mut p = Packet::default();
+    p.header.padding = true;
+    assert!(filter.filter(&mut p)?);
+
+    assert!(filter
+        .filter(&mut Packet {
+            payload: Bytes::from_static(&[0x2C]),
+            ..Default::default()
+        })
+        .is_err());
+
+    Ok(())
+}
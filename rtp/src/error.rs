@@ -31,6 +31,8 @@ pub enum Error {
 
     #[error("packet is not large enough")]
     ErrShortPacket,
+    #[error("MTU {0} is too small to fit the RTP header")]
+    ErrMtuTooSmall(usize),
     #[error("invalid nil packet")]
     ErrNilPacket,
     #[error("too many PDiff")]
@@ -54,6 +56,22 @@ pub enum Error {
     HeaderExtensionPayloadNot32BitWords,
     #[error("audio level overflow")]
     AudioLevelOverflow,
+    #[error("transmission offset overflow")]
+    TransmissionOffsetOverflow,
+    #[error("playout delay overflow")]
+    PlayoutDelayOverflow,
+    #[error("dependency descriptor template id overflow")]
+    DependencyDescriptorTemplateIdOverflow,
+    #[error("SDES item must be 1 to 255 bytes of UTF-8")]
+    ErrInvalidSdesItem,
+    #[error("header extension {0} has not been negotiated")]
+    ErrHeaderExtensionNotNegotiated(String),
+    #[error("no RTX mapping for payload type {0}")]
+    ErrRtxUnknownPayloadType(u8),
+    #[error("no RTX mapping for SSRC {0}")]
+    ErrRtxUnknownSsrc(u32),
+    #[error("packetizer doesn't know its clock rate")]
+    ErrPacketizeDurationUnsupported,
     #[error("payload is not large enough")]
     PayloadIsNotLargeEnough,
     #[error("STAP-A declared size({0}) is larger than buffer({1})")]
@@ -0,0 +1,69 @@
+use bytes::{Bytes, BytesMut};
+
+use super::*;
+use crate::error::Result;
+
+#[test]
+fn test_dependency_descriptor_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 2][..];
+    let result = DependencyDescriptorExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_extension() -> Result<()> {
+    let raw = Bytes::from_static(&[0x81, 0x01, 0x02]);
+    let buf = &mut raw.clone();
+    let d1 = DependencyDescriptorExtension::unmarshal(buf)?;
+    let d2 = DependencyDescriptorExtension {
+        start_of_frame: true,
+        end_of_frame: false,
+        frame_dependency_template_id: 1,
+        frame_number: 0x0102,
+        extended_descriptor: vec![],
+    };
+    assert_eq!(d1, d2);
+
+    let mut dst = BytesMut::with_capacity(d2.marshal_size());
+    dst.resize(d2.marshal_size(), 0);
+    d2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_extension_extended() -> Result<()> {
+    let raw = Bytes::from_static(&[0x7F, 0xFF, 0xFF, 0x80, 0x01, 0x02]);
+    let buf = &mut raw.clone();
+    let d = DependencyDescriptorExtension::unmarshal(buf)?;
+    assert!(!d.start_of_frame);
+    assert!(d.end_of_frame);
+    assert_eq!(
+        d.frame_dependency_template_id,
+        DEPENDENCY_DESCRIPTOR_MAX_TEMPLATE_ID
+    );
+    assert_eq!(d.frame_number, 0xFFFF);
+    assert_eq!(d.extended_descriptor, vec![0x80, 0x01, 0x02]);
+
+    let mut dst = BytesMut::with_capacity(d.marshal_size());
+    dst.resize(d.marshal_size(), 0);
+    d.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_dependency_descriptor_extension_template_id_overflow() {
+    let d = DependencyDescriptorExtension {
+        frame_dependency_template_id: DEPENDENCY_DESCRIPTOR_MAX_TEMPLATE_ID + 1,
+        ..Default::default()
+    };
+
+    let mut dst = BytesMut::with_capacity(d.marshal_size());
+    dst.resize(d.marshal_size(), 0);
+    assert!(d.marshal_to(&mut dst).is_err());
+}
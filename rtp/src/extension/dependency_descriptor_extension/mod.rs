@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod dependency_descriptor_extension_test;
+
+use bytes::{Buf, BufMut};
+use serde::{Deserialize, Serialize};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+
+/// The size of the mandatory fields of the dependency descriptor.
+pub const DEPENDENCY_DESCRIPTOR_MANDATORY_SIZE: usize = 3;
+
+/// The largest template id that fits in its 6 bits.
+pub const DEPENDENCY_DESCRIPTOR_MAX_TEMPLATE_ID: u8 = 0x3F;
+
+/// DependencyDescriptorExtension is the dependency descriptor header extension
+/// described in <https://aomediacodec.github.io/av1-rtp-spec/#dependency-descriptor-rtp-header-extension>
+///
+/// Only the mandatory fields are parsed. The extended fields describe the
+/// template structure and custom dependencies of the frame, and are kept as raw
+/// bytes as decoding them requires the template structure of the stream.
+///
+/// 0                   1                   2
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |S|E| template id |         frame number          |  extended...
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DependencyDescriptorExtension {
+    pub start_of_frame: bool,
+    pub end_of_frame: bool,
+    pub frame_dependency_template_id: u8,
+    pub frame_number: u16,
+    pub extended_descriptor: Vec<u8>,
+}
+
+impl Unmarshal for DependencyDescriptorExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < DEPENDENCY_DESCRIPTOR_MANDATORY_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let b0 = raw_packet.get_u8();
+        let frame_number = raw_packet.get_u16();
+        let extended_descriptor = raw_packet.copy_to_bytes(raw_packet.remaining());
+
+        Ok(DependencyDescriptorExtension {
+            start_of_frame: b0 & 0x80 != 0,
+            end_of_frame: b0 & 0x40 != 0,
+            frame_dependency_template_id: b0 & DEPENDENCY_DESCRIPTOR_MAX_TEMPLATE_ID,
+            frame_number,
+            extended_descriptor: extended_descriptor.to_vec(),
+        })
+    }
+}
+
+impl MarshalSize for DependencyDescriptorExtension {
+    /// MarshalSize returns the size of the DependencyDescriptorExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        DEPENDENCY_DESCRIPTOR_MANDATORY_SIZE + self.extended_descriptor.len()
+    }
+}
+
+impl Marshal for DependencyDescriptorExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        if buf.remaining_mut() < self.marshal_size() {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        if self.frame_dependency_template_id > DEPENDENCY_DESCRIPTOR_MAX_TEMPLATE_ID {
+            return Err(Error::DependencyDescriptorTemplateIdOverflow.into());
+        }
+
+        let mut b0 = self.frame_dependency_template_id;
+        if self.start_of_frame {
+            b0 |= 0x80;
+        }
+        if self.end_of_frame {
+            b0 |= 0x40;
+        }
+        buf.put_u8(b0);
+        buf.put_u16(self.frame_number);
+        buf.put_slice(&self.extended_descriptor);
+
+        Ok(self.marshal_size())
+    }
+}
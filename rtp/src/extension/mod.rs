@@ -5,15 +5,37 @@ use util::{Marshal, MarshalSize};
 
 pub mod abs_send_time_extension;
 pub mod audio_level_extension;
+pub mod dependency_descriptor_extension;
+pub mod playout_delay_extension;
+pub mod registry;
+pub mod sdes_extension;
+pub mod transmission_offset_extension;
 pub mod transport_cc_extension;
 pub mod video_orientation_extension;
 
+pub const ABS_SEND_TIME_URI: &str = "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time";
+pub const TRANSMISSION_OFFSET_URI: &str = "urn:ietf:params:rtp-hdrext:toffset";
+pub const AUDIO_LEVEL_URI: &str = "urn:ietf:params:rtp-hdrext:ssrc-audio-level";
+pub const TRANSPORT_CC_URI: &str =
+    "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01";
+pub const VIDEO_ORIENTATION_URI: &str = "urn:3gpp:video-orientation";
+pub const SDES_MID_URI: &str = "urn:ietf:params:rtp-hdrext:sdes:mid";
+pub const SDES_RTP_STREAM_ID_URI: &str = "urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id";
+pub const PLAYOUT_DELAY_URI: &str = "http://www.webrtc.org/experiments/rtp-hdrext/playout-delay";
+pub const DEPENDENCY_DESCRIPTOR_URI: &str =
+    "https://aomediacodec.github.io/av1-rtp-spec/#dependency-descriptor-rtp-header-extension";
+
 /// A generic RTP header extension.
 pub enum HeaderExtension {
     AbsSendTime(abs_send_time_extension::AbsSendTimeExtension),
+    TransmissionOffset(transmission_offset_extension::TransmissionOffsetExtension),
     AudioLevel(audio_level_extension::AudioLevelExtension),
     TransportCc(transport_cc_extension::TransportCcExtension),
     VideoOrientation(video_orientation_extension::VideoOrientationExtension),
+    Mid(sdes_extension::MidExtension),
+    RtpStreamId(sdes_extension::RtpStreamIdExtension),
+    PlayoutDelay(playout_delay_extension::PlayoutDelayExtension),
+    DependencyDescriptor(dependency_descriptor_extension::DependencyDescriptorExtension),
 
     /// A custom extension
     Custom {
@@ -27,12 +49,15 @@ impl HeaderExtension {
         use HeaderExtension::*;
 
         match self {
-            AbsSendTime(_) => "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time".into(),
-            AudioLevel(_) => "urn:ietf:params:rtp-hdrext:ssrc-audio-level".into(),
-            TransportCc(_) => {
-                "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01".into()
-            }
-            VideoOrientation(_) => "urn:3gpp:video-orientation".into(),
+            AbsSendTime(_) => ABS_SEND_TIME_URI.into(),
+            TransmissionOffset(_) => TRANSMISSION_OFFSET_URI.into(),
+            AudioLevel(_) => AUDIO_LEVEL_URI.into(),
+            TransportCc(_) => TRANSPORT_CC_URI.into(),
+            VideoOrientation(_) => VIDEO_ORIENTATION_URI.into(),
+            Mid(_) => SDES_MID_URI.into(),
+            RtpStreamId(_) => SDES_RTP_STREAM_ID_URI.into(),
+            PlayoutDelay(_) => PLAYOUT_DELAY_URI.into(),
+            DependencyDescriptor(_) => DEPENDENCY_DESCRIPTOR_URI.into(),
             Custom { uri, .. } => uri.clone(),
         }
     }
@@ -41,9 +66,14 @@ impl HeaderExtension {
         use HeaderExtension::*;
         match (self, other) {
             (AbsSendTime(_), AbsSendTime(_)) => true,
+            (TransmissionOffset(_), TransmissionOffset(_)) => true,
             (AudioLevel(_), AudioLevel(_)) => true,
             (TransportCc(_), TransportCc(_)) => true,
             (VideoOrientation(_), VideoOrientation(_)) => true,
+            (Mid(_), Mid(_)) => true,
+            (RtpStreamId(_), RtpStreamId(_)) => true,
+            (PlayoutDelay(_), PlayoutDelay(_)) => true,
+            (DependencyDescriptor(_), DependencyDescriptor(_)) => true,
             (Custom { uri, .. }, Custom { uri: other_uri, .. }) => uri == other_uri,
             _ => false,
         }
@@ -55,9 +85,14 @@ impl MarshalSize for HeaderExtension {
         use HeaderExtension::*;
         match self {
             AbsSendTime(ext) => ext.marshal_size(),
+            TransmissionOffset(ext) => ext.marshal_size(),
             AudioLevel(ext) => ext.marshal_size(),
             TransportCc(ext) => ext.marshal_size(),
             VideoOrientation(ext) => ext.marshal_size(),
+            Mid(ext) => ext.marshal_size(),
+            RtpStreamId(ext) => ext.marshal_size(),
+            PlayoutDelay(ext) => ext.marshal_size(),
+            DependencyDescriptor(ext) => ext.marshal_size(),
             Custom { extension: ext, .. } => ext.marshal_size(),
         }
     }
@@ -68,9 +103,14 @@ impl Marshal for HeaderExtension {
         use HeaderExtension::*;
         match self {
             AbsSendTime(ext) => ext.marshal_to(buf),
+            TransmissionOffset(ext) => ext.marshal_to(buf),
             AudioLevel(ext) => ext.marshal_to(buf),
             TransportCc(ext) => ext.marshal_to(buf),
             VideoOrientation(ext) => ext.marshal_to(buf),
+            Mid(ext) => ext.marshal_to(buf),
+            RtpStreamId(ext) => ext.marshal_to(buf),
+            PlayoutDelay(ext) => ext.marshal_to(buf),
+            DependencyDescriptor(ext) => ext.marshal_to(buf),
             Custom { extension: ext, .. } => ext.marshal_to(buf),
         }
     }
@@ -82,9 +122,14 @@ impl fmt::Debug for HeaderExtension {
 
         match self {
             AbsSendTime(ext) => f.debug_tuple("AbsSendTime").field(ext).finish(),
+            TransmissionOffset(ext) => f.debug_tuple("TransmissionOffset").field(ext).finish(),
             AudioLevel(ext) => f.debug_tuple("AudioLevel").field(ext).finish(),
             TransportCc(ext) => f.debug_tuple("TransportCc").field(ext).finish(),
             VideoOrientation(ext) => f.debug_tuple("VideoOrientation").field(ext).finish(),
+            Mid(ext) => f.debug_tuple("Mid").field(ext).finish(),
+            RtpStreamId(ext) => f.debug_tuple("RtpStreamId").field(ext).finish(),
+            PlayoutDelay(ext) => f.debug_tuple("PlayoutDelay").field(ext).finish(),
+            DependencyDescriptor(ext) => f.debug_tuple("DependencyDescriptor").field(ext).finish(),
             Custom { uri, extension: _ } => f.debug_struct("Custom").field("uri", uri).finish(),
         }
     }
@@ -0,0 +1,58 @@
+use bytes::{Bytes, BytesMut};
+
+use super::*;
+use crate::error::Result;
+
+#[test]
+fn test_playout_delay_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 2][..];
+    let result = PlayoutDelayExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_playout_delay_extension() -> Result<()> {
+    let raw = Bytes::from_static(&[0x01, 0x40, 0x64]);
+    let buf = &mut raw.clone();
+    let p1 = PlayoutDelayExtension::unmarshal(buf)?;
+    let p2 = PlayoutDelayExtension {
+        min_delay: 20,
+        max_delay: 100,
+    };
+    assert_eq!(p1, p2);
+
+    let mut dst = BytesMut::with_capacity(p2.marshal_size());
+    dst.resize(p2.marshal_size(), 0);
+    p2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_playout_delay_extension_max() -> Result<()> {
+    let raw = Bytes::from_static(&[0xFF, 0xFF, 0xFF]);
+    let buf = &mut raw.clone();
+    let p = PlayoutDelayExtension::unmarshal(buf)?;
+    assert_eq!(p.min_delay, PLAYOUT_DELAY_MAX);
+    assert_eq!(p.max_delay, PLAYOUT_DELAY_MAX);
+
+    Ok(())
+}
+
+#[test]
+fn test_playout_delay_extension_overflow() -> Result<()> {
+    let p = PlayoutDelayExtension {
+        min_delay: 0,
+        max_delay: PLAYOUT_DELAY_MAX + 1,
+    };
+
+    let mut dst = BytesMut::with_capacity(p.marshal_size());
+    dst.resize(p.marshal_size(), 0);
+    let result = p.marshal_to(&mut dst);
+    assert!(result.is_err());
+
+    Ok(())
+}
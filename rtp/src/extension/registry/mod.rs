@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod registry_test;
+
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use util::marshal::{Marshal, Unmarshal};
+
+use super::abs_send_time_extension::AbsSendTimeExtension;
+use super::audio_level_extension::AudioLevelExtension;
+use super::dependency_descriptor_extension::DependencyDescriptorExtension;
+use super::playout_delay_extension::PlayoutDelayExtension;
+use super::sdes_extension::{MidExtension, RtpStreamIdExtension};
+use super::transmission_offset_extension::TransmissionOffsetExtension;
+use super::transport_cc_extension::TransportCcExtension;
+use super::video_orientation_extension::VideoOrientationExtension;
+use super::*;
+use crate::error::{Error, Result};
+use crate::header::Header;
+
+/// HeaderExtensionRegistry maps the ids negotiated for RTP header extensions to their
+/// URIs, so that typed extensions can be written to and read from packet headers.
+#[derive(Debug, Default, Clone)]
+pub struct HeaderExtensionRegistry {
+    uris: HashMap<u8, String>,
+}
+
+impl HeaderExtensionRegistry {
+    pub fn new() -> Self {
+        HeaderExtensionRegistry::default()
+    }
+
+    /// register associates id with uri, replacing any previous registration of either.
+    pub fn register(&mut self, id: u8, uri: &str) {
+        self.uris.retain(|_, u| u != uri);
+        self.uris.insert(id, uri.to_owned());
+    }
+
+    /// id returns the id negotiated for uri.
+    pub fn id(&self, uri: &str) -> Option<u8> {
+        self.uris
+            .iter()
+            .find(|(_, u)| u.as_str() == uri)
+            .map(|(id, _)| *id)
+    }
+
+    /// uri returns the URI negotiated for id.
+    pub fn uri(&self, id: u8) -> Option<&str> {
+        self.uris.get(&id).map(|uri| uri.as_str())
+    }
+
+    /// set_extension marshals extension into header under its negotiated id.
+    pub fn set_extension(&self, header: &mut Header, extension: &HeaderExtension) -> Result<()> {
+        let uri = extension.uri();
+        let id = self
+            .id(&uri)
+            .ok_or_else(|| Error::ErrHeaderExtensionNotNegotiated(uri.to_string()))?;
+
+        header.set_extension(id, extension.marshal()?)
+    }
+
+    /// get_extension unmarshals the extension negotiated for uri from header. None is
+    /// returned if the extension is absent or of a URI this crate does not implement.
+    pub fn get_extension(&self, header: &Header, uri: &str) -> Result<Option<HeaderExtension>> {
+        match self.id(uri).and_then(|id| header.get_extension(id)) {
+            Some(payload) => unmarshal_extension(uri, payload),
+            None => Ok(None),
+        }
+    }
+
+    /// extensions unmarshals every extension of header that has been negotiated and is
+    /// implemented by this crate, in the order they appear in the header.
+    pub fn extensions(&self, header: &Header) -> Result<Vec<HeaderExtension>> {
+        let mut extensions = vec![];
+        for id in header.get_extension_ids() {
+            let (uri, payload) = match (self.uri(id), header.get_extension(id)) {
+                (Some(uri), Some(payload)) => (uri, payload),
+                _ => continue,
+            };
+            if let Some(extension) = unmarshal_extension(uri, payload)? {
+                extensions.push(extension);
+            }
+        }
+
+        Ok(extensions)
+    }
+}
+
+fn unmarshal_extension(uri: &str, mut payload: Bytes) -> Result<Option<HeaderExtension>> {
+    let buf = &mut payload;
+    let extension = match uri {
+        ABS_SEND_TIME_URI => HeaderExtension::AbsSendTime(AbsSendTimeExtension::unmarshal(buf)?),
+        TRANSMISSION_OFFSET_URI => {
+            HeaderExtension::TransmissionOffset(TransmissionOffsetExtension::unmarshal(buf)?)
+        }
+        AUDIO_LEVEL_URI => HeaderExtension::AudioLevel(AudioLevelExtension::unmarshal(buf)?),
+        TRANSPORT_CC_URI => HeaderExtension::TransportCc(TransportCcExtension::unmarshal(buf)?),
+        VIDEO_ORIENTATION_URI => {
+            HeaderExtension::VideoOrientation(VideoOrientationExtension::unmarshal(buf)?)
+        }
+        SDES_MID_URI => HeaderExtension::Mid(MidExtension::unmarshal(buf)?),
+        SDES_RTP_STREAM_ID_URI => {
+            HeaderExtension::RtpStreamId(RtpStreamIdExtension::unmarshal(buf)?)
+        }
+        PLAYOUT_DELAY_URI => HeaderExtension::PlayoutDelay(PlayoutDelayExtension::unmarshal(buf)?),
+        DEPENDENCY_DESCRIPTOR_URI => {
+            HeaderExtension::DependencyDescriptor(DependencyDescriptorExtension::unmarshal(buf)?)
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(extension))
+}
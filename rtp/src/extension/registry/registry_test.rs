@@ -0,0 +1,90 @@
+use super::*;
+
+#[test]
+fn test_registry_register() {
+    let mut registry = HeaderExtensionRegistry::new();
+    registry.register(1, SDES_MID_URI);
+    registry.register(2, AUDIO_LEVEL_URI);
+
+    assert_eq!(registry.id(SDES_MID_URI), Some(1));
+    assert_eq!(registry.uri(2), Some(AUDIO_LEVEL_URI));
+    assert_eq!(registry.id(TRANSPORT_CC_URI), None);
+
+    // Renegotiating an URI under a new id drops the old one
+    registry.register(3, SDES_MID_URI);
+    assert_eq!(registry.id(SDES_MID_URI), Some(3));
+    assert_eq!(registry.uri(1), None);
+}
+
+#[test]
+fn test_registry_round_trip() -> Result<()> {
+    let mut registry = HeaderExtensionRegistry::new();
+    registry.register(1, SDES_MID_URI);
+    registry.register(2, AUDIO_LEVEL_URI);
+    registry.register(3, PLAYOUT_DELAY_URI);
+    registry.register(4, "urn:example:unknown");
+
+    let mut header = Header::default();
+    registry.set_extension(
+        &mut header,
+        &HeaderExtension::Mid(MidExtension {
+            mid: "0".to_owned(),
+        }),
+    )?;
+    registry.set_extension(
+        &mut header,
+        &HeaderExtension::AudioLevel(AudioLevelExtension {
+            level: 10,
+            voice: true,
+        }),
+    )?;
+    header.set_extension(4, Bytes::from_static(&[0xAA]))?;
+    header.set_extension(5, Bytes::from_static(&[0xBB]))?;
+
+    assert_eq!(header.get_extension(1), Some(Bytes::from_static(b"0")));
+    assert_eq!(header.get_extension(2), Some(Bytes::from_static(&[0x8A])));
+
+    let extensions = registry.extensions(&header)?;
+    assert_eq!(extensions.len(), 2);
+    match &extensions[0] {
+        HeaderExtension::Mid(ext) => assert_eq!(ext.mid, "0"),
+        ext => panic!("unexpected extension {ext:?}"),
+    }
+    match &extensions[1] {
+        HeaderExtension::AudioLevel(ext) => {
+            assert_eq!(ext.level, 10);
+            assert!(ext.voice);
+        }
+        ext => panic!("unexpected extension {ext:?}"),
+    }
+
+    assert!(registry
+        .get_extension(&header, PLAYOUT_DELAY_URI)?
+        .is_none());
+    assert!(matches!(
+        registry.get_extension(&header, AUDIO_LEVEL_URI)?,
+        Some(HeaderExtension::AudioLevel(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_registry_not_negotiated() {
+    let registry = HeaderExtensionRegistry::new();
+    let mut header = Header::default();
+
+    let result = registry.set_extension(
+        &mut header,
+        &HeaderExtension::TransportCc(TransportCcExtension {
+            transport_sequence: 1,
+        }),
+    );
+    assert_eq!(
+        result,
+        Err(Error::ErrHeaderExtensionNotNegotiated(
+            TRANSPORT_CC_URI.to_owned()
+        ))
+    );
+    assert!(!header.extension);
+}
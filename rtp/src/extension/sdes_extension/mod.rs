@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod sdes_extension_test;
+
+use bytes::{Buf, BufMut};
+use serde::{Deserialize, Serialize};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+
+/// The largest SDES item that fits in a two-byte header extension.
+pub const SDES_EXTENSION_MAX_SIZE: usize = 255;
+
+/// MidExtension carries the media identification of
+/// urn:ietf:params:rtp-hdrext:sdes:mid as described in
+/// https://tools.ietf.org/html/rfc8843#section-15.2
+///
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   |  len  | identification-tag (1-16 or 1-255 bytes) ...   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MidExtension {
+    pub mid: String,
+}
+
+/// RtpStreamIdExtension carries the RTP stream identifier of
+/// urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id as described in
+/// https://tools.ietf.org/html/rfc8852#section-3.1
+///
+/// It uses the same format as [`MidExtension`].
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RtpStreamIdExtension {
+    pub rid: String,
+}
+
+impl Unmarshal for MidExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        Ok(MidExtension {
+            mid: unmarshal_sdes_item(raw_packet)?,
+        })
+    }
+}
+
+impl MarshalSize for MidExtension {
+    /// MarshalSize returns the size of the MidExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        self.mid.len()
+    }
+}
+
+impl Marshal for MidExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize, util::Error> {
+        marshal_sdes_item(&self.mid, buf)
+    }
+}
+
+impl Unmarshal for RtpStreamIdExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        Ok(RtpStreamIdExtension {
+            rid: unmarshal_sdes_item(raw_packet)?,
+        })
+    }
+}
+
+impl MarshalSize for RtpStreamIdExtension {
+    /// MarshalSize returns the size of the RtpStreamIdExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        self.rid.len()
+    }
+}
+
+impl Marshal for RtpStreamIdExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize, util::Error> {
+        marshal_sdes_item(&self.rid, buf)
+    }
+}
+
+/// unmarshal_sdes_item reads the whole remaining payload as an UTF-8 SDES item.
+fn unmarshal_sdes_item<B: Buf>(raw_packet: &mut B) -> Result<String, util::Error> {
+    let len = raw_packet.remaining();
+    if len == 0 || len > SDES_EXTENSION_MAX_SIZE {
+        return Err(Error::ErrInvalidSdesItem.into());
+    }
+
+    let item = raw_packet.copy_to_bytes(len);
+    String::from_utf8(item.to_vec()).map_err(|_| Error::ErrInvalidSdesItem.into())
+}
+
+fn marshal_sdes_item(item: &str, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+    if item.is_empty() || item.len() > SDES_EXTENSION_MAX_SIZE {
+        return Err(Error::ErrInvalidSdesItem.into());
+    }
+    if buf.remaining_mut() < item.len() {
+        return Err(Error::ErrBufferTooSmall.into());
+    }
+
+    buf.put(item.as_bytes());
+
+    Ok(item.len())
+}
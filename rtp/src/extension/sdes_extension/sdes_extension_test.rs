@@ -0,0 +1,55 @@
+use bytes::{Bytes, BytesMut};
+
+use super::*;
+use crate::error::Result;
+
+#[test]
+fn test_mid_extension() -> Result<()> {
+    let raw = Bytes::from_static(b"audio");
+    let buf = &mut raw.clone();
+    let m1 = MidExtension::unmarshal(buf)?;
+    let m2 = MidExtension {
+        mid: "audio".to_owned(),
+    };
+    assert_eq!(m1, m2);
+
+    let mut dst = BytesMut::with_capacity(m2.marshal_size());
+    dst.resize(m2.marshal_size(), 0);
+    m2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_rtp_stream_id_extension() -> Result<()> {
+    let raw = Bytes::from_static(b"hi");
+    let buf = &mut raw.clone();
+    let r1 = RtpStreamIdExtension::unmarshal(buf)?;
+    let r2 = RtpStreamIdExtension {
+        rid: "hi".to_owned(),
+    };
+    assert_eq!(r1, r2);
+    assert_eq!(r2.marshal()?, raw);
+
+    Ok(())
+}
+
+#[test]
+fn test_sdes_extension_invalid() -> Result<()> {
+    let mut buf = &vec![0u8; 0][..];
+    assert!(MidExtension::unmarshal(&mut buf).is_err());
+
+    let mut buf = &[0xFFu8, 0xFE][..];
+    assert!(RtpStreamIdExtension::unmarshal(&mut buf).is_err());
+
+    let m = MidExtension { mid: String::new() };
+    assert!(m.marshal().is_err());
+
+    let r = RtpStreamIdExtension {
+        rid: "a".repeat(SDES_EXTENSION_MAX_SIZE + 1),
+    };
+    assert!(r.marshal().is_err());
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod transmission_offset_extension_test;
+
+use bytes::{Buf, BufMut};
+use serde::{Deserialize, Serialize};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+
+pub const TRANSMISSION_OFFSET_EXTENSION_SIZE: usize = 3;
+
+const TRANSMISSION_OFFSET_MAX: i32 = (1 << 23) - 1;
+const TRANSMISSION_OFFSET_MIN: i32 = -(1 << 23);
+
+/// TransmissionOffsetExtension is a extension payload format described in
+/// https://tools.ietf.org/html/rfc5450
+///
+/// The offset is the signed difference, in RTP timestamp units, between the time the
+/// packet is sent and its RTP timestamp.
+///
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=2 |              transmission offset              |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+pub struct TransmissionOffsetExtension {
+    pub transmission_offset: i32,
+}
+
+impl Unmarshal for TransmissionOffsetExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < TRANSMISSION_OFFSET_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let b0 = raw_packet.get_u8();
+        let b1 = raw_packet.get_u8();
+        let b2 = raw_packet.get_u8();
+        let offset = (b0 as u32) << 16 | (b1 as u32) << 8 | b2 as u32;
+
+        // sign extend the 24 bit value
+        Ok(TransmissionOffsetExtension {
+            transmission_offset: ((offset << 8) as i32) >> 8,
+        })
+    }
+}
+
+impl MarshalSize for TransmissionOffsetExtension {
+    /// MarshalSize returns the size of the TransmissionOffsetExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        TRANSMISSION_OFFSET_EXTENSION_SIZE
+    }
+}
+
+impl Marshal for TransmissionOffsetExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        if buf.remaining_mut() < TRANSMISSION_OFFSET_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        if !(TRANSMISSION_OFFSET_MIN..=TRANSMISSION_OFFSET_MAX).contains(&self.transmission_offset)
+        {
+            return Err(Error::TransmissionOffsetOverflow.into());
+        }
+
+        let offset = self.transmission_offset as u32;
+        buf.put_u8((offset >> 16) as u8);
+        buf.put_u8((offset >> 8) as u8);
+        buf.put_u8(offset as u8);
+
+        Ok(TRANSMISSION_OFFSET_EXTENSION_SIZE)
+    }
+}
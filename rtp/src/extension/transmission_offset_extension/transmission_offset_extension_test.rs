@@ -0,0 +1,59 @@
+use bytes::{Bytes, BytesMut};
+
+use super::*;
+use crate::error::Result;
+
+#[test]
+fn test_transmission_offset_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 2][..];
+    let result = TransmissionOffsetExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_transmission_offset_extension() -> Result<()> {
+    let tests = vec![
+        (Bytes::from_static(&[0x00, 0x01, 0x02]), 0x0102),
+        (
+            Bytes::from_static(&[0x7F, 0xFF, 0xFF]),
+            TRANSMISSION_OFFSET_MAX,
+        ),
+        (Bytes::from_static(&[0xFF, 0xFF, 0xFF]), -1),
+        (
+            Bytes::from_static(&[0x80, 0x00, 0x00]),
+            TRANSMISSION_OFFSET_MIN,
+        ),
+    ];
+
+    for (raw, transmission_offset) in tests {
+        let buf = &mut raw.clone();
+        let t1 = TransmissionOffsetExtension::unmarshal(buf)?;
+        let t2 = TransmissionOffsetExtension {
+            transmission_offset,
+        };
+        assert_eq!(t1, t2);
+
+        let mut dst = BytesMut::with_capacity(t2.marshal_size());
+        dst.resize(t2.marshal_size(), 0);
+        t2.marshal_to(&mut dst)?;
+        assert_eq!(raw, dst.freeze());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_transmission_offset_extension_overflow() -> Result<()> {
+    let t = TransmissionOffsetExtension {
+        transmission_offset: TRANSMISSION_OFFSET_MAX + 1,
+    };
+
+    let mut dst = BytesMut::with_capacity(t.marshal_size());
+    dst.resize(t.marshal_size(), 0);
+    let result = t.marshal_to(&mut dst);
+    assert!(result.is_err());
+
+    Ok(())
+}
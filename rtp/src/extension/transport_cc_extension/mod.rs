@@ -1,11 +1,15 @@
 #[cfg(test)]
 mod transport_cc_extension_test;
 
+use std::sync::atomic::Ordering;
+
 use bytes::{Buf, BufMut};
+use portable_atomic::AtomicU16;
 use serde::{Deserialize, Serialize};
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
 
 use crate::error::Error;
+use crate::header::Header;
 
 // transport-wide sequence
 pub const TRANSPORT_CC_EXTENSION_SIZE: usize = 2;
@@ -59,3 +63,35 @@ impl Marshal for TransportCcExtension {
         Ok(TRANSPORT_CC_EXTENSION_SIZE)
     }
 }
+
+/// TransportSequencer hands out transport-wide sequence numbers. A single instance is meant
+/// to be shared by every stream sent over a transport, so that the numbers increase
+/// monotonically across all SSRCs as required by transport-wide congestion control.
+#[derive(Debug, Default)]
+pub struct TransportSequencer {
+    next_sequence_number: AtomicU16,
+}
+
+impl TransportSequencer {
+    /// new returns a TransportSequencer whose first sequence number is init.
+    pub fn new(init: u16) -> Self {
+        TransportSequencer {
+            next_sequence_number: AtomicU16::new(init),
+        }
+    }
+
+    /// next_extension returns an extension carrying the next sequence number.
+    pub fn next_extension(&self) -> TransportCcExtension {
+        TransportCcExtension {
+            transport_sequence: self.next_sequence_number.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+
+    /// stamp sets the next sequence number as header extension id of header and returns it.
+    pub fn stamp(&self, header: &mut Header, id: u8) -> Result<u16, Error> {
+        let ext = self.next_extension();
+        header.set_extension(id, ext.marshal()?)?;
+
+        Ok(ext.transport_sequence)
+    }
+}
@@ -42,3 +42,37 @@ fn test_transport_cc_extension_extra_bytes() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_transport_sequencer() -> Result<()> {
+    let sequencer = TransportSequencer::new(u16::MAX - 1);
+
+    let mut headers = vec![
+        Header {
+            ssrc: 1,
+            ..Default::default()
+        },
+        Header {
+            ssrc: 2,
+            ..Default::default()
+        },
+        Header {
+            ssrc: 1,
+            ..Default::default()
+        },
+    ];
+
+    let mut stamped = vec![];
+    for header in &mut headers {
+        stamped.push(sequencer.stamp(header, 5)?);
+    }
+    assert_eq!(stamped, vec![u16::MAX - 1, u16::MAX, 0]);
+
+    for (header, expected) in headers.iter().zip(stamped) {
+        let mut payload = header.get_extension(5).expect("extension should be set");
+        let ext = TransportCcExtension::unmarshal(&mut payload)?;
+        assert_eq!(ext.transport_sequence, expected);
+    }
+
+    Ok(())
+}
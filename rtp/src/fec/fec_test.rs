@@ -0,0 +1,159 @@
+use super::*;
+use crate::header::Header;
+
+fn media_packet(sequence_number: u16, payload: &'static [u8]) -> Packet {
+    Packet {
+        header: Header {
+            version: 2,
+            marker: sequence_number.is_multiple_of(2),
+            payload_type: 96,
+            sequence_number,
+            timestamp: 3000 * sequence_number as u32,
+            ssrc: 0x1234_5678,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(payload),
+    }
+}
+
+#[test]
+fn test_fec_packet_round_trip() -> Result<()> {
+    let tests = vec![
+        FecPacket {
+            header_recovery: [0x01, 0xe0],
+            sn_base: 100,
+            ts_recovery: 0xdead_beef,
+            length_recovery: 3,
+            mask: 0xa000 << 32,
+            payload: Bytes::from_static(&[0x01, 0x02, 0x03]),
+        },
+        FecPacket {
+            sn_base: 65535,
+            mask: (1 << 47) | 1,
+            payload: Bytes::from_static(&[0xff]),
+            ..Default::default()
+        },
+    ];
+
+    for fec in tests {
+        let raw = fec.marshal()?;
+        assert_eq!(raw.len(), fec.marshal_size());
+        assert_eq!(raw[0] & 0x40 != 0, fec.long_mask(), "L bit");
+        let parsed = FecPacket::unmarshal(&mut raw.clone())?;
+        assert_eq!(parsed, fec);
+    }
+
+    let fec = FecPacket {
+        sn_base: 65535,
+        mask: (1 << 47) | (1 << 46) | 1,
+        ..Default::default()
+    };
+    assert_eq!(fec.protected_sequence_numbers(), vec![65535, 0, 46]);
+
+    let result = FecPacket::unmarshal(&mut Bytes::from_static(&[0x00; 13]));
+    assert!(result.is_err(), "FEC header must be complete");
+    let result = FecPacket::unmarshal(&mut Bytes::from_static(&[
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x80, 0x00, 0x01,
+    ]));
+    assert!(result.is_err(), "protection length overruns the payload");
+
+    Ok(())
+}
+
+#[test]
+fn test_fec_encoder_protection_rate() -> Result<()> {
+    let mut encoder = FecEncoder::new(4);
+    assert_eq!(encoder.protection_rate(), 0.0);
+
+    for sn in 0..4 {
+        let payloads = encoder.push(&media_packet(sn, &[0x01, 0x02]))?;
+        assert!(payloads.is_empty(), "no FEC without protection");
+    }
+
+    encoder.set_protection_rate(0.5);
+    let mut payloads = vec![];
+    for sn in 4..8 {
+        payloads.extend(encoder.push(&media_packet(sn, &[0x01, 0x02]))?);
+    }
+    assert_eq!(payloads.len(), 2, "half of the group size");
+
+    let first = FecPacket::unmarshal(&mut payloads[0].clone())?;
+    let second = FecPacket::unmarshal(&mut payloads[1].clone())?;
+    assert_eq!(first.protected_sequence_numbers(), vec![4, 6]);
+    assert_eq!(second.protected_sequence_numbers(), vec![5, 7]);
+
+    encoder.set_protection_rate(2.0);
+    assert_eq!(encoder.protection_rate(), 1.0);
+    encoder.set_protection_rate(f64::NAN);
+    assert_eq!(encoder.protection_rate(), 0.0);
+
+    // A sequence number gap closes the group early
+    encoder.set_protection_rate(1.0);
+    assert!(encoder.push(&media_packet(8, &[0x01]))?.is_empty());
+    let payloads = encoder.push(&media_packet(10, &[0x01]))?;
+    assert_eq!(payloads.len(), 1);
+    let fec = FecPacket::unmarshal(&mut payloads[0].clone())?;
+    assert_eq!(fec.protected_sequence_numbers(), vec![8]);
+    assert_eq!(encoder.flush()?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_fec_recover() -> Result<()> {
+    let packets = vec![
+        media_packet(65534, &[0x01, 0x02, 0x03, 0x04]),
+        media_packet(65535, &[0x05]),
+        media_packet(0, &[0x06, 0x07, 0x08]),
+        media_packet(1, &[0x09, 0x0a]),
+    ];
+
+    let mut encoder = FecEncoder::new(4);
+    encoder.set_protection_rate(0.5);
+    let mut fec_payloads = vec![];
+    for packet in &packets {
+        fec_payloads.extend(encoder.push(packet)?);
+    }
+    assert_eq!(fec_payloads.len(), 2);
+
+    // Lose two consecutive packets, each protected by a different FEC packet
+    let mut decoder = FecDecoder::new(0x1234_5678);
+    decoder.push_media(&packets[0])?;
+    decoder.push_media(&packets[3])?;
+    for payload in &fec_payloads {
+        decoder.push_fec(payload)?;
+    }
+
+    let mut recovered = decoder.recover();
+    recovered.sort_by_key(|p| p.header.sequence_number);
+    assert_eq!(recovered, vec![packets[2].clone(), packets[1].clone()]);
+    assert!(decoder.recover().is_empty(), "FEC packets are used once");
+
+    Ok(())
+}
+
+#[test]
+fn test_fec_recover_needs_single_loss() -> Result<()> {
+    let packets: Vec<Packet> = (10..13)
+        .map(|sn| media_packet(sn, &[0xaa, 0xbb, 0xcc]))
+        .collect();
+
+    let mut encoder = FecEncoder::new(3);
+    encoder.set_protection_rate(0.1);
+    let mut fec_payloads = vec![];
+    for packet in &packets {
+        fec_payloads.extend(encoder.push(packet)?);
+    }
+    assert_eq!(fec_payloads.len(), 1);
+
+    let mut decoder = FecDecoder::new(0x1234_5678);
+    decoder.push_media(&packets[0])?;
+    decoder.push_fec(&fec_payloads[0])?;
+    assert!(decoder.recover().is_empty(), "two packets are missing");
+
+    // Once a second packet arrives the last one can be rebuilt
+    decoder.push_media(&packets[2])?;
+    assert_eq!(decoder.recover(), vec![packets[1].clone()]);
+
+    Ok(())
+}
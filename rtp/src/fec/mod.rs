@@ -0,0 +1,402 @@
+//! ULPFEC (RFC 5109) forward error correction.
+//! Reference: https://datatracker.ietf.org/doc/html/rfc5109
+//!
+//! Only the single-level ULPFEC scheme is implemented, FlexFEC (RFC 8627) is not supported.
+
+#[cfg(test)]
+mod fec_test;
+
+use std::collections::{HashMap, VecDeque};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::{Error, Result};
+use crate::packet::Packet;
+
+/// FEC_HEADER_SIZE is the size of the FEC header of a ULPFEC payload.
+pub const FEC_HEADER_SIZE: usize = 10;
+/// FEC_LEVEL_HEADER_SIZE is the size of a level header using a 16 bit mask.
+pub const FEC_LEVEL_HEADER_SIZE: usize = 4;
+/// FEC_LEVEL_HEADER_SIZE_LONG is the size of a level header using a 48 bit mask.
+pub const FEC_LEVEL_HEADER_SIZE_LONG: usize = 8;
+/// FEC_MAX_MEDIA_PACKETS is the number of media packets a single FEC packet can protect.
+pub const FEC_MAX_MEDIA_PACKETS: usize = 48;
+
+const RTP_FIXED_HEADER_SIZE: usize = 12;
+const FEC_SHORT_MASK_PACKETS: usize = 16;
+const FEC_DECODER_MAX_MEDIA_PACKETS: usize = 512;
+const FEC_DECODER_MAX_FEC_PACKETS: usize = 64;
+
+/// FecPacket is a ULPFEC payload with a single protection level.
+/// See <https://datatracker.ietf.org/doc/html/rfc5109#section-7>
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |E|L|P|X|  CC   |M| PT recovery |            SN base            |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                          TS recovery                          |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |        length recovery        |       Protection Length       |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |             mask              |     mask cont. (present only  |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |     when L = 1)               |   Level 0 payload ...         |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FecPacket {
+    /// header_recovery holds the P, X and CC bits then the M and PT fields.
+    pub header_recovery: [u8; 2],
+    pub sn_base: u16,
+    pub ts_recovery: u32,
+    pub length_recovery: u16,
+    /// mask has a bit set for every protected media packet, starting from
+    /// bit 47 for `sn_base`.
+    pub mask: u64,
+    pub payload: Bytes,
+}
+
+impl FecPacket {
+    /// long_mask reports whether the mask protects packets beyond the first 16.
+    pub fn long_mask(&self) -> bool {
+        self.mask & 0xFFFF_FFFF != 0
+    }
+
+    /// protected_sequence_numbers lists the sequence numbers covered by this packet.
+    pub fn protected_sequence_numbers(&self) -> Vec<u16> {
+        (0..FEC_MAX_MEDIA_PACKETS)
+            .filter(|i| self.mask & (1 << (47 - i)) != 0)
+            .map(|i| self.sn_base.wrapping_add(i as u16))
+            .collect()
+    }
+}
+
+impl MarshalSize for FecPacket {
+    fn marshal_size(&self) -> usize {
+        let level_header_size = if self.long_mask() {
+            FEC_LEVEL_HEADER_SIZE_LONG
+        } else {
+            FEC_LEVEL_HEADER_SIZE
+        };
+        FEC_HEADER_SIZE + level_header_size + self.payload.len()
+    }
+}
+
+impl Marshal for FecPacket {
+    /// marshal_to serializes the FEC packet into a ULPFEC payload.
+    fn marshal_to(&self, mut buf: &mut [u8]) -> std::result::Result<usize, util::Error> {
+        let size = self.marshal_size();
+        if buf.remaining_mut() < size {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let long_mask = self.long_mask();
+        buf.put_u8(((long_mask as u8) << 6) | (self.header_recovery[0] & 0x3F));
+        buf.put_u8(self.header_recovery[1]);
+        buf.put_u16(self.sn_base);
+        buf.put_u32(self.ts_recovery);
+        buf.put_u16(self.length_recovery);
+        buf.put_u16(self.payload.len() as u16);
+        buf.put_u16((self.mask >> 32) as u16);
+        if long_mask {
+            buf.put_u32(self.mask as u32);
+        }
+        buf.put(&*self.payload);
+
+        Ok(size)
+    }
+}
+
+impl Unmarshal for FecPacket {
+    /// unmarshal parses a ULPFEC payload.
+    fn unmarshal<B>(raw_packet: &mut B) -> std::result::Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < FEC_HEADER_SIZE + FEC_LEVEL_HEADER_SIZE {
+            return Err(Error::ErrShortPacket.into());
+        }
+
+        let b0 = raw_packet.get_u8();
+        let long_mask = b0 & 0x40 != 0;
+        let header_recovery = [b0 & 0x3F, raw_packet.get_u8()];
+        let sn_base = raw_packet.get_u16();
+        let ts_recovery = raw_packet.get_u32();
+        let length_recovery = raw_packet.get_u16();
+        let protection_length = raw_packet.get_u16() as usize;
+
+        let mask = if long_mask {
+            if raw_packet.remaining() < FEC_LEVEL_HEADER_SIZE_LONG - 2 {
+                return Err(Error::ErrShortPacket.into());
+            }
+            ((raw_packet.get_u16() as u64) << 32) | raw_packet.get_u32() as u64
+        } else {
+            (raw_packet.get_u16() as u64) << 32
+        };
+
+        if raw_packet.remaining() < protection_length {
+            return Err(Error::ErrShortPacket.into());
+        }
+
+        Ok(FecPacket {
+            header_recovery,
+            sn_base,
+            ts_recovery,
+            length_recovery,
+            mask,
+            payload: raw_packet.copy_to_bytes(protection_length),
+        })
+    }
+}
+
+/// FecEncoder generates ULPFEC payloads over groups of consecutive media packets.
+/// The amount of FEC is driven by the protection rate, which the congestion
+/// controller is expected to update from the observed loss and available bandwidth.
+#[derive(Debug, Clone)]
+pub struct FecEncoder {
+    group_size: usize,
+    protection_rate: f64,
+    group: Vec<Bytes>,
+    sn_base: u16,
+}
+
+impl FecEncoder {
+    /// new creates a FecEncoder protecting groups of up to `group_size` packets.
+    pub fn new(group_size: usize) -> Self {
+        FecEncoder {
+            group_size: group_size.clamp(1, FEC_MAX_MEDIA_PACKETS),
+            protection_rate: 0.0,
+            group: Vec::with_capacity(group_size),
+            sn_base: 0,
+        }
+    }
+
+    /// set_protection_rate sets the ratio of FEC packets to media packets,
+    /// clamped between 0 (no FEC) and 1 (one FEC packet per media packet).
+    pub fn set_protection_rate(&mut self, protection_rate: f64) {
+        self.protection_rate = if protection_rate.is_nan() {
+            0.0
+        } else {
+            protection_rate.clamp(0.0, 1.0)
+        };
+    }
+
+    /// protection_rate returns the current ratio of FEC packets to media packets.
+    pub fn protection_rate(&self) -> f64 {
+        self.protection_rate
+    }
+
+    /// push adds a media packet to the current group, returning the FEC payloads
+    /// protecting the group once it is complete. A gap in sequence numbers
+    /// closes the group early.
+    pub fn push(&mut self, packet: &Packet) -> Result<Vec<Bytes>> {
+        let mut payloads = vec![];
+        if !self.group.is_empty()
+            && packet.header.sequence_number != self.sn_base.wrapping_add(self.group.len() as u16)
+        {
+            payloads = self.flush()?;
+        }
+
+        if self.group.is_empty() {
+            self.sn_base = packet.header.sequence_number;
+        }
+        self.group.push(packet.marshal()?);
+
+        if self.group.len() == self.group_size {
+            payloads.extend(self.flush()?);
+        }
+
+        Ok(payloads)
+    }
+
+    /// flush returns the FEC payloads protecting the packets pushed so far and
+    /// starts a new group.
+    pub fn flush(&mut self) -> Result<Vec<Bytes>> {
+        let group = std::mem::take(&mut self.group);
+        let fec_count =
+            ((group.len() as f64 * self.protection_rate).ceil() as usize).min(group.len());
+
+        // FEC packets protect interleaved media packets, so that a burst of up to
+        // fec_count consecutive losses can be recovered.
+        let mut payloads = Vec::with_capacity(fec_count);
+        for i in 0..fec_count {
+            let protected: Vec<(usize, &Bytes)> = group
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| j % fec_count == i)
+                .collect();
+            payloads.push(FecEncoder::encode(self.sn_base, &protected).marshal()?);
+        }
+
+        Ok(payloads)
+    }
+
+    fn encode(sn_base: u16, protected: &[(usize, &Bytes)]) -> FecPacket {
+        let protection_length = protected
+            .iter()
+            .map(|(_, raw)| raw.len() - RTP_FIXED_HEADER_SIZE)
+            .max()
+            .unwrap_or(0);
+
+        let mut fec = FecPacket {
+            sn_base,
+            ..Default::default()
+        };
+        let mut payload = vec![0u8; protection_length];
+        for (index, raw) in protected {
+            fec.header_recovery[0] ^= raw[0] & 0x3F;
+            fec.header_recovery[1] ^= raw[1];
+            fec.ts_recovery ^= u32::from_be_bytes([raw[4], raw[5], raw[6], raw[7]]);
+            fec.length_recovery ^= (raw.len() - RTP_FIXED_HEADER_SIZE) as u16;
+            fec.mask |= 1 << (47 - index);
+            for (p, b) in payload.iter_mut().zip(&raw[RTP_FIXED_HEADER_SIZE..]) {
+                *p ^= b;
+            }
+        }
+        fec.payload = Bytes::from(payload);
+
+        fec
+    }
+}
+
+impl Default for FecEncoder {
+    fn default() -> Self {
+        FecEncoder::new(FEC_SHORT_MASK_PACKETS)
+    }
+}
+
+/// FecDecoder recovers lost media packets of a single RTP stream from ULPFEC
+/// payloads. Recovery is possible whenever a FEC packet covers exactly one
+/// missing media packet.
+#[derive(Debug, Default, Clone)]
+pub struct FecDecoder {
+    ssrc: u32,
+    media: HashMap<u16, Bytes>,
+    media_order: VecDeque<u16>,
+    fec: VecDeque<FecPacket>,
+}
+
+impl FecDecoder {
+    /// new creates a FecDecoder for the media stream `ssrc`.
+    pub fn new(ssrc: u32) -> Self {
+        FecDecoder {
+            ssrc,
+            ..Default::default()
+        }
+    }
+
+    /// push_media records a received media packet.
+    pub fn push_media(&mut self, packet: &Packet) -> Result<()> {
+        let raw = packet.marshal()?;
+        self.insert_media(packet.header.sequence_number, raw);
+        Ok(())
+    }
+
+    /// push_fec records a received ULPFEC payload.
+    pub fn push_fec(&mut self, payload: &Bytes) -> Result<()> {
+        let fec = FecPacket::unmarshal(&mut payload.clone())?;
+        if self.fec.len() == FEC_DECODER_MAX_FEC_PACKETS {
+            self.fec.pop_front();
+        }
+        self.fec.push_back(fec);
+        Ok(())
+    }
+
+    /// recover returns the media packets that can be rebuilt from the FEC
+    /// packets received so far. FEC packets are dropped once they have been used
+    /// or all the packets they protect have been received.
+    pub fn recover(&mut self) -> Vec<Packet> {
+        let mut recovered = vec![];
+
+        loop {
+            let mut progress = false;
+            let mut pending = VecDeque::with_capacity(self.fec.len());
+
+            while let Some(fec) = self.fec.pop_front() {
+                let missing: Vec<u16> = fec
+                    .protected_sequence_numbers()
+                    .into_iter()
+                    .filter(|sn| !self.media.contains_key(sn))
+                    .collect();
+
+                match missing.len() {
+                    0 => {}
+                    1 => {
+                        if let Some(packet) = self.recover_packet(&fec, missing[0]) {
+                            recovered.push(packet);
+                            progress = true;
+                        }
+                    }
+                    _ => pending.push_back(fec),
+                }
+            }
+
+            self.fec = pending;
+            if !progress {
+                break;
+            }
+        }
+
+        recovered
+    }
+
+    fn recover_packet(&mut self, fec: &FecPacket, sequence_number: u16) -> Option<Packet> {
+        let mut header_recovery = fec.header_recovery;
+        let mut ts_recovery = fec.ts_recovery;
+        let mut length_recovery = fec.length_recovery;
+        let mut payload = fec.payload.to_vec();
+
+        for sn in fec.protected_sequence_numbers() {
+            if sn == sequence_number {
+                continue;
+            }
+
+            let raw = self.media.get(&sn)?;
+            let body = &raw[RTP_FIXED_HEADER_SIZE..];
+            if body.len() > payload.len() {
+                // The media packet can't have been protected by this FEC packet.
+                return None;
+            }
+
+            header_recovery[0] ^= raw[0] & 0x3F;
+            header_recovery[1] ^= raw[1];
+            ts_recovery ^= u32::from_be_bytes([raw[4], raw[5], raw[6], raw[7]]);
+            length_recovery ^= body.len() as u16;
+            for (p, b) in payload.iter_mut().zip(body) {
+                *p ^= b;
+            }
+        }
+
+        let length = length_recovery as usize;
+        if length > payload.len() {
+            return None;
+        }
+
+        let mut raw = BytesMut::with_capacity(RTP_FIXED_HEADER_SIZE + length);
+        raw.put_u8(0x80 | header_recovery[0]);
+        raw.put_u8(header_recovery[1]);
+        raw.put_u16(sequence_number);
+        raw.put_u32(ts_recovery);
+        raw.put_u32(self.ssrc);
+        raw.put(&payload[..length]);
+        let raw = raw.freeze();
+
+        let packet = Packet::unmarshal(&mut raw.clone()).ok()?;
+        self.insert_media(sequence_number, raw);
+
+        Some(packet)
+    }
+
+    fn insert_media(&mut self, sequence_number: u16, raw: Bytes) {
+        if self.media.insert(sequence_number, raw).is_none() {
+            self.media_order.push_back(sequence_number);
+        }
+        while self.media_order.len() > FEC_DECODER_MAX_MEDIA_PACKETS {
+            if let Some(sn) = self.media_order.pop_front() {
+                self.media.remove(&sn);
+            }
+        }
+    }
+}
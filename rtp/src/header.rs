@@ -136,6 +136,9 @@ impl Unmarshal for Header {
                             break;
                         }
 
+                        if curr_offset + len > end {
+                            return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                        }
                         extensions.push(Extension {
                             id: extid,
                             payload: raw_packet.copy_to_bytes(len),
@@ -158,9 +161,15 @@ impl Unmarshal for Header {
                         let extid = b;
                         curr_offset += 1;
 
+                        if curr_offset >= end {
+                            return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                        }
                         let len = raw_packet.get_u8() as usize;
                         curr_offset += 1;
 
+                        if curr_offset + len > end {
+                            return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                        }
                         extensions.push(Extension {
                             id: extid,
                             payload: raw_packet.copy_to_bytes(len),
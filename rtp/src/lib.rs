@@ -4,9 +4,11 @@
 pub mod codecs;
 mod error;
 pub mod extension;
+pub mod fec;
 pub mod header;
 pub mod packet;
 pub mod packetizer;
+pub mod rtx;
 pub mod sequence;
 
 pub use error::Error;
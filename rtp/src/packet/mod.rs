@@ -17,6 +17,14 @@ pub struct Packet {
     pub payload: Bytes,
 }
 
+impl Packet {
+    /// is_padding_only reports whether the packet carries padding but no payload,
+    /// e.g. when sent to probe the available bandwidth.
+    pub fn is_padding_only(&self) -> bool {
+        self.header.padding && self.payload.is_empty()
+    }
+}
+
 impl fmt::Display for Packet {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = "RTP PACKET:\n".to_string();
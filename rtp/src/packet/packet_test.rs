@@ -141,6 +141,30 @@ fn test_padding() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_padding_only() -> Result<()> {
+    let pkt = Packet {
+        header: Header {
+            version: 2,
+            padding: true,
+            sequence_number: 27023,
+            ssrc: 476325762,
+            ..Default::default()
+        },
+        payload: Bytes::new(),
+    };
+    assert!(pkt.is_padding_only());
+    assert_eq!(pkt.marshal_size(), 12 + 4);
+
+    let mut raw = pkt.marshal()?;
+    assert_eq!(&raw[12..], &[0, 0, 0, 4]);
+
+    let p = Packet::unmarshal(&mut raw)?;
+    assert_eq!(pkt, p);
+
+    Ok(())
+}
+
 #[test]
 fn test_packet_marshal_unmarshal() -> Result<()> {
     let pkt = Packet {
@@ -161,7 +185,6 @@ fn test_packet_marshal_unmarshal() -> Result<()> {
             ..Default::default()
         },
         payload: Bytes::from_static(&[0xFFu8; 15]),
-        ..Default::default()
     };
     let mut raw = pkt.marshal()?;
     let p = Packet::unmarshal(&mut raw)?;
@@ -1138,6 +1161,7 @@ struct Cases {
     err: Error,
 }
 
+#[test]
 fn test_unmarshal_error_handling() {
     let mut cases = HashMap::new();
 
@@ -1200,6 +1224,34 @@ fn test_unmarshal_error_handling() {
         },
     );
 
+    cases.insert(
+        "OneByteExtensionOverflow",
+        Cases {
+            input: Bytes::from_static(&[
+                0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+                0xBE, 0xDE, 0x00, 0x01, // have 1 extension
+                0x13, 0xAA, 0xBB, 0xCC, // payload of 4 bytes does not fit in the extension
+                0x98, 0x36, 0xbe, 0x88, // payload
+            ]),
+            err: Error::ErrHeaderSizeInsufficientForExtension,
+        },
+    );
+
+    cases.insert(
+        "TwoByteExtensionOverflow",
+        Cases {
+            input: Bytes::from_static(&[
+                0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+                0x10, 0x00, 0x00, 0x01, // have 1 extension
+                0x01, 0x05, 0xAA, 0xBB, // payload of 5 bytes does not fit in the extension
+                0x98, 0x36, 0xbe, 0x88, // payload
+            ]),
+            err: Error::ErrHeaderSizeInsufficientForExtension,
+        },
+    );
+
     for (name, mut test_case) in cases.drain() {
         let result = Header::unmarshal(&mut test_case.input);
         let err = result.err().unwrap();
@@ -1211,6 +1263,7 @@ fn test_unmarshal_error_handling() {
     }
 }
 
+#[test]
 fn test_round_trip() -> Result<()> {
     let raw_pkt = Bytes::from_static(&[
         0x00u8, 0x10, 0x23, 0x45, 0x12, 0x34, 0x45, 0x67, 0xCC, 0xDD, 0xEE, 0xFF, 0x00, 0x11, 0x22,
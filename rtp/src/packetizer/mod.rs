@@ -3,12 +3,12 @@ mod packetizer_test;
 
 use std::fmt;
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use bytes::{Bytes, BytesMut};
 use util::marshal::{Marshal, MarshalSize};
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::extension::abs_send_time_extension::*;
 use crate::header::*;
 use crate::packet::*;
@@ -30,7 +30,22 @@ impl Clone for Box<dyn Payloader + Send + Sync> {
 pub trait Packetizer: fmt::Debug {
     fn enable_abs_send_time(&mut self, value: u8);
     fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>>;
+    /// packetize_duration packetizes a payload lasting `duration`, converting it to
+    /// samples with the clock rate. Fractions of samples are carried over to the
+    /// next call so timestamps don't drift.
+    ///
+    /// Packetizers that don't know their clock rate return
+    /// [`Error::ErrPacketizeDurationUnsupported`]; use [`Packetizer::packetize`] with them.
+    fn packetize_duration(&mut self, _payload: &Bytes, _duration: Duration) -> Result<Vec<Packet>> {
+        Err(Error::ErrPacketizeDurationUnsupported)
+    }
     fn skip_samples(&mut self, skipped_samples: u32);
+    /// generate_padding builds `count` padding-only packets, e.g. to probe the
+    /// available bandwidth. They take sequence numbers from the media stream and
+    /// reuse the current timestamp. Packetizers that can't pad return none.
+    fn generate_padding(&mut self, _count: usize) -> Vec<Packet> {
+        vec![]
+    }
     fn clone_to(&self) -> Box<dyn Packetizer + Send + Sync>;
 }
 
@@ -59,6 +74,10 @@ pub trait Depacketizer {
 /// FnTimeGen provides current SystemTime
 pub type FnTimeGen = Arc<dyn (Fn() -> SystemTime) + Send + Sync>;
 
+/// Size of the one-byte header extension block holding abs-send-time:
+/// 4 bytes of profile and length, 1 byte of id/length and 3 bytes of payload.
+const ABS_SEND_TIME_EXTENSION_SIZE: usize = 8;
+
 #[derive(Clone)]
 pub(crate) struct PacketizerImpl {
     pub(crate) mtu: usize,
@@ -70,6 +89,8 @@ pub(crate) struct PacketizerImpl {
     pub(crate) clock_rate: u32,
     pub(crate) abs_send_time: u8, //http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time
     pub(crate) time_gen: Option<FnTimeGen>,
+    /// Fraction of a sample left over by packetize_duration, in nanoseconds * clock_rate.
+    pub(crate) sample_remainder: u128,
 }
 
 impl fmt::Debug for PacketizerImpl {
@@ -103,6 +124,7 @@ pub fn new_packetizer(
         clock_rate,
         abs_send_time: 0,
         time_gen: None,
+        sample_remainder: 0,
     }
 }
 
@@ -112,7 +134,16 @@ impl Packetizer for PacketizerImpl {
     }
 
     fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>> {
-        let payloads = self.payloader.payload(self.mtu - 12, payload)?;
+        // The fixed header ends where the CSRC list would start
+        let mut overhead = CSRC_OFFSET;
+        if self.abs_send_time != 0 {
+            overhead += ABS_SEND_TIME_EXTENSION_SIZE;
+        }
+        if self.mtu <= overhead {
+            return Err(Error::ErrMtuTooSmall(self.mtu));
+        }
+
+        let payloads = self.payloader.payload(self.mtu - overhead, payload)?;
         let payloads_len = payloads.len();
         let mut packets = Vec::with_capacity(payloads_len);
         for (i, payload) in payloads.into_iter().enumerate() {
@@ -153,12 +184,41 @@ impl Packetizer for PacketizerImpl {
         Ok(packets)
     }
 
+    fn packetize_duration(&mut self, payload: &Bytes, duration: Duration) -> Result<Vec<Packet>> {
+        const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+        let total = duration.as_nanos() * self.clock_rate as u128 + self.sample_remainder;
+        self.sample_remainder = total % NANOS_PER_SECOND;
+        let samples = (total / NANOS_PER_SECOND) as u32;
+
+        self.packetize(payload, samples)
+    }
+
     /// skip_samples causes a gap in sample count between Packetize requests so the
     /// RTP payloads produced have a gap in timestamps
     fn skip_samples(&mut self, skipped_samples: u32) {
         self.timestamp = self.timestamp.wrapping_add(skipped_samples);
     }
 
+    fn generate_padding(&mut self, count: usize) -> Vec<Packet> {
+        (0..count)
+            .map(|_| Packet {
+                header: Header {
+                    version: 2,
+                    padding: true,
+                    extension: false,
+                    marker: false,
+                    payload_type: self.payload_type,
+                    sequence_number: self.sequencer.next_sequence_number(),
+                    timestamp: self.timestamp,
+                    ssrc: self.ssrc,
+                    ..Default::default()
+                },
+                payload: Bytes::new(),
+            })
+            .collect()
+    }
+
     fn clone_to(&self) -> Box<dyn Packetizer + Send + Sync> {
         Box::new(self.clone())
     }
@@ -56,6 +56,7 @@ fn test_packetizer_abs_send_time() -> Result<()> {
         clock_rate: 90000,
         abs_send_time: 0,
         time_gen,
+        sample_remainder: 0,
     };
     pktizer.enable_abs_send_time(1);
 
@@ -108,3 +109,146 @@ fn test_packetizer_timestamp_rollover_does_not_panic() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_packetizer_mtu_accounts_for_extensions() -> Result<()> {
+    let g722 = Box::new(g7xx::G722Payloader {});
+    let seq = Box::new(new_fixed_sequencer(0));
+    let payload = Bytes::from_static(&[0; 88]);
+
+    let mut packetizer = new_packetizer(100, 98, 0x1234ABCD, g722, seq, 8000);
+    let packets = packetizer.packetize(&payload, 88)?;
+    assert_eq!(packets.len(), 1, "88 bytes fit after the 12 byte header");
+
+    packetizer.enable_abs_send_time(1);
+    let packets = packetizer.packetize(&payload, 88)?;
+    assert_eq!(packets.len(), 2, "abs-send-time takes 8 more bytes");
+    for packet in &packets {
+        assert!(packet.marshal_size() <= 100);
+    }
+
+    let mut packetizer = new_packetizer(
+        12,
+        98,
+        0x1234ABCD,
+        Box::new(g7xx::G722Payloader {}),
+        Box::new(new_fixed_sequencer(0)),
+        8000,
+    );
+    assert_eq!(
+        packetizer.packetize(&payload, 88),
+        Err(Error::ErrMtuTooSmall(12))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_packetizer_packetize_duration() -> Result<()> {
+    let g722 = Box::new(g7xx::G722Payloader {});
+    let seq = Box::new(new_fixed_sequencer(0));
+    let payload = Bytes::from_static(&[0; 10]);
+
+    let mut packetizer = PacketizerImpl {
+        mtu: 1200,
+        payload_type: 96,
+        ssrc: 0x1234ABCD,
+        payloader: g722,
+        sequencer: seq,
+        timestamp: 0,
+        clock_rate: 90000,
+        abs_send_time: 0,
+        time_gen: None,
+        sample_remainder: 0,
+    };
+
+    // 30 fps does not map to a whole number of 90kHz ticks per frame when
+    // expressed in nanoseconds, make sure the remainder is carried over.
+    let frame = Duration::from_nanos(33_333_333);
+    let mut timestamps = vec![];
+    for _ in 0..31 {
+        let packets = packetizer.packetize_duration(&payload, frame)?;
+        timestamps.push(packets[0].header.timestamp);
+    }
+    assert_eq!(timestamps[1], 2999);
+    assert_eq!(timestamps[30], 89999, "no drift after 30 frames");
+
+    let mut packetizer = new_packetizer(
+        1200,
+        111,
+        0x1234ABCD,
+        Box::new(opus::OpusPayloader {}),
+        Box::new(new_fixed_sequencer(0)),
+        48000,
+    );
+    let first = packetizer.packetize_duration(&payload, Duration::from_millis(20))?;
+    let second = packetizer.packetize_duration(&payload, Duration::from_millis(20))?;
+    assert_eq!(
+        second[0]
+            .header
+            .timestamp
+            .wrapping_sub(first[0].header.timestamp),
+        960
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_packetizer_generate_padding() -> Result<()> {
+    let payload = Bytes::from_static(&[0; 128]);
+    let g722 = Box::new(g7xx::G722Payloader {});
+    let seq = Box::new(new_fixed_sequencer(65534));
+
+    let mut packetizer = new_packetizer(100, 98, 0x1234ABCD, g722, seq, 90000);
+    let packets = packetizer.packetize(&payload, 2000)?;
+    assert_eq!(packets.len(), 2);
+    let timestamp = packets[0].header.timestamp;
+
+    let padding = packetizer.generate_padding(3);
+    assert_eq!(padding.len(), 3);
+    for (i, p) in padding.iter().enumerate() {
+        assert!(p.is_padding_only());
+        assert!(!p.header.marker);
+        assert_eq!(p.header.payload_type, 98);
+        assert_eq!(p.header.ssrc, 0x1234ABCD);
+        assert_eq!(p.header.sequence_number, i as u16);
+        assert_eq!(p.header.timestamp, timestamp.wrapping_add(2000));
+        assert_eq!(p.marshal_size(), 12 + 4);
+    }
+
+    // Media packets continue after the padding
+    let packets = packetizer.packetize(&payload, 2000)?;
+    assert_eq!(packets[0].header.sequence_number, 3);
+
+    Ok(())
+}
+
+/// A packetizer implementing only the required methods of the trait
+#[derive(Debug, Clone)]
+struct MinimalPacketizer;
+
+impl Packetizer for MinimalPacketizer {
+    fn enable_abs_send_time(&mut self, _value: u8) {}
+
+    fn packetize(&mut self, _payload: &Bytes, _samples: u32) -> Result<Vec<Packet>> {
+        Ok(vec![])
+    }
+
+    fn skip_samples(&mut self, _skipped_samples: u32) {}
+
+    fn clone_to(&self) -> Box<dyn Packetizer + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+#[test]
+fn test_packetizer_default_methods() {
+    let mut packetizer = MinimalPacketizer;
+
+    assert_eq!(
+        packetizer.packetize_duration(&Bytes::from_static(&[0; 128]), Duration::from_millis(20)),
+        Err(Error::ErrPacketizeDurationUnsupported)
+    );
+    assert!(packetizer.generate_padding(3).is_empty());
+}
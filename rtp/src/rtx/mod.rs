@@ -0,0 +1,176 @@
+#[cfg(test)]
+mod rtx_test;
+
+use std::collections::HashMap;
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::error::{Error, Result};
+use crate::header::Header;
+use crate::packet::Packet;
+
+/// RTX_OSN_SIZE is the size of the original sequence number prepended to RTX payloads.
+pub const RTX_OSN_SIZE: usize = 2;
+
+#[derive(Debug, Clone, Copy)]
+struct RtxStream {
+    ssrc: u32,
+    sequence_number: u16,
+}
+
+/// RtxEncoder wraps media packets into RTX packets (RFC 4588) so they can be
+/// retransmitted on a separate SSRC and payload type.
+#[derive(Debug, Default, Clone)]
+pub struct RtxEncoder {
+    /// payload_types maps media payload types to their RTX payload type.
+    payload_types: HashMap<u8, u8>,
+    /// streams maps media SSRCs to their RTX stream.
+    streams: HashMap<u32, RtxStream>,
+}
+
+impl RtxEncoder {
+    /// add_payload_type maps the media `payload_type` to `rtx_payload_type`,
+    /// as signaled by `a=fmtp:<rtx_payload_type> apt=<payload_type>`.
+    pub fn add_payload_type(&mut self, payload_type: u8, rtx_payload_type: u8) {
+        self.payload_types.insert(payload_type, rtx_payload_type);
+    }
+
+    /// add_stream retransmits packets of `media_ssrc` on `rtx_ssrc`, numbering
+    /// them from `sequence_number`.
+    pub fn add_stream(&mut self, media_ssrc: u32, rtx_ssrc: u32, sequence_number: u16) {
+        self.streams.insert(
+            media_ssrc,
+            RtxStream {
+                ssrc: rtx_ssrc,
+                sequence_number,
+            },
+        );
+    }
+
+    /// remove_stream stops retransmitting packets of `media_ssrc`.
+    pub fn remove_stream(&mut self, media_ssrc: u32) {
+        self.streams.remove(&media_ssrc);
+    }
+
+    /// encapsulate builds the RTX packet retransmitting `packet`. The original
+    /// sequence number is prepended to the payload and the packet is
+    /// renumbered in the sequence space of the RTX stream.
+    pub fn encapsulate(&mut self, packet: &Packet) -> Result<Packet> {
+        let rtx_payload_type = *self
+            .payload_types
+            .get(&packet.header.payload_type)
+            .ok_or(Error::ErrRtxUnknownPayloadType(packet.header.payload_type))?;
+        let stream = self
+            .streams
+            .get_mut(&packet.header.ssrc)
+            .ok_or(Error::ErrRtxUnknownSsrc(packet.header.ssrc))?;
+
+        let mut payload = BytesMut::with_capacity(RTX_OSN_SIZE + packet.payload.len());
+        payload.put_u16(packet.header.sequence_number);
+        payload.put(&*packet.payload);
+
+        let mut header = packet.header.clone();
+        header.ssrc = stream.ssrc;
+        header.payload_type = rtx_payload_type;
+        header.sequence_number = stream.sequence_number;
+        stream.sequence_number = stream.sequence_number.wrapping_add(1);
+
+        Ok(Packet {
+            header,
+            payload: payload.freeze(),
+        })
+    }
+
+    /// padding builds a padding-only RTX packet for the stream of `media_ssrc`,
+    /// e.g. to probe the available bandwidth without consuming sequence numbers
+    /// of the media stream. Padding-only RTX packets carry no original sequence
+    /// number.
+    pub fn padding(&mut self, media_ssrc: u32, payload_type: u8, timestamp: u32) -> Result<Packet> {
+        let rtx_payload_type = *self
+            .payload_types
+            .get(&payload_type)
+            .ok_or(Error::ErrRtxUnknownPayloadType(payload_type))?;
+        let stream = self
+            .streams
+            .get_mut(&media_ssrc)
+            .ok_or(Error::ErrRtxUnknownSsrc(media_ssrc))?;
+
+        let header = Header {
+            version: 2,
+            padding: true,
+            payload_type: rtx_payload_type,
+            sequence_number: stream.sequence_number,
+            timestamp,
+            ssrc: stream.ssrc,
+            ..Default::default()
+        };
+        stream.sequence_number = stream.sequence_number.wrapping_add(1);
+
+        Ok(Packet {
+            header,
+            payload: Bytes::new(),
+        })
+    }
+}
+
+/// RtxDecoder unwraps RTX packets (RFC 4588), restoring the payload type,
+/// SSRC and sequence number of the original media packet.
+#[derive(Debug, Default, Clone)]
+pub struct RtxDecoder {
+    /// payload_types maps RTX payload types to their associated (apt) payload type.
+    payload_types: HashMap<u8, u8>,
+    /// ssrcs maps RTX SSRCs to their media SSRC.
+    ssrcs: HashMap<u32, u32>,
+}
+
+impl RtxDecoder {
+    /// add_payload_type maps `rtx_payload_type` back to the media `payload_type`.
+    pub fn add_payload_type(&mut self, rtx_payload_type: u8, payload_type: u8) {
+        self.payload_types.insert(rtx_payload_type, payload_type);
+    }
+
+    /// add_stream maps `rtx_ssrc` back to `media_ssrc`, e.g. from an
+    /// `a=ssrc-group:FID <media_ssrc> <rtx_ssrc>` line.
+    pub fn add_stream(&mut self, rtx_ssrc: u32, media_ssrc: u32) {
+        self.ssrcs.insert(rtx_ssrc, media_ssrc);
+    }
+
+    /// remove_stream forgets about `rtx_ssrc`.
+    pub fn remove_stream(&mut self, rtx_ssrc: u32) {
+        self.ssrcs.remove(&rtx_ssrc);
+    }
+
+    /// is_rtx reports whether `packet` belongs to a known RTX stream.
+    pub fn is_rtx(&self, packet: &Packet) -> bool {
+        self.ssrcs.contains_key(&packet.header.ssrc)
+            && self.payload_types.contains_key(&packet.header.payload_type)
+    }
+
+    /// decapsulate rebuilds the original media packet carried by an RTX packet.
+    /// Padding-only packets carry no media and fail with ErrShortPacket; check
+    /// `Packet::is_padding_only` to drop them beforehand.
+    pub fn decapsulate(&self, packet: &Packet) -> Result<Packet> {
+        let payload_type = *self
+            .payload_types
+            .get(&packet.header.payload_type)
+            .ok_or(Error::ErrRtxUnknownPayloadType(packet.header.payload_type))?;
+        let ssrc = *self
+            .ssrcs
+            .get(&packet.header.ssrc)
+            .ok_or(Error::ErrRtxUnknownSsrc(packet.header.ssrc))?;
+
+        if packet.payload.len() < RTX_OSN_SIZE {
+            return Err(Error::ErrShortPacket);
+        }
+
+        let mut header = packet.header.clone();
+        header.ssrc = ssrc;
+        header.payload_type = payload_type;
+        header.sequence_number = u16::from_be_bytes([packet.payload[0], packet.payload[1]]);
+
+        Ok(Packet {
+            header,
+            payload: packet.payload.slice(RTX_OSN_SIZE..),
+        })
+    }
+}
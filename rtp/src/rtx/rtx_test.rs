@@ -0,0 +1,116 @@
+use bytes::Bytes;
+
+use super::*;
+use crate::header::Header;
+
+fn media_packet(sequence_number: u16) -> Packet {
+    Packet {
+        header: Header {
+            version: 2,
+            marker: true,
+            payload_type: 96,
+            sequence_number,
+            timestamp: 90000,
+            ssrc: 1111,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x01, 0x02, 0x03]),
+    }
+}
+
+#[test]
+fn test_rtx_round_trip() -> Result<()> {
+    let mut encoder = RtxEncoder::default();
+    encoder.add_payload_type(96, 97);
+    encoder.add_stream(1111, 2222, 65535);
+
+    let mut decoder = RtxDecoder::default();
+    decoder.add_payload_type(97, 96);
+    decoder.add_stream(2222, 1111);
+
+    let original = media_packet(1234);
+    let rtx = encoder.encapsulate(&original)?;
+    assert_eq!(rtx.header.ssrc, 2222);
+    assert_eq!(rtx.header.payload_type, 97);
+    assert_eq!(rtx.header.sequence_number, 65535);
+    assert_eq!(rtx.header.timestamp, 90000, "timestamp is kept");
+    assert!(rtx.header.marker, "marker is kept");
+    assert_eq!(&rtx.payload[..], &[0x04, 0xd2, 0x01, 0x02, 0x03]);
+
+    assert!(decoder.is_rtx(&rtx));
+    assert!(!decoder.is_rtx(&original));
+    assert_eq!(decoder.decapsulate(&rtx)?, original);
+
+    // RTX sequence numbers are independent of the original ones
+    let rtx = encoder.encapsulate(&media_packet(10))?;
+    assert_eq!(rtx.header.sequence_number, 0);
+    assert_eq!(decoder.decapsulate(&rtx)?, media_packet(10));
+
+    Ok(())
+}
+
+#[test]
+fn test_rtx_errors() -> Result<()> {
+    let mut encoder = RtxEncoder::default();
+    let original = media_packet(1);
+    assert_eq!(
+        encoder.encapsulate(&original),
+        Err(Error::ErrRtxUnknownPayloadType(96))
+    );
+    encoder.add_payload_type(96, 97);
+    assert_eq!(
+        encoder.encapsulate(&original),
+        Err(Error::ErrRtxUnknownSsrc(1111))
+    );
+    encoder.add_stream(1111, 2222, 0);
+    let rtx = encoder.encapsulate(&original)?;
+    encoder.remove_stream(1111);
+    assert_eq!(
+        encoder.encapsulate(&original),
+        Err(Error::ErrRtxUnknownSsrc(1111))
+    );
+
+    let mut decoder = RtxDecoder::default();
+    decoder.add_payload_type(97, 96);
+    assert_eq!(
+        decoder.decapsulate(&rtx),
+        Err(Error::ErrRtxUnknownSsrc(2222))
+    );
+    decoder.add_stream(2222, 1111);
+
+    let mut truncated = rtx.clone();
+    truncated.payload = Bytes::from_static(&[0x00]);
+    assert_eq!(decoder.decapsulate(&truncated), Err(Error::ErrShortPacket));
+
+    Ok(())
+}
+
+#[test]
+fn test_rtx_padding() -> Result<()> {
+    let mut encoder = RtxEncoder::default();
+    assert_eq!(
+        encoder.padding(1111, 96, 1000),
+        Err(Error::ErrRtxUnknownPayloadType(96))
+    );
+    encoder.add_payload_type(96, 97);
+    encoder.add_stream(1111, 2222, 65535);
+
+    let padding = encoder.padding(1111, 96, 1000)?;
+    assert!(padding.is_padding_only());
+    assert_eq!(padding.header.payload_type, 97);
+    assert_eq!(padding.header.ssrc, 2222);
+    assert_eq!(padding.header.sequence_number, 65535);
+    assert_eq!(padding.header.timestamp, 1000);
+
+    // Padding and retransmissions share the RTX sequence space
+    let rtx = encoder.encapsulate(&media_packet(10))?;
+    assert_eq!(rtx.header.sequence_number, 0);
+
+    let mut decoder = RtxDecoder::default();
+    decoder.add_payload_type(97, 96);
+    decoder.add_stream(2222, 1111);
+    assert!(decoder.is_rtx(&padding));
+    assert_eq!(decoder.decapsulate(&padding), Err(Error::ErrShortPacket));
+
+    Ok(())
+}
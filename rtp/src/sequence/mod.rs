@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod sequence_test;
+
+use std::fmt;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use portable_atomic::{AtomicU16, AtomicU64};
+
+/// Sequencer generates sequential sequence numbers for building RTP packets
+pub trait Sequencer: fmt::Debug {
+    fn next_sequence_number(&self) -> u16;
+    fn roll_over_count(&self) -> u64;
+    fn clone_to(&self) -> Box<dyn Sequencer + Send + Sync>;
+}
+
+impl Clone for Box<dyn Sequencer + Send + Sync> {
+    fn clone(&self) -> Box<dyn Sequencer + Send + Sync> {
+        self.clone_to()
+    }
+}
+
+/// NewRandomSequencer returns a new sequencer starting from a random sequence
+/// number
+pub fn new_random_sequencer() -> impl Sequencer {
+    let c = Counters {
+        sequence_number: Arc::new(AtomicU16::new(rand::random::<u16>())),
+        roll_over_count: Arc::new(AtomicU64::new(0)),
+    };
+    SequencerImpl(c)
+}
+
+/// NewFixedSequencer returns a new sequencer starting from a specific
+/// sequence number
+pub fn new_fixed_sequencer(s: u16) -> impl Sequencer {
+    let sequence_number = if s == 0 { u16::MAX } else { s - 1 };
+
+    let c = Counters {
+        sequence_number: Arc::new(AtomicU16::new(sequence_number)),
+        roll_over_count: Arc::new(AtomicU64::new(0)),
+    };
+
+    SequencerImpl(c)
+}
+
+#[derive(Debug, Clone)]
+struct SequencerImpl(Counters);
+
+#[derive(Debug, Clone)]
+struct Counters {
+    sequence_number: Arc<AtomicU16>,
+    roll_over_count: Arc<AtomicU64>,
+}
+
+impl Sequencer for SequencerImpl {
+    /// NextSequenceNumber increment and returns a new sequence number for
+    /// building RTP packets
+    fn next_sequence_number(&self) -> u16 {
+        // fetch_add wraps around, so concurrent callers never get the same number
+        let sequence_number = self
+            .0
+            .sequence_number
+            .fetch_add(1, Ordering::SeqCst)
+            .wrapping_add(1);
+        if sequence_number == 0 {
+            self.0.roll_over_count.fetch_add(1, Ordering::SeqCst);
+        }
+        sequence_number
+    }
+
+    /// RollOverCount returns the amount of times the 16bit sequence number
+    /// has wrapped
+    fn roll_over_count(&self) -> u64 {
+        self.0.roll_over_count.load(Ordering::SeqCst)
+    }
+
+    fn clone_to(&self) -> Box<dyn Sequencer + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// sequence_number_diff returns how far `a` is ahead of `b`, taking the
+/// 16bit wraparound into account. It is negative when `a` is older than `b`.
+pub fn sequence_number_diff(a: u16, b: u16) -> i16 {
+    a.wrapping_sub(b) as i16
+}
+
+/// is_sequence_number_newer reports whether `a` comes after `b`, taking the
+/// 16bit wraparound into account.
+pub fn is_sequence_number_newer(a: u16, b: u16) -> bool {
+    sequence_number_diff(a, b) > 0
+}
+
+/// timestamp_diff returns how far `a` is ahead of `b`, taking the 32bit
+/// wraparound into account. It is negative when `a` is older than `b`.
+pub fn timestamp_diff(a: u32, b: u32) -> i32 {
+    a.wrapping_sub(b) as i32
+}
+
+/// is_timestamp_newer reports whether `a` comes after `b`, taking the 32bit
+/// wraparound into account.
+pub fn is_timestamp_newer(a: u32, b: u32) -> bool {
+    timestamp_diff(a, b) > 0
+}
+
+/// SequenceNumberUnwrapper extends 16bit sequence numbers to 64bit counters
+/// that keep increasing across wraparounds. Values are unwrapped relative to
+/// the last one, so reordered packets keep their place.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SequenceNumberUnwrapper {
+    last_unwrapped: Option<i64>,
+}
+
+impl SequenceNumberUnwrapper {
+    /// unwrap_sequence_number returns the 64bit counter of `sequence_number`.
+    pub fn unwrap_sequence_number(&mut self, sequence_number: u16) -> i64 {
+        let unwrapped = match self.last_unwrapped {
+            Some(last) => last + sequence_number_diff(sequence_number, last as u16) as i64,
+            None => sequence_number as i64,
+        };
+        self.last_unwrapped = Some(unwrapped);
+        unwrapped
+    }
+}
+
+/// TimestampUnwrapper extends 32bit RTP timestamps to 64bit counters that
+/// keep increasing across wraparounds.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TimestampUnwrapper {
+    last_unwrapped: Option<i64>,
+}
+
+impl TimestampUnwrapper {
+    /// unwrap_timestamp returns the 64bit counter of `timestamp`.
+    pub fn unwrap_timestamp(&mut self, timestamp: u32) -> i64 {
+        let unwrapped = match self.last_unwrapped {
+            Some(last) => last + timestamp_diff(timestamp, last as u32) as i64,
+            None => timestamp as i64,
+        };
+        self.last_unwrapped = Some(unwrapped);
+        unwrapped
+    }
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+#[test]
+fn test_sequencer_roll_over() {
+    let sequencer = new_fixed_sequencer(u16::MAX - 1);
+    assert_eq!(sequencer.next_sequence_number(), u16::MAX - 1);
+    assert_eq!(sequencer.next_sequence_number(), u16::MAX);
+    assert_eq!(sequencer.roll_over_count(), 0);
+    assert_eq!(sequencer.next_sequence_number(), 0);
+    assert_eq!(sequencer.roll_over_count(), 1);
+
+    let sequencer = new_fixed_sequencer(0);
+    assert_eq!(sequencer.next_sequence_number(), 0);
+    assert_eq!(sequencer.roll_over_count(), 1);
+
+    // Clones share their counters
+    let clone = sequencer.clone_to();
+    assert_eq!(clone.next_sequence_number(), 1);
+    assert_eq!(sequencer.next_sequence_number(), 2);
+}
+
+#[test]
+fn test_sequence_number_comparison() {
+    assert_eq!(sequence_number_diff(2, 1), 1);
+    assert_eq!(sequence_number_diff(1, 2), -1);
+    assert_eq!(sequence_number_diff(0, u16::MAX), 1);
+    assert_eq!(sequence_number_diff(u16::MAX, 0), -1);
+
+    assert!(is_sequence_number_newer(1, 0));
+    assert!(is_sequence_number_newer(0, 65500));
+    assert!(!is_sequence_number_newer(65500, 0));
+    assert!(!is_sequence_number_newer(5, 5));
+}
+
+#[test]
+fn test_timestamp_comparison() {
+    assert_eq!(timestamp_diff(3000, 0), 3000);
+    assert_eq!(timestamp_diff(0, u32::MAX - 2999), 3000);
+    assert_eq!(timestamp_diff(u32::MAX - 2999, 0), -3000);
+
+    assert!(is_timestamp_newer(10, u32::MAX));
+    assert!(!is_timestamp_newer(u32::MAX, 10));
+    assert!(!is_timestamp_newer(10, 10));
+}
+
+#[test]
+fn test_sequence_number_unwrapper() {
+    let mut unwrapper = SequenceNumberUnwrapper::default();
+    let tests = vec![
+        (65534, 65534),
+        (65535, 65535),
+        (0, 65536),
+        // Reordered packet from before the wraparound
+        (65533, 65533),
+        (1, 65537),
+        (32768, 98304),
+        (65535, 131071),
+    ];
+    for (sequence_number, expected) in tests {
+        assert_eq!(
+            unwrapper.unwrap_sequence_number(sequence_number),
+            expected,
+            "sequence number {}",
+            sequence_number
+        );
+    }
+
+    // Going back before the first value yields negative counters
+    let mut unwrapper = SequenceNumberUnwrapper::default();
+    assert_eq!(unwrapper.unwrap_sequence_number(0), 0);
+    assert_eq!(unwrapper.unwrap_sequence_number(65535), -1);
+}
+
+#[test]
+fn test_timestamp_unwrapper() {
+    let mut unwrapper = TimestampUnwrapper::default();
+    assert_eq!(
+        unwrapper.unwrap_timestamp(u32::MAX - 1499),
+        u32::MAX as i64 - 1499
+    );
+    assert_eq!(unwrapper.unwrap_timestamp(1500), u32::MAX as i64 + 1501);
+    assert_eq!(unwrapper.unwrap_timestamp(u32::MAX), u32::MAX as i64);
+    assert_eq!(unwrapper.unwrap_timestamp(3000), u32::MAX as i64 + 3001);
+}
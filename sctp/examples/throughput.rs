@@ -64,10 +64,8 @@ fn main() -> Result<(), Error> {
                 println!("listening {}...", conn.local_addr().unwrap());
 
                 let config = Config {
-                    net_conn: Arc::new(conn),
-                    max_receive_buffer_size: 0,
-                    max_message_size: 0,
                     name: "recver".to_owned(),
+                    ..Config::new(Arc::new(conn))
                 };
                 let a = Association::server(config).await?;
                 println!("created a server");
@@ -110,10 +108,8 @@ fn main() -> Result<(), Error> {
                 println!("connecting 127.0.0.1:{port2}..");
 
                 let config = Config {
-                    net_conn: conn,
-                    max_receive_buffer_size: 0,
-                    max_message_size: 0,
                     name: "sender".to_owned(),
+                    ..Config::new(conn)
                 };
                 let a = Association::client(config).await.unwrap();
                 println!("created a client");
@@ -54,6 +54,7 @@ pub struct AssociationInternal {
 
     // Congestion control parameters
     pub(crate) max_receive_buffer_size: u32,
+    pub(crate) max_send_buffer_size: u32,
     pub(crate) cwnd: u32,     // my congestion window size
     rwnd: u32,                // calculated peer's receiver windows size
     pub(crate) ssthresh: u32, // slow start threshold
@@ -68,7 +69,18 @@ pub struct AssociationInternal {
     pub(crate) t2shutdown: Option<RtxTimer<AssociationInternal>>,
     pub(crate) t3rtx: Option<RtxTimer<AssociationInternal>>,
     pub(crate) treconfig: Option<RtxTimer<AssociationInternal>>,
+    pub(crate) theartbeat: Option<RtxTimer<AssociationInternal>>,
     pub(crate) ack_timer: Option<AckTimer<AssociationInternal>>,
+    pub(crate) ack_interval: Duration,
+    pub(crate) heartbeat_interval: Duration,
+
+    // Zero checksum (RFC 9653)
+    pub(crate) enable_zero_checksum: bool,
+    pub(crate) send_zero_checksum: bool,
+
+    // Message interleaving (RFC 8260)
+    pub(crate) enable_message_interleaving: bool,
+    pub(crate) use_interleaving: bool,
 
     // Chunks stored for retransmission
     pub(crate) stored_init: Option<ChunkInit>,
@@ -112,6 +124,18 @@ impl AssociationInternal {
             config.max_message_size
         };
 
+        let ack_interval = if config.ack_interval.is_zero() {
+            ACK_INTERVAL
+        } else {
+            std::cmp::min(config.ack_interval, MAX_ACK_INTERVAL)
+        };
+
+        let heartbeat_interval = if config.heartbeat_interval.is_zero() {
+            HEARTBEAT_INTERVAL
+        } else {
+            config.heartbeat_interval
+        };
+
         let inflight_queue_length = Arc::new(AtomicUsize::new(0));
 
         let mut tsn = random::<u32>();
@@ -121,7 +145,12 @@ impl AssociationInternal {
         let mut a = AssociationInternal {
             name: config.name,
             max_receive_buffer_size,
+            max_send_buffer_size: config.max_send_buffer_size,
             max_message_size: Arc::new(AtomicU32::new(max_message_size)),
+            ack_interval,
+            heartbeat_interval,
+            enable_zero_checksum: config.enable_zero_checksum,
+            enable_message_interleaving: config.enable_message_interleaving,
 
             my_max_num_outbound_streams: u16::MAX,
             my_max_num_inbound_streams: u16::MAX,
@@ -216,6 +245,43 @@ impl AssociationInternal {
         }
     }
 
+    /// caller must hold self.lock
+    fn send_heartbeat(&mut self) {
+        log::debug!("[{}] sending HEARTBEAT", self.name);
+
+        // The heartbeat information is opaque to the peer, which echoes it back
+        // in the HEARTBEAT-ACK. We put the send time in there to measure RTT.
+        let since = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        let outbound = Packet {
+            source_port: self.source_port,
+            destination_port: self.destination_port,
+            verification_tag: self.peer_verification_tag,
+            chunks: vec![Box::new(ChunkHeartbeat {
+                params: vec![Box::new(ParamHeartbeatInfo {
+                    heartbeat_information: Bytes::copy_from_slice(&since.to_be_bytes()),
+                })],
+            })],
+        };
+
+        self.control_queue.push_back(outbound);
+        self.awake_write_loop();
+    }
+
+    /// start_heartbeat_timer (re)arms the heartbeat timer so that the peer is
+    /// probed every RTO + HB.interval (RFC 4960 sec 8.3).
+    pub(crate) async fn start_heartbeat_timer(&self) {
+        if let Some(theartbeat) = &self.theartbeat {
+            theartbeat.stop().await;
+            theartbeat
+                .start(self.heartbeat_interval.as_millis() as u64 + self.rto_mgr.get_rto())
+                .await;
+        }
+    }
+
     pub(crate) async fn close(&mut self) -> Result<()> {
         if self.get_state() != AssociationState::Closed {
             self.set_state(AssociationState::Closed);
@@ -284,6 +350,9 @@ impl AssociationInternal {
         if let Some(treconfig) = &self.treconfig {
             treconfig.stop().await;
         }
+        if let Some(theartbeat) = &self.theartbeat {
+            theartbeat.stop().await;
+        }
         if let Some(ack_timer) = &mut self.ack_timer {
             ack_timer.stop();
         }
@@ -306,12 +375,13 @@ impl AssociationInternal {
                 s.read_notifier.notify_waiters();
             }
             s.write_shutdown.store(true, Ordering::SeqCst);
+            s.write_notifier.notify_waiters();
         }
     }
 
     /// handle_inbound parses incoming raw packets
     pub(crate) async fn handle_inbound(&mut self, raw: &Bytes) -> Result<()> {
-        let p = match Packet::unmarshal(raw) {
+        let p = match Packet::unmarshal_with_zero_checksum(raw, self.enable_zero_checksum) {
             Ok(p) => p,
             Err(err) => {
                 log::warn!("[{}] unable to parse SCTP packet {}", self.name, err);
@@ -419,6 +489,7 @@ impl AssociationInternal {
 
             let mut to_fast_retrans: Vec<Box<dyn Chunk + Send + Sync>> = vec![];
             let mut fast_retrans_size = COMMON_HEADER_SIZE;
+            let data_chunk_header_size = self.data_chunk_header_size();
 
             let mut i = 0;
             loop {
@@ -439,7 +510,7 @@ impl AssociationInternal {
                     //      of cwnd and SHOULD NOT delay retransmission for this single
                     //		packet.
 
-                    let data_chunk_size = DATA_CHUNK_HEADER_SIZE + c.user_data.len() as u32;
+                    let data_chunk_size = data_chunk_header_size + c.user_data.len() as u32;
                     if self.mtu < fast_retrans_size + data_chunk_size {
                         break;
                     }
@@ -498,8 +569,13 @@ impl AssociationInternal {
                 self.advanced_peer_tsn_ack_point,
                 self.cumulative_tsn_ack_point,
             ) {
-                let fwd_tsn = self.create_forward_tsn();
-                let p = self.create_packet(vec![Box::new(fwd_tsn)]);
+                let p = if self.use_interleaving {
+                    let fwd_tsn = self.create_iforward_tsn();
+                    self.create_packet(vec![Box::new(fwd_tsn)])
+                } else {
+                    let fwd_tsn = self.create_forward_tsn();
+                    self.create_packet(vec![Box::new(fwd_tsn)])
+                };
                 raw_packets.push(p);
             }
         }
@@ -559,6 +635,10 @@ impl AssociationInternal {
             }
         }
 
+        // Packets queued by the handshake and inbound handlers are sent as they are; the
+        // ones gathered below are bundled together.
+        let n_control_packets = raw_packets.len();
+
         let state = self.get_state();
         match state {
             AssociationState::Established => {
@@ -569,14 +649,27 @@ impl AssociationInternal {
                 raw_packets = self.gather_outbound_fast_retransmission_packets(raw_packets);
                 raw_packets = self.gather_outbound_sack_packets(raw_packets).await;
                 raw_packets = self.gather_outbound_forward_tsn_packets(raw_packets);
+                raw_packets = self.bundle_outbound_packets(raw_packets, n_control_packets);
                 (raw_packets, true)
             }
-            AssociationState::ShutdownPending
-            | AssociationState::ShutdownSent
-            | AssociationState::ShutdownReceived => {
+            AssociationState::ShutdownPending | AssociationState::ShutdownReceived => {
+                // RFC 4960 sec 9.2
+                //  Data queued before the shutdown was requested (or received) is
+                //  still sent; the SHUTDOWN (ACK) follows once all of it is acked.
+                raw_packets = self.gather_data_packets_to_retransmit(raw_packets);
+                raw_packets = self
+                    .gather_outbound_data_and_reconfig_packets(raw_packets)
+                    .await;
+                raw_packets = self.gather_outbound_fast_retransmission_packets(raw_packets);
+                raw_packets = self.gather_outbound_sack_packets(raw_packets).await;
+                raw_packets = self.bundle_outbound_packets(raw_packets, n_control_packets);
+                self.gather_outbound_shutdown_packets(raw_packets).await
+            }
+            AssociationState::ShutdownSent => {
                 raw_packets = self.gather_data_packets_to_retransmit(raw_packets);
                 raw_packets = self.gather_outbound_fast_retransmission_packets(raw_packets);
                 raw_packets = self.gather_outbound_sack_packets(raw_packets).await;
+                raw_packets = self.bundle_outbound_packets(raw_packets, n_control_packets);
                 self.gather_outbound_shutdown_packets(raw_packets).await
             }
             AssociationState::ShutdownAckSent => {
@@ -586,6 +679,32 @@ impl AssociationInternal {
         }
     }
 
+    /// initiate_shutdown starts the graceful shutdown sequence (RFC 4960 sec 9.2).
+    /// The SHUTDOWN chunk is sent once all queued data has been acknowledged.
+    pub(crate) fn initiate_shutdown(&self) -> Result<()> {
+        let state = self.get_state();
+        if state != AssociationState::Established {
+            return Err(Error::ErrShutdownNonEstablished);
+        }
+
+        self.set_state(AssociationState::ShutdownPending);
+
+        if !self.has_outstanding_data() {
+            // No more outstanding, send shutdown.
+            self.will_send_shutdown.store(true, Ordering::SeqCst);
+            self.set_state(AssociationState::ShutdownSent);
+        }
+        self.awake_write_loop();
+
+        Ok(())
+    }
+
+    /// has_outstanding_data reports whether there is data either waiting to be
+    /// sent or waiting to be acknowledged.
+    fn has_outstanding_data(&self) -> bool {
+        !self.inflight_queue.is_empty() || !self.pending_queue.is_empty()
+    }
+
     /// set_state atomically sets the state of the Association.
     pub(crate) fn set_state(&self, new_state: AssociationState) {
         let old_state = AssociationState::from(self.state.swap(new_state as u8, Ordering::SeqCst));
@@ -633,6 +752,22 @@ impl AssociationInternal {
         self.source_port = p.destination_port;
         self.destination_port = p.source_port;
 
+        self.rwnd = i.advertised_receiver_window_credit;
+        log::debug!("[{}] initial rwnd={}", self.name, self.rwnd);
+
+        // RFC 4690 Sec 7.2.1
+        //  o  The initial value of ssthresh MAY be arbitrarily high (for
+        //     example, implementations MAY use the size of the receiver
+        //     advertised window).
+        self.ssthresh = self.rwnd;
+        log::trace!(
+            "[{}] updated cwnd={} ssthresh={} inflight={} (INI)",
+            self.name,
+            self.cwnd,
+            self.ssthresh,
+            self.inflight_queue.get_num_bytes()
+        );
+
         // 13.2 This is the last TSN received in sequence.  This value
         // is set initially by taking the peer's initial TSN,
         // received in the INIT or INIT ACK chunk, and
@@ -703,7 +838,14 @@ impl AssociationInternal {
             init_ack.params = vec![Box::new(my_cookie.clone())];
         }
 
-        init_ack.set_supported_extensions();
+        init_ack.set_supported_extensions(self.enable_message_interleaving);
+        if self.enable_zero_checksum {
+            init_ack.set_zero_checksum_acceptable();
+            self.send_zero_checksum = i.zero_checksum_acceptable();
+        }
+        if self.enable_message_interleaving && i.supports_interleaving() {
+            self.start_interleaving();
+        }
 
         outbound.chunks = vec![Box::new(init_ack)];
 
@@ -781,6 +923,12 @@ impl AssociationInternal {
         if !self.use_forward_tsn {
             log::warn!("[{}] not using ForwardTSN (on initAck)", self.name);
         }
+        if self.enable_zero_checksum {
+            self.send_zero_checksum = i.zero_checksum_acceptable();
+        }
+        if self.enable_message_interleaving && i.supports_interleaving() {
+            self.start_interleaving();
+        }
 
         if let Some(v) = cookie_param {
             self.stored_cookie_echo = Some(ChunkCookieEcho {
@@ -826,6 +974,67 @@ impl AssociationInternal {
         Ok(vec![])
     }
 
+    /// handle_abort reports the error causes of an ABORT chunk. The returned
+    /// error makes the read loop close the association.
+    fn handle_abort(&self, c: &ChunkAbort) -> Error {
+        let causes = c
+            .error_causes
+            .iter()
+            .map(|cause| {
+                if cause.code == USER_INITIATED_ABORT && !cause.raw.is_empty() {
+                    format!("{}: {}", cause, String::from_utf8_lossy(&cause.raw))
+                } else {
+                    cause.to_string()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        log::warn!("[{}] ABORT received: [{}]", self.name, causes);
+
+        Error::ErrAbortReceived(causes)
+    }
+
+    async fn handle_heartbeat_ack(&mut self, c: &ChunkHeartbeatAck) -> Result<Vec<Packet>> {
+        log::trace!("[{}] chunkHeartbeatAck", self.name);
+        let hbi = match c
+            .params
+            .first()
+            .and_then(|p| p.as_any().downcast_ref::<ParamHeartbeatInfo>())
+        {
+            Some(hbi) => hbi,
+            None => return Ok(vec![]),
+        };
+
+        // Only our own heartbeats carry an 8 byte timestamp
+        if hbi.heartbeat_information.len() != 8 {
+            return Ok(vec![]);
+        }
+        let mut since = [0u8; 8];
+        since.copy_from_slice(&hbi.heartbeat_information);
+        let since = u64::from_be_bytes(since);
+
+        let now = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(now) => now.as_millis() as u64,
+            Err(_) => return Err(Error::ErrInvalidSystemTime),
+        };
+        if now >= since {
+            let rtt = now - since;
+            let srtt = self.rto_mgr.set_new_rtt(rtt);
+            log::trace!(
+                "[{}] HEARTBEAT-ACK: measured-rtt={} srtt={} new-rto={}",
+                self.name,
+                rtt,
+                srtt,
+                self.rto_mgr.get_rto()
+            );
+        }
+
+        // The peer is reachable, start over counting unanswered heartbeats
+        self.start_heartbeat_timer().await;
+
+        Ok(vec![])
+    }
+
     async fn handle_cookie_echo(&mut self, c: &ChunkCookieEcho) -> Result<Vec<Packet>> {
         let state = self.get_state();
         log::debug!("[{}] COOKIE-ECHO received in state '{}'", self.name, state);
@@ -855,6 +1064,7 @@ impl AssociationInternal {
                     self.stored_cookie_echo = None;
 
                     self.set_state(AssociationState::Established);
+                    self.start_heartbeat_timer().await;
                     if let Some(handshake_completed_ch) = &self.handshake_completed_ch_tx {
                         let _ = handshake_completed_ch.send(None).await;
                     }
@@ -891,6 +1101,7 @@ impl AssociationInternal {
         self.stored_cookie_echo = None;
 
         self.set_state(AssociationState::Established);
+        self.start_heartbeat_timer().await;
         if let Some(handshake_completed_ch) = &self.handshake_completed_ch_tx {
             let _ = handshake_completed_ch.send(None).await;
         }
@@ -908,6 +1119,12 @@ impl AssociationInternal {
         );
         self.stats.inc_datas();
 
+        // RFC 8260 Sec 2.2.3: once I-DATA has been negotiated DATA chunks
+        // must not be used, and vice versa.
+        if d.interleaved != self.use_interleaving {
+            return Err(Error::ErrUnexpectedDataChunkType);
+        }
+
         let can_push = self.payload_queue.can_push(d, self.peer_last_tsn);
         let mut stream_handle_data = false;
         if can_push {
@@ -974,7 +1191,9 @@ impl AssociationInternal {
             let rst_reqs: Vec<ParamOutgoingResetRequest> =
                 self.reconfig_requests.values().cloned().collect();
             for rst_req in rst_reqs {
-                self.reset_streams_if_any(&rst_req, false, &mut reply)?;
+                // A deferred reset answers with a reset of our side as well, like one
+                // performed right away does
+                self.reset_streams_if_any(&rst_req, true, &mut reply)?;
             }
         }
 
@@ -1047,6 +1266,7 @@ impl AssociationInternal {
             self.awake_write_loop_ch.clone(),
             Arc::clone(&self.pending_queue),
         ));
+        s.set_max_send_buffer_size(self.max_send_buffer_size as usize);
 
         if accept {
             if let Some(accept_ch) = &self.accept_ch_tx {
@@ -1418,8 +1638,15 @@ impl AssociationInternal {
         }
 
         for (si, n_bytes_acked) in &bytes_acked_per_stream {
-            if let Some(s) = self.streams.get_mut(si) {
-                s.on_buffer_released(*n_bytes_acked).await;
+            if let Some(s) = self.streams.get(si) {
+                if s.release_buffer(*n_bytes_acked) {
+                    // The handler may write more data, which can wait for further SACKs,
+                    // so it must not run on the read loop.
+                    let s = Arc::clone(s);
+                    tokio::spawn(async move {
+                        s.on_buffered_amount_low_event().await;
+                    });
+                }
             }
         }
 
@@ -1495,12 +1722,12 @@ impl AssociationInternal {
             if let Some(t3rtx) = &self.t3rtx {
                 t3rtx.start(self.rto_mgr.get_rto()).await;
             }
-        } else if state == AssociationState::ShutdownPending {
+        } else if state == AssociationState::ShutdownPending && self.pending_queue.is_empty() {
             // No more outstanding, send shutdown.
             should_awake_write_loop = true;
             self.will_send_shutdown.store(true, Ordering::SeqCst);
             self.set_state(AssociationState::ShutdownSent);
-        } else if state == AssociationState::ShutdownReceived {
+        } else if state == AssociationState::ShutdownReceived && self.pending_queue.is_empty() {
             // No more outstanding, send shutdown ack.
             should_awake_write_loop = true;
             self.will_send_shutdown_ack = true;
@@ -1516,7 +1743,7 @@ impl AssociationInternal {
         let state = self.get_state();
 
         if state == AssociationState::Established {
-            if !self.inflight_queue.is_empty() {
+            if self.has_outstanding_data() {
                 self.set_state(AssociationState::ShutdownReceived);
             } else {
                 // No more outstanding, send shutdown ack.
@@ -1610,6 +1837,46 @@ impl AssociationInternal {
         fwd_tsn
     }
 
+    /// create_iforward_tsn generates I-FORWARD-TSN chunk, which replaces
+    /// FORWARD-TSN once message interleaving is in use (RFC 8260 Sec 2.3.1).
+    fn create_iforward_tsn(&self) -> ChunkIForwardTsn {
+        // to report only once per SI and ordering, with the greatest MID
+        let mut stream_map: HashMap<(u16, bool), u32> = HashMap::new();
+        let mut i = self.cumulative_tsn_ack_point + 1;
+        while sna32lte(i, self.advanced_peer_tsn_ack_point) {
+            if let Some(c) = self.inflight_queue.get(i) {
+                let key = (c.stream_identifier, c.unordered);
+                let greater = match stream_map.get(&key) {
+                    Some(mid) => sna32lt(*mid, c.message_identifier),
+                    None => true,
+                };
+                if greater {
+                    stream_map.insert(key, c.message_identifier);
+                }
+            } else {
+                break;
+            }
+
+            i += 1;
+        }
+
+        let mut fwd_tsn = ChunkIForwardTsn {
+            new_cumulative_tsn: self.advanced_peer_tsn_ack_point,
+            streams: vec![],
+        };
+
+        for ((si, unordered), mid) in stream_map {
+            fwd_tsn.streams.push(ChunkIForwardTsnStream {
+                identifier: si,
+                unordered,
+                message_identifier: mid,
+            });
+        }
+        log::trace!("[{}] building i_fwd_tsn: {}", self.name, fwd_tsn);
+
+        fwd_tsn
+    }
+
     /// create_packet wraps chunks in a packet.
     /// The caller should hold the read lock.
     pub(crate) fn create_packet(&self, chunks: Vec<Box<dyn Chunk + Send + Sync>>) -> Packet {
@@ -1637,25 +1904,90 @@ impl AssociationInternal {
         Ok(pp)
     }
 
+    /// Returns an ERROR packet reporting a forward TSN chunk that has not
+    /// been negotiated.
+    fn forward_tsn_not_enabled_packet(&self) -> Packet {
+        let cerr = ChunkError {
+            error_causes: vec![ErrorCauseUnrecognizedChunkType::default()],
+        };
+
+        Packet {
+            verification_tag: self.peer_verification_tag,
+            source_port: self.source_port,
+            destination_port: self.destination_port,
+            chunks: vec![Box::new(cerr)],
+        }
+    }
+
     async fn handle_forward_tsn(&mut self, c: &ChunkForwardTsn) -> Result<Vec<Packet>> {
         log::trace!("[{}] FwdTSN: {}", self.name, c.to_string());
 
-        if !self.use_forward_tsn {
+        if !self.use_forward_tsn || self.use_interleaving {
             log::warn!("[{}] received FwdTSN but not enabled", self.name);
             // Return an error chunk
-            let cerr = ChunkError {
-                error_causes: vec![ErrorCauseUnrecognizedChunkType::default()],
-            };
+            return Ok(vec![self.forward_tsn_not_enabled_packet()]);
+        }
 
-            let outbound = Packet {
-                verification_tag: self.peer_verification_tag,
-                source_port: self.source_port,
-                destination_port: self.destination_port,
-                chunks: vec![Box::new(cerr)],
-            };
-            return Ok(vec![outbound]);
+        if !self.advance_peer_last_tsn_by_forward_tsn(c.new_cumulative_tsn) {
+            return Ok(vec![]);
+        }
+
+        // Report new peer_last_tsn value and abandoned largest SSN value to
+        // corresponding streams so that the abandoned chunks can be removed
+        // from the reassemblyQueue.
+        for forwarded in &c.streams {
+            if let Some(s) = self.streams.get_mut(&forwarded.identifier) {
+                s.handle_forward_tsn_for_ordered(forwarded.sequence).await;
+            }
+        }
+
+        // TSN may be forewared for unordered chunks. ForwardTSN chunk does not
+        // report which stream identifier it skipped for unordered chunks.
+        // Therefore, we need to broadcast this event to all existing streams for
+        // unordered chunks.
+        // See https://github.com/pion/sctp/issues/106
+        for s in self.streams.values_mut() {
+            s.handle_forward_tsn_for_unordered(c.new_cumulative_tsn)
+                .await;
+        }
+
+        self.handle_peer_last_tsn_and_acknowledgement(false)
+    }
+
+    async fn handle_iforward_tsn(&mut self, c: &ChunkIForwardTsn) -> Result<Vec<Packet>> {
+        log::trace!("[{}] I-FwdTSN: {}", self.name, c.to_string());
+
+        if !self.use_forward_tsn || !self.use_interleaving {
+            log::warn!("[{}] received I-FwdTSN but not enabled", self.name);
+            // Return an error chunk
+            return Ok(vec![self.forward_tsn_not_enabled_packet()]);
+        }
+
+        if !self.advance_peer_last_tsn_by_forward_tsn(c.new_cumulative_tsn) {
+            return Ok(vec![]);
         }
 
+        // Unlike FORWARD-TSN, skipped unordered messages are reported per
+        // stream, so fragments of other unordered messages that are still being
+        // interleaved are left alone.
+        for forwarded in &c.streams {
+            if let Some(s) = self.streams.get_mut(&forwarded.identifier) {
+                if forwarded.unordered {
+                    s.handle_iforward_tsn_for_unordered(forwarded.message_identifier)
+                        .await;
+                } else {
+                    s.handle_forward_tsn_for_ordered(forwarded.message_identifier as u16)
+                        .await;
+                }
+            }
+        }
+
+        self.handle_peer_last_tsn_and_acknowledgement(false)
+    }
+
+    /// Moves peer_last_tsn up to new_cumulative_tsn for handle_forward_tsn and
+    /// handle_iforward_tsn. Returns false if the forward TSN is out-of-date.
+    fn advance_peer_last_tsn_by_forward_tsn(&mut self, new_cumulative_tsn: u32) -> bool {
         // From RFC 3758 Sec 3.6:
         //   Note, if the "New Cumulative TSN" value carried in the arrived
         //   FORWARD TSN chunk is found to be behind or at the current cumulative
@@ -1667,17 +1999,17 @@ impl AssociationInternal {
         log::trace!(
             "[{}] should send ack? newCumTSN={} peer_last_tsn={}",
             self.name,
-            c.new_cumulative_tsn,
+            new_cumulative_tsn,
             self.peer_last_tsn
         );
-        if sna32lte(c.new_cumulative_tsn, self.peer_last_tsn) {
+        if sna32lte(new_cumulative_tsn, self.peer_last_tsn) {
             log::trace!("[{}] sending ack on Forward TSN", self.name);
             self.ack_state = AckState::Immediate;
             if let Some(ack_timer) = &mut self.ack_timer {
                 ack_timer.stop();
             }
             self.awake_write_loop();
-            return Ok(vec![]);
+            return false;
         }
 
         // From RFC 3758 Sec 3.6:
@@ -1691,31 +2023,12 @@ impl AssociationInternal {
         //   chunk,
 
         // Advance peer_last_tsn
-        while sna32lt(self.peer_last_tsn, c.new_cumulative_tsn) {
+        while sna32lt(self.peer_last_tsn, new_cumulative_tsn) {
             self.payload_queue.pop(self.peer_last_tsn + 1); // may not exist
             self.peer_last_tsn += 1;
         }
 
-        // Report new peer_last_tsn value and abandoned largest SSN value to
-        // corresponding streams so that the abandoned chunks can be removed
-        // from the reassemblyQueue.
-        for forwarded in &c.streams {
-            if let Some(s) = self.streams.get_mut(&forwarded.identifier) {
-                s.handle_forward_tsn_for_ordered(forwarded.sequence).await;
-            }
-        }
-
-        // TSN may be forewared for unordered chunks. ForwardTSN chunk does not
-        // report which stream identifier it skipped for unordered chunks.
-        // Therefore, we need to broadcast this event to all existing streams for
-        // unordered chunks.
-        // See https://github.com/pion/sctp/issues/106
-        for s in self.streams.values_mut() {
-            s.handle_forward_tsn_for_unordered(c.new_cumulative_tsn)
-                .await;
-        }
-
-        self.handle_peer_last_tsn_and_acknowledgement(false)
+        true
     }
 
     async fn send_reset_request(&mut self, stream_identifier: u16) -> Result<()> {
@@ -1838,13 +2151,22 @@ impl AssociationInternal {
         Ok(())
     }
 
+    /// Removes the chunk peeked with self.pending_queue.peek() from the pending queue.
+    fn pop_pending_data_chunk(&self, peeked: &ChunkPayloadData) -> Option<ChunkPayloadData> {
+        if self.use_interleaving {
+            self.pending_queue.pop_interleaved(peeked)
+        } else {
+            self.pending_queue
+                .pop(peeked.beginning_fragment, peeked.unordered)
+        }
+    }
+
     /// Move the chunk peeked with self.pending_queue.peek() to the inflight_queue.
     async fn move_pending_data_chunk_to_inflight_queue(
         &mut self,
-        beginning_fragment: bool,
-        unordered: bool,
+        peeked: &ChunkPayloadData,
     ) -> Option<ChunkPayloadData> {
-        if let Some(mut c) = self.pending_queue.pop(beginning_fragment, unordered) {
+        if let Some(mut c) = self.pop_pending_data_chunk(peeked) {
             // Mark all fragments are in-flight now
             if c.ending_fragment {
                 c.set_all_inflight();
@@ -1852,6 +2174,7 @@ impl AssociationInternal {
 
             // Assign TSN
             c.tsn = self.generate_next_tsn();
+            c.interleaved = self.use_interleaving;
 
             c.since = SystemTime::now(); // use to calculate RTT and also for maxPacketLifeTime
             c.nsent = 1; // being sent for the first time
@@ -1897,20 +2220,11 @@ impl AssociationInternal {
         //      is 0), the data sender can always have one DATA chunk in flight to
         //      the receiver if allowed by cwnd (see rule B, below).
         while let Some(c) = self.pending_queue.peek() {
-            let (beginning_fragment, unordered, data_len, stream_identifier) = (
-                c.beginning_fragment,
-                c.unordered,
-                c.user_data.len(),
-                c.stream_identifier,
-            );
+            let (data_len, stream_identifier) = (c.user_data.len(), c.stream_identifier);
 
             if data_len == 0 {
                 sis_to_reset.push(stream_identifier);
-                if self
-                    .pending_queue
-                    .pop(beginning_fragment, unordered)
-                    .is_none()
-                {
+                if self.pop_pending_data_chunk(&c).is_none() {
                     log::error!("failed to pop from pending queue");
                 }
                 continue;
@@ -1926,10 +2240,7 @@ impl AssociationInternal {
 
             self.rwnd -= data_len as u32;
 
-            if let Some(chunk) = self
-                .move_pending_data_chunk_to_inflight_queue(beginning_fragment, unordered)
-                .await
-            {
+            if let Some(chunk) = self.move_pending_data_chunk_to_inflight_queue(&c).await {
                 chunks.push(chunk);
             }
         }
@@ -1938,12 +2249,7 @@ impl AssociationInternal {
         if chunks.is_empty() && self.inflight_queue.is_empty() {
             // Send zero window probe
             if let Some(c) = self.pending_queue.peek() {
-                let (beginning_fragment, unordered) = (c.beginning_fragment, c.unordered);
-
-                if let Some(chunk) = self
-                    .move_pending_data_chunk_to_inflight_queue(beginning_fragment, unordered)
-                    .await
-                {
+                if let Some(chunk) = self.move_pending_data_chunk_to_inflight_queue(&c).await {
                     chunks.push(chunk);
                 }
             }
@@ -1966,13 +2272,14 @@ impl AssociationInternal {
             //   single packet.  Furthermore, DATA chunks being retransmitted MAY be
             //   bundled with new DATA chunks, as long as the resulting packet size
             //   does not exceed the path MTU.
-            if bytes_in_packet + c.user_data.len() as u32 > self.mtu {
+            let chunk_size = padded_chunk_size(&c);
+            if !chunks_to_send.is_empty() && bytes_in_packet + chunk_size > self.mtu {
                 packets.push(self.create_packet(chunks_to_send));
                 chunks_to_send = vec![];
                 bytes_in_packet = COMMON_HEADER_SIZE;
             }
 
-            bytes_in_packet += DATA_CHUNK_HEADER_SIZE + c.user_data.len() as u32;
+            bytes_in_packet += chunk_size;
             chunks_to_send.push(Box::new(c));
         }
 
@@ -1983,6 +2290,48 @@ impl AssociationInternal {
         packets
     }
 
+    /// bundle_outbound_packets re-packs the packets gathered from index `from` on, so that
+    /// SACK, FORWARD-TSN, RECONFIG and DATA chunks share packets up to the path MTU instead of
+    /// going out one datagram each. Control chunks are placed ahead of DATA chunks
+    /// (RFC 4960 sec 6.10), and the order within each kind is kept.
+    fn bundle_outbound_packets(&self, mut raw_packets: Vec<Packet>, from: usize) -> Vec<Packet> {
+        if raw_packets.len() <= from + 1 {
+            return raw_packets;
+        }
+
+        let mut control_chunks = vec![];
+        let mut data_chunks = vec![];
+        for p in raw_packets.drain(from..) {
+            for c in p.chunks {
+                if c.as_any().downcast_ref::<ChunkPayloadData>().is_some() {
+                    data_chunks.push(c);
+                } else {
+                    control_chunks.push(c);
+                }
+            }
+        }
+
+        let mut chunks_to_send = vec![];
+        let mut bytes_in_packet = COMMON_HEADER_SIZE;
+        for c in control_chunks.into_iter().chain(data_chunks) {
+            let chunk_size = padded_chunk_size(&*c);
+            if !chunks_to_send.is_empty() && bytes_in_packet + chunk_size > self.mtu {
+                raw_packets.push(self.create_packet(chunks_to_send));
+                chunks_to_send = vec![];
+                bytes_in_packet = COMMON_HEADER_SIZE;
+            }
+
+            bytes_in_packet += chunk_size;
+            chunks_to_send.push(c);
+        }
+
+        if !chunks_to_send.is_empty() {
+            raw_packets.push(self.create_packet(chunks_to_send));
+        }
+
+        raw_packets
+    }
+
     fn check_partial_reliability_status(&self, c: &ChunkPayloadData) {
         if !self.use_forward_tsn {
             return;
@@ -2004,7 +2353,9 @@ impl AssociationInternal {
             let reliability_value = s.reliability_value.load(Ordering::SeqCst);
 
             if reliability_type == ReliabilityType::Rexmit {
-                if c.nsent >= reliability_value {
+                // reliability_value is the number of retransmissions allowed on top
+                // of the first transmission.
+                if c.nsent > reliability_value {
                     c.set_abandoned(true);
                     log::trace!(
                         "[{}] marked as abandoned: tsn={} ppi={} (remix: {})",
@@ -2085,6 +2436,23 @@ impl AssociationInternal {
         self.bundle_data_chunks_into_packets(chunks)
     }
 
+    /// Switches DATA and FORWARD-TSN to their RFC 8260 counterparts once both
+    /// sides announced I-DATA support.
+    fn start_interleaving(&mut self) {
+        log::debug!("[{}] use I-DATA", self.name);
+        self.use_interleaving = true;
+        self.max_payload_size = self.mtu - (COMMON_HEADER_SIZE + IDATA_CHUNK_HEADER_SIZE);
+        self.pending_queue.set_interleaving(true);
+    }
+
+    fn data_chunk_header_size(&self) -> u32 {
+        if self.use_interleaving {
+            IDATA_CHUNK_HEADER_SIZE
+        } else {
+            DATA_CHUNK_HEADER_SIZE
+        }
+    }
+
     /// generate_next_tsn returns the my_next_tsn and increases it. The caller should hold the lock.
     fn generate_next_tsn(&mut self) -> u32 {
         let tsn = self.my_next_tsn;
@@ -2147,12 +2515,14 @@ impl AssociationInternal {
             } else {
                 self.handle_init(p, c).await?
             }
-        } else if chunk_any.downcast_ref::<ChunkAbort>().is_some()
-            || chunk_any.downcast_ref::<ChunkError>().is_some()
-        {
+        } else if let Some(c) = chunk_any.downcast_ref::<ChunkAbort>() {
+            return Err(self.handle_abort(c));
+        } else if chunk_any.downcast_ref::<ChunkError>().is_some() {
             return Err(Error::ErrChunk);
         } else if let Some(c) = chunk_any.downcast_ref::<ChunkHeartbeat>() {
             self.handle_heartbeat(c).await?
+        } else if let Some(c) = chunk_any.downcast_ref::<ChunkHeartbeatAck>() {
+            self.handle_heartbeat_ack(c).await?
         } else if let Some(c) = chunk_any.downcast_ref::<ChunkCookieEcho>() {
             self.handle_cookie_echo(c).await?
         } else if chunk_any.downcast_ref::<ChunkCookieAck>().is_some() {
@@ -2165,6 +2535,8 @@ impl AssociationInternal {
             self.handle_reconfig(c).await?
         } else if let Some(c) = chunk_any.downcast_ref::<ChunkForwardTsn>() {
             self.handle_forward_tsn(c).await?
+        } else if let Some(c) = chunk_any.downcast_ref::<ChunkIForwardTsn>() {
+            self.handle_iforward_tsn(c).await?
         } else if let Some(c) = chunk_any.downcast_ref::<ChunkShutdown>() {
             self.handle_shutdown(c).await?
         } else if let Some(c) = chunk_any.downcast_ref::<ChunkShutdownAck>() {
@@ -2252,6 +2624,13 @@ impl AssociationInternal {
     }
 }
 
+/// padded_chunk_size returns the number of bytes a chunk occupies in a packet, including the
+/// padding to a 4 byte boundary.
+fn padded_chunk_size(c: &dyn Chunk) -> u32 {
+    let len = CHUNK_HEADER_SIZE + c.value_length();
+    (len + get_padding_size(len)) as u32
+}
+
 #[async_trait]
 impl AckTimerObserver for AssociationInternal {
     async fn on_ack_timeout(&mut self) {
@@ -2382,6 +2761,13 @@ impl RtxTimerObserver for AssociationInternal {
                 self.will_retransmit_reconfig = true;
                 self.awake_write_loop();
             }
+
+            RtxTimerId::Heartbeat => {
+                log::debug!("[{}] heartbeat timeout (n_rtos={})", self.name, n_rtos);
+                if self.get_state() == AssociationState::Established {
+                    self.send_heartbeat();
+                }
+            }
         }
     }
 
@@ -2405,7 +2791,14 @@ impl RtxTimerObserver for AssociationInternal {
             }
 
             RtxTimerId::T2Shutdown => {
+                // RFC 4960 sec 9.2
+                //  If this threshold is exceeded, the endpoint should destroy the TCB
+                //  and MUST report the peer endpoint unreachable to the upper layer.
                 log::error!("[{}] retransmission failure: T2-shutdown", self.name);
+                self.accept_ch_tx.take();
+                if let Err(err) = self.close().await {
+                    log::warn!("[{}] failed to close association: {:?}", self.name, err);
+                }
             }
 
             RtxTimerId::T3RTX => {
@@ -2415,6 +2808,24 @@ impl RtxTimerObserver for AssociationInternal {
                 //  * WebRTC spec is not clear how this incident should be reported to ULP
                 log::error!("[{}] retransmission failure: T3-rtx (DATA)", self.name);
             }
+
+            RtxTimerId::Heartbeat => {
+                // RFC 4960 sec 8.1
+                //  An endpoint shall keep a counter on the total number of consecutive
+                //  retransmissions to its peer (this includes retransmissions to all the
+                //  destination transport addresses of the peer if it is multi-homed),
+                //  including unacknowledged HEARTBEAT chunks.
+                log::error!(
+                    "[{}] retransmission failure: heartbeat, peer is unreachable",
+                    self.name
+                );
+                // Closing the accept channel lets the owner of the association
+                // observe the teardown
+                self.accept_ch_tx.take();
+                if let Err(err) = self.close().await {
+                    log::warn!("[{}] failed to close association: {:?}", self.name, err);
+                }
+            }
             _ => {}
         }
     }
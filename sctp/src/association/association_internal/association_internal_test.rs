@@ -363,12 +363,74 @@ async fn test_assoc_create_new_stream() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_check_partial_reliability_status_rexmit() -> Result<()> {
+    let (accept_ch_tx, _accept_ch_rx) = mpsc::channel(ACCEPT_CH_SIZE);
+    let mut a = AssociationInternal {
+        accept_ch_tx: Some(accept_ch_tx),
+        use_forward_tsn: true,
+        ..Default::default()
+    };
+
+    let s = a.create_stream(1, true).unwrap();
+    s.set_reliability_params(false, ReliabilityType::Rexmit, 1);
+
+    let mut c = ChunkPayloadData {
+        stream_identifier: 1,
+        payload_type: PayloadProtocolIdentifier::Binary,
+        ending_fragment: true,
+        user_data: Bytes::from_static(b"ABC"),
+        nsent: 1,
+        ..Default::default()
+    };
+    // A chunk only counts as abandoned once all fragments of its message are in flight
+    c.set_all_inflight();
+
+    a.check_partial_reliability_status(&c);
+    assert!(!c.abandoned(), "first transmission should not be abandoned");
+
+    c.nsent = 2;
+    a.check_partial_reliability_status(&c);
+    assert!(
+        c.abandoned(),
+        "should be abandoned after one retransmission"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_check_partial_reliability_status_dcep_is_reliable() -> Result<()> {
+    let (accept_ch_tx, _accept_ch_rx) = mpsc::channel(ACCEPT_CH_SIZE);
+    let mut a = AssociationInternal {
+        accept_ch_tx: Some(accept_ch_tx),
+        use_forward_tsn: true,
+        ..Default::default()
+    };
+
+    let s = a.create_stream(1, true).unwrap();
+    s.set_reliability_params(false, ReliabilityType::Rexmit, 0);
+
+    let mut c = ChunkPayloadData {
+        stream_identifier: 1,
+        payload_type: PayloadProtocolIdentifier::Dcep,
+        ending_fragment: true,
+        user_data: Bytes::from_static(b"ABC"),
+        nsent: 5,
+        ..Default::default()
+    };
+    c.set_all_inflight();
+
+    a.check_partial_reliability_status(&c);
+    assert!(!c.abandoned(), "DCEP messages must be sent reliably");
+
+    Ok(())
+}
+
 async fn handle_init_test(name: &str, initial_state: AssociationState, expect_err: bool) {
     let mut a = create_association_internal(Config {
-        net_conn: Arc::new(DumbConn {}),
-        max_receive_buffer_size: 0,
-        max_message_size: 0,
         name: "client".to_owned(),
+        ..Config::new(Arc::new(DumbConn {}))
     });
     a.set_state(initial_state);
     let pkt = Packet {
@@ -384,7 +446,7 @@ async fn handle_init_test(name: &str, initial_state: AssociationState, expect_er
         advertised_receiver_window_credit: 512 * 1024,
         ..Default::default()
     };
-    init.set_supported_extensions();
+    init.set_supported_extensions(false);
 
     let result = a.handle_init(&pkt, &init).await;
     if expect_err {
@@ -408,6 +470,11 @@ async fn handle_init_test(name: &str, initial_state: AssociationState, expect_er
     assert_eq!(a.destination_port, pkt.source_port, "{name} should match");
     assert_eq!(a.source_port, pkt.destination_port, "{name} should match");
     assert!(a.use_forward_tsn, "{name} should be set to true");
+    assert_eq!(
+        a.rwnd, init.advertised_receiver_window_credit,
+        "{name} should match"
+    );
+    assert_eq!(a.ssthresh, a.rwnd, "{name} should start in slow start");
 }
 
 #[tokio::test]
@@ -455,10 +522,8 @@ async fn test_assoc_handle_init() -> Result<()> {
 #[tokio::test]
 async fn test_assoc_max_message_size_default() -> Result<()> {
     let mut a = create_association_internal(Config {
-        net_conn: Arc::new(DumbConn {}),
-        max_receive_buffer_size: 0,
-        max_message_size: 0,
         name: "client".to_owned(),
+        ..Config::new(Arc::new(DumbConn {}))
     });
     assert_eq!(
         a.max_message_size.load(Ordering::SeqCst),
@@ -500,10 +565,9 @@ async fn test_assoc_max_message_size_default() -> Result<()> {
 #[tokio::test]
 async fn test_assoc_max_message_size_explicit() -> Result<()> {
     let mut a = create_association_internal(Config {
-        net_conn: Arc::new(DumbConn {}),
-        max_receive_buffer_size: 0,
         max_message_size: 30000,
         name: "client".to_owned(),
+        ..Config::new(Arc::new(DumbConn {}))
     });
 
     assert_eq!(
@@ -542,3 +606,299 @@ async fn test_assoc_max_message_size_explicit() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_assoc_ack_interval() -> Result<()> {
+    let tests = vec![
+        ("default", Duration::ZERO, ACK_INTERVAL),
+        (
+            "explicit",
+            Duration::from_millis(50),
+            Duration::from_millis(50),
+        ),
+        ("capped", Duration::from_secs(1), MAX_ACK_INTERVAL),
+    ];
+
+    for (name, ack_interval, expected) in tests {
+        let a = create_association_internal(Config {
+            name: "client".to_owned(),
+            ack_interval,
+            ..Config::new(Arc::new(DumbConn {}))
+        });
+        assert_eq!(a.ack_interval, expected, "{name} should match");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assoc_handle_init_zero_checksum() -> Result<()> {
+    for (name, enable_zero_checksum, peer_accepts, expected) in [
+        ("both", true, true, true),
+        ("local only", true, false, false),
+        ("peer only", false, true, false),
+    ] {
+        let mut a = create_association_internal(Config {
+            name: "server".to_owned(),
+            enable_zero_checksum,
+            ..Config::new(Arc::new(DumbConn {}))
+        });
+        let pkt = Packet {
+            source_port: 5001,
+            destination_port: 5002,
+            ..Default::default()
+        };
+        let mut init = ChunkInit {
+            initial_tsn: 1234,
+            num_outbound_streams: 1001,
+            num_inbound_streams: 1002,
+            initiate_tag: 5678,
+            advertised_receiver_window_credit: 512 * 1024,
+            ..Default::default()
+        };
+        init.set_supported_extensions(false);
+        if peer_accepts {
+            init.set_zero_checksum_acceptable();
+        }
+
+        let packets = a.handle_init(&pkt, &init).await?;
+        assert_eq!(a.send_zero_checksum, expected, "{name} should match");
+
+        let init_ack = packets[0].chunks[0]
+            .as_any()
+            .downcast_ref::<ChunkInit>()
+            .unwrap();
+        assert_eq!(
+            init_ack.zero_checksum_acceptable(),
+            enable_zero_checksum,
+            "{name} INIT ACK should announce acceptance"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assoc_handle_init_interleaving() -> Result<()> {
+    for (name, enable_message_interleaving, peer_supports, expected) in [
+        ("both", true, true, true),
+        ("local only", true, false, false),
+        ("peer only", false, true, false),
+    ] {
+        let mut a = create_association_internal(Config {
+            name: "server".to_owned(),
+            enable_message_interleaving,
+            ..Config::new(Arc::new(DumbConn {}))
+        });
+        let pkt = Packet {
+            source_port: 5001,
+            destination_port: 5002,
+            ..Default::default()
+        };
+        let mut init = ChunkInit {
+            initial_tsn: 1234,
+            num_outbound_streams: 1001,
+            num_inbound_streams: 1002,
+            initiate_tag: 5678,
+            advertised_receiver_window_credit: 512 * 1024,
+            ..Default::default()
+        };
+        init.set_supported_extensions(peer_supports);
+
+        let packets = a.handle_init(&pkt, &init).await?;
+        assert_eq!(a.use_interleaving, expected, "{name} should match");
+
+        let init_ack = packets[0].chunks[0]
+            .as_any()
+            .downcast_ref::<ChunkInit>()
+            .unwrap();
+        assert_eq!(
+            init_ack.supports_interleaving(),
+            enable_message_interleaving,
+            "{name} INIT ACK should announce I-DATA"
+        );
+
+        // The larger I-DATA header must still fit in the MTU
+        let header_size = if expected {
+            IDATA_CHUNK_HEADER_SIZE
+        } else {
+            DATA_CHUNK_HEADER_SIZE
+        };
+        assert_eq!(
+            a.max_payload_size + COMMON_HEADER_SIZE + header_size,
+            a.mtu,
+            "{name} max payload size should match"
+        );
+
+        // DATA and I-DATA chunks must not be mixed
+        let result = a
+            .handle_data(&ChunkPayloadData {
+                tsn: 1234,
+                beginning_fragment: true,
+                ending_fragment: true,
+                interleaved: !expected,
+                user_data: Bytes::from_static(b"ABC"),
+                ..Default::default()
+            })
+            .await;
+        assert!(
+            matches!(result, Err(Error::ErrUnexpectedDataChunkType)),
+            "{name} should reject the other data chunk type"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assoc_handle_heartbeat_ack() -> Result<()> {
+    let mut a = create_association_internal(Config {
+        name: "client".to_owned(),
+        ..Config::new(Arc::new(DumbConn {}))
+    });
+    assert_eq!(a.heartbeat_interval, HEARTBEAT_INTERVAL);
+
+    // Heartbeat information we didn't generate is ignored
+    let foreign = ChunkHeartbeatAck {
+        params: vec![Box::new(ParamHeartbeatInfo {
+            heartbeat_information: Bytes::from_static(b"foreign"),
+        })],
+    };
+    let packets = a.handle_heartbeat_ack(&foreign).await?;
+    assert!(packets.is_empty());
+    assert_eq!(a.rto_mgr.srtt, 0, "foreign heartbeat should not update rtt");
+
+    let since = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+        - 100;
+    let ack = ChunkHeartbeatAck {
+        params: vec![Box::new(ParamHeartbeatInfo {
+            heartbeat_information: Bytes::copy_from_slice(&since.to_be_bytes()),
+        })],
+    };
+    let packets = a.handle_heartbeat_ack(&ack).await?;
+    assert!(packets.is_empty());
+    assert!(
+        a.rto_mgr.srtt >= 100 && a.rto_mgr.srtt < 1000,
+        "srtt should be measured from the heartbeat, got {}",
+        a.rto_mgr.srtt
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assoc_initiate_shutdown() -> Result<()> {
+    let a = create_association_internal(Config {
+        name: "client".to_owned(),
+        ..Config::new(Arc::new(DumbConn {}))
+    });
+
+    // Shutdown is only possible once established
+    let result = a.initiate_shutdown();
+    assert!(matches!(result, Err(Error::ErrShutdownNonEstablished)));
+
+    a.set_state(AssociationState::Established);
+    a.pending_queue
+        .push(ChunkPayloadData {
+            beginning_fragment: true,
+            ending_fragment: true,
+            user_data: Bytes::from_static(b"ABC"),
+            ..Default::default()
+        })
+        .await;
+
+    // Queued data must be drained before SHUTDOWN is sent
+    a.initiate_shutdown()?;
+    assert_eq!(a.get_state(), AssociationState::ShutdownPending);
+    assert!(!a.will_send_shutdown.load(Ordering::SeqCst));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assoc_handle_abort() -> Result<()> {
+    let a = create_association_internal(Config {
+        name: "client".to_owned(),
+        ..Config::new(Arc::new(DumbConn {}))
+    });
+
+    let abort = ChunkAbort {
+        error_causes: vec![
+            ErrorCause {
+                code: USER_INITIATED_ABORT,
+                raw: Bytes::from_static(b"bye"),
+            },
+            ErrorCause {
+                code: PROTOCOL_VIOLATION,
+                ..Default::default()
+            },
+        ],
+    };
+    assert_eq!(
+        a.handle_abort(&abort),
+        Error::ErrAbortReceived("User Initiated Abort: bye, Protocol Violation".to_owned())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_assoc_bundle_outbound_packets() -> Result<()> {
+    let a = create_association_internal(Config {
+        name: "client".to_owned(),
+        ..Config::new(Arc::new(DumbConn {}))
+    });
+
+    let data = |tsn: u32, len: usize| ChunkPayloadData {
+        tsn,
+        beginning_fragment: true,
+        ending_fragment: true,
+        user_data: Bytes::from(vec![0u8; len]),
+        ..Default::default()
+    };
+
+    let raw_packets = vec![
+        a.create_packet(vec![Box::new(ChunkCookieAck {})]),
+        a.create_packet(vec![Box::new(data(1, 100))]),
+        a.create_packet(vec![Box::new(data(2, 101))]),
+        a.create_packet(vec![Box::new(ChunkSelectiveAck::default())]),
+    ];
+
+    // Small chunks share a single packet, with the SACK ahead of the DATA
+    let packets = a.bundle_outbound_packets(raw_packets, 1);
+    assert_eq!(packets.len(), 2, "should bundle into one packet");
+    assert!(packets[0].chunks[0]
+        .as_any()
+        .downcast_ref::<ChunkCookieAck>()
+        .is_some());
+    let chunks = &packets[1].chunks;
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks[0]
+        .as_any()
+        .downcast_ref::<ChunkSelectiveAck>()
+        .is_some());
+    for (i, c) in chunks[1..].iter().enumerate() {
+        let c = c.as_any().downcast_ref::<ChunkPayloadData>().unwrap();
+        assert_eq!(c.tsn, i as u32 + 1, "DATA chunks should keep their order");
+    }
+
+    // Packets never grow beyond the MTU
+    let raw_packets = (1..=4)
+        .map(|tsn| a.create_packet(vec![Box::new(data(tsn, 500))]))
+        .collect();
+    let packets = a.bundle_outbound_packets(raw_packets, 0);
+    assert_eq!(
+        packets.len(),
+        2,
+        "two DATA chunks should fit in each packet"
+    );
+    for p in &packets {
+        assert_eq!(p.chunks.len(), 2);
+        assert!(p.marshal()?.len() <= a.mtu as usize);
+    }
+
+    Ok(())
+}
@@ -31,10 +31,9 @@ async fn create_new_association_pair(
     // Setup client
     tokio::spawn(async move {
         let client = Association::client(Config {
-            net_conn: ca,
             max_receive_buffer_size: recv_buf_size,
-            max_message_size: 0,
             name: "client".to_owned(),
+            ..Config::new(ca)
         })
         .await;
 
@@ -47,10 +46,9 @@ async fn create_new_association_pair(
     // Setup server
     tokio::spawn(async move {
         let server = Association::server(Config {
-            net_conn: cb,
             max_receive_buffer_size: recv_buf_size,
-            max_message_size: 0,
             name: "server".to_owned(),
+            ..Config::new(cb)
         })
         .await;
 
@@ -2031,6 +2029,13 @@ async fn test_assoc_reset_close_both_ways() -> Result<()> {
         }
     }
 
+    // s1 only hit EOF once the reset from a0 unregistered it, so the stream
+    // identifier is available again on a1.
+    let s2 = a1
+        .open_stream(SI, PayloadProtocolIdentifier::Binary)
+        .await?;
+    assert_eq!(s2.stream_identifier(), SI, "unexpected stream identifier");
+
     close_association_pair(&br, a0, a1).await;
 
     Ok(())
@@ -2096,6 +2101,195 @@ async fn test_assoc_abort() -> Result<()> {
     Ok(())
 }
 
+#[cfg(not(target_os = "windows"))] // this times out in CI on windows.
+#[tokio::test]
+async fn test_assoc_heartbeat() -> Result<()> {
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, a1) =
+        create_new_association_pair(&br, Arc::new(ca), Arc::new(cb), AckMode::NoDelay, 0).await?;
+    {
+        let mut a = a0.association_internal.lock().await;
+        a.rto_mgr.set_rto(10, true);
+        a.heartbeat_interval = Duration::from_millis(10);
+        a.start_heartbeat_timer().await;
+    }
+
+    // Heartbeats are acknowledged while the bridge delivers packets
+    for _ in 0..30 {
+        br.tick().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(a0.get_state(), AssociationState::Established);
+    assert_eq!(a1.get_state(), AssociationState::Established);
+
+    // Without any HEARTBEAT-ACK the peer is declared unreachable
+    let timer = tokio::time::sleep(Duration::from_secs(5));
+    tokio::pin!(timer);
+    tokio::select! {
+        _ = timer.as_mut() => panic!("heartbeat failure should close the association"),
+        s = a0.accept_stream() => assert!(s.is_none(), "no stream should be accepted"),
+    };
+    assert_eq!(a0.get_state(), AssociationState::Closed);
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assoc_close_drains_pending_data() -> Result<()> {
+    const SI: u16 = 1;
+    const MSG_SIZE: usize = 1000;
+    const N_MSGS: u8 = 8;
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (mut a0, mut a1) =
+        create_new_association_pair(&br, Arc::new(ca), Arc::new(cb), AckMode::NoDelay, 0).await?;
+    let (s0, s1) = establish_session_pair(&br, &a0, &mut a1, SI).await?;
+
+    // Opt in to the graceful shutdown, as Config::shutdown_timeout would
+    a0.shutdown_timeout = Duration::from_secs(3);
+
+    // More data than the initial cwnd allows, so some of it is still pending on close
+    for i in 0..N_MSGS {
+        s0.write_sctp(
+            &Bytes::from(vec![i; MSG_SIZE]),
+            PayloadProtocolIdentifier::Binary,
+        )
+        .await?;
+    }
+
+    let a0 = Arc::new(a0);
+    let closing = {
+        let a0 = Arc::clone(&a0);
+        tokio::spawn(async move { a0.close().await })
+    };
+    for _ in 0..300 {
+        if closing.is_finished() {
+            break;
+        }
+        br.tick().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(closing.is_finished(), "close should complete");
+    closing.await.unwrap()?;
+    br.process().await;
+
+    // There is a little delay before changing the state to closed
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    assert_eq!(a0.get_state(), AssociationState::Closed);
+    assert_eq!(a1.get_state(), AssociationState::Closed);
+
+    // Everything written before close() was delivered
+    let mut q = s1.reassembly_queue.lock().await;
+    let mut buf = vec![0u8; MSG_SIZE];
+    for i in 0..N_MSGS {
+        let (n, ppi) = q.read(&mut buf)?;
+        assert_eq!(n, MSG_SIZE, "unexpected length of received data");
+        assert_eq!(ppi, PayloadProtocolIdentifier::Binary, "unexpected ppi");
+        assert!(buf.iter().all(|b| *b == i), "received data mismatch");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assoc_abort_with_reason() -> Result<()> {
+    const SI: u16 = 1;
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, mut a1) =
+        create_new_association_pair(&br, Arc::new(ca), Arc::new(cb), AckMode::NoDelay, 0).await?;
+    let (_s0, _s1) = establish_session_pair(&br, &a0, &mut a1, SI).await?;
+
+    a0.abort("bye").await?;
+    assert_eq!(a0.get_state(), AssociationState::Closed);
+
+    flush_buffers(&br, &a0, &a1).await;
+
+    // There is a little delay before changing the state to closed
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    // The peer tears down its side as soon as it sees the ABORT
+    assert_eq!(a1.get_state(), AssociationState::Closed);
+
+    // Aborting an already closed association is a no-op
+    a0.abort("bye again").await?;
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_assoc_buffered_amount_low_handler_can_write() -> Result<()> {
+    const SI: u16 = 1;
+    const MSG_SIZE: usize = 1000;
+    let (br, ca, cb) = Bridge::new(0, None, None);
+
+    let (a0, mut a1) =
+        create_new_association_pair(&br, Arc::new(ca), Arc::new(cb), AckMode::NoDelay, 0).await?;
+    let (s0, _s1) = establish_session_pair(&br, &a0, &mut a1, SI).await?;
+
+    s0.set_max_send_buffer_size(2 * MSG_SIZE);
+    s0.set_buffered_amount_low_threshold(MSG_SIZE);
+
+    // The handler writes more than the send buffer has room for, so its writes can only
+    // complete once further SACKs have been handled.
+    let (done_tx, mut done_rx) = mpsc::channel::<Result<()>>(1);
+    let n_cbs = Arc::new(AtomicU32::new(0));
+    let s = Arc::clone(&s0);
+    s0.on_buffered_amount_low(Box::new(move || {
+        let s = Arc::clone(&s);
+        let done_tx = done_tx.clone();
+        let first = n_cbs.fetch_add(1, Ordering::SeqCst) == 0;
+        Box::pin(async move {
+            if !first {
+                return;
+            }
+            let mut result = Ok(());
+            for _ in 0..2 {
+                if let Err(err) = s
+                    .write_sctp(
+                        &Bytes::from(vec![0u8; 3 * MSG_SIZE / 2]),
+                        PayloadProtocolIdentifier::Binary,
+                    )
+                    .await
+                {
+                    result = Err(err);
+                    break;
+                }
+            }
+            let _ = done_tx.send(result).await;
+        })
+    }));
+
+    for _ in 0..2 {
+        s0.write_sctp(
+            &Bytes::from(vec![0u8; MSG_SIZE]),
+            PayloadProtocolIdentifier::Binary,
+        )
+        .await?;
+    }
+
+    let mut result = None;
+    for _ in 0..100 {
+        br.tick().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        if let Ok(r) = done_rx.try_recv() {
+            result = Some(r);
+            break;
+        }
+    }
+    assert_eq!(result, Some(Ok(())), "handler writes should complete");
+
+    close_association_pair(&br, a0, a1).await;
+
+    Ok(())
+}
+
 struct FakeEchoConn {
     wr_tx: Mutex<mpsc::Sender<Vec<u8>>>,
     rd_rx: Mutex<mpsc::Receiver<Vec<u8>>>,
@@ -2199,10 +2393,8 @@ async fn test_stats() -> Result<()> {
 
     let conn = Arc::new(FakeEchoConn::type_erased());
     let a = Association::client(Config {
-        net_conn: Arc::clone(&conn) as Arc<dyn Conn + Send + Sync>,
-        max_receive_buffer_size: 0,
-        max_message_size: 0,
         name: "client".to_owned(),
+        ..Config::new(Arc::clone(&conn) as Arc<dyn Conn + Send + Sync>)
     })
     .await?;
 
@@ -2234,10 +2426,8 @@ async fn create_assocs() -> Result<(Association, Association)> {
 
     tokio::spawn(async move {
         let a = Association::client(Config {
-            net_conn: Arc::new(udp1),
-            max_receive_buffer_size: 0,
-            max_message_size: 0,
             name: "client".to_owned(),
+            ..Config::new(Arc::new(udp1))
         })
         .await?;
 
@@ -2248,10 +2438,8 @@ async fn create_assocs() -> Result<(Association, Association)> {
 
     tokio::spawn(async move {
         let a = Association::server(Config {
-            net_conn: Arc::new(udp2),
-            max_receive_buffer_size: 0,
-            max_message_size: 0,
             name: "server".to_owned(),
+            ..Config::new(Arc::new(udp2))
         })
         .await?;
 
@@ -2592,10 +2780,8 @@ async fn test_association_handle_packet_before_init() -> Result<()> {
 
         let (a, _) = Association::new(
             Config {
-                net_conn: Arc::new(a_conn),
-                max_message_size: 0,
-                max_receive_buffer_size: 0,
                 name: "client".to_owned(),
+                ..Config::new(Arc::new(a_conn))
             },
             true,
         )
@@ -2614,3 +2800,60 @@ async fn test_association_handle_packet_before_init() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_association_message_interleaving() -> Result<()> {
+    let (ca, cb) = pipe();
+
+    let (a1, a2) = tokio::join!(
+        Association::client(Config {
+            name: "client".to_owned(),
+            enable_message_interleaving: true,
+            ..Config::new(Arc::new(ca))
+        }),
+        Association::server(Config {
+            name: "server".to_owned(),
+            enable_message_interleaving: true,
+            ..Config::new(Arc::new(cb))
+        }),
+    );
+    let (a1, a2) = (a1?, a2?);
+
+    for a in [&a1, &a2] {
+        let ai = a.association_internal.lock().await;
+        assert!(ai.use_interleaving, "{} should use I-DATA", ai.name);
+    }
+
+    let s11 = a1.open_stream(1, PayloadProtocolIdentifier::Binary).await?;
+    let s12 = a1.open_stream(2, PayloadProtocolIdentifier::String).await?;
+    let s21 = a2.open_stream(1, PayloadProtocolIdentifier::Binary).await?;
+    let s22 = a2.open_stream(2, PayloadProtocolIdentifier::String).await?;
+
+    // A message spanning many fragments, queued before a small one on
+    // another stream.
+    let large: Bytes = (0..32 * 1024).map(|i| i as u8).collect::<Vec<u8>>().into();
+    let small = Bytes::from_static(b"small");
+
+    s11.write_sctp(&large, PayloadProtocolIdentifier::Binary)
+        .await?;
+    s12.write_sctp(&small, PayloadProtocolIdentifier::String)
+        .await?;
+
+    let mut buf = vec![0u8; 64 * 1024];
+    let (n, ppi) = tokio::time::timeout(Duration::from_secs(5), s22.read_sctp(&mut buf))
+        .await
+        .expect("timed out reading the small message")?;
+    assert_eq!(&buf[..n], &small[..], "small message should match");
+    assert_eq!(ppi, PayloadProtocolIdentifier::String, "ppi should match");
+
+    let (n, ppi) = tokio::time::timeout(Duration::from_secs(5), s21.read_sctp(&mut buf))
+        .await
+        .expect("timed out reading the large message")?;
+    assert_eq!(&buf[..n], &large[..], "large message should match");
+    assert_eq!(ppi, PayloadProtocolIdentifier::Binary, "ppi should match");
+
+    a1.close().await?;
+    a2.close().await?;
+
+    Ok(())
+}
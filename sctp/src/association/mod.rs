@@ -8,7 +8,7 @@ use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use association_internal::*;
 use association_stats::*;
@@ -23,8 +23,10 @@ use crate::chunk::chunk_cookie_ack::ChunkCookieAck;
 use crate::chunk::chunk_cookie_echo::ChunkCookieEcho;
 use crate::chunk::chunk_error::ChunkError;
 use crate::chunk::chunk_forward_tsn::{ChunkForwardTsn, ChunkForwardTsnStream};
+use crate::chunk::chunk_header::CHUNK_HEADER_SIZE;
 use crate::chunk::chunk_heartbeat::ChunkHeartbeat;
 use crate::chunk::chunk_heartbeat_ack::ChunkHeartbeatAck;
+use crate::chunk::chunk_iforward_tsn::{ChunkIForwardTsn, ChunkIForwardTsnStream};
 use crate::chunk::chunk_init::ChunkInit;
 use crate::chunk::chunk_payload_data::{ChunkPayloadData, PayloadProtocolIdentifier};
 use crate::chunk::chunk_reconfig::ChunkReconfig;
@@ -58,7 +60,10 @@ pub(crate) const INITIAL_MTU: u32 = 1228;
 pub(crate) const INITIAL_RECV_BUF_SIZE: u32 = 1024 * 1024;
 pub(crate) const COMMON_HEADER_SIZE: u32 = 12;
 pub(crate) const DATA_CHUNK_HEADER_SIZE: u32 = 16;
+pub(crate) const IDATA_CHUNK_HEADER_SIZE: u32 = 20;
 pub(crate) const DEFAULT_MAX_MESSAGE_SIZE: u32 = 65536;
+/// HB.interval recommended by RFC 4960 sec 15
+pub(crate) const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
 
 /// other constants
 pub(crate) const ACCEPT_CH_SIZE: usize = 16;
@@ -116,6 +121,7 @@ pub(crate) enum RtxTimerId {
     T2Shutdown,
     T3RTX,
     Reconfig,
+    Heartbeat,
 }
 
 impl fmt::Display for RtxTimerId {
@@ -126,6 +132,7 @@ impl fmt::Display for RtxTimerId {
             RtxTimerId::T2Shutdown => "T2Shutdown",
             RtxTimerId::T3RTX => "T3RTX",
             RtxTimerId::Reconfig => "Reconfig",
+            RtxTimerId::Heartbeat => "Heartbeat",
         };
         write!(f, "{s}")
     }
@@ -177,7 +184,52 @@ pub struct Config {
     pub net_conn: Arc<dyn Conn + Send + Sync>,
     pub max_receive_buffer_size: u32,
     pub max_message_size: u32,
+    /// max_send_buffer_size limits how many bytes each stream may have
+    /// buffered for sending, whether queued or awaiting acknowledgement.
+    /// Writes wait once the limit is reached. Zero, the default, means
+    /// unlimited.
+    pub max_send_buffer_size: u32,
     pub name: String,
+    /// ack_interval is the delayed ack timeout, capped at 500 ms per RFC 4960
+    /// sec 6.2. Zero selects the default of 200 ms.
+    pub ack_interval: Duration,
+    /// heartbeat_interval is how often an established association probes the
+    /// peer with a HEARTBEAT (RFC 4960 sec 8.3). The association is closed
+    /// once 5 consecutive heartbeats go unanswered. Zero selects the default
+    /// of 30 s.
+    pub heartbeat_interval: Duration,
+    /// enable_zero_checksum announces and accepts the zero checksum extension
+    /// (RFC 9653). Only enable it when the conn is a DTLS connection, which
+    /// already detects corrupted packets.
+    pub enable_zero_checksum: bool,
+    /// enable_message_interleaving announces support for I-DATA chunks
+    /// (RFC 8260). When the peer supports them too, fragments of messages on
+    /// different streams are interleaved so that a large message doesn't
+    /// hold back the other streams.
+    pub enable_message_interleaving: bool,
+    /// shutdown_timeout is how long close() waits for the graceful shutdown
+    /// sequence to deliver queued data before aborting the association. Zero,
+    /// the default, skips the graceful shutdown and closes right away.
+    pub shutdown_timeout: Duration,
+}
+
+impl Config {
+    /// new creates a Config for `net_conn` with every other field set to its default.
+    /// Use it as the base of a struct update to only set the fields of interest.
+    pub fn new(net_conn: Arc<dyn Conn + Send + Sync>) -> Self {
+        Config {
+            net_conn,
+            max_receive_buffer_size: 0,
+            max_message_size: 0,
+            max_send_buffer_size: 0,
+            name: String::new(),
+            ack_interval: Duration::ZERO,
+            heartbeat_interval: Duration::ZERO,
+            enable_zero_checksum: false,
+            enable_message_interleaving: false,
+            shutdown_timeout: Duration::ZERO,
+        }
+    }
 }
 
 ///Association represents an SCTP association
@@ -201,14 +253,12 @@ pub struct Association {
     name: String,
     state: Arc<AtomicU8>,
     max_message_size: Arc<AtomicU32>,
-    inflight_queue_length: Arc<AtomicUsize>,
-    will_send_shutdown: Arc<AtomicBool>,
-    awake_write_loop_ch: Arc<mpsc::Sender<()>>,
     close_loop_ch_rx: Mutex<broadcast::Receiver<()>>,
     accept_ch_rx: Mutex<mpsc::Receiver<Arc<Stream>>>,
     net_conn: Arc<dyn Conn + Send + Sync>,
     bytes_received: Arc<AtomicUsize>,
     bytes_sent: Arc<AtomicUsize>,
+    shutdown_timeout: Duration,
 
     pub(crate) association_internal: Arc<Mutex<AssociationInternal>>,
 }
@@ -216,32 +266,39 @@ pub struct Association {
 impl Association {
     /// server accepts a SCTP stream over a conn
     pub async fn server(config: Config) -> Result<Self> {
-        let (a, mut handshake_completed_ch_rx) = Association::new(config, false).await?;
-
-        if let Some(err_opt) = handshake_completed_ch_rx.recv().await {
-            if let Some(err) = err_opt {
-                Err(err)
-            } else {
-                Ok(a)
-            }
-        } else {
-            Err(Error::ErrAssociationHandshakeClosed)
-        }
+        Association::handshake(config, false).await
     }
 
     /// Client opens a SCTP stream over a conn
     pub async fn client(config: Config) -> Result<Self> {
-        let (a, mut handshake_completed_ch_rx) = Association::new(config, true).await?;
+        Association::handshake(config, true).await
+    }
 
-        if let Some(err_opt) = handshake_completed_ch_rx.recv().await {
-            if let Some(err) = err_opt {
-                Err(err)
-            } else {
-                Ok(a)
+    /// handshake creates the association and waits for the INIT/INIT-ACK and
+    /// COOKIE-ECHO/COOKIE-ACK exchange to complete. If the handshake fails the
+    /// timers and the read/write loops are stopped, leaving the underlying conn
+    /// (e.g. the DTLS connection) open to its owner.
+    async fn handshake(config: Config, is_client: bool) -> Result<Self> {
+        let (a, mut handshake_completed_ch_rx) = Association::new(config, is_client).await?;
+
+        let err = match handshake_completed_ch_rx.recv().await {
+            Some(None) => return Ok(a),
+            Some(Some(err)) => err,
+            None => Error::ErrAssociationHandshakeClosed,
+        };
+
+        {
+            let mut ai = a.association_internal.lock().await;
+            if let Err(close_err) = ai.close().await {
+                log::debug!(
+                    "[{}] failed to close association after handshake failure: {}",
+                    a.name,
+                    close_err
+                );
             }
-        } else {
-            Err(Error::ErrAssociationHandshakeClosed)
         }
+
+        Err(err)
     }
 
     /// Shutdown initiates the shutdown sequence. The method blocks until the
@@ -250,19 +307,10 @@ impl Association {
     pub async fn shutdown(&self) -> Result<()> {
         log::debug!("[{}] closing association..", self.name);
 
-        let state = self.get_state();
-        if state != AssociationState::Established {
-            return Err(Error::ErrShutdownNonEstablished);
-        }
-
         // Attempt a graceful shutdown.
-        self.set_state(AssociationState::ShutdownPending);
-
-        if self.inflight_queue_length.load(Ordering::SeqCst) == 0 {
-            // No more outstanding, send shutdown.
-            self.will_send_shutdown.store(true, Ordering::SeqCst);
-            let _ = self.awake_write_loop_ch.try_send(());
-            self.set_state(AssociationState::ShutdownSent);
+        {
+            let ai = self.association_internal.lock().await;
+            ai.initiate_shutdown()?;
         }
 
         {
@@ -273,18 +321,66 @@ impl Association {
         Ok(())
     }
 
-    /// Close ends the SCTP Association and cleans up any state
+    /// Close ends the SCTP Association and cleans up any state. When
+    /// Config::shutdown_timeout is set, an established association is shut
+    /// down gracefully first, so that queued data reaches the peer. If the
+    /// shutdown sequence doesn't complete in time, the association is aborted
+    /// instead.
     pub async fn close(&self) -> Result<()> {
         log::debug!("[{}] closing association..", self.name);
 
+        if !self.shutdown_timeout.is_zero() && self.get_state() == AssociationState::Established {
+            match tokio::time::timeout(self.shutdown_timeout, self.shutdown()).await {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => {
+                    log::debug!("[{}] failed to shut down association: {}", self.name, err);
+                }
+                Err(_) => {
+                    log::warn!("[{}] shutdown timed out, aborting association", self.name);
+                    if let Err(err) = self.abort("shutdown timed out").await {
+                        log::warn!("[{}] failed to abort association: {}", self.name, err);
+                    }
+                }
+            }
+        }
+
         let _ = self.net_conn.close().await;
 
         let mut ai = self.association_internal.lock().await;
         ai.close().await
     }
 
+    /// Abort ends the SCTP Association immediately (RFC 4960 sec 9.1). The peer
+    /// is sent an ABORT chunk with a User-Initiated Abort cause carrying the
+    /// given reason, and any data not yet delivered is discarded.
+    pub async fn abort(&self, reason: &str) -> Result<()> {
+        log::debug!("[{}] aborting association: {}", self.name, reason);
+
+        if self.get_state() == AssociationState::Closed {
+            return Ok(());
+        }
+
+        let mut ai = self.association_internal.lock().await;
+
+        let abort = ChunkAbort {
+            error_causes: vec![ErrorCause {
+                code: USER_INITIATED_ABORT,
+                raw: Bytes::copy_from_slice(reason.as_bytes()),
+            }],
+        };
+        let mut raw = BytesMut::new();
+        ai.create_packet(vec![Box::new(abort)])
+            .marshal_to_with_zero_checksum(&mut raw, ai.send_zero_checksum)?;
+        if let Err(err) = self.net_conn.send(&raw).await {
+            log::warn!("[{}] failed to send ABORT: {}", self.name, err);
+        }
+
+        ai.close().await
+    }
+
     async fn new(config: Config, is_client: bool) -> Result<(Self, mpsc::Receiver<Option<Error>>)> {
         let net_conn = Arc::clone(&config.net_conn);
+        let shutdown_timeout = config.shutdown_timeout;
 
         let (awake_write_loop_ch_tx, awake_write_loop_ch_rx) = mpsc::channel(1);
         let (accept_ch_tx, accept_ch_rx) = mpsc::channel(ACCEPT_CH_SIZE);
@@ -292,14 +388,13 @@ impl Association {
         let (close_loop_ch_tx, close_loop_ch_rx) = broadcast::channel(1);
         let (close_loop_ch_rx1, close_loop_ch_rx2) =
             (close_loop_ch_tx.subscribe(), close_loop_ch_tx.subscribe());
-        let awake_write_loop_ch = Arc::new(awake_write_loop_ch_tx);
 
         let ai = AssociationInternal::new(
             config,
             close_loop_ch_tx,
             accept_ch_tx,
             handshake_completed_ch_tx,
-            Arc::clone(&awake_write_loop_ch),
+            Arc::new(awake_write_loop_ch_tx),
         );
 
         let bytes_received = Arc::new(AtomicUsize::new(0));
@@ -307,8 +402,6 @@ impl Association {
         let name = ai.name.clone();
         let state = Arc::clone(&ai.state);
         let max_message_size = Arc::clone(&ai.max_message_size);
-        let inflight_queue_length = Arc::clone(&ai.inflight_queue_length);
-        let will_send_shutdown = Arc::clone(&ai.will_send_shutdown);
 
         let mut init = ChunkInit {
             initial_tsn: ai.my_next_tsn,
@@ -318,7 +411,10 @@ impl Association {
             advertised_receiver_window_credit: ai.max_receive_buffer_size,
             ..Default::default()
         };
-        init.set_supported_extensions();
+        init.set_supported_extensions(ai.enable_message_interleaving);
+        if ai.enable_zero_checksum {
+            init.set_zero_checksum_acceptable();
+        }
 
         let name1 = name.clone();
         let name2 = name.clone();
@@ -350,8 +446,8 @@ impl Association {
             ai.t2shutdown = Some(RtxTimer::new(
                 Arc::downgrade(&association_internal3),
                 RtxTimerId::T2Shutdown,
-                NO_MAX_RETRANS,
-            )); // retransmit forever
+                ASSOC_MAX_RETRANS,
+            ));
             ai.t3rtx = Some(RtxTimer::new(
                 Arc::downgrade(&association_internal3),
                 RtxTimerId::T3RTX,
@@ -362,9 +458,14 @@ impl Association {
                 RtxTimerId::Reconfig,
                 NO_MAX_RETRANS,
             )); // retransmit forever
+            ai.theartbeat = Some(RtxTimer::new(
+                Arc::downgrade(&association_internal3),
+                RtxTimerId::Heartbeat,
+                PATH_MAX_RETRANS,
+            ));
             ai.ack_timer = Some(AckTimer::new(
                 Arc::downgrade(&association_internal3),
-                ACK_INTERVAL,
+                ai.ack_interval,
             ));
         }
 
@@ -407,14 +508,12 @@ impl Association {
                 name,
                 state,
                 max_message_size,
-                inflight_queue_length,
-                will_send_shutdown,
-                awake_write_loop_ch,
                 close_loop_ch_rx: Mutex::new(close_loop_ch_rx),
                 accept_ch_rx: Mutex::new(accept_ch_rx),
                 net_conn,
                 bytes_received,
                 bytes_sent,
+                shutdown_timeout,
                 association_internal,
             },
             handshake_completed_ch_rx,
@@ -490,9 +589,10 @@ impl Association {
 
         while !done.load(Ordering::Relaxed) {
             //log::debug!("[{}] gather_outbound begin", name);
-            let (packets, continue_loop) = {
+            let (packets, continue_loop, zero_checksum) = {
                 let mut ai = association_internal.lock().await;
-                ai.gather_outbound().await
+                let (packets, continue_loop) = ai.gather_outbound().await;
+                (packets, continue_loop, ai.send_zero_checksum)
             };
             //log::debug!("[{}] gather_outbound done with {}", name, packets.len());
 
@@ -510,9 +610,12 @@ impl Association {
                 // If we don't tokio tends to run the write_loop and read_loop of one connection on the same OS thread
                 // This means that even though we release the lock above, the read_loop isn't able to take it, simply because it is not being scheduled by tokio
                 // Doing it this way, tokio schedules this work on a dedicated blocking thread, this future is suspended, and the read_loop can make progress
-                match tokio::task::spawn_blocking(move || raw.marshal_to(&mut buf).map(|_| buf))
-                    .await
-                    .unwrap()
+                match tokio::task::spawn_blocking(move || {
+                    raw.marshal_to_with_zero_checksum(&mut buf, zero_checksum)
+                        .map(|_| buf)
+                })
+                .await
+                .unwrap()
                 {
                     Ok(mut buf) => {
                         let raw = buf.as_ref();
@@ -569,6 +672,10 @@ impl Association {
     }
 
     /// open_stream opens a stream
+    ///
+    /// Returns `Error::ErrStreamAlreadyExist` if the stream is open. A stream
+    /// identifier becomes available again once the stream has been reset in
+    /// both directions (RFC 6525), e.g. after `Stream::shutdown`.
     pub async fn open_stream(
         &self,
         stream_identifier: u16,
@@ -595,19 +702,6 @@ impl Association {
             .store(max_message_size, Ordering::SeqCst);
     }
 
-    /// set_state atomically sets the state of the Association.
-    fn set_state(&self, new_state: AssociationState) {
-        let old_state = AssociationState::from(self.state.swap(new_state as u8, Ordering::SeqCst));
-        if new_state != old_state {
-            log::debug!(
-                "[{}] state change: '{}' => '{}'",
-                self.name,
-                old_state,
-                new_state,
-            );
-        }
-    }
-
     /// get_state atomically returns the state of the Association.
     fn get_state(&self) -> AssociationState {
         self.state.load(Ordering::SeqCst).into()
@@ -0,0 +1,185 @@
+use std::fmt;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use super::chunk_forward_tsn::NEW_CUMULATIVE_TSN_LENGTH;
+use super::chunk_header::*;
+use super::chunk_type::*;
+use super::*;
+
+///I-FORWARD-TSN replaces FORWARD-TSN once message interleaving has been
+///negotiated (RFC 8260 Sec 2.3.1). Skipped messages are identified by their
+///32-bit Message Identifier, and unordered messages are reported as well.
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|   Type = 194  |  Flags = 0x00 |        Length = Variable      |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|                       New Cumulative TSN                      |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|       Stream Identifier       |          Reserved           |U|
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|                       Message Identifier                      |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|                              ...                              |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|       Stream Identifier       |          Reserved           |U|
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|                       Message Identifier                      |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(Default, Debug, Clone)]
+pub(crate) struct ChunkIForwardTsn {
+    /// Same meaning as in the FORWARD-TSN chunk.
+    pub(crate) new_cumulative_tsn: u32,
+    pub(crate) streams: Vec<ChunkIForwardTsnStream>,
+}
+
+pub(crate) const IFORWARD_TSN_STREAM_LENGTH: usize = 8;
+
+/// makes ChunkIForwardTsn printable
+impl fmt::Display for ChunkIForwardTsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = vec![self.header().to_string()];
+        res.push(format!("New Cumulative TSN: {}", self.new_cumulative_tsn));
+        for s in &self.streams {
+            res.push(format!(
+                " - si={}, unordered={}, mid={}",
+                s.identifier, s.unordered, s.message_identifier
+            ));
+        }
+
+        write!(f, "{}", res.join("\n"))
+    }
+}
+
+impl Chunk for ChunkIForwardTsn {
+    fn header(&self) -> ChunkHeader {
+        ChunkHeader {
+            typ: CT_IFORWARD_TSN,
+            flags: 0,
+            value_length: self.value_length() as u16,
+        }
+    }
+
+    fn unmarshal(buf: &Bytes) -> Result<Self> {
+        let header = ChunkHeader::unmarshal(buf)?;
+
+        if header.typ != CT_IFORWARD_TSN {
+            return Err(Error::ErrChunkTypeNotIForwardTsn);
+        }
+
+        if header.value_length() < NEW_CUMULATIVE_TSN_LENGTH {
+            return Err(Error::ErrChunkTooShort);
+        }
+
+        let reader = &mut buf.slice(CHUNK_HEADER_SIZE..CHUNK_HEADER_SIZE + header.value_length());
+        let new_cumulative_tsn = reader.get_u32();
+
+        let mut streams = vec![];
+        while reader.has_remaining() {
+            let s = ChunkIForwardTsnStream::unmarshal(reader)?;
+            reader.advance(IFORWARD_TSN_STREAM_LENGTH);
+            streams.push(s);
+        }
+
+        Ok(ChunkIForwardTsn {
+            new_cumulative_tsn,
+            streams,
+        })
+    }
+
+    fn marshal_to(&self, writer: &mut BytesMut) -> Result<usize> {
+        self.header().marshal_to(writer)?;
+
+        writer.put_u32(self.new_cumulative_tsn);
+
+        for s in &self.streams {
+            writer.extend(s.marshal()?);
+        }
+
+        Ok(writer.len())
+    }
+
+    fn check(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn value_length(&self) -> usize {
+        NEW_CUMULATIVE_TSN_LENGTH + IFORWARD_TSN_STREAM_LENGTH * self.streams.len()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ChunkIForwardTsnStream {
+    /// This field holds a stream number that was skipped by this
+    /// I-FORWARD-TSN.
+    pub(crate) identifier: u16,
+
+    /// Whether the skipped message was sent unordered.
+    pub(crate) unordered: bool,
+
+    /// The largest Message Identifier being skipped on this stream for the
+    /// given ordering.
+    pub(crate) message_identifier: u32,
+}
+
+/// makes ChunkIForwardTsnStream printable
+impl fmt::Display for ChunkIForwardTsnStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {}, {}",
+            self.identifier, self.unordered, self.message_identifier
+        )
+    }
+}
+
+impl Chunk for ChunkIForwardTsnStream {
+    fn header(&self) -> ChunkHeader {
+        ChunkHeader {
+            typ: ChunkType(0),
+            flags: 0,
+            value_length: self.value_length() as u16,
+        }
+    }
+
+    fn unmarshal(buf: &Bytes) -> Result<Self> {
+        if buf.len() < IFORWARD_TSN_STREAM_LENGTH {
+            return Err(Error::ErrChunkTooShort);
+        }
+
+        let reader = &mut buf.clone();
+        let identifier = reader.get_u16();
+        let unordered = (reader.get_u16() & 1) != 0;
+        let message_identifier = reader.get_u32();
+
+        Ok(ChunkIForwardTsnStream {
+            identifier,
+            unordered,
+            message_identifier,
+        })
+    }
+
+    fn marshal_to(&self, writer: &mut BytesMut) -> Result<usize> {
+        writer.put_u16(self.identifier);
+        writer.put_u16(self.unordered as u16);
+        writer.put_u32(self.message_identifier);
+        Ok(writer.len())
+    }
+
+    fn check(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn value_length(&self) -> usize {
+        IFORWARD_TSN_STREAM_LENGTH
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+}
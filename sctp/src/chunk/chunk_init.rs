@@ -7,6 +7,7 @@ use super::chunk_type::*;
 use super::*;
 use crate::param::param_header::*;
 use crate::param::param_supported_extensions::ParamSupportedExtensions;
+use crate::param::param_zero_checksum::*;
 use crate::param::*;
 use crate::util::get_padding_size;
 
@@ -292,13 +293,48 @@ impl Chunk for ChunkInit {
 }
 
 impl ChunkInit {
-    pub(crate) fn set_supported_extensions(&mut self) {
+    pub(crate) fn set_supported_extensions(&mut self, interleaving: bool) {
         // TODO RFC5061 https://tools.ietf.org/html/rfc6525#section-5.2
         // An implementation supporting this (Supported Extensions Parameter)
         // extension MUST list the ASCONF, the ASCONF-ACK, and the AUTH chunks
         // in its INIT and INIT-ACK parameters.
-        self.params.push(Box::new(ParamSupportedExtensions {
-            chunk_types: vec![CT_RECONFIG, CT_FORWARD_TSN],
+        let mut chunk_types = vec![CT_RECONFIG, CT_FORWARD_TSN];
+        if interleaving {
+            // RFC 8260 Sec 2.2.1: both I-DATA and I-FORWARD-TSN are listed
+            // when PR-SCTP is supported.
+            chunk_types.extend([CT_IDATA, CT_IFORWARD_TSN]);
+        }
+        self.params
+            .push(Box::new(ParamSupportedExtensions { chunk_types }));
+    }
+
+    /// supports_interleaving returns whether the sender of this INIT or
+    /// INIT ACK listed I-DATA as a supported extension (RFC 8260).
+    pub(crate) fn supports_interleaving(&self) -> bool {
+        self.params.iter().any(
+            |p| match p.as_any().downcast_ref::<ParamSupportedExtensions>() {
+                Some(p) => p.chunk_types.contains(&CT_IDATA),
+                None => false,
+            },
+        )
+    }
+
+    /// set_zero_checksum_acceptable announces that packets protected by DTLS
+    /// may be sent to us with a zero checksum (RFC 9653).
+    pub(crate) fn set_zero_checksum_acceptable(&mut self) {
+        self.params.push(Box::new(ParamZeroChecksumAcceptable {
+            edmid: DTLS_ERROR_DETECTION_METHOD,
         }));
     }
+
+    /// zero_checksum_acceptable returns whether the sender of this INIT or
+    /// INIT ACK accepts packets with a zero checksum over DTLS (RFC 9653).
+    pub(crate) fn zero_checksum_acceptable(&self) -> bool {
+        self.params.iter().any(
+            |p| match p.as_any().downcast_ref::<ParamZeroChecksumAcceptable>() {
+                Some(p) => p.edmid == DTLS_ERROR_DETECTION_METHOD,
+                None => false,
+            },
+        )
+    }
 }
@@ -15,6 +15,7 @@ pub(crate) const PAYLOAD_DATA_BEGINNING_FRAGMENT_BITMASK: u8 = 2;
 pub(crate) const PAYLOAD_DATA_UNORDERED_BITMASK: u8 = 4;
 pub(crate) const PAYLOAD_DATA_IMMEDIATE_SACK: u8 = 8;
 pub(crate) const PAYLOAD_DATA_HEADER_SIZE: usize = 12;
+pub(crate) const IDATA_HEADER_SIZE: usize = 16;
 
 /// PayloadProtocolIdentifier is an enum for DataChannel payload types
 /// PayloadProtocolIdentifier enums
@@ -92,6 +93,30 @@ impl From<u32> for PayloadProtocolIdentifier {
 ///============================================================
 ///|             Table 1: Fragment Description Flags          |
 ///============================================================
+///
+///When message interleaving has been negotiated (RFC 8260), user data is
+///carried in I-DATA chunks instead. The 16-bit Stream Sequence Number is
+///replaced by a 32-bit Message Identifier (MID), and every fragment but the
+///first carries a Fragment Sequence Number (FSN) in place of the Payload
+///Protocol Identifier:
+///
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|   Type = 64   |  Res  |I|U|B|E|       Length = Variable       |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|                              TSN                              |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|        Stream Identifier      |           Reserved            |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|                      Message Identifier                       |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|    Payload Protocol Identifier / Fragment Sequence Number     |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|                                                               |
+///|                           User Data                           |
+///|                                                               |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, Clone)]
 pub struct ChunkPayloadData {
     pub(crate) unordered: bool,
@@ -105,6 +130,14 @@ pub struct ChunkPayloadData {
     pub(crate) payload_type: PayloadProtocolIdentifier,
     pub(crate) user_data: Bytes,
 
+    /// Whether this chunk is sent as I-DATA (RFC 8260)
+    pub(crate) interleaved: bool,
+    /// I-DATA only. For ordered chunks the low 16 bits double as the
+    /// stream_sequence_number so that reassembly works the same for both.
+    pub(crate) message_identifier: u32,
+    /// I-DATA only. Zero for the first fragment of a message.
+    pub(crate) fragment_sequence_number: u32,
+
     /// Whether this data chunk was acknowledged (received by peer)
     pub(crate) acked: bool,
     pub(crate) miss_indicator: u32,
@@ -136,6 +169,9 @@ impl Default for ChunkPayloadData {
             stream_sequence_number: 0,
             payload_type: PayloadProtocolIdentifier::default(),
             user_data: Bytes::new(),
+            interleaved: false,
+            message_identifier: 0,
+            fragment_sequence_number: 0,
             acked: false,
             miss_indicator: 0,
             since: SystemTime::now(),
@@ -171,7 +207,11 @@ impl Chunk for ChunkPayloadData {
         }
 
         ChunkHeader {
-            typ: CT_PAYLOAD_DATA,
+            typ: if self.interleaved {
+                CT_IDATA
+            } else {
+                CT_PAYLOAD_DATA
+            },
             flags,
             value_length: self.value_length() as u16,
         }
@@ -180,9 +220,15 @@ impl Chunk for ChunkPayloadData {
     fn unmarshal(raw: &Bytes) -> Result<Self> {
         let header = ChunkHeader::unmarshal(raw)?;
 
-        if header.typ != CT_PAYLOAD_DATA {
+        if header.typ != CT_PAYLOAD_DATA && header.typ != CT_IDATA {
             return Err(Error::ErrChunkTypeNotPayloadData);
         }
+        let interleaved = header.typ == CT_IDATA;
+        let header_size = if interleaved {
+            IDATA_HEADER_SIZE
+        } else {
+            PAYLOAD_DATA_HEADER_SIZE
+        };
 
         let immediate_sack = (header.flags & PAYLOAD_DATA_IMMEDIATE_SACK) != 0;
         let unordered = (header.flags & PAYLOAD_DATA_UNORDERED_BITMASK) != 0;
@@ -190,7 +236,7 @@ impl Chunk for ChunkPayloadData {
         let ending_fragment = (header.flags & PAYLOAD_DATA_ENDING_FRAGMENT_BITMASK) != 0;
 
         // validity of value_length is checked in ChunkHeader::unmarshal
-        if header.value_length() < PAYLOAD_DATA_HEADER_SIZE {
+        if header.value_length() < header_size {
             return Err(Error::ErrChunkPayloadSmall);
         }
 
@@ -198,11 +244,32 @@ impl Chunk for ChunkPayloadData {
 
         let tsn = reader.get_u32();
         let stream_identifier = reader.get_u16();
-        let stream_sequence_number = reader.get_u16();
-        let payload_type: PayloadProtocolIdentifier = reader.get_u32().into();
-        let user_data = raw.slice(
-            CHUNK_HEADER_SIZE + PAYLOAD_DATA_HEADER_SIZE..CHUNK_HEADER_SIZE + header.value_length(),
-        );
+        let (stream_sequence_number, message_identifier, fragment_sequence_number, payload_type) =
+            if interleaved {
+                let _reserved = reader.get_u16();
+                let message_identifier = reader.get_u32();
+                let ppi_or_fsn = reader.get_u32();
+                if beginning_fragment {
+                    (
+                        message_identifier as u16,
+                        message_identifier,
+                        0,
+                        ppi_or_fsn.into(),
+                    )
+                } else {
+                    (
+                        message_identifier as u16,
+                        message_identifier,
+                        ppi_or_fsn,
+                        PayloadProtocolIdentifier::Unknown,
+                    )
+                }
+            } else {
+                let stream_sequence_number = reader.get_u16();
+                (stream_sequence_number, 0, 0, reader.get_u32().into())
+            };
+        let user_data =
+            raw.slice(CHUNK_HEADER_SIZE + header_size..CHUNK_HEADER_SIZE + header.value_length());
 
         Ok(ChunkPayloadData {
             unordered,
@@ -215,6 +282,9 @@ impl Chunk for ChunkPayloadData {
             stream_sequence_number,
             payload_type,
             user_data,
+            interleaved,
+            message_identifier,
+            fragment_sequence_number,
             acked: false,
             miss_indicator: 0,
             since: SystemTime::now(),
@@ -230,8 +300,18 @@ impl Chunk for ChunkPayloadData {
 
         writer.put_u32(self.tsn);
         writer.put_u16(self.stream_identifier);
-        writer.put_u16(self.stream_sequence_number);
-        writer.put_u32(self.payload_type as u32);
+        if self.interleaved {
+            writer.put_u16(0);
+            writer.put_u32(self.message_identifier);
+            if self.beginning_fragment {
+                writer.put_u32(self.payload_type as u32);
+            } else {
+                writer.put_u32(self.fragment_sequence_number);
+            }
+        } else {
+            writer.put_u16(self.stream_sequence_number);
+            writer.put_u32(self.payload_type as u32);
+        }
         writer.extend_from_slice(&self.user_data);
 
         Ok(writer.len())
@@ -242,7 +322,11 @@ impl Chunk for ChunkPayloadData {
     }
 
     fn value_length(&self) -> usize {
-        PAYLOAD_DATA_HEADER_SIZE + self.user_data.len()
+        if self.interleaved {
+            IDATA_HEADER_SIZE + self.user_data.len()
+        } else {
+            PAYLOAD_DATA_HEADER_SIZE + self.user_data.len()
+        }
     }
 
     fn as_any(&self) -> &(dyn Any + Send + Sync) {
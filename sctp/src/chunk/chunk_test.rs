@@ -22,8 +22,10 @@ fn test_chunk_type_string() -> Result<()> {
         (CT_ECNE, "ECNE"),
         (CT_CWR, "CWR"),
         (CT_SHUTDOWN_COMPLETE, "SHUTDOWN-COMPLETE"),
+        (CT_IDATA, "I-DATA"),
         (CT_RECONFIG, "RECONFIG"),
         (CT_FORWARD_TSN, "FORWARD-TSN"),
+        (CT_IFORWARD_TSN, "I-FORWARD-TSN"),
         (ChunkType(255), "Unknown ChunkType: 255"),
     ];
 
@@ -229,6 +231,67 @@ fn test_chunk_forward_tsn_unmarshal_failure() -> Result<()> {
     Ok(())
 }
 
+///////////////////////////////////////////////////////////////////
+//chunk_iforward_tsn_test
+///////////////////////////////////////////////////////////////////
+use super::chunk_iforward_tsn::*;
+
+#[test]
+fn test_chunk_iforward_tsn_success() -> Result<()> {
+    let tests = vec![
+        Bytes::from_static(&[0xc2, 0x0, 0x0, 0x8, 0x0, 0x0, 0x0, 0x3]),
+        Bytes::from_static(&[
+            0xc2, 0x0, 0x0, 0x10, 0x0, 0x0, 0x0, 0x3, 0x0, 0x4, 0x0, 0x1, 0x0, 0x1, 0x0, 0x5,
+        ]),
+        Bytes::from_static(&[
+            0xc2, 0x0, 0x0, 0x18, 0x0, 0x0, 0x0, 0x3, 0x0, 0x4, 0x0, 0x1, 0x0, 0x1, 0x0, 0x5, 0x0,
+            0x6, 0x0, 0x0, 0x0, 0x0, 0x0, 0x7,
+        ]),
+    ];
+
+    for binary in tests {
+        let actual = ChunkIForwardTsn::unmarshal(&binary)?;
+        let b = actual.marshal()?;
+        assert_eq!(b, binary, "test not equal");
+    }
+
+    let c = ChunkIForwardTsn::unmarshal(&Bytes::from_static(&[
+        0xc2, 0x0, 0x0, 0x10, 0x0, 0x0, 0x0, 0x3, 0x0, 0x4, 0x0, 0x1, 0x0, 0x1, 0x0, 0x5,
+    ]))?;
+    assert_eq!(c.new_cumulative_tsn, 3);
+    assert_eq!(c.streams[0].identifier, 4);
+    assert!(c.streams[0].unordered);
+    assert_eq!(c.streams[0].message_identifier, 0x10005);
+
+    Ok(())
+}
+
+#[test]
+fn test_chunk_iforward_tsn_unmarshal_failure() -> Result<()> {
+    let tests = vec![
+        ("chunk header to short", Bytes::from_static(&[0xc2])),
+        (
+            "missing New Cumulative TSN",
+            Bytes::from_static(&[0xc2, 0x0, 0x0, 0x4]),
+        ),
+        (
+            "missing message identifier",
+            Bytes::from_static(&[0xc2, 0x0, 0x0, 0xc, 0x0, 0x0, 0x0, 0x3, 0x0, 0x4, 0x0, 0x0]),
+        ),
+        (
+            "not an I-FORWARD-TSN chunk",
+            Bytes::from_static(&[0xc0, 0x0, 0x0, 0x8, 0x0, 0x0, 0x0, 0x3]),
+        ),
+    ];
+
+    for (name, binary) in tests {
+        let result = ChunkIForwardTsn::unmarshal(&binary);
+        assert!(result.is_err(), "expected unmarshal: {name} to fail.");
+    }
+
+    Ok(())
+}
+
 ///////////////////////////////////////////////////////////////////
 //chunk_reconfig_test
 ///////////////////////////////////////////////////////////////////
@@ -652,6 +715,50 @@ fn test_payload_data_marshal_unmarshal() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_idata_marshal_unmarshal() -> Result<()> {
+    // First fragment: MID 0x10002, PPI 53 (WebRTC Binary)
+    let first = Bytes::from_static(&[
+        0x40, 0x02, 0x00, 0x18, 0x00, 0x00, 0x00, 0x07, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x35, 0x61, 0x62, 0x63, 0x64,
+    ]);
+    let c = ChunkPayloadData::unmarshal(&first)?;
+    assert!(c.interleaved, "should be I-DATA");
+    assert!(c.beginning_fragment && !c.ending_fragment);
+    assert_eq!(c.tsn, 7);
+    assert_eq!(c.stream_identifier, 3);
+    assert_eq!(c.message_identifier, 0x10002);
+    assert_eq!(
+        c.stream_sequence_number, 2,
+        "SSN should be the low bits of the MID"
+    );
+    assert_eq!(c.fragment_sequence_number, 0);
+    assert_eq!(c.payload_type, PayloadProtocolIdentifier::Binary);
+    assert_eq!(&c.user_data[..], b"abcd");
+    assert_eq!(c.marshal()?, first, "should marshal back to the same bytes");
+
+    // Last fragment: FSN 1 in place of the PPI
+    let last = Bytes::from_static(&[
+        0x40, 0x01, 0x00, 0x16, 0x00, 0x00, 0x00, 0x09, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00,
+        0x02, 0x00, 0x00, 0x00, 0x01, 0x65, 0x66,
+    ]);
+    let c = ChunkPayloadData::unmarshal(&last)?;
+    assert!(!c.beginning_fragment && c.ending_fragment);
+    assert_eq!(c.fragment_sequence_number, 1);
+    assert_eq!(c.payload_type, PayloadProtocolIdentifier::Unknown);
+    assert_eq!(&c.user_data[..], b"ef");
+    assert_eq!(c.marshal()?, last, "should marshal back to the same bytes");
+
+    // Too short to hold the I-DATA header
+    let short = Bytes::from_static(&[
+        0x40, 0x03, 0x00, 0x10, 0x00, 0x00, 0x00, 0x07, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00,
+        0x02,
+    ]);
+    assert!(ChunkPayloadData::unmarshal(&short).is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_select_ack_chunk() -> Result<()> {
     let raw_pkt = Bytes::from_static(&[
@@ -21,8 +21,10 @@ pub(crate) const CT_COOKIE_ACK: ChunkType = ChunkType(11);
 pub(crate) const CT_ECNE: ChunkType = ChunkType(12);
 pub(crate) const CT_CWR: ChunkType = ChunkType(13);
 pub(crate) const CT_SHUTDOWN_COMPLETE: ChunkType = ChunkType(14);
+pub(crate) const CT_IDATA: ChunkType = ChunkType(64);
 pub(crate) const CT_RECONFIG: ChunkType = ChunkType(130);
 pub(crate) const CT_FORWARD_TSN: ChunkType = ChunkType(192);
+pub(crate) const CT_IFORWARD_TSN: ChunkType = ChunkType(194);
 
 impl fmt::Display for ChunkType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -43,8 +45,10 @@ impl fmt::Display for ChunkType {
             CT_ECNE => "ECNE", // Explicit Congestion Notification Echo
             CT_CWR => "CWR",   // Reserved for Congestion Window Reduced (CWR)
             CT_SHUTDOWN_COMPLETE => "SHUTDOWN-COMPLETE",
+            CT_IDATA => "I-DATA",
             CT_RECONFIG => "RECONFIG", // Re-configuration
             CT_FORWARD_TSN => "FORWARD-TSN",
+            CT_IFORWARD_TSN => "I-FORWARD-TSN",
             _ => others.as_str(),
         };
         write!(f, "{s}")
@@ -73,8 +77,10 @@ mod test {
             (CT_ECNE, "ECNE"),
             (CT_CWR, "CWR"),
             (CT_SHUTDOWN_COMPLETE, "SHUTDOWN-COMPLETE"),
+            (CT_IDATA, "I-DATA"),
             (CT_RECONFIG, "RECONFIG"),
             (CT_FORWARD_TSN, "FORWARD-TSN"),
+            (CT_IFORWARD_TSN, "I-FORWARD-TSN"),
             (ChunkType(255), "Unknown ChunkType: 255"),
         ];
 
@@ -34,6 +34,10 @@ pub enum Error {
     ErrChunkTooShort,
     #[error("ChunkType is not of type ForwardTsn")]
     ErrChunkTypeNotForwardTsn,
+    #[error("ChunkType is not of type IForwardTsn")]
+    ErrChunkTypeNotIForwardTsn,
+    #[error("DATA and I-DATA chunks must not be mixed")]
+    ErrUnexpectedDataChunkType,
     #[error("ChunkType is not of type HEARTBEAT")]
     ErrChunkTypeNotHeartbeat,
     #[error("ChunkType is not of type HEARTBEATACK")]
@@ -79,6 +83,8 @@ pub enum Error {
     ErrSsnResetRequestParamTooShort,
     #[error("reconfig response parameter too short")]
     ErrReconfigRespParamTooShort,
+    #[error("zero checksum acceptable parameter too short")]
+    ErrZeroChecksumParamTooShort,
     #[error("invalid algorithm type")]
     ErrInvalidAlgorithmType,
 
@@ -159,6 +165,8 @@ pub enum Error {
 
     #[error("abort chunk, with following errors")]
     ErrChunk,
+    #[error("association aborted by peer: {0}")]
+    ErrAbortReceived(String),
     #[error("shutdown called in non-Established state")]
     ErrShutdownNonEstablished,
     #[error("association closed before connecting")]
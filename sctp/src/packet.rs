@@ -9,6 +9,8 @@ use crate::chunk::chunk_error::ChunkError;
 use crate::chunk::chunk_forward_tsn::ChunkForwardTsn;
 use crate::chunk::chunk_header::*;
 use crate::chunk::chunk_heartbeat::ChunkHeartbeat;
+use crate::chunk::chunk_heartbeat_ack::ChunkHeartbeatAck;
+use crate::chunk::chunk_iforward_tsn::ChunkIForwardTsn;
 use crate::chunk::chunk_init::ChunkInit;
 use crate::chunk::chunk_payload_data::ChunkPayloadData;
 use crate::chunk::chunk_reconfig::ChunkReconfig;
@@ -82,6 +84,16 @@ pub(crate) const PACKET_HEADER_SIZE: usize = 12;
 
 impl Packet {
     pub(crate) fn unmarshal(raw: &Bytes) -> Result<Self> {
+        Packet::unmarshal_with_zero_checksum(raw, false)
+    }
+
+    /// unmarshal_with_zero_checksum parses a packet, also accepting a zero
+    /// checksum in place of the CRC32c if zero_checksum_acceptable is set
+    /// (RFC 9653).
+    pub(crate) fn unmarshal_with_zero_checksum(
+        raw: &Bytes,
+        zero_checksum_acceptable: bool,
+    ) -> Result<Self> {
         if raw.len() < PACKET_HEADER_SIZE {
             return Err(Error::ErrPacketRawTooSmall);
         }
@@ -96,10 +108,12 @@ impl Packet {
         // only check for checksums when we are not fuzzing. This lets the fuzzer test the code much easier without guessing correct checksums.
         {
             let their_checksum = reader.get_u32_le();
-            let our_checksum = generate_packet_checksum(raw);
+            if their_checksum != 0 || !zero_checksum_acceptable {
+                let our_checksum = generate_packet_checksum(raw);
 
-            if their_checksum != our_checksum {
-                return Err(Error::ErrChecksumMismatch);
+                if their_checksum != our_checksum {
+                    return Err(Error::ErrChecksumMismatch);
+                }
             }
         }
 
@@ -121,10 +135,14 @@ impl Packet {
                 CT_COOKIE_ECHO => Box::new(ChunkCookieEcho::unmarshal(&raw.slice(offset..))?),
                 CT_COOKIE_ACK => Box::new(ChunkCookieAck::unmarshal(&raw.slice(offset..))?),
                 CT_HEARTBEAT => Box::new(ChunkHeartbeat::unmarshal(&raw.slice(offset..))?),
-                CT_PAYLOAD_DATA => Box::new(ChunkPayloadData::unmarshal(&raw.slice(offset..))?),
+                CT_HEARTBEAT_ACK => Box::new(ChunkHeartbeatAck::unmarshal(&raw.slice(offset..))?),
+                CT_PAYLOAD_DATA | CT_IDATA => {
+                    Box::new(ChunkPayloadData::unmarshal(&raw.slice(offset..))?)
+                }
                 CT_SACK => Box::new(ChunkSelectiveAck::unmarshal(&raw.slice(offset..))?),
                 CT_RECONFIG => Box::new(ChunkReconfig::unmarshal(&raw.slice(offset..))?),
                 CT_FORWARD_TSN => Box::new(ChunkForwardTsn::unmarshal(&raw.slice(offset..))?),
+                CT_IFORWARD_TSN => Box::new(ChunkIForwardTsn::unmarshal(&raw.slice(offset..))?),
                 CT_ERROR => Box::new(ChunkError::unmarshal(&raw.slice(offset..))?),
                 CT_SHUTDOWN => Box::new(ChunkShutdown::unmarshal(&raw.slice(offset..))?),
                 CT_SHUTDOWN_ACK => Box::new(ChunkShutdownAck::unmarshal(&raw.slice(offset..))?),
@@ -148,6 +166,17 @@ impl Packet {
     }
 
     pub(crate) fn marshal_to(&self, writer: &mut BytesMut) -> Result<usize> {
+        self.marshal_to_with_zero_checksum(writer, false)
+    }
+
+    /// marshal_to_with_zero_checksum serializes the packet, leaving the checksum
+    /// zero if the peer accepts it (RFC 9653) and the packet doesn't carry chunks
+    /// of the association setup, which always use the CRC32c.
+    pub(crate) fn marshal_to_with_zero_checksum(
+        &self,
+        writer: &mut BytesMut,
+        zero_checksum: bool,
+    ) -> Result<usize> {
         // Populate static headers
         // 8-12 is Checksum which will be populated when packet is complete
         writer.put_u16(self.source_port);
@@ -169,6 +198,10 @@ impl Packet {
             }
         }
 
+        if zero_checksum && self.zero_checksum_allowed() {
+            return Ok(writer.len());
+        }
+
         let mut digest = ISCSI_CRC.digest();
         digest.update(writer);
         let checksum = digest.finalize();
@@ -181,6 +214,13 @@ impl Packet {
         Ok(writer.len())
     }
 
+    fn zero_checksum_allowed(&self) -> bool {
+        !self.chunks.iter().any(|c| {
+            let typ = c.header().typ;
+            typ == CT_INIT || typ == CT_INIT_ACK || typ == CT_COOKIE_ECHO
+        })
+    }
+
     pub(crate) fn marshal(&self) -> Result<Bytes> {
         let mut buf = BytesMut::with_capacity(PACKET_HEADER_SIZE);
         self.marshal_to(&mut buf)?;
@@ -294,6 +334,41 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_packet_zero_checksum() -> Result<()> {
+        let zero_checksum = Bytes::from_static(&[
+            0x13, 0x88, 0x13, 0x88, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ]);
+        assert!(
+            Packet::unmarshal(&zero_checksum).is_err(),
+            "zero checksum should be rejected unless acceptable"
+        );
+        let pkt = Packet::unmarshal_with_zero_checksum(&zero_checksum, true)?;
+
+        let mut buf = BytesMut::new();
+        pkt.marshal_to_with_zero_checksum(&mut buf, true)?;
+        assert_eq!(buf.freeze(), zero_checksum, "checksum should be left zero");
+
+        let init = Packet {
+            source_port: 5000,
+            destination_port: 5000,
+            verification_tag: 0,
+            chunks: vec![Box::new(ChunkInit {
+                initiate_tag: 1,
+                num_inbound_streams: 1,
+                num_outbound_streams: 1,
+                advertised_receiver_window_credit: 1500,
+                ..Default::default()
+            })],
+        };
+        let mut buf = BytesMut::new();
+        init.marshal_to_with_zero_checksum(&mut buf, true)?;
+        assert_ne!(&buf[8..12], &[0, 0, 0, 0], "INIT must carry a CRC32c");
+        Packet::unmarshal(&buf.freeze())?;
+
+        Ok(())
+    }
+
     /*fn BenchmarkPacketGenerateChecksum(b *testing.B) {
         var data [1024]byte
 
@@ -14,6 +14,7 @@ pub(crate) mod param_supported_extensions;
 pub(crate) mod param_type;
 pub(crate) mod param_unknown;
 pub(crate) mod param_unrecognized;
+pub(crate) mod param_zero_checksum;
 
 use std::any::Any;
 use std::fmt;
@@ -33,6 +34,7 @@ use crate::param::param_requested_hmac_algorithm::ParamRequestedHmacAlgorithm;
 use crate::param::param_state_cookie::ParamStateCookie;
 use crate::param::param_supported_extensions::ParamSupportedExtensions;
 use crate::param::param_unknown::ParamUnknown;
+use crate::param::param_zero_checksum::ParamZeroChecksumAcceptable;
 
 pub(crate) trait Param: fmt::Display + fmt::Debug {
     fn header(&self) -> ParamHeader;
@@ -74,6 +76,9 @@ pub(crate) fn build_param(raw_param: &Bytes) -> Result<Box<dyn Param + Send + Sy
         ParamType::HeartbeatInfo => Ok(Box::new(ParamHeartbeatInfo::unmarshal(raw_param)?)),
         ParamType::OutSsnResetReq => Ok(Box::new(ParamOutgoingResetRequest::unmarshal(raw_param)?)),
         ParamType::ReconfigResp => Ok(Box::new(ParamReconfigResponse::unmarshal(raw_param)?)),
+        ParamType::ZeroChecksumAcceptable => {
+            Ok(Box::new(ParamZeroChecksumAcceptable::unmarshal(raw_param)?))
+        }
         _ => {
             // According to RFC https://datatracker.ietf.org/doc/html/rfc4960#section-3.2.1
             let stop_processing = ((raw_type >> 15) & 0x01) == 0;
@@ -237,13 +237,64 @@ fn test_reconfig_result_stringer() -> Result<()> {
     Ok(())
 }
 
+///////////////////////////////////////////////////////////////////
+//param_zero_checksum_test
+///////////////////////////////////////////////////////////////////
+use super::param_zero_checksum::*;
+
+static PARAM_ZERO_CHECKSUM_ACCEPTABLE: Bytes =
+    Bytes::from_static(&[0x80, 0x1, 0x0, 0x8, 0x0, 0x0, 0x0, 0x1]);
+
+#[test]
+fn test_param_zero_checksum_acceptable_success() -> Result<()> {
+    let tests = vec![(
+        PARAM_ZERO_CHECKSUM_ACCEPTABLE.clone(),
+        ParamZeroChecksumAcceptable {
+            edmid: DTLS_ERROR_DETECTION_METHOD,
+        },
+    )];
+
+    for (binary, parsed) in tests {
+        let actual = ParamZeroChecksumAcceptable::unmarshal(&binary)?;
+        assert_eq!(actual, parsed);
+        let b = actual.marshal()?;
+        assert_eq!(b, binary);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_param_zero_checksum_acceptable_failure() -> Result<()> {
+    let tests = vec![
+        (
+            "packet too short",
+            PARAM_ZERO_CHECKSUM_ACCEPTABLE.slice(..6),
+        ),
+        (
+            "param too short",
+            Bytes::from_static(&[0x80, 0x1, 0x0, 0x4]),
+        ),
+    ];
+
+    for (name, binary) in tests {
+        let result = ParamZeroChecksumAcceptable::unmarshal(&binary);
+        assert!(result.is_err(), "expected unmarshal: {name} to fail.");
+    }
+
+    Ok(())
+}
+
 ///////////////////////////////////////////////////////////////////
 //param_test
 ///////////////////////////////////////////////////////////////////
 
 #[test]
 fn test_build_param_success() -> Result<()> {
-    let tests = vec![CHUNK_RECONFIG_PARAM_A.clone()];
+    let tests = vec![
+        CHUNK_RECONFIG_PARAM_A.clone(),
+        PARAM_ZERO_CHECKSUM_ACCEPTABLE.clone(),
+    ];
 
     for binary in tests {
         let p = build_param(&binary)?;
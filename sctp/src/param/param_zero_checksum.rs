@@ -0,0 +1,73 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use super::param_header::*;
+use super::param_type::*;
+use super::*;
+
+/// DTLS_ERROR_DETECTION_METHOD is the Error Detection Method Identifier for
+/// SCTP over DTLS, see https://www.rfc-editor.org/rfc/rfc9653#section-7.2
+pub(crate) const DTLS_ERROR_DETECTION_METHOD: u32 = 1;
+
+/// This parameter is used by an endpoint to indicate that it accepts packets
+/// with a zero checksum, because an alternate error detection method (e.g.
+/// DTLS) protects the packets. https://www.rfc-editor.org/rfc/rfc9653#section-4
+///
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|     Type = 0x8001 (suggested) |          Length = 8           |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///|           Error Detection Method Identifier (EDMID)           |
+///+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub(crate) struct ParamZeroChecksumAcceptable {
+    pub(crate) edmid: u32,
+}
+
+impl fmt::Display for ParamZeroChecksumAcceptable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.header(), self.edmid)
+    }
+}
+
+impl Param for ParamZeroChecksumAcceptable {
+    fn header(&self) -> ParamHeader {
+        ParamHeader {
+            typ: ParamType::ZeroChecksumAcceptable,
+            value_length: self.value_length() as u16,
+        }
+    }
+
+    fn unmarshal(raw: &Bytes) -> Result<Self> {
+        let header = ParamHeader::unmarshal(raw)?;
+
+        // validity of value_length is checked in ParamHeader::unmarshal
+        if header.value_length < 4 {
+            return Err(Error::ErrZeroChecksumParamTooShort);
+        }
+
+        let reader =
+            &mut raw.slice(PARAM_HEADER_LENGTH..PARAM_HEADER_LENGTH + header.value_length());
+        let edmid = reader.get_u32();
+
+        Ok(ParamZeroChecksumAcceptable { edmid })
+    }
+
+    fn marshal_to(&self, buf: &mut BytesMut) -> Result<usize> {
+        self.header().marshal_to(buf)?;
+        buf.put_u32(self.edmid);
+        Ok(buf.len())
+    }
+
+    fn value_length(&self) -> usize {
+        4
+    }
+
+    fn clone_to(&self) -> Box<dyn Param + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 use std::sync::atomic::Ordering;
 
-use portable_atomic::{AtomicBool, AtomicUsize};
+use portable_atomic::{AtomicBool, AtomicU16, AtomicUsize};
 use tokio::sync::{Mutex, Semaphore};
 use util::sync::RwLock;
 
@@ -40,6 +40,11 @@ pub(crate) struct PendingQueue {
     n_bytes: AtomicUsize,
     selected: AtomicBool,
     unordered_is_selected: AtomicBool,
+
+    // With message interleaving (RFC 8260) chunks are picked round-robin
+    // across streams instead of sending each message as a whole.
+    interleaving: AtomicBool,
+    last_stream_identifier: AtomicU16,
 }
 
 impl Default for PendingQueue {
@@ -59,6 +64,8 @@ impl PendingQueue {
             n_bytes: Default::default(),
             selected: Default::default(),
             unordered_is_selected: Default::default(),
+            interleaving: Default::default(),
+            last_stream_identifier: Default::default(),
         }
     }
 
@@ -162,7 +169,51 @@ impl PendingQueue {
         self.queue_len.fetch_add(chunks_len, Ordering::SeqCst);
     }
 
+    /// Enables picking fragments of messages on different streams in turn
+    /// once I-DATA has been negotiated.
+    pub(crate) fn set_interleaving(&self, interleaving: bool) {
+        self.interleaving.store(interleaving, Ordering::SeqCst);
+    }
+
+    /// Returns the queue (unordered or not) and the position of the next chunk
+    /// to send with interleaving. Streams take turns in the order of their
+    /// identifiers, starting after the one last sent from, so that no stream
+    /// can block the others. The oldest chunk of a stream is always picked
+    /// first, which keeps its fragments in order.
+    fn interleaved_position(&self) -> Option<(bool, usize)> {
+        let last_stream_identifier = self.last_stream_identifier.load(Ordering::SeqCst);
+        let unordered_queue = self.unordered_queue.read();
+        let ordered_queue = self.ordered_queue.read();
+
+        let mut next: Option<(u16, bool, usize)> = None;
+        for (unordered, queue) in [(true, &*unordered_queue), (false, &*ordered_queue)] {
+            for (i, c) in queue.iter().enumerate() {
+                let distance = c
+                    .stream_identifier
+                    .wrapping_sub(last_stream_identifier)
+                    .wrapping_sub(1);
+                let closer = match next {
+                    Some((d, _, _)) => distance < d,
+                    None => true,
+                };
+                if closer {
+                    next = Some((distance, unordered, i));
+                }
+            }
+        }
+
+        next.map(|(_, unordered, i)| (unordered, i))
+    }
+
     pub(crate) fn peek(&self) -> Option<ChunkPayloadData> {
+        if self.interleaving.load(Ordering::SeqCst) {
+            return match self.interleaved_position() {
+                Some((true, i)) => self.unordered_queue.read().get(i).cloned(),
+                Some((false, i)) => self.ordered_queue.read().get(i).cloned(),
+                None => None,
+            };
+        }
+
         if self.selected.load(Ordering::SeqCst) {
             if self.unordered_is_selected.load(Ordering::SeqCst) {
                 let unordered_queue = self.unordered_queue.read();
@@ -186,6 +237,38 @@ impl PendingQueue {
         ordered_queue.front().cloned()
     }
 
+    /// Removes the chunk returned by peek() with interleaving. Streams may have
+    /// appended chunks since, so the chunk is looked up by its stream, MID and
+    /// FSN rather than picked again.
+    pub(crate) fn pop_interleaved(&self, peeked: &ChunkPayloadData) -> Option<ChunkPayloadData> {
+        let is_peeked = |c: &ChunkPayloadData| {
+            c.stream_identifier == peeked.stream_identifier
+                && c.message_identifier == peeked.message_identifier
+                && c.fragment_sequence_number == peeked.fragment_sequence_number
+        };
+
+        let popped = {
+            let mut queue = if peeked.unordered {
+                self.unordered_queue.write()
+            } else {
+                self.ordered_queue.write()
+            };
+            queue
+                .iter()
+                .position(is_peeked)
+                .and_then(|i| queue.remove(i))
+        };
+
+        if let Some(p) = &popped {
+            self.last_stream_identifier
+                .store(p.stream_identifier, Ordering::SeqCst);
+            self.release(p);
+        }
+
+        popped
+    }
+
+    /// Removes the chunk returned by peek() without interleaving.
     pub(crate) fn pop(
         &self,
         beginning_fragment: bool,
@@ -237,15 +320,20 @@ impl PendingQueue {
         };
 
         if let Some(p) = &popped {
-            let user_data_len = p.user_data.len();
-            self.n_bytes.fetch_sub(user_data_len, Ordering::SeqCst);
-            self.queue_len.fetch_sub(1, Ordering::SeqCst);
-            self.semaphore.add_permits(user_data_len);
+            self.release(p);
         }
 
         popped
     }
 
+    /// Accounts for a chunk that left the queue.
+    fn release(&self, c: &ChunkPayloadData) {
+        let user_data_len = c.user_data.len();
+        self.n_bytes.fetch_sub(user_data_len, Ordering::SeqCst);
+        self.queue_len.fetch_sub(1, Ordering::SeqCst);
+        self.semaphore.add_permits(user_data_len);
+    }
+
     pub(crate) fn get_num_bytes(&self) -> usize {
         self.n_bytes.load(Ordering::SeqCst)
     }
@@ -439,6 +439,69 @@ async fn test_pending_queue_append() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_pending_queue_interleaving() -> Result<()> {
+    let pq = PendingQueue::new();
+    pq.set_interleaving(true);
+
+    // A fragmented message on stream 1 followed by small messages on streams 2
+    // and 3 and a fragmented unordered one on stream 2.
+    for (tsn, si, unordered, frag) in [
+        (0, 1, false, FRAG_BEGIN),
+        (1, 1, false, FRAG_MIDDLE),
+        (2, 1, false, FRAG_END),
+        (3, 2, false, NO_FRAGMENT),
+        (4, 3, false, NO_FRAGMENT),
+        (5, 2, true, FRAG_BEGIN),
+        (6, 2, true, FRAG_END),
+    ] {
+        let mut c = make_data_chunk(tsn, unordered, frag);
+        c.stream_identifier = si;
+        c.fragment_sequence_number = tsn;
+        pq.push(c).await;
+    }
+
+    // Streams take turns; each stream keeps its own order.
+    let expects = vec![0, 5, 4, 1, 6, 2, 3];
+
+    for exp in expects {
+        let c = pq.peek();
+        assert!(c.is_some(), "peek error");
+        let c = c.unwrap();
+        assert_eq!(c.tsn, exp, "TSN should match");
+        let result = pq.pop_interleaved(&c);
+        assert_eq!(
+            result.map(|c| c.tsn),
+            Some(exp),
+            "should pop the peeked chunk"
+        );
+    }
+    assert!(pq.is_empty(), "queue should be empty");
+
+    // A chunk appended between peek and pop must not change what is popped,
+    // even if its stream is next in turn now.
+    let mut c = make_data_chunk(7, false, NO_FRAGMENT);
+    c.stream_identifier = 1;
+    pq.push(c).await;
+    let peeked = pq.peek().unwrap();
+    let mut c = make_data_chunk(8, false, NO_FRAGMENT);
+    c.stream_identifier = 0;
+    pq.push(c).await;
+    assert_eq!(
+        pq.peek().map(|c| c.tsn),
+        Some(8),
+        "stream 0 is next in turn"
+    );
+    assert_eq!(
+        pq.pop_interleaved(&peeked).map(|c| c.tsn),
+        Some(7),
+        "should pop the peeked chunk"
+    );
+    assert_eq!(pq.len(), 1, "len mismatch");
+
+    Ok(())
+}
+
 ///////////////////////////////////////////////////////////////////
 //reassembly_queue_test
 ///////////////////////////////////////////////////////////////////
@@ -995,3 +1058,78 @@ fn test_chunk_set_incomplete_chunk_set_no_contiguous_tsn() -> Result<()> {
     );
     Ok(())
 }
+
+fn make_idata_chunk(
+    tsn: u32,
+    unordered: bool,
+    message_identifier: u32,
+    fragment_sequence_number: u32,
+    frag: usize,
+    user_data: &'static [u8],
+) -> ChunkPayloadData {
+    ChunkPayloadData {
+        interleaved: true,
+        payload_type: if fragment_sequence_number == 0 {
+            PayloadProtocolIdentifier::Binary
+        } else {
+            PayloadProtocolIdentifier::Unknown
+        },
+        stream_sequence_number: message_identifier as u16,
+        message_identifier,
+        fragment_sequence_number,
+        user_data: Bytes::from_static(user_data),
+        ..make_data_chunk(tsn, unordered, frag)
+    }
+}
+
+#[test]
+fn test_reassembly_queue_interleaved_ordered_fragments() -> Result<()> {
+    let mut rq = ReassemblyQueue::new(0);
+
+    // TSNs 2 and 4 carried fragments of messages on other streams.
+    assert!(!rq.push(make_idata_chunk(5, false, 0, 2, FRAG_END, b"G")));
+    assert!(!rq.push(make_idata_chunk(1, false, 0, 0, FRAG_BEGIN, b"ABC")));
+    assert!(rq.push(make_idata_chunk(3, false, 0, 1, FRAG_MIDDLE, b"DEF")));
+    assert!(rq.is_readable(), "should be readable");
+
+    let mut buf = vec![0u8; 16];
+    let (n, ppi) = rq.read(&mut buf)?;
+    assert_eq!(&buf[..n], b"ABCDEFG", "data should match");
+    assert_eq!(
+        ppi,
+        PayloadProtocolIdentifier::Binary,
+        "PPI should be taken from the first fragment"
+    );
+    assert_eq!(rq.next_ssn, 1, "next_ssn should advance");
+    assert_eq!(rq.get_num_bytes(), 0, "num bytes mismatch");
+
+    Ok(())
+}
+
+#[test]
+fn test_reassembly_queue_interleaved_unordered_fragments() -> Result<()> {
+    let mut rq = ReassemblyQueue::new(0);
+
+    // Two unordered messages whose fragments are interleaved.
+    assert!(!rq.push(make_idata_chunk(1, true, 0, 0, FRAG_BEGIN, b"AB")));
+    assert!(!rq.push(make_idata_chunk(2, true, 1, 0, FRAG_BEGIN, b"XY")));
+    assert!(!rq.push(make_idata_chunk(3, true, 0, 1, FRAG_MIDDLE, b"CD")));
+    assert!(rq.push(make_idata_chunk(4, true, 1, 1, FRAG_END, b"Z")));
+    assert_eq!(
+        rq.unordered_chunks.len(),
+        2,
+        "MID 0 should still be pending"
+    );
+
+    let mut buf = vec![0u8; 16];
+    let (n, _) = rq.read(&mut buf)?;
+    assert_eq!(&buf[..n], b"XYZ", "data should match");
+
+    // MID 0 is abandoned by the sender.
+    rq.forward_mid_for_unordered(0);
+    assert!(rq.unordered_chunks.is_empty(), "MID 0 should be removed");
+    assert_eq!(rq.get_num_bytes(), 0, "num bytes mismatch");
+    assert!(!rq.is_readable(), "should not be readable");
+
+    Ok(())
+}
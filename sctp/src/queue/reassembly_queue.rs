@@ -14,6 +14,10 @@ fn sort_chunks_by_tsn(c: &mut [ChunkPayloadData]) {
     });
 }
 
+fn sort_chunks_by_fsn(c: &mut [ChunkPayloadData]) {
+    c.sort_by_key(|c| c.fragment_sequence_number);
+}
+
 fn sort_chunks_by_ssn(c: &mut [ChunkSet]) {
     c.sort_by(|a, b| {
         if sna16lt(a.ssn, b.ssn) {
@@ -50,9 +54,19 @@ impl ChunkSet {
             }
         }
 
+        // Only the first fragment of an I-DATA message carries the PPI
+        if chunk.beginning_fragment {
+            self.ppi = chunk.payload_type;
+        }
+
         // append and sort
+        let interleaved = chunk.interleaved;
         self.chunks.push(chunk);
-        sort_chunks_by_tsn(&mut self.chunks);
+        if interleaved {
+            sort_chunks_by_fsn(&mut self.chunks);
+        } else {
+            sort_chunks_by_tsn(&mut self.chunks);
+        }
 
         // Check if we now have a complete set
         self.is_complete()
@@ -64,6 +78,7 @@ impl ChunkSet {
         //   1. Begins with beginningFragment set to true
         //   2. Ends with endingFragment set to true
         //   3. TSN monotinically increase by 1 from beginning to end
+        //      (FSN for I-DATA chunks)
 
         // 0.
         let n_chunks = self.chunks.len();
@@ -84,6 +99,16 @@ impl ChunkSet {
         // 3.
         let mut last_tsn = 0u32;
         for (i, c) in self.chunks.iter().enumerate() {
+            if c.interleaved {
+                // RFC 8260 Sec 2.1: fragments of other messages may sit
+                // between the fragments of an I-DATA message, so only the FSN
+                // is contiguous.
+                if c.fragment_sequence_number != i as u32 {
+                    return false;
+                }
+                continue;
+            }
+
             if i > 0 {
                 // Fragments must have contiguous TSN
                 // From RFC 4960 Section 3.3.1:
@@ -138,15 +163,23 @@ impl ReassemblyQueue {
         }
 
         if chunk.unordered {
+            let (interleaved, message_identifier) = (chunk.interleaved, chunk.message_identifier);
+
             // First, insert into unordered_chunks array
             //atomic.AddUint64(&r.n_bytes, uint64(len(chunk.userData)))
             self.n_bytes += chunk.user_data.len();
             self.unordered_chunks.push(chunk);
             sort_chunks_by_tsn(&mut self.unordered_chunks);
 
-            // Scan unordered_chunks that are contiguous (in TSN)
+            // Scan unordered_chunks that are contiguous (in TSN), or that share
+            // the MID for I-DATA chunks.
             // If found, append the complete set to the unordered array
-            if let Some(cset) = self.find_complete_unordered_chunk_set() {
+            let cset = if interleaved {
+                self.find_complete_unordered_interleaved_chunk_set(message_identifier)
+            } else {
+                self.find_complete_unordered_chunk_set()
+            };
+            if let Some(cset) = cset {
                 self.unordered.push(cset);
                 return true;
             }
@@ -235,6 +268,29 @@ impl ReassemblyQueue {
         Some(chunk_set)
     }
 
+    /// Gathers the fragments of the unordered I-DATA message with the given
+    /// MID, and removes them from unordered_chunks if the message is complete.
+    pub(crate) fn find_complete_unordered_interleaved_chunk_set(
+        &mut self,
+        message_identifier: u32,
+    ) -> Option<ChunkSet> {
+        let mut chunk_set = ChunkSet::new(0, PayloadProtocolIdentifier::Unknown);
+        for c in &self.unordered_chunks {
+            if c.message_identifier == message_identifier {
+                chunk_set.push(c.clone());
+            }
+        }
+
+        if !chunk_set.is_complete() {
+            return None;
+        }
+
+        self.unordered_chunks
+            .retain(|c| c.message_identifier != message_identifier);
+
+        Some(chunk_set)
+    }
+
     pub(crate) fn is_readable(&self) -> bool {
         // Check unordered first
         if !self.unordered.is_empty() {
@@ -339,6 +395,22 @@ impl ReassemblyQueue {
         }
     }
 
+    /// Remove the fragments of unordered I-DATA messages with a MID equal to
+    /// or older than `message_identifier`, as reported by I-FORWARD-TSN.
+    /// Fragments of other messages stay, since with interleaving they may
+    /// have TSNs older than the new cumulative TSN.
+    pub(crate) fn forward_mid_for_unordered(&mut self, message_identifier: u32) {
+        let num_bytes = self
+            .unordered_chunks
+            .iter()
+            .filter(|c| sna32lte(c.message_identifier, message_identifier))
+            .fold(0, |n, c| n + c.user_data.len());
+        self.subtract_num_bytes(num_bytes);
+
+        self.unordered_chunks
+            .retain(|c| !sna32lte(c.message_identifier, message_identifier));
+    }
+
     pub(crate) fn subtract_num_bytes(&mut self, n_bytes: usize) {
         if self.n_bytes >= n_bytes {
             self.n_bytes -= n_bytes;
@@ -72,7 +72,12 @@ pub struct Stream {
     pub(crate) default_payload_type: AtomicU32, //PayloadProtocolIdentifier,
     pub(crate) reassembly_queue: Mutex<ReassemblyQueue>,
     pub(crate) sequence_number: AtomicU16,
+    /// Message Identifiers for I-DATA (RFC 8260). The ordered one is bumped
+    /// along with sequence_number, so its low 16 bits always match it.
+    pub(crate) message_identifier: AtomicU32,
+    pub(crate) unordered_message_identifier: AtomicU32,
     pub(crate) read_notifier: Notify,
+    pub(crate) write_notifier: Notify,
     pub(crate) read_shutdown: AtomicBool,
     pub(crate) write_shutdown: AtomicBool,
     pub(crate) unordered: AtomicBool,
@@ -80,6 +85,7 @@ pub struct Stream {
     pub(crate) reliability_value: AtomicU32,
     pub(crate) buffered_amount: AtomicUsize,
     pub(crate) buffered_amount_low: AtomicUsize,
+    pub(crate) max_send_buffer_size: AtomicUsize,
     pub(crate) on_buffered_amount_low: ArcSwapOption<Mutex<OnBufferedAmountLowFn>>,
     pub(crate) name: String,
 }
@@ -95,6 +101,11 @@ impl fmt::Debug for Stream {
             .field("default_payload_type", &self.default_payload_type)
             .field("reassembly_queue", &self.reassembly_queue)
             .field("sequence_number", &self.sequence_number)
+            .field("message_identifier", &self.message_identifier)
+            .field(
+                "unordered_message_identifier",
+                &self.unordered_message_identifier,
+            )
             .field("read_shutdown", &self.read_shutdown)
             .field("write_shutdown", &self.write_shutdown)
             .field("unordered", &self.unordered)
@@ -102,6 +113,7 @@ impl fmt::Debug for Stream {
             .field("reliability_value", &self.reliability_value)
             .field("buffered_amount", &self.buffered_amount)
             .field("buffered_amount_low", &self.buffered_amount_low)
+            .field("max_send_buffer_size", &self.max_send_buffer_size)
             .field("name", &self.name)
             .finish()
     }
@@ -128,7 +140,10 @@ impl Stream {
             default_payload_type: AtomicU32::new(0), //PayloadProtocolIdentifier::Unknown,
             reassembly_queue: Mutex::new(ReassemblyQueue::new(stream_identifier)),
             sequence_number: AtomicU16::new(0),
+            message_identifier: AtomicU32::new(0),
+            unordered_message_identifier: AtomicU32::new(0),
             read_notifier: Notify::new(),
+            write_notifier: Notify::new(),
             read_shutdown: AtomicBool::new(false),
             write_shutdown: AtomicBool::new(false),
             unordered: AtomicBool::new(false),
@@ -136,6 +151,7 @@ impl Stream {
             reliability_value: AtomicU32::new(0),
             buffered_amount: AtomicUsize::new(0),
             buffered_amount_low: AtomicUsize::new(0),
+            max_send_buffer_size: AtomicUsize::new(0),
             on_buffered_amount_low: ArcSwapOption::empty(),
             name,
         }
@@ -146,12 +162,41 @@ impl Stream {
         self.stream_identifier
     }
 
+    /// is_association_closed returns true once the association this stream belongs to has
+    /// been closed, e.g. because it was shut down or aborted by the peer.
+    pub fn is_association_closed(&self) -> bool {
+        self.get_state() == AssociationState::Closed
+    }
+
     /// set_default_payload_type sets the default payload type used by write.
     pub fn set_default_payload_type(&self, default_payload_type: PayloadProtocolIdentifier) {
         self.default_payload_type
             .store(default_payload_type as u32, Ordering::SeqCst);
     }
 
+    /// default_payload_type returns the payload type used by write.
+    pub fn default_payload_type(&self) -> PayloadProtocolIdentifier {
+        PayloadProtocolIdentifier::from(self.default_payload_type.load(Ordering::SeqCst))
+    }
+
+    /// is_unordered returns whether messages written to this stream are sent
+    /// with the unordered flag, so the peer delivers them as soon as they are
+    /// reassembled.
+    pub fn is_unordered(&self) -> bool {
+        self.unordered.load(Ordering::SeqCst)
+    }
+
+    /// reliability_type returns the partial reliability policy of this stream.
+    pub fn reliability_type(&self) -> ReliabilityType {
+        self.reliability_type.load(Ordering::SeqCst).into()
+    }
+
+    /// reliability_value returns the max retransmits or lifetime (in ms) used
+    /// together with reliability_type.
+    pub fn reliability_value(&self) -> u32 {
+        self.reliability_value.load(Ordering::SeqCst)
+    }
+
     /// set_reliability_params sets reliability parameters for this stream.
     pub fn set_reliability_params(&self, unordered: bool, rel_type: ReliabilityType, rel_val: u32) {
         log::debug!(
@@ -258,8 +303,25 @@ impl Stream {
         }
     }
 
+    pub(crate) async fn handle_iforward_tsn_for_unordered(&self, message_identifier: u32) {
+        // Remove the fragments of the abandoned unordered messages from the
+        // reassembly_queue.
+        let readable = {
+            let mut reassembly_queue = self.reassembly_queue.lock().await;
+            reassembly_queue.forward_mid_for_unordered(message_identifier);
+            reassembly_queue.is_readable()
+        };
+
+        // Notify the reader asynchronously if there's a data chunk to read.
+        if readable {
+            self.read_notifier.notify_one();
+        }
+    }
+
     /// Writes `p` to the DTLS connection with the default Payload Protocol Identifier.
     ///
+    /// Waits while the send buffer is full, see [`Stream::max_send_buffer_size`].
+    ///
     /// Returns an error if the write half of this stream is shutdown or `p` is too large.
     pub async fn write(&self, p: &Bytes) -> Result<usize> {
         self.write_sctp(p, self.default_payload_type.load(Ordering::SeqCst).into())
@@ -268,14 +330,50 @@ impl Stream {
 
     /// Writes `p` to the DTLS connection with the given Payload Protocol Identifier.
     ///
+    /// Waits while the send buffer is full, see [`Stream::max_send_buffer_size`].
+    ///
     /// Returns an error if the write half of this stream is shutdown or `p` is too large.
     pub async fn write_sctp(&self, p: &Bytes, ppi: PayloadProtocolIdentifier) -> Result<usize> {
+        self.wait_for_send_buffer(p.len()).await?;
         let chunks = self.prepare_write(p, ppi)?;
         self.send_payload_data(chunks).await?;
 
         Ok(p.len())
     }
 
+    /// wait_for_send_buffer waits until `len` more bytes fit in the send buffer. Data stays
+    /// buffered until the peer acknowledges it, so this also holds writers back while the
+    /// peer's receive window is full. A message is always accepted into an empty buffer, even
+    /// if it exceeds the limit.
+    async fn wait_for_send_buffer(&self, len: usize) -> Result<()> {
+        loop {
+            // Register before checking, so a release in between isn't missed
+            let notified = self.write_notifier.notified();
+
+            if self.write_shutdown.load(Ordering::SeqCst) {
+                return Err(Error::ErrStreamClosed);
+            }
+
+            let max_send_buffer_size = self.max_send_buffer_size.load(Ordering::SeqCst);
+            let buffered_amount = self.buffered_amount.load(Ordering::SeqCst);
+            if max_send_buffer_size == 0
+                || buffered_amount == 0
+                || buffered_amount + len <= max_send_buffer_size
+            {
+                return Ok(());
+            }
+
+            log::trace!(
+                "[{}] send buffer full ({} + {} > {}), waiting",
+                self.name,
+                buffered_amount,
+                len,
+                max_send_buffer_size
+            );
+            notified.await;
+        }
+    }
+
     /// common stuff for write and try_write
     fn prepare_write(
         &self,
@@ -314,6 +412,16 @@ impl Stream {
 
         let mut chunks = vec![];
 
+        // The chunks are only sent as I-DATA if interleaving has been
+        // negotiated, but the MID and FSN are filled in regardless.
+        let message_identifier = if unordered {
+            self.unordered_message_identifier
+                .fetch_add(1, Ordering::SeqCst)
+        } else {
+            self.message_identifier.fetch_add(1, Ordering::SeqCst)
+        };
+        let mut fragment_sequence_number = 0;
+
         let head_abandoned = Arc::new(AtomicBool::new(false));
         let head_all_inflight = Arc::new(AtomicBool::new(false));
         while remaining != 0 {
@@ -332,6 +440,8 @@ impl Stream {
                 immediate_sack: false,
                 payload_type: ppi,
                 stream_sequence_number: self.sequence_number.load(Ordering::SeqCst),
+                message_identifier,
+                fragment_sequence_number,
                 abandoned: head_abandoned.clone(), // all fragmented chunks use the same abandoned
                 all_inflight: head_all_inflight.clone(), // all fragmented chunks use the same all_inflight
                 ..Default::default()
@@ -341,6 +451,7 @@ impl Stream {
 
             remaining -= fragment_size;
             i += fragment_size;
+            fragment_sequence_number += 1;
         }
 
         // RFC 4960 Sec 6.6
@@ -378,6 +489,7 @@ impl Stream {
 
         if how == Shutdown::Write || how == Shutdown::Both {
             self.write_shutdown.store(true, Ordering::SeqCst);
+            self.write_notifier.notify_waiters();
         }
 
         if (how == Shutdown::Read || how == Shutdown::Both)
@@ -415,6 +527,20 @@ impl Stream {
         self.buffered_amount_low.store(th, Ordering::SeqCst);
     }
 
+    /// max_send_buffer_size returns the number of bytes this stream may have buffered for
+    /// sending before writes wait for the peer to acknowledge data. Zero means unlimited.
+    pub fn max_send_buffer_size(&self) -> usize {
+        self.max_send_buffer_size.load(Ordering::SeqCst)
+    }
+
+    /// set_max_send_buffer_size is used to update the send buffer limit.
+    /// See max_send_buffer_size().
+    pub fn set_max_send_buffer_size(&self, max_send_buffer_size: usize) {
+        self.max_send_buffer_size
+            .store(max_send_buffer_size, Ordering::SeqCst);
+        self.write_notifier.notify_waiters();
+    }
+
     /// on_buffered_amount_low sets the callback handler which would be called when the number of
     /// bytes of outgoing data buffered is lower than the threshold.
     pub fn on_buffered_amount_low(&self, f: OnBufferedAmountLowFn) {
@@ -422,11 +548,22 @@ impl Stream {
             .store(Some(Arc::new(Mutex::new(f))));
     }
 
-    /// This method is called by association's read_loop (go-)routine to notify this stream
-    /// of the specified amount of outgoing data has been delivered to the peer.
+    /// on_buffer_released releases the buffer and invokes the on_buffered_amount_low handler
+    /// in place. The association instead runs the handler on its own task.
+    #[cfg(test)]
     pub(crate) async fn on_buffer_released(&self, n_bytes_released: i64) {
+        if self.release_buffer(n_bytes_released) {
+            self.on_buffered_amount_low_event().await;
+        }
+    }
+
+    /// This method is called by association's read_loop (go-)routine to notify this stream
+    /// of the specified amount of outgoing data has been delivered to the peer. It returns true
+    /// if the buffered amount dropped to or below the threshold, in which case the caller is
+    /// responsible for invoking the on_buffered_amount_low handler.
+    pub(crate) fn release_buffer(&self, n_bytes_released: i64) -> bool {
         if n_bytes_released <= 0 {
-            return;
+            return false;
         }
 
         let from_amount = self.buffered_amount.load(Ordering::SeqCst);
@@ -445,6 +582,7 @@ impl Stream {
 
             from_amount - n_bytes_released as usize
         };
+        self.write_notifier.notify_waiters();
 
         let buffered_amount_low = self.buffered_amount_low.load(Ordering::SeqCst);
 
@@ -456,11 +594,14 @@ impl Stream {
             buffered_amount_low,
         );
 
-        if from_amount > buffered_amount_low && new_amount <= buffered_amount_low {
-            if let Some(handler) = &*self.on_buffered_amount_low.load() {
-                let mut f = handler.lock().await;
-                f().await;
-            }
+        from_amount > buffered_amount_low && new_amount <= buffered_amount_low
+    }
+
+    /// on_buffered_amount_low_event invokes the on_buffered_amount_low handler, if any.
+    pub(crate) async fn on_buffered_amount_low_event(&self) {
+        if let Some(handler) = &*self.on_buffered_amount_low.load() {
+            let mut f = handler.lock().await;
+            f().await;
         }
     }
 
@@ -1,5 +1,6 @@
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
 use portable_atomic::AtomicU32;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -25,6 +26,25 @@ fn test_stream_buffered_amount() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_stream_reliability_params() -> Result<()> {
+    let s = Stream::default();
+
+    assert_eq!(s.default_payload_type(), PayloadProtocolIdentifier::Unknown);
+    assert!(!s.is_unordered());
+    assert_eq!(s.reliability_type(), ReliabilityType::Reliable);
+    assert_eq!(s.reliability_value(), 0);
+
+    s.set_default_payload_type(PayloadProtocolIdentifier::Binary);
+    s.set_reliability_params(true, ReliabilityType::Rexmit, 3);
+    assert_eq!(s.default_payload_type(), PayloadProtocolIdentifier::Binary);
+    assert!(s.is_unordered());
+    assert_eq!(s.reliability_type(), ReliabilityType::Rexmit);
+    assert_eq!(s.reliability_value(), 3);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stream_amount_on_buffered_amount_low() -> Result<()> {
     let s = Stream::default();
@@ -147,6 +167,65 @@ async fn test_stream() -> std::result::Result<(), io::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_stream_write_backpressure() -> Result<()> {
+    let s = Arc::new(Stream::new(
+        "test_stream_write_backpressure".to_owned(),
+        0,
+        4096,
+        Arc::new(AtomicU32::new(4096)),
+        Arc::new(AtomicU8::new(AssociationState::Established as u8)),
+        None,
+        Arc::new(PendingQueue::new()),
+    ));
+    s.set_max_send_buffer_size(8);
+    assert_eq!(s.max_send_buffer_size(), 8);
+
+    // A message larger than the limit is still accepted into an empty buffer
+    let n = s.write(&Bytes::from_static(b"0123456789")).await?;
+    assert_eq!(n, 10);
+    assert_eq!(s.buffered_amount(), 10);
+
+    // The next write waits until enough data has been acknowledged
+    let writer = {
+        let s = Arc::clone(&s);
+        tokio::spawn(async move { s.write(&Bytes::from_static(b"abcd")).await })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(
+        !writer.is_finished(),
+        "write should wait for the send buffer"
+    );
+
+    s.on_buffer_released(6).await; // bufferedAmount = 4
+    let n = tokio::time::timeout(Duration::from_secs(1), writer)
+        .await
+        .expect("write should resume once data is acknowledged")
+        .unwrap()?;
+    assert_eq!(n, 4);
+    assert_eq!(s.buffered_amount(), 8);
+
+    // Shutting down the write half releases a waiting writer
+    let writer = {
+        let s = Arc::clone(&s);
+        tokio::spawn(async move { s.write(&Bytes::from_static(b"e")).await })
+    };
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(
+        !writer.is_finished(),
+        "write should wait for the send buffer"
+    );
+
+    s.shutdown(Shutdown::Write).await?;
+    let result = tokio::time::timeout(Duration::from_secs(1), writer)
+        .await
+        .expect("shutdown should wake up the writer")
+        .unwrap();
+    assert_eq!(result, Err(Error::ErrStreamClosed));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_poll_stream() -> std::result::Result<(), io::Error> {
     let s = Arc::new(Stream::new(
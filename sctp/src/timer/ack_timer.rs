@@ -5,6 +5,10 @@ use tokio::sync::{mpsc, Mutex};
 use tokio::time::Duration;
 
 pub(crate) const ACK_INTERVAL: Duration = Duration::from_millis(200);
+/// RFC 4960 Sec 6.2: an acknowledgement SHOULD be generated within 200 ms
+/// of the arrival of any unacknowledged DATA chunk, and MUST be generated
+/// within 500 ms.
+pub(crate) const MAX_ACK_INTERVAL: Duration = Duration::from_millis(500);
 
 /// ackTimerObserver is the interface to an ack timer observer.
 #[async_trait]
@@ -148,6 +148,7 @@ fn test_marshal() -> Result<()> {
                 }],
                 encryption_key: Some("prompt".to_string()),
                 attributes: vec![Attribute::new("sendrecv".to_string(), None)],
+                other: vec![],
             },
             MediaDescription {
                 media_name: MediaName {
@@ -167,8 +168,10 @@ fn test_marshal() -> Result<()> {
                     "rtpmap".to_string(),
                     Some("99 h263-1998/90000".to_string()),
                 )],
+                other: vec![],
             },
         ],
+        other: vec![],
     };
 
     let actual = sd.marshal();
@@ -605,3 +608,296 @@ fn test_unmarshal_non_nil_address() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_unmarshal_connection_address() -> Result<()> {
+    let tests = vec![
+        ("IN IP4 203.0.113.1", "203.0.113.1", None, None),
+        ("IN IP4 224.2.17.12/127", "224.2.17.12", Some(127), None),
+        ("IN IP4 224.2.1.1/127/3", "224.2.1.1", Some(127), Some(3)),
+        ("IN IP6 ff15::101/3", "ff15::101", None, Some(3)),
+    ];
+
+    for (value, address, ttl, range) in tests {
+        let input = format!("v=0\r\no=0 0 0 IN IP4 0\r\ns=0\r\nc={value}\r\nt=0 0\r\n");
+        let mut reader = Cursor::new(input.as_bytes());
+        let sdp = SessionDescription::unmarshal(&mut reader)?;
+
+        let actual = sdp
+            .connection_information
+            .as_ref()
+            .and_then(|c| c.address.as_ref())
+            .unwrap_or_else(|| panic!("{value}: missing address"));
+        assert_eq!(actual.address, address, "{value}");
+        assert_eq!(actual.ttl, ttl, "{value}");
+        assert_eq!(actual.range, range, "{value}");
+
+        assert_eq!(sdp.marshal(), input, "{value}");
+    }
+
+    for value in [
+        "IN IP4 224.2.1.1/ttl",
+        "IN IP4 224.2.1.1/127/3/1",
+        "IN IP6 ff15::101/3/1",
+    ] {
+        let input = format!("v=0\r\no=0 0 0 IN IP4 0\r\ns=0\r\nc={value}\r\nt=0 0\r\n");
+        let mut reader = Cursor::new(input.as_bytes());
+        assert!(
+            SessionDescription::unmarshal(&mut reader).is_err(),
+            "{value}: expected error"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_builder() -> Result<()> {
+    let mut sdp = SessionDescription::new_jsep_session_description(false)
+        .with_ice_credentials("ufrag".to_owned(), "pwd".to_owned())
+        .with_fingerprint("sha-256".to_owned(), "AB:CD".to_owned())
+        .with_media(
+            MediaDescription::new_jsep_media_description("video".to_owned(), vec![])
+                .with_codec(96, "VP8".to_owned(), 90000, 0, String::new())
+                .with_codec(
+                    97,
+                    "H264".to_owned(),
+                    90000,
+                    0,
+                    "packetization-mode=1".to_owned(),
+                )
+                .with_rtcp_fb(96, "nack pli".to_owned())
+                .with_bandwidth("AS".to_owned(), 512),
+        );
+    sdp.origin.session_id = 1;
+    sdp.origin.session_version = 2;
+
+    let expected = "v=0\r\n\
+o=- 1 2 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=ice-ufrag:ufrag\r\n\
+a=ice-pwd:pwd\r\n\
+a=fingerprint:sha-256 AB:CD\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+c=IN IP4 0.0.0.0\r\n\
+b=AS:512\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtpmap:97 H264/90000\r\n\
+a=fmtp:97 packetization-mode=1\r\n\
+a=rtcp-fb:96 nack pli\r\n";
+    assert_eq!(sdp.marshal(), expected);
+
+    let mut reader = Cursor::new(expected.as_bytes());
+    let parsed = SessionDescription::unmarshal(&mut reader)?;
+    assert_eq!(parsed.marshal(), expected);
+
+    Ok(())
+}
+
+const ICE_ATTRIBUTES_SDP: &str = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=ice-ufrag:session\r\n\
+a=ice-pwd:sessionpwd\r\n\
+a=ice-options:trickle ice2\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+a=ice-ufrag:media\r\n\
+a=ice-pwd:mediapwd\r\n\
+a=candidate:1 1 udp 2130706431 10.0.0.1 5000 typ host\r\n\
+a=candidate:2 1 udp 1694498815 1.2.3.4 5001 typ srflx raddr 10.0.0.1 rport 5000\r\n\
+a=end-of-candidates\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96\r\n";
+
+#[test]
+fn test_ice_attributes() -> Result<()> {
+    let mut reader = Cursor::new(ICE_ATTRIBUTES_SDP.as_bytes());
+    let sdp = SessionDescription::unmarshal(&mut reader)?;
+
+    assert_eq!(sdp.ice_ufrag(), Some("session"));
+    assert_eq!(sdp.ice_pwd(), Some("sessionpwd"));
+    assert_eq!(sdp.ice_options(), vec!["trickle", "ice2"]);
+    assert!(sdp.candidates().is_empty());
+    assert!(!sdp.has_end_of_candidates());
+
+    let audio = &sdp.media_descriptions[0];
+    assert_eq!(audio.ice_ufrag(), Some("media"));
+    assert_eq!(audio.ice_pwd(), Some("mediapwd"));
+    assert!(audio.ice_options().is_empty());
+    assert_eq!(
+        audio.candidates(),
+        vec![
+            "1 1 udp 2130706431 10.0.0.1 5000 typ host",
+            "2 1 udp 1694498815 1.2.3.4 5001 typ srflx raddr 10.0.0.1 rport 5000",
+        ]
+    );
+    assert!(audio.has_end_of_candidates());
+
+    let video = &sdp.media_descriptions[1];
+    assert_eq!(video.ice_ufrag(), None);
+    assert!(video.candidates().is_empty());
+    assert!(!video.has_end_of_candidates());
+
+    let md = MediaDescription::default()
+        .with_ice_credentials("u".to_owned(), "p".to_owned())
+        .with_ice_options("trickle".to_owned())
+        .with_candidate("1 1 udp 2130706431 10.0.0.1 5000 typ host".to_owned())
+        .with_end_of_candidates();
+    assert_eq!(md.ice_ufrag(), Some("u"));
+    assert_eq!(md.ice_pwd(), Some("p"));
+    assert_eq!(md.ice_options(), vec!["trickle"]);
+    assert_eq!(md.candidates().len(), 1);
+    assert!(md.has_end_of_candidates());
+
+    Ok(())
+}
+
+#[test]
+fn test_extmaps() -> Result<()> {
+    let input = "v=0\r\n\
+o=- 0 0 IN IP4 0.0.0.0\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=extmap-allow-mixed\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+a=extmap:1 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=extmap:2/recvonly urn:3gpp:video-orientation\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+a=extmap-allow-mixed\r\n";
+
+    let mut reader = Cursor::new(input.as_bytes());
+    let sdp = SessionDescription::unmarshal(&mut reader)?;
+    assert!(sdp.has_extmap_allow_mixed());
+
+    let video = &sdp.media_descriptions[0];
+    assert!(!video.has_extmap_allow_mixed());
+    let extmaps = video.extmaps()?;
+    assert_eq!(extmaps.len(), 2);
+    assert_eq!(extmaps[0].value, 1);
+    assert_eq!(
+        extmaps[0].uri.as_ref().map(|u| u.as_str()),
+        Some("urn:ietf:params:rtp-hdrext:sdes:mid")
+    );
+    assert_eq!(extmaps[1].value, 2);
+    assert_eq!(extmaps[1].direction, crate::direction::Direction::RecvOnly);
+
+    let audio = &sdp.media_descriptions[1];
+    assert!(audio.has_extmap_allow_mixed());
+    assert!(audio.extmaps()?.is_empty());
+
+    Ok(())
+}
+
+fn bundle_media(mid: &str) -> MediaDescription {
+    MediaDescription::new_jsep_media_description("audio".to_owned(), vec![])
+        .with_value_attribute(ATTR_KEY_MID.to_owned(), mid.to_owned())
+}
+
+#[test]
+fn test_bundle_groups() -> Result<()> {
+    let offer = SessionDescription::new_jsep_session_description(false)
+        .with_bundle_group(&["0", "1", "2"])
+        .with_value_attribute(ATTR_KEY_GROUP.to_owned(), "LS 0 1".to_owned())
+        .with_media(bundle_media("0"))
+        .with_media(bundle_media("1"))
+        .with_media(bundle_media("2").with_bundle_only());
+
+    assert_eq!(
+        offer.attribute(ATTR_KEY_GROUP),
+        Some(&"BUNDLE 0 1 2".to_owned())
+    );
+    assert_eq!(offer.bundle_groups(), vec![vec!["0", "1", "2"]]);
+    assert_eq!(offer.bundle_mids(), ["0", "1", "2"].into_iter().collect());
+    assert!(!offer.media_descriptions[0].is_bundle_only());
+    assert!(offer.media_descriptions[2].is_bundle_only());
+
+    // Rejecting a media description by leaving it out of the group is allowed
+    let answer = SessionDescription::new_jsep_session_description(false)
+        .with_bundle_group(&["0", "1"])
+        .with_media(bundle_media("0"))
+        .with_media(bundle_media("1"));
+    answer.validate_bundle_answer(&offer)?;
+
+    // An answer without any BUNDLE group is valid too
+    let answer =
+        SessionDescription::new_jsep_session_description(false).with_media(bundle_media("0"));
+    answer.validate_bundle_answer(&offer)?;
+
+    // Mids which weren't offered in the group can't be bundled
+    let answer = SessionDescription::new_jsep_session_description(false)
+        .with_bundle_group(&["0", "3"])
+        .with_media(bundle_media("0"))
+        .with_media(bundle_media("3"));
+    assert!(answer.validate_bundle_answer(&offer).is_err());
+
+    // Every bundled mid must have a media description in the answer
+    let answer = SessionDescription::new_jsep_session_description(false)
+        .with_bundle_group(&["0", "1"])
+        .with_media(bundle_media("0"));
+    assert!(answer.validate_bundle_answer(&offer).is_err());
+
+    Ok(())
+}
+
+const NON_CONFORMANT_SDP: &str = "v=0\r\n\
+o=- 4596489990601351948 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+x=vendor-extension\r\n\
+c=IN IP4 127.0.0.1\r\n\
+b=bogus\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+y=media-extension\r\n\
+b=AS:64\r\n\
+c=IN IPX 0.0.0.0\r\n\
+a=mid:0\r\n\
+a=rtpmap:111 opus/48000/2\r\n";
+
+#[test]
+fn test_unmarshal_lenient() -> Result<()> {
+    let mut reader = Cursor::new(NON_CONFORMANT_SDP.as_bytes());
+    assert!(SessionDescription::unmarshal(&mut reader).is_err());
+
+    let mut reader = Cursor::new(NON_CONFORMANT_SDP.as_bytes());
+    let sdp = SessionDescription::unmarshal_lenient(&mut reader)?;
+
+    assert_eq!(sdp.other, vec!["x=vendor-extension", "b=bogus"]);
+    assert!(sdp.connection_information.is_some());
+    assert!(sdp.bandwidth.is_empty());
+    assert_eq!(sdp.bundle_groups(), vec![vec!["0"]]);
+
+    let audio = &sdp.media_descriptions[0];
+    assert_eq!(audio.other, vec!["y=media-extension", "c=IN IPX 0.0.0.0"]);
+    assert_eq!(audio.bandwidth.len(), 1);
+    assert!(audio.connection_information.is_none());
+    assert_eq!(audio.attribute("mid"), Some(Some("0")));
+
+    let expected = "v=0\r\n\
+o=- 4596489990601351948 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+c=IN IP4 127.0.0.1\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0\r\n\
+x=vendor-extension\r\n\
+b=bogus\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+b=AS:64\r\n\
+a=mid:0\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+y=media-extension\r\n\
+c=IN IPX 0.0.0.0\r\n";
+    assert_eq!(sdp.marshal(), expected);
+
+    // The marshaled output parses to the same description again
+    let mut reader = Cursor::new(expected.as_bytes());
+    let reparsed = SessionDescription::unmarshal_lenient(&mut reader)?;
+    assert_eq!(reparsed.marshal(), expected);
+
+    // Mandatory lines still have to be there
+    let mut reader = Cursor::new(b"v=0\r\nx=1\r\n".as_slice());
+    assert!(SessionDescription::unmarshal_lenient(&mut reader).is_err());
+
+    Ok(())
+}
@@ -4,6 +4,13 @@ use std::fmt;
 use url::Url;
 
 use crate::description::common::*;
+use crate::description::session::{
+    ATTR_KEY_BUNDLE_ONLY, ATTR_KEY_CANDIDATE, ATTR_KEY_END_OF_CANDIDATES,
+    ATTR_KEY_EXTMAP_ALLOW_MIXED, ATTR_KEY_EXT_MAP, ATTR_KEY_ICE_OPTIONS, ATTR_KEY_ICE_PWD,
+    ATTR_KEY_ICE_UFRAG, ATTR_KEY_MAX_MESSAGE_SIZE, ATTR_KEY_MSID, ATTR_KEY_RTCPMUX,
+    ATTR_KEY_RTCPMUX_ONLY, ATTR_KEY_SCTPMAP, ATTR_KEY_SCTP_PORT,
+};
+use crate::error::Result;
 use crate::extmap::*;
 
 /// Constants for extmap key
@@ -20,6 +27,19 @@ fn ext_map_uri() -> HashMap<isize, &'static str> {
     m
 }
 
+/// RtcpMux describes how a media description multiplexes RTCP with RTP,
+/// <https://datatracker.ietf.org/doc/html/rfc8858>
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum RtcpMux {
+    /// RTCP is sent on a separate port.
+    #[default]
+    None,
+    /// 'a=rtcp-mux': RTCP is multiplexed on the RTP port if the remote agrees.
+    Mux,
+    /// 'a=rtcp-mux-only': RTCP must be multiplexed on the RTP port.
+    MuxOnly,
+}
+
 /// MediaDescription represents a media type.
 /// <https://tools.ietf.org/html/rfc4566#section-5.14>
 #[derive(Debug, Default, Clone)]
@@ -57,6 +77,10 @@ pub struct MediaDescription {
     ///
     /// <https://tools.ietf.org/html/rfc4566#section-5.12>
     pub attributes: Vec<Attribute>,
+
+    /// Media level lines kept verbatim by `SessionDescription::unmarshal_lenient`
+    /// because they couldn't be parsed. They are written back after the attributes.
+    pub other: Vec<String>,
 }
 
 impl MediaDescription {
@@ -70,6 +94,109 @@ impl MediaDescription {
         None
     }
 
+    /// ice_ufrag returns the 'a=ice-ufrag' value of the media description
+    pub fn ice_ufrag(&self) -> Option<&str> {
+        self.attribute(ATTR_KEY_ICE_UFRAG).flatten()
+    }
+
+    /// ice_pwd returns the 'a=ice-pwd' value of the media description
+    pub fn ice_pwd(&self) -> Option<&str> {
+        self.attribute(ATTR_KEY_ICE_PWD).flatten()
+    }
+
+    /// ice_options returns the tokens of the 'a=ice-options' attribute, e.g. `trickle`
+    pub fn ice_options(&self) -> Vec<&str> {
+        self.attribute(ATTR_KEY_ICE_OPTIONS)
+            .flatten()
+            .map(|s| s.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+
+    /// candidates returns the values of all the 'a=candidate' attributes
+    pub fn candidates(&self) -> Vec<&str> {
+        self.attributes
+            .iter()
+            .filter(|a| a.is_ice_candidate())
+            .filter_map(|a| a.value.as_deref())
+            .collect()
+    }
+
+    /// has_end_of_candidates returns true if 'a=end-of-candidates' is present
+    pub fn has_end_of_candidates(&self) -> bool {
+        self.attribute(ATTR_KEY_END_OF_CANDIDATES).is_some()
+    }
+
+    /// extmaps parses all the 'a=extmap' attributes of the media description
+    pub fn extmaps(&self) -> Result<Vec<ExtMap>> {
+        self.attributes
+            .iter()
+            .filter(|a| a.key == ATTR_KEY_EXT_MAP)
+            .map(|a| {
+                let line = a.to_string();
+                ExtMap::unmarshal(&mut line.as_bytes())
+            })
+            .collect()
+    }
+
+    /// has_extmap_allow_mixed returns true if 'a=extmap-allow-mixed' is present
+    pub fn has_extmap_allow_mixed(&self) -> bool {
+        self.attribute(ATTR_KEY_EXTMAP_ALLOW_MIXED).is_some()
+    }
+
+    /// is_bundle_only returns true if 'a=bundle-only' is present
+    pub fn is_bundle_only(&self) -> bool {
+        self.attribute(ATTR_KEY_BUNDLE_ONLY).is_some()
+    }
+
+    /// rtcp_mux returns how the media description multiplexes RTCP with RTP
+    pub fn rtcp_mux(&self) -> RtcpMux {
+        if self.attribute(ATTR_KEY_RTCPMUX_ONLY).is_some() {
+            RtcpMux::MuxOnly
+        } else if self.attribute(ATTR_KEY_RTCPMUX).is_some() {
+            RtcpMux::Mux
+        } else {
+            RtcpMux::None
+        }
+    }
+
+    /// sctp_port returns the SCTP port of a data channel media description, read from
+    /// 'a=sctp-port' or else from the legacy 'a=sctpmap:<port> webrtc-datachannel' form
+    /// <https://datatracker.ietf.org/doc/html/rfc8841#section-5>
+    pub fn sctp_port(&self) -> Option<u16> {
+        if let Some(port) = self.attribute(ATTR_KEY_SCTP_PORT).flatten() {
+            return port.trim().parse().ok();
+        }
+
+        self.attribute(ATTR_KEY_SCTPMAP)
+            .flatten()
+            .and_then(|value| value.split_whitespace().next())
+            .and_then(|port| port.parse().ok())
+    }
+
+    /// max_message_size returns the 'a=max-message-size' value, the largest message
+    /// the remote is willing to receive
+    /// <https://datatracker.ietf.org/doc/html/rfc8841#section-6>
+    pub fn max_message_size(&self) -> Option<u32> {
+        self.attribute(ATTR_KEY_MAX_MESSAGE_SIZE)
+            .flatten()
+            .and_then(|size| size.trim().parse().ok())
+    }
+
+    /// msids returns the stream id and optional track id of every 'a=msid' attribute,
+    /// <https://datatracker.ietf.org/doc/html/rfc8830#section-2>
+    pub fn msids(&self) -> Vec<(&str, Option<&str>)> {
+        self.attributes
+            .iter()
+            .filter(|a| a.key == ATTR_KEY_MSID)
+            .filter_map(|a| a.value.as_deref())
+            .filter_map(|value| {
+                let mut fields = value.split_whitespace();
+                let stream_id = fields.next()?;
+                Some((stream_id, fields.next()))
+            })
+            .collect()
+    }
+
     /// new_jsep_media_description creates a new MediaName with
     /// some settings that are required by the JSEP spec.
     pub fn new_jsep_media_description(codec_type: String, _codec_prefs: Vec<&str>) -> Self {
@@ -101,6 +228,7 @@ impl MediaDescription {
             bandwidth: vec![],
             encryption_key: None,
             attributes: vec![],
+            other: vec![],
         }
     }
 
@@ -123,8 +251,13 @@ impl MediaDescription {
 
     /// with_ice_credentials adds ICE credentials to the media description
     pub fn with_ice_credentials(self, username: String, password: String) -> Self {
-        self.with_value_attribute("ice-ufrag".to_string(), username)
-            .with_value_attribute("ice-pwd".to_string(), password)
+        self.with_value_attribute(ATTR_KEY_ICE_UFRAG.to_string(), username)
+            .with_value_attribute(ATTR_KEY_ICE_PWD.to_string(), password)
+    }
+
+    /// with_ice_options adds an 'a=ice-options' attribute, e.g. `trickle`, to the media description
+    pub fn with_ice_options(self, options: String) -> Self {
+        self.with_value_attribute(ATTR_KEY_ICE_OPTIONS.to_string(), options)
     }
 
     /// with_codec adds codec information to the media description
@@ -150,6 +283,23 @@ impl MediaDescription {
         }
     }
 
+    /// with_rtcp_fb adds an RTCP feedback mechanism 'a=rtcp-fb:<payload type> <feedback>'
+    /// to the media description
+    pub fn with_rtcp_fb(self, payload_type: u8, feedback: String) -> Self {
+        self.with_value_attribute("rtcp-fb".to_string(), format!("{payload_type} {feedback}"))
+    }
+
+    /// with_bandwidth adds a 'b=<bwtype>:<bandwidth>' line to the media description
+    pub fn with_bandwidth(mut self, bandwidth_type: String, bandwidth: u64) -> Self {
+        let experimental = bandwidth_type.starts_with("X-");
+        self.bandwidth.push(Bandwidth {
+            experimental,
+            bandwidth_type: bandwidth_type.trim_start_matches("X-").to_owned(),
+            bandwidth,
+        });
+        self
+    }
+
     /// with_media_source adds media source information to the media description
     pub fn with_media_source(
         self,
@@ -166,14 +316,56 @@ impl MediaDescription {
         // Deprecated but not phased out?
     }
 
+    /// with_msid adds an 'a=msid' attribute associating the media description with
+    /// the given media stream and track
+    pub fn with_msid(self, stream_id: String, track_id: String) -> Self {
+        self.with_value_attribute(ATTR_KEY_MSID.to_string(), format!("{stream_id} {track_id}"))
+    }
+
+    /// with_bundle_only adds 'a=bundle-only' to the media description
+    pub fn with_bundle_only(self) -> Self {
+        self.with_property_attribute(ATTR_KEY_BUNDLE_ONLY.to_string())
+    }
+
+    /// with_rtcp_mux_only adds 'a=rtcp-mux' and 'a=rtcp-mux-only' to the media description,
+    /// offering exclusively multiplexed RTCP while staying compatible with older answerers
+    pub fn with_rtcp_mux_only(self) -> Self {
+        self.with_property_attribute(ATTR_KEY_RTCPMUX.to_string())
+            .with_property_attribute(ATTR_KEY_RTCPMUX_ONLY.to_string())
+    }
+
+    /// with_sctp_port adds 'a=sctp-port' to the media description
+    pub fn with_sctp_port(self, port: u16) -> Self {
+        self.with_value_attribute(ATTR_KEY_SCTP_PORT.to_string(), port.to_string())
+    }
+
+    /// with_sctpmap adds the legacy 'a=sctpmap' attribute used by endpoints predating
+    /// 'a=sctp-port'
+    pub fn with_sctpmap(self, port: u16, streams: u16) -> Self {
+        self.with_value_attribute(
+            ATTR_KEY_SCTPMAP.to_string(),
+            format!("{port} webrtc-datachannel {streams}"),
+        )
+    }
+
+    /// with_max_message_size adds 'a=max-message-size' to the media description
+    pub fn with_max_message_size(self, size: u32) -> Self {
+        self.with_value_attribute(ATTR_KEY_MAX_MESSAGE_SIZE.to_string(), size.to_string())
+    }
+
     /// with_candidate adds an ICE candidate to the media description
     /// Deprecated: use WithICECandidate instead
     pub fn with_candidate(self, value: String) -> Self {
-        self.with_value_attribute("candidate".to_string(), value)
+        self.with_value_attribute(ATTR_KEY_CANDIDATE.to_string(), value)
+    }
+
+    /// with_end_of_candidates adds 'a=end-of-candidates' to the media description
+    pub fn with_end_of_candidates(self) -> Self {
+        self.with_property_attribute(ATTR_KEY_END_OF_CANDIDATES.to_string())
     }
 
     pub fn with_extmap(self, e: ExtMap) -> Self {
-        self.with_property_attribute(e.marshal())
+        self.with_value_attribute(ATTR_KEY_EXT_MAP.to_owned(), e.to_string())
     }
 
     /// with_transport_cc_extmap adds an extmap to the media description
@@ -243,7 +435,7 @@ impl fmt::Display for MediaName {
 
 #[cfg(test)]
 mod tests {
-    use super::MediaDescription;
+    use super::{MediaDescription, RtcpMux};
 
     #[test]
     fn test_attribute_missing() {
@@ -267,4 +459,91 @@ mod tests {
 
         assert_eq!(media_description.attribute("ptime"), Some(Some("1")));
     }
+
+    #[test]
+    fn test_msids() {
+        let media_description = MediaDescription::default()
+            .with_msid("stream1".to_owned(), "track1".to_owned())
+            .with_msid("stream2".to_owned(), "track1".to_owned())
+            .with_value_attribute("msid".to_owned(), "-".to_owned());
+
+        assert_eq!(
+            media_description.attribute("msid"),
+            Some(Some("stream1 track1"))
+        );
+        assert_eq!(
+            media_description.msids(),
+            vec![
+                ("stream1", Some("track1")),
+                ("stream2", Some("track1")),
+                ("-", None)
+            ]
+        );
+        assert!(MediaDescription::default().msids().is_empty());
+    }
+
+    #[test]
+    fn test_rtcp_mux() {
+        assert_eq!(MediaDescription::default().rtcp_mux(), RtcpMux::None);
+
+        let media_description =
+            MediaDescription::default().with_property_attribute("rtcp-mux".to_owned());
+        assert_eq!(media_description.rtcp_mux(), RtcpMux::Mux);
+
+        let media_description = MediaDescription::default().with_rtcp_mux_only();
+        assert_eq!(media_description.attribute("rtcp-mux"), Some(None));
+        assert_eq!(media_description.rtcp_mux(), RtcpMux::MuxOnly);
+    }
+
+    #[test]
+    fn test_sctp_attributes() {
+        let media_description = MediaDescription::default()
+            .with_sctp_port(5000)
+            .with_max_message_size(262144);
+        assert_eq!(media_description.attribute("sctp-port"), Some(Some("5000")));
+        assert_eq!(media_description.sctp_port(), Some(5000));
+        assert_eq!(media_description.max_message_size(), Some(262144));
+
+        let legacy = MediaDescription::default().with_sctpmap(5001, 1024);
+        assert_eq!(
+            legacy.attribute("sctpmap"),
+            Some(Some("5001 webrtc-datachannel 1024"))
+        );
+        assert_eq!(legacy.sctp_port(), Some(5001));
+        assert_eq!(legacy.max_message_size(), None);
+
+        let invalid = MediaDescription::default()
+            .with_value_attribute("sctp-port".to_owned(), "port".to_owned());
+        assert_eq!(invalid.sctp_port(), None);
+    }
+
+    #[test]
+    fn test_with_rtcp_fb() {
+        let media_description = MediaDescription::default()
+            .with_rtcp_fb(96, "nack".to_owned())
+            .with_rtcp_fb(96, "nack pli".to_owned());
+
+        let values: Vec<_> = media_description
+            .attributes
+            .iter()
+            .filter(|a| a.key == "rtcp-fb")
+            .filter_map(|a| a.value.as_deref())
+            .collect();
+        assert_eq!(values, vec!["96 nack", "96 nack pli"]);
+    }
+
+    #[test]
+    fn test_with_bandwidth() {
+        let media_description = MediaDescription::default()
+            .with_bandwidth("AS".to_owned(), 512)
+            .with_bandwidth("X-YZ".to_owned(), 128);
+
+        let lines: Vec<String> = media_description
+            .bandwidth
+            .iter()
+            .map(|b| b.to_string())
+            .collect();
+        assert_eq!(lines, vec!["AS:512", "X-YZ:128"]);
+        assert!(media_description.bandwidth[1].experimental);
+    }
 }
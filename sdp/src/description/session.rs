@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{fmt, io};
@@ -24,18 +24,28 @@ pub const ATTR_KEY_CONNECTION_SETUP: &str = "setup";
 pub const ATTR_KEY_MID: &str = "mid";
 pub const ATTR_KEY_ICELITE: &str = "ice-lite";
 pub const ATTR_KEY_RTCPMUX: &str = "rtcp-mux";
+pub const ATTR_KEY_RTCPMUX_ONLY: &str = "rtcp-mux-only";
 pub const ATTR_KEY_RTCPRSIZE: &str = "rtcp-rsize";
 pub const ATTR_KEY_INACTIVE: &str = "inactive";
 pub const ATTR_KEY_RECV_ONLY: &str = "recvonly";
 pub const ATTR_KEY_SEND_ONLY: &str = "sendonly";
 pub const ATTR_KEY_SEND_RECV: &str = "sendrecv";
 pub const ATTR_KEY_EXT_MAP: &str = "extmap";
+pub const ATTR_KEY_EXTMAP_ALLOW_MIXED: &str = "extmap-allow-mixed";
+pub const ATTR_KEY_ICE_UFRAG: &str = "ice-ufrag";
+pub const ATTR_KEY_ICE_PWD: &str = "ice-pwd";
+pub const ATTR_KEY_ICE_OPTIONS: &str = "ice-options";
+pub const ATTR_KEY_BUNDLE_ONLY: &str = "bundle-only";
+pub const ATTR_KEY_SCTP_PORT: &str = "sctp-port";
+pub const ATTR_KEY_SCTPMAP: &str = "sctpmap";
+pub const ATTR_KEY_MAX_MESSAGE_SIZE: &str = "max-message-size";
 
 /// Constants for semantic tokens used in JSEP
 pub const SEMANTIC_TOKEN_LIP_SYNCHRONIZATION: &str = "LS";
 pub const SEMANTIC_TOKEN_FLOW_IDENTIFICATION: &str = "FID";
 pub const SEMANTIC_TOKEN_FORWARD_ERROR_CORRECTION: &str = "FEC";
 pub const SEMANTIC_TOKEN_WEBRTC_MEDIA_STREAMS: &str = "WMS";
+pub const SEMANTIC_TOKEN_BUNDLE: &str = "BUNDLE";
 
 /// Version describes the value provided by the "v=" field which gives
 /// the version of the Session Description Protocol.
@@ -232,6 +242,10 @@ pub struct SessionDescription {
 
     /// <https://tools.ietf.org/html/rfc4566#section-5.14>
     pub media_descriptions: Vec<MediaDescription>,
+
+    /// Session level lines kept verbatim by [`SessionDescription::unmarshal_lenient`]
+    /// because they couldn't be parsed. They are written back after the attributes.
+    pub other: Vec<String>,
 }
 
 /// Reset cleans the SessionDescription, and sets all fields back to their default values
@@ -273,6 +287,7 @@ impl SessionDescription {
             encryption_key: None,
             attributes: vec![], // TODO: implement trickle ICE
             media_descriptions: vec![],
+            other: vec![],
         };
 
         if identity {
@@ -299,6 +314,22 @@ impl SessionDescription {
         self.with_value_attribute("fingerprint".to_string(), algorithm + " " + value.as_str())
     }
 
+    /// WithICECredentials adds session level ICE credentials, which apply to every
+    /// media description that doesn't carry its own
+    pub fn with_ice_credentials(self, username: String, password: String) -> Self {
+        self.with_value_attribute(ATTR_KEY_ICE_UFRAG.to_string(), username)
+            .with_value_attribute(ATTR_KEY_ICE_PWD.to_string(), password)
+    }
+
+    /// with_bundle_group adds an 'a=group:BUNDLE' attribute listing the given mids
+    pub fn with_bundle_group(self, mids: &[&str]) -> Self {
+        let value = std::iter::once(SEMANTIC_TOKEN_BUNDLE)
+            .chain(mids.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.with_value_attribute(ATTR_KEY_GROUP.to_string(), value)
+    }
+
     /// WithMedia adds a media description to the session description
     pub fn with_media(mut self, md: MediaDescription) -> Self {
         self.media_descriptions.push(md);
@@ -365,6 +396,102 @@ impl SessionDescription {
         None
     }
 
+    /// ice_ufrag returns the session level 'a=ice-ufrag' value
+    pub fn ice_ufrag(&self) -> Option<&str> {
+        self.attribute(ATTR_KEY_ICE_UFRAG).map(|s| s.as_str())
+    }
+
+    /// ice_pwd returns the session level 'a=ice-pwd' value
+    pub fn ice_pwd(&self) -> Option<&str> {
+        self.attribute(ATTR_KEY_ICE_PWD).map(|s| s.as_str())
+    }
+
+    /// ice_options returns the tokens of the session level 'a=ice-options' attribute
+    pub fn ice_options(&self) -> Vec<&str> {
+        self.attribute(ATTR_KEY_ICE_OPTIONS)
+            .map(|s| s.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+
+    /// candidates returns the values of all the session level 'a=candidate' attributes
+    pub fn candidates(&self) -> Vec<&str> {
+        self.attributes
+            .iter()
+            .filter(|a| a.is_ice_candidate())
+            .filter_map(|a| a.value.as_deref())
+            .collect()
+    }
+
+    /// has_end_of_candidates returns true if 'a=end-of-candidates' is present at session level
+    pub fn has_end_of_candidates(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|a| a.key == ATTR_KEY_END_OF_CANDIDATES)
+    }
+
+    /// has_extmap_allow_mixed returns true if 'a=extmap-allow-mixed' is present at session level
+    pub fn has_extmap_allow_mixed(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|a| a.key == ATTR_KEY_EXTMAP_ALLOW_MIXED)
+    }
+
+    /// bundle_groups returns the mids of every 'a=group:BUNDLE' attribute,
+    /// <https://datatracker.ietf.org/doc/html/rfc8843#section-7.1>
+    pub fn bundle_groups(&self) -> Vec<Vec<&str>> {
+        self.attributes
+            .iter()
+            .filter(|a| a.key == ATTR_KEY_GROUP)
+            .filter_map(|a| a.value.as_deref())
+            .filter_map(|value| {
+                let mut fields = value.split_whitespace();
+                if fields.next() == Some(SEMANTIC_TOKEN_BUNDLE) {
+                    Some(fields.collect())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// bundle_mids returns the set of mids taking part in any BUNDLE group
+    pub fn bundle_mids(&self) -> HashSet<&str> {
+        self.bundle_groups().into_iter().flatten().collect()
+    }
+
+    /// validate_bundle_answer checks the BUNDLE groups of this answer against the offer it
+    /// responds to. Every answered group must be a subset of a single offered group and
+    /// may only reference media descriptions present in the answer,
+    /// <https://datatracker.ietf.org/doc/html/rfc8843#section-7.3>
+    pub fn validate_bundle_answer(&self, offer: &SessionDescription) -> Result<()> {
+        let offered_groups = offer.bundle_groups();
+        let answered_mids: HashSet<&str> = self
+            .media_descriptions
+            .iter()
+            .filter_map(|m| m.attribute(ATTR_KEY_MID).flatten())
+            .collect();
+
+        for group in self.bundle_groups() {
+            if let Some(mid) = group.iter().find(|mid| !answered_mids.contains(*mid)) {
+                return Err(Error::SdpInvalidValue(format!(
+                    "BUNDLE group references unknown mid {mid}"
+                )));
+            }
+
+            let offered = offered_groups
+                .iter()
+                .any(|offered| group.iter().all(|mid| offered.contains(mid)));
+            if !offered {
+                return Err(Error::SdpInvalidValue(format!(
+                    "BUNDLE group {} was not offered",
+                    group.join(" ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Marshal takes a SDP struct to text
     ///
     /// <https://tools.ietf.org/html/rfc4566#section-5>
@@ -436,6 +563,10 @@ impl SessionDescription {
         for attribute in &self.attributes {
             result += key_value_build("a=", Some(&attribute.to_string())).as_str();
         }
+        for line in &self.other {
+            result += line;
+            result += END_LINE;
+        }
 
         for media_description in &self.media_descriptions {
             result +=
@@ -451,6 +582,10 @@ impl SessionDescription {
             for attribute in &media_description.attributes {
                 result += key_value_build("a=", Some(&attribute.to_string())).as_str();
             }
+            for line in &media_description.other {
+                result += line;
+                result += END_LINE;
+            }
         }
 
         result
@@ -535,6 +670,21 @@ impl SessionDescription {
     /// +--------+----+-------+----+-----+----+-----+---+----+----+---+---+-----+---+---+----+---+----+
     /// ```
     pub fn unmarshal<R: io::BufRead + io::Seek>(reader: &mut R) -> Result<Self> {
+        Self::unmarshal_with_mode(reader, false)
+    }
+
+    /// unmarshal_lenient deserializes a session description like [`Self::unmarshal`], but
+    /// keeps unknown, out of order or malformed lines in the `other` list of the session or
+    /// media description they appear in rather than failing. The mandatory 'v=', 'o=', 's='
+    /// and 'm=' lines must still be well formed.
+    pub fn unmarshal_lenient<R: io::BufRead + io::Seek>(reader: &mut R) -> Result<Self> {
+        Self::unmarshal_with_mode(reader, true)
+    }
+
+    fn unmarshal_with_mode<R: io::BufRead + io::Seek>(
+        reader: &mut R,
+        lenient: bool,
+    ) -> Result<Self> {
         let mut lexer = Lexer {
             desc: SessionDescription {
                 version: 0,
@@ -551,8 +701,10 @@ impl SessionDescription {
                 encryption_key: None,
                 attributes: vec![],
                 media_descriptions: vec![],
+                other: vec![],
             },
             reader,
+            lenient,
         };
 
         let mut state = Some(StateFn { f: s1 });
@@ -630,7 +782,7 @@ fn s4<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option<
         b"t=" => Ok(Some(StateFn {
             f: unmarshal_timing,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s4),
     }
 }
 
@@ -643,7 +795,7 @@ fn s5<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option<
         b"t=" => Ok(Some(StateFn {
             f: unmarshal_timing,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s5),
     }
 }
 
@@ -660,7 +812,7 @@ fn s6<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option<
         b"t=" => Ok(Some(StateFn {
             f: unmarshal_timing,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s6),
     }
 }
 
@@ -679,7 +831,7 @@ fn s7<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option<
         b"t=" => Ok(Some(StateFn {
             f: unmarshal_timing,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s7),
     }
 }
 
@@ -695,7 +847,7 @@ fn s8<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option<
         b"t=" => Ok(Some(StateFn {
             f: unmarshal_timing,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s8),
     }
 }
 
@@ -724,7 +876,7 @@ fn s9<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option<
         b"m=" => Ok(Some(StateFn {
             f: unmarshal_media_description,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s9),
     }
 }
 
@@ -742,7 +894,7 @@ fn s10<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option
         b"t=" => Ok(Some(StateFn {
             f: unmarshal_timing,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s10),
     }
 }
 
@@ -759,7 +911,7 @@ fn s11<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option
         b"m=" => Ok(Some(StateFn {
             f: unmarshal_media_description,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s11),
     }
 }
 
@@ -788,7 +940,7 @@ fn s12<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option
         b"m=" => Ok(Some(StateFn {
             f: unmarshal_media_description,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s12),
     }
 }
 
@@ -808,7 +960,7 @@ fn s13<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option
         b"m=" => Ok(Some(StateFn {
             f: unmarshal_media_description,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s13),
     }
 }
 
@@ -841,7 +993,7 @@ fn s14<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option
         b"m=" => Ok(Some(StateFn {
             f: unmarshal_media_description,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s14),
     }
 }
 
@@ -871,7 +1023,7 @@ fn s15<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option
         b"m=" => Ok(Some(StateFn {
             f: unmarshal_media_description,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s15),
     }
 }
 
@@ -901,10 +1053,46 @@ fn s16<'a, R: io::BufRead + io::Seek>(lexer: &mut Lexer<'a, R>) -> Result<Option
         b"m=" => Ok(Some(StateFn {
             f: unmarshal_media_description,
         })),
-        _ => Err(Error::SdpInvalidSyntax(String::from_utf8(key)?)),
+        _ => unmarshal_other(lexer, key, s16),
     }
 }
 
+/// collect_other keeps a line which couldn't be parsed as an `other` line of the current
+/// session or media description when parsing leniently, and fails with err otherwise.
+fn collect_other<R: io::BufRead + io::Seek>(
+    lexer: &mut Lexer<'_, R>,
+    line: String,
+    err: Error,
+) -> Result<()> {
+    if !lexer.lenient {
+        return Err(err);
+    }
+
+    if let Some(latest_media_desc) = lexer.desc.media_descriptions.last_mut() {
+        latest_media_desc.other.push(line);
+    } else {
+        lexer.desc.other.push(line);
+    }
+
+    Ok(())
+}
+
+fn unmarshal_other<'a, R: io::BufRead + io::Seek>(
+    lexer: &mut Lexer<'a, R>,
+    key: Vec<u8>,
+    state: StateFnType<'a, R>,
+) -> Result<Option<StateFn<'a, R>>> {
+    let key = String::from_utf8(key)?;
+    if key.is_empty() || !lexer.lenient {
+        return Err(Error::SdpInvalidSyntax(key));
+    }
+
+    let (value, _) = read_value(lexer.reader)?;
+    collect_other(lexer, key.clone() + &value, Error::SdpInvalidSyntax(key))?;
+
+    Ok(Some(StateFn { f: state }))
+}
+
 fn unmarshal_protocol_version<'a, R: io::BufRead + io::Seek>(
     lexer: &mut Lexer<'a, R>,
 ) -> Result<Option<StateFn<'a, R>>> {
@@ -1006,7 +1194,10 @@ fn unmarshal_session_connection_information<'a, R: io::BufRead + io::Seek>(
     lexer: &mut Lexer<'a, R>,
 ) -> Result<Option<StateFn<'a, R>>> {
     let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.connection_information = unmarshal_connection_information(&value)?;
+    match unmarshal_connection_information(&value) {
+        Ok(connection_information) => lexer.desc.connection_information = connection_information,
+        Err(err) => collect_other(lexer, format!("c={value}"), err)?,
+    }
     Ok(Some(StateFn { f: s5 }))
 }
 
@@ -1031,11 +1222,7 @@ fn unmarshal_connection_information(value: &str) -> Result<Option<ConnectionInfo
     }
 
     let address = if fields.len() > 2 {
-        Some(Address {
-            address: fields[2].to_owned(),
-            ttl: None,
-            range: None,
-        })
+        Some(unmarshal_address(fields[1], fields[2])?)
     } else {
         None
     };
@@ -1047,11 +1234,39 @@ fn unmarshal_connection_information(value: &str) -> Result<Option<ConnectionInfo
     }))
 }
 
+// The multicast TTL is only carried by IP4 addresses; both IP4 and IP6 may carry
+// the number of addresses in the range.
+// https://datatracker.ietf.org/doc/html/rfc8866#section-5.7
+fn unmarshal_address(address_type: &str, value: &str) -> Result<Address> {
+    let parts: Vec<&str> = value.split('/').collect();
+    let parse_part = |part: &str| -> Result<isize> {
+        part.parse::<isize>()
+            .map_err(|_| Error::SdpInvalidValue(value.to_owned()))
+    };
+
+    let (ttl, range) = match (address_type, parts.len()) {
+        (_, 1) => (None, None),
+        ("IP4", 2) => (Some(parse_part(parts[1])?), None),
+        ("IP4", 3) => (Some(parse_part(parts[1])?), Some(parse_part(parts[2])?)),
+        ("IP6", 2) => (None, Some(parse_part(parts[1])?)),
+        _ => return Err(Error::SdpInvalidValue(value.to_owned())),
+    };
+
+    Ok(Address {
+        address: parts[0].to_owned(),
+        ttl,
+        range,
+    })
+}
+
 fn unmarshal_session_bandwidth<'a, R: io::BufRead + io::Seek>(
     lexer: &mut Lexer<'a, R>,
 ) -> Result<Option<StateFn<'a, R>>> {
     let (value, _) = read_value(lexer.reader)?;
-    lexer.desc.bandwidth.push(unmarshal_bandwidth(&value)?);
+    match unmarshal_bandwidth(&value) {
+        Ok(bandwidth) => lexer.desc.bandwidth.push(bandwidth),
+        Err(err) => collect_other(lexer, format!("b={value}"), err)?,
+    }
     Ok(Some(StateFn { f: s5 }))
 }
 
@@ -1263,6 +1478,7 @@ fn unmarshal_media_description<'a, R: io::BufRead + io::Seek>(
         bandwidth: vec![],
         encryption_key: None,
         attributes: vec![],
+        other: vec![],
     });
 
     Ok(Some(StateFn { f: s12 }))
@@ -1286,8 +1502,16 @@ fn unmarshal_media_connection_information<'a, R: io::BufRead + io::Seek>(
 ) -> Result<Option<StateFn<'a, R>>> {
     let (value, _) = read_value(lexer.reader)?;
 
+    let connection_information = match unmarshal_connection_information(&value) {
+        Ok(connection_information) => connection_information,
+        Err(err) => {
+            collect_other(lexer, format!("c={value}"), err)?;
+            return Ok(Some(StateFn { f: s15 }));
+        }
+    };
+
     if let Some(latest_media_desc) = lexer.desc.media_descriptions.last_mut() {
-        latest_media_desc.connection_information = unmarshal_connection_information(&value)?;
+        latest_media_desc.connection_information = connection_information;
         Ok(Some(StateFn { f: s15 }))
     } else {
         Err(Error::SdpEmptyTimeDescription)
@@ -1299,8 +1523,15 @@ fn unmarshal_media_bandwidth<'a, R: io::BufRead + io::Seek>(
 ) -> Result<Option<StateFn<'a, R>>> {
     let (value, _) = read_value(lexer.reader)?;
 
+    let bandwidth = match unmarshal_bandwidth(&value) {
+        Ok(bandwidth) => bandwidth,
+        Err(err) => {
+            collect_other(lexer, format!("b={value}"), err)?;
+            return Ok(Some(StateFn { f: s15 }));
+        }
+    };
+
     if let Some(latest_media_desc) = lexer.desc.media_descriptions.last_mut() {
-        let bandwidth = unmarshal_bandwidth(&value)?;
         latest_media_desc.bandwidth.push(bandwidth);
         Ok(Some(StateFn { f: s15 }))
     } else {
@@ -75,3 +75,52 @@ fn test_transport_cc_extmap() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_extmap_multiple_ext_attributes() -> Result<()> {
+    let line = "extmap:5/sendonly urn:example:ext attr1 attr2";
+    let mut reader = BufReader::new(line.as_bytes());
+    let e = ExtMap::unmarshal(&mut reader)?;
+
+    assert_eq!(e.ext_attr.as_deref(), Some("attr1 attr2"));
+    assert_eq!(e.marshal(), line);
+
+    Ok(())
+}
+
+#[test]
+fn test_intersect_extmaps() -> Result<()> {
+    let ext = |value: isize, direction: Direction, uri: &str| -> Result<ExtMap> {
+        Ok(ExtMap {
+            value,
+            direction,
+            uri: Some(Url::parse(uri)?),
+            ext_attr: None,
+        })
+    };
+
+    let local = vec![
+        ext(1, Direction::Unspecified, SDES_MID_URI)?,
+        ext(2, Direction::Unspecified, TRANSPORT_CC_URI)?,
+        ext(3, Direction::Unspecified, AUDIO_LEVEL_URI)?,
+        ext(4, Direction::SendOnly, VIDEO_ORIENTATION_URI)?,
+    ];
+    let remote = vec![
+        ext(4, Direction::Unspecified, TRANSPORT_CC_URI)?,
+        ext(5, Direction::SendOnly, AUDIO_LEVEL_URI)?,
+        ext(6, Direction::SendOnly, VIDEO_ORIENTATION_URI)?,
+    ];
+
+    let answer = intersect_extmaps(&local, &remote);
+    let answer: Vec<String> = answer.iter().map(|e| e.marshal()).collect();
+    assert_eq!(
+        answer,
+        vec![
+            format!("extmap:4 {TRANSPORT_CC_URI}"),
+            format!("extmap:5/recvonly {AUDIO_LEVEL_URI}"),
+            format!("extmap:6/inactive {VIDEO_ORIENTATION_URI}"),
+        ]
+    );
+
+    Ok(())
+}
@@ -8,6 +8,7 @@ use url::Url;
 use super::direction::*;
 use super::error::{Error, Result};
 use crate::description::common::*;
+use crate::negotiation::answer_direction;
 
 /// Default ext values
 pub const DEF_EXT_MAP_VALUE_ABS_SEND_TIME: usize = 1;
@@ -99,8 +100,8 @@ impl ExtMap {
 
         let uri = Some(Url::parse(fields[1])?);
 
-        let ext_attr = if fields.len() == 3 {
-            Some(fields[2].to_owned())
+        let ext_attr = if fields.len() > 2 {
+            Some(fields[2..].join(" "))
         } else {
             None
         };
@@ -117,4 +118,35 @@ impl ExtMap {
     pub fn marshal(&self) -> String {
         "extmap:".to_string() + self.to_string().as_str()
     }
+
+    /// Returns the direction to answer this extension with, given the direction the
+    /// answerer supports it in. An extension without direction on both sides stays
+    /// without direction, i.e. sendrecv.
+    pub fn answer_direction(&self, local: &Direction) -> Direction {
+        if self.direction == Direction::Unspecified && *local == Direction::Unspecified {
+            Direction::Unspecified
+        } else {
+            answer_direction(&self.direction, local)
+        }
+    }
+}
+
+/// Intersects the locally supported extensions with the remote ones by URI. The remote
+/// ids are kept, as the answerer has to use the ids picked by the offerer, and
+/// directions are the intersection of the mirrored remote direction with the local one.
+/// See <https://tools.ietf.org/html/rfc8285#section-7>.
+pub fn intersect_extmaps(local: &[ExtMap], remote: &[ExtMap]) -> Vec<ExtMap> {
+    remote
+        .iter()
+        .filter(|r| r.uri.is_some())
+        .filter_map(|r| {
+            let l = local.iter().find(|l| l.uri == r.uri)?;
+            Some(ExtMap {
+                value: r.value,
+                direction: r.answer_direction(&l.direction),
+                uri: r.uri.clone(),
+                ext_attr: r.ext_attr.clone(),
+            })
+        })
+        .collect()
 }
@@ -9,6 +9,8 @@ pub(crate) const END_LINE: &str = "\r\n";
 pub struct Lexer<'a, R: io::BufRead + io::Seek> {
     pub desc: SessionDescription,
     pub reader: &'a mut R,
+    /// Collect unknown and malformed lines into `other` instead of failing
+    pub lenient: bool,
 }
 
 pub type StateFnType<'a, R> = fn(&mut Lexer<'a, R>) -> Result<Option<StateFn<'a, R>>>;
@@ -4,6 +4,7 @@
 pub mod description;
 pub mod direction;
 pub mod extmap;
+pub mod negotiation;
 pub mod util;
 
 mod error;
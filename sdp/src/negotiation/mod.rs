@@ -0,0 +1,452 @@
+#[cfg(test)]
+mod negotiation_test;
+
+use std::collections::HashMap;
+
+use crate::description::media::{MediaDescription, RangedPort, RtcpMux};
+use crate::description::session::*;
+use crate::direction::Direction;
+use crate::error::{Error, Result};
+use crate::extmap::{intersect_extmaps, ExtMap};
+use crate::util::{merge_codecs, parse_fmtp, parse_rtcp_fb, parse_rtpmap, Codec, ConnectionRole};
+
+const MEDIA_AUDIO: &str = "audio";
+const MEDIA_VIDEO: &str = "video";
+const MEDIA_APPLICATION: &str = "application";
+const CODEC_RTX: &str = "rtx";
+const CODEC_H264: &str = "h264";
+const CODEC_VP9: &str = "vp9";
+/// The profile-level-id assumed when absent, <https://datatracker.ietf.org/doc/html/rfc6184#section-8.1>
+const DEFAULT_H264_PROFILE_LEVEL_ID: &str = "420010";
+const DEFAULT_SCTP_PORT: u16 = 5000;
+
+/// MediaCapabilities describes what the local endpoint supports for one kind of media.
+#[derive(Debug, Default, Clone)]
+pub struct MediaCapabilities {
+    /// Supported codecs in order of preference. Their payload types are ignored when
+    /// answering, as the answer reuses the payload types of the offer.
+    pub codecs: Vec<Codec>,
+    /// Supported RTP header extensions.
+    pub extensions: Vec<ExtMap>,
+    /// The direction the local endpoint wants for this kind of media.
+    pub direction: Direction,
+}
+
+/// TransportCapabilities holds the ICE and DTLS parameters of the local endpoint.
+#[derive(Debug, Default, Clone)]
+pub struct TransportCapabilities {
+    pub ice_ufrag: String,
+    pub ice_pwd: String,
+    /// DTLS certificate fingerprints as (hash algorithm, value) pairs.
+    pub fingerprints: Vec<(String, String)>,
+    /// The DTLS role to take when the offerer leaves the choice to the answerer,
+    /// either `Active` or `Passive`. `Active` is used when unspecified.
+    pub setup: ConnectionRole,
+}
+
+/// LocalCapabilities describes everything the local endpoint is able to negotiate.
+#[derive(Debug, Default, Clone)]
+pub struct LocalCapabilities {
+    pub audio: MediaCapabilities,
+    pub video: MediaCapabilities,
+    /// Whether data channels, m=application sections over SCTP, are supported.
+    pub data_channels: bool,
+    pub transport: TransportCapabilities,
+}
+
+/// NegotiatedMedia is the outcome of the negotiation of a single media section.
+#[derive(Debug, Default, Clone)]
+pub struct NegotiatedMedia {
+    pub mid: String,
+    /// The media type of the section, e.g. `audio`.
+    pub kind: String,
+    /// Whether the section was rejected, in which case it carries no media.
+    pub rejected: bool,
+    /// The direction of the section from the point of view of the answerer.
+    pub direction: Direction,
+    /// The codecs both sides agreed on, with the payload types of the offer.
+    pub codecs: Vec<Codec>,
+    /// The RTP header extensions both sides agreed on, with the ids of the offer.
+    pub extensions: Vec<ExtMap>,
+}
+
+/// Negotiation holds an answer to an offer along with what it negotiated.
+#[derive(Debug, Clone)]
+pub struct Negotiation {
+    pub answer: SessionDescription,
+    /// One entry per media section, in the order of the offer.
+    pub media: Vec<NegotiatedMedia>,
+    /// The mids bundled in the answer.
+    pub bundle: Vec<String>,
+}
+
+/// create_answer answers offer from the capabilities of the local endpoint as specified in
+/// <https://datatracker.ietf.org/doc/html/rfc8829#section-5.3>. Media sections for which
+/// no codec could be agreed on, or of a kind not supported locally, are rejected.
+pub fn create_answer(offer: &SessionDescription, local: &LocalCapabilities) -> Result<Negotiation> {
+    let mut answer = SessionDescription::new_jsep_session_description(false);
+    let mut media = Vec::with_capacity(offer.media_descriptions.len());
+
+    for offered in &offer.media_descriptions {
+        let (answered, negotiated) = answer_media(offer, offered, local)?;
+        answer = answer.with_media(answered);
+        media.push(negotiated);
+    }
+
+    // Bundle the accepted sections of the first offered BUNDLE group
+    let bundle: Vec<String> = offer
+        .bundle_groups()
+        .first()
+        .map(|group| {
+            group
+                .iter()
+                .filter(|mid| media.iter().any(|m| m.mid == **mid && !m.rejected))
+                .map(|mid| mid.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    if !bundle.is_empty() {
+        let mids: Vec<&str> = bundle.iter().map(|mid| mid.as_str()).collect();
+        answer = answer.with_bundle_group(&mids);
+    }
+
+    if offer.has_extmap_allow_mixed() {
+        answer = answer.with_property_attribute(ATTR_KEY_EXTMAP_ALLOW_MIXED.to_owned());
+    }
+
+    Ok(Negotiation {
+        answer,
+        media,
+        bundle,
+    })
+}
+
+/// answer_direction returns the direction to answer an offered direction with, given the
+/// direction the answerer wants.
+pub fn answer_direction(offered: &Direction, local: &Direction) -> Direction {
+    let offered = match offered {
+        Direction::Unspecified => &Direction::SendRecv,
+        direction => direction,
+    };
+    let local = match local {
+        Direction::Unspecified => &Direction::SendRecv,
+        direction => direction,
+    };
+
+    let can_send = matches!(offered, Direction::SendRecv | Direction::RecvOnly)
+        && matches!(local, Direction::SendRecv | Direction::SendOnly);
+    let can_recv = matches!(offered, Direction::SendRecv | Direction::SendOnly)
+        && matches!(local, Direction::SendRecv | Direction::RecvOnly);
+
+    match (can_send, can_recv) {
+        (true, true) => Direction::SendRecv,
+        (true, false) => Direction::SendOnly,
+        (false, true) => Direction::RecvOnly,
+        (false, false) => Direction::Inactive,
+    }
+}
+
+fn answer_media(
+    offer: &SessionDescription,
+    offered: &MediaDescription,
+    local: &LocalCapabilities,
+) -> Result<(MediaDescription, NegotiatedMedia)> {
+    let mid = offered
+        .attribute(ATTR_KEY_MID)
+        .flatten()
+        .ok_or_else(|| {
+            Error::SdpInvalidValue(format!(
+                "media section {} without mid",
+                offered.media_name.media
+            ))
+        })?
+        .to_owned();
+
+    let mut negotiated = NegotiatedMedia {
+        mid: mid.clone(),
+        kind: offered.media_name.media.clone(),
+        rejected: true,
+        direction: Direction::Inactive,
+        ..Default::default()
+    };
+
+    if offered.media_name.port.value == 0 {
+        return Ok((reject_media(offered, &mid), negotiated));
+    }
+
+    let capabilities = match offered.media_name.media.as_str() {
+        MEDIA_AUDIO => &local.audio,
+        MEDIA_VIDEO => &local.video,
+        MEDIA_APPLICATION => {
+            let is_sctp = offered.media_name.protos.iter().any(|p| p == "SCTP");
+            if !local.data_channels || !is_sctp {
+                return Ok((reject_media(offered, &mid), negotiated));
+            }
+
+            let mut answered = MediaDescription::new_jsep_media_description(
+                offered.media_name.media.clone(),
+                vec![],
+            );
+            answered.media_name.protos = offered.media_name.protos.clone();
+            answered.media_name.formats = offered.media_name.formats.clone();
+            let answered = with_transport(offer, offered, answered, &local.transport, &mid)
+                .with_sctp_port(offered.sctp_port().unwrap_or(DEFAULT_SCTP_PORT));
+
+            negotiated.rejected = false;
+            negotiated.direction = Direction::SendRecv;
+            return Ok((answered, negotiated));
+        }
+        _ => return Ok((reject_media(offered, &mid), negotiated)),
+    };
+
+    let codecs = negotiate_codecs(&media_codecs(offered), &capabilities.codecs);
+    if codecs
+        .iter()
+        .all(|c| c.name.eq_ignore_ascii_case(CODEC_RTX))
+    {
+        return Ok((reject_media(offered, &mid), negotiated));
+    }
+
+    let extensions = intersect_extmaps(&capabilities.extensions, &offered.extmaps()?);
+    let direction = answer_direction(&media_direction(offered), &capabilities.direction);
+
+    let mut answered =
+        MediaDescription::new_jsep_media_description(offered.media_name.media.clone(), vec![]);
+    answered.media_name.protos = offered.media_name.protos.clone();
+    let mut answered = with_transport(offer, offered, answered, &local.transport, &mid);
+
+    match offered.rtcp_mux() {
+        RtcpMux::Mux | RtcpMux::MuxOnly => {
+            answered = answered.with_property_attribute(ATTR_KEY_RTCPMUX.to_owned());
+        }
+        RtcpMux::None => {}
+    }
+    if offered.attribute(ATTR_KEY_RTCPRSIZE).is_some() {
+        answered = answered.with_property_attribute(ATTR_KEY_RTCPRSIZE.to_owned());
+    }
+    if offered.has_extmap_allow_mixed() {
+        answered = answered.with_property_attribute(ATTR_KEY_EXTMAP_ALLOW_MIXED.to_owned());
+    }
+    for extension in &extensions {
+        answered = answered.with_extmap(extension.clone());
+    }
+    answered = answered.with_property_attribute(direction.to_string());
+
+    for codec in &codecs {
+        let channels = codec.encoding_parameters.parse().unwrap_or(0);
+        answered = answered.with_codec(
+            codec.payload_type,
+            codec.name.clone(),
+            codec.clock_rate,
+            channels,
+            codec.fmtp.clone(),
+        );
+        for feedback in &codec.rtcp_feedback {
+            answered = answered.with_rtcp_fb(codec.payload_type, feedback.clone());
+        }
+    }
+
+    negotiated.rejected = false;
+    negotiated.direction = direction;
+    negotiated.codecs = codecs;
+    negotiated.extensions = extensions;
+
+    Ok((answered, negotiated))
+}
+
+/// reject_media answers a media section with port zero, which rejects it.
+fn reject_media(offered: &MediaDescription, mid: &str) -> MediaDescription {
+    let mut rejected =
+        MediaDescription::new_jsep_media_description(offered.media_name.media.clone(), vec![]);
+    rejected.media_name.port = RangedPort {
+        value: 0,
+        range: None,
+    };
+    rejected.media_name.protos = offered.media_name.protos.clone();
+    rejected.media_name.formats = offered.media_name.formats.clone();
+
+    rejected.with_value_attribute(ATTR_KEY_MID.to_owned(), mid.to_owned())
+}
+
+/// with_transport adds the mid, ICE and DTLS attributes to an accepted media section.
+fn with_transport(
+    offer: &SessionDescription,
+    offered: &MediaDescription,
+    mut answered: MediaDescription,
+    transport: &TransportCapabilities,
+    mid: &str,
+) -> MediaDescription {
+    let offered_setup = offered
+        .attribute(ATTR_KEY_CONNECTION_SETUP)
+        .flatten()
+        .or_else(|| {
+            offer
+                .attribute(ATTR_KEY_CONNECTION_SETUP)
+                .map(|s| s.as_str())
+        })
+        .map(ConnectionRole::from)
+        .unwrap_or_default();
+    let setup = match offered_setup {
+        ConnectionRole::Active => ConnectionRole::Passive,
+        ConnectionRole::Passive => ConnectionRole::Active,
+        _ => match transport.setup {
+            ConnectionRole::Passive => ConnectionRole::Passive,
+            _ => ConnectionRole::Active,
+        },
+    };
+
+    answered = answered
+        .with_value_attribute(ATTR_KEY_MID.to_owned(), mid.to_owned())
+        .with_ice_credentials(transport.ice_ufrag.clone(), transport.ice_pwd.clone())
+        .with_value_attribute(ATTR_KEY_CONNECTION_SETUP.to_owned(), setup.to_string());
+    for (algorithm, value) in &transport.fingerprints {
+        answered = answered.with_fingerprint(algorithm.clone(), value.clone());
+    }
+
+    answered
+}
+
+/// media_direction returns the direction attribute of a media section, sendrecv if absent.
+fn media_direction(media: &MediaDescription) -> Direction {
+    media
+        .attributes
+        .iter()
+        .map(|a| Direction::new(&a.key))
+        .find(|d| *d != Direction::Unspecified)
+        .unwrap_or(Direction::SendRecv)
+}
+
+/// media_codecs collects the codecs of a media section in the order of its format list.
+fn media_codecs(media: &MediaDescription) -> Vec<Codec> {
+    let mut codecs = HashMap::new();
+    for a in &media.attributes {
+        let attr = a.to_string();
+        let codec = match a.key.as_str() {
+            "rtpmap" => parse_rtpmap(&attr),
+            "fmtp" => parse_fmtp(&attr),
+            "rtcp-fb" => parse_rtcp_fb(&attr),
+            _ => continue,
+        };
+        if let Ok(codec) = codec {
+            merge_codecs(codec, &mut codecs);
+        }
+    }
+
+    media
+        .media_name
+        .formats
+        .iter()
+        .filter_map(|format| format.parse::<u8>().ok())
+        .filter_map(|payload_type| codecs.remove(&payload_type))
+        .filter(|codec| !codec.name.is_empty())
+        .collect()
+}
+
+/// negotiate_codecs keeps the offered codecs the local endpoint supports, in the order of
+/// the offer. RTX is kept only if the codec it repairs was kept.
+fn negotiate_codecs(offered: &[Codec], local: &[Codec]) -> Vec<Codec> {
+    let mut negotiated: Vec<Codec> = vec![];
+
+    for codec in offered
+        .iter()
+        .filter(|c| !c.name.eq_ignore_ascii_case(CODEC_RTX))
+    {
+        if let Some(l) = local.iter().find(|l| codec_compatible(l, codec)) {
+            negotiated.push(Codec {
+                fmtp: if l.fmtp.is_empty() {
+                    codec.fmtp.clone()
+                } else {
+                    l.fmtp.clone()
+                },
+                rtcp_feedback: codec
+                    .rtcp_feedback
+                    .iter()
+                    .filter(|fb| l.rtcp_feedback.contains(fb))
+                    .cloned()
+                    .collect(),
+                ..codec.clone()
+            });
+        }
+    }
+
+    let supports_rtx = local.iter().any(|l| l.name.eq_ignore_ascii_case(CODEC_RTX));
+    if supports_rtx {
+        for codec in offered
+            .iter()
+            .filter(|c| c.name.eq_ignore_ascii_case(CODEC_RTX))
+        {
+            let repairs_negotiated = fmtp_parameters(&codec.fmtp)
+                .get("apt")
+                .and_then(|apt| apt.parse::<u8>().ok())
+                .is_some_and(|apt| {
+                    negotiated
+                        .iter()
+                        .any(|c| c.payload_type == apt && !c.name.eq_ignore_ascii_case(CODEC_RTX))
+                });
+            if repairs_negotiated {
+                negotiated.push(codec.clone());
+            }
+        }
+    }
+
+    negotiated
+}
+
+/// codec_compatible returns true if the local and offered codecs have the same name, clock
+/// rate and channels, and agree on the format parameters that define the codec
+/// configuration. The other format parameters describe the capabilities and preferences of
+/// one side and may differ, e.g. the level of H264 or the Opus `useinbandfec` and `stereo`.
+fn codec_compatible(local: &Codec, offered: &Codec) -> bool {
+    if !local.name.eq_ignore_ascii_case(&offered.name) || local.clock_rate != offered.clock_rate {
+        return false;
+    }
+    if !local.encoding_parameters.is_empty()
+        && !offered.encoding_parameters.is_empty()
+        && local.encoding_parameters != offered.encoding_parameters
+    {
+        return false;
+    }
+
+    let local_parameters = fmtp_parameters(&local.fmtp);
+    let offered_parameters = fmtp_parameters(&offered.fmtp);
+    let parameter = |parameters: &HashMap<String, String>, key: &str, default: &str| {
+        parameters
+            .get(key)
+            .map_or(default, |v| v.as_str())
+            .to_lowercase()
+    };
+
+    match local.name.to_lowercase().as_str() {
+        // RFC 6184 Section 8.2.2: packetization-mode and the profile part of
+        // profile-level-id must be used symmetrically, the level may differ.
+        CODEC_H264 => {
+            let profile = |parameters: &HashMap<String, String>| {
+                let profile_level_id = parameter(
+                    parameters,
+                    "profile-level-id",
+                    DEFAULT_H264_PROFILE_LEVEL_ID,
+                );
+                profile_level_id.get(..4).map(str::to_owned)
+            };
+            parameter(&local_parameters, "packetization-mode", "0")
+                == parameter(&offered_parameters, "packetization-mode", "0")
+                && profile(&local_parameters).is_some()
+                && profile(&local_parameters) == profile(&offered_parameters)
+        }
+        CODEC_VP9 => {
+            parameter(&local_parameters, "profile-id", "0")
+                == parameter(&offered_parameters, "profile-id", "0")
+        }
+        _ => true,
+    }
+}
+
+fn fmtp_parameters(fmtp: &str) -> HashMap<String, String> {
+    fmtp.split(';')
+        .filter_map(|parameter| {
+            let mut kv = parameter.trim().splitn(2, '=');
+            let key = kv.next().filter(|k| !k.is_empty())?;
+            Some((key.to_lowercase(), kv.next().unwrap_or_default().to_owned()))
+        })
+        .collect()
+}
@@ -0,0 +1,351 @@
+use std::io::Cursor;
+
+use url::Url;
+
+use super::*;
+
+const OFFER: &str = "v=0\r\n\
+o=- 4596489990601351948 2 IN IP4 127.0.0.1\r\n\
+s=-\r\n\
+t=0 0\r\n\
+a=group:BUNDLE 0 1 2 3\r\n\
+a=extmap-allow-mixed\r\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 0\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=setup:actpass\r\n\
+a=mid:0\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=extmap:5 urn:ietf:params:rtp-hdrext:ssrc-audio-level\r\n\
+a=sendonly\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:111 opus/48000/2\r\n\
+a=rtcp-fb:111 transport-cc\r\n\
+a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+a=rtpmap:0 PCMU/8000\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 96 97 98 99\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=setup:actpass\r\n\
+a=mid:1\r\n\
+a=extmap:4 urn:ietf:params:rtp-hdrext:sdes:mid\r\n\
+a=sendrecv\r\n\
+a=rtcp-mux\r\n\
+a=rtcp-rsize\r\n\
+a=rtpmap:96 VP8/90000\r\n\
+a=rtcp-fb:96 nack\r\n\
+a=rtcp-fb:96 nack pli\r\n\
+a=rtcp-fb:96 goog-remb\r\n\
+a=rtpmap:97 rtx/90000\r\n\
+a=fmtp:97 apt=96\r\n\
+a=rtpmap:98 H264/90000\r\n\
+a=fmtp:98 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f\r\n\
+a=rtpmap:99 rtx/90000\r\n\
+a=fmtp:99 apt=98\r\n\
+m=video 9 UDP/TLS/RTP/SAVPF 100\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=setup:actpass\r\n\
+a=mid:2\r\n\
+a=sendrecv\r\n\
+a=rtcp-mux\r\n\
+a=rtpmap:100 AV1/90000\r\n\
+m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n\
+c=IN IP4 0.0.0.0\r\n\
+a=setup:actpass\r\n\
+a=mid:3\r\n\
+a=sctp-port:5000\r\n";
+
+fn local_capabilities() -> LocalCapabilities {
+    LocalCapabilities {
+        audio: MediaCapabilities {
+            codecs: vec![Codec {
+                name: "opus".to_owned(),
+                clock_rate: 48000,
+                encoding_parameters: "2".to_owned(),
+                fmtp: "minptime=10;useinbandfec=1".to_owned(),
+                rtcp_feedback: vec!["transport-cc".to_owned()],
+                ..Default::default()
+            }],
+            extensions: vec![ExtMap {
+                value: 1,
+                uri: Some(Url::parse("urn:ietf:params:rtp-hdrext:sdes:mid").unwrap()),
+                ..Default::default()
+            }],
+            direction: Direction::SendRecv,
+        },
+        video: MediaCapabilities {
+            codecs: vec![
+                Codec {
+                    name: "VP8".to_owned(),
+                    clock_rate: 90000,
+                    rtcp_feedback: vec!["nack".to_owned(), "nack pli".to_owned()],
+                    ..Default::default()
+                },
+                Codec {
+                    name: "rtx".to_owned(),
+                    clock_rate: 90000,
+                    ..Default::default()
+                },
+            ],
+            extensions: vec![],
+            direction: Direction::RecvOnly,
+        },
+        data_channels: true,
+        transport: TransportCapabilities {
+            ice_ufrag: "ufrag".to_owned(),
+            ice_pwd: "pwd".to_owned(),
+            fingerprints: vec![("sha-256".to_owned(), "AB:CD".to_owned())],
+            setup: ConnectionRole::Active,
+        },
+    }
+}
+
+#[test]
+fn test_create_answer() -> Result<()> {
+    let mut reader = Cursor::new(OFFER.as_bytes());
+    let offer = SessionDescription::unmarshal(&mut reader)?;
+
+    let negotiation = create_answer(&offer, &local_capabilities())?;
+    let answer = &negotiation.answer;
+
+    assert_eq!(negotiation.bundle, vec!["0", "1", "3"]);
+    assert_eq!(answer.bundle_groups(), vec![vec!["0", "1", "3"]]);
+    assert!(answer.has_extmap_allow_mixed());
+    answer.validate_bundle_answer(&offer)?;
+
+    // Audio: opus only, the offerer only sends
+    let audio = &negotiation.media[0];
+    assert!(!audio.rejected);
+    assert_eq!(audio.mid, "0");
+    assert_eq!(audio.kind, "audio");
+    assert_eq!(audio.direction, Direction::RecvOnly);
+    assert_eq!(audio.codecs.len(), 1);
+    assert_eq!(audio.codecs[0].payload_type, 111);
+    assert_eq!(audio.codecs[0].rtcp_feedback, vec!["transport-cc"]);
+    assert_eq!(audio.extensions.len(), 1);
+    assert_eq!(audio.extensions[0].value, 4);
+
+    let m = &answer.media_descriptions[0];
+    assert_eq!(m.media_name.formats, vec!["111"]);
+    assert_eq!(m.attribute("setup"), Some(Some("active")));
+    assert_eq!(m.attribute("recvonly"), Some(None));
+    assert_eq!(m.ice_ufrag(), Some("ufrag"));
+    assert_eq!(m.rtcp_mux(), RtcpMux::Mux);
+    assert_eq!(
+        m.attribute("fmtp"),
+        Some(Some("111 minptime=10;useinbandfec=1"))
+    );
+    assert_eq!(m.extmaps()?.len(), 1);
+
+    // Video: VP8 and its RTX, the RTX of the unsupported H264 is dropped
+    let video = &negotiation.media[1];
+    assert!(!video.rejected);
+    assert_eq!(video.direction, Direction::RecvOnly);
+    let payload_types: Vec<u8> = video.codecs.iter().map(|c| c.payload_type).collect();
+    assert_eq!(payload_types, vec![96, 97]);
+    assert_eq!(video.codecs[0].rtcp_feedback, vec!["nack", "nack pli"]);
+    assert!(video.extensions.is_empty());
+
+    let m = &answer.media_descriptions[1];
+    assert_eq!(m.media_name.formats, vec!["96", "97"]);
+    assert_eq!(m.attribute("rtcp-rsize"), Some(None));
+    assert_eq!(m.attribute("fmtp"), Some(Some("97 apt=96")));
+
+    // Video without a common codec is rejected
+    let rejected = &negotiation.media[2];
+    assert!(rejected.rejected);
+    assert_eq!(rejected.direction, Direction::Inactive);
+    assert_eq!(answer.media_descriptions[2].media_name.port.value, 0);
+    assert_eq!(
+        answer.media_descriptions[2].attribute("mid"),
+        Some(Some("2"))
+    );
+
+    // Data channels
+    let application = &negotiation.media[3];
+    assert!(!application.rejected);
+    let m = &answer.media_descriptions[3];
+    assert_eq!(m.media_name.formats, vec!["webrtc-datachannel"]);
+    assert_eq!(m.sctp_port(), Some(5000));
+
+    // The answer can be parsed back
+    let mut reader = Cursor::new(answer.marshal().into_bytes());
+    let parsed = SessionDescription::unmarshal(&mut reader)?;
+    assert_eq!(parsed.media_descriptions.len(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_create_answer_without_data_channels() -> Result<()> {
+    let mut reader = Cursor::new(OFFER.as_bytes());
+    let offer = SessionDescription::unmarshal(&mut reader)?;
+
+    let local = LocalCapabilities {
+        data_channels: false,
+        ..local_capabilities()
+    };
+    let negotiation = create_answer(&offer, &local)?;
+
+    assert!(negotiation.media[3].rejected);
+    assert_eq!(negotiation.bundle, vec!["0", "1"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_create_answer_requires_mid() {
+    let offer = SessionDescription::new_jsep_session_description(false).with_media(
+        MediaDescription::new_jsep_media_description("audio".to_owned(), vec![]).with_codec(
+            111,
+            "opus".to_owned(),
+            48000,
+            2,
+            String::new(),
+        ),
+    );
+
+    assert!(create_answer(&offer, &local_capabilities()).is_err());
+}
+
+#[test]
+fn test_answer_direction() {
+    let tests = vec![
+        (
+            Direction::SendRecv,
+            Direction::SendRecv,
+            Direction::SendRecv,
+        ),
+        (
+            Direction::SendRecv,
+            Direction::RecvOnly,
+            Direction::RecvOnly,
+        ),
+        (
+            Direction::SendRecv,
+            Direction::SendOnly,
+            Direction::SendOnly,
+        ),
+        (
+            Direction::SendOnly,
+            Direction::SendRecv,
+            Direction::RecvOnly,
+        ),
+        (
+            Direction::SendOnly,
+            Direction::SendOnly,
+            Direction::Inactive,
+        ),
+        (
+            Direction::RecvOnly,
+            Direction::SendRecv,
+            Direction::SendOnly,
+        ),
+        (
+            Direction::RecvOnly,
+            Direction::RecvOnly,
+            Direction::Inactive,
+        ),
+        (
+            Direction::Inactive,
+            Direction::SendRecv,
+            Direction::Inactive,
+        ),
+        (
+            Direction::Unspecified,
+            Direction::Unspecified,
+            Direction::SendRecv,
+        ),
+    ];
+
+    for (offered, local, expected) in tests {
+        assert_eq!(
+            answer_direction(&offered, &local),
+            expected,
+            "{offered} answered by {local}"
+        );
+    }
+}
+
+#[test]
+fn test_codec_compatible() {
+    let codec = |name: &str, clock_rate: u32, fmtp: &str| Codec {
+        name: name.to_owned(),
+        clock_rate,
+        fmtp: fmtp.to_owned(),
+        ..Default::default()
+    };
+
+    let tests = vec![
+        (
+            "H264 level asymmetry",
+            codec(
+                "H264",
+                90000,
+                "level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f",
+            ),
+            codec(
+                "H264",
+                90000,
+                "packetization-mode=1;profile-level-id=42e034",
+            ),
+            true,
+        ),
+        (
+            "H264 packetization-mode",
+            codec(
+                "H264",
+                90000,
+                "packetization-mode=1;profile-level-id=42e01f",
+            ),
+            codec("H264", 90000, "profile-level-id=42e01f"),
+            false,
+        ),
+        (
+            "H264 profile",
+            codec(
+                "H264",
+                90000,
+                "packetization-mode=1;profile-level-id=42e01f",
+            ),
+            codec(
+                "H264",
+                90000,
+                "packetization-mode=1;profile-level-id=64001f",
+            ),
+            false,
+        ),
+        (
+            "H264 default profile",
+            codec("H264", 90000, "profile-level-id=42001f"),
+            codec("H264", 90000, ""),
+            true,
+        ),
+        (
+            "VP9 profile",
+            codec("VP9", 90000, "profile-id=0"),
+            codec("VP9", 90000, "profile-id=2"),
+            false,
+        ),
+        (
+            "VP9 default profile",
+            codec("VP9", 90000, "profile-id=0;max-fr=30"),
+            codec("VP9", 90000, ""),
+            true,
+        ),
+        (
+            "Opus preferences",
+            codec("opus", 48000, "minptime=10;useinbandfec=1"),
+            codec("opus", 48000, "useinbandfec=0;stereo=1"),
+            true,
+        ),
+        (
+            "Clock rate",
+            codec("opus", 48000, ""),
+            codec("opus", 16000, ""),
+            false,
+        ),
+    ];
+
+    for (name, local, offered, expected) in tests {
+        assert_eq!(codec_compatible(&local, &offered), expected, "{name}");
+    }
+}
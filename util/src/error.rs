@@ -93,6 +93,8 @@ pub enum Error {
     ErrLocalIpBeyondStaticIpsSubset,
     #[error("all static_ips must have associated local IPs")]
     ErrLocalIpNoStaticsIpsAssociated,
+    #[error("loss rate must be between 0.0 and 1.0")]
+    ErrInvalidLossRate,
     #[error("router already started")]
     ErrRouterAlreadyStarted,
     #[error("router already stopped")]
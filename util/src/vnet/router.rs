@@ -14,6 +14,8 @@ use std::time::SystemTime;
 use async_trait::async_trait;
 use ipnet::*;
 use portable_atomic::AtomicU64;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use tokio::sync::{mpsc, Mutex};
 use tokio::time::Duration;
 
@@ -59,6 +61,11 @@ pub struct RouterConfig {
     pub min_delay: Duration,
     // Max Jitter
     pub max_jitter: Duration,
+    // Probability, between 0.0 and 1.0, of dropping each routed chunk
+    pub loss_rate: f64,
+    // Seed of the RNG deciding which chunks are lost, so that a lossy run can
+    // be reproduced. If not specified, a random seed is used.
+    pub loss_seed: Option<u64>,
 }
 
 // NIC is a network interface controller that interfaces Router
@@ -86,6 +93,26 @@ pub struct RouterInternal {
     pub(crate) last_id: u8, // requires mutex [x], used to assign the last digit of IPv4 address
 }
 
+// PacketLoss decides which routed chunks are dropped
+struct PacketLoss {
+    rate: f64,
+    rng: StdRng,
+}
+
+impl PacketLoss {
+    fn new(rate: f64, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        PacketLoss { rate, rng }
+    }
+
+    fn drop_chunk(&mut self) -> bool {
+        self.rate > 0.0 && self.rng.gen::<f64>() < self.rate
+    }
+}
+
 // Router ...
 #[derive(Default)]
 pub struct Router {
@@ -93,6 +120,8 @@ pub struct Router {
     ipv4net: IpNet,                            // read-only
     min_delay: Duration,                       // requires mutex [x]
     max_jitter: Duration,                      // requires mutex [x]
+    loss_rate: f64,                            // requires mutex [x]
+    loss_seed: Option<u64>,                    // requires mutex [x]
     queue: Arc<ChunkQueue>,                    // read-only
     interfaces: Vec<Interface>,                // read-only
     static_ips: Vec<IpAddr>,                   // read-only
@@ -217,6 +246,10 @@ impl Router {
     pub fn new(config: RouterConfig) -> Result<Self> {
         let ipv4net: IpNet = config.cidr.parse()?;
 
+        if !(0.0..=1.0).contains(&config.loss_rate) {
+            return Err(Error::ErrInvalidLossRate);
+        }
+
         let queue_size = if config.queue_size > 0 {
             config.queue_size
         } else {
@@ -289,6 +322,8 @@ impl Router {
             queue: Arc::new(ChunkQueue::new(queue_size)),
             min_delay: config.min_delay,
             max_jitter: config.max_jitter,
+            loss_rate: config.loss_rate,
+            loss_seed: config.loss_seed,
             ..Default::default()
         })
     }
@@ -313,6 +348,7 @@ impl Router {
         let queue = Arc::clone(&self.queue);
         let max_jitter = self.max_jitter;
         let min_delay = self.min_delay;
+        let mut loss = PacketLoss::new(self.loss_rate, self.loss_seed);
         let name = self.name.clone();
         let ipv4net = self.ipv4net;
 
@@ -322,6 +358,7 @@ impl Router {
                 ipv4net,
                 max_jitter,
                 min_delay,
+                &mut loss,
                 &queue,
                 &router_internal,
             )
@@ -431,6 +468,7 @@ impl Router {
         ipv4net: IpNet,
         max_jitter: Duration,
         min_delay: Duration,
+        loss: &mut PacketLoss,
         queue: &Arc<ChunkQueue>,
         router_internal: &Arc<Mutex<RouterInternal>>,
     ) -> Result<Duration> {
@@ -487,6 +525,11 @@ impl Router {
                     continue; // discard
                 }
 
+                if loss.drop_chunk() {
+                    log::debug!("[{}] lost {}", name, c);
+                    continue; // discard
+                }
+
                 let dst_ip = c.get_destination_ip();
 
                 // check if the destination is in our subnet
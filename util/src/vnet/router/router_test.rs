@@ -498,6 +498,102 @@ async fn test_router_delay() -> Result<()> {
     Ok(())
 }
 
+async fn loss_sub_test(loss_rate: f64, loss_seed: Option<u64>) -> Result<i32> {
+    let wan = Arc::new(Mutex::new(Router::new(RouterConfig {
+        cidr: "1.2.3.0/24".to_string(),
+        loss_rate,
+        loss_seed,
+        ..Default::default()
+    })?));
+
+    let npkts = 10;
+    let mut nics = vec![];
+    let mut ips = vec![];
+    for i in 0..2 {
+        let nic = Arc::new(Mutex::new(DummyNic {
+            net: Net::new(Some(NetConfig::default())),
+            on_inbound_chunk_handler: 0,
+            ..Default::default()
+        }));
+
+        {
+            let n = Arc::clone(&nic) as Arc<Mutex<dyn Nic + Send + Sync>>;
+            let mut w = wan.lock().await;
+            w.add_net(n).await?;
+        }
+        {
+            let n = nic.lock().await;
+            n.set_router(Arc::clone(&wan)).await?;
+        }
+
+        {
+            let n = nic.lock().await;
+            if let Some(eth0) = n.get_interface("eth0").await {
+                let addrs = eth0.addrs();
+                assert_eq!(addrs.len(), 1, "should match");
+                ips.push(SocketAddr::new(addrs[0].addr(), 1111 * (i + 1)));
+            }
+        }
+
+        nics.push(nic);
+    }
+
+    {
+        let mut r = wan.lock().await;
+        r.start().await?;
+
+        for _ in 0..npkts {
+            let c = Box::new(ChunkUdp::new(ips[0], ips[1]));
+            r.push(c).await;
+        }
+    }
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    {
+        let mut r = wan.lock().await;
+        r.stop().await?;
+    }
+
+    let n = nics[1].lock().await;
+    Ok(n.cbs0.load(Ordering::SeqCst))
+}
+
+#[tokio::test]
+async fn test_router_loss() -> Result<()> {
+    assert_eq!(
+        loss_sub_test(0.0, None).await?,
+        10,
+        "should deliver every chunk"
+    );
+    assert_eq!(
+        loss_sub_test(1.0, None).await?,
+        0,
+        "should drop every chunk"
+    );
+
+    let delivered = loss_sub_test(0.5, Some(42)).await?;
+    assert_eq!(
+        loss_sub_test(0.5, Some(42)).await?,
+        delivered,
+        "should drop the same chunks with the same seed"
+    );
+
+    for loss_rate in [-0.1, 1.1, f64::NAN] {
+        let result = Router::new(RouterConfig {
+            cidr: "1.2.3.0/24".to_string(),
+            loss_rate,
+            ..Default::default()
+        });
+        assert!(
+            matches!(result, Err(Error::ErrInvalidLossRate)),
+            "should reject loss rate {loss_rate}"
+        );
+    }
+
+    Ok(())
+}
+
 //use std::io::Write;
 
 #[tokio::test]
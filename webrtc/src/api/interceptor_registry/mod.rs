@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod interceptor_registry_test;
 
+use interceptor::gcc::SendSideBweBuilder;
 use interceptor::nack::generator::Generator;
 use interceptor::nack::responder::Responder;
 use interceptor::registry::Registry;
@@ -130,6 +131,21 @@ pub fn configure_twcc_sender_only(
     Ok(registry)
 }
 
+/// configure_congestion_control will setup everything necessary for estimating the bandwidth
+/// available to the sender from the TWCC reports of the remote peer. The estimator of each
+/// PeerConnection is handed to the application by the callback set with
+/// `SendSideBweBuilder::with_on_new_estimator`.
+pub fn configure_congestion_control(
+    mut registry: Registry,
+    media_engine: &mut MediaEngine,
+    builder: SendSideBweBuilder,
+) -> Result<Registry> {
+    // The estimator reads the sequence numbers added by the TWCC sender, so its writer has to
+    // be called after the one of the sender.
+    registry.add(Box::new(builder));
+    configure_twcc_sender_only(registry, media_engine)
+}
+
 /// configure_twcc_receiver will setup everything necessary for generating TWCC reports.
 pub fn configure_twcc_receiver_only(
     mut registry: Registry,
@@ -597,6 +597,113 @@ async fn test_media_engine_header_extension_direction() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_media_engine_header_extension_preferred_id() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+
+    m.register_header_extension(
+        RTCRtpHeaderExtensionCapability {
+            uri: sdp::extmap::SDES_MID_URI.to_owned(),
+        },
+        RTPCodecType::Video,
+        None,
+    )?;
+    m.register_header_extension_with_id(
+        RTCRtpHeaderExtensionCapability {
+            uri: sdp::extmap::ABS_SEND_TIME_URI.to_owned(),
+        },
+        RTPCodecType::Video,
+        None,
+        1,
+    )?;
+
+    // The preferred id is proposed, the other extension doesn't take it
+    let params =
+        m.get_rtp_parameters_by_kind(RTPCodecType::Video, RTCRtpTransceiverDirection::Sendrecv);
+    let id_of = |uri: &str| {
+        params
+            .header_extensions
+            .iter()
+            .find(|ext| ext.uri == uri)
+            .map(|ext| ext.id)
+    };
+    assert_eq!(id_of(sdp::extmap::ABS_SEND_TIME_URI), Some(1));
+    assert_eq!(id_of(sdp::extmap::SDES_MID_URI), Some(2));
+
+    // Registering the same id again is fine, anything else isn't
+    m.register_header_extension_with_id(
+        RTCRtpHeaderExtensionCapability {
+            uri: sdp::extmap::ABS_SEND_TIME_URI.to_owned(),
+        },
+        RTPCodecType::Audio,
+        None,
+        1,
+    )?;
+    for (uri, id) in [
+        (sdp::extmap::ABS_SEND_TIME_URI, 3),
+        (sdp::extmap::SDES_MID_URI, 1),
+        (sdp::extmap::AUDIO_LEVEL_URI, 0),
+        (sdp::extmap::AUDIO_LEVEL_URI, 15),
+    ] {
+        assert_eq!(
+            m.register_header_extension_with_id(
+                RTCRtpHeaderExtensionCapability {
+                    uri: uri.to_owned(),
+                },
+                RTPCodecType::Audio,
+                None,
+                id,
+            ),
+            Err(Error::ErrRegisterHeaderExtensionInvalidID),
+            "{uri} as {id}"
+        );
+    }
+
+    // A rejected registration leaves the extension as it was
+    assert_eq!(
+        m.register_header_extension_with_id(
+            RTCRtpHeaderExtensionCapability {
+                uri: sdp::extmap::ABS_SEND_TIME_URI.to_owned(),
+            },
+            RTPCodecType::Video,
+            Some(RTCRtpTransceiverDirection::Recvonly),
+            3,
+        ),
+        Err(Error::ErrRegisterHeaderExtensionInvalidID)
+    );
+    let params =
+        m.get_rtp_parameters_by_kind(RTPCodecType::Video, RTCRtpTransceiverDirection::Sendonly);
+    assert!(params
+        .header_extensions
+        .iter()
+        .any(|ext| ext.uri == sdp::extmap::ABS_SEND_TIME_URI));
+
+    // The remote peer has the final say
+    const OFFER: &str = "v=0
+o=- 4596489990601351948 2 IN IP4 127.0.0.1
+s=-
+t=0 0
+m=video 9 UDP/TLS/RTP/SAVPF 96
+a=extmap:4 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time
+a=rtpmap:96 VP8/90000
+";
+    let m = m.clone_to();
+    let mut reader = Cursor::new(OFFER.as_bytes());
+    m.update_from_remote_description(&SessionDescription::unmarshal(&mut reader)?)
+        .await?;
+
+    let (id, _, is_video) = m
+        .get_header_extension_id(RTCRtpHeaderExtensionCapability {
+            uri: sdp::extmap::ABS_SEND_TIME_URI.to_owned(),
+        })
+        .await;
+    assert_eq!(id, 4);
+    assert!(is_video);
+
+    Ok(())
+}
+
 /// If a user attempts to register a codec twice we should just discard duplicate calls
 #[tokio::test]
 async fn test_media_engine_double_register() -> Result<()> {
@@ -636,6 +743,30 @@ async fn test_media_engine_double_register() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_media_engine_register_red_codec() -> Result<()> {
+    let mut m = MediaEngine::default();
+    assert_eq!(
+        m.register_red_codec(63, 1),
+        Err(Error::ErrCodecNotFound),
+        "RED requires a registered Opus codec"
+    );
+
+    m.register_default_codecs()?;
+    m.register_red_codec(63, 1)?;
+
+    let red = m
+        .audio_codecs
+        .iter()
+        .find(|c| c.capability.mime_type == MIME_TYPE_RED)
+        .expect("RED codec should be registered");
+    assert_eq!(red.payload_type, 63);
+    assert_eq!(red.capability.clock_rate, 48000);
+    assert_eq!(red.capability.sdp_fmtp_line, "111/111");
+
+    Ok(())
+}
+
 async fn validate(m: &MediaEngine) -> Result<()> {
     m.update_header_extension(2, "test-extension", RTPCodecType::Audio)
         .await?;
@@ -52,6 +52,9 @@ pub const MIME_TYPE_PCMA: &str = "audio/PCMA";
 /// MIME_TYPE_TELEPHONE_EVENT telephone-event MIME type
 /// Note: Matching should be case insensitive.
 pub const MIME_TYPE_TELEPHONE_EVENT: &str = "audio/telephone-event";
+/// MIME_TYPE_RED RED (RFC 2198) redundant audio MIME type
+/// Note: Matching should be case insensitive.
+pub const MIME_TYPE_RED: &str = "audio/red";
 
 const VALID_EXT_IDS: Range<isize> = 1..15;
 
@@ -61,6 +64,7 @@ pub(crate) struct MediaEngineHeaderExtension {
     pub(crate) is_audio: bool,
     pub(crate) is_video: bool,
     pub(crate) allowed_direction: Option<RTCRtpTransceiverDirection>,
+    pub(crate) preferred_id: Option<isize>,
 }
 
 impl MediaEngineHeaderExtension {
@@ -349,6 +353,30 @@ impl MediaEngine {
         }
     }
 
+    /// register_red_codec adds RED redundant audio on top of the registered Opus codec,
+    /// carrying the previous `distance` Opus payloads in each packet.
+    /// The Opus codec must be registered first.
+    pub fn register_red_codec(&mut self, payload_type: PayloadType, distance: usize) -> Result<()> {
+        let opus = self
+            .audio_codecs
+            .iter()
+            .find(|c| c.capability.mime_type.eq_ignore_ascii_case(MIME_TYPE_OPUS))
+            .ok_or(Error::ErrCodecNotFound)?;
+
+        let codec = RTCRtpCodecParameters {
+            capability: RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_RED.to_owned(),
+                clock_rate: opus.capability.clock_rate,
+                channels: opus.capability.channels,
+                sdp_fmtp_line: rtp::codecs::red::fmtp_line(opus.payload_type, distance),
+                rtcp_feedback: vec![],
+            },
+            payload_type,
+            ..Default::default()
+        };
+        self.register_codec(codec, RTPCodecType::Audio)
+    }
+
     /// Adds a header extension to the MediaEngine
     /// To determine the negotiated value use [`MediaEngine::get_header_extension_id`] after signaling is complete.
     ///
@@ -399,6 +427,43 @@ impl MediaEngine {
         Ok(())
     }
 
+    /// Adds a header extension to the MediaEngine like [`MediaEngine::register_header_extension`],
+    /// proposing `id` for it in offers we create.
+    ///
+    /// The `id` has to be between 1 and 14 and must not be the preferred ID of another extension.
+    /// It is only a preference, when the remote peer offers a different ID for the extension that
+    /// one is used, which [`MediaEngine::get_header_extension_id`] returns after signaling.
+    pub fn register_header_extension_with_id(
+        &mut self,
+        extension: RTCRtpHeaderExtensionCapability,
+        typ: RTPCodecType,
+        allowed_direction: Option<RTCRtpTransceiverDirection>,
+        id: isize,
+    ) -> Result<()> {
+        // The ID is taken by another extension, or this one already prefers a different ID
+        if !VALID_EXT_IDS.contains(&id)
+            || self.header_extensions.iter().any(|ext| {
+                if ext.uri == extension.uri {
+                    ext.preferred_id
+                        .is_some_and(|preferred_id| preferred_id != id)
+                } else {
+                    ext.preferred_id == Some(id)
+                }
+            })
+        {
+            return Err(Error::ErrRegisterHeaderExtensionInvalidID);
+        }
+
+        let uri = extension.uri.clone();
+        self.register_header_extension(extension, typ, allowed_direction)?;
+
+        if let Some(ext) = self.header_extensions.iter_mut().find(|ext| ext.uri == uri) {
+            ext.preferred_id = Some(id);
+        }
+
+        Ok(())
+    }
+
     /// register_feedback adds feedback mechanism to already registered codecs.
     pub fn register_feedback(&mut self, feedback: RTCPFeedback, typ: RTPCodecType) {
         match typ {
@@ -579,6 +644,7 @@ impl MediaEngine {
                         is_audio: local_extension.is_audio && typ == RTPCodecType::Audio,
                         is_video: local_extension.is_video && typ == RTPCodecType::Video,
                         allowed_direction: local_extension.allowed_direction,
+                        preferred_id: local_extension.preferred_id,
                     };
                     negotiated_header_extensions.insert(id, h);
                 }
@@ -743,11 +809,25 @@ impl MediaEngine {
                     continue;
                 }
 
-                // Figure out which (unused id) to propose.
-                let id = VALID_EXT_IDS.clone().find(|id| {
+                // Figure out which (unused id) to propose. The preferred id of the extension
+                // comes first, the ids other extensions prefer are only used as a last resort.
+                let is_unused = |id: &isize| {
                     !negotiated_header_extensions.keys().any(|nid| nid == id)
                         && !proposed_header_extensions.keys().any(|pid| pid == id)
-                });
+                };
+                let id = local_extension
+                    .preferred_id
+                    .filter(is_unused)
+                    .or_else(|| {
+                        VALID_EXT_IDS.clone().find(|id| {
+                            is_unused(id)
+                                && !self
+                                    .header_extensions
+                                    .iter()
+                                    .any(|e| e.preferred_id == Some(*id))
+                        })
+                    })
+                    .or_else(|| VALID_EXT_IDS.clone().find(is_unused));
 
                 if let Some(id) = id {
                     proposed_header_extensions.insert(
@@ -757,6 +837,7 @@ impl MediaEngine {
                             is_audio: local_extension.is_audio,
                             is_video: local_extension.is_video,
                             allowed_direction: local_extension.allowed_direction,
+                            preferred_id: local_extension.preferred_id,
                         },
                     );
 
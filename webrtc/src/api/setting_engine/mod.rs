@@ -7,13 +7,15 @@ use dtls::extension::extension_use_srtp::SrtpProtectionProfile;
 use ice::agent::agent_config::{InterfaceFilterFn, IpFilterFn};
 use ice::mdns::MulticastDnsMode;
 use ice::network_type::NetworkType;
-use ice::udp_network::UDPNetwork;
+use ice::tcp_mux::TCPMux;
+use ice::udp_network::{EphemeralUDP, UDPNetwork};
 use tokio::time::Duration;
 use util::vnet::net::*;
 
 use crate::dtls_transport::dtls_role::DTLSRole;
 use crate::error::{Error, Result};
 use crate::ice_transport::ice_candidate_type::RTCIceCandidateType;
+use crate::sctp_transport::SCTP_MAX_MESSAGE_SIZE;
 use crate::RECEIVE_MTU;
 
 #[derive(Default, Clone)]
@@ -26,10 +28,14 @@ pub struct Timeout {
     pub ice_disconnected_timeout: Option<Duration>,
     pub ice_failed_timeout: Option<Duration>,
     pub ice_keepalive_interval: Option<Duration>,
+    pub ice_consent_check_interval: Option<Duration>,
+    pub ice_consent_max_failures: Option<u16>,
     pub ice_host_acceptance_min_wait: Option<Duration>,
     pub ice_srflx_acceptance_min_wait: Option<Duration>,
     pub ice_prflx_acceptance_min_wait: Option<Duration>,
     pub ice_relay_acceptance_min_wait: Option<Duration>,
+    pub dtls_retransmission_interval: Option<Duration>,
+    pub sctp_shutdown_timeout: Option<Duration>,
 }
 
 #[derive(Default, Clone)]
@@ -70,13 +76,18 @@ pub struct SettingEngine {
     pub(crate) disable_srtcp_replay_protection: bool,
     pub(crate) vnet: Option<Arc<Net>>,
     //BufferFactory                             :func(packetType packetio.BufferPacketType, ssrc uint32) io.ReadWriteCloser,
-    //iceTCPMux                                 :ice.TCPMux,?
+    pub(crate) ice_tcp_mux: Option<Arc<dyn TCPMux + Send + Sync>>,
     //iceProxyDialer                            :proxy.Dialer,?
     pub(crate) udp_network: UDPNetwork,
     pub(crate) disable_media_engine_copy: bool,
     pub(crate) srtp_protection_profiles: Vec<SrtpProtectionProfile>,
     pub(crate) receive_mtu: usize,
     pub(crate) mid_generator: Option<Arc<dyn Fn(isize) -> String + Send + Sync>>,
+    pub(crate) enable_sctp_zero_checksum: bool,
+    pub(crate) enable_sctp_message_interleaving: bool,
+    pub(crate) sctp_max_message_size: u32,
+    pub(crate) sctp_max_receive_buffer_size: u32,
+    pub(crate) sctp_max_send_buffer_size: u32,
 }
 
 impl SettingEngine {
@@ -88,6 +99,16 @@ impl SettingEngine {
             RECEIVE_MTU
         }
     }
+
+    /// get_sctp_max_message_size returns the largest message we accept from the remote peer.
+    /// If SettingEngine's value is configured to 0 it returns the default
+    pub(crate) fn get_sctp_max_message_size(&self) -> u32 {
+        if self.sctp_max_message_size != 0 {
+            self.sctp_max_message_size
+        } else {
+            SCTP_MAX_MESSAGE_SIZE
+        }
+    }
     /// detach_data_channels enables detaching data channels. When enabled
     /// data channels have to be detached in the OnOpen callback using the
     /// DataChannel.Detach method.
@@ -116,6 +137,19 @@ impl SettingEngine {
         self.timeout.ice_keepalive_interval = keep_alive_interval;
     }
 
+    /// set_ice_consent_freshness enables RFC 7675 consent freshness checks on the selected
+    /// candidate pair.
+    /// * consent_check_interval is how often a consent check is sent, even if media is flowing. Consent freshness is disabled when this is None or 0
+    /// * consent_max_failures is how many consent checks in a row can go unanswered before the Agent is considered failed. Default is 6
+    pub fn set_ice_consent_freshness(
+        &mut self,
+        consent_check_interval: Option<Duration>,
+        consent_max_failures: Option<u16>,
+    ) {
+        self.timeout.ice_consent_check_interval = consent_check_interval;
+        self.timeout.ice_consent_max_failures = consent_max_failures;
+    }
+
     /// set_host_acceptance_min_wait sets the icehost_acceptance_min_wait
     pub fn set_host_acceptance_min_wait(&mut self, t: Option<Duration>) {
         self.timeout.ice_host_acceptance_min_wait = t;
@@ -136,6 +170,20 @@ impl SettingEngine {
         self.timeout.ice_relay_acceptance_min_wait = t;
     }
 
+    /// set_dtls_retransmission_interval sets how often a DTLS handshake flight is resent
+    /// while waiting for the answer of the remote peer. Leave this None for the default
+    /// of the dtls crate.
+    pub fn set_dtls_retransmission_interval(&mut self, interval: Option<Duration>) {
+        self.timeout.dtls_retransmission_interval = interval;
+    }
+
+    /// set_sctp_shutdown_timeout sets how long closing the SCTP association waits for the
+    /// data still queued on the data channels to reach the remote peer. Leave this None for
+    /// the default of 1 second, a zero duration closes right away.
+    pub fn set_sctp_shutdown_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout.sctp_shutdown_timeout = timeout;
+    }
+
     /// set_udp_network allows ICE traffic to come through Ephemeral or UDPMux.
     /// UDPMux drastically simplifying deployments where ports will need to be opened/forwarded.
     /// UDPMux should be started prior to creating PeerConnections.
@@ -143,6 +191,21 @@ impl SettingEngine {
         self.udp_network = udp_network;
     }
 
+    /// set_ephemeral_udp_port_range limits the pool of ephemeral ports that
+    /// ICE UDP connections can allocate from. This affects both host candidates,
+    /// and the local address of server reflexive candidates.
+    /// Replaces any UDPNetwork previously configured with set_udp_network.
+    pub fn set_ephemeral_udp_port_range(&mut self, port_min: u16, port_max: u16) -> Result<()> {
+        self.udp_network = UDPNetwork::Ephemeral(EphemeralUDP::new(port_min, port_max)?);
+        Ok(())
+    }
+
+    /// set_ice_tcp_mux enables ICE-TCP when set to a non-nil value. Make sure that
+    /// NetworkTypeTCP4 or NetworkTypeTCP6 is enabled as well.
+    pub fn set_ice_tcp_mux(&mut self, tcp_mux: Arc<dyn TCPMux + Send + Sync>) {
+        self.ice_tcp_mux = Some(tcp_mux);
+    }
+
     /// set_lite configures whether or not the ice agent should be a lite agent
     pub fn set_lite(&mut self, lite: bool) {
         self.candidates.ice_lite = lite;
@@ -311,6 +374,42 @@ impl SettingEngine {
         self.receive_mtu = receive_mtu;
     }
 
+    /// enable_sctp_zero_checksum announces support for the SCTP zero checksum
+    /// extension (RFC 9653). Since SCTP runs over DTLS, which already detects
+    /// corrupted packets, the CRC32c computation is skipped on both sides if the
+    /// remote peer announces support too.
+    pub fn enable_sctp_zero_checksum(&mut self, is_enabled: bool) {
+        self.enable_sctp_zero_checksum = is_enabled;
+    }
+
+    /// enable_sctp_message_interleaving announces support for SCTP message
+    /// interleaving (RFC 8260). When the remote peer supports it too, a large
+    /// message on one data channel no longer delays messages on the others.
+    pub fn enable_sctp_message_interleaving(&mut self, is_enabled: bool) {
+        self.enable_sctp_message_interleaving = is_enabled;
+    }
+
+    /// set_sctp_max_message_size sets the largest message the remote peer may send us,
+    /// announced through the 'a=max-message-size' SDP attribute.
+    /// Leave this 0 for the default sctp_max_message_size
+    pub fn set_sctp_max_message_size(&mut self, max_message_size: u32) {
+        self.sctp_max_message_size = max_message_size;
+    }
+
+    /// set_sctp_max_receive_buffer_size sets the receive window advertised to the remote peer,
+    /// i.e. how many bytes it may send before we read them.
+    /// Leave this 0 for the default of the sctp association
+    pub fn set_sctp_max_receive_buffer_size(&mut self, max_receive_buffer_size: u32) {
+        self.sctp_max_receive_buffer_size = max_receive_buffer_size;
+    }
+
+    /// set_sctp_max_send_buffer_size sets how many bytes each data channel may have
+    /// buffered for sending before writes to it wait.
+    /// Leave this 0 to not limit the send buffer
+    pub fn set_sctp_max_send_buffer_size(&mut self, max_send_buffer_size: u32) {
+        self.sctp_max_send_buffer_size = max_send_buffer_size;
+    }
+
     /// Sets a callback used to generate mid for transceivers created by this side of the RTCPeerconnection.
     /// By having separate "naming schemes" for mids generated by either side of a connection, it's
     /// possible to reduce complexity when handling SDP offers/answers clashing.
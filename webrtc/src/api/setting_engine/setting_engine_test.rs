@@ -32,6 +32,65 @@ fn test_set_connection_timeout() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_set_ice_consent_freshness() -> Result<()> {
+    let mut s = SettingEngine::default();
+
+    assert_eq!(s.timeout.ice_consent_check_interval, None);
+    assert_eq!(s.timeout.ice_consent_max_failures, None);
+
+    s.set_ice_consent_freshness(Some(Duration::from_secs(5)), Some(3));
+    assert_eq!(
+        s.timeout.ice_consent_check_interval,
+        Some(Duration::from_secs(5))
+    );
+    assert_eq!(s.timeout.ice_consent_max_failures, Some(3));
+
+    Ok(())
+}
+
+#[test]
+fn test_set_dtls_retransmission_interval() -> Result<()> {
+    let mut s = SettingEngine::default();
+
+    assert_eq!(s.timeout.dtls_retransmission_interval, None);
+
+    s.set_dtls_retransmission_interval(Some(Duration::from_millis(100)));
+    assert_eq!(
+        s.timeout.dtls_retransmission_interval,
+        Some(Duration::from_millis(100))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_set_sctp_shutdown_timeout() -> Result<()> {
+    let mut s = SettingEngine::default();
+
+    assert_eq!(s.timeout.sctp_shutdown_timeout, None);
+
+    s.set_sctp_shutdown_timeout(Some(Duration::ZERO));
+    assert_eq!(s.timeout.sctp_shutdown_timeout, Some(Duration::ZERO));
+
+    Ok(())
+}
+
+#[test]
+fn test_set_sctp_buffer_sizes() -> Result<()> {
+    let mut s = SettingEngine::default();
+
+    assert_eq!(s.sctp_max_receive_buffer_size, 0);
+    assert_eq!(s.sctp_max_send_buffer_size, 0);
+
+    s.set_sctp_max_receive_buffer_size(2 * 1024 * 1024);
+    s.set_sctp_max_send_buffer_size(512 * 1024);
+    assert_eq!(s.sctp_max_receive_buffer_size, 2 * 1024 * 1024);
+    assert_eq!(s.sctp_max_send_buffer_size, 512 * 1024);
+
+    Ok(())
+}
+
 #[test]
 fn test_detach_data_channels() -> Result<()> {
     let mut s = SettingEngine::default();
@@ -94,6 +153,27 @@ fn test_set_answering_dtls_role() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_set_ephemeral_udp_port_range() -> Result<()> {
+    let mut s = SettingEngine::default();
+
+    assert!(
+        s.set_ephemeral_udp_port_range(3000, 2999).is_err(),
+        "SetEphemeralUDPPortRange should fail if port_max < port_min"
+    );
+
+    s.set_ephemeral_udp_port_range(3000, 4000)?;
+    match &s.udp_network {
+        UDPNetwork::Ephemeral(ephemeral) => {
+            assert_eq!(ephemeral.port_min(), 3000);
+            assert_eq!(ephemeral.port_max(), 4000);
+        }
+        UDPNetwork::Muxed(_) => panic!("expected an ephemeral UDP network"),
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_set_replay_protection() -> Result<()> {
     let mut s = SettingEngine::default();
@@ -125,31 +205,23 @@ fn test_set_replay_protection() -> Result<()> {
     Ok(())
 }
 
-/*TODO:#[test] fn test_setting_engine_set_ice_tcp_mux() ->Result<()> {
-
-    listener, err := net.ListenTCP("tcp", &net.TCPAddr{})
-    if err != nil {
-        panic(err)
-    }
-
-    defer func() {
-        _ = listener.Close()
-    }()
-
-    tcpMux := NewICETCPMux(nil, listener, 8)
+#[tokio::test]
+async fn test_setting_engine_set_ice_tcp_mux() -> Result<()> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(util::Error::from)?;
+    let tcp_mux = ice::tcp_mux::TCPMuxDefault::new(ice::tcp_mux::TCPMuxParams::new(listener));
 
-    defer func() {
-        _ = tcpMux.Close()
-    }()
+    let mut s = SettingEngine::default();
+    assert!(s.ice_tcp_mux.is_none());
 
-     let mut s = SettingEngine::default();
-    settingEngine.SetICETCPMux(tcpMux)
+    s.set_ice_tcp_mux(tcp_mux.clone());
+    assert!(s.ice_tcp_mux.is_some());
 
-    assert.Equal(t, tcpMux, settingEngine.iceTCPMux)
+    tcp_mux.close().await?;
 
     Ok(())
 }
-*/
 
 #[tokio::test]
 async fn test_setting_engine_set_disable_media_engine_copy() -> Result<()> {
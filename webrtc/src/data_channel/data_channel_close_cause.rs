@@ -0,0 +1,123 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// DataChannelCloseCause indicates why a data channel was closed.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RTCDataChannelCloseCause {
+    /// DataChannelCloseCauseUnspecified indicates that the data channel has
+    /// not been closed (yet).
+    #[serde(rename = "unspecified")]
+    #[default]
+    Unspecified = 0,
+
+    /// DataChannelCloseCauseLocalClose indicates that the data channel was
+    /// closed locally with close().
+    #[serde(rename = "local-close")]
+    LocalClose,
+
+    /// DataChannelCloseCauseStreamReset indicates that the remote peer reset
+    /// the SCTP stream carrying the data channel.
+    #[serde(rename = "stream-reset")]
+    StreamReset,
+
+    /// DataChannelCloseCauseAssociationFailure indicates that the SCTP
+    /// association carrying the data channel was aborted, shut down by the
+    /// remote peer or failed.
+    #[serde(rename = "association-failure")]
+    AssociationFailure,
+
+    /// DataChannelCloseCauseTransportClosed indicates that the SCTP transport
+    /// carrying the data channel was stopped.
+    #[serde(rename = "transport-closed")]
+    TransportClosed,
+
+    /// DataChannelCloseCauseReadError indicates that the data channel could
+    /// not be read. The error is passed to the on_error handler.
+    #[serde(rename = "read-error")]
+    ReadError,
+}
+
+const DATA_CHANNEL_CLOSE_CAUSE_LOCAL_CLOSE_STR: &str = "local-close";
+const DATA_CHANNEL_CLOSE_CAUSE_STREAM_RESET_STR: &str = "stream-reset";
+const DATA_CHANNEL_CLOSE_CAUSE_ASSOCIATION_FAILURE_STR: &str = "association-failure";
+const DATA_CHANNEL_CLOSE_CAUSE_TRANSPORT_CLOSED_STR: &str = "transport-closed";
+const DATA_CHANNEL_CLOSE_CAUSE_READ_ERROR_STR: &str = "read-error";
+
+impl From<u8> for RTCDataChannelCloseCause {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => RTCDataChannelCloseCause::LocalClose,
+            2 => RTCDataChannelCloseCause::StreamReset,
+            3 => RTCDataChannelCloseCause::AssociationFailure,
+            4 => RTCDataChannelCloseCause::TransportClosed,
+            5 => RTCDataChannelCloseCause::ReadError,
+            _ => RTCDataChannelCloseCause::Unspecified,
+        }
+    }
+}
+
+impl fmt::Display for RTCDataChannelCloseCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match *self {
+            RTCDataChannelCloseCause::LocalClose => DATA_CHANNEL_CLOSE_CAUSE_LOCAL_CLOSE_STR,
+            RTCDataChannelCloseCause::StreamReset => DATA_CHANNEL_CLOSE_CAUSE_STREAM_RESET_STR,
+            RTCDataChannelCloseCause::AssociationFailure => {
+                DATA_CHANNEL_CLOSE_CAUSE_ASSOCIATION_FAILURE_STR
+            }
+            RTCDataChannelCloseCause::TransportClosed => {
+                DATA_CHANNEL_CLOSE_CAUSE_TRANSPORT_CLOSED_STR
+            }
+            RTCDataChannelCloseCause::ReadError => DATA_CHANNEL_CLOSE_CAUSE_READ_ERROR_STR,
+            RTCDataChannelCloseCause::Unspecified => crate::UNSPECIFIED_STR,
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_data_channel_close_cause_from_u8() {
+        let tests = vec![
+            (RTCDataChannelCloseCause::Unspecified, 0),
+            (RTCDataChannelCloseCause::LocalClose, 1),
+            (RTCDataChannelCloseCause::StreamReset, 2),
+            (RTCDataChannelCloseCause::AssociationFailure, 3),
+            (RTCDataChannelCloseCause::TransportClosed, 4),
+            (RTCDataChannelCloseCause::ReadError, 5),
+        ];
+
+        for (cause, v) in tests {
+            assert_eq!(RTCDataChannelCloseCause::from(cause as u8), cause);
+            assert_eq!(RTCDataChannelCloseCause::from(v), cause);
+        }
+    }
+
+    #[test]
+    fn test_data_channel_close_cause_string() {
+        let tests = vec![
+            (
+                RTCDataChannelCloseCause::Unspecified,
+                crate::UNSPECIFIED_STR,
+            ),
+            (RTCDataChannelCloseCause::LocalClose, "local-close"),
+            (RTCDataChannelCloseCause::StreamReset, "stream-reset"),
+            (
+                RTCDataChannelCloseCause::AssociationFailure,
+                "association-failure",
+            ),
+            (
+                RTCDataChannelCloseCause::TransportClosed,
+                "transport-closed",
+            ),
+            (RTCDataChannelCloseCause::ReadError, "read-error"),
+        ];
+
+        for (cause, expected_string) in tests {
+            assert_eq!(cause.to_string(), expected_string)
+        }
+    }
+}
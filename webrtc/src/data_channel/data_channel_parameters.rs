@@ -6,7 +6,7 @@ pub struct DataChannelParameters {
     pub label: String,
     pub protocol: String,
     pub ordered: bool,
-    pub max_packet_life_time: u16,
-    pub max_retransmits: u16,
+    pub max_packet_life_time: Option<u16>,
+    pub max_retransmits: Option<u16>,
     pub negotiated: Option<u16>,
 }
@@ -9,6 +9,7 @@ use waitgroup::WaitGroup;
 use super::*;
 use crate::api::media_engine::MediaEngine;
 use crate::api::{APIBuilder, API};
+use crate::data_channel::data_channel_close_cause::RTCDataChannelCloseCause;
 use crate::data_channel::data_channel_init::RTCDataChannelInit;
 //use log::LevelFilter;
 //use std::io::Write;
@@ -20,6 +21,7 @@ use crate::ice_transport::ice_connection_state::RTCIceConnectionState;
 use crate::ice_transport::ice_gatherer::{RTCIceGatherOptions, RTCIceGatherer};
 use crate::ice_transport::ice_parameters::RTCIceParameters;
 use crate::ice_transport::ice_role::RTCIceRole;
+use crate::ice_transport::ice_transport_state::RTCIceTransportState;
 use crate::ice_transport::RTCIceTransport;
 use crate::peer_connection::configuration::RTCConfiguration;
 use crate::peer_connection::peer_connection_test::*;
@@ -377,7 +379,7 @@ async fn test_data_channel_parameters_max_packet_life_time_exchange() -> Result<
     );
     assert_eq!(
         dc.max_packet_lifetime(),
-        max_packet_life_time,
+        Some(max_packet_life_time),
         "should match"
     );
 
@@ -394,7 +396,7 @@ async fn test_data_channel_parameters_max_packet_life_time_exchange() -> Result<
         );
         assert_eq!(
             d.max_packet_lifetime(),
-            max_packet_life_time,
+            Some(max_packet_life_time),
             "should match"
         );
         let done_tx2 = Arc::clone(&done_tx);
@@ -428,7 +430,7 @@ async fn test_data_channel_parameters_max_retransmits_exchange() -> Result<()> {
 
     // Check if parameters are correctly set
     assert!(!dc.ordered(), "Ordered should be set to false");
-    assert_eq!(dc.max_retransmits(), max_retransmits, "should match");
+    assert_eq!(dc.max_retransmits(), Some(max_retransmits), "should match");
 
     let done_tx = Arc::new(Mutex::new(Some(done_tx)));
     answer_pc.on_data_channel(Box::new(move |d: Arc<RTCDataChannel>| {
@@ -440,7 +442,50 @@ async fn test_data_channel_parameters_max_retransmits_exchange() -> Result<()> {
 
         // Check if parameters are correctly set
         assert!(!d.ordered(), "Ordered should be set to false");
-        assert_eq!(max_retransmits, d.max_retransmits(), "should match");
+        assert_eq!(Some(max_retransmits), d.max_retransmits(), "should match");
+        let done_tx2 = Arc::clone(&done_tx);
+        Box::pin(async move {
+            let mut done = done_tx2.lock().await;
+            done.take();
+        })
+    }));
+
+    close_reliability_param_test(&mut offer_pc, &mut answer_pc, done_rx).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_data_channel_parameters_zero_max_retransmits_exchange() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+
+    // Zero retransmits still makes the channel partially reliable
+    let options = RTCDataChannelInit {
+        ordered: Some(false),
+        max_retransmits: Some(0),
+        ..Default::default()
+    };
+
+    let (mut offer_pc, mut answer_pc, dc, done_tx, done_rx) =
+        set_up_data_channel_parameters_test(&api, Some(options)).await?;
+
+    assert_eq!(dc.max_retransmits, Some(0), "should match");
+    assert_eq!(dc.max_packet_lifetime, None, "should match");
+
+    let done_tx = Arc::new(Mutex::new(Some(done_tx)));
+    answer_pc.on_data_channel(Box::new(move |d: Arc<RTCDataChannel>| {
+        // Make sure this is the data channel we were looking for. (Not the one
+        // created in signalPair).
+        if d.label() != EXPECTED_LABEL {
+            return Box::pin(async {});
+        }
+
+        // Check if parameters are correctly set
+        assert!(!d.ordered(), "Ordered should be set to false");
+        assert_eq!(d.max_retransmits, Some(0), "should match");
+        assert_eq!(d.max_packet_lifetime, None, "should match");
         let done_tx2 = Arc::clone(&done_tx);
         Box::pin(async move {
             let mut done = done_tx2.lock().await;
@@ -588,6 +633,72 @@ async fn test_data_channel_parameters_negotiated_exchange() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_data_channel_negotiated_created_after_remote_sends() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+
+    const EXPECTED_MESSAGE: &str = "Hello World";
+
+    let options = RTCDataChannelInit {
+        negotiated: Some(600),
+        ..Default::default()
+    };
+
+    let (mut offer_pc, mut answer_pc, offer_datachannel, _done_tx, _done_rx) =
+        set_up_data_channel_parameters_test(&api, Some(options.clone())).await?;
+
+    answer_pc.on_data_channel(Box::new(move |d: Arc<RTCDataChannel>| {
+        // Ignore our default channel, exists to force ICE candidates. See signalPair for more info
+        if d.label() == "initial_data_channel" {
+            return Box::pin(async {});
+        }
+        panic!("OnDataChannel must not be fired when negotiated == true");
+    }));
+
+    let (open_tx, mut open_rx) = mpsc::channel::<()>(1);
+    offer_datachannel.on_open(Box::new(move || {
+        Box::pin(async move {
+            let _ = open_tx.send(()).await;
+        })
+    }));
+
+    signal_pair(&mut offer_pc, &mut answer_pc).await?;
+    let _ = open_rx.recv().await;
+
+    // The offerer starts sending before the answerer has created its side
+    offer_datachannel
+        .send_text(EXPECTED_MESSAGE.to_owned())
+        .await?;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let answer_datachannel = answer_pc
+        .create_data_channel(EXPECTED_LABEL, Some(options))
+        .await?;
+    assert_eq!(answer_datachannel.ready_state(), RTCDataChannelState::Open);
+
+    let (message_tx, mut message_rx) = mpsc::channel::<DataChannelMessage>(1);
+    answer_datachannel.on_message(Box::new(move |msg: DataChannelMessage| {
+        let message_tx = message_tx.clone();
+        Box::pin(async move {
+            let _ = message_tx.send(msg).await;
+        })
+    }));
+
+    // The message sent before the channel was created here is the first one delivered
+    let msg = tokio::time::timeout(Duration::from_secs(5), message_rx.recv())
+        .await
+        .expect("timed out waiting for the message")
+        .unwrap();
+    assert!(msg.is_string);
+    assert_eq!(msg.data, EXPECTED_MESSAGE);
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_data_channel_event_handlers() -> Result<()> {
     let api = APIBuilder::new().build();
@@ -743,7 +854,7 @@ async fn test_data_channel_parameters_go() -> Result<()> {
         // Check if parameters are correctly set
         assert!(dc.ordered(), "Ordered should be set to true");
         assert_eq!(
-            max_packet_life_time,
+            Some(max_packet_life_time),
             dc.max_packet_lifetime(),
             "should match"
         );
@@ -759,7 +870,7 @@ async fn test_data_channel_parameters_go() -> Result<()> {
             // Check if parameters are correctly set
             assert!(d.ordered, "Ordered should be set to true");
             assert_eq!(
-                max_packet_life_time,
+                Some(max_packet_life_time),
                 d.max_packet_lifetime(),
                 "should match"
             );
@@ -1202,6 +1313,94 @@ async fn test_eof_no_detach() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_data_channel_close_cause() -> Result<()> {
+    let label: &str = "test-channel";
+
+    let api = APIBuilder::new().build();
+
+    // Set up two peer connections.
+    let mut pca = api.new_peer_connection(RTCConfiguration::default()).await?;
+    let mut pcb = api.new_peer_connection(RTCConfiguration::default()).await?;
+
+    let (dcb_closed_ch_tx, mut dcb_closed_ch_rx) = mpsc::channel::<RTCDataChannelCloseCause>(1);
+
+    let dcb_closed_ch_tx = Arc::new(dcb_closed_ch_tx);
+    pcb.on_data_channel(Box::new(move |dc: Arc<RTCDataChannel>| {
+        if dc.label() != label {
+            return Box::pin(async {});
+        }
+
+        let dcb_closed_ch_tx2 = Arc::clone(&dcb_closed_ch_tx);
+        Box::pin(async move {
+            assert_eq!(dc.close_cause(), RTCDataChannelCloseCause::Unspecified);
+
+            let dc2 = Arc::clone(&dc);
+            dc.on_close(Box::new(move || {
+                let dcb_closed_ch_tx3 = Arc::clone(&dcb_closed_ch_tx2);
+                let cause = dc2.close_cause();
+                Box::pin(async move {
+                    let _ = dcb_closed_ch_tx3.send(cause).await;
+                })
+            }));
+        })
+    }));
+
+    let dca = pca.create_data_channel(label, None).await?;
+    let dca2 = Arc::clone(&dca);
+    dca.on_open(Box::new(move || {
+        Box::pin(async move {
+            assert!(dca2.close().await.is_ok(), "should succeed");
+        })
+    }));
+
+    signal_pair(&mut pca, &mut pcb).await?;
+
+    // The remote side sees its stream being reset
+    let cause = dcb_closed_ch_rx.recv().await;
+    assert_eq!(cause, Some(RTCDataChannelCloseCause::StreamReset));
+    assert_eq!(dca.close_cause(), RTCDataChannelCloseCause::LocalClose);
+
+    close_pair_now(&pca, &pcb).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_data_channel_label_and_protocol_size_limit() -> Result<()> {
+    let api = APIBuilder::new().build();
+    let pc = api.new_peer_connection(RTCConfiguration::default()).await?;
+
+    let too_long = "a".repeat(65536);
+
+    let result = pc.create_data_channel(&too_long, None).await;
+    assert!(
+        matches!(result, Err(Error::ErrStringSizeLimit)),
+        "a label longer than 65535 bytes should be rejected"
+    );
+
+    let result = pc
+        .create_data_channel(
+            "data",
+            Some(RTCDataChannelInit {
+                protocol: Some(too_long),
+                ..Default::default()
+            }),
+        )
+        .await;
+    assert!(
+        matches!(result, Err(Error::ErrProtocolTooLarge)),
+        "a protocol longer than 65535 bytes should be rejected"
+    );
+
+    // Neither is kept around to be negotiated
+    assert!(pc.sctp().data_channels.lock().await.is_empty());
+
+    pc.close().await?;
+
+    Ok(())
+}
+
 // Assert that a Session Description that doesn't follow
 // draft-ietf-mmusic-sctp-sdp is still accepted
 #[tokio::test]
@@ -1502,3 +1701,88 @@ async fn test_data_channel_ortc_e2e() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_data_channel_ortc_custom_transport() -> Result<()> {
+    let api = APIBuilder::new().build();
+
+    let (stack_a, stack_b) = new_ortc_pair(&api).await?;
+
+    let (await_message_tx, mut await_message_rx) = mpsc::channel::<String>(1);
+    let await_message_tx = Arc::new(await_message_tx);
+    stack_b
+        .sctp
+        .on_data_channel(Box::new(move |d: Arc<RTCDataChannel>| {
+            let await_message_tx2 = Arc::clone(&await_message_tx);
+            Box::pin(async move {
+                d.on_message(Box::new(move |msg: DataChannelMessage| {
+                    let await_message_tx3 = Arc::clone(&await_message_tx2);
+                    Box::pin(async move {
+                        let _ = await_message_tx3
+                            .send(String::from_utf8(msg.data.to_vec()).unwrap())
+                            .await;
+                    })
+                }));
+            })
+        }));
+
+    // Run the stacks over an in-memory transport instead of ICE
+    let (conn_a, conn_b) = util::conn::conn_pipe::pipe();
+    stack_a
+        .ice
+        .start_with_conn(Arc::new(conn_a), RTCIceRole::Controlling)
+        .await?;
+    stack_b
+        .ice
+        .start_with_conn(Arc::new(conn_b), RTCIceRole::Controlled)
+        .await?;
+    assert_eq!(stack_a.ice.state(), RTCIceTransportState::Connected);
+    assert_eq!(
+        stack_a
+            .ice
+            .start_with_conn(
+                Arc::new(util::conn::conn_pipe::pipe().0),
+                RTCIceRole::Controlling
+            )
+            .await,
+        Err(Error::ErrICETransportNotInNew)
+    );
+
+    let dtls_parameters_a = stack_a.dtls.get_local_parameters()?;
+    let dtls_parameters_b = stack_b.dtls.get_local_parameters()?;
+    let sctp_capabilities_a = stack_a.sctp.get_capabilities();
+    let sctp_capabilities_b = stack_b.sctp.get_capabilities();
+    let (start_tx, mut start_rx) = mpsc::channel(1);
+    let stack_b2 = Arc::clone(&stack_b);
+    tokio::spawn(async move {
+        let result = async {
+            stack_b2.dtls.start(dtls_parameters_a).await?;
+            stack_b2.sctp.start(sctp_capabilities_a).await
+        };
+        let _ = start_tx.send(result.await).await;
+    });
+    stack_a.dtls.start(dtls_parameters_b).await?;
+    stack_a.sctp.start(sctp_capabilities_b).await?;
+    start_rx.recv().await.unwrap()?;
+
+    let channel_a = api
+        .new_data_channel(
+            Arc::clone(&stack_a.sctp),
+            DataChannelParameters {
+                label: "Foo".to_owned(),
+                ..Default::default()
+            },
+        )
+        .await?;
+    channel_a.send_text("ABC".to_owned()).await?;
+
+    let msg = tokio::time::timeout(Duration::from_secs(5), await_message_rx.recv())
+        .await
+        .expect("timed out waiting for the message");
+    assert_eq!(msg.as_deref(), Some("ABC"));
+
+    stack_a.close().await?;
+    stack_b.close().await?;
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod data_channel_test;
 
+pub mod data_channel_close_cause;
 pub mod data_channel_init;
 pub mod data_channel_message;
 pub mod data_channel_parameters;
@@ -15,6 +16,7 @@ use std::time::SystemTime;
 use arc_swap::ArcSwapOption;
 use bytes::Bytes;
 use data::message::message_channel_open::ChannelType;
+use data_channel_close_cause::RTCDataChannelCloseCause;
 use data_channel_message::*;
 use data_channel_parameters::*;
 use data_channel_state::RTCDataChannelState;
@@ -29,9 +31,6 @@ use crate::sctp_transport::RTCSctpTransport;
 use crate::stats::stats_collector::StatsCollector;
 use crate::stats::{DataChannelStats, StatsReportType};
 
-/// message size limit for Chromium
-const DATA_CHANNEL_BUFFER_SIZE: u16 = u16::MAX;
-
 pub type OnMessageHdlrFn = Box<
     dyn (FnMut(DataChannelMessage) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>)
         + Send
@@ -52,12 +51,13 @@ pub struct RTCDataChannel {
     pub(crate) stats_id: String,
     pub(crate) label: String,
     pub(crate) ordered: bool,
-    pub(crate) max_packet_lifetime: u16,
-    pub(crate) max_retransmits: u16,
+    pub(crate) max_packet_lifetime: Option<u16>,
+    pub(crate) max_retransmits: Option<u16>,
     pub(crate) protocol: String,
     pub(crate) negotiated: bool,
     pub(crate) id: AtomicU16,
     pub(crate) ready_state: Arc<AtomicU8>, // DataChannelState
+    pub(crate) close_cause: Arc<AtomicU8>, // DataChannelCloseCause
     pub(crate) buffered_amount_low_threshold: AtomicUsize,
     pub(crate) detach_called: Arc<AtomicBool>,
 
@@ -129,27 +129,29 @@ impl RTCDataChannel {
             let channel_type;
             let reliability_parameter;
 
-            if self.max_packet_lifetime == 0 && self.max_retransmits == 0 {
-                reliability_parameter = 0u32;
-                if self.ordered {
-                    channel_type = ChannelType::Reliable;
-                } else {
-                    channel_type = ChannelType::ReliableUnordered;
-                }
-            } else if self.max_retransmits != 0 {
-                reliability_parameter = self.max_retransmits as u32;
+            // Zero is a valid limit for both options (e.g. no retransmissions at all),
+            // so only an unset option means reliable delivery.
+            if let Some(max_retransmits) = self.max_retransmits {
+                reliability_parameter = max_retransmits as u32;
                 if self.ordered {
                     channel_type = ChannelType::PartialReliableRexmit;
                 } else {
                     channel_type = ChannelType::PartialReliableRexmitUnordered;
                 }
-            } else {
-                reliability_parameter = self.max_packet_lifetime as u32;
+            } else if let Some(max_packet_lifetime) = self.max_packet_lifetime {
+                reliability_parameter = max_packet_lifetime as u32;
                 if self.ordered {
                     channel_type = ChannelType::PartialReliableTimed;
                 } else {
                     channel_type = ChannelType::PartialReliableTimedUnordered;
                 }
+            } else {
+                reliability_parameter = 0u32;
+                if self.ordered {
+                    channel_type = ChannelType::Reliable;
+                } else {
+                    channel_type = ChannelType::ReliableUnordered;
+                }
             }
 
             let cfg = data::data_channel::Config {
@@ -172,7 +174,19 @@ impl RTCDataChannel {
                 );
             }
 
-            let dc = data::data_channel::DataChannel::dial(&association, self.id(), cfg).await?;
+            let unclaimed_stream = if self.negotiated {
+                sctp_transport.take_unclaimed_stream(self.id()).await
+            } else {
+                None
+            };
+            let dc = if let Some(unclaimed) = unclaimed_stream {
+                // The remote already started sending on this pre-negotiated channel
+                let dc = data::data_channel::DataChannel::client(unclaimed.stream, cfg).await?;
+                dc.unread(unclaimed.ppi, unclaimed.data);
+                dc
+            } else {
+                data::data_channel::DataChannel::dial(&association, self.id(), cfg).await?
+            };
 
             // buffered_amount_low_threshold and on_buffered_amount_low might be set earlier
             dc.set_buffered_amount_low_threshold(
@@ -235,6 +249,7 @@ impl RTCDataChannel {
 
     /// on_close sets an event handler which is invoked when
     /// the underlying data transport has been closed.
+    /// The reason is available from close_cause() by the time it is invoked.
     pub fn on_close(&self, f: OnCloseHdlrFn) {
         self.on_close_handler.store(Some(Arc::new(Mutex::new(f))));
     }
@@ -267,15 +282,19 @@ impl RTCDataChannel {
 
         if !self.setting_engine.detach.data_channels {
             let ready_state = Arc::clone(&self.ready_state);
+            let close_cause = Arc::clone(&self.close_cause);
             let on_message_handler = Arc::clone(&self.on_message_handler);
             let on_close_handler = Arc::clone(&self.on_close_handler);
             let on_error_handler = Arc::clone(&self.on_error_handler);
             let notify_rx = self.notify_tx.clone();
+            let max_message_size = self.setting_engine.get_sctp_max_message_size() as usize;
             tokio::spawn(async move {
                 RTCDataChannel::read_loop(
                     notify_rx,
                     dc,
+                    max_message_size,
                     ready_state,
+                    close_cause,
                     on_message_handler,
                     on_close_handler,
                     on_error_handler,
@@ -291,24 +310,35 @@ impl RTCDataChannel {
         self.on_error_handler.store(Some(Arc::new(Mutex::new(f))));
     }
 
+    /// read_loop delivers the incoming messages to the on_message handler. Messages larger
+    /// than the max-message-size we announced fail the read and close the channel.
+    #[allow(clippy::too_many_arguments)]
     async fn read_loop(
         notify_rx: Arc<Notify>,
         data_channel: Arc<data::data_channel::DataChannel>,
+        max_message_size: usize,
         ready_state: Arc<AtomicU8>,
+        close_cause: Arc<AtomicU8>,
         on_message_handler: Arc<ArcSwapOption<Mutex<OnMessageHdlrFn>>>,
         on_close_handler: Arc<ArcSwapOption<Mutex<OnCloseHdlrFn>>>,
         on_error_handler: Arc<ArcSwapOption<Mutex<OnErrorHdlrFn>>>,
     ) {
-        let mut buffer = vec![0u8; DATA_CHANNEL_BUFFER_SIZE as usize];
+        let mut buffer = vec![0u8; max_message_size];
         loop {
             let (n, is_string) = tokio::select! {
                 _ = notify_rx.notified() => break,
-                result = data_channel.read_data_channel(&mut buffer) => {
+                result = data_channel.read_message(&mut buffer) => {
                     match result{
                         // EOF (`data_channel` was either closed or the underlying stream got
                         // reset by the remote) => close and run `on_close` handler.
-                        Ok((0, _)) =>
+                        Ok(None) =>
                         {
+                            let cause = if data_channel.is_association_closed() {
+                                RTCDataChannelCloseCause::AssociationFailure
+                            } else {
+                                RTCDataChannelCloseCause::StreamReset
+                            };
+                            RTCDataChannel::record_close_cause(&close_cause, cause);
                             ready_state.store(RTCDataChannelState::Closed as u8, Ordering::SeqCst);
 
                             let on_close_handler2 = Arc::clone(&on_close_handler);
@@ -321,8 +351,12 @@ impl RTCDataChannel {
 
                             break;
                         }
-                        Ok((n, is_string)) => (n, is_string),
+                        Ok(Some((n, is_string))) => (n, is_string),
                         Err(err) => {
+                            RTCDataChannel::record_close_cause(
+                                &close_cause,
+                                RTCDataChannelCloseCause::ReadError,
+                            );
                             ready_state.store(RTCDataChannelState::Closed as u8, Ordering::SeqCst);
 
                             let on_error_handler2 = Arc::clone(&on_error_handler);
@@ -361,6 +395,7 @@ impl RTCDataChannel {
     /// send sends the binary message to the DataChannel peer
     pub async fn send(&self, data: &Bytes) -> Result<usize> {
         self.ensure_open()?;
+        self.ensure_message_size(data.len()).await?;
 
         let data_channel = self.data_channel.lock().await;
         if let Some(dc) = &*data_channel {
@@ -373,10 +408,12 @@ impl RTCDataChannel {
     /// send_text sends the text message to the DataChannel peer
     pub async fn send_text(&self, s: impl Into<String>) -> Result<usize> {
         self.ensure_open()?;
+        let data = Bytes::from(s.into());
+        self.ensure_message_size(data.len()).await?;
 
         let data_channel = self.data_channel.lock().await;
         if let Some(dc) = &*data_channel {
-            Ok(dc.write_data_channel(&Bytes::from(s.into()), true).await?)
+            Ok(dc.write_data_channel(&data, true).await?)
         } else {
             Err(Error::ErrClosedPipe)
         }
@@ -390,6 +427,16 @@ impl RTCDataChannel {
         }
     }
 
+    async fn ensure_message_size(&self, len: usize) -> Result<()> {
+        let sctp_transport = self.sctp_transport.lock().await;
+        if let Some(sctp_transport) = sctp_transport.as_ref().and_then(|st| st.upgrade()) {
+            if len > sctp_transport.max_message_size() as usize {
+                return Err(Error::ErrOutboundMessageTooLarge);
+            }
+        }
+        Ok(())
+    }
+
     /// detach allows you to detach the underlying datachannel. This provides
     /// an idiomatic API to work with, however it disables the OnMessage callback.
     /// Before calling Detach you have to enable this behavior by calling
@@ -420,6 +467,7 @@ impl RTCDataChannel {
             return Ok(());
         }
 
+        self.set_close_cause(RTCDataChannelCloseCause::LocalClose);
         self.set_ready_state(RTCDataChannelState::Closing);
         self.notify_tx.notify_waiters();
 
@@ -446,13 +494,14 @@ impl RTCDataChannel {
 
     /// max_packet_lifetime represents the length of the time window (msec) during
     /// which transmissions and retransmissions may occur in unreliable mode.
-    pub fn max_packet_lifetime(&self) -> u16 {
+    /// It is None if the option was not set.
+    pub fn max_packet_lifetime(&self) -> Option<u16> {
         self.max_packet_lifetime
     }
 
     /// max_retransmits represents the maximum number of retransmissions that are
-    /// attempted in unreliable mode.
-    pub fn max_retransmits(&self) -> u16 {
+    /// attempted in unreliable mode. It is None if the option was not set.
+    pub fn max_retransmits(&self) -> Option<u16> {
         self.max_retransmits
     }
 
@@ -483,6 +532,12 @@ impl RTCDataChannel {
         self.ready_state.load(Ordering::SeqCst).into()
     }
 
+    /// close_cause represents the reason the DataChannel was closed. It is
+    /// Unspecified as long as the DataChannel has not been closed.
+    pub fn close_cause(&self) -> RTCDataChannelCloseCause {
+        self.close_cause.load(Ordering::SeqCst).into()
+    }
+
     /// buffered_amount represents the number of bytes of application data
     /// (UTF-8 text and binary data) that have been queued using send(). Even
     /// though the data transmission can occur in parallel, the returned value
@@ -553,4 +608,19 @@ impl RTCDataChannel {
     pub(crate) fn set_ready_state(&self, r: RTCDataChannelState) {
         self.ready_state.store(r as u8, Ordering::SeqCst);
     }
+
+    pub(crate) fn set_close_cause(&self, cause: RTCDataChannelCloseCause) {
+        RTCDataChannel::record_close_cause(&self.close_cause, cause);
+    }
+
+    /// record_close_cause stores the cause unless one was recorded before,
+    /// since only the first reason a DataChannel closes is meaningful.
+    fn record_close_cause(close_cause: &AtomicU8, cause: RTCDataChannelCloseCause) {
+        let _ = close_cause.compare_exchange(
+            RTCDataChannelCloseCause::Unspecified as u8,
+            cause as u8,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+    }
 }
@@ -59,10 +59,9 @@ impl From<&SessionDescription> for DTLSRole {
             for attribute in &media_section.attributes {
                 if attribute.key == "setup" {
                     if let Some(value) = &attribute.value {
-                        match value.as_str() {
-                            "active" => return DTLSRole::Client,
-                            "passive" => return DTLSRole::Server,
-                            _ => return DTLSRole::Auto,
+                        match DTLSRole::from(ConnectionRole::from(value.as_str())) {
+                            DTLSRole::Unspecified => return DTLSRole::Auto,
+                            role => return role,
                         };
                     } else {
                         return DTLSRole::Auto;
@@ -75,6 +74,19 @@ impl From<&SessionDescription> for DTLSRole {
     }
 }
 
+/// Maps an a=setup value to the DTLS role of the endpoint that advertised it,
+/// RFC 5763 Section 5: the active endpoint initiates the handshake as the client.
+impl From<ConnectionRole> for DTLSRole {
+    fn from(connection_role: ConnectionRole) -> Self {
+        match connection_role {
+            ConnectionRole::Active => DTLSRole::Client,
+            ConnectionRole::Passive => DTLSRole::Server,
+            ConnectionRole::Actpass => DTLSRole::Auto,
+            _ => DTLSRole::Unspecified,
+        }
+    }
+}
+
 impl DTLSRole {
     pub(crate) fn to_connection_role(self) -> ConnectionRole {
         match self {
@@ -202,3 +202,73 @@ async fn test_peer_connection_dtls_role_setting_engine_client() -> Result<()> {
 
     run_test(DTLSRole::Client).await
 }
+
+#[tokio::test]
+async fn test_validate_fingerprint_multiple_algorithms() -> Result<()> {
+    use crate::dtls_transport::dtls_fingerprint::RTCDtlsFingerprint;
+    use crate::ice_transport::ice_gatherer::RTCIceGatherOptions;
+
+    let api = APIBuilder::new().build();
+    let gatherer = Arc::new(api.new_ice_gatherer(RTCIceGatherOptions::default())?);
+    let ice = Arc::new(api.new_ice_transport(gatherer));
+    let dtls = api.new_dtls_transport(ice, vec![])?;
+
+    let cert = b"certificate";
+    let fingerprint = |algorithm: &str, hashed: &[u8]| RTCDtlsFingerprint {
+        algorithm: algorithm.to_owned(),
+        value: hashed
+            .iter()
+            .map(|x| format!("{x:02X}"))
+            .collect::<Vec<String>>()
+            .join(":"),
+    };
+
+    let tests = vec![
+        (
+            "Unknown algorithms are skipped",
+            vec![
+                fingerprint("md5", &[0xAA]),
+                fingerprint("sha-384", &Sha384::digest(cert)),
+            ],
+            None,
+        ),
+        (
+            "sha-1 fingerprints are checked too",
+            vec![fingerprint("sha-1", &sha1::Sha1::digest(cert))],
+            None,
+        ),
+        (
+            "Any matching fingerprint is accepted",
+            vec![
+                fingerprint("sha-256", &[0xAA]),
+                fingerprint("sha-512", &Sha512::digest(cert)),
+            ],
+            None,
+        ),
+        (
+            "Mismatching fingerprints",
+            vec![fingerprint("sha-256", &[0xAA])],
+            Some(Error::ErrNoMatchingCertificateFingerprint),
+        ),
+        (
+            "Only unknown algorithms",
+            vec![fingerprint("md5", &[0xAA])],
+            Some(Error::ErrUnsupportedFingerprintAlgorithm),
+        ),
+    ];
+
+    for (name, fingerprints, expected) in tests {
+        {
+            let mut remote_parameters = dtls.remote_parameters.lock().await;
+            remote_parameters.fingerprints = fingerprints;
+        }
+
+        match (dtls.validate_fingerprint(cert).await, expected) {
+            (Ok(()), None) => {}
+            (Err(err), Some(expected)) => assert_eq!(err, expected, "{name}"),
+            (result, expected) => panic!("{name}: got {result:?}, expected {expected:?}"),
+        }
+    }
+
+    Ok(())
+}
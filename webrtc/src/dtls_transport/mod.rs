@@ -13,7 +13,8 @@ use dtls_role::*;
 use interceptor::stream_info::StreamInfo;
 use interceptor::{Interceptor, RTCPReader, RTPReader};
 use portable_atomic::{AtomicBool, AtomicU8};
-use sha2::{Digest, Sha256};
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
 use srtp::protection_profile::ProtectionProfile;
 use srtp::session::Session;
 use srtp::stream::Stream;
@@ -373,6 +374,9 @@ impl RTCDtlsTransport {
             if self.setting_engine.replay_protection.dtls != 0 {
                 dtls_config.replay_protection_window = self.setting_engine.replay_protection.dtls;
             }
+            if let Some(interval) = self.setting_engine.timeout.dtls_retransmission_interval {
+                dtls_config.flight_interval = interval;
+            }
 
             // Connect as DTLS Client/Server, function is blocking and we
             // must not hold the DTLSTransport lock
@@ -539,14 +543,19 @@ impl RTCDtlsTransport {
 
     pub(crate) async fn validate_fingerprint(&self, remote_cert: &[u8]) -> Result<()> {
         let remote_parameters = self.remote_parameters.lock().await;
+        let mut supported_algorithm = false;
         for fp in &remote_parameters.fingerprints {
-            if fp.algorithm != "sha-256" {
-                return Err(Error::ErrUnsupportedFingerprintAlgorithm);
-            }
+            // Fingerprints using unknown hash functions are ignored, RFC 8122 Section 5
+            let hashed = match fp.algorithm.to_lowercase().as_str() {
+                "sha-1" => Sha1::digest(remote_cert).to_vec(),
+                "sha-224" => Sha224::digest(remote_cert).to_vec(),
+                "sha-256" => Sha256::digest(remote_cert).to_vec(),
+                "sha-384" => Sha384::digest(remote_cert).to_vec(),
+                "sha-512" => Sha512::digest(remote_cert).to_vec(),
+                _ => continue,
+            };
+            supported_algorithm = true;
 
-            let mut h = Sha256::new();
-            h.update(remote_cert);
-            let hashed = h.finalize();
             let values: Vec<String> = hashed.iter().map(|x| format! {"{x:02x}"}).collect();
             let remote_value = values.join(":").to_lowercase();
 
@@ -555,7 +564,11 @@ impl RTCDtlsTransport {
             }
         }
 
-        Err(Error::ErrNoMatchingCertificateFingerprint)
+        if supported_algorithm {
+            Err(Error::ErrNoMatchingCertificateFingerprint)
+        } else {
+            Err(Error::ErrUnsupportedFingerprintAlgorithm)
+        }
     }
 
     pub(crate) fn ensure_ice_conn(&self) -> Result<()> {
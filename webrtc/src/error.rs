@@ -31,6 +31,11 @@ pub enum Error {
     #[error("data channel not open")]
     ErrDataChannelNotOpen,
 
+    /// ErrOutboundMessageTooLarge indicates that a message passed to a data
+    /// channel exceeds the max-message-size announced by the remote peer.
+    #[error("outbound message larger than the remote max-message-size")]
+    ErrOutboundMessageTooLarge,
+
     /// ErrCertificateExpired indicates that an x509 certificate has expired.
     #[error("x509Cert expired")]
     ErrCertificateExpired,
@@ -154,6 +159,11 @@ pub enum Error {
     #[error("set_remote_description called with multiple conflicting fingerprint")]
     ErrSessionDescriptionConflictingFingerprints,
 
+    /// ErrSessionDescriptionInvalidSetupRole indicates set_remote_description was called with a SessionDescription that
+    /// has a setup attribute which isn't allowed for its type
+    #[error("set_remote_description called with an invalid setup attribute")]
+    ErrSessionDescriptionInvalidSetupRole,
+
     /// ErrSessionDescriptionMissingIceUfrag indicates set_remote_description was called with a SessionDescription that
     /// is missing an ice-ufrag value
     #[error("set_remote_description called with no ice-ufrag")]
@@ -190,6 +200,11 @@ pub enum Error {
     #[error("unable to start track, codec is not supported by remote")]
     ErrUnsupportedCodec,
 
+    /// ErrFrameTransformUnsupportedCodec indicates that the payloader of the codec parses the frames,
+    /// so they can't be transformed before they are packetized
+    #[error("frames of this codec can't be transformed before packetization")]
+    ErrFrameTransformUnsupportedCodec,
+
     /// ErrSenderWithNoCodecs indicates that a RTPSender was created without any codecs. To send media the MediaEngine needs at
     /// least one configured codec.
     #[error("unable to populate media section, RTPSender created with no codecs")]
@@ -225,6 +240,11 @@ pub enum Error {
     #[error("no header extension ID was free to use(this means the maximum of 15 extensions have been registered)")]
     ErrRegisterHeaderExtensionNoFreeID,
 
+    /// ErrRegisterHeaderExtensionInvalidID indicates that a extension was registered with an ID outside
+    /// of 1 through 14, an ID another extension prefers or different IDs for two different calls.
+    #[error("a header extension must be registered with the same valid and unused ID each time")]
+    ErrRegisterHeaderExtensionInvalidID,
+
     /// ErrSimulcastProbeOverflow indicates that too many Simulcast probe streams are in flight and the requested SSRC was ignored
     #[error("simulcast probe limit has been reached, new SSRC has been discarded")]
     ErrSimulcastProbeOverflow,
@@ -273,6 +293,8 @@ pub enum Error {
     ErrSDPDoesNotMatchOffer,
     #[error("new sdp does not match previous answer")]
     ErrSDPDoesNotMatchAnswer,
+    #[error("remote description doesn't multiplex RTCP as required by the rtcp mux policy")]
+    ErrSDPRtcpMuxRequired,
     #[error("provided value is not a valid enum value of type SDPType")]
     ErrPeerConnSDPTypeInvalidValue,
     #[error("invalid state change op")]
@@ -336,6 +358,22 @@ pub enum Error {
     ErrRTPSenderDTLSTransportNil,
     #[error("Send has already been called")]
     ErrRTPSenderSendAlreadyCalled,
+    #[error("set_parameters can not modify read-only or encoding count parameters")]
+    ErrRTPSenderInvalidModification,
+    #[error("RTPSender stopped")]
+    ErrRTPSenderStopped,
+    #[error("Encoding must have a RID to be added to an RTPSender")]
+    ErrRTPSenderRidNil,
+    #[error("RTPSender must have a base encoding with a RID to add simulcast encodings")]
+    ErrRTPSenderNoBaseEncoding,
+    #[error("Simulcast encoding must have the same id, stream id and kind as the base encoding")]
+    ErrRTPSenderBaseEncodingMismatch,
+    #[error("RTPSender already has an encoding with the same RID")]
+    ErrRTPSenderRIDCollision,
+    #[error("no encoding found for RID")]
+    ErrRTPSenderNoTrackForRID,
+    #[error("new track must not replace the track of a simulcast RTPSender")]
+    ErrRTPSenderNewTrackHasIncorrectEnvelope,
     #[error("errRTPSenderTrackNil")]
     ErrRTPTransceiverCannotChangeMid,
     #[error("invalid state change in RTPTransceiver.setSending")]
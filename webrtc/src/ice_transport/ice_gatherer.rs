@@ -113,6 +113,8 @@ impl RTCIceGatherer {
             disconnected_timeout: self.setting_engine.timeout.ice_disconnected_timeout,
             failed_timeout: self.setting_engine.timeout.ice_failed_timeout,
             keepalive_interval: self.setting_engine.timeout.ice_keepalive_interval,
+            consent_check_interval: self.setting_engine.timeout.ice_consent_check_interval,
+            consent_max_failures: self.setting_engine.timeout.ice_consent_max_failures,
             candidate_types,
             host_acceptance_min_wait: self.setting_engine.timeout.ice_host_acceptance_min_wait,
             srflx_acceptance_min_wait: self.setting_engine.timeout.ice_srflx_acceptance_min_wait,
@@ -131,7 +133,7 @@ impl RTCIceGatherer {
                 .clone(),
             local_ufrag: self.setting_engine.candidates.username_fragment.clone(),
             local_pwd: self.setting_engine.candidates.password.clone(),
-            //TODO: TCPMux:                 self.setting_engine.iceTCPMux,
+            tcp_mux: self.setting_engine.ice_tcp_mux.clone(),
             //TODO: ProxyDialer:            self.setting_engine.iceProxyDialer,
             ..Default::default()
         };
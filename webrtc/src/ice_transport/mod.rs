@@ -196,20 +196,62 @@ impl RTCIceTransport {
         }
     }
 
+    /// start_with_conn starts the transport over a user provided packet transport instead of
+    /// the ICE agent, e.g. to run the DTLS, SCTP and SRTP stack over a proprietary overlay.
+    /// The conn is expected to be connected already, so no connectivity checks are run and the
+    /// transport moves straight to the connected state. The role is still used to pick the
+    /// DTLS role when the remote one is auto.
+    pub async fn start_with_conn(
+        &self,
+        conn: Arc<dyn Conn + Send + Sync>,
+        role: RTCIceRole,
+    ) -> Result<()> {
+        if self.state() != RTCIceTransportState::New {
+            return Err(Error::ErrICETransportNotInNew);
+        }
+        if role == RTCIceRole::Unspecified {
+            return Err(Error::ErrICERoleUnknown);
+        }
+
+        let config = Config {
+            conn: Arc::clone(&conn),
+            buffer_size: self.gatherer.setting_engine.get_receive_mtu(),
+        };
+
+        {
+            let mut internal = self.internal.lock().await;
+            internal.role = role;
+            internal.conn = Some(conn);
+            internal.mux = Some(Mux::new(config));
+        }
+
+        self.set_state(RTCIceTransportState::Connected);
+        if let Some(handler) = &*self.on_connection_state_change_handler.load() {
+            let mut f = handler.lock().await;
+            f(RTCIceTransportState::Connected).await;
+        }
+
+        Ok(())
+    }
+
     /// restart is not exposed currently because ORTC has users create a whole new ICETransport
     /// so for now lets keep it private so we don't cause ORTC users to depend on non-standard APIs
     pub(crate) async fn restart(&self) -> Result<()> {
         if let Some(agent) = self.gatherer.get_agent().await {
-            agent
-                .restart(
-                    self.gatherer
-                        .setting_engine
-                        .candidates
-                        .username_fragment
-                        .clone(),
-                    self.gatherer.setting_engine.candidates.password.clone(),
-                )
-                .await?;
+            let candidates = &self.gatherer.setting_engine.candidates;
+            let (ufrag, pwd) = agent.get_local_user_credentials().await;
+            // A restart has to change the credentials, so static ones can't be used twice.
+            // Empty ones make the agent generate new random credentials.
+            let (ufrag, pwd) =
+                if candidates.username_fragment == ufrag || candidates.password == pwd {
+                    (String::new(), String::new())
+                } else {
+                    (
+                        candidates.username_fragment.clone(),
+                        candidates.password.clone(),
+                    )
+                };
+            agent.restart(ufrag, pwd).await?;
         } else {
             return Err(Error::ErrICEAgentNotExist);
         }
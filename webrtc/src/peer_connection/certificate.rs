@@ -80,7 +80,12 @@ impl RTCCertificate {
         let expires = if cfg!(target_arch = "arm") {
             // Workaround for issue overflow when adding duration to instant on armv7
             // https://github.com/webrtc-rs/examples/issues/5 https://github.com/chronotope/chrono/issues/343
-            SystemTime::now().add(Duration::from_secs(172800)) //60*60*48 or 2 days
+            // Only fall back to 2 days when `not_after`, e.g. the far future default, doesn't fit.
+            u64::try_from(not_after.unix_timestamp())
+                .ok()
+                .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+                .unwrap_or_else(|| SystemTime::now().add(Duration::from_secs(172800)))
+        //60*60*48 or 2 days
         } else {
             not_after.into()
         };
@@ -97,6 +102,34 @@ impl RTCCertificate {
 
     /// Generates a new certificate with default [`CertificateParams`] using the given keypair.
     pub fn from_key_pair(key_pair: KeyPair) -> Result<Self> {
+        RTCCertificate::from_key_pair_params(
+            CertificateParams::new(vec![math_rand_alpha(16)]).unwrap(),
+            key_pair,
+        )
+    }
+
+    /// Generates a new certificate using the given keypair, which is valid for `validity`
+    /// from now on.
+    ///
+    /// Browsers limit the lifetime of certificates they generate, use this to do the same
+    /// instead of the far future default of [`CertificateParams`].
+    pub fn from_key_pair_with_validity(key_pair: KeyPair, validity: Duration) -> Result<Self> {
+        let mut params = CertificateParams::new(vec![math_rand_alpha(16)]).unwrap();
+        let now = SystemTime::now();
+        // Allow for some clock skew of the remote peer
+        params.not_before = now
+            .checked_sub(Duration::from_secs(86400))
+            .unwrap_or(now)
+            .into();
+        params.not_after = now
+            .checked_add(validity)
+            .ok_or_else(|| Error::new("certificate validity overflows".to_owned()))?
+            .into();
+
+        RTCCertificate::from_key_pair_params(params, key_pair)
+    }
+
+    fn from_key_pair_params(params: CertificateParams, key_pair: KeyPair) -> Result<Self> {
         if !(key_pair.is_compatible(&rcgen::PKCS_ED25519)
             || key_pair.is_compatible(&rcgen::PKCS_ECDSA_P256_SHA256)
             || key_pair.is_compatible(&rcgen::PKCS_RSA_SHA256))
@@ -104,10 +137,7 @@ impl RTCCertificate {
             return Err(Error::new("Unsupported key_pair".to_owned()));
         }
 
-        RTCCertificate::from_params(
-            CertificateParams::new(vec![math_rand_alpha(16)]).unwrap(),
-            key_pair,
-        )
+        RTCCertificate::from_params(params, key_pair)
     }
 
     /// Parses a certificate from the ASCII PEM format.
@@ -157,6 +187,14 @@ impl RTCCertificate {
         }
     }
 
+    /// expires returns the timestamp after which this certificate is no longer valid.
+    ///
+    /// A PeerConnection can't be created with an expired certificate, check this before
+    /// reusing a persisted one.
+    pub fn expires(&self) -> SystemTime {
+        self.expires
+    }
+
     /// Serializes the certificate (including the private key) in PKCS#8 format in PEM.
     #[cfg(any(doc, feature = "pem"))]
     pub fn serialize_pem(&self) -> String {
@@ -275,6 +313,21 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_generate_certificate_with_validity() -> Result<()> {
+        let kp = KeyPair::generate_for(&rcgen::PKCS_ECDSA_P256_SHA256)?;
+        let cert = RTCCertificate::from_key_pair_with_validity(kp, Duration::from_secs(3600))?;
+
+        let remaining = cert.expires().duration_since(SystemTime::now()).unwrap();
+        assert!(remaining <= Duration::from_secs(3600));
+        assert!(remaining > Duration::from_secs(3500));
+
+        let kp = KeyPair::generate_for(&rcgen::PKCS_ECDSA_P256_SHA256)?;
+        assert!(RTCCertificate::from_key_pair_with_validity(kp, Duration::MAX).is_err());
+
+        Ok(())
+    }
+
     #[cfg(feature = "pem")]
     #[test]
     fn test_certificate_serialize_pem_and_from_pem() -> Result<()> {
@@ -15,6 +15,7 @@ pub mod peer_connection_state;
 pub mod policy;
 pub mod signaling_state;
 
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::Ordering;
@@ -32,6 +33,8 @@ use peer_connection_internal::*;
 use portable_atomic::{AtomicBool, AtomicU64, AtomicU8};
 use rand::{thread_rng, Rng};
 use rcgen::KeyPair;
+use rtcp::payload_feedbacks::full_intra_request::FullIntraRequest;
+use rtcp::payload_feedbacks::picture_loss_indication::PictureLossIndication;
 use smol_str::SmolStr;
 use srtp::stream::Stream;
 use tokio::sync::{mpsc, Mutex};
@@ -81,8 +84,8 @@ use crate::rtp_transceiver::rtp_receiver::RTCRtpReceiver;
 use crate::rtp_transceiver::rtp_sender::RTCRtpSender;
 use crate::rtp_transceiver::rtp_transceiver_direction::RTCRtpTransceiverDirection;
 use crate::rtp_transceiver::{
-    find_by_mid, handle_unknown_rtp_packet, satisfy_type_and_direction, RTCRtpTransceiver,
-    RTCRtpTransceiverInit, SSRC,
+    find_by_mid, handle_unknown_rtp_header, handle_unknown_rtp_packet, satisfy_type_and_direction,
+    RTCRtpTransceiver, RTCRtpTransceiverInit, SSRC, TYPE_RTCP_FB_CCM, TYPE_RTCP_FB_NACK,
 };
 use crate::sctp_transport::sctp_transport_capabilities::SCTPTransportCapabilities;
 use crate::sctp_transport::sctp_transport_state::RTCSctpTransportState;
@@ -195,6 +198,9 @@ pub struct RTCPeerConnection {
 
     interceptor_rtcp_writer: Arc<dyn RTCPWriter + Send + Sync>,
 
+    /// Sequence numbers of the next Full Intra Request sent by request_key_frame, per media SSRC
+    fir_sequence_numbers: Mutex<HashMap<SSRC, u8>>,
+
     interceptor: Arc<dyn Interceptor + Send + Sync>,
 
     pub(crate) internal: Arc<PeerConnectionInternal>,
@@ -255,6 +261,7 @@ impl RTCPeerConnection {
             ),
             interceptor,
             interceptor_rtcp_writer,
+            fir_sequence_numbers: Mutex::new(HashMap::new()),
             internal,
             configuration,
             idp_login_url: None,
@@ -472,28 +479,21 @@ impl RTCPeerConnection {
                     if let Some(m) = m {
                         // Step 5.3.1
                         if t.direction().has_send() {
-                            let dmsid = match m.attribute(ATTR_KEY_MSID).and_then(|o| o) {
-                                Some(m) => m,
-                                None => return true, // doesn't contain a single a=msid line
-                            };
+                            let msids = m.msids();
+                            if msids.is_empty() {
+                                return true; // doesn't contain a single a=msid line
+                            }
 
                             let sender = t.sender().await;
                             // (...)or the number of MSIDs from the a=msid lines in this m= section,
                             // or the MSID values themselves, differ from what is in
                             // transceiver.sender.[[AssociatedMediaStreamIds]], return true.
-
-                            // TODO: This check should be robuster by storing all streams in the
-                            // local description so we can compare all of them. For no we only
-                            // consider the first one.
-
-                            let stream_ids = sender.associated_media_stream_ids();
-                            // Different number of lines, 1 vs 0
-                            if stream_ids.is_empty() {
-                                return true;
-                            }
-
-                            // different stream id
-                            if dmsid.split_whitespace().next() != Some(&stream_ids[0]) {
+                            let mut described: Vec<&str> =
+                                msids.iter().map(|(stream_id, _)| *stream_id).collect();
+                            let mut stream_ids = sender.associated_media_stream_ids();
+                            described.sort_unstable();
+                            stream_ids.sort_unstable();
+                            if described != stream_ids {
                                 return true;
                             }
                         }
@@ -551,12 +551,19 @@ impl RTCPeerConnection {
                 if t.stopped.load(Ordering::SeqCst) {
                     let search_mid = match t.mid() {
                         Some(mid) => mid,
-                        None => return false,
+                        None => continue,
                     };
 
-                    if let Some(remote_desc) = &*params.current_remote_description.lock().await {
-                        return get_by_mid(search_mid.as_str(), local_desc).is_some()
-                            || get_by_mid(search_mid.as_str(), remote_desc).is_some();
+                    // A stopped transceiver only needs negotiation while its m= section
+                    // hasn't been rejected yet, the remaining transceivers still need checking.
+                    if let Some(remote_desc) = &current_remote_description {
+                        let is_unrejected = |desc: &RTCSessionDescription| {
+                            let m = get_by_mid(search_mid.as_str(), desc);
+                            matches!(m, Some(m) if m.media_name.port.value != 0)
+                        };
+                        if is_unrejected(local_desc) || is_unrejected(remote_desc) {
+                            return true;
+                        }
                     }
                 }
             }
@@ -864,6 +871,58 @@ impl RTCPeerConnection {
         Ok(offer)
     }
 
+    /// aggregate_connection_state combines the states of the ICE and DTLS transport,
+    /// checking the conditions in the order of the spec.
+    /// <https://www.w3.org/TR/webrtc/#rtcpeerconnectionstate-enum>
+    pub(crate) fn aggregate_connection_state(
+        is_closed: bool,
+        ice_connection_state: RTCIceConnectionState,
+        dtls_transport_state: RTCDtlsTransportState,
+    ) -> RTCPeerConnectionState {
+        let ice_is_new = matches!(
+            ice_connection_state,
+            RTCIceConnectionState::Unspecified | RTCIceConnectionState::New
+        );
+        let dtls_is_new = matches!(
+            dtls_transport_state,
+            RTCDtlsTransportState::Unspecified | RTCDtlsTransportState::New
+        );
+
+        if is_closed {
+            // The RTCPeerConnection object's [[IsClosed]] slot is true.
+            RTCPeerConnectionState::Closed
+        } else if ice_connection_state == RTCIceConnectionState::Failed
+            || dtls_transport_state == RTCDtlsTransportState::Failed
+        {
+            // Any of the RTCIceTransports or RTCDtlsTransports are in a "failed" state.
+            RTCPeerConnectionState::Failed
+        } else if ice_connection_state == RTCIceConnectionState::Disconnected
+            && dtls_transport_state != RTCDtlsTransportState::Connecting
+        {
+            // Any of the RTCIceTransports or RTCDtlsTransports are in the "disconnected"
+            // state and none of them are in the "failed" or "connecting" or "checking" state.
+            RTCPeerConnectionState::Disconnected
+        } else if (ice_is_new || ice_connection_state == RTCIceConnectionState::Closed)
+            && (dtls_is_new || dtls_transport_state == RTCDtlsTransportState::Closed)
+        {
+            // None of the previous states apply and all RTCIceTransports and
+            // RTCDtlsTransports are in the "new" or "closed" state.
+            RTCPeerConnectionState::New
+        } else if ice_is_new
+            || ice_connection_state == RTCIceConnectionState::Checking
+            || dtls_is_new
+            || dtls_transport_state == RTCDtlsTransportState::Connecting
+        {
+            // None of the previous states apply and any RTCIceTransport or RTCDtlsTransport
+            // is in the "new", "connecting" or "checking" state.
+            RTCPeerConnectionState::Connecting
+        } else {
+            // None of the previous states apply and all RTCIceTransports and RTCDtlsTransports
+            // are in the "connected", "completed" or "closed" state.
+            RTCPeerConnectionState::Connected
+        }
+    }
+
     /// Update the PeerConnectionState given the state of relevant transports
     /// <https://www.w3.org/TR/webrtc/#rtcpeerconnectionstate-enum>
     async fn update_connection_state(
@@ -875,28 +934,11 @@ impl RTCPeerConnection {
         ice_connection_state: RTCIceConnectionState,
         dtls_transport_state: RTCDtlsTransportState,
     ) {
-        let connection_state =
-            // The RTCPeerConnection object's [[IsClosed]] slot is true.
-            if is_closed.load(Ordering::SeqCst) {
-                RTCPeerConnectionState::Closed
-            } else if ice_connection_state == RTCIceConnectionState::Failed || dtls_transport_state == RTCDtlsTransportState::Failed {
-                // Any of the RTCIceTransports or RTCDtlsTransports are in a "failed" state.
-                RTCPeerConnectionState::Failed
-            } else if ice_connection_state == RTCIceConnectionState::Disconnected {
-                // Any of the RTCIceTransports or RTCDtlsTransports are in the "disconnected"
-                // state and none of them are in the "failed" or "connecting" or "checking" state.
-                RTCPeerConnectionState::Disconnected
-            } else if ice_connection_state == RTCIceConnectionState::Connected && dtls_transport_state == RTCDtlsTransportState::Connected {
-                // All RTCIceTransports and RTCDtlsTransports are in the "connected", "completed" or "closed"
-                // state and at least one of them is in the "connected" or "completed" state.
-                RTCPeerConnectionState::Connected
-            } else if ice_connection_state == RTCIceConnectionState::Checking && dtls_transport_state == RTCDtlsTransportState::Connecting {
-                //  Any of the RTCIceTransports or RTCDtlsTransports are in the "connecting" or
-                // "checking" state and none of them is in the "failed" state.
-                RTCPeerConnectionState::Connecting
-            } else {
-                RTCPeerConnectionState::New
-            };
+        let connection_state = RTCPeerConnection::aggregate_connection_state(
+            is_closed.load(Ordering::SeqCst),
+            ice_connection_state,
+            dtls_transport_state,
+        );
 
         if peer_connection_state.load(Ordering::SeqCst) == connection_state as u8 {
             return;
@@ -1072,9 +1114,12 @@ impl RTCPeerConnection {
                                 sd.sdp_type,
                             );
                             if next_state.is_ok() {
-                                let mut pending_local_description =
-                                    self.internal.pending_local_description.lock().await;
-                                *pending_local_description = None;
+                                {
+                                    let mut pending_local_description =
+                                        self.internal.pending_local_description.lock().await;
+                                    *pending_local_description = None;
+                                }
+                                self.rollback_transceivers().await;
                             }
                             next_state
                         }
@@ -1164,9 +1209,12 @@ impl RTCPeerConnection {
                                 sd.sdp_type,
                             );
                             if next_state.is_ok() {
-                                let mut pending_remote_description =
-                                    self.internal.pending_remote_description.lock().await;
-                                *pending_remote_description = None;
+                                {
+                                    let mut pending_remote_description =
+                                        self.internal.pending_remote_description.lock().await;
+                                    *pending_remote_description = None;
+                                }
+                                self.rollback_transceivers().await;
                             }
                             next_state
                         }
@@ -1197,6 +1245,7 @@ impl RTCPeerConnection {
                     .signaling_state
                     .store(next_state as u8, Ordering::SeqCst);
                 if self.signaling_state() == RTCSignalingState::Stable {
+                    self.internal.remote_offer_transceivers.lock().await.clear();
                     self.internal
                         .is_negotiation_needed
                         .store(false, Ordering::SeqCst);
@@ -1209,12 +1258,69 @@ impl RTCPeerConnection {
         }
     }
 
+    /// rollback_transceivers undoes what applying the rolled back offer did to the transceivers,
+    /// <https://www.w3.org/TR/webrtc/#set-description> (step 4.6.6 for rollback).
+    /// Transceivers it created are removed unless a track has been attached to them since, and
+    /// the ones it associated with a media section lose their mid again.
+    async fn rollback_transceivers(&self) {
+        let mut negotiated_mids = vec![];
+        for desc in [
+            self.current_local_description().await,
+            self.current_remote_description().await,
+        ]
+        .iter()
+        .flatten()
+        {
+            if let Some(parsed) = &desc.parsed {
+                negotiated_mids.extend(
+                    parsed
+                        .media_descriptions
+                        .iter()
+                        .filter_map(get_mid_value)
+                        .map(SmolStr::from),
+                );
+            }
+        }
+
+        let created = {
+            let mut remote_offer_transceivers =
+                self.internal.remote_offer_transceivers.lock().await;
+            std::mem::take(&mut *remote_offer_transceivers)
+        };
+        let mut removed = vec![];
+        for t in created {
+            if t.sender().await.track().await.is_none() {
+                removed.push(t);
+            }
+        }
+
+        let mut rtp_transceivers = self.internal.rtp_transceivers.lock().await;
+        rtp_transceivers.retain(|t| !removed.iter().any(|r| Arc::ptr_eq(t, r)));
+        for t in &*rtp_transceivers {
+            if matches!(t.mid(), Some(mid) if !negotiated_mids.contains(&mid)) {
+                t.clear_mid();
+            }
+        }
+        drop(rtp_transceivers);
+
+        for t in removed {
+            if let Err(err) = t.stop().await {
+                log::warn!("Failed to stop rolled back transceiver: {}", err);
+            }
+        }
+    }
+
     /// set_local_description sets the SessionDescription of the local peer
     pub async fn set_local_description(&self, mut desc: RTCSessionDescription) -> Result<()> {
         if self.internal.is_closed.load(Ordering::SeqCst) {
             return Err(Error::ErrConnectionClosed);
         }
 
+        // A rollback carries no SDP, it only returns to the last stable state
+        if desc.sdp_type == RTCSdpType::Rollback {
+            return self.set_description(&desc, StateChangeOp::SetLocal).await;
+        }
+
         let have_local_description = {
             let current_local_description = self.internal.current_local_description.lock().await;
             current_local_description.is_some()
@@ -1332,12 +1438,30 @@ impl RTCPeerConnection {
             return Err(Error::ErrConnectionClosed);
         }
 
+        // A rollback carries no SDP, it only returns to the last stable state
+        if desc.sdp_type == RTCSdpType::Rollback {
+            return self.set_description(&desc, StateChangeOp::SetRemote).await;
+        }
+
         let is_renegotiation = {
             let current_remote_description = self.internal.current_remote_description.lock().await;
             current_remote_description.is_some()
         };
 
-        desc.parsed = Some(desc.unmarshal()?);
+        let parsed = desc.unmarshal()?;
+        check_rtcp_mux(&parsed, self.configuration.rtcp_mux_policy)?;
+        check_setup_role(&parsed, desc.sdp_type)?;
+        if desc.sdp_type == RTCSdpType::Answer {
+            let pending_local_description = self.internal.pending_local_description.lock().await;
+            if let Some(offer) = pending_local_description
+                .as_ref()
+                .and_then(|d| d.parsed.as_ref())
+            {
+                parsed.validate_bundle_answer(offer)?;
+            }
+        }
+
+        desc.parsed = Some(parsed);
         self.set_description(&desc, StateChangeOp::SetRemote)
             .await?;
 
@@ -1432,6 +1556,11 @@ impl RTCPeerConnection {
                             .await;
 
                             self.internal.add_rtp_transceiver(Arc::clone(&t)).await;
+                            self.internal
+                                .remote_offer_transceivers
+                                .lock()
+                                .await
+                                .push(Arc::clone(&t));
 
                             if t.mid().is_none() {
                                 t.set_mid(SmolStr::from(mid_value))?;
@@ -1547,7 +1676,7 @@ impl RTCPeerConnection {
 
             let remote_is_lite = Self::is_lite_set(parsed);
 
-            let (fingerprint, fingerprint_hash) = extract_fingerprint(parsed)?;
+            let fingerprints = extract_fingerprints(parsed)?;
 
             // If one of the agents is lite and the other one is not, the lite agent must be the controlling agent.
             // If both or neither agents are lite the offering agent is controlling.
@@ -1580,16 +1709,14 @@ impl RTCPeerConnection {
                         let rd = Arc::clone(&remote_desc);
                         let ru = remote_ufrag.clone();
                         let rp = remote_pwd.clone();
-                        let fp = fingerprint.clone();
-                        let fp_hash = fingerprint_hash.clone();
+                        let fps = fingerprints.clone();
                         Box::pin(async move {
                             log::trace!(
                                 "start_transports: ice_role={}, dtls_role={}",
                                 ice_role,
                                 dtls_role,
                             );
-                            pc.start_transports(ice_role, dtls_role, ru, rp, fp, fp_hash)
-                                .await;
+                            pc.start_transports(ice_role, dtls_role, ru, rp, fps).await;
 
                             if we_offer {
                                 let _ = pc.start_rtp(false, rd).await;
@@ -1629,6 +1756,11 @@ impl RTCPeerConnection {
     /// add_ice_candidate accepts an ICE candidate string and adds it
     /// to the existing set of candidates.
     pub async fn add_ice_candidate(&self, candidate: RTCIceCandidateInit) -> Result<()> {
+        // https://www.w3.org/TR/webrtc/#dom-peerconnection-addicecandidate (step #4)
+        if self.internal.is_closed.load(Ordering::SeqCst) {
+            return Err(Error::ErrConnectionClosed);
+        }
+
         if self.remote_description().await.is_none() {
             return Err(Error::ErrNoRemoteDescription);
         }
@@ -1821,6 +1953,11 @@ impl RTCPeerConnection {
             return Err(Error::ErrConnectionClosed);
         }
 
+        // https://w3c.github.io/webrtc-pc/#peer-to-peer-data-api (Step #5)
+        if label.len() > 65535 {
+            return Err(Error::ErrStringSizeLimit);
+        }
+
         let mut params = DataChannelParameters {
             label: label.to_owned(),
             ordered: true,
@@ -1837,14 +1974,10 @@ impl RTCPeerConnection {
             }
 
             // https://w3c.github.io/webrtc-pc/#peer-to-peer-data-api (Step #7)
-            if let Some(max_packet_life_time) = options.max_packet_life_time {
-                params.max_packet_life_time = max_packet_life_time;
-            }
+            params.max_packet_life_time = options.max_packet_life_time;
 
             // https://w3c.github.io/webrtc-pc/#peer-to-peer-data-api (Step #8)
-            if let Some(max_retransmits) = options.max_retransmits {
-                params.max_retransmits = max_retransmits;
-            }
+            params.max_retransmits = options.max_retransmits;
 
             // https://w3c.github.io/webrtc-pc/#peer-to-peer-data-api (Step #10)
             if let Some(protocol) = options.protocol {
@@ -1866,7 +1999,7 @@ impl RTCPeerConnection {
         ));
 
         // https://w3c.github.io/webrtc-pc/#peer-to-peer-data-api (Step #16)
-        if d.max_packet_lifetime != 0 && d.max_retransmits != 0 {
+        if d.max_packet_lifetime.is_some() && d.max_retransmits.is_some() {
             return Err(Error::ErrRetransmitsOrPacketLifeTime);
         }
 
@@ -1904,6 +2037,47 @@ impl RTCPeerConnection {
         Ok(self.interceptor_rtcp_writer.write(pkts, &a).await?)
     }
 
+    /// request_key_frame asks the remote sender of the media with the given SSRC for a keyframe,
+    /// e.g. when a new subscriber starts consuming it. A Picture Loss Indication is sent, unless
+    /// the codec of the remote track only negotiated Full Intra Requests.
+    pub async fn request_key_frame(&self, media_ssrc: SSRC) -> Result<usize> {
+        let mut use_fir = false;
+        for receiver in self.get_receivers().await {
+            for track in receiver.tracks().await {
+                if track.ssrc() != media_ssrc {
+                    continue;
+                }
+
+                let feedback = track.codec().capability.rtcp_feedback;
+                let supports = |typ: &str, parameter: &str| {
+                    feedback
+                        .iter()
+                        .any(|f| f.typ == typ && f.parameter == parameter)
+                };
+                use_fir = !supports(TYPE_RTCP_FB_NACK, "pli") && supports(TYPE_RTCP_FB_CCM, "fir");
+            }
+        }
+
+        let pkt: Box<dyn rtcp::packet::Packet + Send + Sync> = if use_fir {
+            let sequence_number = self.next_fir_sequence_number(media_ssrc).await;
+            Box::new(FullIntraRequest::new(0, media_ssrc, sequence_number))
+        } else {
+            Box::new(PictureLossIndication::new(0, media_ssrc))
+        };
+
+        self.write_rtcp(&[pkt]).await
+    }
+
+    /// next_fir_sequence_number returns the sequence number of the next Full Intra Request for
+    /// `media_ssrc`. RFC 5104 section 4.3.1.2 keeps a separate counter for every media sender.
+    async fn next_fir_sequence_number(&self, media_ssrc: SSRC) -> u8 {
+        let mut fir_sequence_numbers = self.fir_sequence_numbers.lock().await;
+        let sequence_number = fir_sequence_numbers.entry(media_ssrc).or_insert(0);
+        let current = *sequence_number;
+        *sequence_number = sequence_number.wrapping_add(1);
+        current
+    }
+
     /// close ends the PeerConnection
     pub async fn close(&self) -> Result<()> {
         // https://www.w3.org/TR/webrtc/#dom-rtcpeerconnection-close (step #1)
@@ -47,6 +47,9 @@ pub(crate) struct PeerConnectionInternal {
 
     pub(super) sctp_transport: Arc<RTCSctpTransport>,
     pub(super) rtp_transceivers: Arc<Mutex<Vec<Arc<RTCRtpTransceiver>>>>,
+    /// transceivers created by applying the pending remote offer, removed again if
+    /// that offer is rolled back
+    pub(super) remote_offer_transceivers: Mutex<Vec<Arc<RTCRtpTransceiver>>>,
 
     pub(super) on_track_handler: Arc<ArcSwapOption<Mutex<OnTrackHdlrFn>>>,
     pub(super) on_signaling_state_change_handler:
@@ -93,6 +96,7 @@ impl PeerConnectionInternal {
             ice_connection_state: Arc::new(AtomicU8::new(RTCIceConnectionState::New as u8)),
             sctp_transport: Arc::new(Default::default()),
             rtp_transceivers: Arc::new(Default::default()),
+            remote_offer_transceivers: Mutex::new(vec![]),
             on_track_handler: Arc::new(ArcSwapOption::empty()),
             on_signaling_state_change_handler: ArcSwapOption::empty(),
             on_ice_connection_state_change_handler: Arc::new(ArcSwapOption::empty()),
@@ -122,13 +126,17 @@ impl PeerConnectionInternal {
         })?);
 
         // Create the ice transport
-        pc.ice_transport = pc.create_ice_transport(api).await;
+        pc.ice_transport = Arc::new(api.new_ice_transport(Arc::clone(&pc.ice_gatherer)));
 
         // Create the DTLS transport
         let certificates = configuration.certificates.drain(..).collect();
         pc.dtls_transport =
             Arc::new(api.new_dtls_transport(Arc::clone(&pc.ice_transport), certificates)?);
 
+        // The connection state is aggregated from the ICE and DTLS transports,
+        // so the ICE state handler can only be wired up once both exist
+        pc.register_ice_connection_state_handler();
+
         // Create the SCTP transport
         pc.sctp_transport = Arc::new(api.new_sctp_transport(Arc::clone(&pc.dtls_transport))?);
 
@@ -225,8 +233,8 @@ impl PeerConnectionInternal {
         self.start_rtp_receivers(&mut track_details, &current_transceivers)
             .await?;
         if let Some(parsed) = &remote_desc.parsed {
-            if have_application_media_section(parsed) {
-                self.start_sctp().await;
+            if let Some(remote_caps) = extract_sctp_capabilities(parsed) {
+                self.start_sctp(remote_caps).await;
             }
         }
 
@@ -389,15 +397,9 @@ impl PeerConnectionInternal {
     }
 
     /// Start SCTP subsystem
-    async fn start_sctp(&self) {
+    async fn start_sctp(&self, remote_caps: SCTPTransportCapabilities) {
         // Start sctp
-        if let Err(err) = self
-            .sctp_transport
-            .start(SCTPTransportCapabilities {
-                max_message_size: 0,
-            })
-            .await
-        {
+        if let Err(err) = self.sctp_transport.start(remote_caps).await {
             log::warn!("Failed to start SCTP: {}", err);
             if let Err(err) = self.sctp_transport.stop().await {
                 log::warn!("Failed to stop SCTPTransport: {}", err);
@@ -600,8 +602,7 @@ impl PeerConnectionInternal {
         dtls_role: DTLSRole,
         remote_ufrag: String,
         remote_pwd: String,
-        fingerprint: String,
-        fingerprint_hash: String,
+        fingerprints: Vec<RTCDtlsFingerprint>,
     ) {
         // Start the ice transport
         if let Err(err) = self
@@ -625,10 +626,7 @@ impl PeerConnectionInternal {
             .dtls_transport
             .start(DTLSParameters {
                 role: dtls_role,
-                fingerprints: vec![RTCDtlsFingerprint {
-                    algorithm: fingerprint_hash,
-                    value: fingerprint,
-                }],
+                fingerprints,
             })
             .await;
         RTCPeerConnection::update_connection_state(
@@ -700,6 +698,7 @@ impl PeerConnectionInternal {
             is_icelite: self.setting_engine.candidates.ice_lite,
             connection_role: DEFAULT_DTLS_ROLE_OFFER.to_connection_role(),
             ice_gathering_state: self.ice_gathering_state(),
+            sctp_max_message_size: self.sctp_transport.get_capabilities().max_message_size,
         };
         populate_sdp(
             d,
@@ -816,6 +815,7 @@ impl PeerConnectionInternal {
             is_icelite: self.setting_engine.candidates.ice_lite,
             connection_role,
             ice_gathering_state: self.ice_gathering_state(),
+            sctp_max_message_size: self.sctp_transport.get_capabilities().max_message_size,
         };
         populate_sdp(
             d,
@@ -989,8 +989,10 @@ impl PeerConnectionInternal {
         for _ in 0..=SIMULCAST_PROBE_COUNT {
             if mid.is_empty() || (rid.is_empty() && rsid.is_empty()) {
                 let (pkt, _) = rtp_interceptor.read(&mut buf, &a).await?;
-                let (m, r, rs, _) = handle_unknown_rtp_packet(
-                    &buf[..n],
+                // The probed packet may have a different length than the first one, so
+                // use the packet that was read instead of re-parsing the buffer.
+                let (m, r, rs, _) = handle_unknown_rtp_header(
+                    &pkt.header,
                     mid_extension_id as u8,
                     sid_extension_id as u8,
                     rsid_extension_id as u8,
@@ -1104,9 +1106,7 @@ impl PeerConnectionInternal {
         }
     }
 
-    pub(super) async fn create_ice_transport(&self, api: &API) -> Arc<RTCIceTransport> {
-        let ice_transport = Arc::new(api.new_ice_transport(Arc::clone(&self.ice_gatherer)));
-
+    fn register_ice_connection_state_handler(&self) {
         let ice_connection_state = Arc::clone(&self.ice_connection_state);
         let peer_connection_state = Arc::clone(&self.peer_connection_state);
         let is_closed = Arc::clone(&self.is_closed);
@@ -1116,49 +1116,49 @@ impl PeerConnectionInternal {
         let on_peer_connection_state_change_handler =
             Arc::clone(&self.on_peer_connection_state_change_handler);
 
-        ice_transport.on_connection_state_change(Box::new(move |state: RTCIceTransportState| {
-            let cs = match state {
-                RTCIceTransportState::New => RTCIceConnectionState::New,
-                RTCIceTransportState::Checking => RTCIceConnectionState::Checking,
-                RTCIceTransportState::Connected => RTCIceConnectionState::Connected,
-                RTCIceTransportState::Completed => RTCIceConnectionState::Completed,
-                RTCIceTransportState::Failed => RTCIceConnectionState::Failed,
-                RTCIceTransportState::Disconnected => RTCIceConnectionState::Disconnected,
-                RTCIceTransportState::Closed => RTCIceConnectionState::Closed,
-                _ => {
-                    log::warn!("on_connection_state_change: unhandled ICE state: {}", state);
-                    return Box::pin(async {});
-                }
-            };
-
-            let ice_connection_state2 = Arc::clone(&ice_connection_state);
-            let on_ice_connection_state_change_handler2 =
-                Arc::clone(&on_ice_connection_state_change_handler);
-            let on_peer_connection_state_change_handler2 =
-                Arc::clone(&on_peer_connection_state_change_handler);
-            let is_closed2 = Arc::clone(&is_closed);
-            let dtls_transport_state = dtls_transport.state();
-            let peer_connection_state2 = Arc::clone(&peer_connection_state);
-            Box::pin(async move {
-                RTCPeerConnection::do_ice_connection_state_change(
-                    &on_ice_connection_state_change_handler2,
-                    &ice_connection_state2,
-                    cs,
-                )
-                .await;
-
-                RTCPeerConnection::update_connection_state(
-                    &on_peer_connection_state_change_handler2,
-                    &is_closed2,
-                    &peer_connection_state2,
-                    cs,
-                    dtls_transport_state,
-                )
-                .await;
-            })
-        }));
+        self.ice_transport.on_connection_state_change(Box::new(
+            move |state: RTCIceTransportState| {
+                let cs = match state {
+                    RTCIceTransportState::New => RTCIceConnectionState::New,
+                    RTCIceTransportState::Checking => RTCIceConnectionState::Checking,
+                    RTCIceTransportState::Connected => RTCIceConnectionState::Connected,
+                    RTCIceTransportState::Completed => RTCIceConnectionState::Completed,
+                    RTCIceTransportState::Failed => RTCIceConnectionState::Failed,
+                    RTCIceTransportState::Disconnected => RTCIceConnectionState::Disconnected,
+                    RTCIceTransportState::Closed => RTCIceConnectionState::Closed,
+                    _ => {
+                        log::warn!("on_connection_state_change: unhandled ICE state: {}", state);
+                        return Box::pin(async {});
+                    }
+                };
 
-        ice_transport
+                let ice_connection_state2 = Arc::clone(&ice_connection_state);
+                let on_ice_connection_state_change_handler2 =
+                    Arc::clone(&on_ice_connection_state_change_handler);
+                let on_peer_connection_state_change_handler2 =
+                    Arc::clone(&on_peer_connection_state_change_handler);
+                let is_closed2 = Arc::clone(&is_closed);
+                let dtls_transport_state = dtls_transport.state();
+                let peer_connection_state2 = Arc::clone(&peer_connection_state);
+                Box::pin(async move {
+                    RTCPeerConnection::do_ice_connection_state_change(
+                        &on_ice_connection_state_change_handler2,
+                        &ice_connection_state2,
+                        cs,
+                    )
+                    .await;
+
+                    RTCPeerConnection::update_connection_state(
+                        &on_peer_connection_state_change_handler2,
+                        &is_closed2,
+                        &peer_connection_state2,
+                        cs,
+                        dtls_transport_state,
+                    )
+                    .await;
+                })
+            },
+        ));
     }
 
     /// has_local_description_changed returns whether local media (rtp_transceivers) has changed
@@ -1166,6 +1166,11 @@ impl PeerConnectionInternal {
     pub(super) async fn has_local_description_changed(&self, desc: &RTCSessionDescription) -> bool {
         let rtp_transceivers = self.rtp_transceivers.lock().await;
         for t in &*rtp_transceivers {
+            // Stopped transceivers aren't part of a generated offer
+            if t.stopped.load(Ordering::SeqCst) {
+                continue;
+            }
+
             let m = match t.mid().and_then(|mid| get_by_mid(mid.as_str(), desc)) {
                 Some(m) => m,
                 None => return true,
@@ -1246,6 +1251,8 @@ impl PeerConnectionInternal {
             let id = format!("RTCInboundRTP{}Stream_{}", capitalize(kind), ssrc);
             let (
                 packets_received,
+                packets_lost,
+                jitter,
                 header_bytes_received,
                 bytes_received,
                 last_packet_received_timestamp,
@@ -1258,6 +1265,8 @@ impl PeerConnectionInternal {
                 remote_round_trip_time_measurements,
             ) = (
                 stats.packets_received(),
+                stats.packets_lost(),
+                stats.jitter(),
                 stats.header_bytes_received(),
                 stats.payload_bytes_received(),
                 stats.last_packet_received_timestamp(),
@@ -1279,6 +1288,8 @@ impl PeerConnectionInternal {
                     ssrc,
                     kind: kind.to_owned(),
                     packets_received,
+                    packets_lost,
+                    jitter,
                     track_identifier: info.track_id,
                     mid: info.mid,
                     last_packet_received_timestamp,
@@ -1331,6 +1342,7 @@ impl PeerConnectionInternal {
             mid: SmolStr,
             rid: Option<SmolStr>,
             kind: &'static str,
+            clock_rate: u32,
         }
         let mut track_infos = vec![];
         for transceiver in transceivers {
@@ -1353,12 +1365,21 @@ impl PeerConnectionInternal {
                 RTPCodecType::Video => "video",
             };
 
+            let clock_rate = {
+                let track_encodings = sender.track_encodings.lock().await;
+                track_encodings
+                    .first()
+                    .map(|e| e.stream_info.clock_rate)
+                    .unwrap_or_default()
+            };
+
             track_infos.push(TrackInfo {
                 track_id,
                 ssrc: sender.ssrc,
                 mid,
                 rid: None,
                 kind,
+                clock_rate,
             });
         }
 
@@ -1385,6 +1406,7 @@ impl PeerConnectionInternal {
                 nack_count,
                 remote_inbound_packets_received,
                 remote_inbound_packets_lost,
+                remote_jitter,
                 remote_rtt_ms,
                 remote_total_rtt_ms,
                 remote_rtt_measurements,
@@ -1396,6 +1418,7 @@ impl PeerConnectionInternal {
                 stats.nacks_received(),
                 stats.remote_packets_received(),
                 stats.remote_total_lost(),
+                stats.remote_jitter(),
                 stats.remote_round_trip_time(),
                 stats.remote_total_round_trip_time(),
                 stats.remote_round_trip_time_measurements(),
@@ -1408,6 +1431,7 @@ impl PeerConnectionInternal {
                 rid,
                 kind,
                 track_id: track_identifier,
+                clock_rate,
             } = info;
 
             collector.insert(
@@ -1449,6 +1473,12 @@ impl PeerConnectionInternal {
 
                     packets_received: remote_inbound_packets_received,
                     packets_lost: remote_inbound_packets_lost as i64,
+                    // The remote reports the jitter in RTP timestamp units
+                    jitter: if clock_rate != 0 {
+                        remote_jitter as f64 / clock_rate as f64
+                    } else {
+                        0.0
+                    },
 
                     local_id,
 
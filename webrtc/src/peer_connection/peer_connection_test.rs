@@ -16,7 +16,8 @@ use crate::api::APIBuilder;
 use crate::ice_transport::ice_candidate_pair::RTCIceCandidatePair;
 use crate::ice_transport::ice_server::RTCIceServer;
 use crate::peer_connection::configuration::RTCConfiguration;
-use crate::rtp_transceiver::rtp_codec::RTCRtpCodecCapability;
+use crate::rtp_transceiver::rtp_codec::{RTCRtpCodecCapability, RTCRtpCodecParameters};
+use crate::rtp_transceiver::RTCPFeedback;
 use crate::stats::StatsReportType;
 use crate::track::track_local::track_local_static_sample::TrackLocalStaticSample;
 use crate::Error;
@@ -305,20 +306,25 @@ async fn test_get_stats() -> Result<()> {
         "video".to_owned(),
         "webrtc-rs".to_owned(),
     ));
-    pc_offer
+    let sender = pc_offer
         .add_track(track.clone())
         .await
         .expect("Failed to add track");
+    // The incoming RTCP is only processed by the interceptors while it's being read
+    tokio::spawn(async move { while sender.read_rtcp().await.is_ok() {} });
     let (packet_tx, packet_rx) = mpsc::channel(1);
+    let (seq_tx, mut seq_rx) = mpsc::channel::<u16>(1);
 
     pc_answer.on_track(Box::new(move |track, _, _| {
         let packet_tx = packet_tx.clone();
+        let seq_tx = seq_tx.clone();
         tokio::spawn(async move {
             while let Ok((pkt, _)) = track.read_rtp().await {
                 dbg!(&pkt);
                 let last = pkt.payload[pkt.payload.len() - 1];
 
                 if last == 0xAA {
+                    let _ = seq_tx.send(pkt.header.sequence_number).await;
                     let _ = packet_tx.send(()).await;
                     break;
                 }
@@ -377,11 +383,368 @@ async fn test_get_stats() -> Result<()> {
     assert_eq!(inbound_stats.bytes_received, 8);
     assert_eq!(inbound_stats.header_bytes_received, 12);
 
+    // The remote inbound stats are filled from the receiver reports of the answerer. Report two
+    // lost packets after the one that was received.
+    let seq = seq_rx.recv().await.unwrap();
+    pc_answer
+        .write_rtcp(&[Box::new(rtcp::receiver_report::ReceiverReport {
+            reports: vec![rtcp::reception_report::ReceptionReport {
+                ssrc: outbound_stats.ssrc,
+                fraction_lost: 170,
+                total_lost: 2,
+                last_sequence_number: seq as u32 + 2,
+                jitter: 4500,
+                ..Default::default()
+            }],
+            ..Default::default()
+        })])
+        .await?;
+    let remote_inbound_stats = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let offer_stats = pc_offer.get_stats().await;
+            let remote_inbound_stats = offer_stats.reports.into_values().find_map(|v| match v {
+                StatsReportType::RemoteInboundRTP(d) if d.packets_received > 0 => Some(d),
+                _ => None,
+            });
+            if let Some(remote_inbound_stats) = remote_inbound_stats {
+                return remote_inbound_stats;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("Should have produced an RTP remote inbound stat");
+    assert_eq!(remote_inbound_stats.ssrc, outbound_stats.ssrc);
+    assert_eq!(remote_inbound_stats.kind, "video");
+    assert_eq!(remote_inbound_stats.packets_received, 1);
+    assert_eq!(remote_inbound_stats.packets_lost, 2);
+    assert_eq!(remote_inbound_stats.fraction_lost, 170.0 / 255.0);
+    // 4500 timestamp units of the 90kHz VP8 clock
+    assert_eq!(remote_inbound_stats.jitter, 0.05);
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_ice_candidate_after_close() -> Result<()> {
+    let api = APIBuilder::new().build();
+    let pc = api.new_peer_connection(RTCConfiguration::default()).await?;
+
+    pc.close().await?;
+
+    let result = pc
+        .add_ice_candidate(RTCIceCandidateInit {
+            candidate: "candidate:1 1 udp 2130706431 1.2.3.4 5000 typ host".to_owned(),
+            ..Default::default()
+        })
+        .await;
+    assert!(
+        matches!(result, Err(Error::ErrConnectionClosed)),
+        "adding a candidate to a closed PeerConnection should fail"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rollback() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let (mut pc_offer, mut pc_answer) = new_pair(&api).await?;
+
+    let rollback = RTCSessionDescription {
+        sdp_type: RTCSdpType::Rollback,
+        ..Default::default()
+    };
+
+    // Nothing to roll back while stable
+    assert_eq!(
+        pc_offer.set_local_description(rollback.clone()).await,
+        Err(Error::ErrSignalingStateCannotRollback)
+    );
+
+    pc_offer
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+    let offer = pc_offer.create_offer(None).await?;
+    pc_offer.set_local_description(offer.clone()).await?;
+
+    // The remote offer created a transceiver, rolling it back removes it again
+    pc_answer.set_remote_description(offer.clone()).await?;
+    assert_eq!(pc_answer.get_transceivers().await.len(), 1);
+    pc_answer.set_remote_description(rollback.clone()).await?;
+    assert_eq!(pc_answer.signaling_state(), RTCSignalingState::Stable);
+    assert!(pc_answer.remote_description().await.is_none());
+    assert!(pc_answer.get_transceivers().await.is_empty());
+
+    // The local transceiver stays but isn't associated with a media section anymore
+    pc_offer.set_local_description(rollback).await?;
+    assert_eq!(pc_offer.signaling_state(), RTCSignalingState::Stable);
+    assert!(pc_offer.local_description().await.is_none());
+    let transceivers = pc_offer.get_transceivers().await;
+    assert_eq!(transceivers.len(), 1);
+    assert_eq!(transceivers[0].mid(), None);
+
+    // Negotiating afterwards works as usual
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+    assert!(transceivers[0].mid().is_some());
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_remote_description_invalid_bundle() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let (pc_offer, pc_answer) = new_pair(&api).await?;
+
+    pc_offer
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+    pc_offer
+        .add_transceiver_from_kind(RTPCodecType::Audio, None)
+        .await?;
+    let offer = pc_offer.create_offer(None).await?;
+    pc_offer.set_local_description(offer.clone()).await?;
+
+    pc_answer.set_remote_description(offer).await?;
+    let answer = pc_answer.create_answer(None).await?;
+
+    // The answer bundles a media section it doesn't contain
+    let sdp = answer
+        .sdp
+        .lines()
+        .map(|line| {
+            if line.starts_with("a=group:BUNDLE") {
+                format!("{line} 9")
+            } else {
+                line.to_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    assert_ne!(sdp, answer.sdp.trim_end());
+
+    let result = pc_offer
+        .set_remote_description(RTCSessionDescription::answer(sdp)?)
+        .await;
+    assert!(
+        matches!(result, Err(Error::Sdp(::sdp::Error::SdpInvalidValue(_)))),
+        "invalid BUNDLE group should be rejected, got {result:?}"
+    );
+    assert_eq!(
+        pc_offer.signaling_state(),
+        RTCSignalingState::HaveLocalOffer
+    );
+    assert!(pc_offer.remote_description().await.is_none());
+
+    close_pair_now(&pc_offer, &pc_answer).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ice_restart() -> Result<()> {
+    // Static credentials can't be reused by the restart
+    let mut s = SettingEngine::default();
+    s.set_ice_credentials(
+        "staticufrag".to_owned(),
+        "staticpasswordstaticpassword".to_owned(),
+    );
+    let api = APIBuilder::new().with_setting_engine(s).build();
+    let mut pc_offer = api.new_peer_connection(RTCConfiguration::default()).await?;
+    let mut pc_answer = APIBuilder::new()
+        .build()
+        .new_peer_connection(RTCConfiguration::default())
+        .await?;
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_offer, &wg, RTCPeerConnectionState::Connected).await;
+    signal_pair(&mut pc_offer, &mut pc_answer).await?;
+    wg.wait().await;
+
+    let ufrag = |desc: RTCSessionDescription| async move {
+        let parsed = desc.parsed.unwrap();
+        extract_ice_details(&parsed)
+            .await
+            .map(|(ufrag, _, _)| ufrag)
+    };
+    let offer = pc_offer.local_description().await.unwrap();
+    assert_eq!(ufrag(offer).await?, "staticufrag");
+
+    let wg = WaitGroup::new();
+    until_connection_state(&mut pc_offer, &wg, RTCPeerConnectionState::Connected).await;
+
+    let offer = pc_offer
+        .create_offer(Some(RTCOfferOptions {
+            ice_restart: true,
+            ..Default::default()
+        }))
+        .await?;
+    let mut offer_gathering_complete = pc_offer.gathering_complete_promise().await;
+    pc_offer.set_local_description(offer).await?;
+    let _ = offer_gathering_complete.recv().await;
+
+    let offer = pc_offer.local_description().await.unwrap();
+    assert_ne!(ufrag(offer.clone()).await?, "staticufrag");
+
+    pc_answer.set_remote_description(offer).await?;
+    let answer = pc_answer.create_answer(None).await?;
+    let mut answer_gathering_complete = pc_answer.gathering_complete_promise().await;
+    pc_answer.set_local_description(answer).await?;
+    let _ = answer_gathering_complete.recv().await;
+    pc_offer
+        .set_remote_description(pc_answer.local_description().await.unwrap())
+        .await?;
+
+    // The connection is established again with the new credentials
+    wg.wait().await;
+
     close_pair_now(&pc_offer, &pc_answer).await;
 
     Ok(())
 }
 
+#[tokio::test]
+async fn test_request_key_frame() -> Result<()> {
+    // The remote is asked with a PLI, unless the codec only negotiated FIR
+    for (feedback, want_fir) in [
+        (
+            RTCPFeedback {
+                typ: "nack".to_owned(),
+                parameter: "pli".to_owned(),
+            },
+            false,
+        ),
+        (
+            RTCPFeedback {
+                typ: "ccm".to_owned(),
+                parameter: "fir".to_owned(),
+            },
+            true,
+        ),
+    ] {
+        let vp8 = RTCRtpCodecParameters {
+            capability: RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_VP8.to_owned(),
+                clock_rate: 90000,
+                rtcp_feedback: vec![feedback],
+                ..Default::default()
+            },
+            payload_type: 96,
+            ..Default::default()
+        };
+        let mut m = MediaEngine::default();
+        m.register_codec(vp8, RTPCodecType::Video)?;
+        let api = APIBuilder::new().with_media_engine(m).build();
+        let (mut pc_offer, mut pc_answer) = new_pair(&api).await?;
+
+        let track = Arc::new(TrackLocalStaticSample::new(
+            RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_VP8.to_owned(),
+                ..Default::default()
+            },
+            "video".to_owned(),
+            "webrtc-rs".to_owned(),
+        ));
+        let sender = pc_offer
+            .add_track(Arc::clone(&track) as Arc<dyn TrackLocal + Send + Sync>)
+            .await?;
+
+        let (ssrc_tx, mut ssrc_rx) = mpsc::channel::<SSRC>(1);
+        pc_answer.on_track(Box::new(move |track, _, _| {
+            let ssrc_tx = ssrc_tx.clone();
+            Box::pin(async move {
+                let _ = ssrc_tx.send(track.ssrc()).await;
+            })
+        }));
+
+        signal_pair(&mut pc_offer, &mut pc_answer).await?;
+
+        let (done_tx, done_rx) = mpsc::channel::<()>(1);
+        tokio::spawn(async move {
+            send_video_until_done(done_rx, vec![track], Bytes::from_static(&[0xAA]), None).await;
+        });
+        let ssrc = ssrc_rx.recv().await.unwrap();
+        drop(done_tx);
+
+        pc_answer.request_key_frame(ssrc).await?;
+
+        let mut got_key_frame_request = false;
+        while !got_key_frame_request {
+            let (pkts, _) = tokio::time::timeout(Duration::from_secs(5), sender.read_rtcp())
+                .await
+                .expect("no keyframe request received")?;
+            for pkt in pkts {
+                if let Some(fir) = pkt.as_any().downcast_ref::<FullIntraRequest>() {
+                    assert!(want_fir);
+                    assert_eq!(fir.fir[0].ssrc, ssrc);
+                    got_key_frame_request = true;
+                } else if let Some(pli) = pkt.as_any().downcast_ref::<PictureLossIndication>() {
+                    assert!(!want_fir);
+                    assert_eq!(pli.media_ssrc, ssrc);
+                    got_key_frame_request = true;
+                }
+            }
+        }
+
+        close_pair_now(&pc_offer, &pc_answer).await;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fir_sequence_number_per_ssrc() -> Result<()> {
+    let api = APIBuilder::new().build();
+    let pc = api.new_peer_connection(RTCConfiguration::default()).await?;
+
+    assert_eq!(pc.next_fir_sequence_number(1).await, 0);
+    assert_eq!(pc.next_fir_sequence_number(1).await, 1);
+    assert_eq!(pc.next_fir_sequence_number(2).await, 0);
+    assert_eq!(pc.next_fir_sequence_number(1).await, 2);
+
+    pc.close().await?;
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_connection_state() {
+    use RTCDtlsTransportState as Dtls;
+    use RTCIceConnectionState as Ice;
+    use RTCPeerConnectionState as Pc;
+
+    let tests = vec![
+        (false, Ice::New, Dtls::New, Pc::New),
+        (false, Ice::Checking, Dtls::New, Pc::Connecting),
+        (false, Ice::Connected, Dtls::New, Pc::Connecting),
+        (false, Ice::Connected, Dtls::Connecting, Pc::Connecting),
+        (false, Ice::Connected, Dtls::Connected, Pc::Connected),
+        (false, Ice::Completed, Dtls::Connected, Pc::Connected),
+        (false, Ice::Connected, Dtls::Closed, Pc::Connected),
+        (false, Ice::Disconnected, Dtls::Connected, Pc::Disconnected),
+        (false, Ice::Disconnected, Dtls::Connecting, Pc::Connecting),
+        (false, Ice::Failed, Dtls::Connected, Pc::Failed),
+        (false, Ice::Connected, Dtls::Failed, Pc::Failed),
+        (false, Ice::Closed, Dtls::Closed, Pc::New),
+        (true, Ice::Connected, Dtls::Connected, Pc::Closed),
+    ];
+
+    for (is_closed, ice, dtls, expected) in tests {
+        assert_eq!(
+            RTCPeerConnection::aggregate_connection_state(is_closed, ice, dtls),
+            expected,
+            "closed: {is_closed}, ice: {ice}, dtls: {dtls}"
+        );
+    }
+}
+
 #[tokio::test]
 async fn test_peer_connection_close_is_send() -> Result<()> {
     let handle = tokio::spawn(async move { peer().await });
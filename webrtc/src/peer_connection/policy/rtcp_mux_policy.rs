@@ -1,5 +1,6 @@
 use std::fmt;
 
+use sdp::description::media::RtcpMux;
 use serde::{Deserialize, Serialize};
 
 /// RTCPMuxPolicy affects what ICE candidates are gathered to support
@@ -47,6 +48,14 @@ impl fmt::Display for RTCRtcpMuxPolicy {
     }
 }
 
+impl RTCRtcpMuxPolicy {
+    /// accepts returns whether a remote media description negotiating RTCP
+    /// multiplexing as described by rtcp_mux is acceptable under this policy.
+    pub(crate) fn accepts(self, rtcp_mux: RtcpMux) -> bool {
+        self != RTCRtcpMuxPolicy::Require || rtcp_mux != RtcpMux::None
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -76,4 +85,20 @@ mod test {
             assert_eq!(policy.to_string(), expected_string);
         }
     }
+
+    #[test]
+    fn test_rtcp_mux_policy_accepts() {
+        let tests = vec![
+            (RTCRtcpMuxPolicy::Unspecified, RtcpMux::None, true),
+            (RTCRtcpMuxPolicy::Negotiate, RtcpMux::None, true),
+            (RTCRtcpMuxPolicy::Negotiate, RtcpMux::Mux, true),
+            (RTCRtcpMuxPolicy::Require, RtcpMux::None, false),
+            (RTCRtcpMuxPolicy::Require, RtcpMux::Mux, true),
+            (RTCRtcpMuxPolicy::Require, RtcpMux::MuxOnly, true),
+        ];
+
+        for (policy, rtcp_mux, expected) in tests {
+            assert_eq!(policy.accepts(rtcp_mux), expected, "{policy} {rtcp_mux:?}");
+        }
+    }
 }
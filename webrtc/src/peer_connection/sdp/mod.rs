@@ -13,25 +13,27 @@ use crate::rtp_transceiver::rtp_codec::{
 };
 use crate::rtp_transceiver::rtp_transceiver_direction::RTCRtpTransceiverDirection;
 use crate::rtp_transceiver::{PayloadType, RTCPFeedback, RTCRtpTransceiver, SSRC};
+use crate::sctp_transport::sctp_transport_capabilities::SCTPTransportCapabilities;
+use crate::sctp_transport::SCTP_MAX_MESSAGE_SIZE_UNSET;
 
 pub mod sdp_type;
 pub mod session_description;
 
 use std::collections::HashMap;
 use std::convert::From;
-use std::io::BufReader;
 use std::sync::Arc;
 
 use ice::candidate::candidate_base::unmarshal_candidate;
 use ice::candidate::Candidate;
-use sdp::description::common::{Address, ConnectionInformation};
-use sdp::description::media::{MediaDescription, MediaName, RangedPort};
+use sdp::description::common::{Address, Attribute, ConnectionInformation};
+use sdp::description::media::{MediaDescription, MediaName, RangedPort, RtcpMux};
 use sdp::description::session::*;
-use sdp::extmap::ExtMap;
 use sdp::util::ConnectionRole;
 use smol_str::SmolStr;
 use url::Url;
 
+use crate::peer_connection::policy::rtcp_mux_policy::RTCRtcpMuxPolicy;
+use crate::peer_connection::sdp::sdp_type::RTCSdpType;
 use crate::peer_connection::MEDIA_SECTION_APPLICATION;
 use crate::{SDP_ATTRIBUTE_RID, SDP_ATTRIBUTE_SIMULCAST};
 
@@ -325,6 +327,7 @@ pub(crate) struct AddDataMediaSectionParams {
     ice_params: RTCIceParameters,
     dtls_role: ConnectionRole,
     ice_gathering_state: RTCIceGatheringState,
+    max_message_size: u32,
 }
 
 pub(crate) async fn add_data_media_section(
@@ -356,6 +359,7 @@ pub(crate) async fn add_data_media_section(
         bandwidth: vec![],
         encryption_key: None,
         attributes: vec![],
+        other: vec![],
     }
     .with_value_attribute(
         ATTR_KEY_CONNECTION_SETUP.to_owned(),
@@ -363,7 +367,8 @@ pub(crate) async fn add_data_media_section(
     )
     .with_value_attribute(ATTR_KEY_MID.to_owned(), params.mid_value)
     .with_property_attribute(RTCRtpTransceiverDirection::Sendrecv.to_string())
-    .with_property_attribute("sctp-port:5000".to_owned())
+    .with_sctp_port(5000)
+    .with_max_message_size(params.max_message_size)
     .with_ice_credentials(
         params.ice_params.username_fragment,
         params.ice_params.password,
@@ -527,6 +532,7 @@ pub(crate) async fn add_transceiver_sdp(
             bandwidth: vec![],
             encryption_key: None,
             attributes: vec![],
+            other: vec![],
         });
         return Ok((d, false));
     }
@@ -546,7 +552,7 @@ pub(crate) async fn add_transceiver_sdp(
         let mut send_sc_list: Vec<String> = vec![];
 
         for rid in &media_section.rid_map {
-            let rid_syntax = match rid.direction {
+            let mut rid_syntax = match rid.direction {
                 SimulcastDirection::Send => {
                     // If Send rid, then reply with a recv rid
                     if rid.paused {
@@ -566,37 +572,69 @@ pub(crate) async fn add_transceiver_sdp(
                     format!("{} send", rid.id)
                 }
             };
+
+            // Accept the offered restrictions. Payload types are left out as they refer
+            // to the offer's formats, RFC 8851 Section 6.2
+            let restrictions: Vec<String> = rid
+                .restrictions()
+                .into_iter()
+                .filter(|(key, _)| *key != "pt")
+                .map(|(key, value)| match value {
+                    Some(value) => format!("{key}={value}"),
+                    None => key.to_owned(),
+                })
+                .collect();
+            if !restrictions.is_empty() {
+                rid_syntax = format!("{rid_syntax} {}", restrictions.join(";"));
+            }
+
             media = media.with_value_attribute(SDP_ATTRIBUTE_RID.to_owned(), rid_syntax);
         }
 
         // Simulcast
-        let mut sc_attr = String::new();
-        if !recv_sc_list.is_empty() {
-            sc_attr.push_str(&format!("recv {}", recv_sc_list.join(";")));
-        }
-        if !send_sc_list.is_empty() {
-            sc_attr.push_str(&format!("send {}", send_sc_list.join(";")));
-        }
-        media = media.with_value_attribute(SDP_ATTRIBUTE_SIMULCAST.to_owned(), sc_attr);
+        media = media.with_value_attribute(
+            SDP_ATTRIBUTE_SIMULCAST.to_owned(),
+            build_simulcast_attribute(&recv_sc_list, &send_sc_list),
+        );
     }
 
     for mt in transceivers {
         let sender = mt.sender().await;
         if let Some(track) = sender.track().await {
-            media = media.with_media_source(
-                sender.ssrc,
-                track.stream_id().to_owned(), /* cname */
-                track.stream_id().to_owned(), /* streamLabel */
-                track.id().to_owned(),
-            );
+            let encodings = sender.get_parameters().await.encodings;
+            for encoding in &encodings {
+                media = media.with_media_source(
+                    encoding.ssrc,
+                    track.stream_id().to_owned(), /* cname */
+                    track.stream_id().to_owned(), /* streamLabel */
+                    track.id().to_owned(),
+                );
+            }
+
+            // Offer the encodings of a simulcast sender, when answering the rids were
+            // already added from the offered ones above.
+            if encodings.len() > 1 && media_section.rid_map.is_empty() {
+                let mut send_sc_list = Vec::with_capacity(encodings.len());
+                for encoding in &encodings {
+                    media = media.with_value_attribute(
+                        SDP_ATTRIBUTE_RID.to_owned(),
+                        format!("{} send", encoding.rid),
+                    );
+                    send_sc_list.push(encoding.rid.to_string());
+                }
+
+                media = media.with_value_attribute(
+                    SDP_ATTRIBUTE_SIMULCAST.to_owned(),
+                    build_simulcast_attribute(&[], &send_sc_list),
+                );
+            }
 
             // Send msid based on the configured track if we haven't already
             // sent on this sender. If we have sent we must keep the msid line consistent, this
             // is handled below.
             if sender.initial_track_id().is_none() {
                 for stream_id in sender.associated_media_stream_ids() {
-                    media =
-                        media.with_property_attribute(format!("msid:{} {}", stream_id, track.id()));
+                    media = media.with_msid(stream_id.to_owned(), track.id().to_owned());
                 }
 
                 sender.set_initial_track_id(track.id().to_string())?;
@@ -617,7 +655,7 @@ pub(crate) async fn add_transceiver_sdp(
             // description, "a=msid" line(s) MUST be generated according to the
             // same rules as for an initial offer.
             for stream_id in sender.associated_media_stream_ids() {
-                media = media.with_property_attribute(format!("msid:{stream_id} {track_id}"));
+                media = media.with_msid(stream_id.to_owned(), track_id.to_owned());
             }
 
             break;
@@ -738,6 +776,36 @@ impl TryFrom<&String> for SimulcastRid {
     }
 }
 
+impl SimulcastRid {
+    /// Returns the rid-param-list restrictions, e.g. `max-width=1280`, in the order given.
+    pub(crate) fn restrictions(&self) -> Vec<(&str, Option<&str>)> {
+        self.params
+            .split(';')
+            .filter(|param| !param.is_empty())
+            .map(|param| match param.split_once('=') {
+                Some((key, value)) => (key, Some(value)),
+                None => (param, None),
+            })
+            .collect()
+    }
+}
+
+/// Builds the `a=simulcast` value from the recv and send alternative lists,
+/// e.g. `recv h;~m send l`.
+pub(crate) fn build_simulcast_attribute(
+    recv_sc_list: &[String],
+    send_sc_list: &[String],
+) -> String {
+    let mut sc_attr = vec![];
+    if !recv_sc_list.is_empty() {
+        sc_attr.push(format!("recv {}", recv_sc_list.join(";")));
+    }
+    if !send_sc_list.is_empty() {
+        sc_attr.push(format!("send {}", send_sc_list.join(";")));
+    }
+    sc_attr.join(" ")
+}
+
 #[derive(Default)]
 pub(crate) struct MediaSection {
     pub(crate) id: String,
@@ -752,6 +820,7 @@ pub(crate) struct PopulateSdpParams {
     pub(crate) is_icelite: bool,
     pub(crate) connection_role: ConnectionRole,
     pub(crate) ice_gathering_state: RTCIceGatheringState,
+    pub(crate) sctp_max_message_size: u32,
 }
 
 /// populate_sdp serializes a PeerConnections state into an SDP
@@ -770,12 +839,7 @@ pub(crate) async fn populate_sdp(
         vec![]
     };
 
-    let mut bundle_value = "BUNDLE".to_owned();
-    let mut bundle_count = 0;
-    let append_bundle = |mid_value: &str, value: &mut String, count: &mut i32| {
-        *value = value.clone() + " " + mid_value;
-        *count += 1;
-    };
+    let mut bundle_mids = vec![];
 
     for (i, m) in media_sections.iter().enumerate() {
         if m.data && !m.transceivers.is_empty() {
@@ -793,6 +857,7 @@ pub(crate) async fn populate_sdp(
                 ice_params: ice_params.clone(),
                 dtls_role: params.connection_role,
                 ice_gathering_state: params.ice_gathering_state,
+                max_message_size: params.sctp_max_message_size,
             };
             d = add_data_media_section(d, &media_dtls_fingerprints, candidates, params).await?;
             true
@@ -819,7 +884,7 @@ pub(crate) async fn populate_sdp(
         };
 
         if should_add_id {
-            append_bundle(&m.id, &mut bundle_value, &mut bundle_count);
+            bundle_mids.push(m.id.as_str());
         }
     }
 
@@ -837,7 +902,53 @@ pub(crate) async fn populate_sdp(
         d = d.with_value_attribute(ATTR_KEY_ICELITE.to_owned(), ATTR_KEY_ICELITE.to_owned());
     }
 
-    Ok(d.with_value_attribute(ATTR_KEY_GROUP.to_owned(), bundle_value))
+    Ok(d.with_bundle_group(&bundle_mids))
+}
+
+/// check_rtcp_mux verifies that the media sections of a remote description negotiate
+/// RTCP multiplexing as demanded by the rtcp mux policy. RTCP is always expected on the
+/// RTP port, since separate RTCP candidates are never gathered.
+pub(crate) fn check_rtcp_mux(desc: &SessionDescription, policy: RTCRtcpMuxPolicy) -> Result<()> {
+    for media in &desc.media_descriptions {
+        if media.media_name.media == MEDIA_SECTION_APPLICATION || media.media_name.port.value == 0 {
+            continue;
+        }
+
+        let rtcp_mux = media.rtcp_mux();
+        if !policy.accepts(rtcp_mux) {
+            return Err(Error::ErrSDPRtcpMuxRequired);
+        }
+        if rtcp_mux == RtcpMux::None {
+            log::warn!(
+                "media section {:?} doesn't multiplex RTCP, expecting it on the RTP port anyway",
+                get_mid_value(media)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// check_setup_role verifies the a=setup attributes of a remote description, RFC 5763
+/// Section 5: an answer must take either the active or the passive role, only an offer
+/// may leave the choice to the other side with actpass.
+pub(crate) fn check_setup_role(desc: &SessionDescription, sdp_type: RTCSdpType) -> Result<()> {
+    for media in &desc.media_descriptions {
+        let Some(Some(value)) = media.attribute(ATTR_KEY_CONNECTION_SETUP) else {
+            continue;
+        };
+
+        let valid = match ConnectionRole::from(value) {
+            ConnectionRole::Active | ConnectionRole::Passive => true,
+            ConnectionRole::Actpass => sdp_type == RTCSdpType::Offer,
+            _ => false,
+        };
+        if !valid {
+            return Err(Error::ErrSessionDescriptionInvalidSetupRole);
+        }
+    }
+
+    Ok(())
 }
 
 pub(crate) fn get_mid_value(media: &MediaDescription) -> Option<&String> {
@@ -859,16 +970,30 @@ pub(crate) fn get_peer_direction(media: &MediaDescription) -> RTCRtpTransceiverD
     RTCRtpTransceiverDirection::Unspecified
 }
 
-pub(crate) fn extract_fingerprint(desc: &SessionDescription) -> Result<(String, String)> {
+/// Returns the remote certificate fingerprints. A certificate may be advertised with several
+/// hash functions (RFC 8122 Section 5), but every section carrying fingerprints must agree.
+pub(crate) fn extract_fingerprints(desc: &SessionDescription) -> Result<Vec<RTCDtlsFingerprint>> {
+    let fingerprint_values = |attributes: &[Attribute]| -> Vec<String> {
+        let mut values: Vec<String> = attributes
+            .iter()
+            .filter(|a| a.key == "fingerprint")
+            .filter_map(|a| a.value.clone())
+            .collect();
+        values.sort_unstable();
+        values
+    };
+
     let mut fingerprints = vec![];
 
-    if let Some(fingerprint) = desc.attribute("fingerprint") {
-        fingerprints.push(fingerprint.clone());
+    let session_fingerprints = fingerprint_values(&desc.attributes);
+    if !session_fingerprints.is_empty() {
+        fingerprints.push(session_fingerprints);
     }
 
     for m in &desc.media_descriptions {
-        if let Some(fingerprint) = m.attribute("fingerprint").and_then(|o| o) {
-            fingerprints.push(fingerprint.to_owned());
+        let media_fingerprints = fingerprint_values(&m.attributes);
+        if !media_fingerprints.is_empty() {
+            fingerprints.push(media_fingerprints);
         }
     }
 
@@ -882,12 +1007,20 @@ pub(crate) fn extract_fingerprint(desc: &SessionDescription) -> Result<(String,
         }
     }
 
-    let parts: Vec<&str> = fingerprints[0].split(' ').collect();
-    if parts.len() != 2 {
-        return Err(Error::ErrSessionDescriptionInvalidFingerprint);
-    }
+    fingerprints[0]
+        .iter()
+        .map(|fingerprint| {
+            let parts: Vec<&str> = fingerprint.split(' ').collect();
+            if parts.len() != 2 {
+                return Err(Error::ErrSessionDescriptionInvalidFingerprint);
+            }
 
-    Ok((parts[1].to_owned(), parts[0].to_owned()))
+            Ok(RTCDtlsFingerprint {
+                algorithm: parts[0].to_owned(),
+                value: parts[1].to_owned(),
+            })
+        })
+        .collect()
 }
 
 pub(crate) async fn extract_ice_details(
@@ -897,29 +1030,25 @@ pub(crate) async fn extract_ice_details(
     let mut remote_pwds = vec![];
     let mut remote_ufrags = vec![];
 
-    if let Some(ufrag) = desc.attribute("ice-ufrag") {
-        remote_ufrags.push(ufrag.clone());
+    if let Some(ufrag) = desc.ice_ufrag() {
+        remote_ufrags.push(ufrag.to_owned());
     }
-    if let Some(pwd) = desc.attribute("ice-pwd") {
-        remote_pwds.push(pwd.clone());
+    if let Some(pwd) = desc.ice_pwd() {
+        remote_pwds.push(pwd.to_owned());
     }
 
     for m in &desc.media_descriptions {
-        if let Some(ufrag) = m.attribute("ice-ufrag").and_then(|o| o) {
+        if let Some(ufrag) = m.ice_ufrag() {
             remote_ufrags.push(ufrag.to_owned());
         }
-        if let Some(pwd) = m.attribute("ice-pwd").and_then(|o| o) {
+        if let Some(pwd) = m.ice_pwd() {
             remote_pwds.push(pwd.to_owned());
         }
 
-        for a in &m.attributes {
-            if a.is_ice_candidate() {
-                if let Some(value) = &a.value {
-                    let c: Arc<dyn Candidate + Send + Sync> = Arc::new(unmarshal_candidate(value)?);
-                    let candidate = RTCIceCandidate::from(&c);
-                    candidates.push(candidate);
-                }
-            }
+        for value in m.candidates() {
+            let c: Arc<dyn Candidate + Send + Sync> = Arc::new(unmarshal_candidate(value)?);
+            let candidate = RTCIceCandidate::from(&c);
+            candidates.push(candidate);
         }
     }
 
@@ -944,14 +1073,19 @@ pub(crate) async fn extract_ice_details(
     Ok((remote_ufrags[0].clone(), remote_pwds[0].clone(), candidates))
 }
 
-pub(crate) fn have_application_media_section(desc: &SessionDescription) -> bool {
-    for m in &desc.media_descriptions {
-        if m.media_name.media == MEDIA_SECTION_APPLICATION {
-            return true;
-        }
-    }
-
-    false
+/// extract_sctp_capabilities returns the SCTP capabilities announced by the application
+/// media section of a remote description, if it has one
+pub(crate) fn extract_sctp_capabilities(
+    desc: &SessionDescription,
+) -> Option<SCTPTransportCapabilities> {
+    desc.media_descriptions
+        .iter()
+        .find(|m| m.media_name.media == MEDIA_SECTION_APPLICATION)
+        .map(|m| SCTPTransportCapabilities {
+            // Absence of max-message-size means the peer accepts up to 64K,
+            // <https://datatracker.ietf.org/doc/html/rfc8841#section-6>
+            max_message_size: m.max_message_size().unwrap_or(SCTP_MAX_MESSAGE_SIZE_UNSET),
+        })
 }
 
 pub(crate) fn get_by_mid<'a>(
@@ -1047,15 +1181,9 @@ pub(crate) fn rtp_extensions_from_media_description(
 ) -> Result<HashMap<String, isize>> {
     let mut out = HashMap::new();
 
-    for a in &m.attributes {
-        if a.key == ATTR_KEY_EXT_MAP {
-            let a_str = a.to_string();
-            let mut reader = BufReader::new(a_str.as_bytes());
-            let e = ExtMap::unmarshal(&mut reader)?;
-
-            if let Some(uri) = e.uri {
-                out.insert(uri.to_string(), e.value);
-            }
+    for e in m.extmaps()? {
+        if let Some(uri) = e.uri {
+            out.insert(uri.to_string(), e.value);
         }
     }
 
@@ -9,6 +9,7 @@ use crate::dtls_transport::dtls_role::DEFAULT_DTLS_ROLE_OFFER;
 use crate::dtls_transport::RTCDtlsTransport;
 use crate::peer_connection::certificate::RTCCertificate;
 use crate::rtp_transceiver::rtp_sender::RTCRtpSender;
+use crate::sctp_transport::{SCTP_MAX_MESSAGE_SIZE, SCTP_MAX_MESSAGE_SIZE_UNSET};
 use crate::track::track_local::track_local_static_sample::TrackLocalStaticSample;
 use crate::track::track_local::TrackLocal;
 
@@ -24,9 +25,10 @@ fn test_extract_fingerprint() -> Result<()> {
             ..Default::default()
         };
 
-        let (fingerprint, hash) = extract_fingerprint(&s)?;
-        assert_eq!(fingerprint, "bar");
-        assert_eq!(hash, "foo");
+        let fingerprints = extract_fingerprints(&s)?;
+        assert_eq!(fingerprints.len(), 1);
+        assert_eq!(fingerprints[0].value, "bar");
+        assert_eq!(fingerprints[0].algorithm, "foo");
     }
 
     //"Good Media Fingerprint"
@@ -42,9 +44,10 @@ fn test_extract_fingerprint() -> Result<()> {
             ..Default::default()
         };
 
-        let (fingerprint, hash) = extract_fingerprint(&s)?;
-        assert_eq!(fingerprint, "bar");
-        assert_eq!(hash, "foo");
+        let fingerprints = extract_fingerprints(&s)?;
+        assert_eq!(fingerprints.len(), 1);
+        assert_eq!(fingerprints[0].value, "bar");
+        assert_eq!(fingerprints[0].algorithm, "foo");
     }
 
     //"No Fingerprint"
@@ -52,7 +55,7 @@ fn test_extract_fingerprint() -> Result<()> {
         let s = SessionDescription::default();
 
         assert_eq!(
-            extract_fingerprint(&s).expect_err("fingerprint absence must be detected"),
+            extract_fingerprints(&s).expect_err("fingerprint absence must be detected"),
             Error::ErrSessionDescriptionNoFingerprint
         );
     }
@@ -68,7 +71,7 @@ fn test_extract_fingerprint() -> Result<()> {
         };
 
         assert_eq!(
-            extract_fingerprint(&s).expect_err("invalid fingerprint text must be detected"),
+            extract_fingerprints(&s).expect_err("invalid fingerprint text must be detected"),
             Error::ErrSessionDescriptionInvalidFingerprint
         );
     }
@@ -91,7 +94,61 @@ fn test_extract_fingerprint() -> Result<()> {
         };
 
         assert_eq!(
-            extract_fingerprint(&s).expect_err("mismatching fingerprint texts must be detected"),
+            extract_fingerprints(&s).expect_err("mismatching fingerprint texts must be detected"),
+            Error::ErrSessionDescriptionConflictingFingerprints
+        );
+    }
+
+    //"Multiple Fingerprints"
+    {
+        let fingerprint = |value: &str| Attribute {
+            key: "fingerprint".to_owned(),
+            value: Some(value.to_owned()),
+        };
+        let s = SessionDescription {
+            media_descriptions: vec![
+                MediaDescription {
+                    attributes: vec![fingerprint("sha-256 AA"), fingerprint("sha-1 BB")],
+                    ..Default::default()
+                },
+                MediaDescription {
+                    attributes: vec![fingerprint("sha-1 BB"), fingerprint("sha-256 AA")],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let fingerprints = extract_fingerprints(&s)?;
+        assert_eq!(fingerprints.len(), 2);
+        assert_eq!(fingerprints[0].algorithm, "sha-1");
+        assert_eq!(fingerprints[0].value, "BB");
+        assert_eq!(fingerprints[1].algorithm, "sha-256");
+        assert_eq!(fingerprints[1].value, "AA");
+    }
+
+    //"Conflicting Multiple Fingerprints"
+    {
+        let fingerprint = |value: &str| Attribute {
+            key: "fingerprint".to_owned(),
+            value: Some(value.to_owned()),
+        };
+        let s = SessionDescription {
+            media_descriptions: vec![
+                MediaDescription {
+                    attributes: vec![fingerprint("sha-256 AA"), fingerprint("sha-1 BB")],
+                    ..Default::default()
+                },
+                MediaDescription {
+                    attributes: vec![fingerprint("sha-256 AA")],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            extract_fingerprints(&s).expect_err("mismatching fingerprint sets must be detected"),
             Error::ErrSessionDescriptionConflictingFingerprints
         );
     }
@@ -476,7 +533,7 @@ fn test_track_details_from_sdp() -> Result<()> {
 }
 
 #[test]
-fn test_have_application_media_section() -> Result<()> {
+fn test_extract_sctp_capabilities() -> Result<()> {
     //"Audio only"
     {
         let s = SessionDescription {
@@ -500,7 +557,7 @@ fn test_have_application_media_section() -> Result<()> {
             ..Default::default()
         };
 
-        assert!(!have_application_media_section(&s));
+        assert!(extract_sctp_capabilities(&s).is_none());
     }
 
     //"Application"
@@ -516,7 +573,35 @@ fn test_have_application_media_section() -> Result<()> {
             ..Default::default()
         };
 
-        assert!(have_application_media_section(&s));
+        assert_eq!(
+            extract_sctp_capabilities(&s),
+            Some(SCTPTransportCapabilities {
+                max_message_size: SCTP_MAX_MESSAGE_SIZE_UNSET
+            })
+        );
+    }
+
+    //"Application with max-message-size"
+    {
+        let s = SessionDescription {
+            media_descriptions: vec![MediaDescription {
+                media_name: MediaName {
+                    media: MEDIA_SECTION_APPLICATION.to_owned(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+            .with_sctp_port(5000)
+            .with_max_message_size(262144)],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            extract_sctp_capabilities(&s),
+            Some(SCTPTransportCapabilities {
+                max_message_size: 262144
+            })
+        );
     }
 
     Ok(())
@@ -538,6 +623,7 @@ async fn fingerprint_test(
         is_icelite: false,
         connection_role: ConnectionRole::Active,
         ice_gathering_state: RTCIceGatheringState::New,
+        sctp_max_message_size: SCTP_MAX_MESSAGE_SIZE,
     };
 
     let s = populate_sdp(
@@ -732,6 +818,7 @@ async fn test_populate_sdp() -> Result<()> {
             is_icelite: se.candidates.ice_lite,
             connection_role: DEFAULT_DTLS_ROLE_OFFER.to_connection_role(),
             ice_gathering_state: RTCIceGatheringState::Complete,
+            sctp_max_message_size: se.get_sctp_max_message_size(),
         };
         let offer_sdp = populate_sdp(
             d,
@@ -754,6 +841,7 @@ async fn test_populate_sdp() -> Result<()> {
             let rid_map = get_rids(desc);
             if let Some(rid) = rid_map.iter().find(|rid| rid.id == "ridkey") {
                 assert!(!rid.paused, "Rid should be active");
+                assert_eq!(rid.params, "some", "Rid restrictions should be kept");
                 assert_eq!(
                     rid.direction,
                     SimulcastDirection::Send,
@@ -836,6 +924,7 @@ async fn test_populate_sdp() -> Result<()> {
             is_icelite: se.candidates.ice_lite,
             connection_role: DEFAULT_DTLS_ROLE_OFFER.to_connection_role(),
             ice_gathering_state: RTCIceGatheringState::Complete,
+            sctp_max_message_size: se.get_sctp_max_message_size(),
         };
         let offer_sdp = populate_sdp(
             d,
@@ -869,6 +958,48 @@ async fn test_populate_sdp() -> Result<()> {
         assert!(found_vp8, "vp8 should be present in sdp");
     }
 
+    //"DataChannel max-message-size"
+    {
+        let mut se = SettingEngine::default();
+        se.set_sctp_max_message_size(1 << 20);
+        let mut me = MediaEngine::default();
+        me.register_default_codecs()?;
+        let api = APIBuilder::new().with_media_engine(me).build();
+
+        let media_sections = vec![MediaSection {
+            id: "data".to_owned(),
+            data: true,
+            ..Default::default()
+        }];
+
+        let d = SessionDescription::default();
+
+        let params = PopulateSdpParams {
+            media_description_fingerprint: se.sdp_media_level_fingerprints,
+            is_icelite: se.candidates.ice_lite,
+            connection_role: DEFAULT_DTLS_ROLE_OFFER.to_connection_role(),
+            ice_gathering_state: RTCIceGatheringState::Complete,
+            sctp_max_message_size: se.get_sctp_max_message_size(),
+        };
+        let offer_sdp = populate_sdp(
+            d,
+            &[],
+            &api.media_engine,
+            &[],
+            &RTCIceParameters::default(),
+            &media_sections,
+            params,
+        )
+        .await?;
+
+        assert_eq!(
+            extract_sctp_capabilities(&offer_sdp),
+            Some(SCTPTransportCapabilities {
+                max_message_size: 1 << 20
+            })
+        );
+    }
+
     Ok(())
 }
 
@@ -962,6 +1093,7 @@ async fn test_populate_sdp_reject() -> Result<()> {
         is_icelite: se.candidates.ice_lite,
         connection_role: DEFAULT_DTLS_ROLE_OFFER.to_connection_role(),
         ice_gathering_state: RTCIceGatheringState::Complete,
+        sctp_max_message_size: se.get_sctp_max_message_size(),
     };
     let offer_sdp = populate_sdp(
         d,
@@ -1007,7 +1139,7 @@ async fn test_populate_sdp_reject() -> Result<()> {
 
 #[test]
 fn test_get_rids() {
-    let m = vec![MediaDescription {
+    let m = [MediaDescription {
         media_name: MediaName {
             media: "video".to_owned(),
             ..Default::default()
@@ -1031,6 +1163,32 @@ fn test_get_rids() {
 
     let f = rids.iter().find(|rid| rid.id == "f");
     assert!(f.is_some(), "rid values should contain 'f'");
+    assert_eq!(
+        f.unwrap().restrictions(),
+        vec![
+            ("pt", Some("97")),
+            ("max-width", Some("1280")),
+            ("max-height", Some("720")),
+        ]
+    );
+}
+
+#[test]
+fn test_build_simulcast_attribute() {
+    let to_strings = |ids: &[&str]| -> Vec<String> { ids.iter().map(|s| s.to_string()).collect() };
+
+    assert_eq!(
+        build_simulcast_attribute(&to_strings(&["h", "~m"]), &[]),
+        "recv h;~m"
+    );
+    assert_eq!(
+        build_simulcast_attribute(&[], &to_strings(&["l"])),
+        "send l"
+    );
+    assert_eq!(
+        build_simulcast_attribute(&to_strings(&["h", "~m"]), &to_strings(&["l"])),
+        "recv h;~m send l"
+    );
 }
 
 #[test]
@@ -1149,3 +1307,78 @@ fn test_rtp_extensions_from_media_description() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_check_rtcp_mux() -> Result<()> {
+    let muxed = SessionDescription::default()
+        .with_media(
+            MediaDescription::new_jsep_media_description("audio".to_owned(), vec![])
+                .with_property_attribute(ATTR_KEY_RTCPMUX.to_owned()),
+        )
+        .with_media(MediaDescription::new_jsep_media_description(
+            "application".to_owned(),
+            vec![],
+        ));
+    let not_muxed = SessionDescription::default().with_media(
+        MediaDescription::new_jsep_media_description("video".to_owned(), vec![]),
+    );
+    let mut rejected = not_muxed.clone();
+    rejected.media_descriptions[0].media_name.port.value = 0;
+
+    for policy in [
+        RTCRtcpMuxPolicy::Unspecified,
+        RTCRtcpMuxPolicy::Negotiate,
+        RTCRtcpMuxPolicy::Require,
+    ] {
+        check_rtcp_mux(&muxed, policy)?;
+        check_rtcp_mux(&rejected, policy)?;
+    }
+
+    check_rtcp_mux(&not_muxed, RTCRtcpMuxPolicy::Negotiate)?;
+    assert_eq!(
+        check_rtcp_mux(&not_muxed, RTCRtcpMuxPolicy::Require),
+        Err(Error::ErrSDPRtcpMuxRequired)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_check_setup_role() {
+    let with_setup = |role: &str| {
+        SessionDescription::default().with_media(
+            MediaDescription::new_jsep_media_description("audio".to_owned(), vec![])
+                .with_value_attribute(ATTR_KEY_CONNECTION_SETUP.to_owned(), role.to_owned()),
+        )
+    };
+
+    let tests = vec![
+        ("actpass", RTCSdpType::Offer, true),
+        ("active", RTCSdpType::Offer, true),
+        ("passive", RTCSdpType::Offer, true),
+        ("holdconn", RTCSdpType::Offer, false),
+        ("unknown", RTCSdpType::Offer, false),
+        ("active", RTCSdpType::Answer, true),
+        ("passive", RTCSdpType::Answer, true),
+        ("actpass", RTCSdpType::Answer, false),
+        ("actpass", RTCSdpType::Pranswer, false),
+    ];
+
+    for (role, sdp_type, valid) in tests {
+        let result = check_setup_role(&with_setup(role), sdp_type);
+        if valid {
+            assert_eq!(result, Ok(()), "setup:{role} in {sdp_type}");
+        } else {
+            assert_eq!(
+                result,
+                Err(Error::ErrSessionDescriptionInvalidSetupRole),
+                "setup:{role} in {sdp_type}"
+            );
+        }
+    }
+
+    assert_eq!(
+        check_setup_role(&SessionDescription::default(), RTCSdpType::Answer),
+        Ok(())
+    );
+}
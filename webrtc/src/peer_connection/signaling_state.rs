@@ -116,11 +116,30 @@ pub(crate) fn check_next_signaling_state(
     op: StateChangeOp,
     sdp_type: RTCSdpType,
 ) -> Result<RTCSignalingState> {
-    // Special case for rollbacks
-    if sdp_type == RTCSdpType::Rollback && cur == RTCSignalingState::Stable {
+    // Special case for rollbacks, there is no offer to roll back in these states,
+    // <https://www.w3.org/TR/webrtc/#set-description> (step 4.2.4)
+    if sdp_type == RTCSdpType::Rollback
+        && matches!(
+            cur,
+            RTCSignalingState::Stable
+                | RTCSignalingState::HaveLocalPranswer
+                | RTCSignalingState::HaveRemotePranswer
+        )
+    {
         return Err(Error::ErrSignalingStateCannotRollback);
     }
 
+    // A rollback returns to stable from the offer applied on the same side
+    if sdp_type == RTCSdpType::Rollback && next == RTCSignalingState::Stable {
+        let can_rollback = match op {
+            StateChangeOp::SetLocal => cur == RTCSignalingState::HaveLocalOffer,
+            StateChangeOp::SetRemote => cur == RTCSignalingState::HaveRemoteOffer,
+        };
+        if can_rollback {
+            return Ok(next);
+        }
+    }
+
     // 4.3.1 valid state transitions
     match cur {
         RTCSignalingState::Stable => {
@@ -345,6 +364,62 @@ mod test {
                 RTCSdpType::Rollback,
                 Some(Error::ErrSignalingStateCannotRollback),
             ),
+            (
+                "have-local-offer->SetLocal(rollback)->stable",
+                RTCSignalingState::HaveLocalOffer,
+                RTCSignalingState::Stable,
+                StateChangeOp::SetLocal,
+                RTCSdpType::Rollback,
+                None,
+            ),
+            (
+                "have-remote-offer->SetRemote(rollback)->stable",
+                RTCSignalingState::HaveRemoteOffer,
+                RTCSignalingState::Stable,
+                StateChangeOp::SetRemote,
+                RTCSdpType::Rollback,
+                None,
+            ),
+            (
+                "(invalid) have-local-offer->SetRemote(rollback)->stable",
+                RTCSignalingState::HaveLocalOffer,
+                RTCSignalingState::Stable,
+                StateChangeOp::SetRemote,
+                RTCSdpType::Rollback,
+                Some(Error::ErrSignalingStateProposedTransitionInvalid {
+                    from: RTCSignalingState::HaveLocalOffer,
+                    is_local: false,
+                    applying: RTCSdpType::Rollback,
+                }),
+            ),
+            (
+                "(invalid) have-remote-offer->SetLocal(rollback)->stable",
+                RTCSignalingState::HaveRemoteOffer,
+                RTCSignalingState::Stable,
+                StateChangeOp::SetLocal,
+                RTCSdpType::Rollback,
+                Some(Error::ErrSignalingStateProposedTransitionInvalid {
+                    from: RTCSignalingState::HaveRemoteOffer,
+                    is_local: true,
+                    applying: RTCSdpType::Rollback,
+                }),
+            ),
+            (
+                "(invalid) have-remote-pranswer->SetLocal(rollback)->stable",
+                RTCSignalingState::HaveRemotePranswer,
+                RTCSignalingState::Stable,
+                StateChangeOp::SetLocal,
+                RTCSdpType::Rollback,
+                Some(Error::ErrSignalingStateCannotRollback),
+            ),
+            (
+                "(invalid) have-local-pranswer->SetRemote(rollback)->stable",
+                RTCSignalingState::HaveLocalPranswer,
+                RTCSignalingState::Stable,
+                StateChangeOp::SetRemote,
+                RTCSdpType::Rollback,
+                Some(Error::ErrSignalingStateCannotRollback),
+            ),
         ];
 
         for (desc, cur, next, op, sdp_type, expected_err) in tests {
@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn test_av1_fmtp_parse() {
+    let f = parse("video/AV1", "profile=1;level-idx=8;tier=0");
+    assert_eq!(f.mime_type(), "video/av1");
+
+    let expected: Box<dyn Fmtp> = Box::new(Av1Fmtp {
+        parameters: [
+            ("profile".to_owned(), "1".to_owned()),
+            ("level-idx".to_owned(), "8".to_owned()),
+            ("tier".to_owned(), "0".to_owned()),
+        ]
+        .iter()
+        .cloned()
+        .collect(),
+    });
+    assert_eq!(&f, &expected);
+}
+
+#[test]
+fn test_av1_fmtp_compare() {
+    let tests = vec![
+        ("Equal", "profile=0", "profile=0", true),
+        ("DefaultProfile", "profile=0", "", true),
+        (
+            "CapabilitiesDiffer",
+            "profile=1;level-idx=8",
+            "profile=1;level-idx=5;tier=1",
+            true,
+        ),
+        ("DifferentProfile", "profile=0", "profile=1", false),
+        ("DefaultVsProfile2", "", "profile=2", false),
+    ];
+
+    for (name, a, b, consist) in tests {
+        let aa = parse("video/av1", a);
+        let bb = parse("video/av1", b);
+
+        assert_eq!(aa.match_fmtp(&*bb), consist, "{name}: forward");
+        assert_eq!(bb.match_fmtp(&*aa), consist, "{name}: reverse");
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod av1_test;
+
+use super::*;
+
+/// The profile assumed when profile is absent,
+/// <https://aomediacodec.github.io/av1-rtp-spec/#72-sdp-parameters>
+const DEFAULT_PROFILE: &str = "0";
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Av1Fmtp {
+    pub(crate) parameters: HashMap<String, String>,
+}
+
+impl Av1Fmtp {
+    fn profile(&self) -> &str {
+        self.parameters
+            .get("profile")
+            .map_or(DEFAULT_PROFILE, |s| s.as_str())
+    }
+}
+
+impl Fmtp for Av1Fmtp {
+    fn mime_type(&self) -> &str {
+        "video/av1"
+    }
+
+    /// Match returns true if a and b are compatible fmtp descriptions.
+    /// Only the profile has to agree, level-idx and tier describe what the
+    /// receiver is able to decode.
+    fn match_fmtp(&self, f: &dyn Fmtp) -> bool {
+        if let Some(c) = f.as_any().downcast_ref::<Av1Fmtp>() {
+            self.profile() == c.profile()
+        } else {
+            false
+        }
+    }
+
+    fn parameter(&self, key: &str) -> Option<&String> {
+        self.parameters.get(key)
+    }
+
+    fn equal(&self, other: &dyn Fmtp) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<Av1Fmtp>()
+            .is_some_and(|a| self == a)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
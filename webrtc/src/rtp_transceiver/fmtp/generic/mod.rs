@@ -3,8 +3,8 @@ mod generic_test;
 
 use super::*;
 
-/// fmtp_consist checks that two FMTP parameters are not inconsistent.
-fn fmtp_consist(a: &HashMap<String, String>, b: &HashMap<String, String>) -> bool {
+/// parameters_consist checks that two FMTP parameters are not inconsistent.
+fn parameters_consist(a: &HashMap<String, String>, b: &HashMap<String, String>) -> bool {
     //TODO: add unicode case-folding equal support
     for (k, v) in a {
         if let Some(vb) = b.get(k) {
@@ -42,7 +42,7 @@ impl Fmtp for GenericFmtp {
                 return false;
             }
 
-            fmtp_consist(&self.parameters, &c.parameters)
+            parameters_consist(&self.parameters, &c.parameters)
         } else {
             false
         }
@@ -51,15 +51,15 @@ impl Fmtp for H264Fmtp {
     ///     for the other stream properties and capability parameters.
     fn match_fmtp(&self, f: &(dyn Fmtp)) -> bool {
         if let Some(c) = f.as_any().downcast_ref::<H264Fmtp>() {
-            // test packetization-mode
-            let hpmode = match self.parameters.get("packetization-mode") {
-                Some(s) => s,
-                None => return false,
-            };
-            let cpmode = match c.parameters.get("packetization-mode") {
-                Some(s) => s,
-                None => return false,
-            };
+            // test packetization-mode, which is 0 when absent (RFC6184 Section 8.1)
+            let hpmode = self
+                .parameters
+                .get("packetization-mode")
+                .map_or("0", |s| s.as_str());
+            let cpmode = c
+                .parameters
+                .get("packetization-mode")
+                .map_or("0", |s| s.as_str());
 
             if hpmode != cpmode {
                 return false;
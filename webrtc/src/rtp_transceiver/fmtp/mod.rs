@@ -1,12 +1,18 @@
+pub(crate) mod av1;
 pub(crate) mod generic;
 pub(crate) mod h264;
+pub(crate) mod opus;
+pub(crate) mod vp9;
 
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
 
+use crate::rtp_transceiver::fmtp::av1::Av1Fmtp;
 use crate::rtp_transceiver::fmtp::generic::GenericFmtp;
 use crate::rtp_transceiver::fmtp::h264::H264Fmtp;
+use crate::rtp_transceiver::fmtp::opus::OpusFmtp;
+use crate::rtp_transceiver::fmtp::vp9::Vp9Fmtp;
 
 /// Fmtp interface for implementing custom
 /// Fmtp parsers based on mime_type
@@ -49,6 +55,12 @@ pub fn parse(mime_type: &str, line: &str) -> Box<dyn Fmtp> {
 
     if mime_type.to_uppercase() == "video/h264".to_uppercase() {
         Box::new(H264Fmtp { parameters })
+    } else if mime_type.to_uppercase() == "video/vp9".to_uppercase() {
+        Box::new(Vp9Fmtp { parameters })
+    } else if mime_type.to_uppercase() == "video/av1".to_uppercase() {
+        Box::new(Av1Fmtp { parameters })
+    } else if mime_type.to_uppercase() == "audio/opus".to_uppercase() {
+        Box::new(OpusFmtp { parameters })
     } else {
         Box::new(GenericFmtp {
             mime_type: mime_type.to_owned(),
@@ -56,3 +68,10 @@ pub fn parse(mime_type: &str, line: &str) -> Box<dyn Fmtp> {
         })
     }
 }
+
+/// fmtp_consist returns true if two fmtp descriptions of the same mime_type are
+/// compatible in both directions, i.e. the codec-defining parameters agree and
+/// only the capabilities and preferences of either side differ.
+pub(crate) fn fmtp_consist(a: &dyn Fmtp, b: &dyn Fmtp) -> bool {
+    a.mime_type().eq_ignore_ascii_case(b.mime_type()) && a.match_fmtp(b) && b.match_fmtp(a)
+}
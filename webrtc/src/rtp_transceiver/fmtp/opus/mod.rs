@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod opus_test;
+
+use super::*;
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct OpusFmtp {
+    pub(crate) parameters: HashMap<String, String>,
+}
+
+impl OpusFmtp {
+    fn flag(&self, key: &str) -> bool {
+        self.parameters.get(key).is_some_and(|s| s == "1")
+    }
+
+    fn number(&self, key: &str) -> Option<u32> {
+        self.parameters.get(key).and_then(|s| s.parse().ok())
+    }
+
+    /// minptime is the minimum packet duration in milliseconds the receiver wants.
+    pub(crate) fn minptime(&self) -> Option<u32> {
+        self.number("minptime")
+    }
+
+    /// max_playback_rate is the maximum output sampling rate the receiver can render.
+    pub(crate) fn max_playback_rate(&self) -> Option<u32> {
+        self.number("maxplaybackrate")
+    }
+
+    /// max_average_bitrate is the maximum average bitrate the receiver wants to receive.
+    pub(crate) fn max_average_bitrate(&self) -> Option<u32> {
+        self.number("maxaveragebitrate")
+    }
+
+    /// stereo is true if the receiver prefers stereo signals.
+    pub(crate) fn stereo(&self) -> bool {
+        self.flag("stereo")
+    }
+
+    /// sprop_stereo is true if the sender is likely to send stereo signals.
+    pub(crate) fn sprop_stereo(&self) -> bool {
+        self.flag("sprop-stereo")
+    }
+
+    /// use_inband_fec is true if the receiver can take advantage of Opus in-band FEC.
+    pub(crate) fn use_inband_fec(&self) -> bool {
+        self.flag("useinbandfec")
+    }
+
+    /// use_dtx is true if the receiver prefers discontinuous transmission.
+    pub(crate) fn use_dtx(&self) -> bool {
+        self.flag("usedtx")
+    }
+
+    /// cbr is true if the receiver prefers constant bitrate.
+    pub(crate) fn cbr(&self) -> bool {
+        self.flag("cbr")
+    }
+}
+
+impl Fmtp for OpusFmtp {
+    fn mime_type(&self) -> &str {
+        "audio/opus"
+    }
+
+    /// Match returns true if o and b are compatible fmtp descriptions.
+    /// Based on RFC7587 Section 7: all Opus parameters describe the preferences
+    /// and capabilities of one side and can be used asymmetrically, so any two
+    /// Opus descriptions are compatible.
+    fn match_fmtp(&self, f: &dyn Fmtp) -> bool {
+        f.as_any().downcast_ref::<OpusFmtp>().is_some()
+    }
+
+    fn parameter(&self, key: &str) -> Option<&String> {
+        self.parameters.get(key)
+    }
+
+    fn equal(&self, other: &dyn Fmtp) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<OpusFmtp>()
+            .is_some_and(|a| self == a)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn test_opus_fmtp_parse() {
+    let f = parse(
+        "audio/OPUS",
+        "minptime=10;useinbandfec=1;stereo=1;maxaveragebitrate=64000",
+    );
+    assert_eq!(f.mime_type(), "audio/opus");
+
+    let opus = f.as_any().downcast_ref::<OpusFmtp>().unwrap();
+    assert_eq!(opus.minptime(), Some(10));
+    assert_eq!(opus.max_average_bitrate(), Some(64000));
+    assert_eq!(opus.max_playback_rate(), None);
+    assert!(opus.use_inband_fec());
+    assert!(opus.stereo());
+    assert!(!opus.sprop_stereo());
+    assert!(!opus.use_dtx());
+    assert!(!opus.cbr());
+}
+
+#[test]
+fn test_opus_fmtp_compare() {
+    let tests = vec![
+        (
+            "Equal",
+            "minptime=10;useinbandfec=1",
+            "minptime=10;useinbandfec=1",
+        ),
+        ("Empty", "minptime=10;useinbandfec=1", ""),
+        ("FecDiffers", "useinbandfec=1", "useinbandfec=0"),
+        ("StereoDiffers", "stereo=1;sprop-stereo=1", "stereo=0"),
+        ("PtimeDiffers", "minptime=10", "minptime=20"),
+    ];
+
+    for (name, a, b) in tests {
+        let aa = parse("audio/opus", a);
+        let bb = parse("audio/opus", b);
+
+        assert!(aa.match_fmtp(&*bb), "{name}: forward");
+        assert!(bb.match_fmtp(&*aa), "{name}: reverse");
+        assert!(fmtp_consist(&*aa, &*bb), "{name}: consist");
+    }
+
+    let generic = parse("audio/pcmu", "");
+    assert!(!fmtp_consist(&*parse("audio/opus", ""), &*generic));
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod vp9_test;
+
+use super::*;
+
+/// The profile assumed when profile-id is absent,
+/// <https://datatracker.ietf.org/doc/html/draft-ietf-payload-vp9-16#section-6>
+const DEFAULT_PROFILE_ID: &str = "0";
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Vp9Fmtp {
+    pub(crate) parameters: HashMap<String, String>,
+}
+
+impl Vp9Fmtp {
+    fn profile_id(&self) -> &str {
+        self.parameters
+            .get("profile-id")
+            .map_or(DEFAULT_PROFILE_ID, |s| s.as_str())
+    }
+}
+
+impl Fmtp for Vp9Fmtp {
+    fn mime_type(&self) -> &str {
+        "video/vp9"
+    }
+
+    /// Match returns true if v and b are compatible fmtp descriptions.
+    /// Streams of different VP9 profiles can't be decoded interchangeably, so the
+    /// profile-id has to agree, while the other parameters are receiver capabilities.
+    fn match_fmtp(&self, f: &dyn Fmtp) -> bool {
+        if let Some(c) = f.as_any().downcast_ref::<Vp9Fmtp>() {
+            self.profile_id() == c.profile_id()
+        } else {
+            false
+        }
+    }
+
+    fn parameter(&self, key: &str) -> Option<&String> {
+        self.parameters.get(key)
+    }
+
+    fn equal(&self, other: &dyn Fmtp) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<Vp9Fmtp>()
+            .is_some_and(|a| self == a)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn test_vp9_fmtp_parse() {
+    let f = parse("video/VP9", "profile-id=2;max-fr=30");
+    assert_eq!(f.mime_type(), "video/vp9");
+
+    let expected: Box<dyn Fmtp> = Box::new(Vp9Fmtp {
+        parameters: [
+            ("profile-id".to_owned(), "2".to_owned()),
+            ("max-fr".to_owned(), "30".to_owned()),
+        ]
+        .iter()
+        .cloned()
+        .collect(),
+    });
+    assert_eq!(&f, &expected);
+}
+
+#[test]
+fn test_vp9_fmtp_compare() {
+    let tests = vec![
+        ("Equal", "profile-id=0", "profile-id=0", true),
+        ("DefaultProfile", "profile-id=0", "", true),
+        ("BothDefault", "", "max-fr=30", true),
+        (
+            "CapabilitiesDiffer",
+            "profile-id=2;max-fr=30",
+            "profile-id=2;max-fs=1200",
+            true,
+        ),
+        ("DifferentProfile", "profile-id=0", "profile-id=2", false),
+        ("DefaultVsProfile2", "", "profile-id=2", false),
+    ];
+
+    for (name, a, b, consist) in tests {
+        let aa = parse("video/vp9", a);
+        let bb = parse("video/vp9", b);
+
+        assert_eq!(aa.match_fmtp(&*bb), consist, "{name}: forward");
+        assert_eq!(bb.match_fmtp(&*aa), consist, "{name}: reverse");
+    }
+}
@@ -13,7 +13,8 @@ use log::trace;
 use portable_atomic::{AtomicBool, AtomicU8};
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
-use tokio::sync::{Mutex, OnceCell};
+use tokio::sync::Mutex;
+use util::sync::Mutex as SyncMutex;
 use util::Unmarshal;
 
 use crate::api::media_engine::MediaEngine;
@@ -98,6 +99,12 @@ pub struct RTCRtpCodingParameters {
     pub ssrc: SSRC,
     pub payload_type: PayloadType,
     pub rtx: RTCRtpRtxParameters,
+    /// max_bitrate is the maximum bitrate, in bits per second, the application
+    /// should encode this encoding at. None means no limit.
+    pub max_bitrate: Option<u64>,
+    /// scale_resolution_down_by is the factor the application should scale the
+    /// resolution of this encoding down by. None means no scaling.
+    pub scale_resolution_down_by: Option<f64>,
 }
 
 /// RTPDecodingParameters provides information relating to both encoding and decoding.
@@ -165,6 +172,7 @@ pub(crate) fn create_stream_info(
         channels: codec.channels,
         sdp_fmtp_line: codec.sdp_fmtp_line,
         rtcp_feedback: feedbacks,
+        ..Default::default()
     }
 }
 
@@ -173,7 +181,7 @@ pub type TriggerNegotiationNeededFnOption =
 
 /// RTPTransceiver represents a combination of an RTPSender and an RTPReceiver that share a common mid.
 pub struct RTCRtpTransceiver {
-    mid: OnceCell<SmolStr>,               //atomic.Value
+    mid: SyncMutex<Option<SmolStr>>,      //atomic.Value
     sender: Mutex<Arc<RTCRtpSender>>,     //atomic.Value
     receiver: Mutex<Arc<RTCRtpReceiver>>, //atomic.Value
 
@@ -204,7 +212,7 @@ impl RTCRtpTransceiver {
         receiver.set_transceiver_codecs(Some(Arc::clone(&codecs)));
 
         let t = Arc::new(RTCRtpTransceiver {
-            mid: OnceCell::new(),
+            mid: SyncMutex::new(None),
             sender: Mutex::new(sender),
             receiver: Mutex::new(receiver),
 
@@ -226,6 +234,10 @@ impl RTCRtpTransceiver {
 
     /// set_codec_preferences sets preferred list of supported codecs
     /// if codecs is empty or nil we reset to default from MediaEngine
+    ///
+    /// The codecs are offered and answered in the order of the list, so the remote
+    /// picks the first one it supports. A zero payload type, an empty fmtp line or an
+    /// empty list of RTCP feedback is filled in from the matching MediaEngine codec.
     pub async fn set_codec_preferences(&self, codecs: Vec<RTCRtpCodecParameters>) -> Result<()> {
         for codec in &codecs {
             let media_engine_codecs = self.media_engine.get_codecs_by_kind(self.kind);
@@ -295,14 +307,23 @@ impl RTCRtpTransceiver {
 
     /// set_mid sets the RTPTransceiver's mid. If it was already set, will return an error.
     pub(crate) fn set_mid(&self, mid: SmolStr) -> Result<()> {
-        self.mid
-            .set(mid)
-            .map_err(|_| Error::ErrRTPTransceiverCannotChangeMid)
+        let mut current = self.mid.lock();
+        if current.is_some() {
+            return Err(Error::ErrRTPTransceiverCannotChangeMid);
+        }
+        *current = Some(mid);
+        Ok(())
+    }
+
+    /// clear_mid disassociates the RTPTransceiver from its media section, when the
+    /// description that associated it is rolled back.
+    pub(crate) fn clear_mid(&self) {
+        *self.mid.lock() = None;
     }
 
     /// mid gets the Transceiver's mid value. When not already set, this value will be set in CreateOffer or create_answer.
     pub fn mid(&self) -> Option<SmolStr> {
-        self.mid.get().cloned()
+        self.mid.lock().clone()
     }
 
     /// kind returns RTPTransceiver's kind.
@@ -316,7 +337,14 @@ impl RTCRtpTransceiver {
     }
 
     /// Set the direction of this transceiver. This might trigger a renegotiation.
+    ///
+    /// The direction of a stopped transceiver stays [`RTCRtpTransceiverDirection::Inactive`].
     pub async fn set_direction(&self, d: RTCRtpTransceiverDirection) {
+        if self.stopped.load(Ordering::SeqCst) {
+            trace!("Ignoring direction change to {} of stopped transceiver", d);
+            return;
+        }
+
         let changed = self.set_direction_internal(d);
 
         if changed {
@@ -463,7 +491,7 @@ impl RTCRtpTransceiver {
 impl fmt::Debug for RTCRtpTransceiver {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("RTCRtpTransceiver")
-            .field("mid", &self.mid)
+            .field("mid", &self.mid())
             .field("sender", &self.sender)
             .field("receiver", &self.receiver)
             .field("direction", &self.direction)
@@ -533,25 +561,41 @@ pub(crate) fn handle_unknown_rtp_packet(
     let mut reader = buf;
     let rp = rtp::packet::Packet::unmarshal(&mut reader)?;
 
-    if !rp.header.extension {
+    handle_unknown_rtp_header(
+        &rp.header,
+        mid_extension_id,
+        sid_extension_id,
+        rsid_extension_id,
+    )
+}
+
+/// handle_unknown_rtp_header is the same as handle_unknown_rtp_packet for an already unmarshaled
+/// RTP header.
+pub(crate) fn handle_unknown_rtp_header(
+    header: &rtp::header::Header,
+    mid_extension_id: u8,
+    sid_extension_id: u8,
+    rsid_extension_id: u8,
+) -> Result<(String, String, String, PayloadType)> {
+    if !header.extension {
         return Ok((String::new(), String::new(), String::new(), 0));
     }
 
-    let payload_type = rp.header.payload_type;
+    let payload_type = header.payload_type;
 
-    let mid = if let Some(payload) = rp.header.get_extension(mid_extension_id) {
+    let mid = if let Some(payload) = header.get_extension(mid_extension_id) {
         String::from_utf8(payload.to_vec())?
     } else {
         String::new()
     };
 
-    let rid = if let Some(payload) = rp.header.get_extension(sid_extension_id) {
+    let rid = if let Some(payload) = header.get_extension(sid_extension_id) {
         String::from_utf8(payload.to_vec())?
     } else {
         String::new()
     };
 
-    let srid = if let Some(payload) = rp.header.get_extension(rsid_extension_id) {
+    let srid = if let Some(payload) = header.get_extension(rsid_extension_id) {
         String::from_utf8(payload.to_vec())?
     } else {
         String::new()
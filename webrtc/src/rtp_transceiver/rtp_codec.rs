@@ -147,7 +147,7 @@ pub(crate) fn codec_parameters_fuzzy_search(
     // First attempt to match on mime_type + sdpfmtp_line
     for c in haystack {
         let cfmpt = fmtp::parse(&c.capability.mime_type, &c.capability.sdp_fmtp_line);
-        if needle_fmtp.match_fmtp(&*cfmpt) {
+        if fmtp::fmtp_consist(&*needle_fmtp, &*cfmpt) {
             return (c.clone(), CodecMatch::Exact);
         }
     }
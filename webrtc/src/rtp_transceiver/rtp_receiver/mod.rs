@@ -344,9 +344,16 @@ impl RTPReceiverInternal {
         for codec in codecs {
             let (c, match_type) = codec_parameters_fuzzy_search(codec, &media_engine_codecs);
             if match_type != CodecMatch::None {
+                // Anything left unset by the preference is taken from the MediaEngine
                 if codec.payload_type == 0 {
                     codec.payload_type = c.payload_type;
                 }
+                if codec.capability.sdp_fmtp_line.is_empty() {
+                    codec.capability.sdp_fmtp_line = c.capability.sdp_fmtp_line;
+                }
+                if codec.capability.rtcp_feedback.is_empty() {
+                    codec.capability.rtcp_feedback = c.capability.rtcp_feedback;
+                }
                 filtered_codecs.push(codec.clone());
             }
         }
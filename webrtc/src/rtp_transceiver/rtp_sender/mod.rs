@@ -14,7 +14,7 @@ use util::sync::Mutex as SyncMutex;
 use super::srtp_writer_future::SequenceTransformer;
 use crate::api::media_engine::MediaEngine;
 use crate::dtls_transport::RTCDtlsTransport;
-use crate::error::{Error, Result};
+use crate::error::{flatten_errs, Error, Result};
 use crate::rtp_transceiver::rtp_codec::{RTCRtpCodecParameters, RTPCodecType};
 use crate::rtp_transceiver::rtp_transceiver_direction::RTCRtpTransceiverDirection;
 use crate::rtp_transceiver::srtp_writer_future::SrtpWriterFuture;
@@ -77,18 +77,63 @@ impl RTPSenderInternal {
 
         Ok((pkts, attributes))
     }
+
+    /// read_simulcast reads incoming RTCP for the encoding of the given rtcp_interceptor
+    async fn read_simulcast(
+        &self,
+        b: &mut [u8],
+        rtcp_interceptor: &Arc<dyn RTCPReader + Send + Sync>,
+    ) -> Result<(Vec<Box<dyn rtcp::packet::Packet + Send + Sync>>, Attributes)> {
+        {
+            let mut send_called_rx = self.send_called_rx.lock().await;
+
+            tokio::select! {
+                _ = send_called_rx.recv() => {}
+                _ = self.stop_called_rx.notified() => return Err(Error::ErrClosedPipe),
+            }
+        }
+
+        let a = Attributes::new();
+        tokio::select! {
+            _ = self.stop_called_rx.notified() => {
+                Err(Error::ErrClosedPipe)
+            }
+            result = rtcp_interceptor.read(b, &a) => {
+                Ok(result?)
+            }
+        }
+    }
+}
+
+/// TrackEncoding is a single encoding of an RTPSender, a simulcast RTPSender has one
+/// encoding per RID.
+pub(crate) struct TrackEncoding {
+    pub(crate) track: Option<Arc<dyn TrackLocal + Send + Sync>>,
+    pub(crate) srtp_stream: Arc<SrtpWriterFuture>,
+    pub(crate) rtcp_interceptor: Arc<dyn RTCPReader + Send + Sync>,
+    pub(crate) stream_info: StreamInfo,
+    pub(crate) context: TrackLocalContext,
+    pub(crate) ssrc: SSRC,
+    pub(crate) max_bitrate: Option<u64>,
+    pub(crate) scale_resolution_down_by: Option<f64>,
+}
+
+impl TrackEncoding {
+    fn rid(&self) -> &str {
+        self.track
+            .as_ref()
+            .and_then(|t| t.rid())
+            .unwrap_or_default()
+    }
 }
 
 /// RTPSender allows an application to control how a given Track is encoded and transmitted to a remote peer
 pub struct RTCRtpSender {
-    pub(crate) track: Mutex<Option<Arc<dyn TrackLocal + Send + Sync>>>,
-
-    pub(crate) srtp_stream: Arc<SrtpWriterFuture>,
-    pub(crate) stream_info: Mutex<StreamInfo>,
+    /// The encodings of this sender, the first one is the base encoding that
+    /// always exists, simulcast adds one per RID.
+    pub(crate) track_encodings: Mutex<Vec<TrackEncoding>>,
     seq_trans: Arc<SequenceTransformer>,
 
-    pub(crate) context: Mutex<TrackLocalContext>,
-
     pub(crate) transport: Arc<RTCDtlsTransport>,
 
     pub(crate) payload_type: PayloadType,
@@ -155,36 +200,30 @@ impl RTCRtpSender {
             rtcp_interceptor: Mutex::new(None),
         });
 
+        let stream_ids = track
+            .as_ref()
+            .map(|track| vec![track.stream_id().to_string()])
+            .unwrap_or_default();
+
         let seq_trans = Arc::new(SequenceTransformer::new());
-        let srtp_stream = Arc::new(SrtpWriterFuture {
-            closed: AtomicBool::new(false),
+        let base_encoding = Self::new_track_encoding(
+            track,
             ssrc,
-            rtp_sender: Arc::downgrade(&internal),
-            rtp_transport: Arc::clone(&transport),
-            rtcp_read_stream: Mutex::new(None),
-            rtp_write_session: Mutex::new(None),
-            seq_trans: Arc::clone(&seq_trans),
-        });
-
-        let srtp_rtcp_reader = Arc::clone(&srtp_stream) as Arc<dyn RTCPReader + Send + Sync>;
-        let rtcp_interceptor = interceptor.bind_rtcp_reader(srtp_rtcp_reader).await;
+            Arc::clone(&seq_trans),
+            &transport,
+            &internal,
+            &interceptor,
+        )
+        .await;
         {
             let mut internal_rtcp_interceptor = internal.rtcp_interceptor.lock().await;
-            *internal_rtcp_interceptor = Some(rtcp_interceptor);
+            *internal_rtcp_interceptor = Some(Arc::clone(&base_encoding.rtcp_interceptor));
         }
 
-        let stream_ids = track
-            .as_ref()
-            .map(|track| vec![track.stream_id().to_string()])
-            .unwrap_or_default();
         Self {
-            track: Mutex::new(track),
-
-            srtp_stream,
-            stream_info: Mutex::new(StreamInfo::default()),
+            track_encodings: Mutex::new(vec![base_encoding]),
             seq_trans,
 
-            context: Mutex::new(TrackLocalContext::default()),
             transport,
 
             payload_type: 0,
@@ -212,6 +251,87 @@ impl RTCRtpSender {
         }
     }
 
+    async fn new_track_encoding(
+        track: Option<Arc<dyn TrackLocal + Send + Sync>>,
+        ssrc: SSRC,
+        seq_trans: Arc<SequenceTransformer>,
+        transport: &Arc<RTCDtlsTransport>,
+        internal: &Arc<RTPSenderInternal>,
+        interceptor: &Arc<dyn Interceptor + Send + Sync>,
+    ) -> TrackEncoding {
+        let srtp_stream = Arc::new(SrtpWriterFuture {
+            closed: AtomicBool::new(false),
+            ssrc,
+            rtp_sender: Arc::downgrade(internal),
+            rtp_transport: Arc::clone(transport),
+            rtcp_read_stream: Mutex::new(None),
+            rtp_write_session: Mutex::new(None),
+            seq_trans,
+        });
+
+        let srtp_rtcp_reader = Arc::clone(&srtp_stream) as Arc<dyn RTCPReader + Send + Sync>;
+        let rtcp_interceptor = interceptor.bind_rtcp_reader(srtp_rtcp_reader).await;
+
+        TrackEncoding {
+            track,
+            srtp_stream,
+            rtcp_interceptor,
+            stream_info: StreamInfo::default(),
+            context: TrackLocalContext::default(),
+            ssrc,
+            max_bitrate: None,
+            scale_resolution_down_by: None,
+        }
+    }
+
+    /// add_encoding adds an encoding to the RTPSender for simulcast. The track must
+    /// have a RID and the same id, stream id and kind as the base track of the sender,
+    /// which must have a RID too. Encodings can only be added before sending started.
+    pub async fn add_encoding(&self, track: Arc<dyn TrackLocal + Send + Sync>) -> Result<()> {
+        let rid = match track.rid() {
+            Some(rid) => rid.to_owned(),
+            None => return Err(Error::ErrRTPSenderRidNil),
+        };
+
+        if self.has_stopped().await {
+            return Err(Error::ErrRTPSenderStopped);
+        }
+
+        if self.has_sent() {
+            return Err(Error::ErrRTPSenderSendAlreadyCalled);
+        }
+
+        let mut track_encodings = self.track_encodings.lock().await;
+        let base_track = match track_encodings.first().and_then(|e| e.track.as_ref()) {
+            Some(base_track) if base_track.rid().is_some() => base_track,
+            _ => return Err(Error::ErrRTPSenderNoBaseEncoding),
+        };
+
+        if base_track.id() != track.id()
+            || base_track.stream_id() != track.stream_id()
+            || base_track.kind() != track.kind()
+        {
+            return Err(Error::ErrRTPSenderBaseEncodingMismatch);
+        }
+
+        if track_encodings.iter().any(|e| e.rid() == rid) {
+            return Err(Error::ErrRTPSenderRIDCollision);
+        }
+
+        let encoding = Self::new_track_encoding(
+            Some(track),
+            rand::random::<u32>(),
+            Arc::new(SequenceTransformer::new()),
+            &self.transport,
+            &self.internal,
+            &self.interceptor,
+        )
+        .await;
+        track_encodings.push(encoding);
+
+        Ok(())
+    }
+
     pub(crate) fn is_negotiated(&self) -> bool {
         self.negotiated.load(Ordering::SeqCst)
     }
@@ -241,13 +361,25 @@ impl RTCRtpSender {
     /// get_parameters describes the current configuration for the encoding and
     /// transmission of media on the sender's track.
     pub async fn get_parameters(&self) -> RTCRtpSendParameters {
-        let kind = {
-            let track = self.track.lock().await;
-            if let Some(t) = &*track {
-                t.kind()
-            } else {
-                RTPCodecType::default()
-            }
+        let (kind, encodings) = {
+            let track_encodings = self.track_encodings.lock().await;
+            let kind = track_encodings
+                .first()
+                .and_then(|e| e.track.as_ref())
+                .map(|t| t.kind())
+                .unwrap_or_default();
+            let encodings = track_encodings
+                .iter()
+                .map(|e| RTCRtpEncodingParameters {
+                    rid: e.rid().into(),
+                    ssrc: e.ssrc,
+                    payload_type: self.payload_type,
+                    max_bitrate: e.max_bitrate,
+                    scale_resolution_down_by: e.scale_resolution_down_by,
+                    ..Default::default()
+                })
+                .collect();
+            (kind, encodings)
         };
 
         let mut send_parameters = {
@@ -255,11 +387,7 @@ impl RTCRtpSender {
                 rtp_parameters: self
                     .media_engine
                     .get_rtp_parameters_by_kind(kind, RTCRtpTransceiverDirection::Sendonly),
-                encodings: vec![RTCRtpEncodingParameters {
-                    ssrc: self.ssrc,
-                    payload_type: self.payload_type,
-                    ..Default::default()
-                }],
+                encodings,
             }
         };
 
@@ -280,10 +408,36 @@ impl RTCRtpSender {
         send_parameters
     }
 
+    /// set_parameters updates the configuration for the encodings of media on the
+    /// sender's tracks without requiring renegotiation. The parameters should be
+    /// obtained from get_parameters; changing the number of encodings or any of
+    /// their read-only fields (rid, ssrc) results in an error.
+    pub async fn set_parameters(&self, parameters: RTCRtpSendParameters) -> Result<()> {
+        let mut track_encodings = self.track_encodings.lock().await;
+        if parameters.encodings.len() != track_encodings.len() {
+            return Err(Error::ErrRTPSenderInvalidModification);
+        }
+        for (encoding, track_encoding) in parameters.encodings.iter().zip(track_encodings.iter()) {
+            if encoding.ssrc != track_encoding.ssrc || encoding.rid.as_str() != track_encoding.rid()
+            {
+                return Err(Error::ErrRTPSenderInvalidModification);
+            }
+        }
+
+        for (encoding, track_encoding) in
+            parameters.encodings.iter().zip(track_encodings.iter_mut())
+        {
+            track_encoding.max_bitrate = encoding.max_bitrate;
+            track_encoding.scale_resolution_down_by = encoding.scale_resolution_down_by;
+        }
+
+        Ok(())
+    }
+
     /// track returns the RTCRtpTransceiver track, or nil
     pub async fn track(&self) -> Option<Arc<dyn TrackLocal + Send + Sync>> {
-        let track = self.track.lock().await;
-        track.clone()
+        let track_encodings = self.track_encodings.lock().await;
+        track_encodings.first().and_then(|e| e.track.clone())
     }
 
     /// replace_track replaces the track currently being used as the sender's source with a new TrackLocal.
@@ -308,27 +462,29 @@ impl RTCRtpSender {
             }
         }
 
+        let mut track_encodings = self.track_encodings.lock().await;
+        if track.is_some() && track_encodings.len() > 1 {
+            // A simulcast sender can't have its tracks replaced by a single track
+            return Err(Error::ErrRTPSenderNewTrackHasIncorrectEnvelope);
+        }
+
         if self.has_sent() {
-            let t = {
-                let t = self.track.lock().await;
-                t.clone()
-            };
-            if let Some(t) = t {
-                let context = self.context.lock().await;
-                t.unbind(&context).await?;
+            for encoding in track_encodings.iter() {
+                if let Some(t) = &encoding.track {
+                    t.unbind(&encoding.context).await?;
+                }
             }
         }
 
         if !self.has_sent() || track.is_none() {
-            let mut t = self.track.lock().await;
-            *t = track;
+            for encoding in track_encodings.iter_mut() {
+                encoding.track = track.clone();
+            }
             return Ok(());
         }
 
-        let context = {
-            let context = self.context.lock().await;
-            context.clone()
-        };
+        let encoding = &mut track_encodings[0];
+        let context = encoding.context.clone();
 
         let result = if let Some(t) = &track {
             self.seq_trans.reset_offset();
@@ -351,8 +507,7 @@ impl RTCRtpSender {
         match result {
             Err(err) => {
                 // Re-bind the original track
-                let track = self.track.lock().await;
-                if let Some(t) = &*track {
+                if let Some(t) = &encoding.track {
                     t.bind(&context).await?;
                 }
 
@@ -361,14 +516,10 @@ impl RTCRtpSender {
             Ok(codec) => {
                 // Codec has changed
                 if self.payload_type != codec.payload_type {
-                    let mut context = self.context.lock().await;
-                    context.params.codecs = vec![codec];
+                    encoding.context.params.codecs = vec![codec];
                 }
 
-                {
-                    let mut t = self.track.lock().await;
-                    *t = track;
-                }
+                encoding.track = track;
 
                 Ok(())
             }
@@ -381,27 +532,33 @@ impl RTCRtpSender {
             return Err(Error::ErrRTPSenderSendAlreadyCalled);
         }
 
-        let write_stream = Arc::new(InterceptorToTrackLocalWriter::new(self.paused.clone()));
-        let (context, stream_info) = {
-            let track = self.track.lock().await;
+        let mut track_encodings = self.track_encodings.lock().await;
+        if parameters.encodings.len() != track_encodings.len() {
+            return Err(Error::ErrRTPSenderInvalidModification);
+        }
+
+        for (encoding, parameters_encoding) in
+            track_encodings.iter_mut().zip(parameters.encodings.iter())
+        {
+            let write_stream = Arc::new(InterceptorToTrackLocalWriter::new(self.paused.clone()));
             let mut context = TrackLocalContext {
                 id: self.id.clone(),
                 params: self.media_engine.get_rtp_parameters_by_kind(
-                    if let Some(t) = &*track {
+                    if let Some(t) = &encoding.track {
                         t.kind()
                     } else {
                         RTPCodecType::default()
                     },
                     RTCRtpTransceiverDirection::Sendonly,
                 ),
-                ssrc: parameters.encodings[0].ssrc,
+                ssrc: parameters_encoding.ssrc,
                 write_stream: Some(
                     Arc::clone(&write_stream) as Arc<dyn TrackLocalWriter + Send + Sync>
                 ),
                 paused: self.paused.clone(),
             };
 
-            let codec = if let Some(t) = &*track {
+            let codec = if let Some(t) = &encoding.track {
                 t.bind(&context).await?
             } else {
                 RTCRtpCodecParameters::default()
@@ -411,32 +568,25 @@ impl RTCRtpSender {
             context.params.codecs = vec![codec];
             let stream_info = create_stream_info(
                 self.id.clone(),
-                parameters.encodings[0].ssrc,
+                parameters_encoding.ssrc,
                 payload_type,
                 capability,
                 &parameters.rtp_parameters.header_extensions,
             );
 
-            (context, stream_info)
-        };
-
-        let srtp_rtp_writer = Arc::clone(&self.srtp_stream) as Arc<dyn RTPWriter + Send + Sync>;
-        let rtp_interceptor = self
-            .interceptor
-            .bind_local_stream(&stream_info, srtp_rtp_writer)
-            .await;
-        {
-            let mut interceptor_rtp_writer = write_stream.interceptor_rtp_writer.lock().await;
-            *interceptor_rtp_writer = Some(rtp_interceptor);
-        }
+            let srtp_rtp_writer =
+                Arc::clone(&encoding.srtp_stream) as Arc<dyn RTPWriter + Send + Sync>;
+            let rtp_interceptor = self
+                .interceptor
+                .bind_local_stream(&stream_info, srtp_rtp_writer)
+                .await;
+            {
+                let mut interceptor_rtp_writer = write_stream.interceptor_rtp_writer.lock().await;
+                *interceptor_rtp_writer = Some(rtp_interceptor);
+            }
 
-        {
-            let mut ctx = self.context.lock().await;
-            *ctx = context;
-        }
-        {
-            let mut si = self.stream_info.lock().await;
-            *si = stream_info;
+            encoding.context = context;
+            encoding.stream_info = stream_info;
         }
 
         {
@@ -461,12 +611,19 @@ impl RTCRtpSender {
 
         self.replace_track(None).await?;
 
-        {
-            let stream_info = self.stream_info.lock().await;
-            self.interceptor.unbind_local_stream(&stream_info).await;
+        let mut errs = vec![];
+        let track_encodings = self.track_encodings.lock().await;
+        for encoding in track_encodings.iter() {
+            self.interceptor
+                .unbind_local_stream(&encoding.stream_info)
+                .await;
+
+            if let Err(err) = encoding.srtp_stream.close().await {
+                errs.push(err);
+            }
         }
 
-        self.srtp_stream.close().await
+        flatten_errs(errs)
     }
 
     /// read reads incoming RTCP for this RTPReceiver
@@ -484,6 +641,32 @@ impl RTCRtpSender {
         self.internal.read_rtcp(self.receive_mtu).await
     }
 
+    /// read_simulcast reads incoming RTCP for the encoding of this RTPSender with the given rid
+    pub async fn read_simulcast(
+        &self,
+        b: &mut [u8],
+        rid: &str,
+    ) -> Result<(Vec<Box<dyn rtcp::packet::Packet + Send + Sync>>, Attributes)> {
+        let rtcp_interceptor = {
+            let track_encodings = self.track_encodings.lock().await;
+            match track_encodings.iter().find(|e| e.rid() == rid) {
+                Some(encoding) => Arc::clone(&encoding.rtcp_interceptor),
+                None => return Err(Error::ErrRTPSenderNoTrackForRID),
+            }
+        };
+
+        self.internal.read_simulcast(b, &rtcp_interceptor).await
+    }
+
+    /// read_simulcast_rtcp is a convenience method that wraps read_simulcast and unmarshal for you
+    pub async fn read_simulcast_rtcp(
+        &self,
+        rid: &str,
+    ) -> Result<(Vec<Box<dyn rtcp::packet::Packet + Send + Sync>>, Attributes)> {
+        let mut b = vec![0u8; self.receive_mtu];
+        self.read_simulcast(&mut b, rid).await
+    }
+
     /// Enables overriding outgoing `RTP` packets' `sequence number`s.
     ///
     /// Must be called once before any data sent or never called at all.
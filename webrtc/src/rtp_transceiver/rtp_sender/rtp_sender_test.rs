@@ -144,6 +144,55 @@ async fn test_rtp_sender_get_parameters() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_rtp_sender_set_parameters() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+
+    let (mut offerer, mut answerer) = new_pair(&api).await?;
+
+    let rtp_transceiver = offerer
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+
+    signal_pair(&mut offerer, &mut answerer).await?;
+
+    let sender = rtp_transceiver.sender().await;
+    let mut parameters = sender.get_parameters().await;
+    assert_eq!(None, parameters.encodings[0].max_bitrate);
+
+    parameters.encodings[0].max_bitrate = Some(500_000);
+    sender.set_parameters(parameters).await?;
+
+    let mut parameters = sender.get_parameters().await;
+    assert_eq!(Some(500_000), parameters.encodings[0].max_bitrate);
+
+    parameters.encodings[0].ssrc = sender.ssrc.wrapping_add(1);
+    let result = sender.set_parameters(parameters).await;
+    assert!(
+        matches!(result, Err(Error::ErrRTPSenderInvalidModification)),
+        "changing the ssrc must be rejected"
+    );
+
+    let mut parameters = sender.get_parameters().await;
+    parameters
+        .encodings
+        .push(RTCRtpEncodingParameters::default());
+    let result = sender.set_parameters(parameters).await;
+    assert!(
+        matches!(result, Err(Error::ErrRTPSenderInvalidModification)),
+        "changing the number of encodings must be rejected"
+    );
+    assert_eq!(
+        Some(500_000),
+        sender.get_parameters().await.encodings[0].max_bitrate
+    );
+
+    close_pair_now(&offerer, &answerer).await;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_rtp_sender_set_read_deadline() -> Result<()> {
     let (mut sender, mut receiver, wan) = create_vnet_pair().await?;
@@ -333,3 +382,97 @@ async fn test_rtp_sender_replace_track_invalid_codec_change() -> Result<()> {
     close_pair_now(&sender, &receiver).await;
     Ok(())
 }
+
+fn new_vp8_track_with_rid(id: &str, rid: &str) -> Arc<dyn TrackLocal + Send + Sync> {
+    Arc::new(TrackLocalStaticSample::new_with_rid(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        id.to_owned(),
+        rid.to_owned(),
+        "webrtc-rs".to_owned(),
+    ))
+}
+
+#[tokio::test]
+async fn test_rtp_sender_add_encoding() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+
+    let (offerer, answerer) = new_pair(&api).await?;
+
+    let track_without_rid = Arc::new(TrackLocalStaticSample::new(
+        RTCRtpCodecCapability {
+            mime_type: MIME_TYPE_VP8.to_owned(),
+            ..Default::default()
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ));
+
+    let rtp_sender = offerer
+        .add_track(Arc::clone(&track_without_rid) as Arc<dyn TrackLocal + Send + Sync>)
+        .await?;
+    assert_eq!(
+        rtp_sender
+            .add_encoding(new_vp8_track_with_rid("video", "h"))
+            .await,
+        Err(Error::ErrRTPSenderNoBaseEncoding)
+    );
+
+    let rtp_sender = offerer
+        .add_track(new_vp8_track_with_rid("simulcast", "q"))
+        .await?;
+    assert_eq!(
+        rtp_sender.add_encoding(track_without_rid).await,
+        Err(Error::ErrRTPSenderRidNil)
+    );
+    assert_eq!(
+        rtp_sender
+            .add_encoding(new_vp8_track_with_rid("other", "h"))
+            .await,
+        Err(Error::ErrRTPSenderBaseEncodingMismatch)
+    );
+    assert_eq!(
+        rtp_sender
+            .add_encoding(new_vp8_track_with_rid("simulcast", "q"))
+            .await,
+        Err(Error::ErrRTPSenderRIDCollision)
+    );
+
+    rtp_sender
+        .add_encoding(new_vp8_track_with_rid("simulcast", "h"))
+        .await?;
+
+    let parameters = rtp_sender.get_parameters().await;
+    assert_eq!(2, parameters.encodings.len());
+    assert_eq!("q", parameters.encodings[0].rid);
+    assert_eq!(rtp_sender.ssrc, parameters.encodings[0].ssrc);
+    assert_eq!("h", parameters.encodings[1].rid);
+    assert_ne!(parameters.encodings[0].ssrc, parameters.encodings[1].ssrc);
+
+    assert_eq!(
+        rtp_sender
+            .replace_track(Some(new_vp8_track_with_rid("simulcast", "q")))
+            .await,
+        Err(Error::ErrRTPSenderNewTrackHasIncorrectEnvelope)
+    );
+
+    let offer = offerer.create_offer(None).await?;
+    assert!(offer.sdp.contains("a=rid:q send"));
+    assert!(offer.sdp.contains("a=rid:h send"));
+    assert!(offer.sdp.contains("a=simulcast:send q;h"));
+
+    rtp_sender.stop().await?;
+    assert_eq!(
+        rtp_sender
+            .add_encoding(new_vp8_track_with_rid("simulcast", "f"))
+            .await,
+        Err(Error::ErrRTPSenderStopped)
+    );
+
+    close_pair_now(&offerer, &answerer).await;
+    Ok(())
+}
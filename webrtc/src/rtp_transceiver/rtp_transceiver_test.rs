@@ -1,7 +1,8 @@
+use bytes::Bytes;
 use portable_atomic::AtomicUsize;
 
 use super::*;
-use crate::api::media_engine::{MIME_TYPE_OPUS, MIME_TYPE_VP8, MIME_TYPE_VP9};
+use crate::api::media_engine::{MIME_TYPE_H264, MIME_TYPE_OPUS, MIME_TYPE_VP8, MIME_TYPE_VP9};
 use crate::api::APIBuilder;
 use crate::dtls_transport::RTCDtlsTransport;
 use crate::peer_connection::configuration::RTCConfiguration;
@@ -212,6 +213,90 @@ async fn test_rtp_transceiver_set_codec_preferences_payload_type() -> Result<()>
     Ok(())
 }
 
+// Assert that the codecs are offered and answered in the order of the preferences
+#[tokio::test]
+async fn test_rtp_transceiver_set_codec_preferences_order() -> Result<()> {
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let offer_pc = api.new_peer_connection(RTCConfiguration::default()).await?;
+
+    let mut m = MediaEngine::default();
+    m.register_default_codecs()?;
+    let api = APIBuilder::new().with_media_engine(m).build();
+    let answer_pc = api.new_peer_connection(RTCConfiguration::default()).await?;
+
+    let offer_transceiver = offer_pc
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+    let answer_transceiver = answer_pc
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+
+    // Only the mime types are given, the rest comes from the MediaEngine
+    offer_transceiver
+        .set_codec_preferences(vec![
+            RTCRtpCodecParameters {
+                capability: RTCRtpCodecCapability {
+                    mime_type: MIME_TYPE_H264.to_string(),
+                    clock_rate: 90000,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            RTCRtpCodecParameters {
+                capability: RTCRtpCodecCapability {
+                    mime_type: MIME_TYPE_VP8.to_string(),
+                    clock_rate: 90000,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ])
+        .await?;
+
+    let offer = offer_pc.create_offer(None).await?;
+    assert!(
+        offer.sdp.contains("m=video 9 UDP/TLS/RTP/SAVPF 102 96\r\n"),
+        "{}",
+        offer.sdp
+    );
+    assert!(
+        offer.sdp.contains(
+            "a=fmtp:102 level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42001f"
+        ),
+        "{}",
+        offer.sdp
+    );
+    assert!(
+        offer.sdp.contains("a=rtcp-fb:102 nack pli"),
+        "{}",
+        offer.sdp
+    );
+
+    offer_pc.set_local_description(offer.clone()).await?;
+    answer_pc.set_remote_description(offer).await?;
+
+    let answer = answer_pc.create_answer(None).await?;
+    assert!(
+        answer
+            .sdp
+            .contains("m=video 9 UDP/TLS/RTP/SAVPF 102 96\r\n"),
+        "{}",
+        answer.sdp
+    );
+    assert_eq!(
+        answer_transceiver.get_codecs().await[0]
+            .capability
+            .mime_type,
+        MIME_TYPE_H264
+    );
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_rtp_transceiver_direction_change() -> Result<()> {
     let (offer_pc, answer_pc, _) = create_vnet_pair().await?;
@@ -311,6 +396,88 @@ async fn test_rtp_transceiver_set_direction_causing_negotiation() -> Result<()>
     Ok(())
 }
 
+#[tokio::test]
+async fn test_rtp_transceiver_negotiation_needed_after_stopped_transceiver() -> Result<()> {
+    let (offer_pc, answer_pc, _) = create_vnet_pair().await?;
+
+    let count = Arc::new(AtomicUsize::new(0));
+
+    {
+        let count = count.clone();
+        offer_pc.on_negotiation_needed(Box::new(move || {
+            let count = count.clone();
+            Box::pin(async move {
+                count.fetch_add(1, Ordering::SeqCst);
+            })
+        }));
+    }
+
+    let stopped_transceiver = offer_pc
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+    stopped_transceiver.stop().await?;
+
+    let offer_transceiver = offer_pc
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+
+    // Renegotiate once so that the transports of the initial negotiation
+    // don't hold up the operations queue.
+    for _ in 0..2 {
+        let offer = offer_pc.create_offer(None).await?;
+        offer_pc.set_local_description(offer.clone()).await?;
+        answer_pc.set_remote_description(offer).await?;
+
+        let answer = answer_pc.create_answer(None).await?;
+        answer_pc.set_local_description(answer.clone()).await?;
+        offer_pc.set_remote_description(answer).await?;
+    }
+
+    let before = count.load(Ordering::SeqCst);
+
+    // The stopped transceiver comes first, it must not hide the change of the
+    // transceiver that follows it.
+    offer_transceiver
+        .set_direction(RTCRtpTransceiverDirection::Inactive)
+        .await;
+
+    offer_pc.internal.ops.done().await;
+
+    assert_eq!(count.load(Ordering::SeqCst), before + 1);
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rtp_transceiver_set_direction_after_stop() -> Result<()> {
+    let (offer_pc, answer_pc, _) = create_vnet_pair().await?;
+
+    let offer_transceiver = offer_pc
+        .add_transceiver_from_kind(RTPCodecType::Video, None)
+        .await?;
+
+    offer_transceiver.stop().await?;
+    assert_eq!(
+        offer_transceiver.direction(),
+        RTCRtpTransceiverDirection::Inactive
+    );
+
+    offer_transceiver
+        .set_direction(RTCRtpTransceiverDirection::Sendrecv)
+        .await;
+    assert_eq!(
+        offer_transceiver.direction(),
+        RTCRtpTransceiverDirection::Inactive,
+        "a stopped transceiver must not change direction"
+    );
+
+    close_pair_now(&offer_pc, &answer_pc).await;
+
+    Ok(())
+}
+
 #[ignore]
 #[tokio::test]
 async fn test_rtp_transceiver_stopping() -> Result<()> {
@@ -354,3 +521,27 @@ async fn test_rtp_transceiver_stopping() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_handle_unknown_rtp_header() -> Result<()> {
+    let mut header = rtp::header::Header {
+        version: 2,
+        payload_type: 96,
+        ..Default::default()
+    };
+    assert_eq!(
+        handle_unknown_rtp_header(&header, 1, 2, 3)?,
+        (String::new(), String::new(), String::new(), 0)
+    );
+
+    header.set_extension(1, Bytes::from_static(b"0"))?;
+    header.set_extension(2, Bytes::from_static(b"hi"))?;
+
+    let (mid, rid, rsid, payload_type) = handle_unknown_rtp_header(&header, 1, 2, 3)?;
+    assert_eq!(mid, "0");
+    assert_eq!(rid, "hi");
+    assert_eq!(rsid, "");
+    assert_eq!(payload_type, 96);
+
+    Ok(())
+}
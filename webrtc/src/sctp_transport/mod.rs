@@ -9,17 +9,22 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
 use arc_swap::ArcSwapOption;
+use bytes::Bytes;
 use data::data_channel::DataChannel;
 use data::message::message_channel_open::ChannelType;
 use portable_atomic::{AtomicBool, AtomicU32, AtomicU8};
 use sctp::association::Association;
+use sctp::chunk::chunk_payload_data::PayloadProtocolIdentifier;
+use sctp::stream::Stream;
 use sctp_transport_state::RTCSctpTransportState;
 use tokio::sync::{Mutex, Notify};
 use util::Conn;
 
 use crate::api::setting_engine::SettingEngine;
+use crate::data_channel::data_channel_close_cause::RTCDataChannelCloseCause;
 use crate::data_channel::data_channel_parameters::DataChannelParameters;
 use crate::data_channel::data_channel_state::RTCDataChannelState;
 use crate::data_channel::RTCDataChannel;
@@ -33,6 +38,15 @@ use crate::stats::{ICETransportStats, PeerConnectionStats};
 
 const SCTP_MAX_CHANNELS: u16 = u16::MAX;
 
+/// Largest message we accept from the remote peer unless configured otherwise
+pub(crate) const SCTP_MAX_MESSAGE_SIZE: u32 = 262144;
+
+/// Largest message we may send when the remote peer doesn't announce 'a=max-message-size'
+pub(crate) const SCTP_MAX_MESSAGE_SIZE_UNSET: u32 = 65536;
+
+/// How long closing the association waits for queued data unless configured otherwise
+const SCTP_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub type OnDataChannelHdlrFn = Box<
     dyn (FnMut(Arc<RTCDataChannel>) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>)
         + Send
@@ -45,10 +59,19 @@ pub type OnDataChannelOpenedHdlrFn = Box<
         + Sync,
 >;
 
+/// UnclaimedStream is a stream the remote started sending on without a DATA_CHANNEL_OPEN,
+/// along with the message read off it while looking for one.
+pub(crate) struct UnclaimedStream {
+    pub(crate) stream: Arc<Stream>,
+    pub(crate) ppi: PayloadProtocolIdentifier,
+    pub(crate) data: Bytes,
+}
+
 struct AcceptDataChannelParams {
     notify_rx: Arc<Notify>,
     sctp_association: Arc<Association>,
     data_channels: Arc<Mutex<Vec<Arc<RTCDataChannel>>>>,
+    unclaimed_streams: Arc<Mutex<HashMap<u16, UnclaimedStream>>>,
     on_error_handler: Arc<ArcSwapOption<Mutex<OnErrorHdlrFn>>>,
     on_data_channel_handler: Arc<ArcSwapOption<Mutex<OnDataChannelHdlrFn>>>,
     on_data_channel_opened_handler: Arc<ArcSwapOption<Mutex<OnDataChannelOpenedHdlrFn>>>,
@@ -70,8 +93,8 @@ pub struct RTCSctpTransport {
     is_started: AtomicBool,
 
     // max_message_size represents the maximum size of data that can be passed to
-    // DataChannel's send() method.
-    max_message_size: usize,
+    // DataChannel's send() method, as negotiated with the remote peer.
+    max_message_size: AtomicU32,
 
     // max_channels represents the maximum amount of DataChannel's that can
     // be used simultaneously.
@@ -89,6 +112,10 @@ pub struct RTCSctpTransport {
     pub(crate) data_channels_requested: Arc<AtomicU32>,
     data_channels_accepted: Arc<AtomicU32>,
 
+    // Streams the remote started sending on without a DATA_CHANNEL_OPEN. They
+    // belong to pre-negotiated channels that haven't been created locally yet.
+    unclaimed_streams: Arc<Mutex<HashMap<u16, UnclaimedStream>>>,
+
     notify_tx: Arc<Notify>,
 
     setting_engine: Arc<SettingEngine>,
@@ -103,7 +130,7 @@ impl RTCSctpTransport {
             dtls_transport,
             state: AtomicU8::new(RTCSctpTransportState::Connecting as u8),
             is_started: AtomicBool::new(false),
-            max_message_size: RTCSctpTransport::calc_message_size(65536, 65536),
+            max_message_size: AtomicU32::new(SCTP_MAX_MESSAGE_SIZE_UNSET),
             max_channels: SCTP_MAX_CHANNELS,
             sctp_association: Mutex::new(None),
             on_error_handler: Arc::new(ArcSwapOption::empty()),
@@ -115,6 +142,8 @@ impl RTCSctpTransport {
             data_channels_requested: Arc::new(AtomicU32::new(0)),
             data_channels_accepted: Arc::new(AtomicU32::new(0)),
 
+            unclaimed_streams: Arc::new(Mutex::new(HashMap::new())),
+
             notify_tx: Arc::new(Notify::new()),
 
             setting_engine,
@@ -129,19 +158,29 @@ impl RTCSctpTransport {
     /// get_capabilities returns the SCTPCapabilities of the SCTPTransport.
     pub fn get_capabilities(&self) -> SCTPTransportCapabilities {
         SCTPTransportCapabilities {
-            max_message_size: 0,
+            max_message_size: self.setting_engine.get_sctp_max_message_size(),
         }
     }
 
     /// Start the SCTPTransport. Since both local and remote parties must mutually
     /// create an SCTPTransport, SCTP SO (Simultaneous Open) is used to establish
     /// a connection over SCTP.
-    pub async fn start(&self, _remote_caps: SCTPTransportCapabilities) -> Result<()> {
+    pub async fn start(&self, remote_caps: SCTPTransportCapabilities) -> Result<()> {
         if self.is_started.load(Ordering::SeqCst) {
             return Ok(());
         }
         self.is_started.store(true, Ordering::SeqCst);
 
+        // A max-message-size of 0 means the remote peer accepts messages of any size,
+        // <https://datatracker.ietf.org/doc/html/rfc8841#section-6>
+        let max_message_size = if remote_caps.max_message_size == 0 {
+            u32::MAX
+        } else {
+            remote_caps.max_message_size
+        };
+        self.max_message_size
+            .store(max_message_size, Ordering::SeqCst);
+
         let dtls_transport = self.transport();
         if let Some(net_conn) = &dtls_transport.conn().await {
             let sctp_association = loop {
@@ -155,10 +194,19 @@ impl RTCSctpTransport {
                         }
                     },
                     association = sctp::association::Association::client(sctp::association::Config {
-                        net_conn: Arc::clone(net_conn) as Arc<dyn Conn + Send + Sync>,
-                        max_receive_buffer_size: 0,
-                        max_message_size: 0,
-                        name: String::new(),
+                        max_receive_buffer_size: self.setting_engine.sctp_max_receive_buffer_size,
+                        max_message_size,
+                        max_send_buffer_size: self.setting_engine.sctp_max_send_buffer_size,
+                        enable_zero_checksum: self.setting_engine.enable_sctp_zero_checksum,
+                        enable_message_interleaving: self
+                            .setting_engine
+                            .enable_sctp_message_interleaving,
+                        shutdown_timeout: self
+                            .setting_engine
+                            .timeout
+                            .sctp_shutdown_timeout
+                            .unwrap_or(SCTP_SHUTDOWN_TIMEOUT),
+                        ..sctp::association::Config::new(Arc::clone(net_conn) as Arc<dyn Conn + Send + Sync>)
                     }) => {
                         break Arc::new(association?);
                     }
@@ -176,6 +224,7 @@ impl RTCSctpTransport {
                 notify_rx: self.notify_tx.clone(),
                 sctp_association,
                 data_channels: Arc::clone(&self.data_channels),
+                unclaimed_streams: Arc::clone(&self.unclaimed_streams),
                 on_error_handler: Arc::clone(&self.on_error_handler),
                 on_data_channel_handler: Arc::clone(&self.on_data_channel_handler),
                 on_data_channel_opened_handler: Arc::clone(&self.on_data_channel_opened_handler),
@@ -195,6 +244,13 @@ impl RTCSctpTransport {
 
     /// Stop stops the SCTPTransport
     pub async fn stop(&self) -> Result<()> {
+        {
+            let data_channels = self.data_channels.lock().await;
+            for dc in &*data_channels {
+                dc.set_close_cause(RTCDataChannelCloseCause::TransportClosed);
+            }
+        }
+
         {
             let mut sctp_association = self.sctp_association.lock().await;
             if let Some(sa) = sctp_association.take() {
@@ -211,41 +267,73 @@ impl RTCSctpTransport {
     }
 
     async fn accept_data_channels(param: AcceptDataChannelParams) {
-        let dcs = param.data_channels.lock().await;
-        let mut existing_data_channels = Vec::new();
-        for dc in dcs.iter() {
-            if let Some(dc) = dc.data_channel.lock().await.clone() {
-                existing_data_channels.push(dc);
+        loop {
+            // Channels opened since the last stream was accepted are matched too
+            let mut existing_data_channels = Vec::new();
+            {
+                let dcs = param.data_channels.lock().await;
+                for dc in dcs.iter() {
+                    if let Some(dc) = dc.data_channel.lock().await.clone() {
+                        existing_data_channels.push(dc);
+                    }
+                }
             }
-        }
-        drop(dcs);
 
-        loop {
-            let dc = tokio::select! {
+            let accepted = tokio::select! {
                 _ = param.notify_rx.notified() => break,
-                result = DataChannel::accept(
-                    &param.sctp_association,
-                    data::data_channel::Config::default(),
-                    &existing_data_channels,
-                ) => {
-                    match result {
-                        Ok(dc) => dc,
-                        Err(err) => {
-                            if data::Error::ErrStreamClosed == err {
-                                log::error!("Failed to accept data channel: {}", err);
-                                if let Some(handler) = &*param.on_error_handler.load() {
-                                    let mut f = handler.lock().await;
-                                    f(err.into()).await;
-                                }
-                            }
-                            break;
-                        }
+                accepted = async {
+                    let stream = param.sctp_association.accept_stream().await?;
+                    let result = DataChannel::accept_stream(
+                        Arc::clone(&stream),
+                        data::data_channel::Config::default(),
+                        &existing_data_channels,
+                    )
+                    .await;
+                    Some((stream, result))
+                } => accepted,
+            };
+
+            let (stream, result) = match accepted {
+                Some(accepted) => accepted,
+                None => {
+                    let err = data::Error::ErrStreamClosed;
+                    log::error!("Failed to accept data channel: {}", err);
+                    if let Some(handler) = &*param.on_error_handler.load() {
+                        let mut f = handler.lock().await;
+                        f(err.into()).await;
                     }
+                    break;
                 }
             };
 
-            let mut max_retransmits = 0;
-            let mut max_packet_lifetime = 0;
+            let dc = match result {
+                Ok(dc) => dc,
+                Err(data::Error::UnexpectedUserMessage { ppi, data }) => {
+                    // The remote is sending on a pre-negotiated channel that hasn't been
+                    // created here yet. Keep the stream and its first message for when it is.
+                    log::debug!(
+                        "stream {} opened without DATA_CHANNEL_OPEN, keeping it for a negotiated data channel",
+                        stream.stream_identifier()
+                    );
+                    let mut unclaimed_streams = param.unclaimed_streams.lock().await;
+                    unclaimed_streams.insert(
+                        stream.stream_identifier(),
+                        UnclaimedStream { stream, ppi, data },
+                    );
+                    continue;
+                }
+                Err(err) => {
+                    log::warn!(
+                        "Failed to accept data channel on stream {}: {}",
+                        stream.stream_identifier(),
+                        err
+                    );
+                    continue;
+                }
+            };
+
+            let mut max_retransmits = None;
+            let mut max_packet_lifetime = None;
             let val = dc.config.reliability_parameter as u16;
             let ordered;
 
@@ -258,19 +346,19 @@ impl RTCSctpTransport {
                 }
                 ChannelType::PartialReliableRexmit => {
                     ordered = true;
-                    max_retransmits = val;
+                    max_retransmits = Some(val);
                 }
                 ChannelType::PartialReliableRexmitUnordered => {
                     ordered = false;
-                    max_retransmits = val;
+                    max_retransmits = Some(val);
                 }
                 ChannelType::PartialReliableTimed => {
                     ordered = true;
-                    max_packet_lifetime = val;
+                    max_packet_lifetime = Some(val);
                 }
                 ChannelType::PartialReliableTimedUnordered => {
                     ordered = false;
-                    max_packet_lifetime = val;
+                    max_packet_lifetime = Some(val);
                 }
             };
 
@@ -331,15 +419,15 @@ impl RTCSctpTransport {
             .store(Some(Arc::new(Mutex::new(f))));
     }
 
-    fn calc_message_size(remote_max_message_size: usize, can_send_size: usize) -> usize {
-        if remote_max_message_size == 0 && can_send_size == 0 {
-            usize::MAX
-        } else if remote_max_message_size == 0 {
-            can_send_size
-        } else if can_send_size == 0 || can_send_size > remote_max_message_size {
-            remote_max_message_size
+    /// max_message_size is the largest message that can be passed to
+    /// RTCDataChannel's send(), as announced by the remote peer.
+    /// It is `u32::MAX` if the remote peer doesn't limit the message size.
+    pub fn max_message_size(&self) -> u32 {
+        let max_message_size = self.max_message_size.load(Ordering::SeqCst);
+        if max_message_size == 0 {
+            SCTP_MAX_MESSAGE_SIZE_UNSET
         } else {
-            can_send_size
+            max_message_size
         }
     }
 
@@ -428,6 +516,13 @@ impl RTCSctpTransport {
         sctp_association.clone()
     }
 
+    /// take_unclaimed_stream returns the stream the remote already opened for the
+    /// pre-negotiated data channel with the given id, if any.
+    pub(crate) async fn take_unclaimed_stream(&self, id: u16) -> Option<UnclaimedStream> {
+        let mut unclaimed_streams = self.unclaimed_streams.lock().await;
+        unclaimed_streams.remove(&id)
+    }
+
     pub(crate) fn data_channels_accepted(&self) -> u32 {
         self.data_channels_accepted.load(Ordering::SeqCst)
     }
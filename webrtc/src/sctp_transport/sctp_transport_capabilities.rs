@@ -3,5 +3,7 @@ use serde::{Deserialize, Serialize};
 /// SCTPTransportCapabilities indicates the capabilities of the SCTPTransport.
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
 pub struct SCTPTransportCapabilities {
+    /// The largest message the endpoint accepts, as in 'a=max-message-size'.
+    /// 0 means there is no limit.
     pub max_message_size: u32,
 }
@@ -41,3 +41,45 @@ async fn test_generate_data_channel_id() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_sctp_transport_max_message_size() -> Result<()> {
+    let mut setting_engine = SettingEngine::default();
+    let sctp_transport = RTCSctpTransport::default();
+    assert_eq!(
+        sctp_transport.get_capabilities().max_message_size,
+        SCTP_MAX_MESSAGE_SIZE
+    );
+    assert_eq!(
+        sctp_transport.max_message_size(),
+        SCTP_MAX_MESSAGE_SIZE_UNSET
+    );
+
+    setting_engine.set_sctp_max_message_size(1024);
+    let sctp_transport = RTCSctpTransport {
+        setting_engine: Arc::new(setting_engine),
+        ..Default::default()
+    };
+    assert_eq!(sctp_transport.get_capabilities().max_message_size, 1024);
+
+    // The remote limit is applied even though there is no DTLS connection to start on
+    let result = sctp_transport
+        .start(SCTPTransportCapabilities {
+            max_message_size: 4096,
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(sctp_transport.max_message_size(), 4096);
+
+    // 0 means the remote peer doesn't limit the message size
+    let sctp_transport = RTCSctpTransport::default();
+    let result = sctp_transport
+        .start(SCTPTransportCapabilities {
+            max_message_size: 0,
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(sctp_transport.max_message_size(), u32::MAX);
+
+    Ok(())
+}
@@ -19,6 +19,9 @@ use crate::rtp_transceiver::rtp_codec::RTCRtpCodecParameters;
 use crate::rtp_transceiver::{PayloadType, SSRC};
 use crate::sctp_transport::RTCSctpTransport;
 
+#[cfg(test)]
+mod stats_test;
+
 mod serialize;
 pub mod stats_collector;
 
@@ -143,9 +146,6 @@ impl<'de> Deserialize<'de> for StatsReportType {
                 let stats = serde_json::from_value(value).map_err(serde::de::Error::custom)?;
                 Ok(StatsReportType::Codec(stats))
             }
-            RTCStatsType::CSRC => {
-                todo!()
-            }
             RTCStatsType::DataChannel => {
                 let stats = serde_json::from_value(value).map_err(serde::de::Error::custom)?;
                 Ok(StatsReportType::DataChannel(stats))
@@ -166,9 +166,6 @@ impl<'de> Deserialize<'de> for StatsReportType {
                 let stats = serde_json::from_value(value).map_err(serde::de::Error::custom)?;
                 Ok(StatsReportType::PeerConnection(stats))
             }
-            RTCStatsType::Receiver => {
-                todo!()
-            }
             RTCStatsType::RemoteCandidate => {
                 let stats = serde_json::from_value(value).map_err(serde::de::Error::custom)?;
                 Ok(StatsReportType::RemoteCandidate(stats))
@@ -181,13 +178,13 @@ impl<'de> Deserialize<'de> for StatsReportType {
                 let stats = serde_json::from_value(value).map_err(serde::de::Error::custom)?;
                 Ok(StatsReportType::RemoteOutboundRTP(stats))
             }
-            RTCStatsType::Sender => {
-                todo!()
-            }
             RTCStatsType::Transport => {
                 let stats = serde_json::from_value(value).map_err(serde::de::Error::custom)?;
                 Ok(StatsReportType::Transport(stats))
             }
+            RTCStatsType::CSRC | RTCStatsType::Receiver | RTCStatsType::Sender => Err(
+                serde::de::Error::custom(format!("unsupported stats type {type_field}")),
+            ),
         }
     }
 }
@@ -555,8 +552,9 @@ pub struct InboundRTPStats {
 
     // RTCReceivedRtpStreamStats
     pub packets_received: u64,
-    // TODO: packetsLost
-    // TODO: jitter(maybe, might be uattainable for the same reason as `framesDropped`)
+    pub packets_lost: i64,
+    /// Interarrival jitter in seconds
+    pub jitter: f64,
     // NB: `framesDropped` can't be produced since we aren't decoding, might be worth introducing a
     // way for consumers to control this in the future.
 
@@ -646,7 +644,8 @@ pub struct RemoteInboundRTPStats {
     // RTCReceivedRtpStreamStats
     pub packets_received: u64,
     pub packets_lost: i64,
-    // TODO: jitter(maybe, might be uattainable for the same reason as `framesDropped`)
+    /// Interarrival jitter in seconds, as reported by the remote
+    pub jitter: f64,
     // NB: `framesDropped` can't be produced since we aren't decoding, might be worth introducing a
     // way for consumers to control this in the future.
 
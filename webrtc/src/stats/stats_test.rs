@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn test_stats_report_deserialize_unsupported_type() {
+    let json = r#"{"sender":{"timestamp":1653950726.456,"type":"sender","id":"sender"}}"#;
+    let result = serde_json::from_str::<StatsReport>(json);
+    assert!(
+        result.is_err(),
+        "unsupported stats type must not deserialize"
+    );
+}
+
+#[test]
+fn test_stats_report_deserialize_data_channel() {
+    let json = r#"{"dc":{"timestamp":1653950726.456,"type":"data-channel","id":"dc","bytesReceived":1,"bytesSent":2,"dataChannelIdentifier":3,"label":"chat","messagesReceived":4,"messagesSent":5,"protocol":"","state":"open"}}"#;
+    let report = serde_json::from_str::<StatsReport>(json).expect("valid data channel report");
+    match report.reports.get("dc") {
+        Some(StatsReportType::DataChannel(stats)) => {
+            assert_eq!(stats.label, "chat");
+            assert_eq!(stats.data_channel_identifier, 3);
+            assert_eq!(stats.state, RTCDataChannelState::Open);
+        }
+        other => panic!("unexpected report: {other:?}"),
+    }
+}
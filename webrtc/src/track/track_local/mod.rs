@@ -92,6 +92,12 @@ pub trait TrackLocal {
     /// stream_id is the group this track belongs too. This must be unique
     fn stream_id(&self) -> &str;
 
+    /// rid is the RTP stream identifier of this track when it is sent as one of
+    /// the encodings of a simulcast RTCRtpSender
+    fn rid(&self) -> Option<&str> {
+        None
+    }
+
     /// kind controls if this TrackLocal is audio or video
     fn kind(&self) -> RTPCodecType;
 
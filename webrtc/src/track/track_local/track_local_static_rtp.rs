@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use tokio::sync::Mutex;
 use util::{Marshal, MarshalSize};
 
@@ -14,6 +14,7 @@ pub struct TrackLocalStaticRTP {
     pub(crate) bindings: Mutex<Vec<Arc<TrackBinding>>>,
     codec: RTCRtpCodecCapability,
     id: String,
+    rid: Option<String>,
     stream_id: String,
 }
 
@@ -24,6 +25,24 @@ impl TrackLocalStaticRTP {
             codec,
             bindings: Mutex::new(vec![]),
             id,
+            rid: None,
+            stream_id,
+        }
+    }
+
+    /// returns a TrackLocalStaticRTP with a RID, used as one of the encodings of a
+    /// simulcast RTCRtpSender.
+    pub fn new_with_rid(
+        codec: RTCRtpCodecCapability,
+        id: String,
+        rid: String,
+        stream_id: String,
+    ) -> Self {
+        TrackLocalStaticRTP {
+            codec,
+            bindings: Mutex::new(vec![]),
+            id,
+            rid: Some(rid),
             stream_id,
         }
     }
@@ -99,6 +118,23 @@ impl TrackLocalStaticRTP {
             pkt.header.ssrc = b.ssrc;
             pkt.header.payload_type = b.payload_type;
 
+            if let Some(rid) = &self.rid {
+                if let Some(id) = b
+                    .params
+                    .header_extensions
+                    .iter()
+                    .find(|ext| ext.uri == ::sdp::extmap::SDES_RTP_STREAM_ID_URI)
+                    .map(|ext| ext.id)
+                {
+                    if let Err(err) = pkt
+                        .header
+                        .set_extension(id as u8, Bytes::copy_from_slice(rid.as_bytes()))
+                    {
+                        write_errs.push(Error::Rtp(err));
+                    }
+                }
+            }
+
             for (uri, data) in extension_data.iter() {
                 if let Some(id) = b
                     .params
@@ -195,6 +231,11 @@ impl TrackLocal for TrackLocalStaticRTP {
         self.stream_id.as_str()
     }
 
+    /// rid is the RTP stream identifier of this track, if it is a simulcast encoding
+    fn rid(&self) -> Option<&str> {
+        self.rid.as_deref()
+    }
+
     /// kind controls if this TrackLocal is audio or video
     fn kind(&self) -> RTPCodecType {
         if self.codec.mime_type.starts_with("audio/") {
@@ -230,8 +271,9 @@ impl TrackLocalWriter for TrackLocalStaticRTP {
     /// If one PeerConnection fails the packets will still be sent to
     /// all PeerConnections. The error message will contain the ID of the failed
     /// PeerConnections so you can remove them
-    async fn write(&self, mut b: &[u8]) -> Result<usize> {
-        let pkt = rtp::packet::Packet::unmarshal(&mut b)?;
+    async fn write(&self, b: &[u8]) -> Result<usize> {
+        let mut buf = b;
+        let pkt = rtp::packet::Packet::unmarshal(&mut buf)?;
         self.write_rtp(&pkt).await?;
         Ok(b.len())
     }
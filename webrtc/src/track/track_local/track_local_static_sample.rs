@@ -1,9 +1,12 @@
 use log::warn;
-use media::Sample;
+use media::{FrameTransform, Sample};
 use tokio::sync::Mutex;
 
 use super::track_local_static_rtp::TrackLocalStaticRTP;
 use super::*;
+use crate::api::media_engine::{
+    MIME_TYPE_G722, MIME_TYPE_OPUS, MIME_TYPE_PCMA, MIME_TYPE_PCMU, MIME_TYPE_VP8, MIME_TYPE_VP9,
+};
 use crate::error::flatten_errs;
 use crate::track::RTP_OUTBOUND_MTU;
 
@@ -13,6 +16,7 @@ struct TrackLocalStaticSampleInternal {
     sequencer: Option<Box<dyn rtp::sequence::Sequencer + Send + Sync>>,
     clock_rate: f64,
     did_warn_about_wonky_pause: bool,
+    frame_transform: Option<Arc<dyn FrameTransform>>,
 }
 
 /// TrackLocalStaticSample is a TrackLocal that has a pre-set codec and accepts Samples.
@@ -35,10 +39,62 @@ impl TrackLocalStaticSample {
                 sequencer: None,
                 clock_rate: 0.0f64,
                 did_warn_about_wonky_pause: false,
+                frame_transform: None,
             }),
         }
     }
 
+    /// returns a TrackLocalStaticSample with a RID, used as one of the encodings of a
+    /// simulcast RTCRtpSender.
+    pub fn new_with_rid(
+        codec: RTCRtpCodecCapability,
+        id: String,
+        rid: String,
+        stream_id: String,
+    ) -> Self {
+        let rtp_track = TrackLocalStaticRTP::new_with_rid(codec, id, rid, stream_id);
+
+        TrackLocalStaticSample {
+            rtp_track,
+            internal: Mutex::new(TrackLocalStaticSampleInternal {
+                packetizer: None,
+                sequencer: None,
+                clock_rate: 0.0f64,
+                did_warn_about_wonky_pause: false,
+                frame_transform: None,
+            }),
+        }
+    }
+
+    /// with_frame_transform transforms each sample before it is packetized, e.g. to encrypt
+    /// it end to end. Samples the transform drops are not sent.
+    ///
+    /// Only codecs whose payloader doesn't look into the frames can be transformed: VP8, VP9,
+    /// Opus and G.711/G.722. The H.264 and AV1 payloaders split the frames along their NAL
+    /// units and OBUs, which a transformed frame doesn't have.
+    pub fn with_frame_transform(
+        mut self,
+        frame_transform: Arc<dyn FrameTransform>,
+    ) -> Result<Self> {
+        let mime_type = self.codec().mime_type.to_lowercase();
+        if ![
+            MIME_TYPE_VP8,
+            MIME_TYPE_VP9,
+            MIME_TYPE_OPUS,
+            MIME_TYPE_G722,
+            MIME_TYPE_PCMU,
+            MIME_TYPE_PCMA,
+        ]
+        .iter()
+        .any(|m| m.to_lowercase() == mime_type)
+        {
+            return Err(Error::ErrFrameTransformUnsupportedCodec);
+        }
+
+        self.internal.get_mut().frame_transform = Some(frame_transform);
+        Ok(self)
+    }
+
     /// codec gets the Codec of the track
     pub fn codec(&self) -> RTCRtpCodecCapability {
         self.rtp_track.codec()
@@ -74,7 +130,12 @@ impl TrackLocalStaticSample {
         );
 
         if all_paused {
-            // Abort already here to not increment sequence numbers.
+            // Abort already here to not increment sequence numbers. The RTP timestamp still
+            // follows the media clock, so that it reflects the paused time once resumed.
+            let samples = (sample.duration.as_secs_f64() * internal.clock_rate) as u32;
+            if let Some(packetizer) = &mut internal.packetizer {
+                packetizer.skip_samples(samples);
+            }
             return Ok(());
         }
 
@@ -113,12 +174,25 @@ impl TrackLocalStaticSample {
 
         let clock_rate = internal.clock_rate;
 
+        let data = match &internal.frame_transform {
+            Some(frame_transform) => frame_transform.transform(sample),
+            None => Some(sample.data.clone()),
+        };
+
         let packets = if let Some(packetizer) = &mut internal.packetizer {
-            let samples = (sample.duration.as_secs_f64() * clock_rate) as u32;
             if sample.prev_dropped_packets > 0 {
+                let samples = (sample.duration.as_secs_f64() * clock_rate) as u32;
                 packetizer.skip_samples(samples * sample.prev_dropped_packets as u32);
             }
-            packetizer.packetize(&sample.data, samples)?
+            match data {
+                Some(data) => packetizer.packetize_duration(&data, sample.duration)?,
+                None => {
+                    // The dropped sample still advances the media clock.
+                    let samples = (sample.duration.as_secs_f64() * clock_rate) as u32;
+                    packetizer.skip_samples(samples);
+                    vec![]
+                }
+            }
         } else {
             vec![]
         };
@@ -226,6 +300,11 @@ impl TrackLocal for TrackLocalStaticSample {
         self.rtp_track.stream_id()
     }
 
+    /// rid is the RTP stream identifier of this track, if it is a simulcast encoding
+    fn rid(&self) -> Option<&str> {
+        self.rtp_track.rid()
+    }
+
     /// kind controls if this TrackLocal is audio or video
     fn kind(&self) -> RTPCodecType {
         self.rtp_track.kind()
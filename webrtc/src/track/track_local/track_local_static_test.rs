@@ -6,7 +6,7 @@ use tokio::sync::{mpsc, Mutex};
 use super::track_local_static_rtp::*;
 use super::track_local_static_sample::*;
 use super::*;
-use crate::api::media_engine::{MediaEngine, MIME_TYPE_VP8};
+use crate::api::media_engine::{MediaEngine, MIME_TYPE_H264, MIME_TYPE_VP8};
 use crate::api::APIBuilder;
 use crate::peer_connection::configuration::RTCConfiguration;
 use crate::peer_connection::peer_connection_test::*;
@@ -406,6 +406,174 @@ async fn test_track_local_static_binding_non_blocking() -> Result<()> {
     Ok(())
 }
 
+#[derive(Debug, Default)]
+struct CapturingWriter {
+    packets: Mutex<Vec<rtp::packet::Packet>>,
+}
+
+#[async_trait]
+impl TrackLocalWriter for CapturingWriter {
+    async fn write_rtp(&self, p: &rtp::packet::Packet) -> Result<usize> {
+        self.packets.lock().await.push(p.clone());
+        Ok(p.payload.len())
+    }
+
+    async fn write(&self, b: &[u8]) -> Result<usize> {
+        Ok(b.len())
+    }
+}
+
+#[tokio::test]
+async fn test_track_local_static_sample_pause_advances_timestamp() -> Result<()> {
+    let codec = RTCRtpCodecCapability {
+        mime_type: MIME_TYPE_VP8.to_owned(),
+        clock_rate: 90000,
+        ..Default::default()
+    };
+    let track =
+        TrackLocalStaticSample::new(codec.clone(), "video".to_owned(), "webrtc-rs".to_owned());
+
+    let writer = Arc::new(CapturingWriter::default());
+    let paused = Arc::new(AtomicBool::new(false));
+    let ctx = TrackLocalContext {
+        id: "ctx".to_owned(),
+        params: RTCRtpParameters {
+            codecs: vec![RTCRtpCodecParameters {
+                capability: codec,
+                payload_type: 96,
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        ssrc: 1234,
+        write_stream: Some(Arc::clone(&writer) as Arc<dyn TrackLocalWriter + Send + Sync>),
+        paused: Arc::clone(&paused),
+    };
+    track.bind(&ctx).await?;
+
+    let sample = media::Sample {
+        data: Bytes::from_static(&[0u8; 10]),
+        duration: std::time::Duration::from_millis(100),
+        ..Default::default()
+    };
+
+    track.write_sample(&sample).await?;
+    paused.store(true, Ordering::SeqCst);
+    track.write_sample(&sample).await?;
+    track.write_sample(&sample).await?;
+    paused.store(false, Ordering::SeqCst);
+    track.write_sample(&sample).await?;
+
+    let packets = writer.packets.lock().await;
+    assert_eq!(packets.len(), 2, "no packets should be sent while paused");
+    assert_eq!(
+        packets[1].header.sequence_number,
+        packets[0].header.sequence_number.wrapping_add(1),
+        "sequence numbers should not skip over the paused samples"
+    );
+    assert_eq!(
+        packets[1].header.timestamp,
+        packets[0].header.timestamp.wrapping_add(3 * 9000),
+        "timestamp should account for the paused samples"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_track_local_static_sample_frame_transform_codecs() -> Result<()> {
+    let track = |mime_type: &str| {
+        TrackLocalStaticSample::new(
+            RTCRtpCodecCapability {
+                mime_type: mime_type.to_owned(),
+                clock_rate: 90000,
+                ..Default::default()
+            },
+            "video".to_owned(),
+            "webrtc-rs".to_owned(),
+        )
+    };
+    let sframe = || -> Arc<dyn media::FrameTransform> {
+        Arc::new(media::sframe::SFrameSender::new(
+            media::sframe::CipherSuite::Aes128GcmSha256,
+            1,
+            b"key",
+        ))
+    };
+
+    track(MIME_TYPE_VP8).with_frame_transform(sframe())?;
+
+    // The H.264 payloader would split the encrypted frame along NAL units it doesn't have
+    assert_eq!(
+        track(MIME_TYPE_H264)
+            .with_frame_transform(sframe())
+            .unwrap_err(),
+        Error::ErrFrameTransformUnsupportedCodec
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_track_local_static_rtp_write_rewrites_header() -> Result<()> {
+    let codec = RTCRtpCodecCapability {
+        mime_type: MIME_TYPE_VP8.to_owned(),
+        clock_rate: 90000,
+        ..Default::default()
+    };
+    let track = TrackLocalStaticRTP::new(codec.clone(), "video".to_owned(), "webrtc-rs".to_owned());
+
+    let writer = Arc::new(CapturingWriter::default());
+    let ctx = TrackLocalContext {
+        id: "ctx".to_owned(),
+        params: RTCRtpParameters {
+            codecs: vec![RTCRtpCodecParameters {
+                capability: codec,
+                payload_type: 96,
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        ssrc: 1234,
+        write_stream: Some(Arc::clone(&writer) as Arc<dyn TrackLocalWriter + Send + Sync>),
+        paused: Arc::new(AtomicBool::new(false)),
+    };
+    track.bind(&ctx).await?;
+
+    // A packet as received from another peer connection
+    let pkt = rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            payload_type: 100,
+            sequence_number: 27,
+            timestamp: 3653407706,
+            ssrc: 476325762,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x98, 0x36, 0xbe, 0x88, 0x9e]),
+    };
+    let raw = util::Marshal::marshal(&pkt)?;
+
+    let n = track.write(&raw).await?;
+    assert_eq!(
+        n,
+        raw.len(),
+        "write should report the whole packet as written"
+    );
+
+    let packets = writer.packets.lock().await;
+    assert_eq!(packets.len(), 1);
+    assert_eq!(packets[0].header.ssrc, 1234, "ssrc should be rewritten");
+    assert_eq!(
+        packets[0].header.payload_type, 96,
+        "payload type should be rewritten"
+    );
+    assert_eq!(packets[0].header.sequence_number, 27);
+    assert_eq!(packets[0].payload, pkt.payload);
+
+    Ok(())
+}
+
 /*
 //TODO: func BenchmarkTrackLocalWrite(b *testing.B) {
     offerPC, answerPC, err := newPair()
@@ -432,3 +600,57 @@ async fn test_track_local_static_binding_non_blocking() -> Result<()> {
     }
 }
 */
+
+#[tokio::test]
+async fn test_track_local_static_rtp_write_sets_rid() -> Result<()> {
+    let codec = RTCRtpCodecCapability {
+        mime_type: MIME_TYPE_VP8.to_owned(),
+        clock_rate: 90000,
+        ..Default::default()
+    };
+    let track = TrackLocalStaticRTP::new_with_rid(
+        codec.clone(),
+        "video".to_owned(),
+        "q".to_owned(),
+        "webrtc-rs".to_owned(),
+    );
+    assert_eq!(track.rid(), Some("q"));
+
+    let writer = Arc::new(CapturingWriter::default());
+    let ctx = TrackLocalContext {
+        id: "ctx".to_owned(),
+        params: RTCRtpParameters {
+            header_extensions: vec![RTCRtpHeaderExtensionParameters {
+                uri: ::sdp::extmap::SDES_RTP_STREAM_ID_URI.to_owned(),
+                id: 5,
+            }],
+            codecs: vec![RTCRtpCodecParameters {
+                capability: codec,
+                payload_type: 96,
+                ..Default::default()
+            }],
+        },
+        ssrc: 1234,
+        write_stream: Some(Arc::clone(&writer) as Arc<dyn TrackLocalWriter + Send + Sync>),
+        paused: Arc::new(AtomicBool::new(false)),
+    };
+    track.bind(&ctx).await?;
+
+    let pkt = rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x98, 0x36, 0xbe, 0x88, 0x9e]),
+    };
+    track.write_rtp(&pkt).await?;
+
+    let packets = writer.packets.lock().await;
+    assert_eq!(packets.len(), 1);
+    assert_eq!(
+        packets[0].header.get_extension(5),
+        Some(Bytes::from_static(b"q"))
+    );
+
+    Ok(())
+}
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod track_remote_test;
+
 use std::collections::VecDeque;
 use std::future::Future;
 use std::pin::Pin;
@@ -214,25 +217,39 @@ impl TrackRemote {
     ///
     /// **Cancel Safety:** This method is not cancel safe. Dropping the resulting [`Future`] before
     /// it returns [`std::task::Poll::Ready`] will cause data loss.
+    ///
+    /// Packets with a payload type that wasn't negotiated are dropped.
     pub async fn read(&self, b: &mut [u8]) -> Result<(rtp::packet::Packet, Attributes)> {
-        {
-            // Internal lock scope
-            let mut internal = self.internal.lock().await;
-            if let Some((pkt, attributes)) = internal.peeked.pop_front() {
-                self.check_and_update_track(&pkt).await?;
+        loop {
+            let peeked = {
+                // Internal lock scope
+                let mut internal = self.internal.lock().await;
+                internal.peeked.pop_front()
+            };
+
+            let (pkt, attributes) = if let Some(peeked) = peeked {
+                peeked
+            } else {
+                let receiver = match self.receiver.as_ref().and_then(|r| r.upgrade()) {
+                    Some(r) => r,
+                    None => return Err(Error::ErrRTPReceiverNil),
+                };
 
-                return Ok((pkt, attributes));
+                receiver.read_rtp(b, self.tid).await?
+            };
+
+            match self.check_and_update_track(&pkt).await {
+                Ok(()) => return Ok((pkt, attributes)),
+                Err(Error::ErrCodecNotFound) => {
+                    log::debug!(
+                        "dropping RTP packet with unknown payload type {} on track {}",
+                        pkt.header.payload_type,
+                        self.tid
+                    );
+                }
+                Err(err) => return Err(err),
             }
-        };
-
-        let receiver = match self.receiver.as_ref().and_then(|r| r.upgrade()) {
-            Some(r) => r,
-            None => return Err(Error::ErrRTPReceiverNil),
-        };
-
-        let (pkt, attributes) = receiver.read_rtp(b, self.tid).await?;
-        self.check_and_update_track(&pkt).await?;
-        Ok((pkt, attributes))
+        }
     }
 
     /// check_and_update_track checks payloadType for every incoming packet
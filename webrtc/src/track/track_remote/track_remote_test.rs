@@ -0,0 +1,62 @@
+use interceptor::noop::NoOp;
+
+use super::*;
+use crate::api::media_engine::MIME_TYPE_VP8;
+use crate::rtp_transceiver::rtp_codec::RTCRtpCodecCapability;
+
+fn rtp_packet(payload_type: PayloadType, sequence_number: u16) -> rtp::packet::Packet {
+    rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            payload_type,
+            sequence_number,
+            ssrc: 1234,
+            ..Default::default()
+        },
+        payload: bytes::Bytes::from_static(&[0x00, 0x01]),
+    }
+}
+
+#[tokio::test]
+async fn test_track_remote_read_drops_unknown_payload_type() -> Result<()> {
+    let media_engine = MediaEngine::default();
+    media_engine
+        .negotiated_video_codecs
+        .lock()
+        .push(RTCRtpCodecParameters {
+            capability: RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_VP8.to_owned(),
+                clock_rate: 90000,
+                ..Default::default()
+            },
+            payload_type: 96,
+            ..Default::default()
+        });
+
+    let track = TrackRemote::new(
+        1460,
+        RTPCodecType::Video,
+        1234,
+        SmolStr::default(),
+        Weak::new(),
+        Arc::new(media_engine),
+        Arc::new(NoOp),
+    );
+    track
+        .prepopulate_peeked_data(VecDeque::from(vec![
+            (rtp_packet(111, 1), Attributes::new()),
+            (rtp_packet(96, 2), Attributes::new()),
+        ]))
+        .await;
+
+    let mut b = vec![0u8; 1460];
+    let (pkt, _) = track.read(&mut b).await?;
+    assert_eq!(
+        pkt.header.sequence_number, 2,
+        "unknown payload type not dropped"
+    );
+    assert_eq!(track.payload_type(), 96);
+    assert_eq!(track.codec().capability.mime_type, MIME_TYPE_VP8);
+
+    Ok(())
+}